@@ -16,7 +16,7 @@ import { arbitrumSepolia } from "viem/chains";
 // Export Universal Proof Protocol types
 export { ProofType, PublicStatement, UniversalProof } from "./types";
 
-// Export Universal Proof Descriptor (UPD v2) types
+// Export Universal Proof Descriptor (UPD v3) types
 export {
   CurveId,
   HashFunctionId,
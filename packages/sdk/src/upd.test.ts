@@ -1,5 +1,5 @@
 /**
- * Universal Proof Descriptor (UPD) v2 - Unit Tests
+ * Universal Proof Descriptor (UPD) v3 - Unit Tests
  *
  * Tests for UPD encoding/decoding, validation, and gas estimation.
  * Ensures byte-for-byte compatibility with Rust implementation.
@@ -49,10 +49,10 @@ describe("UniversalProofDescriptor", () => {
     });
 
     const encoded = descriptor.encode();
-    expect(encoded.length).toBe(75);
+    expect(encoded.length).toBe(76);
 
     const decoded = UniversalProofDescriptor.decode(encoded);
-    expect(decoded.updVersion).toBe(2);
+    expect(decoded.updVersion).toBe(3);
     expect(decoded.proofSystemId).toBe(ProofType.PLONK);
     expect(decoded.curveId).toBe(CurveId.BN254);
     expect(decoded.hashFunctionId).toBe(HashFunctionId.Keccak256);
@@ -174,6 +174,22 @@ describe("UniversalProofDescriptor", () => {
     expect(starkGas).toBe(200_000n + 5_000n * 4n + 10n * 50_000n); // 720k
   });
 
+  it("should round-trip the compression flag", () => {
+    const descriptor = UniversalProofDescriptor.groth16Compressed(
+      4,
+      new Uint8Array(32),
+      new Uint8Array(32),
+    );
+    expect(descriptor.proofLength).toBe(128);
+
+    const decoded = UniversalProofDescriptor.decode(descriptor.encode());
+    expect(decoded.isCompressed()).toBe(true);
+    expect(() => decoded.validate()).not.toThrow();
+
+    const unknown = new UniversalProofDescriptor({ ...decoded, flags: 0x80 });
+    expect(() => unknown.validate()).toThrow(DescriptorError.UnknownFlags);
+  });
+
   it("should decode and reject buffer too short", () => {
     const tooShort = new Uint8Array(50);
     expect(() => UniversalProofDescriptor.decode(tooShort)).toThrow(
@@ -182,8 +198,8 @@ describe("UniversalProofDescriptor", () => {
   });
 
   it("should decode and reject unknown curve", () => {
-    const buffer = new Uint8Array(75);
-    buffer[0] = 2; // version
+    const buffer = new Uint8Array(76);
+    buffer[0] = 3; // version
     buffer[1] = 0; // proof system (Groth16)
     buffer[2] = 100; // invalid curve ID
 
@@ -206,21 +222,22 @@ describe("Cross-Compatibility with Rust", () => {
     expect(encoded.length).toBe(UniversalProofDescriptor.ENCODED_SIZE);
 
     // Verify header bytes
-    expect(encoded[0]).toBe(2); // version
+    expect(encoded[0]).toBe(3); // version
     expect(encoded[1]).toBe(0); // Groth16
     expect(encoded[2]).toBe(0); // BN254
     expect(encoded[3]).toBe(0); // Poseidon
     expect(encoded[4]).toBe(0); // recursion depth
+    expect(encoded[5]).toBe(0); // flags
 
     // public_input_count (big-endian u16) = 4
-    expect(encoded[5]).toBe(0);
-    expect(encoded[6]).toBe(4);
+    expect(encoded[6]).toBe(0);
+    expect(encoded[7]).toBe(4);
 
     // proof_length (big-endian u32) = 256
-    expect(encoded[7]).toBe(0);
     expect(encoded[8]).toBe(0);
-    expect(encoded[9]).toBe(1);
-    expect(encoded[10]).toBe(0);
+    expect(encoded[9]).toBe(0);
+    expect(encoded[10]).toBe(1);
+    expect(encoded[11]).toBe(0);
 
     const decoded = UniversalProofDescriptor.decode(encoded);
     expect(decoded.updVersion).toBe(original.updVersion);
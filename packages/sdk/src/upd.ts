@@ -1,10 +1,10 @@
 /**
- * Universal Proof Descriptor (UPD) v2
+ * Universal Proof Descriptor (UPD) v3
  *
- * TypeScript implementation of the self-describing 75-byte proof header.
+ * TypeScript implementation of the self-describing 76-byte proof header.
  * Enables safe dispatch, cost prediction, and future-proof extensibility.
  *
- * Protocol Version: 2
+ * Protocol Version: 3
  * Encoding: Big-endian for network order (matches Rust implementation)
  *
  * @see packages/stylus/src/types.rs for Rust implementation
@@ -66,24 +66,28 @@ export enum DescriptorError {
   ExcessiveRecursionDepth = "ExcessiveRecursionDepth",
   /** Buffer too short to decode */
   BufferTooShort = "BufferTooShort",
+  /** Reserved flag bits are set */
+  UnknownFlags = "UnknownFlags",
 }
 
 /**
- * Universal Proof Descriptor (UPD) v2
+ * Universal Proof Descriptor (UPD) v3
  *
  * Self-describing proof header that enables:
  * - **Safe dispatch** before parsing proof bytes
  * - **Cost prediction** for gas estimation
  * - **Future-proof extensibility** via version field
  * - **Recursion tracking** via depth field
+ * - **Encoding hints** via flags (e.g. compressed curve points)
  *
- * Binary Layout (75 bytes):
+ * Binary Layout (76 bytes):
  * ```
  * [upd_version: 1 byte]
  * [proof_system_id: 1 byte]
  * [curve_id: 1 byte]
  * [hash_function_id: 1 byte]
  * [recursion_depth: 1 byte]
+ * [flags: 1 byte]
  * [public_input_count: 2 bytes (u16 big-endian)]
  * [proof_length: 4 bytes (u32 big-endian)]
  * [vk_commitment: 32 bytes]
@@ -92,10 +96,16 @@ export enum DescriptorError {
  */
 export class UniversalProofDescriptor {
   /** Encoded size in bytes */
-  static readonly ENCODED_SIZE = 75;
+  static readonly ENCODED_SIZE = 76;
 
   /** Current UPD version */
-  static readonly CURRENT_VERSION = 2;
+  static readonly CURRENT_VERSION = 3;
+
+  /** Flag: proof curve points are compressed */
+  static readonly FLAG_COMPRESSED = 0x01;
+
+  /** All defined flag bits */
+  static readonly KNOWN_FLAGS = UniversalProofDescriptor.FLAG_COMPRESSED;
 
   /** Maximum allowed public inputs */
   static readonly MAX_PUBLIC_INPUTS = 1024;
@@ -103,7 +113,7 @@ export class UniversalProofDescriptor {
   /** Maximum recursion depth */
   static readonly MAX_RECURSION_DEPTH = 16;
 
-  /** UPD format version (2 for this format) */
+  /** UPD format version (3 for this format) */
   public readonly updVersion: number;
 
   /** Proof system identifier (matches ProofType enum) */
@@ -118,6 +128,9 @@ export class UniversalProofDescriptor {
   /** Recursion depth (0 = base proof, 1+ = recursive) */
   public readonly recursionDepth: number;
 
+  /** Encoding flags (`FLAG_*`) */
+  public readonly flags: number;
+
   /** Number of public inputs */
   public readonly publicInputCount: number;
 
@@ -136,6 +149,7 @@ export class UniversalProofDescriptor {
     curveId: CurveId;
     hashFunctionId: HashFunctionId;
     recursionDepth: number;
+    flags?: number;
     publicInputCount: number;
     proofLength: number;
     vkCommitment: Uint8Array;
@@ -147,6 +161,7 @@ export class UniversalProofDescriptor {
     this.curveId = params.curveId;
     this.hashFunctionId = params.hashFunctionId;
     this.recursionDepth = params.recursionDepth;
+    this.flags = params.flags ?? 0;
     this.publicInputCount = params.publicInputCount;
     this.proofLength = params.proofLength;
 
@@ -181,6 +196,27 @@ export class UniversalProofDescriptor {
     });
   }
 
+  /**
+   * Convenience constructor for compressed (128-byte) Groth16 proofs
+   */
+  static groth16Compressed(
+    publicInputCount: number,
+    vkCommitment: Uint8Array,
+    circuitId: Uint8Array,
+  ): UniversalProofDescriptor {
+    return new UniversalProofDescriptor({
+      proofSystemId: ProofType.Groth16,
+      curveId: CurveId.BN254,
+      hashFunctionId: HashFunctionId.Poseidon,
+      recursionDepth: 0,
+      flags: UniversalProofDescriptor.FLAG_COMPRESSED,
+      publicInputCount,
+      proofLength: 128, // Compressed Groth16 proof size
+      vkCommitment,
+      circuitId,
+    });
+  }
+
   /**
    * Convenience constructor for PLONK proofs
    */
@@ -245,6 +281,17 @@ export class UniversalProofDescriptor {
     if (this.recursionDepth > UniversalProofDescriptor.MAX_RECURSION_DEPTH) {
       throw new Error(DescriptorError.ExcessiveRecursionDepth);
     }
+
+    if ((this.flags & ~UniversalProofDescriptor.KNOWN_FLAGS) !== 0) {
+      throw new Error(DescriptorError.UnknownFlags);
+    }
+  }
+
+  /**
+   * Whether the proof's curve points are compressed
+   */
+  isCompressed(): boolean {
+    return (this.flags & UniversalProofDescriptor.FLAG_COMPRESSED) !== 0;
   }
 
   /**
@@ -302,6 +349,7 @@ export class UniversalProofDescriptor {
     buffer[offset++] = this.curveId;
     buffer[offset++] = this.hashFunctionId;
     buffer[offset++] = this.recursionDepth;
+    buffer[offset++] = this.flags;
 
     // Multi-byte fields (big-endian for network order)
     // public_input_count (u16)
@@ -338,6 +386,7 @@ export class UniversalProofDescriptor {
     const curveIdValue = bytes[offset++];
     const hashFunctionIdValue = bytes[offset++];
     const recursionDepth = bytes[offset++];
+    const flags = bytes[offset++];
 
     // Validate curve ID
     if (!isValidCurveId(curveIdValue)) {
@@ -373,6 +422,7 @@ export class UniversalProofDescriptor {
       curveId,
       hashFunctionId,
       recursionDepth,
+      flags,
       publicInputCount,
       proofLength,
       vkCommitment,
@@ -53,6 +53,19 @@ interface IUniversalVerifier {
         uint8 proofType
     ) external returns (bool[] memory);
 
+    /// @notice Batch verify PLONK proofs under a key bound to a program and an SRS
+    /// @param programId Program the verification key is registered under
+    /// @param proofs Array of serialized proofs
+    /// @param publicInputs Array of serialized public inputs
+    /// @param vkHash Hash of the verification key
+    /// @return Array of verification results
+    function batch_verify_plonk(
+        uint32 programId,
+        bytes[] calldata proofs,
+        bytes[] calldata publicInputs,
+        bytes32 vkHash
+    ) external returns (bool[] memory);
+
     /// @notice Get total number of successful verifications
     /// @return Total verification count
     function get_verification_count() external view returns (uint256);
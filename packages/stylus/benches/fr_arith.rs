@@ -0,0 +1,76 @@
+//! Fr Arithmetic Benchmark: Montgomery `utils::Fr` vs `U256::mul_mod`
+//!
+//! Run with:
+//! ```text
+//! cargo bench --features std --target x86_64-unknown-linux-gnu --bench fr_arith
+//! ```
+//!
+//! Prints the host time per operation of the `U256::mul_mod` arithmetic the
+//! `fr_*` helpers used before, the helpers themselves (which convert in and out
+//! of Montgomery form on every call) and `Fr` kept in Montgomery form.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use ark_std::{rand::RngCore, test_rng};
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::utils::{fr_inv, fr_mul, Fr, BN254_SCALAR_MODULUS};
+
+const MULS: u32 = 200_000;
+const INVERSES: u32 = 2_000;
+
+/// Nanoseconds per iteration of `f`
+fn time<T>(iterations: u32, mut f: impl FnMut() -> T) -> f64 {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        black_box(f());
+    }
+    start.elapsed().as_nanos() as f64 / iterations as f64
+}
+
+fn main() {
+    let rng = &mut test_rng();
+    let mut random = || {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+        U256::from_be_bytes(bytes) % BN254_SCALAR_MODULUS
+    };
+    let (a, b) = (random(), random());
+    let (ma, mb) = (Fr::from_u256(a).unwrap(), Fr::from_u256(b).unwrap());
+    let r_minus_2 = BN254_SCALAR_MODULUS - U256::from(2);
+
+    // Chained so each product depends on the previous one
+    let mut acc = a;
+    let mul_mod = time(MULS, || {
+        acc = acc.mul_mod(b, BN254_SCALAR_MODULUS);
+        acc
+    });
+    let mut acc = a;
+    let helper = time(MULS, || {
+        acc = fr_mul(acc, b);
+        acc
+    });
+    let mut acc = ma;
+    let montgomery = time(MULS, || {
+        acc = acc.mul(&mb);
+        acc
+    });
+
+    let mul_mod_inv = time(INVERSES, || {
+        let (mut result, mut base, mut exp) = (U256::from(1), black_box(a), r_minus_2);
+        while exp > U256::ZERO {
+            if exp.bit(0) {
+                result = result.mul_mod(base, BN254_SCALAR_MODULUS);
+            }
+            base = base.mul_mod(base, BN254_SCALAR_MODULUS);
+            exp >>= 1;
+        }
+        result
+    });
+    let helper_inv = time(INVERSES, || fr_inv(black_box(a)));
+    let montgomery_inv = time(INVERSES, || black_box(ma).inverse());
+
+    println!("{:>12} {:>14} {:>14} {:>14}", "op", "mul_mod ns", "fr_* ns", "Fr ns");
+    println!("{:>12} {:>14.1} {:>14.1} {:>14.1}", "mul", mul_mod, helper, montgomery);
+    println!("{:>12} {:>14.1} {:>14.1} {:>14.1}", "inverse", mul_mod_inv, helper_inv, montgomery_inv);
+}
@@ -0,0 +1,74 @@
+//! G1 MSM Benchmark: native Pippenger vs the 0x06/0x07 precompile path
+//!
+//! Run with:
+//! ```text
+//! cargo bench --features std --target x86_64-unknown-linux-gnu --bench g1_msm
+//! ```
+//!
+//! For each MSM size this prints the host time of `groth16::msm::msm` (arkworks
+//! for reference) and the estimated on-chain gas of both backends, then the size
+//! from which `MsmBackend::Auto` switches to the native path. Gas for the native
+//! path is modelled as field multiplications × `NATIVE_FQ_MUL_GAS`, so the
+//! crossover moves with that constant.
+
+use std::time::Instant;
+
+use ark_bn254::{Fr, G1Affine, G1Projective};
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_ff::{BigInteger, PrimeField, UniformRand};
+use ark_std::test_rng;
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::groth16::{codec, msm, MsmBackend};
+
+const SIZES: [usize; 10] = [1, 2, 4, 8, 16, 32, 64, 128, 256, 512];
+const ITERATIONS: u32 = 5;
+
+fn main() {
+    let mut rng = test_rng();
+
+    println!(
+        "{:>5} {:>12} {:>12} {:>12} {:>12} {:>10}",
+        "n", "native µs", "arkworks µs", "native gas", "precomp gas", "cheaper"
+    );
+    for n in SIZES {
+        let bases: Vec<G1Affine> = (0..n).map(|_| G1Affine::rand(&mut rng)).collect();
+        let scalars: Vec<Fr> = (0..n).map(|_| Fr::rand(&mut rng)).collect();
+        let encoded: Vec<[u8; 64]> = bases.iter().map(codec::g1_from_ark).collect();
+        let raw: Vec<U256> = scalars
+            .iter()
+            .map(|s| U256::from_be_slice(&s.into_bigint().to_bytes_be()))
+            .collect();
+
+        let start = Instant::now();
+        let mut native = [0u8; 64];
+        for _ in 0..ITERATIONS {
+            native = msm::msm(&encoded, &raw).unwrap();
+        }
+        let native_us = start.elapsed().as_micros() / ITERATIONS as u128;
+
+        let start = Instant::now();
+        let mut reference = G1Projective::default();
+        for _ in 0..ITERATIONS {
+            reference = G1Projective::msm(&bases, &scalars).unwrap();
+        }
+        let ark_us = start.elapsed().as_micros() / ITERATIONS as u128;
+        assert_eq!(native, codec::g1_from_ark(&reference.into_affine()));
+
+        let native_gas = msm::native_msm_gas(n);
+        let precompile_gas = msm::precompile_msm_gas(n);
+        let cheaper = if MsmBackend::Auto.use_native(n) { "native" } else { "precompile" };
+        println!(
+            "{:>5} {:>12} {:>12} {:>12} {:>12} {:>10}",
+            n, native_us, ark_us, native_gas, precompile_gas, cheaper
+        );
+    }
+
+    match (1..=4096).find(|n| MsmBackend::Auto.use_native(*n)) {
+        Some(n) => println!(
+            "\nnative MSM is cheaper from {} terms (NATIVE_FQ_MUL_GAS = {})",
+            n,
+            msm::NATIVE_FQ_MUL_GAS
+        ),
+        None => println!("\nprecompile path is cheaper up to 4096 terms"),
+    }
+}
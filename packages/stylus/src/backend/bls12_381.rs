@@ -0,0 +1,231 @@
+//! BLS12-381 Curve Backend
+//!
+//! Interface and implementations for the BLS12-381 operations the Groth16
+//! verifier needs, following EIP-2537:
+//!
+//! | Operation | Precompile | Checks |
+//! |-----------|------------|--------|
+//! | `g1_add` | 0x0b | on curve |
+//! | `g1_msm` | 0x0c | on curve, in subgroup |
+//! | `pairing_check` | 0x0f | on curve, in subgroup |
+//!
+//! # Encoding
+//! - Fp: 64 bytes, 16 zero bytes then the 48-byte big-endian value (< p)
+//! - G1: 128 bytes, x || y; all zeros is the identity
+//! - G2: 256 bytes, x.c0 || x.c1 || y.c0 || y.c1; all zeros is the identity
+//! - MSM input: k >= 1 pairs of (G1, 32-byte big-endian scalar)
+//! - Pairing input: k >= 1 pairs of (G1, G2); empty input is an error
+
+use super::{BackendError, Fallback, PrecompileBackend, Result};
+use stylus_sdk::{
+    alloy_primitives::{address, Address},
+    call::StaticCallContext,
+};
+
+/// Fp element size in the EIP-2537 encoding
+pub const FP_SIZE: usize = 64;
+
+/// G1 point size
+pub const G1_SIZE: usize = 2 * FP_SIZE;
+
+/// G2 point size
+pub const G2_SIZE: usize = 4 * FP_SIZE;
+
+/// Size of one (G1, scalar) pair in an MSM input
+pub const MSM_PAIR_SIZE: usize = G1_SIZE + 32;
+
+/// Size of one (G1, G2) pair in a pairing input
+pub const PAIR_SIZE: usize = G1_SIZE + G2_SIZE;
+
+/// BLS12-381 operations needed by the verifiers
+pub trait Bls12381Backend {
+    /// p1 + p2
+    fn g1_add(&self, p1: &[u8], p2: &[u8]) -> Result<[u8; G1_SIZE]>;
+
+    /// sum(scalar_i * point_i) over concatenated (G1, scalar) pairs
+    fn g1_msm(&self, pairs: &[u8]) -> Result<[u8; G1_SIZE]>;
+
+    /// Whether the product of pairings over all (G1, G2) pairs is one
+    fn pairing_check(&self, pairs: &[u8]) -> Result<bool>;
+}
+
+impl<B: Bls12381Backend + ?Sized> Bls12381Backend for &B {
+    fn g1_add(&self, p1: &[u8], p2: &[u8]) -> Result<[u8; G1_SIZE]> {
+        (**self).g1_add(p1, p2)
+    }
+
+    fn g1_msm(&self, pairs: &[u8]) -> Result<[u8; G1_SIZE]> {
+        (**self).g1_msm(pairs)
+    }
+
+    fn pairing_check(&self, pairs: &[u8]) -> Result<bool> {
+        (**self).pairing_check(pairs)
+    }
+}
+
+impl<P: Bls12381Backend, F: Bls12381Backend> Bls12381Backend for Fallback<P, F> {
+    fn g1_add(&self, p1: &[u8], p2: &[u8]) -> Result<[u8; G1_SIZE]> {
+        self.primary.g1_add(p1, p2).or_else(|_| self.fallback.g1_add(p1, p2))
+    }
+
+    fn g1_msm(&self, pairs: &[u8]) -> Result<[u8; G1_SIZE]> {
+        self.primary.g1_msm(pairs).or_else(|_| self.fallback.g1_msm(pairs))
+    }
+
+    fn pairing_check(&self, pairs: &[u8]) -> Result<bool> {
+        self.primary.pairing_check(pairs).or_else(|_| self.fallback.pairing_check(pairs))
+    }
+}
+
+// =========================================================================
+// PRECOMPILES (EIP-2537)
+// =========================================================================
+
+const BLS12_G1ADD: Address = address!("000000000000000000000000000000000000000b");
+const BLS12_G1MSM: Address = address!("000000000000000000000000000000000000000c");
+const BLS12_PAIRING_CHECK: Address = address!("000000000000000000000000000000000000000f");
+
+impl<S: StaticCallContext + Copy> PrecompileBackend<S> {
+    fn call_bls_g1(&self, precompile: Address, input: &[u8]) -> Result<[u8; G1_SIZE]> {
+        // A chain without the precompile returns empty output, which fails here
+        self.call(precompile, input)?.try_into().map_err(|_| BackendError)
+    }
+}
+
+impl<S: StaticCallContext + Copy> Bls12381Backend for PrecompileBackend<S> {
+    fn g1_add(&self, p1: &[u8], p2: &[u8]) -> Result<[u8; G1_SIZE]> {
+        if p1.len() != G1_SIZE || p2.len() != G1_SIZE {
+            return Err(BackendError);
+        }
+        let mut input = [0u8; 2 * G1_SIZE];
+        input[..G1_SIZE].copy_from_slice(p1);
+        input[G1_SIZE..].copy_from_slice(p2);
+        self.call_bls_g1(BLS12_G1ADD, &input)
+    }
+
+    fn g1_msm(&self, pairs: &[u8]) -> Result<[u8; G1_SIZE]> {
+        if pairs.is_empty() || pairs.len() % MSM_PAIR_SIZE != 0 {
+            return Err(BackendError);
+        }
+        self.call_bls_g1(BLS12_G1MSM, pairs)
+    }
+
+    fn pairing_check(&self, pairs: &[u8]) -> Result<bool> {
+        if pairs.is_empty() || pairs.len() % PAIR_SIZE != 0 {
+            return Err(BackendError);
+        }
+        let output = self.call(BLS12_PAIRING_CHECK, pairs)?;
+        if output.len() != 32 {
+            return Err(BackendError);
+        }
+        Ok(output[31] == 1 && output[..31].iter().all(|&b| b == 0))
+    }
+}
+
+// =========================================================================
+// PURE RUST (arkworks)
+// =========================================================================
+
+#[cfg(feature = "ark-backend")]
+mod ark {
+    use super::*;
+    use crate::backend::ArkBackend;
+    use alloc::vec::Vec;
+    use ark_bls12_381::{Bls12_381, Fq, Fq2, Fr, G1Affine, G1Projective, G2Affine};
+    use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+    use ark_ff::{BigInt, One, PrimeField, Zero};
+
+    impl Bls12381Backend for ArkBackend {
+        fn g1_add(&self, p1: &[u8], p2: &[u8]) -> Result<[u8; G1_SIZE]> {
+            let sum = G1Projective::from(read_g1(p1, false)?) + read_g1(p2, false)?;
+            Ok(write_g1(&sum.into_affine()))
+        }
+
+        fn g1_msm(&self, pairs: &[u8]) -> Result<[u8; G1_SIZE]> {
+            if pairs.is_empty() || pairs.len() % MSM_PAIR_SIZE != 0 {
+                return Err(BackendError);
+            }
+            let mut bases = Vec::with_capacity(pairs.len() / MSM_PAIR_SIZE);
+            let mut scalars = Vec::with_capacity(pairs.len() / MSM_PAIR_SIZE);
+            for pair in pairs.chunks_exact(MSM_PAIR_SIZE) {
+                bases.push(read_g1(&pair[..G1_SIZE], true)?);
+                // Bases are in the order-r subgroup, so reducing mod r is exact
+                scalars.push(Fr::from_be_bytes_mod_order(&pair[G1_SIZE..]));
+            }
+            let sum = G1Projective::msm(&bases, &scalars).map_err(|_| BackendError)?;
+            Ok(write_g1(&sum.into_affine()))
+        }
+
+        fn pairing_check(&self, pairs: &[u8]) -> Result<bool> {
+            if pairs.is_empty() || pairs.len() % PAIR_SIZE != 0 {
+                return Err(BackendError);
+            }
+            let mut g1 = Vec::with_capacity(pairs.len() / PAIR_SIZE);
+            let mut g2 = Vec::with_capacity(pairs.len() / PAIR_SIZE);
+            for pair in pairs.chunks_exact(PAIR_SIZE) {
+                g1.push(read_g1(&pair[..G1_SIZE], true)?);
+                g2.push(read_g2(&pair[G1_SIZE..])?);
+            }
+            Ok(Bls12_381::multi_pairing(g1, g2).0.is_one())
+        }
+    }
+
+    fn read_fp(bytes: &[u8]) -> Result<Fq> {
+        if bytes[..16].iter().any(|&b| b != 0) {
+            return Err(BackendError);
+        }
+        let mut limbs = [0u64; 6];
+        for (limb, chunk) in limbs.iter_mut().zip(bytes[16..].rchunks_exact(8)) {
+            *limb = u64::from_be_bytes(chunk.try_into().unwrap());
+        }
+        Fq::from_bigint(BigInt(limbs)).ok_or(BackendError)
+    }
+
+    fn write_fp(value: &Fq, out: &mut [u8]) {
+        let limbs = value.into_bigint().0;
+        for (limb, chunk) in limbs.iter().zip(out[16..].rchunks_exact_mut(8)) {
+            chunk.copy_from_slice(&limb.to_be_bytes());
+        }
+    }
+
+    fn read_g1(bytes: &[u8], subgroup: bool) -> Result<G1Affine> {
+        if bytes.len() != G1_SIZE {
+            return Err(BackendError);
+        }
+        let x = read_fp(&bytes[..FP_SIZE])?;
+        let y = read_fp(&bytes[FP_SIZE..])?;
+        if x.is_zero() && y.is_zero() {
+            return Ok(G1Affine::zero());
+        }
+        let point = G1Affine::new_unchecked(x, y);
+        if point.is_on_curve() && (!subgroup || point.is_in_correct_subgroup_assuming_on_curve()) {
+            Ok(point)
+        } else {
+            Err(BackendError)
+        }
+    }
+
+    fn write_g1(point: &G1Affine) -> [u8; G1_SIZE] {
+        let mut out = [0u8; G1_SIZE];
+        if let Some((x, y)) = point.xy() {
+            write_fp(x, &mut out[..FP_SIZE]);
+            write_fp(y, &mut out[FP_SIZE..]);
+        }
+        out
+    }
+
+    fn read_g2(bytes: &[u8]) -> Result<G2Affine> {
+        let fp = |i: usize| read_fp(&bytes[i * FP_SIZE..(i + 1) * FP_SIZE]);
+        let x = Fq2::new(fp(0)?, fp(1)?);
+        let y = Fq2::new(fp(2)?, fp(3)?);
+        if x.is_zero() && y.is_zero() {
+            return Ok(G2Affine::zero());
+        }
+        let point = G2Affine::new_unchecked(x, y);
+        if point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve() {
+            Ok(point)
+        } else {
+            Err(BackendError)
+        }
+    }
+}
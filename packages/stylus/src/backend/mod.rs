@@ -0,0 +1,256 @@
+//! Curve Backends
+//!
+//! The on-chain verifiers only need three curve operations: G1 addition, G1
+//! scalar multiplication and a multi-pairing check. `Bn254Backend` abstracts
+//! them so the same verifier code (`groth16::generic`, `plonk`) runs against:
+//!
+//! - `PrecompileBackend`: EIP-196/197 precompiles 0x06, 0x07, 0x08 through a
+//!   Stylus `StaticCallContext` (the deployed contract)
+//! - `ArkBackend`: pure Rust on arkworks `ark-bn254`, no_std (feature
+//!   `ark-backend`, implied by `std`), for cargo tests, host binaries and chains
+//!   without the precompiles
+//!
+//! # Encoding
+//! Both backends use the precompile byte layout and semantics:
+//! - G1: 64 bytes, x || y big-endian; (0, 0) is the identity
+//! - G2: 128 bytes, x.c1 || x.c0 || y.c1 || y.c0; all zeros is the identity
+//! - Pairing input: concatenated 192-byte (G1, G2) pairs; empty input is true
+//!
+//! Non-canonical coordinates, points off the curve and G2 points outside the
+//! subgroup are rejected with `BackendError`, exactly where the precompiles fail.
+//!
+//! `bls12_381::Bls12381Backend` is the same interface for BLS12-381 (EIP-2537
+//! precompiles); `PrecompileBackend` and `ArkBackend` implement both. `Fallback`
+//! chains two backends: where the primary fails (for instance because the
+//! precompile is not deployed), the secondary gives the answer.
+
+pub mod bls12_381;
+
+use stylus_sdk::{
+    alloy_primitives::{address, Address, U256},
+    call::{static_call, StaticCallContext},
+};
+
+/// G1 point size in the precompile encoding
+pub const G1_SIZE: usize = 64;
+
+/// G2 point size in the precompile encoding
+pub const G2_SIZE: usize = 128;
+
+/// Size of one (G1, G2) pair in a pairing input
+pub const PAIR_SIZE: usize = G1_SIZE + G2_SIZE;
+
+/// A curve operation failed: malformed input or a failed precompile call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackendError;
+
+pub type Result<T> = core::result::Result<T, BackendError>;
+
+/// BN254 operations needed by the verifiers
+pub trait Bn254Backend {
+    /// p1 + p2
+    fn g1_add(&self, p1: &[u8], p2: &[u8]) -> Result<[u8; G1_SIZE]>;
+
+    /// scalar * point (the scalar is taken as a full 256-bit integer)
+    fn g1_mul(&self, point: &[u8], scalar: U256) -> Result<[u8; G1_SIZE]>;
+
+    /// Whether the product of pairings over all (G1, G2) pairs is one
+    fn pairing_check(&self, pairs: &[u8]) -> Result<bool>;
+}
+
+impl<B: Bn254Backend + ?Sized> Bn254Backend for &B {
+    fn g1_add(&self, p1: &[u8], p2: &[u8]) -> Result<[u8; G1_SIZE]> {
+        (**self).g1_add(p1, p2)
+    }
+
+    fn g1_mul(&self, point: &[u8], scalar: U256) -> Result<[u8; G1_SIZE]> {
+        (**self).g1_mul(point, scalar)
+    }
+
+    fn pairing_check(&self, pairs: &[u8]) -> Result<bool> {
+        (**self).pairing_check(pairs)
+    }
+}
+
+/// Use `primary`, and `fallback` for any operation the primary rejects
+///
+/// Sound as long as both implement the same semantics: inputs the primary
+/// rejects as malformed are rejected again by the fallback.
+#[derive(Debug, Clone, Copy)]
+pub struct Fallback<P, F> {
+    pub primary: P,
+    pub fallback: F,
+}
+
+impl<P, F> Fallback<P, F> {
+    pub fn new(primary: P, fallback: F) -> Self {
+        Self { primary, fallback }
+    }
+}
+
+impl<P: Bn254Backend, F: Bn254Backend> Bn254Backend for Fallback<P, F> {
+    fn g1_add(&self, p1: &[u8], p2: &[u8]) -> Result<[u8; G1_SIZE]> {
+        self.primary.g1_add(p1, p2).or_else(|_| self.fallback.g1_add(p1, p2))
+    }
+
+    fn g1_mul(&self, point: &[u8], scalar: U256) -> Result<[u8; G1_SIZE]> {
+        self.primary.g1_mul(point, scalar).or_else(|_| self.fallback.g1_mul(point, scalar))
+    }
+
+    fn pairing_check(&self, pairs: &[u8]) -> Result<bool> {
+        self.primary.pairing_check(pairs).or_else(|_| self.fallback.pairing_check(pairs))
+    }
+}
+
+// =========================================================================
+// PRECOMPILES (0x06 - 0x08)
+// =========================================================================
+
+const BN256_ADD: Address = address!("0000000000000000000000000000000000000006");
+const BN256_MUL: Address = address!("0000000000000000000000000000000000000007");
+const BN256_PAIRING: Address = address!("0000000000000000000000000000000000000008");
+
+/// Backend calling the EVM precompiles through a Stylus context
+#[derive(Debug, Clone, Copy)]
+pub struct PrecompileBackend<S: StaticCallContext + Copy> {
+    context: S,
+}
+
+impl<S: StaticCallContext + Copy> PrecompileBackend<S> {
+    pub fn new(context: S) -> Self {
+        Self { context }
+    }
+
+    fn call(&self, precompile: Address, input: &[u8]) -> Result<alloc::vec::Vec<u8>> {
+        static_call(self.context, precompile, input).map_err(|_| BackendError)
+    }
+
+    fn call_g1(&self, precompile: Address, input: &[u8]) -> Result<[u8; G1_SIZE]> {
+        self.call(precompile, input)?.try_into().map_err(|_| BackendError)
+    }
+}
+
+impl<S: StaticCallContext + Copy> Bn254Backend for PrecompileBackend<S> {
+    fn g1_add(&self, p1: &[u8], p2: &[u8]) -> Result<[u8; G1_SIZE]> {
+        if p1.len() != G1_SIZE || p2.len() != G1_SIZE {
+            return Err(BackendError);
+        }
+        let mut input = [0u8; 2 * G1_SIZE];
+        input[..G1_SIZE].copy_from_slice(p1);
+        input[G1_SIZE..].copy_from_slice(p2);
+        self.call_g1(BN256_ADD, &input)
+    }
+
+    fn g1_mul(&self, point: &[u8], scalar: U256) -> Result<[u8; G1_SIZE]> {
+        if point.len() != G1_SIZE {
+            return Err(BackendError);
+        }
+        let mut input = [0u8; G1_SIZE + 32];
+        input[..G1_SIZE].copy_from_slice(point);
+        input[G1_SIZE..].copy_from_slice(&scalar.to_be_bytes::<32>());
+        self.call_g1(BN256_MUL, &input)
+    }
+
+    fn pairing_check(&self, pairs: &[u8]) -> Result<bool> {
+        if pairs.len() % PAIR_SIZE != 0 {
+            return Err(BackendError);
+        }
+        let output = self.call(BN256_PAIRING, pairs)?;
+        if output.len() != 32 {
+            return Err(BackendError);
+        }
+        Ok(output[31] == 1 && output[..31].iter().all(|&b| b == 0))
+    }
+}
+
+// =========================================================================
+// PURE RUST (arkworks)
+// =========================================================================
+
+#[cfg(feature = "ark-backend")]
+pub use ark::ArkBackend;
+
+#[cfg(feature = "ark-backend")]
+mod ark {
+    use super::*;
+    use alloc::vec::Vec;
+    use ark_bn254::{Bn254, Fq, Fq2, G1Affine, G1Projective, G2Affine};
+    use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+    use ark_ff::{BigInt, One, PrimeField, Zero};
+
+    /// Backend evaluating everything in Rust with `ark-bn254`
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct ArkBackend;
+
+    impl Bn254Backend for ArkBackend {
+        fn g1_add(&self, p1: &[u8], p2: &[u8]) -> Result<[u8; G1_SIZE]> {
+            let sum = G1Projective::from(read_g1(p1)?) + read_g1(p2)?;
+            Ok(write_g1(&sum.into_affine()))
+        }
+
+        fn g1_mul(&self, point: &[u8], scalar: U256) -> Result<[u8; G1_SIZE]> {
+            let product = read_g1(point)?.mul_bigint(scalar.as_limbs());
+            Ok(write_g1(&product.into_affine()))
+        }
+
+        fn pairing_check(&self, pairs: &[u8]) -> Result<bool> {
+            if pairs.len() % PAIR_SIZE != 0 {
+                return Err(BackendError);
+            }
+            let mut g1 = Vec::with_capacity(pairs.len() / PAIR_SIZE);
+            let mut g2 = Vec::with_capacity(pairs.len() / PAIR_SIZE);
+            for pair in pairs.chunks_exact(PAIR_SIZE) {
+                g1.push(read_g1(&pair[..G1_SIZE])?);
+                g2.push(read_g2(&pair[G1_SIZE..])?);
+            }
+            Ok(Bn254::multi_pairing(g1, g2).0.is_one())
+        }
+    }
+
+    fn read_fq(bytes: &[u8]) -> Result<Fq> {
+        let value = U256::from_be_slice(bytes);
+        Fq::from_bigint(BigInt(*value.as_limbs())).ok_or(BackendError)
+    }
+
+    fn write_fq(value: &Fq, out: &mut [u8]) {
+        let limbs = value.into_bigint().0;
+        out.copy_from_slice(&U256::from_limbs(limbs).to_be_bytes::<32>());
+    }
+
+    fn read_g1(bytes: &[u8]) -> Result<G1Affine> {
+        if bytes.len() != G1_SIZE {
+            return Err(BackendError);
+        }
+        let x = read_fq(&bytes[0..32])?;
+        let y = read_fq(&bytes[32..64])?;
+        if x.is_zero() && y.is_zero() {
+            return Ok(G1Affine::zero());
+        }
+        // G1 has cofactor 1: on the curve means in the subgroup
+        let point = G1Affine::new_unchecked(x, y);
+        point.is_on_curve().then_some(point).ok_or(BackendError)
+    }
+
+    fn write_g1(point: &G1Affine) -> [u8; G1_SIZE] {
+        let mut out = [0u8; G1_SIZE];
+        if let Some((x, y)) = point.xy() {
+            write_fq(x, &mut out[0..32]);
+            write_fq(y, &mut out[32..64]);
+        }
+        out
+    }
+
+    fn read_g2(bytes: &[u8]) -> Result<G2Affine> {
+        let x = Fq2::new(read_fq(&bytes[32..64])?, read_fq(&bytes[0..32])?);
+        let y = Fq2::new(read_fq(&bytes[96..128])?, read_fq(&bytes[64..96])?);
+        if x.is_zero() && y.is_zero() {
+            return Ok(G2Affine::zero());
+        }
+        let point = G2Affine::new_unchecked(x, y);
+        if point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve() {
+            Ok(point)
+        } else {
+            Err(BackendError)
+        }
+    }
+}
@@ -4,19 +4,30 @@
 //! or any other environment to verify Groth16, PLONK, and STARK proofs.
 //!
 //! Usage:
-//!   uzkv-cli --proof-type <groth16|plonk|stark> \
+//!   uzkv-cli --proof-type <groth16|gnark|plonk|snarkjs-plonk|fflonk|halo2|ultra-plonk|stark> \
 //!            --proof <path> \
 //!            --public-inputs <path> \
-//!            --vk <path>
+//!            --vk <path> \
+//!            [--hash <keccak256|sha256|blake3|poseidon|blake2b>] \
+//!            [--curve <bn254|bls12-381>]
 //!
 //! For STARK proofs, the --vk parameter is optional as they use transparent setup.
+//! --hash selects the PLONK / halo2 transcript or STARK Merkle hash (default keccak256).
+//! --curve selects the pairing curve (default bn254); Groth16 also verifies over bls12-381.
+//! Groth16 files use the canonical encoding documented in `groth16::codec`.
+//! gnark files are gnark's own binary output; a gnark public witness is accepted as-is.
+//! snarkjs PLONK and fflonk files use the byte layout produced by `formats::snarkjs`.
+//! halo2 files are the raw transcript and the compact VK of `halo2::vk`.
+//! UltraPLONK files use the layout of `plonk::ultra`, the VK with `[τ]₂` appended.
 
 use std::fs;
 use std::path::PathBuf;
 use std::process;
 
 // Import UZKV library functions
-use uzkv_stylus::uzkv::{ProofSystem, verify_offchain};
+use uzkv_stylus::groth16::gnark;
+use uzkv_stylus::types::{CurveId, HashFunctionId};
+use uzkv_stylus::uzkv::{ProofSystem, verify_offchain_with_hash};
 
 fn main() {
     let args: Vec<String> = std::env::args().collect();
@@ -31,6 +42,8 @@ fn main() {
     let mut proof_path: Option<PathBuf> = None;
     let mut public_inputs_path: Option<PathBuf> = None;
     let mut vk_path: Option<PathBuf> = None;
+    let mut hash_name = String::from("keccak256");
+    let mut curve_name = String::from("bn254");
     
     let mut i = 1;
     while i < args.len() {
@@ -71,6 +84,24 @@ fn main() {
                     process::exit(1);
                 }
             }
+            "--hash" => {
+                if i + 1 < args.len() {
+                    hash_name = args[i + 1].to_lowercase();
+                    i += 2;
+                } else {
+                    eprintln!("Error: --hash requires a value");
+                    process::exit(1);
+                }
+            }
+            "--curve" => {
+                if i + 1 < args.len() {
+                    curve_name = args[i + 1].to_lowercase();
+                    i += 2;
+                } else {
+                    eprintln!("Error: --curve requires a value");
+                    process::exit(1);
+                }
+            }
             "--help" | "-h" => {
                 print_usage();
                 process::exit(0);
@@ -102,10 +133,38 @@ fn main() {
     // Map proof type string to ProofSystem enum
     let proof_system = match proof_type.as_str() {
         "groth16" => ProofSystem::Groth16,
+        "gnark" => ProofSystem::Groth16Gnark,
         "plonk" => ProofSystem::Plonk,
+        "snarkjs-plonk" => ProofSystem::PlonkSnarkjs,
+        "fflonk" => ProofSystem::Fflonk,
+        "halo2" => ProofSystem::Halo2,
+        "ultra-plonk" => ProofSystem::UltraPlonk,
         "stark" => ProofSystem::Stark,
         _ => {
-            eprintln!("Error: Invalid proof type '{}'. Must be 'groth16', 'plonk', or 'stark'", proof_type);
+            eprintln!("Error: Invalid proof type '{}'. Must be 'groth16', 'gnark', 'plonk', 'snarkjs-plonk', 'fflonk', 'halo2', 'ultra-plonk', or 'stark'", proof_type);
+            process::exit(1);
+        }
+    };
+    
+    // Map hash name to the descriptor's HashFunctionId
+    let hash = match hash_name.as_str() {
+        "keccak256" => HashFunctionId::Keccak256,
+        "sha256" => HashFunctionId::SHA256,
+        "blake3" => HashFunctionId::Blake3,
+        "poseidon" => HashFunctionId::Poseidon,
+        "blake2b" => HashFunctionId::Blake2b,
+        _ => {
+            eprintln!("Error: Invalid hash '{}'. Must be 'keccak256', 'sha256', 'blake3', 'poseidon', or 'blake2b'", hash_name);
+            process::exit(1);
+        }
+    };
+    
+    // Map curve name to the descriptor's CurveId
+    let curve = match curve_name.as_str() {
+        "bn254" => CurveId::BN254,
+        "bls12-381" => CurveId::BLS12_381,
+        _ => {
+            eprintln!("Error: Invalid curve '{}'. Must be 'bn254' or 'bls12-381'", curve_name);
             process::exit(1);
         }
     };
@@ -146,11 +205,26 @@ fn main() {
         }
     };
     
+    // gnark public witnesses carry a 12-byte header before the scalars
+    let public_inputs_bytes = if proof_system == ProofSystem::Groth16Gnark
+        && public_inputs_bytes.len() % 32 == gnark::WITNESS_HEADER_SIZE
+    {
+        match gnark::inputs_from_witness(&public_inputs_bytes) {
+            Ok(inputs) => inputs.to_vec(),
+            Err(e) => {
+                eprintln!("Error reading gnark public witness: {:?}", e);
+                process::exit(1);
+            }
+        }
+    } else {
+        public_inputs_bytes
+    };
+    
     // Convert ProofSystem enum to u8
     let proof_system_u8 = proof_system as u8;
     
     // Verify proof
-    match verify_offchain(proof_system_u8, &proof_bytes, &public_inputs_bytes, &vk_bytes) {
+    match verify_offchain_with_hash(proof_system_u8, curve, hash, &proof_bytes, &public_inputs_bytes, &vk_bytes) {
         Ok(is_valid) => {
             // Output result as JSON for easy parsing by Node.js
             let result = serde_json::json!({
@@ -183,19 +257,42 @@ USAGE:
     uzkv-cli [OPTIONS]
 
 OPTIONS:
-    -t, --proof-type <TYPE>        Proof system type: groth16, plonk, or stark
+    -t, --proof-type <TYPE>        Proof system type: groth16, gnark, plonk, snarkjs-plonk, fflonk, halo2, ultra-plonk, or stark
     -p, --proof <PATH>             Path to proof file
     -i, --public-inputs <PATH>     Path to public inputs file
     -v, --vk <PATH>                Path to verification key file (not required for STARK)
+        --hash <HASH>              Transcript hash: keccak256 (default), sha256, blake3, poseidon, or blake2b
+        --curve <CURVE>            Pairing curve: bn254 (default) or bls12-381 (Groth16 only)
     -h, --help                     Print this help message
 
 EXAMPLES:
     # Verify Groth16 proof
     uzkv-cli -t groth16 -p proof.bin -i inputs.bin -v vk.bin
 
+    # Verify Groth16 proof over BLS12-381
+    uzkv-cli -t groth16 -p proof.bin -i inputs.bin -v vk.bin --curve bls12-381
+
+    # Verify gnark Groth16 proof (gnark binary encoding)
+    uzkv-cli -t gnark -p proof.bin -i witness.bin -v vk.bin
+
     # Verify PLONK proof
     uzkv-cli -t plonk -p proof.bin -i inputs.bin -v vk.bin
 
+    # Verify snarkjs PLONK proof (converted with formats::snarkjs)
+    uzkv-cli -t snarkjs-plonk -p proof.bin -i inputs.bin -v vk.bin
+
+    # Verify snarkjs fflonk proof (converted with formats::snarkjs)
+    uzkv-cli -t fflonk -p proof.bin -i inputs.bin -v vk.bin
+
+    # Verify PSE halo2 proof with a Blake2b transcript
+    uzkv-cli -t halo2 -p proof.bin -i instances.bin -v vk.bin --hash blake2b
+
+    # Verify UltraPLONK proof (custom gates and lookups)
+    uzkv-cli -t ultra-plonk -p proof.bin -i inputs.bin -v vk.bin
+
+    # Verify PLONK proof with a Poseidon transcript
+    uzkv-cli -t plonk -p proof.bin -i inputs.bin -v vk.bin --hash poseidon
+
     # Verify STARK proof (no VK needed)
     uzkv-cli -t stark -p proof.bin -i inputs.bin
 
@@ -12,11 +12,15 @@
 //! | System | Base | Per Input | Per Byte | Notes |
 //! |--------|------|-----------|----------|-------|
 //! | Groth16 | 250k | 40k | 0 | Pairing-based, fixed size |
-//! | PLONK | 350k | 10k | 0 | More pairings |
+//! | PLONK | 350k | 10k | 0 | More pairings |
+//! | fflonk | 150k | 500 | 0 | One KZG opening |
+//! | halo2 | 300k | 1.5k | 16 | SHPLONK, one G1 mul per commitment |
+//! | UltraPLONK | 220k | 500 | 12 | Custom gates and plookup, sized by the VK |
 //! | STARK | 200k | 5k | 10 | FRI verification |
 
 extern crate alloc;
 
+use crate::stark::{self, StarkVerificationKey};
 use crate::types::{ProofType, UniversalProofDescriptor};
 use crate::verifier_traits::GasCost;
 
@@ -74,6 +78,32 @@ impl VerificationCost {
         }
     }
 
+    /// Calculate verification cost for a gnark Groth16 proof
+    ///
+    /// Cost model:
+    /// - Base: 320,000 gas (4 pairings + commitment knowledge check)
+    /// - Per input: 6,500 gas (MSM operations)
+    /// - Per byte: 0
+    ///
+    /// Typical total: 320k + 6.5k * inputs ≈ 346k for 4 inputs
+    pub fn for_groth16_gnark(public_inputs: usize) -> Self {
+        let model = GasCost::groth16_gnark();
+        // Ar, Bs, Krs, one commitment and its proof of knowledge (uncompressed)
+        let proof_size = 256 + 4 + 64 + 64;
+
+        let estimated_total = model.estimate(public_inputs, proof_size);
+
+        Self {
+            base_gas: model.base,
+            per_input_gas: model.per_public_input,
+            per_byte_gas: model.per_proof_byte,
+            public_input_count: public_inputs,
+            proof_size,
+            estimated_total,
+            proof_system: ProofType::Groth16Gnark,
+        }
+    }
+
     /// Calculate verification cost for PLONK proof
     ///
     /// Cost model:
@@ -101,6 +131,83 @@ impl VerificationCost {
         }
     }
 
+    /// Calculate verification cost for a snarkjs PLONK proof
+    ///
+    /// Same checks as `for_plonk`; only the transcript differs.
+    pub fn for_plonk_snarkjs(public_inputs: usize, circuit_size: usize) -> Self {
+        Self {
+            proof_system: ProofType::PlonkSnarkjs,
+            ..Self::for_plonk(public_inputs, circuit_size)
+        }
+    }
+
+    /// Calculate verification cost for a snarkjs fflonk proof
+    ///
+    /// Cost model:
+    /// - Base: 150,000 gas (2 pairings, 5 G1 multiplications)
+    /// - Per input: 500 gas (Lagrange basis, no curve ops)
+    /// - Per byte: 0 (fixed 768-byte proof)
+    pub fn for_fflonk(public_inputs: usize) -> Self {
+        let model = GasCost::fflonk();
+        let proof_size = crate::plonk::fflonk::PROOF_SIZE;
+
+        let estimated_total = model.estimate(public_inputs, proof_size);
+
+        Self {
+            base_gas: model.base,
+            per_input_gas: model.per_public_input,
+            per_byte_gas: model.per_proof_byte,
+            public_input_count: public_inputs,
+            proof_size,
+            estimated_total,
+            proof_system: ProofType::Fflonk,
+        }
+    }
+
+    /// Calculate verification cost for a halo2 (KZG/SHPLONK) proof
+    ///
+    /// Cost model:
+    /// - Base: 300,000 gas (2 pairings, SHPLONK combination)
+    /// - Per input: 1,500 gas (Lagrange basis per instance query)
+    /// - Per byte: 16 gas (the proof size tracks the number of commitments)
+    pub fn for_halo2(public_inputs: usize, proof_size: usize) -> Self {
+        let model = GasCost::halo2();
+
+        let estimated_total = model.estimate(public_inputs, proof_size);
+
+        Self {
+            base_gas: model.base,
+            per_input_gas: model.per_public_input,
+            per_byte_gas: model.per_proof_byte,
+            public_input_count: public_inputs,
+            proof_size,
+            estimated_total,
+            proof_system: ProofType::Halo2,
+        }
+    }
+
+    /// Calculate verification cost for an UltraPLONK (custom gates, plookup) proof
+    ///
+    /// Cost model:
+    /// - Base: 220,000 gas (2 pairings, one G1 multiplication per polynomial)
+    /// - Per input: 500 gas (Lagrange basis, no curve ops)
+    /// - Per byte: 12 gas (the proof size tracks the wires, selectors and table)
+    pub fn for_ultra_plonk(public_inputs: usize, proof_size: usize) -> Self {
+        let model = GasCost::ultra_plonk();
+
+        let estimated_total = model.estimate(public_inputs, proof_size);
+
+        Self {
+            base_gas: model.base,
+            per_input_gas: model.per_public_input,
+            per_byte_gas: model.per_proof_byte,
+            public_input_count: public_inputs,
+            proof_size,
+            estimated_total,
+            proof_system: ProofType::UltraPlonk,
+        }
+    }
+
     /// Calculate verification cost for STARK proof
     ///
     /// Cost model:
@@ -131,6 +238,27 @@ impl VerificationCost {
         }
     }
 
+    /// Calculate verification cost for a STARK proof under its parsed VK
+    ///
+    /// The total is `stark::estimate_gas_cost` for the key's FRI parameters at
+    /// the verifier's security level: it follows the query count and the
+    /// number of FRI layers rather than an assumed trace length.
+    pub fn for_stark_vk(vk: &StarkVerificationKey, proof_size: usize) -> Self {
+        let model = GasCost::stark();
+
+        let estimated_total = stark::estimate_gas_cost(stark::SECURITY_LEVEL, &vk.fri).total as u64;
+
+        Self {
+            base_gas: model.base,
+            per_input_gas: model.per_public_input,
+            per_byte_gas: model.per_proof_byte,
+            public_input_count: vk.boundaries.len(),
+            proof_size,
+            estimated_total,
+            proof_system: ProofType::STARK,
+        }
+    }
+
     /// Create cost from UniversalProofDescriptor
     ///
     /// Uses the descriptor's metadata to compute accurate gas estimate.
@@ -139,6 +267,11 @@ impl VerificationCost {
             0 => GasCost::groth16(),
             1 => GasCost::plonk(),
             2 => GasCost::stark(),
+            3 => GasCost::groth16_gnark(),
+            4 => GasCost::plonk(),
+            5 => GasCost::fflonk(),
+            6 => GasCost::halo2(),
+            7 => GasCost::ultra_plonk(),
             _ => GasCost::groth16(), // Fallback
         };
 
@@ -146,6 +279,11 @@ impl VerificationCost {
             0 => ProofType::Groth16,
             1 => ProofType::PLONK,
             2 => ProofType::STARK,
+            3 => ProofType::Groth16Gnark,
+            4 => ProofType::PlonkSnarkjs,
+            5 => ProofType::Fflonk,
+            6 => ProofType::Halo2,
+            7 => ProofType::UltraPlonk,
             _ => ProofType::Groth16,
         };
 
@@ -282,6 +420,11 @@ impl GasLimitRecommendation {
             ProofType::Groth16 => VerificationCost::for_groth16(public_inputs),
             ProofType::PLONK => VerificationCost::for_plonk(public_inputs, 65536),
             ProofType::STARK => VerificationCost::for_stark(1024, 128),
+            ProofType::Groth16Gnark => VerificationCost::for_groth16_gnark(public_inputs),
+            ProofType::PlonkSnarkjs => VerificationCost::for_plonk_snarkjs(public_inputs, 65536),
+            ProofType::Fflonk => VerificationCost::for_fflonk(public_inputs),
+            ProofType::Halo2 => VerificationCost::for_halo2(public_inputs, 4_096),
+            ProofType::UltraPlonk => VerificationCost::for_ultra_plonk(public_inputs, 2_048),
         };
 
         Self {
@@ -306,6 +449,15 @@ mod tests {
         assert!(cost.estimated_total < 300_000);
     }
 
+    #[test]
+    fn test_groth16_gnark_cost() {
+        let cost = VerificationCost::for_groth16_gnark(4);
+
+        assert_eq!(cost.proof_system, ProofType::Groth16Gnark);
+        // The commitment knowledge check costs an extra pairing over plain Groth16
+        assert!(cost.estimated_total > VerificationCost::for_groth16(4).estimated_total);
+    }
+
     #[test]
     fn test_plonk_cost() {
         let cost = VerificationCost::for_plonk(4, 65536);
@@ -314,6 +466,41 @@ mod tests {
         assert!(cost.estimated_total > 150_000);
     }
 
+    #[test]
+    fn test_plonk_snarkjs_cost() {
+        let cost = VerificationCost::for_plonk_snarkjs(4, 65536);
+
+        assert_eq!(cost.proof_system, ProofType::PlonkSnarkjs);
+        assert_eq!(cost.estimated_total, VerificationCost::for_plonk(4, 65536).estimated_total);
+    }
+
+    #[test]
+    fn test_fflonk_cost() {
+        let cost = VerificationCost::for_fflonk(4);
+
+        assert_eq!(cost.proof_system, ProofType::Fflonk);
+        assert_eq!(cost.proof_size, 768);
+        assert!(cost.estimated_total < VerificationCost::for_plonk(4, 65536).estimated_total);
+    }
+
+    #[test]
+    fn test_halo2_cost() {
+        let cost = VerificationCost::for_halo2(4, 4_096);
+
+        assert_eq!(cost.proof_system, ProofType::Halo2);
+        assert_eq!(cost.estimated_total, 300_000 + 4 * 1_500 + 4_096 * 16);
+        // Proofs grow with the circuit
+        assert!(VerificationCost::for_halo2(4, 8_192).estimated_total > cost.estimated_total);
+    }
+
+    #[test]
+    fn test_ultra_plonk_cost() {
+        let cost = VerificationCost::for_ultra_plonk(4, 2_048);
+
+        assert_eq!(cost.proof_system, ProofType::UltraPlonk);
+        assert_eq!(cost.estimated_total, 220_000 + 4 * 500 + 2_048 * 12);
+    }
+
     #[test]
     fn test_stark_cost() {
         let cost = VerificationCost::for_stark(1024, 128);
@@ -323,6 +510,32 @@ mod tests {
         assert!(cost.estimated_total > 400_000);
     }
 
+    #[test]
+    fn test_stark_vk_cost() {
+        let vk = |num_layers: u8, grinding_bits: u8| StarkVerificationKey {
+            trace_width: 1,
+            constraints: vec![],
+            boundaries: vec![],
+            log_trace_length: 10,
+            fri: stark::FriParams {
+                log_blowup: 2,
+                log_folding: 1,
+                num_layers,
+                remainder_degree: (1 << (10 - num_layers)) - 1,
+                grinding_bits,
+            },
+            digest: [0; 32],
+        };
+        let cost = VerificationCost::for_stark_vk(&vk(7, 0), 50_000);
+
+        assert_eq!(cost.proof_system, ProofType::STARK);
+        assert_eq!(cost.proof_size, 50_000);
+        assert_eq!(cost.estimated_total, stark::estimate_gas_cost(stark::SECURITY_LEVEL, &vk(7, 0).fri).total as u64);
+        // More layers and fewer queries move the estimate
+        assert!(VerificationCost::for_stark_vk(&vk(8, 0), 50_000).estimated_total > cost.estimated_total);
+        assert!(VerificationCost::for_stark_vk(&vk(7, 20), 50_000).estimated_total < cost.estimated_total);
+    }
+
     #[test]
     fn test_cost_comparison() {
         let groth16 = VerificationCost::for_groth16(4);
@@ -0,0 +1,6 @@
+//! Importers for third-party proof artifacts
+//!
+//! Host-only (`std`): these parse JSON files produced by external tooling into the
+//! byte layouts consumed by the on-chain verifiers.
+
+pub mod snarkjs;
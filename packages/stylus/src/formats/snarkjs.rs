@@ -0,0 +1,366 @@
+//! snarkjs JSON Import
+//!
+//! Parses `verification_key.json`, `proof.json` and `public.json` as written by
+//! snarkjs (`groth16`, `plonk` and `fflonk` protocols, `bn128` curve).
+//!
+//! # Output Layouts
+//! - Groth16 VK / proof / inputs: the canonical encoding from `groth16::codec`
+//! - PLONK VK: the `plonk::deserialize_plonk_vk` layout with the SRS point `X_2`
+//!   appended (as `plonk::verify` expects)
+//! - PLONK proof: snarkjs order, 768 bytes (the `plonk::deserialize_plonk_proof`
+//!   layout)
+//!   ```text
+//!   [A, B, C, Z, T1, T2, T3, Wxi, Wxiw: 9 × 64 bytes]
+//!   [eval_a, eval_b, eval_c, eval_s1, eval_s2, eval_zw: 6 × 32 bytes]
+//!   ```
+//! - fflonk VK / proof: the `plonk::fflonk` layouts (VK with `X_2` appended);
+//!   the proof's `polynomials` and `evaluations` objects are flattened in
+//!   Solidity calldata order, 768 bytes
+//! - Public signals: 32-byte big-endian scalars
+//!
+//! PLONK output verifies with `plonk::snarkjs::verify` (`ProofType::PlonkSnarkjs`),
+//! which replays the snarkjs transcript; fflonk output with `plonk::fflonk::verify`
+//! (`ProofType::Fflonk`).
+//!
+//! snarkjs field elements are decimal strings, points are projective
+//! (`[x, y, "1"]`) and G2 limbs are listed `[c0, c1]`; the conversions live in
+//! `groth16::codec`.
+
+use std::fmt;
+
+use serde_json::Value;
+use sha3::{Digest, Keccak256};
+
+use crate::groth16::codec;
+use crate::types::{ProofType, UniversalProof};
+
+/// PLONK proof size in snarkjs order
+pub const PLONK_PROOF_SIZE: usize = 9 * 64 + 6 * 32;
+
+/// PLONK commitment fields in snarkjs order
+const PLONK_PROOF_POINTS: [&str; 9] = ["A", "B", "C", "Z", "T1", "T2", "T3", "Wxi", "Wxiw"];
+
+/// PLONK evaluation fields in snarkjs order
+const PLONK_PROOF_EVALS: [&str; 6] = ["eval_a", "eval_b", "eval_c", "eval_s1", "eval_s2", "eval_zw"];
+
+/// fflonk proof size (4 commitments, 16 evaluations)
+pub const FFLONK_PROOF_SIZE: usize = crate::plonk::fflonk::PROOF_SIZE;
+
+/// fflonk commitments, in `polynomials`
+const FFLONK_PROOF_POINTS: [&str; 4] = ["C1", "C2", "W1", "W2"];
+
+/// fflonk evaluations, in `evaluations`
+const FFLONK_PROOF_EVALS: [&str; 16] = [
+    "ql", "qr", "qm", "qo", "qc", "s1", "s2", "s3", "a", "b", "c", "z", "zw", "t1w", "t2w", "inv",
+];
+
+/// Largest supported PLONK domain (2^power)
+const MAX_PLONK_POWER: u64 = 28;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// Input is not valid JSON
+    InvalidJson,
+    /// `protocol` is missing or not the expected one
+    UnsupportedProtocol(String),
+    /// `curve` is not bn128 / bn254
+    UnsupportedCurve(String),
+    /// A required field is absent
+    MissingField(&'static str),
+    /// A field is present but malformed or out of range
+    InvalidField(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidJson => write!(f, "invalid JSON"),
+            Error::UnsupportedProtocol(p) => write!(f, "unsupported protocol: {}", p),
+            Error::UnsupportedCurve(c) => write!(f, "unsupported curve: {}", c),
+            Error::MissingField(name) => write!(f, "missing field: {}", name),
+            Error::InvalidField(name) => write!(f, "invalid field: {}", name),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// snarkjs proving system
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Groth16,
+    Plonk,
+    Fflonk,
+}
+
+impl Protocol {
+    fn from_json(json: &Value) -> Result<Self> {
+        match json.get("protocol").and_then(Value::as_str) {
+            Some("groth16") => Ok(Protocol::Groth16),
+            Some("plonk") => Ok(Protocol::Plonk),
+            Some("fflonk") => Ok(Protocol::Fflonk),
+            Some(other) => Err(Error::UnsupportedProtocol(other.to_string())),
+            None => Err(Error::MissingField("protocol")),
+        }
+    }
+
+    /// Matching `ProofType`
+    pub fn proof_type(self) -> ProofType {
+        match self {
+            Protocol::Groth16 => ProofType::Groth16,
+            Protocol::Plonk => ProofType::PlonkSnarkjs,
+            Protocol::Fflonk => ProofType::Fflonk,
+        }
+    }
+}
+
+// =========================================================================
+// GROTH16
+// =========================================================================
+
+/// Parse a Groth16 `verification_key.json` into the canonical VK encoding
+pub fn parse_groth16_vk(json: &str) -> Result<Vec<u8>> {
+    let json = parse_json(json)?;
+    expect_protocol(&json, Protocol::Groth16)?;
+    check_curve(&json)?;
+
+    let ic = g1_list(&json, "IC")?;
+    if let Some(n_public) = json.get("nPublic") {
+        if n_public.as_u64() != Some(ic.len() as u64 - 1) {
+            return Err(Error::InvalidField("nPublic"));
+        }
+    }
+
+    let mut vk = Vec::with_capacity(codec::G1_SIZE + 3 * codec::G2_SIZE + ic.len() * codec::G1_SIZE);
+    vk.extend_from_slice(&g1(&json, "vk_alpha_1")?);
+    vk.extend_from_slice(&g2(&json, "vk_beta_2")?);
+    vk.extend_from_slice(&g2(&json, "vk_gamma_2")?);
+    vk.extend_from_slice(&g2(&json, "vk_delta_2")?);
+    for point in ic {
+        vk.extend_from_slice(&point);
+    }
+    Ok(vk)
+}
+
+/// Parse a Groth16 `proof.json` into the canonical proof encoding
+pub fn parse_groth16_proof(json: &str) -> Result<Vec<u8>> {
+    let json = parse_json(json)?;
+    expect_protocol(&json, Protocol::Groth16)?;
+    check_curve(&json)?;
+
+    let mut proof = Vec::with_capacity(crate::groth16::PROOF_SIZE);
+    proof.extend_from_slice(&g1(&json, "pi_a")?);
+    proof.extend_from_slice(&g2(&json, "pi_b")?);
+    proof.extend_from_slice(&g1(&json, "pi_c")?);
+    Ok(proof)
+}
+
+// =========================================================================
+// PLONK
+// =========================================================================
+
+/// Parse a PLONK `verification_key.json` into the `deserialize_plonk_vk` layout
+/// followed by the 128-byte `X_2` SRS point
+pub fn parse_plonk_vk(json: &str) -> Result<Vec<u8>> {
+    let json = parse_json(json)?;
+    expect_protocol(&json, Protocol::Plonk)?;
+    check_curve(&json)?;
+
+    let (power, n_public) = domain(&json)?;
+
+    let mut vk = Vec::with_capacity(16 + 8 * codec::G1_SIZE + 3 * 32 + codec::G2_SIZE);
+    vk.extend_from_slice(&(1u64 << power).to_be_bytes());
+    vk.extend_from_slice(&n_public.to_be_bytes());
+    for name in ["Ql", "Qr", "Qo", "Qm", "Qc", "S1", "S2", "S3"] {
+        vk.extend_from_slice(&g1(&json, name)?);
+    }
+    for name in ["w", "k1", "k2"] {
+        vk.extend_from_slice(&scalar(&json, name)?);
+    }
+    vk.extend_from_slice(&g2(&json, "X_2")?);
+    Ok(vk)
+}
+
+/// Parse a PLONK `proof.json` into the 768-byte snarkjs-order layout
+pub fn parse_plonk_proof(json: &str) -> Result<Vec<u8>> {
+    let json = parse_json(json)?;
+    expect_protocol(&json, Protocol::Plonk)?;
+    check_curve(&json)?;
+
+    let mut proof = Vec::with_capacity(PLONK_PROOF_SIZE);
+    for name in PLONK_PROOF_POINTS {
+        proof.extend_from_slice(&g1(&json, name)?);
+    }
+    for name in PLONK_PROOF_EVALS {
+        proof.extend_from_slice(&scalar(&json, name)?);
+    }
+    Ok(proof)
+}
+
+// =========================================================================
+// FFLONK
+// =========================================================================
+
+/// Parse an fflonk `verification_key.json` into the `deserialize_fflonk_vk`
+/// layout followed by the 128-byte `X_2` SRS point
+pub fn parse_fflonk_vk(json: &str) -> Result<Vec<u8>> {
+    let json = parse_json(json)?;
+    expect_protocol(&json, Protocol::Fflonk)?;
+    check_curve(&json)?;
+
+    let (power, n_public) = domain(&json)?;
+
+    let mut vk = Vec::with_capacity(crate::plonk::fflonk::VK_SIZE + codec::G2_SIZE);
+    vk.extend_from_slice(&(1u64 << power).to_be_bytes());
+    vk.extend_from_slice(&n_public.to_be_bytes());
+    vk.extend_from_slice(&g1(&json, "C0")?);
+    for name in ["k1", "k2", "w", "w3", "w4", "w8", "wr"] {
+        vk.extend_from_slice(&scalar(&json, name)?);
+    }
+    vk.extend_from_slice(&g2(&json, "X_2")?);
+    Ok(vk)
+}
+
+/// Parse an fflonk `proof.json` into the 768-byte calldata-order layout
+pub fn parse_fflonk_proof(json: &str) -> Result<Vec<u8>> {
+    let json = parse_json(json)?;
+    expect_protocol(&json, Protocol::Fflonk)?;
+    check_curve(&json)?;
+
+    let polynomials = field(&json, "polynomials")?;
+    let evaluations = field(&json, "evaluations")?;
+
+    let mut proof = Vec::with_capacity(FFLONK_PROOF_SIZE);
+    for name in FFLONK_PROOF_POINTS {
+        proof.extend_from_slice(&g1(polynomials, name)?);
+    }
+    for name in FFLONK_PROOF_EVALS {
+        proof.extend_from_slice(&scalar(evaluations, name)?);
+    }
+    Ok(proof)
+}
+
+// =========================================================================
+// SHARED
+// =========================================================================
+
+/// Parse `public.json` (array of decimal strings) into 32-byte scalars
+pub fn parse_public_signals(json: &str) -> Result<Vec<u8>> {
+    let json = parse_json(json)?;
+    let signals = string_list(&json, "public signals")?;
+    codec::inputs_from_snarkjs(&signals).map_err(|_| Error::InvalidField("public signals"))
+}
+
+/// Detect the protocol of a snarkjs VK or proof
+pub fn detect_protocol(json: &str) -> Result<Protocol> {
+    Protocol::from_json(&parse_json(json)?)
+}
+
+/// Parse a snarkjs VK, proof and public signals into the VK bytes and a
+/// `UniversalProof` bound to them
+///
+/// The protocol is taken from the VK and must match the proof. `vk_hash` is the
+/// keccak256 of the VK bytes, matching `UZKVContract::register_vk_universal`.
+pub fn parse_universal_proof(
+    vk_json: &str,
+    proof_json: &str,
+    public_json: &str,
+    program_id: u32,
+) -> Result<(Vec<u8>, UniversalProof)> {
+    let protocol = detect_protocol(vk_json)?;
+    let proof_protocol = detect_protocol(proof_json)?;
+    if proof_protocol != protocol {
+        return Err(Error::UnsupportedProtocol(format!("{:?} proof for {:?} key", proof_protocol, protocol)));
+    }
+
+    let (vk, proof) = match protocol {
+        Protocol::Groth16 => (parse_groth16_vk(vk_json)?, parse_groth16_proof(proof_json)?),
+        Protocol::Plonk => (parse_plonk_vk(vk_json)?, parse_plonk_proof(proof_json)?),
+        Protocol::Fflonk => (parse_fflonk_vk(vk_json)?, parse_fflonk_proof(proof_json)?),
+    };
+    let public_inputs = parse_public_signals(public_json)?;
+
+    let vk_hash: [u8; 32] = Keccak256::digest(&vk).into();
+    let universal = UniversalProof::new(protocol.proof_type(), program_id, vk_hash, proof, public_inputs);
+    Ok((vk, universal))
+}
+
+fn parse_json(json: &str) -> Result<Value> {
+    serde_json::from_str(json).map_err(|_| Error::InvalidJson)
+}
+
+fn expect_protocol(json: &Value, expected: Protocol) -> Result<()> {
+    let protocol = Protocol::from_json(json)?;
+    if protocol != expected {
+        return Err(Error::UnsupportedProtocol(format!("{:?}", protocol).to_lowercase()));
+    }
+    Ok(())
+}
+
+/// Domain size exponent and public input count of a PLONK or fflonk VK
+fn domain(json: &Value) -> Result<(u64, u64)> {
+    let power = field(json, "power")?
+        .as_u64()
+        .filter(|p| *p <= MAX_PLONK_POWER)
+        .ok_or(Error::InvalidField("power"))?;
+    let n_public = field(json, "nPublic")?.as_u64().ok_or(Error::InvalidField("nPublic"))?;
+    Ok((power, n_public))
+}
+
+/// Proofs written by older snarkjs versions omit `curve`; accept that
+fn check_curve(json: &Value) -> Result<()> {
+    match json.get("curve").map(|c| c.as_str()) {
+        None | Some(Some("bn128")) | Some(Some("bn254")) => Ok(()),
+        Some(Some(other)) => Err(Error::UnsupportedCurve(other.to_string())),
+        Some(None) => Err(Error::InvalidField("curve")),
+    }
+}
+
+fn field<'a>(json: &'a Value, name: &'static str) -> Result<&'a Value> {
+    json.get(name).ok_or(Error::MissingField(name))
+}
+
+fn string_list<'a>(value: &'a Value, name: &'static str) -> Result<Vec<&'a str>> {
+    value
+        .as_array()
+        .ok_or(Error::InvalidField(name))?
+        .iter()
+        .map(|v| v.as_str().ok_or(Error::InvalidField(name)))
+        .collect()
+}
+
+fn g1(json: &Value, name: &'static str) -> Result<[u8; codec::G1_SIZE]> {
+    let coords = string_list(field(json, name)?, name)?;
+    codec::g1_from_snarkjs(&coords).map_err(|_| Error::InvalidField(name))
+}
+
+fn g2(json: &Value, name: &'static str) -> Result<[u8; codec::G2_SIZE]> {
+    let rows = field(json, name)?
+        .as_array()
+        .ok_or(Error::InvalidField(name))?
+        .iter()
+        .map(|row| string_list(row, name))
+        .collect::<Result<Vec<_>>>()?;
+    codec::g2_from_snarkjs(&rows).map_err(|_| Error::InvalidField(name))
+}
+
+fn g1_list(json: &Value, name: &'static str) -> Result<Vec<[u8; codec::G1_SIZE]>> {
+    let points = field(json, name)?.as_array().ok_or(Error::InvalidField(name))?;
+    if points.is_empty() {
+        return Err(Error::InvalidField(name));
+    }
+    points
+        .iter()
+        .map(|point| {
+            let coords = string_list(point, name)?;
+            codec::g1_from_snarkjs(&coords).map_err(|_| Error::InvalidField(name))
+        })
+        .collect()
+}
+
+fn scalar(json: &Value, name: &'static str) -> Result<[u8; 32]> {
+    let value = field(json, name)?.as_str().ok_or(Error::InvalidField(name))?;
+    codec::scalar_from_snarkjs(value).map_err(|_| Error::InvalidField(name))
+}
@@ -0,0 +1,382 @@
+//! Groth16 over BLS12-381
+//!
+//! The same verification equation as the BN254 verifier, for circuits produced
+//! by Zcash- and Filecoin-style tooling (bellman, bellperson, arkworks over
+//! `Bls12_381`). Points use the EIP-2537 encoding (see
+//! `crate::backend::bls12_381`):
+//!
+//! ```text
+//! proof:  A (128) || B (256) || C (128)                              = 512 bytes
+//! vk:     alpha (128) || beta (256) || gamma (256) || delta (256)    = 896 bytes
+//!         || IC_0 .. IC_n (128 each)
+//! inputs: n 32-byte big-endian scalars < r
+//! ```
+//!
+//! `generic` runs against any `Bls12381Backend`. On chain that is the EIP-2537
+//! precompiles, with `ArkBackend` as fallback when built with `ark-backend`;
+//! `host_impl` is the ark-groth16 reference.
+//!
+//! Layout, canonical coordinates, scalar range and identity VK points are
+//! checked here. Curve and subgroup membership are left to the backend: the
+//! MSM (0x0c) and pairing (0x0f) reject points that fail either.
+
+use alloc::vec::Vec;
+use stylus_sdk::alloy_primitives::U256;
+use super::{Error, Result};
+use crate::backend::bls12_381::{Bls12381Backend, FP_SIZE, G1_SIZE, G2_SIZE, MSM_PAIR_SIZE, PAIR_SIZE};
+
+/// Proof size: A (128) + B (256) + C (128)
+pub const PROOF_SIZE: usize = 2 * G1_SIZE + G2_SIZE;
+
+/// VK header size: alpha (128) + beta (256) + gamma (256) + delta (256)
+pub const VK_HEADER_SIZE: usize = G1_SIZE + 3 * G2_SIZE;
+
+/// BLS12-381 scalar field modulus (r)
+/// r = 52435875175126190479447740508185965837690552500527637822603658699938581184513
+pub const BLS12_381_SCALAR_MODULUS: U256 = U256::from_be_bytes([
+    0x73, 0xed, 0xa7, 0x53, 0x29, 0x9d, 0x7d, 0x48,
+    0x33, 0x39, 0xd8, 0x08, 0x09, 0xa1, 0xd8, 0x05,
+    0x53, 0xbd, 0xa4, 0x02, 0xff, 0xfe, 0x5b, 0xfe,
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01,
+]);
+
+/// BLS12-381 base field modulus (p), 48 bytes big-endian
+const BASE_MODULUS: [u8; 48] = [
+    0x1a, 0x01, 0x11, 0xea, 0x39, 0x7f, 0xe6, 0x9a,
+    0x4b, 0x1b, 0xa7, 0xb6, 0x43, 0x4b, 0xac, 0xd7,
+    0x64, 0x77, 0x4b, 0x84, 0xf3, 0x85, 0x12, 0xbf,
+    0x67, 0x30, 0xd2, 0xa0, 0xf6, 0xb0, 0xf6, 0x24,
+    0x1e, 0xab, 0xff, 0xfe, 0xb1, 0x53, 0xff, 0xff,
+    0xb9, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xaa, 0xab,
+];
+
+// =========================================================================
+// VALIDATION
+// =========================================================================
+
+/// Validate a VK: layout, canonical coordinates and no point at infinity
+pub fn validate_vk(vk_bytes: &[u8]) -> Result<()> {
+    if vk_bytes.len() < VK_HEADER_SIZE + G1_SIZE || (vk_bytes.len() - VK_HEADER_SIZE) % G1_SIZE != 0 {
+        return Err(Error::InvalidVerificationKey);
+    }
+    let g1_points = core::iter::once(&vk_bytes[..G1_SIZE]).chain(vk_bytes[VK_HEADER_SIZE..].chunks(G1_SIZE));
+    for point in g1_points {
+        if validate_point(point, Error::G1NotOnCurve)? {
+            return Err(Error::IdentityPoint);
+        }
+    }
+    for point in vk_bytes[G1_SIZE..VK_HEADER_SIZE].chunks(G2_SIZE) {
+        if validate_point(point, Error::G2NotOnCurve)? {
+            return Err(Error::IdentityPoint);
+        }
+    }
+    Ok(())
+}
+
+/// Number of public inputs a (validated) VK expects
+pub fn num_inputs(vk_bytes: &[u8]) -> usize {
+    (vk_bytes.len() - VK_HEADER_SIZE) / G1_SIZE - 1
+}
+
+/// Validate a proof: layout and canonical coordinates
+pub fn validate_proof(proof_bytes: &[u8]) -> Result<()> {
+    if proof_bytes.len() != PROOF_SIZE {
+        return Err(Error::InvalidProof);
+    }
+    validate_point(&proof_bytes[..G1_SIZE], Error::G1NotOnCurve)?;
+    validate_point(&proof_bytes[G1_SIZE..G1_SIZE + G2_SIZE], Error::G2NotOnCurve)?;
+    validate_point(&proof_bytes[G1_SIZE + G2_SIZE..], Error::G1NotOnCurve)?;
+    Ok(())
+}
+
+/// Validate public inputs against the expected count and parse them as scalars < r
+pub fn validate_inputs(public_inputs_bytes: &[u8], expected: usize) -> Result<Vec<U256>> {
+    if public_inputs_bytes.len() % 32 != 0 || public_inputs_bytes.len() / 32 != expected {
+        return Err(Error::InvalidInputs);
+    }
+    public_inputs_bytes
+        .chunks(32)
+        .map(|chunk| {
+            let scalar = U256::from_be_slice(chunk);
+            if scalar >= BLS12_381_SCALAR_MODULUS {
+                return Err(Error::ScalarOutOfRange);
+            }
+            Ok(scalar)
+        })
+        .collect()
+}
+
+/// Check every Fp coordinate of a point is canonical; returns whether it is the identity
+fn validate_point(point: &[u8], invalid: Error) -> Result<bool> {
+    if !point.chunks(FP_SIZE).all(is_canonical_fp) {
+        return Err(invalid);
+    }
+    Ok(point.iter().all(|b| *b == 0))
+}
+
+/// 16 zero bytes of padding, then a 48-byte value < p
+fn is_canonical_fp(bytes: &[u8]) -> bool {
+    bytes[..16].iter().all(|b| *b == 0) && bytes[16..] < BASE_MODULUS[..]
+}
+
+/// Negate a canonical G1 point: (x, y) -> (x, p - y); the identity maps to itself
+fn negate_g1(point: &[u8]) -> [u8; G1_SIZE] {
+    let mut out = [0u8; G1_SIZE];
+    out.copy_from_slice(point);
+    let y = &point[FP_SIZE + 16..];
+    if y.iter().all(|b| *b == 0) {
+        return out;
+    }
+    let mut borrow = 0u16;
+    for i in (0..48).rev() {
+        let diff = BASE_MODULUS[i] as u16 + 0x100 - y[i] as u16 - borrow;
+        out[FP_SIZE + 16 + i] = diff as u8;
+        borrow = u16::from(diff < 0x100);
+    }
+    out
+}
+
+// =========================================================================
+// GENERIC IMPLEMENTATION (any Bls12381Backend)
+// =========================================================================
+
+pub mod generic {
+    use super::*;
+
+    /// Verify a proof against a VK
+    ///
+    /// Checks e(A, B) · e(-α, β) · e(-L, γ) · e(-C, δ) == 1 with
+    /// L = IC_0 + Σ x_i·IC_{i+1} computed in a single MSM.
+    pub fn verify<B: Bls12381Backend>(
+        curve: &B,
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+        vk_bytes: &[u8],
+    ) -> Result<bool> {
+        validate_vk(vk_bytes)?;
+        validate_proof(proof_bytes)?;
+        let inputs = validate_inputs(public_inputs_bytes, num_inputs(vk_bytes))?;
+
+        // L = IC_0 + sum(input[i] * IC[i+1])
+        let ic = vk_bytes[VK_HEADER_SIZE..].chunks(G1_SIZE);
+        let scalars = core::iter::once(U256::from(1)).chain(inputs);
+        let mut msm_input = Vec::with_capacity(ic.len() * MSM_PAIR_SIZE);
+        for (base, scalar) in ic.zip(scalars) {
+            msm_input.extend_from_slice(base);
+            msm_input.extend_from_slice(&scalar.to_be_bytes::<32>());
+        }
+        let l = curve.g1_msm(&msm_input)?;
+
+        let a = &proof_bytes[..G1_SIZE];
+        let b = &proof_bytes[G1_SIZE..G1_SIZE + G2_SIZE];
+        let c = &proof_bytes[G1_SIZE + G2_SIZE..];
+        let alpha = &vk_bytes[..G1_SIZE];
+        let g2 = |i: usize| &vk_bytes[G1_SIZE + i * G2_SIZE..G1_SIZE + (i + 1) * G2_SIZE];
+
+        let mut pairing_input = Vec::with_capacity(4 * PAIR_SIZE);
+        for (g1_point, g2_point) in [
+            (a.to_vec(), b),
+            (negate_g1(alpha).to_vec(), g2(0)),
+            (negate_g1(&l).to_vec(), g2(1)),
+            (negate_g1(c).to_vec(), g2(2)),
+        ] {
+            pairing_input.extend_from_slice(&g1_point);
+            pairing_input.extend_from_slice(g2_point);
+        }
+
+        Ok(curve.pairing_check(&pairing_input)?)
+    }
+}
+
+// =========================================================================
+// STYLUS IMPLEMENTATION (WASM / EIP-2537 Precompiles)
+// =========================================================================
+
+#[cfg(not(feature = "std"))]
+pub mod stylus_impl {
+    use super::*;
+    use crate::backend::PrecompileBackend;
+    use stylus_sdk::call::StaticCallContext;
+
+    /// Verify on the EIP-2537 precompiles, falling back to pure Rust if they
+    /// are unavailable and `ark-backend` is compiled in
+    pub fn verify<S: StaticCallContext + Copy>(
+        context: S,
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+        vk_bytes: &[u8],
+    ) -> Result<bool> {
+        let precompiles = PrecompileBackend::new(context);
+        #[cfg(feature = "ark-backend")]
+        let curve = crate::backend::Fallback::new(precompiles, crate::backend::ArkBackend);
+        #[cfg(not(feature = "ark-backend"))]
+        let curve = precompiles;
+        generic::verify(&curve, proof_bytes, public_inputs_bytes, vk_bytes)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+pub use stylus_impl::verify;
+
+// =========================================================================
+// HOST IMPLEMENTATION (CLI / Tests using arkworks)
+// =========================================================================
+
+#[cfg(feature = "std")]
+pub mod host_impl {
+    use super::*;
+    use ark_bls12_381::{Bls12_381, Fq, Fq2, Fr, G1Affine, G2Affine};
+    use ark_ec::AffineRepr;
+    use ark_ff::{BigInt, PrimeField, Zero};
+    use ark_groth16::{Groth16, Proof, VerifyingKey};
+    use ark_snark::SNARK;
+
+    /// Verify a proof in the EIP-2537 encoding using arkworks
+    pub fn verify_host(
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+        vk_bytes: &[u8],
+    ) -> Result<bool> {
+        // Same structural checks, and errors, as the generic path
+        validate_vk(vk_bytes)?;
+        validate_proof(proof_bytes)?;
+        validate_inputs(public_inputs_bytes, num_inputs(vk_bytes))?;
+
+        let proof = proof_to_ark(proof_bytes)?;
+        let vk = vk_to_ark(vk_bytes)?;
+        let inputs = inputs_to_ark(public_inputs_bytes);
+
+        Groth16::<Bls12_381>::verify(&vk, &inputs, &proof).map_err(|_| Error::VerificationFailed)
+    }
+
+    /// Parse a G1 point (on curve and in the prime-order subgroup)
+    pub fn g1_to_ark(bytes: &[u8]) -> Option<G1Affine> {
+        if bytes.len() != G1_SIZE {
+            return None;
+        }
+        let x = fp_to_ark(&bytes[..FP_SIZE])?;
+        let y = fp_to_ark(&bytes[FP_SIZE..])?;
+        if x.is_zero() && y.is_zero() {
+            return Some(G1Affine::identity());
+        }
+        let point = G1Affine::new_unchecked(x, y);
+        (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve()).then_some(point)
+    }
+
+    /// Parse a G2 point (on curve and in the prime-order subgroup)
+    pub fn g2_to_ark(bytes: &[u8]) -> Option<G2Affine> {
+        if bytes.len() != G2_SIZE {
+            return None;
+        }
+        let fp = |i: usize| fp_to_ark(&bytes[i * FP_SIZE..(i + 1) * FP_SIZE]);
+        let x = Fq2::new(fp(0)?, fp(1)?);
+        let y = Fq2::new(fp(2)?, fp(3)?);
+        if x.is_zero() && y.is_zero() {
+            return Some(G2Affine::identity());
+        }
+        let point = G2Affine::new_unchecked(x, y);
+        (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve()).then_some(point)
+    }
+
+    /// Encode a G1 point in the EIP-2537 layout
+    pub fn g1_from_ark(point: &G1Affine) -> [u8; G1_SIZE] {
+        let mut out = [0u8; G1_SIZE];
+        if let Some((x, y)) = point.xy() {
+            out[..FP_SIZE].copy_from_slice(&fp_from_ark(x));
+            out[FP_SIZE..].copy_from_slice(&fp_from_ark(y));
+        }
+        out
+    }
+
+    /// Encode a G2 point in the EIP-2537 layout
+    pub fn g2_from_ark(point: &G2Affine) -> [u8; G2_SIZE] {
+        let mut out = [0u8; G2_SIZE];
+        if let Some((x, y)) = point.xy() {
+            for (i, coordinate) in [x.c0, x.c1, y.c0, y.c1].iter().enumerate() {
+                out[i * FP_SIZE..(i + 1) * FP_SIZE].copy_from_slice(&fp_from_ark(coordinate));
+            }
+        }
+        out
+    }
+
+    pub fn proof_from_ark(proof: &Proof<Bls12_381>) -> Vec<u8> {
+        let mut out = Vec::with_capacity(PROOF_SIZE);
+        out.extend_from_slice(&g1_from_ark(&proof.a));
+        out.extend_from_slice(&g2_from_ark(&proof.b));
+        out.extend_from_slice(&g1_from_ark(&proof.c));
+        out
+    }
+
+    pub fn proof_to_ark(bytes: &[u8]) -> Result<Proof<Bls12_381>> {
+        if bytes.len() != PROOF_SIZE {
+            return Err(Error::InvalidProof);
+        }
+        Ok(Proof {
+            a: g1_to_ark(&bytes[..G1_SIZE]).ok_or(Error::InvalidProof)?,
+            b: g2_to_ark(&bytes[G1_SIZE..G1_SIZE + G2_SIZE]).ok_or(Error::InvalidProof)?,
+            c: g1_to_ark(&bytes[G1_SIZE + G2_SIZE..]).ok_or(Error::InvalidProof)?,
+        })
+    }
+
+    pub fn vk_from_ark(vk: &VerifyingKey<Bls12_381>) -> Vec<u8> {
+        let mut out = Vec::with_capacity(VK_HEADER_SIZE + vk.gamma_abc_g1.len() * G1_SIZE);
+        out.extend_from_slice(&g1_from_ark(&vk.alpha_g1));
+        out.extend_from_slice(&g2_from_ark(&vk.beta_g2));
+        out.extend_from_slice(&g2_from_ark(&vk.gamma_g2));
+        out.extend_from_slice(&g2_from_ark(&vk.delta_g2));
+        for point in &vk.gamma_abc_g1 {
+            out.extend_from_slice(&g1_from_ark(point));
+        }
+        out
+    }
+
+    pub fn vk_to_ark(bytes: &[u8]) -> Result<VerifyingKey<Bls12_381>> {
+        validate_vk(bytes)?;
+        let invalid = Error::InvalidVerificationKey;
+        let g2 = |i: usize| g2_to_ark(&bytes[G1_SIZE + i * G2_SIZE..G1_SIZE + (i + 1) * G2_SIZE]).ok_or(invalid);
+        Ok(VerifyingKey {
+            alpha_g1: g1_to_ark(&bytes[..G1_SIZE]).ok_or(invalid)?,
+            beta_g2: g2(0)?,
+            gamma_g2: g2(1)?,
+            delta_g2: g2(2)?,
+            gamma_abc_g1: bytes[VK_HEADER_SIZE..]
+                .chunks(G1_SIZE)
+                .map(|chunk| g1_to_ark(chunk).ok_or(invalid))
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    pub fn inputs_from_ark(inputs: &[Fr]) -> Vec<u8> {
+        inputs
+            .iter()
+            .flat_map(|x| U256::from_limbs(x.into_bigint().0).to_be_bytes::<32>())
+            .collect()
+    }
+
+    /// Parse public inputs; they must already be range-checked
+    pub fn inputs_to_ark(bytes: &[u8]) -> Vec<Fr> {
+        bytes.chunks(32).map(Fr::from_be_bytes_mod_order).collect()
+    }
+
+    fn fp_to_ark(bytes: &[u8]) -> Option<Fq> {
+        if !is_canonical_fp(bytes) {
+            return None;
+        }
+        let mut limbs = [0u64; 6];
+        for (limb, chunk) in limbs.iter_mut().zip(bytes[16..].rchunks_exact(8)) {
+            *limb = u64::from_be_bytes(chunk.try_into().ok()?);
+        }
+        Fq::from_bigint(BigInt(limbs))
+    }
+
+    fn fp_from_ark(value: &Fq) -> [u8; FP_SIZE] {
+        let mut out = [0u8; FP_SIZE];
+        for (limb, chunk) in value.into_bigint().0.iter().zip(out[16..].rchunks_exact_mut(8)) {
+            chunk.copy_from_slice(&limb.to_be_bytes());
+        }
+        out
+    }
+}
+
+#[cfg(feature = "std")]
+pub use host_impl::verify_host;
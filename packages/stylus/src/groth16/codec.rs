@@ -0,0 +1,545 @@
+//! Groth16 Encoding Conversions
+//!
+//! # Canonical Wire Format
+//! Both the Stylus verifier and `verify_host` accept exactly one encoding: the
+//! uncompressed EVM layout consumed by the BN254 precompiles.
+//!
+//! ```text
+//! Scalar (Fr):  32 bytes, big-endian, must be < r
+//! G1:           64 bytes, x || y (big-endian Fq), identity = all zeros
+//! G2:          128 bytes, x.c1 || x.c0 || y.c1 || y.c0 (EIP-197), identity = all zeros
+//!
+//! Proof:        A (G1) || B (G2) || C (G1)                           = 256 bytes
+//! VK:           α (G1) || β (G2) || γ (G2) || δ (G2) || IC_0..IC_n   = 448 + 64·(n+1) bytes
+//! Inputs:       x_1 || ... || x_n                                    = 32·n bytes
+//! ```
+//!
+//! Proofs may also be submitted compressed (A 32 || B 64 || C 32 = 128 bytes,
+//! arkworks `serialize_compressed` layout, see `curve`); verifiers decompress
+//! them into the layout above before any other check.
+//!
+//! This module converts that layout to and from:
+//! - snarkjs JSON coordinates (decimal strings, projective `[x, y, "1"]`,
+//!   G2 limbs in `[c0, c1]` order)
+//! - arkworks types and their `serialize_compressed` bytes (host only)
+
+use alloc::{string::String, vec::Vec};
+use stylus_sdk::alloy_primitives::U256;
+use super::{curve, Error, Result, COMPRESSED_PROOF_SIZE, PROOF_SIZE, VK_HEADER_SIZE};
+use crate::utils::{BN254_BASE_MODULUS, BN254_SCALAR_MODULUS};
+
+/// Uncompressed G1 size
+pub const G1_SIZE: usize = 64;
+
+/// Uncompressed G2 size
+pub const G2_SIZE: usize = 128;
+
+/// Scalar size
+pub const SCALAR_SIZE: usize = 32;
+
+// =========================================================================
+// SNARKJS
+// =========================================================================
+
+/// Parse a snarkjs G1 point (`[x, y]` or projective `[x, y, z]`)
+///
+/// snarkjs writes affine points with z = "1" and the identity as `["0", "1", "0"]`.
+pub fn g1_from_snarkjs<S: AsRef<str>>(coords: &[S]) -> Result<[u8; G1_SIZE]> {
+    let mut out = [0u8; G1_SIZE];
+    match coords.len() {
+        2 => {}
+        3 => match coords[2].as_ref() {
+            "1" => {}
+            "0" => return Ok(out),
+            _ => return Err(Error::DeserializationError),
+        },
+        _ => return Err(Error::DeserializationError),
+    }
+    out[0..32].copy_from_slice(&fq_from_decimal(coords[0].as_ref())?);
+    out[32..64].copy_from_slice(&fq_from_decimal(coords[1].as_ref())?);
+    Ok(out)
+}
+
+/// Parse a snarkjs G2 point (`[[x0, x1], [y0, y1]]`, optionally with `["1", "0"]`)
+///
+/// snarkjs lists each Fq2 limb as `[c0, c1]`; the EVM layout wants `c1` first.
+pub fn g2_from_snarkjs<R: AsRef<[S]>, S: AsRef<str>>(coords: &[R]) -> Result<[u8; G2_SIZE]> {
+    let mut out = [0u8; G2_SIZE];
+    match coords.len() {
+        2 => {}
+        3 => {
+            let z = coords[2].as_ref();
+            if z.len() != 2 || z[1].as_ref() != "0" {
+                return Err(Error::DeserializationError);
+            }
+            match z[0].as_ref() {
+                "1" => {}
+                "0" => return Ok(out),
+                _ => return Err(Error::DeserializationError),
+            }
+        }
+        _ => return Err(Error::DeserializationError),
+    }
+
+    for (i, limb) in coords[..2].iter().enumerate() {
+        let limb = limb.as_ref();
+        if limb.len() != 2 {
+            return Err(Error::DeserializationError);
+        }
+        let offset = i * 64;
+        out[offset..offset + 32].copy_from_slice(&fq_from_decimal(limb[1].as_ref())?);
+        out[offset + 32..offset + 64].copy_from_slice(&fq_from_decimal(limb[0].as_ref())?);
+    }
+    Ok(out)
+}
+
+/// Parse a snarkjs public signal (decimal string, must be < r)
+pub fn scalar_from_snarkjs(value: &str) -> Result<[u8; SCALAR_SIZE]> {
+    let value = parse_decimal(value)?;
+    if value >= BN254_SCALAR_MODULUS {
+        return Err(Error::ScalarOutOfRange);
+    }
+    Ok(value.to_be_bytes::<32>())
+}
+
+/// Encode a canonical G1 point as snarkjs projective coordinates
+pub fn g1_to_snarkjs(bytes: &[u8]) -> Result<[String; 3]> {
+    if bytes.len() != G1_SIZE {
+        return Err(Error::DeserializationError);
+    }
+    if is_zero(bytes) {
+        return Ok(["0".into(), "1".into(), "0".into()]);
+    }
+    Ok([fq_to_decimal(&bytes[0..32])?, fq_to_decimal(&bytes[32..64])?, "1".into()])
+}
+
+/// Encode a canonical G2 point as snarkjs projective coordinates
+pub fn g2_to_snarkjs(bytes: &[u8]) -> Result<[[String; 2]; 3]> {
+    if bytes.len() != G2_SIZE {
+        return Err(Error::DeserializationError);
+    }
+    if is_zero(bytes) {
+        return Ok([
+            ["0".into(), "0".into()],
+            ["1".into(), "0".into()],
+            ["0".into(), "0".into()],
+        ]);
+    }
+    Ok([
+        [fq_to_decimal(&bytes[32..64])?, fq_to_decimal(&bytes[0..32])?],
+        [fq_to_decimal(&bytes[96..128])?, fq_to_decimal(&bytes[64..96])?],
+        ["1".into(), "0".into()],
+    ])
+}
+
+/// Encode a canonical scalar as a snarkjs decimal string
+pub fn scalar_to_snarkjs(bytes: &[u8]) -> Result<String> {
+    if bytes.len() != SCALAR_SIZE {
+        return Err(Error::InvalidInputs);
+    }
+    let value = U256::from_be_slice(bytes);
+    if value >= BN254_SCALAR_MODULUS {
+        return Err(Error::ScalarOutOfRange);
+    }
+    Ok(to_decimal(value))
+}
+
+/// Build a canonical proof from snarkjs `pi_a`, `pi_b`, `pi_c`
+pub fn proof_from_snarkjs<S, R>(pi_a: &[S], pi_b: &[R], pi_c: &[S]) -> Result<Vec<u8>>
+where
+    S: AsRef<str>,
+    R: AsRef<[S]>,
+{
+    let mut out = Vec::with_capacity(PROOF_SIZE);
+    out.extend_from_slice(&g1_from_snarkjs(pi_a)?);
+    out.extend_from_slice(&g2_from_snarkjs(pi_b)?);
+    out.extend_from_slice(&g1_from_snarkjs(pi_c)?);
+    Ok(out)
+}
+
+/// Build a canonical VK from snarkjs `vk_alpha_1`, `vk_beta_2`, `vk_gamma_2`,
+/// `vk_delta_2` and `IC`
+pub fn vk_from_snarkjs<S, R>(
+    alpha: &[S],
+    beta: &[R],
+    gamma: &[R],
+    delta: &[R],
+    ic: &[R],
+) -> Result<Vec<u8>>
+where
+    S: AsRef<str>,
+    R: AsRef<[S]>,
+{
+    if ic.is_empty() {
+        return Err(Error::InvalidVerificationKey);
+    }
+    let mut out = Vec::with_capacity(VK_HEADER_SIZE + G1_SIZE * ic.len());
+    out.extend_from_slice(&g1_from_snarkjs(alpha)?);
+    out.extend_from_slice(&g2_from_snarkjs(beta)?);
+    out.extend_from_slice(&g2_from_snarkjs(gamma)?);
+    out.extend_from_slice(&g2_from_snarkjs(delta)?);
+    for point in ic {
+        out.extend_from_slice(&g1_from_snarkjs(point.as_ref())?);
+    }
+    Ok(out)
+}
+
+/// Build canonical public inputs from snarkjs public signals
+pub fn inputs_from_snarkjs<S: AsRef<str>>(values: &[S]) -> Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(SCALAR_SIZE * values.len());
+    for value in values {
+        out.extend_from_slice(&scalar_from_snarkjs(value.as_ref())?);
+    }
+    Ok(out)
+}
+
+// =========================================================================
+// COMPRESSED PROOFS
+// =========================================================================
+
+/// Decompress a 128-byte proof into the canonical 256-byte encoding
+///
+/// A and C are on G1 by construction; B is on the twist but still needs the
+/// subgroup check in `validate_proof`.
+pub fn decompress_proof(bytes: &[u8]) -> Result<[u8; PROOF_SIZE]> {
+    if bytes.len() != COMPRESSED_PROOF_SIZE {
+        return Err(Error::InvalidProof);
+    }
+    let mut out = [0u8; PROOF_SIZE];
+    out[0..64].copy_from_slice(&curve::decompress_g1(&bytes[0..32])?);
+    out[64..192].copy_from_slice(&curve::decompress_g2(&bytes[32..96])?);
+    out[192..256].copy_from_slice(&curve::decompress_g1(&bytes[96..128])?);
+    Ok(out)
+}
+
+/// Compress a canonical 256-byte proof to 128 bytes
+pub fn compress_proof(bytes: &[u8]) -> Result<[u8; COMPRESSED_PROOF_SIZE]> {
+    if bytes.len() != PROOF_SIZE {
+        return Err(Error::InvalidProof);
+    }
+    let mut out = [0u8; COMPRESSED_PROOF_SIZE];
+    out[0..32].copy_from_slice(&curve::compress_g1(&bytes[0..64])?);
+    out[32..96].copy_from_slice(&curve::compress_g2(&bytes[64..192])?);
+    out[96..128].copy_from_slice(&curve::compress_g1(&bytes[192..256])?);
+    Ok(out)
+}
+
+fn parse_decimal(value: &str) -> Result<U256> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(Error::DeserializationError);
+    }
+    U256::from_str_radix(value, 10).map_err(|_| Error::DeserializationError)
+}
+
+fn fq_from_decimal(value: &str) -> Result<[u8; 32]> {
+    let value = parse_decimal(value)?;
+    if value >= BN254_BASE_MODULUS {
+        return Err(Error::DeserializationError);
+    }
+    Ok(value.to_be_bytes::<32>())
+}
+
+fn fq_to_decimal(bytes: &[u8]) -> Result<String> {
+    let value = U256::from_be_slice(bytes);
+    if value >= BN254_BASE_MODULUS {
+        return Err(Error::DeserializationError);
+    }
+    Ok(to_decimal(value))
+}
+
+fn to_decimal(value: U256) -> String {
+    use alloc::string::ToString;
+    value.to_string()
+}
+
+fn is_zero(bytes: &[u8]) -> bool {
+    bytes.iter().all(|b| *b == 0)
+}
+
+// =========================================================================
+// ARKWORKS (Host only)
+// =========================================================================
+
+#[cfg(feature = "std")]
+pub mod host_impl {
+    use super::*;
+    use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine};
+    use ark_ec::AffineRepr;
+    use ark_ff::{BigInteger, PrimeField, Zero};
+    use ark_groth16::{Proof, VerifyingKey};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+    /// Parse a canonical G1 point, applying the same checks as the precompiles
+    pub fn g1_to_ark(bytes: &[u8]) -> Option<G1Affine> {
+        if bytes.len() != G1_SIZE {
+            return None;
+        }
+        let x = fq_to_ark(&bytes[0..32])?;
+        let y = fq_to_ark(&bytes[32..64])?;
+        if x.is_zero() && y.is_zero() {
+            return Some(G1Affine::identity());
+        }
+        let point = G1Affine::new_unchecked(x, y);
+        point.is_on_curve().then_some(point)
+    }
+
+    /// Parse a canonical G2 point (on curve and in the prime-order subgroup)
+    pub fn g2_to_ark(bytes: &[u8]) -> Option<G2Affine> {
+        if bytes.len() != G2_SIZE {
+            return None;
+        }
+        let x = Fq2::new(fq_to_ark(&bytes[32..64])?, fq_to_ark(&bytes[0..32])?);
+        let y = Fq2::new(fq_to_ark(&bytes[96..128])?, fq_to_ark(&bytes[64..96])?);
+        if x.is_zero() && y.is_zero() {
+            return Some(G2Affine::identity());
+        }
+        let point = G2Affine::new_unchecked(x, y);
+        (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve()).then_some(point)
+    }
+
+    /// Encode a G1 point in the canonical layout
+    pub fn g1_from_ark(point: &G1Affine) -> [u8; G1_SIZE] {
+        let mut out = [0u8; G1_SIZE];
+        if let Some((x, y)) = point.xy() {
+            out[0..32].copy_from_slice(&fq_from_ark(x));
+            out[32..64].copy_from_slice(&fq_from_ark(y));
+        }
+        out
+    }
+
+    /// Encode a G2 point in the canonical (EIP-197) layout
+    pub fn g2_from_ark(point: &G2Affine) -> [u8; G2_SIZE] {
+        let mut out = [0u8; G2_SIZE];
+        if let Some((x, y)) = point.xy() {
+            out[0..32].copy_from_slice(&fq_from_ark(&x.c1));
+            out[32..64].copy_from_slice(&fq_from_ark(&x.c0));
+            out[64..96].copy_from_slice(&fq_from_ark(&y.c1));
+            out[96..128].copy_from_slice(&fq_from_ark(&y.c0));
+        }
+        out
+    }
+
+    pub fn proof_from_ark(proof: &Proof<Bn254>) -> Vec<u8> {
+        let mut out = Vec::with_capacity(PROOF_SIZE);
+        out.extend_from_slice(&g1_from_ark(&proof.a));
+        out.extend_from_slice(&g2_from_ark(&proof.b));
+        out.extend_from_slice(&g1_from_ark(&proof.c));
+        out
+    }
+
+    pub fn proof_to_ark(bytes: &[u8]) -> Result<Proof<Bn254>> {
+        if bytes.len() != PROOF_SIZE {
+            return Err(Error::InvalidProof);
+        }
+        Ok(Proof {
+            a: g1_to_ark(&bytes[0..64]).ok_or(Error::InvalidProof)?,
+            b: g2_to_ark(&bytes[64..192]).ok_or(Error::InvalidProof)?,
+            c: g1_to_ark(&bytes[192..256]).ok_or(Error::InvalidProof)?,
+        })
+    }
+
+    pub fn vk_from_ark(vk: &VerifyingKey<Bn254>) -> Vec<u8> {
+        let mut out = Vec::with_capacity(VK_HEADER_SIZE + G1_SIZE * vk.gamma_abc_g1.len());
+        out.extend_from_slice(&g1_from_ark(&vk.alpha_g1));
+        out.extend_from_slice(&g2_from_ark(&vk.beta_g2));
+        out.extend_from_slice(&g2_from_ark(&vk.gamma_g2));
+        out.extend_from_slice(&g2_from_ark(&vk.delta_g2));
+        for point in &vk.gamma_abc_g1 {
+            out.extend_from_slice(&g1_from_ark(point));
+        }
+        out
+    }
+
+    pub fn vk_to_ark(bytes: &[u8]) -> Result<VerifyingKey<Bn254>> {
+        if bytes.len() < VK_HEADER_SIZE + G1_SIZE || (bytes.len() - VK_HEADER_SIZE) % G1_SIZE != 0 {
+            return Err(Error::InvalidVerificationKey);
+        }
+        let invalid_vk = Error::InvalidVerificationKey;
+        Ok(VerifyingKey {
+            alpha_g1: g1_to_ark(&bytes[0..64]).ok_or(invalid_vk)?,
+            beta_g2: g2_to_ark(&bytes[64..192]).ok_or(invalid_vk)?,
+            gamma_g2: g2_to_ark(&bytes[192..320]).ok_or(invalid_vk)?,
+            delta_g2: g2_to_ark(&bytes[320..448]).ok_or(invalid_vk)?,
+            gamma_abc_g1: bytes[VK_HEADER_SIZE..]
+                .chunks(G1_SIZE)
+                .map(|chunk| g1_to_ark(chunk).ok_or(invalid_vk))
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+
+    pub fn inputs_from_ark(inputs: &[Fr]) -> Vec<u8> {
+        inputs.iter().flat_map(|x| x.into_bigint().to_bytes_be()).collect()
+    }
+
+    pub fn inputs_to_ark(bytes: &[u8]) -> Result<Vec<Fr>> {
+        if bytes.len() % SCALAR_SIZE != 0 {
+            return Err(Error::InvalidInputs);
+        }
+        bytes
+            .chunks(SCALAR_SIZE)
+            .map(|chunk| {
+                if U256::from_be_slice(chunk) >= BN254_SCALAR_MODULUS {
+                    return Err(Error::ScalarOutOfRange);
+                }
+                Ok(Fr::from_be_bytes_mod_order(chunk))
+            })
+            .collect()
+    }
+
+    /// arkworks `serialize_compressed` proof -> canonical proof
+    pub fn proof_from_compressed(bytes: &[u8]) -> Result<Vec<u8>> {
+        let proof = Proof::<Bn254>::deserialize_compressed(bytes)
+            .map_err(|_| Error::DeserializationError)?;
+        Ok(proof_from_ark(&proof))
+    }
+
+    /// Canonical proof -> arkworks `serialize_compressed` proof
+    pub fn proof_to_compressed(bytes: &[u8]) -> Result<Vec<u8>> {
+        compress(&proof_to_ark(bytes)?)
+    }
+
+    /// arkworks `serialize_compressed` VK -> canonical VK
+    pub fn vk_from_compressed(bytes: &[u8]) -> Result<Vec<u8>> {
+        let vk = VerifyingKey::<Bn254>::deserialize_compressed(bytes)
+            .map_err(|_| Error::InvalidVerificationKey)?;
+        Ok(vk_from_ark(&vk))
+    }
+
+    /// Canonical VK -> arkworks `serialize_compressed` VK
+    pub fn vk_to_compressed(bytes: &[u8]) -> Result<Vec<u8>> {
+        compress(&vk_to_ark(bytes)?)
+    }
+
+    /// arkworks compressed scalars (32-byte little-endian each) -> canonical inputs
+    pub fn inputs_from_compressed(bytes: &[u8]) -> Result<Vec<u8>> {
+        if bytes.len() % SCALAR_SIZE != 0 {
+            return Err(Error::InvalidInputs);
+        }
+        let inputs = bytes
+            .chunks(SCALAR_SIZE)
+            .map(|chunk| Fr::deserialize_compressed(chunk).map_err(|_| Error::InvalidInputs))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(inputs_from_ark(&inputs))
+    }
+
+    /// Canonical inputs -> arkworks compressed scalars
+    pub fn inputs_to_compressed(bytes: &[u8]) -> Result<Vec<u8>> {
+        let mut out = Vec::with_capacity(bytes.len());
+        for input in inputs_to_ark(bytes)? {
+            out.extend(compress(&input)?);
+        }
+        Ok(out)
+    }
+
+    fn compress<T: CanonicalSerialize>(value: &T) -> Result<Vec<u8>> {
+        let mut out = Vec::new();
+        value
+            .serialize_compressed(&mut out)
+            .map_err(|_| Error::DeserializationError)?;
+        Ok(out)
+    }
+
+    fn fq_to_ark(bytes: &[u8]) -> Option<Fq> {
+        if U256::from_be_slice(bytes) >= BN254_BASE_MODULUS {
+            return None;
+        }
+        Some(Fq::from_be_bytes_mod_order(bytes))
+    }
+
+    fn fq_from_ark(value: &Fq) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&value.into_bigint().to_bytes_be());
+        out
+    }
+}
+
+#[cfg(feature = "std")]
+pub use host_impl::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    // BN254 G1 generator (1, 2) and G2 generator from EIP-197
+    const G2_X: [&str; 2] = [
+        "10857046999023057135944570762232829481370756359578518086990519993285655852781",
+        "11559732032986387107991004021392285783925812861821192530917403151452391805634",
+    ];
+    const G2_Y: [&str; 2] = [
+        "8495653923123431417604973247489272438418190587263600148770280649306958101930",
+        "4082367875863433681332203403145435568316851327593401208105741076214120093531",
+    ];
+
+    #[test]
+    fn test_g1_snarkjs_roundtrip() {
+        let point = g1_from_snarkjs(&["1", "2", "1"]).unwrap();
+        assert_eq!(U256::from_be_slice(&point[0..32]), U256::from(1));
+        assert_eq!(U256::from_be_slice(&point[32..64]), U256::from(2));
+        assert_eq!(point, g1_from_snarkjs(&["1", "2"]).unwrap());
+        assert_eq!(g1_to_snarkjs(&point).unwrap(), ["1", "2", "1"]);
+
+        // Identity
+        let identity = g1_from_snarkjs(&["0", "1", "0"]).unwrap();
+        assert_eq!(identity, [0u8; 64]);
+        assert_eq!(g1_to_snarkjs(&identity).unwrap(), ["0", "1", "0"]);
+    }
+
+    #[test]
+    fn test_g2_snarkjs_limb_order() {
+        let point = g2_from_snarkjs(&[G2_X, G2_Y, ["1", "0"]]).unwrap();
+
+        // EIP-197 puts the imaginary limb first
+        assert_eq!(
+            &point[0..32],
+            &fq_from_decimal(G2_X[1]).unwrap()[..]
+        );
+        assert_eq!(&point[32..64], &fq_from_decimal(G2_X[0]).unwrap()[..]);
+        assert_eq!(&point[64..96], &fq_from_decimal(G2_Y[1]).unwrap()[..]);
+        assert_eq!(&point[96..128], &fq_from_decimal(G2_Y[0]).unwrap()[..]);
+
+        let back = g2_to_snarkjs(&point).unwrap();
+        assert_eq!(back[0], G2_X);
+        assert_eq!(back[1], G2_Y);
+    }
+
+    #[test]
+    fn test_snarkjs_rejects_malformed() {
+        assert!(g1_from_snarkjs(&["1"]).is_err());
+        assert!(g1_from_snarkjs(&["1", "2", "2"]).is_err());
+        assert!(g1_from_snarkjs(&["0x01", "2"]).is_err());
+        assert!(g1_from_snarkjs(&["", "2"]).is_err());
+        assert!(g2_from_snarkjs(&[G2_X, G2_Y, ["1", "1"]]).is_err());
+
+        // Coordinates >= p and signals >= r
+        let p = to_decimal(BN254_BASE_MODULUS);
+        assert!(g1_from_snarkjs(&[p.as_str(), "2"]).is_err());
+        let r = to_decimal(BN254_SCALAR_MODULUS);
+        assert_eq!(scalar_from_snarkjs(&r), Err(Error::ScalarOutOfRange));
+        assert_eq!(
+            U256::from_be_slice(&scalar_from_snarkjs("42").unwrap()),
+            U256::from(42)
+        );
+    }
+
+    #[test]
+    fn test_proof_and_vk_layout() {
+        let g1 = ["1", "2", "1"];
+        let g2 = [G2_X, G2_Y, ["1", "0"]];
+        let g2_rows: Vec<&[&str]> = g2.iter().map(|row| &row[..]).collect();
+
+        let proof = proof_from_snarkjs(&g1, &g2_rows, &g1).unwrap();
+        assert_eq!(proof.len(), PROOF_SIZE);
+
+        let ic: Vec<&[&str]> = vec![&g1, &g1, &g1];
+        let vk = vk_from_snarkjs(&g1, &g2_rows, &g2_rows, &g2_rows, &ic).unwrap();
+        assert_eq!(vk.len(), VK_HEADER_SIZE + 3 * G1_SIZE);
+        assert_eq!(&vk[VK_HEADER_SIZE..VK_HEADER_SIZE + 64], &proof[0..64]);
+
+        let empty_ic: Vec<&[&str]> = Vec::new();
+        assert_eq!(
+            vk_from_snarkjs(&g1, &g2_rows, &g2_rows, &g2_rows, &empty_ic),
+            Err(Error::InvalidVerificationKey)
+        );
+    }
+}
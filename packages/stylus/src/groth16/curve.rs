@@ -0,0 +1,510 @@
+//! BN254 Point Validation
+//!
+//! The precompiles only see raw coordinates, so everything that must hold before a
+//! point is trusted is checked here in plain U256 arithmetic:
+//! - coordinates are canonical (< p)
+//! - G1: y² = x³ + 3
+//! - G2: y² = x³ + 3/(9+u) over Fq2 = Fq[u]/(u² + 1), and [r]P = O
+//!
+//! The identity is encoded as all zeros, matching EIP-196/197.
+//!
+//! # Point Compression
+//! Compressed points follow arkworks `serialize_compressed`, so host-side
+//! artifacts decompress on-chain unchanged:
+//! ```text
+//! G1: 32 bytes, x little-endian
+//! G2: 64 bytes, x.c0 || x.c1, each little-endian
+//! flags (top two bits of the last byte):
+//!   bit 7  y is the larger root (y > -y)
+//!   bit 6  point at infinity (x must be zero)
+//! ```
+//! Fq2 roots are ordered by c1, then by c0 when c1 is zero.
+
+use stylus_sdk::alloy_primitives::U256;
+use super::{Error, Result};
+use crate::utils::{
+    fq_add, fq_inv, fq_mul, fq_neg, fq_sqrt, fq_sub, BN254_BASE_MODULUS,
+    BN254_SCALAR_MODULUS,
+};
+
+/// Compressed G1 size
+pub const G1_COMPRESSED_SIZE: usize = 32;
+
+/// Compressed G2 size
+pub const G2_COMPRESSED_SIZE: usize = 64;
+
+/// Flag: y is the larger of the two roots
+const FLAG_Y_LARGEST: u8 = 1 << 7;
+
+/// Flag: point at infinity
+const FLAG_INFINITY: u8 = 1 << 6;
+
+/// (p - 1) / 2; y is the larger root iff y > this
+const HALF_MODULUS: U256 = U256::from_be_bytes([
+    0x18, 0x32, 0x27, 0x39, 0x70, 0x98, 0xd0, 0x14,
+    0xdc, 0x28, 0x22, 0xdb, 0x40, 0xc0, 0xac, 0x2e,
+    0xcb, 0xc0, 0xb5, 0x48, 0xb4, 0x38, 0xe5, 0x46,
+    0x9e, 0x10, 0x46, 0x0b, 0x6c, 0x3e, 0x7e, 0xa3,
+]);
+
+/// G1 curve constant b
+const G1_B: U256 = U256::from_limbs([3, 0, 0, 0]);
+
+/// G2 twist constant b' = 3 / (9 + u)
+const G2_B: Fq2 = Fq2 {
+    c0: U256::from_be_bytes([
+        0x2b, 0x14, 0x9d, 0x40, 0xce, 0xb8, 0xaa, 0xae,
+        0x81, 0xbe, 0x18, 0x99, 0x1b, 0xe0, 0x6a, 0xc3,
+        0xb5, 0xb4, 0xc5, 0xe5, 0x59, 0xdb, 0xef, 0xa3,
+        0x32, 0x67, 0xe6, 0xdc, 0x24, 0xa1, 0x38, 0xe5,
+    ]),
+    c1: U256::from_be_bytes([
+        0x00, 0x97, 0x13, 0xb0, 0x3a, 0xf0, 0xfe, 0xd4,
+        0xcd, 0x2c, 0xaf, 0xad, 0xee, 0xd8, 0xfd, 0xf4,
+        0xa7, 0x4f, 0xa0, 0x84, 0xe5, 0x2d, 0x18, 0x52,
+        0xe4, 0xa2, 0xbd, 0x06, 0x85, 0xc3, 0x15, 0xd2,
+    ]),
+};
+
+/// Quadratic extension element c0 + c1·u
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fq2 {
+    pub c0: U256,
+    pub c1: U256,
+}
+
+impl Fq2 {
+    pub const ZERO: Fq2 = Fq2 { c0: U256::ZERO, c1: U256::ZERO };
+    pub const ONE: Fq2 = Fq2 { c0: U256::from_limbs([1, 0, 0, 0]), c1: U256::ZERO };
+
+    pub fn new(c0: U256, c1: U256) -> Self {
+        Self { c0, c1 }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.c0 == U256::ZERO && self.c1 == U256::ZERO
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        Self::new(fq_add(self.c0, other.c0), fq_add(self.c1, other.c1))
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        Self::new(fq_sub(self.c0, other.c0), fq_sub(self.c1, other.c1))
+    }
+
+    pub fn neg(&self) -> Self {
+        Self::new(fq_neg(self.c0), fq_neg(self.c1))
+    }
+
+    pub fn double(&self) -> Self {
+        self.add(self)
+    }
+
+    /// (a0 + a1·u)(b0 + b1·u) = (a0·b0 − a1·b1) + (a0·b1 + a1·b0)·u
+    pub fn mul(&self, other: &Self) -> Self {
+        let c0 = fq_sub(fq_mul(self.c0, other.c0), fq_mul(self.c1, other.c1));
+        let c1 = fq_add(fq_mul(self.c0, other.c1), fq_mul(self.c1, other.c0));
+        Self::new(c0, c1)
+    }
+
+    pub fn square(&self) -> Self {
+        self.mul(self)
+    }
+
+    /// Square root over Fq2, or `None` for non-residues
+    ///
+    /// For a = a0 + a1·u with a1 ≠ 0, the root x0 + x1·u satisfies
+    /// x0² = (a0 ± √(a0² + a1²)) / 2 and x1 = a1 / (2·x0).
+    pub fn sqrt(&self) -> Option<Self> {
+        if self.c1 == U256::ZERO {
+            // a0 or -a0 is a residue: √a0 or √(-a0)·u
+            return match fq_sqrt(self.c0) {
+                Some(root) => Some(Self::new(root, U256::ZERO)),
+                None => fq_sqrt(fq_neg(self.c0)).map(|root| Self::new(U256::ZERO, root)),
+            };
+        }
+
+        let norm = fq_add(fq_mul(self.c0, self.c0), fq_mul(self.c1, self.c1));
+        let n = fq_sqrt(norm)?;
+        let half = fq_inv(U256::from(2))?;
+        let x0 = fq_sqrt(fq_mul(fq_add(self.c0, n), half))
+            .or_else(|| fq_sqrt(fq_mul(fq_sub(self.c0, n), half)))?;
+        let x1 = fq_mul(self.c1, fq_inv(fq_add(x0, x0))?);
+        let root = Self::new(x0, x1);
+        (root.square() == *self).then_some(root)
+    }
+
+    /// Whether this is the larger of {self, -self}: compares c1, then c0
+    pub fn is_lexicographically_largest(&self) -> bool {
+        if self.c1 == U256::ZERO {
+            self.c0 > HALF_MODULUS
+        } else {
+            self.c1 > HALF_MODULUS
+        }
+    }
+}
+
+/// Check a public input scalar is canonical (< r)
+///
+/// Without this, x and x + r produce the same pairing result and proofs
+/// become malleable.
+pub fn validate_scalar(bytes: &[u8]) -> Result<U256> {
+    let value = U256::from_be_slice(bytes);
+    if value >= BN254_SCALAR_MODULUS {
+        return Err(Error::ScalarOutOfRange);
+    }
+    Ok(value)
+}
+
+/// Check a 64-byte G1 point is on the curve; returns whether it is the identity
+pub fn validate_g1(bytes: &[u8]) -> Result<bool> {
+    let x = read_fq(&bytes[0..32]).ok_or(Error::G1NotOnCurve)?;
+    let y = read_fq(&bytes[32..64]).ok_or(Error::G1NotOnCurve)?;
+    if x == U256::ZERO && y == U256::ZERO {
+        return Ok(true);
+    }
+    let rhs = fq_add(fq_mul(fq_mul(x, x), x), G1_B);
+    if fq_mul(y, y) != rhs {
+        return Err(Error::G1NotOnCurve);
+    }
+    Ok(false)
+}
+
+/// Check a 128-byte G2 point (EIP-197 order) is on the twist and in the
+/// order-r subgroup; returns whether it is the identity
+pub fn validate_g2(bytes: &[u8]) -> Result<bool> {
+    let (x, y) = read_g2(bytes).ok_or(Error::G2NotOnCurve)?;
+    if x.is_zero() && y.is_zero() {
+        return Ok(true);
+    }
+    if !is_on_twist(&x, &y) {
+        return Err(Error::G2NotOnCurve);
+    }
+    if !is_in_g2_subgroup(&x, &y) {
+        return Err(Error::G2NotInSubgroup);
+    }
+    Ok(false)
+}
+
+/// Parse EIP-197 G2 bytes (x.c1, x.c0, y.c1, y.c0), rejecting non-canonical limbs
+pub fn read_g2(bytes: &[u8]) -> Option<(Fq2, Fq2)> {
+    let x = Fq2::new(read_fq(&bytes[32..64])?, read_fq(&bytes[0..32])?);
+    let y = Fq2::new(read_fq(&bytes[96..128])?, read_fq(&bytes[64..96])?);
+    Some((x, y))
+}
+
+/// Decompress a 32-byte G1 point into the 64-byte uncompressed encoding
+///
+/// Rejects bad flags and x with no point on the curve. The output is on the
+/// curve by construction.
+pub fn decompress_g1(bytes: &[u8]) -> Result<[u8; 64]> {
+    if bytes.len() != G1_COMPRESSED_SIZE {
+        return Err(Error::InvalidProof);
+    }
+    match read_flagged_fq(bytes, Error::G1NotOnCurve)? {
+        Flagged::Infinity => Ok([0u8; 64]),
+        Flagged::Point(x, largest) => g1_from_x(x, largest),
+    }
+}
+
+/// Recover the G1 point with abscissa `x` (canonical) and the chosen y root
+pub fn g1_from_x(x: U256, largest: bool) -> Result<[u8; 64]> {
+    let mut y = fq_sqrt(fq_add(fq_mul(fq_mul(x, x), x), G1_B)).ok_or(Error::G1NotOnCurve)?;
+    if (y > HALF_MODULUS) != largest {
+        y = fq_neg(y);
+    }
+
+    let mut out = [0u8; 64];
+    out[0..32].copy_from_slice(&x.to_be_bytes::<32>());
+    out[32..64].copy_from_slice(&y.to_be_bytes::<32>());
+    Ok(out)
+}
+
+/// Decompress a 64-byte G2 point into the 128-byte EIP-197 encoding
+///
+/// Subgroup membership is not checked here; `validate_g2` does that.
+pub fn decompress_g2(bytes: &[u8]) -> Result<[u8; 128]> {
+    if bytes.len() != G2_COMPRESSED_SIZE {
+        return Err(Error::InvalidProof);
+    }
+    let x0 = read_le_fq(&bytes[0..32]).ok_or(Error::G2NotOnCurve)?;
+    let (x1, largest) = match read_flagged_fq(&bytes[32..64], Error::G2NotOnCurve)? {
+        Flagged::Infinity if x0 == U256::ZERO => return Ok([0u8; 128]),
+        Flagged::Infinity => return Err(Error::InvalidProof),
+        Flagged::Point(x1, largest) => (x1, largest),
+    };
+    g2_from_x(Fq2::new(x0, x1), largest)
+}
+
+/// Recover the EIP-197 G2 point with abscissa `x` and the chosen y root
+///
+/// Subgroup membership is not checked here; `validate_g2` does that.
+pub fn g2_from_x(x: Fq2, largest: bool) -> Result<[u8; 128]> {
+    let mut y = x.square().mul(&x).add(&G2_B).sqrt().ok_or(Error::G2NotOnCurve)?;
+    if y.is_lexicographically_largest() != largest {
+        y = y.neg();
+    }
+
+    let mut out = [0u8; 128];
+    out[0..32].copy_from_slice(&x.c1.to_be_bytes::<32>());
+    out[32..64].copy_from_slice(&x.c0.to_be_bytes::<32>());
+    out[64..96].copy_from_slice(&y.c1.to_be_bytes::<32>());
+    out[96..128].copy_from_slice(&y.c0.to_be_bytes::<32>());
+    Ok(out)
+}
+
+/// Compress a 64-byte G1 point; the point is validated first
+pub fn compress_g1(bytes: &[u8]) -> Result<[u8; 32]> {
+    let mut out = [0u8; 32];
+    if validate_g1(bytes)? {
+        out[31] = FLAG_INFINITY;
+        return Ok(out);
+    }
+    let x = U256::from_be_slice(&bytes[0..32]);
+    let y = U256::from_be_slice(&bytes[32..64]);
+    out.copy_from_slice(&x.to_le_bytes::<32>());
+    if y > HALF_MODULUS {
+        out[31] |= FLAG_Y_LARGEST;
+    }
+    Ok(out)
+}
+
+/// Compress a 128-byte EIP-197 G2 point; the point is validated first
+pub fn compress_g2(bytes: &[u8]) -> Result<[u8; 64]> {
+    let mut out = [0u8; 64];
+    if validate_g2(bytes)? {
+        out[63] = FLAG_INFINITY;
+        return Ok(out);
+    }
+    let (x, y) = read_g2(bytes).ok_or(Error::G2NotOnCurve)?;
+    out[0..32].copy_from_slice(&x.c0.to_le_bytes::<32>());
+    out[32..64].copy_from_slice(&x.c1.to_le_bytes::<32>());
+    if y.is_lexicographically_largest() {
+        out[63] |= FLAG_Y_LARGEST;
+    }
+    Ok(out)
+}
+
+/// A flagged x limb, after the flag bits are split off
+enum Flagged {
+    Infinity,
+    /// x (or x.c1) and whether y is the larger root
+    Point(U256, bool),
+}
+
+/// Split the flags off a little-endian flagged limb; `invalid` is returned for
+/// a non-canonical limb
+fn read_flagged_fq(bytes: &[u8], invalid: Error) -> Result<Flagged> {
+    let flags = bytes[31] & (FLAG_Y_LARGEST | FLAG_INFINITY);
+    let mut limb = [0u8; 32];
+    limb.copy_from_slice(bytes);
+    limb[31] &= !flags;
+    let value = read_le_fq(&limb).ok_or(invalid)?;
+
+    match flags {
+        FLAG_INFINITY if value == U256::ZERO => Ok(Flagged::Infinity),
+        0 => Ok(Flagged::Point(value, false)),
+        FLAG_Y_LARGEST => Ok(Flagged::Point(value, true)),
+        _ => Err(Error::InvalidProof),
+    }
+}
+
+fn read_le_fq(bytes: &[u8]) -> Option<U256> {
+    let value = U256::from_le_slice(bytes);
+    (value < BN254_BASE_MODULUS).then_some(value)
+}
+
+fn read_fq(bytes: &[u8]) -> Option<U256> {
+    let value = U256::from_be_slice(bytes);
+    (value < BN254_BASE_MODULUS).then_some(value)
+}
+
+fn is_on_twist(x: &Fq2, y: &Fq2) -> bool {
+    y.square() == x.square().mul(x).add(&G2_B)
+}
+
+/// [r]P == O, computed with Jacobian double-and-add
+fn is_in_g2_subgroup(x: &Fq2, y: &Fq2) -> bool {
+    let base = (*x, *y);
+    let mut acc = G2Jacobian::IDENTITY;
+    for i in (0..BN254_SCALAR_MODULUS.bit_len()).rev() {
+        acc = acc.double();
+        if BN254_SCALAR_MODULUS.bit(i) {
+            acc = acc.add_affine(&base);
+        }
+    }
+    acc.is_identity()
+}
+
+/// G2 point in Jacobian coordinates (X/Z², Y/Z³); Z = 0 is the identity
+#[derive(Debug, Clone, Copy)]
+struct G2Jacobian {
+    x: Fq2,
+    y: Fq2,
+    z: Fq2,
+}
+
+impl G2Jacobian {
+    const IDENTITY: G2Jacobian = G2Jacobian { x: Fq2::ONE, y: Fq2::ONE, z: Fq2::ZERO };
+
+    fn is_identity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    /// dbl-2009-l (a = 0)
+    fn double(&self) -> Self {
+        if self.is_identity() || self.y.is_zero() {
+            return Self::IDENTITY;
+        }
+        let a = self.x.square();
+        let b = self.y.square();
+        let c = b.square();
+        let d = self.x.add(&b).square().sub(&a).sub(&c).double();
+        let e = a.double().add(&a);
+        let f = e.square();
+        let x3 = f.sub(&d.double());
+        let y3 = e.mul(&d.sub(&x3)).sub(&c.double().double().double());
+        let z3 = self.y.mul(&self.z).double();
+        Self { x: x3, y: y3, z: z3 }
+    }
+
+    /// madd-2007-bl (affine addend)
+    fn add_affine(&self, other: &(Fq2, Fq2)) -> Self {
+        if self.is_identity() {
+            return Self { x: other.0, y: other.1, z: Fq2::ONE };
+        }
+        let z1z1 = self.z.square();
+        let u2 = other.0.mul(&z1z1);
+        let s2 = other.1.mul(&self.z).mul(&z1z1);
+        let h = u2.sub(&self.x);
+        let r = s2.sub(&self.y).double();
+        if h.is_zero() {
+            return if r.is_zero() { self.double() } else { Self::IDENTITY };
+        }
+        let hh = h.square();
+        let i = hh.double().double();
+        let j = h.mul(&i);
+        let v = self.x.mul(&i);
+        let x3 = r.square().sub(&j).sub(&v.double());
+        let y3 = r.mul(&v.sub(&x3)).sub(&self.y.mul(&j).double());
+        let z3 = self.z.add(&h).square().sub(&z1z1).sub(&hh);
+        Self { x: x3, y: y3, z: z3 }
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    fn hex32(s: &str) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        for i in 0..32 {
+            out[i] = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).unwrap();
+        }
+        out
+    }
+
+    /// G1 generator (1, 2)
+    pub fn g1_generator() -> [u8; 64] {
+        let mut out = [0u8; 64];
+        out[31] = 1;
+        out[63] = 2;
+        out
+    }
+
+    /// G2 generator in EIP-197 order
+    pub fn g2_generator() -> [u8; 128] {
+        let mut out = [0u8; 128];
+        out[0..32].copy_from_slice(&hex32("198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2"));
+        out[32..64].copy_from_slice(&hex32("1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed"));
+        out[64..96].copy_from_slice(&hex32("090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b"));
+        out[96..128].copy_from_slice(&hex32("12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"));
+        out
+    }
+
+    #[test]
+    fn test_g1_validation() {
+        let generator = g1_generator();
+        assert_eq!(validate_g1(&generator), Ok(false));
+        assert_eq!(validate_g1(&[0u8; 64]), Ok(true));
+
+        let mut off_curve = generator;
+        off_curve[63] = 3;
+        assert_eq!(validate_g1(&off_curve), Err(Error::G1NotOnCurve));
+
+        // y + p encodes the same residue but is not canonical
+        let mut non_canonical = generator;
+        let y = U256::from(2) + BN254_BASE_MODULUS;
+        non_canonical[32..64].copy_from_slice(&y.to_be_bytes::<32>());
+        assert_eq!(validate_g1(&non_canonical), Err(Error::G1NotOnCurve));
+    }
+
+    #[test]
+    fn test_g2_validation() {
+        let generator = g2_generator();
+        assert_eq!(validate_g2(&generator), Ok(false));
+        assert_eq!(validate_g2(&[0u8; 128]), Ok(true));
+
+        let mut off_curve = generator;
+        off_curve[127] ^= 1;
+        assert_eq!(validate_g2(&off_curve), Err(Error::G2NotOnCurve));
+    }
+
+    #[test]
+    fn test_scalar_validation() {
+        let r_minus_1 = (BN254_SCALAR_MODULUS - U256::from(1)).to_be_bytes::<32>();
+        assert_eq!(validate_scalar(&r_minus_1), Ok(BN254_SCALAR_MODULUS - U256::from(1)));
+        assert_eq!(
+            validate_scalar(&BN254_SCALAR_MODULUS.to_be_bytes::<32>()),
+            Err(Error::ScalarOutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_point_compression_roundtrip() {
+        let g1 = g1_generator();
+        let mut neg_g1 = g1;
+        neg_g1[32..64].copy_from_slice(&fq_neg(U256::from(2)).to_be_bytes::<32>());
+        for point in [g1, neg_g1, [0u8; 64]] {
+            assert_eq!(decompress_g1(&compress_g1(&point).unwrap()), Ok(point));
+        }
+        assert_ne!(compress_g1(&g1).unwrap(), compress_g1(&neg_g1).unwrap());
+
+        let g2 = g2_generator();
+        let (x, y) = read_g2(&g2).unwrap();
+        let mut neg_g2 = g2;
+        neg_g2[64..96].copy_from_slice(&y.neg().c1.to_be_bytes::<32>());
+        neg_g2[96..128].copy_from_slice(&y.neg().c0.to_be_bytes::<32>());
+        for point in [g2, neg_g2, [0u8; 128]] {
+            assert_eq!(decompress_g2(&compress_g2(&point).unwrap()), Ok(point));
+        }
+        assert_eq!(x.square().mul(&x).add(&G2_B).sqrt().map(|r| r.square()), Some(y.square()));
+    }
+
+    #[test]
+    fn test_decompression_rejections() {
+        let compressed = compress_g1(&g1_generator()).unwrap();
+
+        // Both flags set
+        let mut bad_flags = compressed;
+        bad_flags[31] |= FLAG_Y_LARGEST | FLAG_INFINITY;
+        assert_eq!(decompress_g1(&bad_flags), Err(Error::InvalidProof));
+
+        // Infinity with a non-zero x
+        let mut bad_infinity = compressed;
+        bad_infinity[31] |= FLAG_INFINITY;
+        assert_eq!(decompress_g1(&bad_infinity), Err(Error::InvalidProof));
+
+        // x = 0 has no point: 3 is not a square mod p
+        assert_eq!(decompress_g1(&[0u8; 32]), Err(Error::G1NotOnCurve));
+
+        // x >= p
+        let mut non_canonical = BN254_BASE_MODULUS.to_le_bytes::<32>();
+        non_canonical[31] &= !(FLAG_Y_LARGEST | FLAG_INFINITY);
+        assert_eq!(decompress_g1(&non_canonical), Err(Error::G1NotOnCurve));
+
+        assert_eq!(decompress_g1(&compressed[..31]), Err(Error::InvalidProof));
+        assert_eq!(decompress_g2(&[0u8; 63]), Err(Error::InvalidProof));
+    }
+}
@@ -0,0 +1,623 @@
+//! gnark Groth16 with BSB22 Commitments (BN254)
+//!
+//! Verifies proofs written by gnark's `groth16.Prove`, read directly from
+//! gnark's binary serialization. Circuits that call `api.Commit` add Pedersen
+//! commitments D_i and one folded proof of knowledge to the proof; the hash of
+//! each commitment is an extra public input that the verifier derives itself.
+//! Circuits without commitments verify here too (m = 0).
+//!
+//! # Binary Layout
+//! `VerifyingKey.WriteTo` / `WriteRawTo` and `Proof.WriteTo` / `WriteRawTo`:
+//! ```text
+//! vk:    α₁ ‖ β₁ ‖ β₂ ‖ γ₂ ‖ δ₁ ‖ δ₂
+//!        ‖ u32 n ‖ K_0 .. K_{n-1}                    (G1)
+//!        ‖ u32 m ‖ (u32 len ‖ u64 wire × len) × m    public wires each commitment covers
+//!        ‖ u32 m ‖ (G ‖ GRootSigmaNeg) × m           Pedersen keys (G2)
+//! proof: Ar ‖ Bs ‖ Krs ‖ u32 m ‖ D_0 .. D_{m-1} ‖ PoK
+//! ```
+//! Integers are big-endian. Points use gnark-crypto's encoding: big-endian
+//! coordinates, G2 as `x.a1 ‖ x.a0 ‖ y.a1 ‖ y.a0`, and the top two bits of the
+//! first byte give the form:
+//! ```text
+//! 00  uncompressed (64 / 128 bytes), infinity = all zeros
+//! 10  compressed (32 / 64 bytes), smaller y
+//! 11  compressed, larger y
+//! 01  compressed infinity
+//! ```
+//! The form is read per point, so raw and compressed files both parse.
+//!
+//! Public inputs are the canonical 32-byte big-endian scalars;
+//! `inputs_from_witness` strips the header of a gnark public witness.
+//!
+//! # Verification
+//! ```text
+//! h_i = hash_to_field(D_i ‖ x[wires_i], "bsb22-commitment")    appended to x
+//! r   = hash_to_field(h_0 ‖ .. ‖ h_{m-1}, "G16-BSB22")
+//! e(PoK, G) · Π e(r^i·D_i, GRootSigmaNeg_i) == 1
+//! e(A, B) · e(α, -β) · e(K_0 + Σ x_j·K_{j+1} + Σ D_i, -γ) · e(C, -δ) == 1
+//! ```
+//! `hash_to_field` is gnark-crypto's `fr.Hash`: RFC 9380 `expand_message_xmd`
+//! with SHA-256, 48 bytes reduced mod r.
+
+use alloc::vec::Vec;
+use sha2::{Digest, Sha256};
+use stylus_sdk::alloy_primitives::{U256, U512};
+use super::{curve, negate_g2, validate_inputs, Error, MsmBackend, Result};
+use crate::utils::{fr_mul, BN254_BASE_MODULUS, BN254_SCALAR_MODULUS};
+
+/// Domain separator of the commitment hash (gnark `constraint.CommitmentDst`)
+pub const COMMITMENT_DST: &[u8] = b"bsb22-commitment";
+
+/// Domain separator of the proof-of-knowledge folding challenge
+pub const CHALLENGE_DST: &[u8] = b"G16-BSB22";
+
+/// Public witness header: nbPublic (4) + nbSecret (4) + vector length (4)
+pub const WITNESS_HEADER_SIZE: usize = 12;
+
+/// `hash_to_field` output length: 32 + 16 bytes for 128-bit security
+const HASH_TO_FIELD_SIZE: usize = 48;
+
+/// Point form flags (top two bits of the first byte)
+const FLAG_MASK: u8 = 0b11 << 6;
+const FLAG_UNCOMPRESSED: u8 = 0b00 << 6;
+const FLAG_COMPRESSED_INFINITY: u8 = 0b01 << 6;
+const FLAG_COMPRESSED_LARGEST: u8 = 0b11 << 6;
+
+/// Pedersen commitment verifying key (gnark-crypto `pedersen.VerifyingKey`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitmentKey {
+    /// G (G2)
+    pub g: [u8; 128],
+    /// G^{-1/σ} (G2)
+    pub g_root_sigma_neg: [u8; 128],
+}
+
+/// gnark Groth16 verifying key, points in the canonical EVM layout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyingKey {
+    pub alpha: [u8; 64],
+    /// β in G1 (prover side, carried for completeness)
+    pub beta_g1: [u8; 64],
+    pub beta: [u8; 128],
+    pub gamma: [u8; 128],
+    /// δ in G1 (prover side, carried for completeness)
+    pub delta_g1: [u8; 64],
+    pub delta: [u8; 128],
+    /// K_0 (constant wire), one base per public input, one per commitment
+    pub k: Vec<[u8; 64]>,
+    /// For each commitment, the 1-based public wires it covers
+    pub committed: Vec<Vec<u64>>,
+    /// One Pedersen key per commitment
+    pub commitment_keys: Vec<CommitmentKey>,
+}
+
+impl VerifyingKey {
+    /// Parse and validate a VK in gnark's binary serialization
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let mut reader = Reader::new(bytes, Error::InvalidVerificationKey);
+        let alpha = reader.g1()?;
+        let beta_g1 = reader.g1()?;
+        let beta = reader.g2()?;
+        let gamma = reader.g2()?;
+        let delta_g1 = reader.g1()?;
+        let delta = reader.g2()?;
+
+        let k = (0..reader.u32()?).map(|_| reader.g1()).collect::<Result<Vec<_>>>()?;
+        let committed = (0..reader.u32()?)
+            .map(|_| {
+                let len = reader.u32()?;
+                (0..len).map(|_| reader.u64()).collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let commitment_keys = (0..reader.u32()?)
+            .map(|_| Ok(CommitmentKey { g: reader.g2()?, g_root_sigma_neg: reader.g2()? }))
+            .collect::<Result<Vec<_>>>()?;
+        reader.finish()?;
+
+        let vk = Self { alpha, beta_g1, beta, gamma, delta_g1, delta, k, committed, commitment_keys };
+        vk.validate()?;
+        Ok(vk)
+    }
+
+    /// Number of public inputs the caller supplies (commitment hashes excluded)
+    pub fn num_inputs(&self) -> usize {
+        self.k.len() - self.committed.len() - 1
+    }
+
+    /// No identity points, consistent commitment metadata, and wire indices
+    /// that only reach public inputs and earlier commitment hashes
+    fn validate(&self) -> Result<()> {
+        let g1_points = [&self.alpha, &self.beta_g1, &self.delta_g1].into_iter().chain(&self.k);
+        let g2_points = [&self.beta, &self.gamma, &self.delta].into_iter().chain(
+            self.commitment_keys.iter().flat_map(|key| [&key.g, &key.g_root_sigma_neg]),
+        );
+        if g1_points.map(|p| &p[..]).chain(g2_points.map(|p| &p[..])).any(is_identity) {
+            return Err(Error::IdentityPoint);
+        }
+
+        if self.committed.len() != self.commitment_keys.len() || self.k.len() <= self.committed.len() {
+            return Err(Error::InvalidVerificationKey);
+        }
+        let num_inputs = self.num_inputs() as u64;
+        for (i, wires) in self.committed.iter().enumerate() {
+            if wires.iter().any(|&wire| wire == 0 || wire > num_inputs + i as u64) {
+                return Err(Error::InvalidVerificationKey);
+            }
+        }
+
+        // The proofs of knowledge are folded into one pairing against a shared G
+        if self.commitment_keys.iter().any(|key| key.g != self.commitment_keys[0].g) {
+            return Err(Error::InvalidVerificationKey);
+        }
+        Ok(())
+    }
+}
+
+/// gnark Groth16 proof, points in the canonical EVM layout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proof {
+    pub a: [u8; 64],
+    pub b: [u8; 128],
+    pub c: [u8; 64],
+    /// Pedersen commitments D_i
+    pub commitments: Vec<[u8; 64]>,
+    /// Folded proof of knowledge for all commitments
+    pub commitment_pok: [u8; 64],
+}
+
+impl Proof {
+    /// Parse and validate a proof in gnark's binary serialization
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let mut reader = Reader::new(bytes, Error::InvalidProof);
+        let a = reader.g1()?;
+        let b = reader.g2()?;
+        let c = reader.g1()?;
+        let commitments = (0..reader.u32()?).map(|_| reader.g1()).collect::<Result<Vec<_>>>()?;
+        let commitment_pok = reader.g1()?;
+        reader.finish()?;
+        Ok(Self { a, b, c, commitments, commitment_pok })
+    }
+}
+
+/// Parse the public inputs and append the commitment hashes h_i
+///
+/// The result has one scalar per K base after K_0.
+pub fn inputs_with_commitments(
+    proof: &Proof,
+    public_inputs_bytes: &[u8],
+    vk: &VerifyingKey,
+) -> Result<Vec<U256>> {
+    if proof.commitments.len() != vk.commitment_keys.len() {
+        return Err(Error::InvalidProof);
+    }
+    let mut inputs = validate_inputs(public_inputs_bytes, vk.num_inputs())?;
+    for (commitment, wires) in proof.commitments.iter().zip(&vk.committed) {
+        let mut prehash = Vec::with_capacity(64 + 32 * wires.len());
+        prehash.extend_from_slice(commitment);
+        for wire in wires {
+            prehash.extend_from_slice(&inputs[*wire as usize - 1].to_be_bytes::<32>());
+        }
+        inputs.push(hash_to_field(&prehash, COMMITMENT_DST));
+    }
+    Ok(inputs)
+}
+
+/// Challenge r folding the proofs of knowledge, from the commitment hashes
+pub fn commitment_challenge(hashes: &[U256]) -> U256 {
+    let serialized: Vec<u8> = hashes.iter().flat_map(|h| h.to_be_bytes::<32>()).collect();
+    hash_to_field(&serialized, CHALLENGE_DST)
+}
+
+/// Strip the header from a gnark public witness (`witness.WriteTo` of `Public()`)
+///
+/// ```text
+/// [nbPublic: u32][nbSecret: u32 = 0][n: u32 = nbPublic][n × 32-byte scalars]
+/// ```
+pub fn inputs_from_witness(bytes: &[u8]) -> Result<&[u8]> {
+    if bytes.len() < WITNESS_HEADER_SIZE {
+        return Err(Error::InvalidInputs);
+    }
+    let word = |i: usize| u32::from_be_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]) as usize;
+    let (nb_public, nb_secret, len) = (word(0), word(4), word(8));
+    if nb_secret != 0 || nb_public != len || bytes.len() - WITNESS_HEADER_SIZE != 32 * len {
+        return Err(Error::InvalidInputs);
+    }
+    Ok(&bytes[WITNESS_HEADER_SIZE..])
+}
+
+/// gnark-crypto `fr.Hash(msg, dst, 1)`: 48 expanded bytes reduced mod r
+pub fn hash_to_field(msg: &[u8], dst: &[u8]) -> U256 {
+    let modulus = U512::from_be_slice(&BN254_SCALAR_MODULUS.to_be_bytes::<32>());
+    let wide = U512::from_be_slice(&expand_message_xmd(msg, dst, HASH_TO_FIELD_SIZE)) % modulus;
+    U256::from_be_slice(&wide.to_be_bytes::<64>()[32..])
+}
+
+/// RFC 9380 `expand_message_xmd` with SHA-256
+///
+/// `dst` must be at most 255 bytes and `len` at most 255 · 32.
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len: usize) -> Vec<u8> {
+    let dst_len = [dst.len() as u8];
+    let b0 = Sha256::new()
+        .chain_update([0u8; 64])
+        .chain_update(msg)
+        .chain_update((len as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(dst)
+        .chain_update(dst_len)
+        .finalize();
+
+    let mut out = Vec::with_capacity(len.div_ceil(32) * 32);
+    let mut block = Sha256::new()
+        .chain_update(b0)
+        .chain_update([1u8])
+        .chain_update(dst)
+        .chain_update(dst_len)
+        .finalize();
+    out.extend_from_slice(&block);
+    for i in 2..=len.div_ceil(32) {
+        let mut chained = [0u8; 32];
+        for (byte, (x, y)) in chained.iter_mut().zip(b0.iter().zip(block.iter())) {
+            *byte = x ^ y;
+        }
+        block = Sha256::new()
+            .chain_update(chained)
+            .chain_update([i as u8])
+            .chain_update(dst)
+            .chain_update(dst_len)
+            .finalize();
+        out.extend_from_slice(&block);
+    }
+    out.truncate(len);
+    out
+}
+
+fn is_identity(point: &[u8]) -> bool {
+    point.iter().all(|b| *b == 0)
+}
+
+/// Cursor over gnark's binary encoding; `invalid` is returned for truncated
+/// input, trailing bytes and malformed flags
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    invalid: Error,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8], invalid: Error) -> Self {
+        Self { bytes, offset: 0, invalid }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.offset.checked_add(len).ok_or(self.invalid)?;
+        let slice = self.bytes.get(self.offset..end).ok_or(self.invalid)?;
+        self.offset = end;
+        Ok(slice)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let mut word = [0u8; 4];
+        word.copy_from_slice(self.take(4)?);
+        Ok(u32::from_be_bytes(word))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(self.take(8)?);
+        Ok(u64::from_be_bytes(word))
+    }
+
+    fn flags(&self) -> Result<u8> {
+        self.bytes.get(self.offset).map(|b| b & FLAG_MASK).ok_or(self.invalid)
+    }
+
+    /// G1 point, checked to be on the curve
+    fn g1(&mut self) -> Result<[u8; 64]> {
+        let point = match self.flags()? {
+            FLAG_UNCOMPRESSED => {
+                let mut point = [0u8; 64];
+                point.copy_from_slice(self.take(64)?);
+                point
+            }
+            FLAG_COMPRESSED_INFINITY => {
+                self.compressed_infinity(32)?;
+                [0u8; 64]
+            }
+            flags => {
+                let x = self.compressed_fq(Error::G1NotOnCurve)?;
+                curve::g1_from_x(x, flags == FLAG_COMPRESSED_LARGEST)?
+            }
+        };
+        curve::validate_g1(&point)?;
+        Ok(point)
+    }
+
+    /// G2 point, checked to be on the twist and in the subgroup
+    fn g2(&mut self) -> Result<[u8; 128]> {
+        let point = match self.flags()? {
+            FLAG_UNCOMPRESSED => {
+                let mut point = [0u8; 128];
+                point.copy_from_slice(self.take(128)?);
+                point
+            }
+            FLAG_COMPRESSED_INFINITY => {
+                self.compressed_infinity(64)?;
+                [0u8; 128]
+            }
+            flags => {
+                let c1 = self.compressed_fq(Error::G2NotOnCurve)?;
+                let c0 = read_fq(self.take(32)?).ok_or(Error::G2NotOnCurve)?;
+                curve::g2_from_x(curve::Fq2::new(c0, c1), flags == FLAG_COMPRESSED_LARGEST)?
+            }
+        };
+        curve::validate_g2(&point)?;
+        Ok(point)
+    }
+
+    /// A 32-byte big-endian coordinate with the flag bits cleared
+    fn compressed_fq(&mut self, invalid: Error) -> Result<U256> {
+        let mut limb = [0u8; 32];
+        limb.copy_from_slice(self.take(32)?);
+        limb[0] &= !FLAG_MASK;
+        read_fq(&limb).ok_or(invalid)
+    }
+
+    fn compressed_infinity(&mut self, len: usize) -> Result<()> {
+        let bytes = self.take(len)?;
+        if bytes[0] != FLAG_COMPRESSED_INFINITY || !is_identity(&bytes[1..]) {
+            return Err(self.invalid);
+        }
+        Ok(())
+    }
+
+    fn finish(&self) -> Result<()> {
+        if self.offset != self.bytes.len() {
+            return Err(self.invalid);
+        }
+        Ok(())
+    }
+}
+
+fn read_fq(bytes: &[u8]) -> Option<U256> {
+    let value = U256::from_be_slice(bytes);
+    (value < BN254_BASE_MODULUS).then_some(value)
+}
+
+// =========================================================================
+// GENERIC IMPLEMENTATION (any Bn254Backend)
+// =========================================================================
+
+pub mod generic {
+    use super::*;
+    use crate::backend::{Bn254Backend, PAIR_SIZE};
+
+    pub fn verify<B: Bn254Backend>(
+        curve: &B,
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+        vk_bytes: &[u8],
+    ) -> Result<bool> {
+        let vk = VerifyingKey::decode(vk_bytes)?;
+        let proof = Proof::decode(proof_bytes)?;
+        verify_parsed(curve, &proof, public_inputs_bytes, &vk)
+    }
+
+    /// Verify an already parsed proof against an already parsed VK
+    pub fn verify_parsed<B: Bn254Backend>(
+        curve: &B,
+        proof: &Proof,
+        public_inputs_bytes: &[u8],
+        vk: &VerifyingKey,
+    ) -> Result<bool> {
+        let inputs = inputs_with_commitments(proof, public_inputs_bytes, vk)?;
+
+        // 1. Commitment proof of knowledge
+        // e(PoK, G) · Π e(r^i·D_i, GRootSigmaNeg_i) == 1
+        if let Some(first) = vk.commitment_keys.first() {
+            let challenge = commitment_challenge(&inputs[vk.num_inputs()..]);
+            let mut pairing_input = Vec::with_capacity(PAIR_SIZE * (proof.commitments.len() + 1));
+            pairing_input.extend_from_slice(&proof.commitment_pok);
+            pairing_input.extend_from_slice(&first.g);
+
+            let mut weight = U256::from(1);
+            for (commitment, key) in proof.commitments.iter().zip(&vk.commitment_keys) {
+                let term = if weight == U256::from(1) {
+                    *commitment
+                } else {
+                    curve.g1_mul(commitment, weight)?
+                };
+                pairing_input.extend_from_slice(&term);
+                pairing_input.extend_from_slice(&key.g_root_sigma_neg);
+                weight = fr_mul(weight, challenge);
+            }
+            if !curve.pairing_check(&pairing_input)? {
+                return Ok(false);
+            }
+        }
+
+        // 2. L = K_0 + Σ x_j·K_{j+1} + Σ D_i
+        let mut scalars = Vec::with_capacity(inputs.len() + 1);
+        scalars.push(U256::from(1));
+        scalars.extend(inputs);
+        let mut l = super::super::generic::g1_msm(curve, &vk.k, &scalars, MsmBackend::Auto)?;
+        for commitment in &proof.commitments {
+            l = curve.g1_add(&l, commitment)?;
+        }
+
+        // 3. e(A, B) · e(α, -β) · e(L, -γ) · e(C, -δ) == 1
+        let mut pairing_input = Vec::with_capacity(4 * PAIR_SIZE);
+        pairing_input.extend_from_slice(&proof.a);
+        pairing_input.extend_from_slice(&proof.b);
+        pairing_input.extend_from_slice(&vk.alpha);
+        pairing_input.extend_from_slice(&negate_g2(&vk.beta)?);
+        pairing_input.extend_from_slice(&l);
+        pairing_input.extend_from_slice(&negate_g2(&vk.gamma)?);
+        pairing_input.extend_from_slice(&proof.c);
+        pairing_input.extend_from_slice(&negate_g2(&vk.delta)?);
+
+        Ok(curve.pairing_check(&pairing_input)?)
+    }
+}
+
+// =========================================================================
+// STYLUS IMPLEMENTATION (WASM / Precompiles)
+// =========================================================================
+
+#[cfg(not(feature = "std"))]
+pub mod stylus_impl {
+    use super::*;
+    use crate::backend::PrecompileBackend;
+    use stylus_sdk::call::StaticCallContext;
+
+    pub fn verify<S: StaticCallContext + Copy>(
+        context: S,
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+        vk_bytes: &[u8],
+    ) -> Result<bool> {
+        generic::verify(&PrecompileBackend::new(context), proof_bytes, public_inputs_bytes, vk_bytes)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+pub use stylus_impl::verify;
+
+// =========================================================================
+// HOST IMPLEMENTATION (CLI / Tests using arkworks)
+// =========================================================================
+
+#[cfg(feature = "std")]
+pub mod host_impl {
+    use super::*;
+    use crate::groth16::codec;
+    use ark_bn254::{Bn254, Fr, G1Affine};
+    use ark_ec::{pairing::Pairing, CurveGroup};
+    use ark_ff::{One, PrimeField};
+    use ark_groth16::Groth16;
+
+    /// Verify with arkworks: the proof of knowledge as one multi-pairing, the
+    /// Groth16 equation through ark-groth16 with the commitments folded into
+    /// the prepared inputs
+    pub fn verify_host(
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+        vk_bytes: &[u8],
+    ) -> Result<bool> {
+        let vk = VerifyingKey::decode(vk_bytes)?;
+        let proof = Proof::decode(proof_bytes)?;
+        let raw_inputs = inputs_with_commitments(&proof, public_inputs_bytes, &vk)?;
+        let inputs: Vec<Fr> = raw_inputs.iter().map(u256_to_fr).collect();
+
+        let g1 = |p: &[u8]| codec::g1_to_ark(p).ok_or(Error::InvalidProof);
+        let g2 = |p: &[u8]| codec::g2_to_ark(p).ok_or(Error::InvalidProof);
+        let commitments = proof.commitments.iter().map(|d| g1(d)).collect::<Result<Vec<G1Affine>>>()?;
+
+        if let Some(first) = vk.commitment_keys.first() {
+            let challenge = u256_to_fr(&commitment_challenge(&raw_inputs[vk.num_inputs()..]));
+
+            let mut g1_points = vec![g1(&proof.commitment_pok)?];
+            let mut g2_points = vec![g2(&first.g)?];
+            let mut weight = Fr::one();
+            for (commitment, key) in commitments.iter().zip(&vk.commitment_keys) {
+                g1_points.push((*commitment * weight).into_affine());
+                g2_points.push(g2(&key.g_root_sigma_neg)?);
+                weight *= challenge;
+            }
+            if !Bn254::multi_pairing(g1_points, g2_points).0.is_one() {
+                return Ok(false);
+            }
+        }
+
+        let ark_vk = ark_groth16::VerifyingKey::<Bn254> {
+            alpha_g1: g1(&vk.alpha)?,
+            beta_g2: g2(&vk.beta)?,
+            gamma_g2: g2(&vk.gamma)?,
+            delta_g2: g2(&vk.delta)?,
+            gamma_abc_g1: vk.k.iter().map(|p| g1(p)).collect::<Result<Vec<_>>>()?,
+        };
+        let pvk = ark_groth16::prepare_verifying_key(&ark_vk);
+        let mut prepared_inputs =
+            Groth16::<Bn254>::prepare_inputs(&pvk, &inputs).map_err(|_| Error::InvalidInputs)?;
+        for commitment in &commitments {
+            prepared_inputs += commitment;
+        }
+
+        let ark_proof = ark_groth16::Proof::<Bn254> {
+            a: g1(&proof.a)?,
+            b: g2(&proof.b)?,
+            c: g1(&proof.c)?,
+        };
+        Groth16::<Bn254>::verify_proof_with_prepared_inputs(&pvk, &ark_proof, &prepared_inputs)
+            .map_err(|_| Error::VerificationFailed)
+    }
+
+    fn u256_to_fr(value: &U256) -> Fr {
+        Fr::from_be_bytes_mod_order(&value.to_be_bytes::<32>())
+    }
+}
+
+#[cfg(feature = "std")]
+pub use host_impl::verify_host;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn hex(bytes: &[u8]) -> alloc::string::String {
+        bytes.iter().map(|b| alloc::format!("{:02x}", b)).collect()
+    }
+
+    /// RFC 9380 Appendix K.1 (expand_message_xmd, SHA-256)
+    #[test]
+    fn test_expand_message_xmd_vectors() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        assert_eq!(
+            hex(&expand_message_xmd(b"", dst, 0x20)),
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"
+        );
+        assert_eq!(
+            hex(&expand_message_xmd(b"abc", dst, 0x20)),
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"
+        );
+        assert_eq!(expand_message_xmd(b"abc", dst, HASH_TO_FIELD_SIZE).len(), HASH_TO_FIELD_SIZE);
+    }
+
+    #[test]
+    fn test_hash_to_field_is_reduced() {
+        for msg in [&b""[..], b"abc", &[0xff; 96]] {
+            assert!(hash_to_field(msg, COMMITMENT_DST) < BN254_SCALAR_MODULUS);
+        }
+        assert_ne!(hash_to_field(b"abc", COMMITMENT_DST), hash_to_field(b"abc", CHALLENGE_DST));
+    }
+
+    #[test]
+    fn test_inputs_from_witness() {
+        let mut witness = vec![0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 2];
+        witness.extend_from_slice(&[1u8; 64]);
+        assert_eq!(inputs_from_witness(&witness), Ok(&[1u8; 64][..]));
+
+        // Full (secret-bearing) witness and length mismatch
+        let mut full = witness.clone();
+        full[7] = 1;
+        assert_eq!(inputs_from_witness(&full), Err(Error::InvalidInputs));
+        assert_eq!(inputs_from_witness(&witness[..witness.len() - 1]), Err(Error::InvalidInputs));
+    }
+
+    #[test]
+    fn test_reader_rejects_malformed_points() {
+        // Compressed infinity with a non-zero tail
+        let mut bytes = [0u8; 32];
+        bytes[0] = FLAG_COMPRESSED_INFINITY;
+        bytes[31] = 1;
+        assert_eq!(Reader::new(&bytes, Error::InvalidProof).g1(), Err(Error::InvalidProof));
+
+        // Compressed x >= p
+        let mut bytes = [0xffu8; 32];
+        bytes[0] = FLAG_COMPRESSED_LARGEST | 0x3f;
+        assert_eq!(Reader::new(&bytes, Error::InvalidProof).g1(), Err(Error::G1NotOnCurve));
+
+        // Truncated uncompressed point
+        assert_eq!(Reader::new(&[0u8; 63], Error::InvalidProof).g1(), Err(Error::InvalidProof));
+    }
+}
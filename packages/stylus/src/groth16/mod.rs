@@ -0,0 +1,1004 @@
+//! Groth16 Verifier
+//!
+//! Dual-mode implementation:
+//! 1. WASM (Stylus): Uses Arbitrum BN256 precompiles (0x06, 0x07, 0x08) via `generic`
+//! 2. Host (CLI): Uses arkworks (ark-groth16) for pure Rust verification
+//!
+//! Both modes take the same canonical encoding of proof, VK and public inputs
+//! (uncompressed EVM layout); see `codec` for the format and for conversions from
+//! arkworks-compressed and snarkjs artifacts.
+//!
+//! Proofs may instead be submitted compressed (`COMPRESSED_PROOF_SIZE` bytes);
+//! `decode_proof` decompresses them in-contract, after which they go through
+//! exactly the same validation and pairing check.
+//!
+//! # Precomputed Verification Keys
+//! `compute_precomputed_pairing` turns a VK into a `PreparedVerifyingKey` once, at
+//! registration time. The prepared form caches everything that does not depend on
+//! the proof: the pairing-ready (α, -β) pair, -γ, -δ and the IC bases. Verification
+//! through `verify_with_precomputed` then skips VK parsing and the G2 negations.
+//!
+//! ## Precomputed Layout
+//! ```text
+//! [version: 1 byte]
+//! [vk_hash: 32 bytes]          keccak256 of the VK bytes the cache was built from
+//! [alpha || -beta: 192 bytes]  pairing-ready (G1, G2) pair
+//! [-gamma: 128 bytes]
+//! [-delta: 128 bytes]
+//! [ic: 64 * (n + 1) bytes]
+//! ```
+//!
+//! # Batch Verification
+//! Proofs sharing a VK are checked with one random linear combination:
+//! ```text
+//! prod_i e(r_i·A_i, B_i) · e(Σr_i·α, -β) · e(Σr_i·L_i, -γ) · e(Σr_i·C_i, -δ) == 1
+//! ```
+//! The weights r_i are 128-bit scalars squeezed from a Keccak transcript over the
+//! VK hash and every proof and input, so a prover cannot pick proofs whose errors
+//! cancel. Σr_i·L_i collapses into a single MSM over the IC bases. If the combined
+//! check fails, each proof is re-verified alone to report per-proof results.
+//!
+//! # Curve Backends
+//! The verifier itself lives in `generic` and is written against
+//! `crate::backend::Bn254Backend`. `stylus_impl` runs it on the precompiles;
+//! with `ArkBackend` the same code runs anywhere (tests, host, chains without
+//! 0x06-0x08). `host_impl` remains an independent arkworks reference.
+//!
+//! # MSM Backends
+//! The IC accumulation (and the batch MSMs) run either through the curve
+//! backend's add/mul or natively in WASM (`msm`); `MsmBackend::Auto`, the
+//! default, picks whichever is estimated cheaper for the number of terms.
+
+use alloc::vec::Vec;
+use sha3::{Digest, Keccak256};
+use stylus_sdk::alloy_primitives::U256;
+use crate::utils::{fr_add, fr_mul, BN254_BASE_MODULUS};
+
+pub mod bls12_381;
+pub mod codec;
+pub mod curve;
+pub mod gnark;
+pub mod msm;
+
+pub use msm::MsmBackend;
+
+// =========================================================================
+// SHARED TYPES
+// =========================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    InvalidProof,
+    InvalidInputs,
+    VerificationFailed,
+    PrecompileFailed,
+    InvalidVerificationKey,
+    DeserializationError,
+    /// Public input is not a canonical scalar (>= r)
+    ScalarOutOfRange,
+    /// G1 point has a coordinate >= p or is not on the curve
+    G1NotOnCurve,
+    /// G2 point has a coordinate >= p or is not on the twist
+    G2NotOnCurve,
+    /// G2 point is on the twist but outside the order-r subgroup
+    G2NotInSubgroup,
+    /// Verification key contains the point at infinity
+    IdentityPoint,
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+impl From<crate::backend::BackendError> for Error {
+    fn from(_: crate::backend::BackendError) -> Self {
+        Error::PrecompileFailed
+    }
+}
+
+/// Uncompressed proof size: A (64) + B (128) + C (64)
+pub const PROOF_SIZE: usize = 256;
+
+/// Compressed proof size: A (32) + B (64) + C (32)
+pub const COMPRESSED_PROOF_SIZE: usize = 128;
+
+/// VK header size: alpha (64) + beta (128) + gamma (128) + delta (128)
+pub const VK_HEADER_SIZE: usize = 448;
+
+/// Current version of the precomputed VK layout
+pub const PRECOMPUTED_VERSION: u8 = 1;
+
+/// Domain separator for the batch weight transcript
+pub const BATCH_DOMAIN: &[u8] = b"uzkv_groth16_batch_v1";
+
+/// Precomputed header size: version (1) + vk_hash (32) + (α, -β) (192) + -γ (128) + -δ (128)
+const PRECOMPUTED_HEADER_SIZE: usize = 1 + 32 + 192 + 128 + 128;
+
+/// Verification key prepared for repeated verification
+///
+/// Holds the proof-independent parts of the pairing input in the exact byte
+/// layout expected by the pairing precompile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreparedVerifyingKey {
+    /// keccak256 of the VK bytes this key was prepared from
+    pub vk_hash: [u8; 32],
+    /// alpha (G1) followed by -beta (G2)
+    pub alpha_neg_beta: [u8; 192],
+    /// -gamma (G2)
+    pub neg_gamma: [u8; 128],
+    /// -delta (G2)
+    pub neg_delta: [u8; 128],
+    /// IC bases: IC_0 followed by one base per public input
+    pub ic: Vec<[u8; 64]>,
+}
+
+impl PreparedVerifyingKey {
+    /// Prepare a VK from its uncompressed byte encoding
+    ///
+    /// The VK is fully validated (see `validate_vk`), so a stored prepared key
+    /// never needs re-checking.
+    pub fn from_vk_bytes(vk_bytes: &[u8]) -> Result<Self> {
+        validate_vk(vk_bytes)?;
+
+        let mut alpha_neg_beta = [0u8; 192];
+        alpha_neg_beta[0..64].copy_from_slice(&vk_bytes[0..64]);
+        alpha_neg_beta[64..192].copy_from_slice(&negate_g2(&vk_bytes[64..192])?);
+
+        let ic = vk_bytes[VK_HEADER_SIZE..]
+            .chunks(64)
+            .map(|chunk| {
+                let mut point = [0u8; 64];
+                point.copy_from_slice(chunk);
+                point
+            })
+            .collect();
+
+        Ok(Self {
+            vk_hash: keccak256(vk_bytes),
+            alpha_neg_beta,
+            neg_gamma: negate_g2(&vk_bytes[192..320])?,
+            neg_delta: negate_g2(&vk_bytes[320..448])?,
+            ic,
+        })
+    }
+
+    /// Number of public inputs this key expects
+    pub fn num_inputs(&self) -> usize {
+        self.ic.len() - 1
+    }
+
+    /// Serialize to the precomputed storage layout
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(PRECOMPUTED_HEADER_SIZE + self.ic.len() * 64);
+        buf.push(PRECOMPUTED_VERSION);
+        buf.extend_from_slice(&self.vk_hash);
+        buf.extend_from_slice(&self.alpha_neg_beta);
+        buf.extend_from_slice(&self.neg_gamma);
+        buf.extend_from_slice(&self.neg_delta);
+        for point in &self.ic {
+            buf.extend_from_slice(point);
+        }
+        buf
+    }
+
+    /// Deserialize from the precomputed storage layout
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < PRECOMPUTED_HEADER_SIZE + 64
+            || (bytes.len() - PRECOMPUTED_HEADER_SIZE) % 64 != 0
+        {
+            return Err(Error::DeserializationError);
+        }
+        if bytes[0] != PRECOMPUTED_VERSION {
+            return Err(Error::DeserializationError);
+        }
+
+        let mut offset = 1;
+        let mut vk_hash = [0u8; 32];
+        vk_hash.copy_from_slice(&bytes[offset..offset + 32]);
+        offset += 32;
+        let mut alpha_neg_beta = [0u8; 192];
+        alpha_neg_beta.copy_from_slice(&bytes[offset..offset + 192]);
+        offset += 192;
+        let mut neg_gamma = [0u8; 128];
+        neg_gamma.copy_from_slice(&bytes[offset..offset + 128]);
+        offset += 128;
+        let mut neg_delta = [0u8; 128];
+        neg_delta.copy_from_slice(&bytes[offset..offset + 128]);
+        offset += 128;
+
+        let ic = bytes[offset..]
+            .chunks(64)
+            .map(|chunk| {
+                let mut point = [0u8; 64];
+                point.copy_from_slice(chunk);
+                point
+            })
+            .collect();
+
+        Ok(Self { vk_hash, alpha_neg_beta, neg_gamma, neg_delta, ic })
+    }
+
+    /// Decode a precomputed key and check it was built from `vk_bytes`
+    ///
+    /// Prevents a stale or foreign cache from being paired with a VK.
+    pub fn decode_for_vk(bytes: &[u8], vk_bytes: &[u8]) -> Result<Self> {
+        let prepared = Self::decode(bytes)?;
+        if prepared.vk_hash != keccak256(vk_bytes) {
+            return Err(Error::InvalidVerificationKey);
+        }
+        Ok(prepared)
+    }
+}
+
+/// Validate an uncompressed VK: layout, every point on its curve, G2 points in
+/// the prime-order subgroup, and no point at infinity
+pub fn validate_vk(vk_bytes: &[u8]) -> Result<()> {
+    if vk_bytes.len() < VK_HEADER_SIZE + 64 || (vk_bytes.len() - VK_HEADER_SIZE) % 64 != 0 {
+        return Err(Error::InvalidVerificationKey);
+    }
+    let g1_points = core::iter::once(&vk_bytes[0..64]).chain(vk_bytes[VK_HEADER_SIZE..].chunks(64));
+    for point in g1_points {
+        if curve::validate_g1(point)? {
+            return Err(Error::IdentityPoint);
+        }
+    }
+    for point in vk_bytes[64..VK_HEADER_SIZE].chunks(128) {
+        if curve::validate_g2(point)? {
+            return Err(Error::IdentityPoint);
+        }
+    }
+    Ok(())
+}
+
+/// Validate an uncompressed proof: A and C on G1, B on G2 and in the subgroup
+pub fn validate_proof(proof_bytes: &[u8]) -> Result<()> {
+    if proof_bytes.len() != PROOF_SIZE {
+        return Err(Error::InvalidProof);
+    }
+    curve::validate_g1(&proof_bytes[0..64])?;
+    curve::validate_g2(&proof_bytes[64..192])?;
+    curve::validate_g1(&proof_bytes[192..256])?;
+    Ok(())
+}
+
+/// Decode a compressed or uncompressed proof into the validated canonical encoding
+pub fn decode_proof(proof_bytes: &[u8]) -> Result<[u8; PROOF_SIZE]> {
+    let proof = match proof_bytes.len() {
+        COMPRESSED_PROOF_SIZE => codec::decompress_proof(proof_bytes)?,
+        PROOF_SIZE => {
+            let mut proof = [0u8; PROOF_SIZE];
+            proof.copy_from_slice(proof_bytes);
+            proof
+        }
+        _ => return Err(Error::InvalidProof),
+    };
+    validate_proof(&proof)?;
+    Ok(proof)
+}
+
+/// Validate public inputs against the expected count and parse them as scalars < r
+pub fn validate_inputs(public_inputs_bytes: &[u8], expected: usize) -> Result<Vec<U256>> {
+    if public_inputs_bytes.len() % 32 != 0 || public_inputs_bytes.len() / 32 != expected {
+        return Err(Error::InvalidInputs);
+    }
+    public_inputs_bytes.chunks(32).map(curve::validate_scalar).collect()
+}
+
+/// Compute the precomputed VK data stored at registration time
+pub fn compute_precomputed_pairing(vk_bytes: &[u8]) -> Result<Vec<u8>> {
+    Ok(PreparedVerifyingKey::from_vk_bytes(vk_bytes)?.encode())
+}
+
+/// Load a prepared VK, preferring the registration-time cache when present
+pub fn prepare_vk(vk_bytes: &[u8], precomputed: &[u8]) -> Result<PreparedVerifyingKey> {
+    if precomputed.is_empty() {
+        PreparedVerifyingKey::from_vk_bytes(vk_bytes)
+    } else {
+        PreparedVerifyingKey::decode_for_vk(precomputed, vk_bytes)
+    }
+}
+
+/// Derive the batch weights r_i from a transcript over the whole batch
+///
+/// Every byte of every proof and input is absorbed (length-prefixed) before any
+/// weight is squeezed. Weights are 128-bit and never zero.
+pub fn batch_weights(vk_hash: &[u8; 32], proofs: &[Vec<u8>], inputs: &[Vec<u8>]) -> Vec<U256> {
+    let mut hasher = Keccak256::new();
+    hasher.update(BATCH_DOMAIN);
+    hasher.update(vk_hash);
+    hasher.update((proofs.len() as u64).to_le_bytes());
+    for (proof, input) in proofs.iter().zip(inputs) {
+        hasher.update((proof.len() as u64).to_le_bytes());
+        hasher.update(proof);
+        hasher.update((input.len() as u64).to_le_bytes());
+        hasher.update(input);
+    }
+    let seed: [u8; 32] = hasher.finalize().into();
+
+    (0..proofs.len() as u64)
+        .map(|i| {
+            let mut hasher = Keccak256::new();
+            hasher.update(seed);
+            hasher.update(i.to_le_bytes());
+            let digest: [u8; 32] = hasher.finalize().into();
+            let weight = U256::from_be_slice(&digest[16..32]);
+            if weight == U256::ZERO { U256::from(1) } else { weight }
+        })
+        .collect()
+}
+
+/// MSM scalars for Σr_i·L_i over the IC bases
+///
+/// Returns `[Σr_i, Σr_i·x_i1, ..., Σr_i·x_in]` reduced mod r, where `x_ij` is the
+/// j-th public input of proof i. Inputs must already be length-checked.
+pub fn batch_ic_scalars(weights: &[U256], inputs: &[Vec<u8>], num_inputs: usize) -> Vec<U256> {
+    let mut scalars = alloc::vec![U256::ZERO; num_inputs + 1];
+    for (weight, input) in weights.iter().zip(inputs) {
+        scalars[0] = fr_add(scalars[0], *weight);
+        for (j, chunk) in input.chunks(32).enumerate() {
+            let x = U256::from_be_slice(chunk);
+            scalars[j + 1] = fr_add(scalars[j + 1], fr_mul(*weight, x));
+        }
+    }
+    scalars
+}
+
+/// Decode every proof and validate every input set in a batch
+fn decode_batch(
+    proofs: &[Vec<u8>],
+    inputs: &[Vec<u8>],
+    num_inputs: usize,
+) -> Result<Vec<[u8; PROOF_SIZE]>> {
+    proofs
+        .iter()
+        .zip(inputs)
+        .map(|(proof, input)| {
+            let proof = decode_proof(proof)?;
+            validate_inputs(input, num_inputs)?;
+            Ok(proof)
+        })
+        .collect()
+}
+
+/// Negate an uncompressed G2 point: (x, y) -> (x, -y)
+///
+/// Coordinates must be canonical (< p); the identity (all zeros) maps to itself.
+fn negate_g2(p: &[u8]) -> Result<[u8; 128]> {
+    let mut output = [0u8; 128];
+    output.copy_from_slice(p);
+
+    for offset in [64, 96] {
+        let y = U256::from_be_slice(&p[offset..offset + 32]);
+        if y >= BN254_BASE_MODULUS {
+            return Err(Error::InvalidVerificationKey);
+        }
+        let neg_y = if y == U256::ZERO { U256::ZERO } else { BN254_BASE_MODULUS - y };
+        output[offset..offset + 32].copy_from_slice(&neg_y.to_be_bytes::<32>());
+    }
+    Ok(output)
+}
+
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+// =========================================================================
+// GENERIC IMPLEMENTATION (any Bn254Backend)
+// =========================================================================
+
+/// The on-chain verifier, written against `Bn254Backend`
+///
+/// The Stylus entry points run this with `PrecompileBackend`; with `ArkBackend`
+/// the identical code runs in cargo tests, host binaries and on chains without
+/// the BN254 precompiles.
+pub mod generic {
+    use super::*;
+    use crate::backend::Bn254Backend;
+
+    pub fn verify<B: Bn254Backend>(
+        curve: &B,
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+        vk_bytes: &[u8],
+    ) -> Result<bool> {
+        let prepared = PreparedVerifyingKey::from_vk_bytes(vk_bytes)?;
+        verify_prepared(curve, proof_bytes, public_inputs_bytes, &prepared)
+    }
+
+    /// Verify using the precomputed VK data stored at registration
+    pub fn verify_with_precomputed<B: Bn254Backend>(
+        curve: &B,
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+        vk_bytes: &[u8],
+        precomputed: &[u8],
+    ) -> Result<bool> {
+        let prepared = PreparedVerifyingKey::decode_for_vk(precomputed, vk_bytes)?;
+        verify_prepared(curve, proof_bytes, public_inputs_bytes, &prepared)
+    }
+
+    /// Verify a proof against a prepared VK
+    pub fn verify_prepared<B: Bn254Backend>(
+        curve: &B,
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+        prepared: &PreparedVerifyingKey,
+    ) -> Result<bool> {
+        verify_prepared_with_msm(curve, proof_bytes, public_inputs_bytes, prepared, MsmBackend::Auto)
+    }
+
+    /// `verify_prepared` with an explicit backend for the IC accumulation
+    pub fn verify_prepared_with_msm<B: Bn254Backend>(
+        curve: &B,
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+        prepared: &PreparedVerifyingKey,
+        msm_backend: MsmBackend,
+    ) -> Result<bool> {
+        // 1. Parsing and validation (decompressing if needed)
+        let proof = decode_proof(proof_bytes)?;
+        let inputs = validate_inputs(public_inputs_bytes, prepared.num_inputs())?;
+        let a = &proof[0..64];
+        let b = &proof[64..192];
+        let c = &proof[192..256];
+
+        // 2. Compute Linear Combination L
+        // L = IC_0 + sum(input[i] * IC[i+1])
+        let mut scalars = Vec::with_capacity(inputs.len() + 1);
+        scalars.push(U256::from(1));
+        scalars.extend(inputs);
+        let l = g1_msm(curve, &prepared.ic, &scalars, msm_backend)?;
+
+        // 3. Pairing Check
+        // e(A, B) * e(α, -β) * e(L, -γ) * e(C, -δ) == 1
+        let mut pairing_input = Vec::with_capacity(768);
+        pairing_input.extend_from_slice(a);
+        pairing_input.extend_from_slice(b);
+        pairing_input.extend_from_slice(&prepared.alpha_neg_beta);
+        pairing_input.extend_from_slice(&l);
+        pairing_input.extend_from_slice(&prepared.neg_gamma);
+        pairing_input.extend_from_slice(c);
+        pairing_input.extend_from_slice(&prepared.neg_delta);
+
+        Ok(curve.pairing_check(&pairing_input)?)
+    }
+
+    /// Batch-verify proofs sharing one VK
+    ///
+    /// Runs the combined check first; if it does not pass, every proof is verified
+    /// alone so the result still says which proofs are valid.
+    pub fn batch_verify<B: Bn254Backend>(
+        curve: &B,
+        proofs: &[Vec<u8>],
+        inputs: &[Vec<u8>],
+        vk_bytes: &[u8],
+        precomputed: &[u8],
+    ) -> Result<Vec<bool>> {
+        if proofs.len() != inputs.len() {
+            return Err(Error::InvalidInputs);
+        }
+        let prepared = prepare_vk(vk_bytes, precomputed)?;
+
+        if proofs.len() > 1
+            && matches!(verify_batch_prepared(curve, proofs, inputs, &prepared), Ok(true))
+        {
+            return Ok(alloc::vec![true; proofs.len()]);
+        }
+
+        Ok(proofs
+            .iter()
+            .zip(inputs)
+            .map(|(proof, input)| {
+                matches!(verify_prepared(curve, proof, input, &prepared), Ok(true))
+            })
+            .collect())
+    }
+
+    /// Combined random-linear-combination check with a single pairing call
+    ///
+    /// Returns `Ok(true)` only if every proof in the batch is valid (except with
+    /// probability ~N/2^128).
+    pub fn verify_batch_prepared<B: Bn254Backend>(
+        curve: &B,
+        proofs: &[Vec<u8>],
+        inputs: &[Vec<u8>],
+        prepared: &PreparedVerifyingKey,
+    ) -> Result<bool> {
+        if proofs.len() != inputs.len() {
+            return Err(Error::InvalidInputs);
+        }
+        let decoded = decode_batch(proofs, inputs, prepared.num_inputs())?;
+
+        let weights = batch_weights(&prepared.vk_hash, proofs, inputs);
+
+        // Σr_i·L_i as one MSM over the IC bases
+        let ic_scalars = batch_ic_scalars(&weights, inputs, prepared.num_inputs());
+        let l_acc = g1_msm(curve, &prepared.ic, &ic_scalars, MsmBackend::Auto)?;
+
+        // Σr_i·α
+        let alpha_acc = curve.g1_mul(&prepared.alpha_neg_beta[0..64], ic_scalars[0])?;
+
+        // Σr_i·C_i
+        let c_points: Vec<[u8; 64]> = decoded
+            .iter()
+            .map(|proof| {
+                let mut point = [0u8; 64];
+                point.copy_from_slice(&proof[192..256]);
+                point
+            })
+            .collect();
+        let c_acc = g1_msm(curve, &c_points, &weights, MsmBackend::Auto)?;
+
+        let mut pairing_input = Vec::with_capacity(192 * (proofs.len() + 3));
+        for (proof, weight) in decoded.iter().zip(&weights) {
+            pairing_input.extend_from_slice(&curve.g1_mul(&proof[0..64], *weight)?);
+            pairing_input.extend_from_slice(&proof[64..192]);
+        }
+        pairing_input.extend_from_slice(&alpha_acc);
+        pairing_input.extend_from_slice(&prepared.alpha_neg_beta[64..192]);
+        pairing_input.extend_from_slice(&l_acc);
+        pairing_input.extend_from_slice(&prepared.neg_gamma);
+        pairing_input.extend_from_slice(&c_acc);
+        pairing_input.extend_from_slice(&prepared.neg_delta);
+
+        Ok(curve.pairing_check(&pairing_input)?)
+    }
+
+    /// Multi-scalar multiplication sum(scalars[i] * bases[i])
+    ///
+    /// On the backend path zero scalars are skipped and unit scalars are added
+    /// without a `g1_mul`.
+    pub(super) fn g1_msm<B: Bn254Backend>(
+        curve: &B,
+        bases: &[[u8; 64]],
+        scalars: &[U256],
+        msm_backend: MsmBackend,
+    ) -> Result<[u8; 64]> {
+        let terms = scalars.iter().filter(|s| **s != U256::ZERO).count();
+        if msm_backend.use_native(terms) {
+            return msm::msm(bases, scalars);
+        }
+
+        let mut acc = [0u8; 64];
+        for (base, scalar) in bases.iter().zip(scalars) {
+            if *scalar == U256::ZERO {
+                continue;
+            }
+            let term = if *scalar == U256::from(1) {
+                *base
+            } else {
+                curve.g1_mul(base, *scalar)?
+            };
+            acc = curve.g1_add(&acc, &term)?;
+        }
+        Ok(acc)
+    }
+}
+
+// =========================================================================
+// STYLUS IMPLEMENTATION (WASM / Precompiles)
+// =========================================================================
+
+#[cfg(not(feature = "std"))]
+pub mod stylus_impl {
+    use super::*;
+    use crate::backend::PrecompileBackend;
+    use stylus_sdk::call::StaticCallContext;
+
+    pub fn verify<S: StaticCallContext + Copy>(
+        context: S,
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+        vk_bytes: &[u8],
+    ) -> Result<bool> {
+        generic::verify(&PrecompileBackend::new(context), proof_bytes, public_inputs_bytes, vk_bytes)
+    }
+
+    /// Verify using the precomputed VK data stored at registration
+    pub fn verify_with_precomputed<S: StaticCallContext + Copy>(
+        context: S,
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+        vk_bytes: &[u8],
+        precomputed: &[u8],
+    ) -> Result<bool> {
+        generic::verify_with_precomputed(
+            &PrecompileBackend::new(context),
+            proof_bytes,
+            public_inputs_bytes,
+            vk_bytes,
+            precomputed,
+        )
+    }
+
+    /// Verify a proof against a prepared VK
+    pub fn verify_prepared<S: StaticCallContext + Copy>(
+        context: S,
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+        prepared: &PreparedVerifyingKey,
+    ) -> Result<bool> {
+        generic::verify_prepared(&PrecompileBackend::new(context), proof_bytes, public_inputs_bytes, prepared)
+    }
+
+    /// `verify_prepared` with an explicit backend for the IC accumulation
+    pub fn verify_prepared_with_backend<S: StaticCallContext + Copy>(
+        context: S,
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+        prepared: &PreparedVerifyingKey,
+        backend: MsmBackend,
+    ) -> Result<bool> {
+        generic::verify_prepared_with_msm(
+            &PrecompileBackend::new(context),
+            proof_bytes,
+            public_inputs_bytes,
+            prepared,
+            backend,
+        )
+    }
+
+    /// Batch-verify proofs sharing one VK (see `generic::batch_verify`)
+    pub fn batch_verify<S: StaticCallContext + Copy>(
+        context: S,
+        proofs: &[Vec<u8>],
+        inputs: &[Vec<u8>],
+        vk_bytes: &[u8],
+        precomputed: &[u8],
+    ) -> Result<Vec<bool>> {
+        generic::batch_verify(&PrecompileBackend::new(context), proofs, inputs, vk_bytes, precomputed)
+    }
+
+    /// Combined random-linear-combination check with a single pairing call
+    pub fn verify_batch_prepared<S: StaticCallContext + Copy>(
+        context: S,
+        proofs: &[Vec<u8>],
+        inputs: &[Vec<u8>],
+        prepared: &PreparedVerifyingKey,
+    ) -> Result<bool> {
+        generic::verify_batch_prepared(&PrecompileBackend::new(context), proofs, inputs, prepared)
+    }
+}
+
+// Re-export specific Stylus functions when compiling for WASM
+#[cfg(not(feature = "std"))]
+pub use stylus_impl::{batch_verify, verify, verify_with_precomputed};
+
+// =========================================================================
+// HOST IMPLEMENTATION (CLI / Tests using arkworks)
+// =========================================================================
+
+#[cfg(feature = "std")]
+pub mod host_impl {
+    use super::*;
+    use ark_bn254::{Bn254, Fr, G1Affine, G1Projective};
+    use ark_ec::{pairing::Pairing, CurveGroup, VariableBaseMSM};
+    use ark_ff::{One, PrimeField};
+    use ark_groth16::Groth16;
+    use ark_snark::SNARK;
+
+    /// Verify a proof in the canonical encoding using arkworks
+    pub fn verify_host(
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+        vk_bytes: &[u8],
+    ) -> Result<bool> {
+        // Same checks, and the same errors, as the Stylus path
+        validate_vk(vk_bytes)?;
+        let proof = decode_proof(proof_bytes)?;
+
+        // 1. Deserialize Proof
+        let proof = codec::proof_to_ark(&proof)?;
+
+        // 2. Deserialize VK
+        let vk = codec::vk_to_ark(vk_bytes)?;
+
+        // 3. Deserialize Public Inputs
+        validate_inputs(public_inputs_bytes, vk.gamma_abc_g1.len() - 1)?;
+        let inputs = codec::inputs_to_ark(public_inputs_bytes)?;
+
+        // 4. Verify using arkworks
+        match Groth16::<Bn254>::verify(&vk, &inputs, &proof) {
+            Ok(valid) => Ok(valid),
+            Err(_) => Err(Error::VerificationFailed),
+        }
+    }
+
+    /// Host twin of `stylus_impl::verify_with_precomputed`
+    ///
+    /// Consumes the same proof encodings, inputs and precomputed layout, and
+    /// evaluates the same four-pair product with arkworks instead of 0x08.
+    pub fn verify_with_precomputed_host(
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+        vk_bytes: &[u8],
+        precomputed: &[u8],
+    ) -> Result<bool> {
+        let prepared = PreparedVerifyingKey::decode_for_vk(precomputed, vk_bytes)?;
+        verify_prepared_host(proof_bytes, public_inputs_bytes, &prepared)
+    }
+
+    /// Verify a proof against a prepared VK using arkworks
+    pub fn verify_prepared_host(
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+        prepared: &PreparedVerifyingKey,
+    ) -> Result<bool> {
+        let proof = decode_proof(proof_bytes)?;
+        validate_inputs(public_inputs_bytes, prepared.num_inputs())?;
+        let a = codec::g1_to_ark(&proof[0..64]).ok_or(Error::InvalidProof)?;
+        let b = codec::g2_to_ark(&proof[64..192]).ok_or(Error::InvalidProof)?;
+        let c = codec::g1_to_ark(&proof[192..256]).ok_or(Error::InvalidProof)?;
+
+        let invalid_vk = Error::InvalidVerificationKey;
+        let alpha = codec::g1_to_ark(&prepared.alpha_neg_beta[0..64]).ok_or(invalid_vk)?;
+        let neg_beta = codec::g2_to_ark(&prepared.alpha_neg_beta[64..192]).ok_or(invalid_vk)?;
+        let neg_gamma = codec::g2_to_ark(&prepared.neg_gamma).ok_or(invalid_vk)?;
+        let neg_delta = codec::g2_to_ark(&prepared.neg_delta).ok_or(invalid_vk)?;
+
+        // L = IC_0 + sum(input[i] * IC[i+1])
+        let ic = parse_ic(prepared)?;
+        let mut scalars = Vec::with_capacity(ic.len());
+        scalars.push(Fr::one());
+        scalars.extend(public_inputs_bytes.chunks(32).map(Fr::from_be_bytes_mod_order));
+        let l = G1Projective::msm(&ic, &scalars).map_err(|_| Error::InvalidInputs)?;
+
+        let result = Bn254::multi_pairing(
+            [a, alpha, l.into_affine(), c],
+            [b, neg_beta, neg_gamma, neg_delta],
+        );
+        Ok(result.0.is_one())
+    }
+
+    /// Host twin of `stylus_impl::batch_verify`
+    pub fn batch_verify_host(
+        proofs: &[Vec<u8>],
+        inputs: &[Vec<u8>],
+        vk_bytes: &[u8],
+        precomputed: &[u8],
+    ) -> Result<Vec<bool>> {
+        if proofs.len() != inputs.len() {
+            return Err(Error::InvalidInputs);
+        }
+        let prepared = prepare_vk(vk_bytes, precomputed)?;
+
+        if proofs.len() > 1 && matches!(verify_batch_prepared_host(proofs, inputs, &prepared), Ok(true)) {
+            return Ok(vec![true; proofs.len()]);
+        }
+
+        Ok(proofs
+            .iter()
+            .zip(inputs)
+            .map(|(proof, input)| matches!(verify_prepared_host(proof, input, &prepared), Ok(true)))
+            .collect())
+    }
+
+    /// Host twin of `stylus_impl::verify_batch_prepared`: the same N + 3 pairs in
+    /// one multi-pairing
+    pub fn verify_batch_prepared_host(
+        proofs: &[Vec<u8>],
+        inputs: &[Vec<u8>],
+        prepared: &PreparedVerifyingKey,
+    ) -> Result<bool> {
+        if proofs.len() != inputs.len() {
+            return Err(Error::InvalidInputs);
+        }
+        let decoded = decode_batch(proofs, inputs, prepared.num_inputs())?;
+
+        let raw_weights = batch_weights(&prepared.vk_hash, proofs, inputs);
+        let weights: Vec<Fr> = raw_weights.iter().map(u256_to_fr).collect();
+        let ic_scalars: Vec<Fr> = batch_ic_scalars(&raw_weights, inputs, prepared.num_inputs())
+            .iter()
+            .map(u256_to_fr)
+            .collect();
+
+        let invalid_vk = Error::InvalidVerificationKey;
+        let alpha = codec::g1_to_ark(&prepared.alpha_neg_beta[0..64]).ok_or(invalid_vk)?;
+        let neg_beta = codec::g2_to_ark(&prepared.alpha_neg_beta[64..192]).ok_or(invalid_vk)?;
+        let neg_gamma = codec::g2_to_ark(&prepared.neg_gamma).ok_or(invalid_vk)?;
+        let neg_delta = codec::g2_to_ark(&prepared.neg_delta).ok_or(invalid_vk)?;
+
+        let ic = parse_ic(prepared)?;
+        let l_acc = G1Projective::msm(&ic, &ic_scalars).map_err(|_| Error::InvalidInputs)?;
+
+        let mut g1_points = Vec::with_capacity(proofs.len() + 3);
+        let mut g2_points = Vec::with_capacity(proofs.len() + 3);
+        let mut c_points = Vec::with_capacity(proofs.len());
+        for (proof, weight) in decoded.iter().zip(&weights) {
+            let a = codec::g1_to_ark(&proof[0..64]).ok_or(Error::InvalidProof)?;
+            let b = codec::g2_to_ark(&proof[64..192]).ok_or(Error::InvalidProof)?;
+            c_points.push(codec::g1_to_ark(&proof[192..256]).ok_or(Error::InvalidProof)?);
+            g1_points.push((a * weight).into_affine());
+            g2_points.push(b);
+        }
+        let c_acc = G1Projective::msm(&c_points, &weights).map_err(|_| Error::InvalidProof)?;
+
+        g1_points.extend([
+            (alpha * ic_scalars[0]).into_affine(),
+            l_acc.into_affine(),
+            c_acc.into_affine(),
+        ]);
+        g2_points.extend([neg_beta, neg_gamma, neg_delta]);
+
+        Ok(Bn254::multi_pairing(g1_points, g2_points).0.is_one())
+    }
+
+    fn parse_ic(prepared: &PreparedVerifyingKey) -> Result<Vec<G1Affine>> {
+        prepared
+            .ic
+            .iter()
+            .map(|point| codec::g1_to_ark(point).ok_or(Error::InvalidVerificationKey))
+            .collect()
+    }
+
+    fn u256_to_fr(value: &U256) -> Fr {
+        Fr::from_be_bytes_mod_order(&value.to_be_bytes::<32>())
+    }
+}
+
+#[cfg(feature = "std")]
+pub use host_impl::{batch_verify_host, verify_host, verify_with_precomputed_host};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    use curve::tests::{g1_generator, g2_generator};
+
+    /// Structurally valid VK built from the generators
+    fn sample_vk(num_inputs: usize) -> Vec<u8> {
+        let mut vk = g1_generator().to_vec();
+        for _ in 0..3 {
+            vk.extend_from_slice(&g2_generator());
+        }
+        for _ in 0..=num_inputs {
+            vk.extend_from_slice(&g1_generator());
+        }
+        vk
+    }
+
+    #[test]
+    fn test_precomputed_roundtrip() {
+        let vk = sample_vk(2);
+        let encoded = compute_precomputed_pairing(&vk).unwrap();
+        assert_eq!(encoded.len(), PRECOMPUTED_HEADER_SIZE + 3 * 64);
+
+        let decoded = PreparedVerifyingKey::decode_for_vk(&encoded, &vk).unwrap();
+        assert_eq!(decoded.num_inputs(), 2);
+        assert_eq!(decoded.encode(), encoded);
+        assert_eq!(&decoded.alpha_neg_beta[0..64], &vk[0..64]);
+        assert_eq!(&decoded.ic[1][..], &vk[VK_HEADER_SIZE + 64..VK_HEADER_SIZE + 128]);
+    }
+
+    #[test]
+    fn test_precomputed_negates_g2_points() {
+        let vk = sample_vk(1);
+        let prepared = PreparedVerifyingKey::from_vk_bytes(&vk).unwrap();
+
+        // x coordinates are untouched, y coordinates sum to p
+        assert_eq!(&prepared.neg_gamma[0..64], &vk[192..256]);
+        for offset in [64, 96] {
+            let y = U256::from_be_slice(&vk[192 + offset..192 + offset + 32]);
+            let neg_y = U256::from_be_slice(&prepared.neg_gamma[offset..offset + 32]);
+            assert_eq!(y + neg_y, BN254_BASE_MODULUS);
+        }
+
+        // Negating twice is the identity
+        assert_eq!(negate_g2(&negate_g2(&vk[320..448]).unwrap()).unwrap()[..], vk[320..448]);
+    }
+
+    #[test]
+    fn test_precomputed_rejects_foreign_vk() {
+        let vk = sample_vk(1);
+        let encoded = compute_precomputed_pairing(&vk).unwrap();
+
+        let mut other_vk = vk.clone();
+        other_vk[10] ^= 1;
+        assert_eq!(
+            PreparedVerifyingKey::decode_for_vk(&encoded, &other_vk),
+            Err(Error::InvalidVerificationKey)
+        );
+    }
+
+    #[test]
+    fn test_precomputed_rejects_malformed_input() {
+        // Missing IC_0
+        assert_eq!(
+            compute_precomputed_pairing(&[0u8; VK_HEADER_SIZE]),
+            Err(Error::InvalidVerificationKey)
+        );
+        // Non-canonical y coordinate
+        let mut vk = sample_vk(0);
+        vk[64 + 64..64 + 96].copy_from_slice(&[0xff; 32]);
+        assert_eq!(compute_precomputed_pairing(&vk), Err(Error::G2NotOnCurve));
+
+        // Truncated / unknown version cache
+        let encoded = compute_precomputed_pairing(&sample_vk(0)).unwrap();
+        assert!(PreparedVerifyingKey::decode(&encoded[..encoded.len() - 1]).is_err());
+        let mut wrong_version = encoded.clone();
+        wrong_version[0] = 0;
+        assert!(PreparedVerifyingKey::decode(&wrong_version).is_err());
+    }
+
+    #[test]
+    fn test_validate_vk_rejections() {
+        assert_eq!(validate_vk(&sample_vk(2)), Ok(()));
+
+        // Identity alpha, delta and IC point
+        for range in [0..64, 320..448, VK_HEADER_SIZE + 64..VK_HEADER_SIZE + 128] {
+            let mut vk = sample_vk(1);
+            vk[range].fill(0);
+            assert_eq!(validate_vk(&vk), Err(Error::IdentityPoint));
+        }
+
+        // Off-curve IC point
+        let mut vk = sample_vk(1);
+        vk[VK_HEADER_SIZE + 63] = 3;
+        assert_eq!(validate_vk(&vk), Err(Error::G1NotOnCurve));
+
+        // Off-curve gamma
+        let mut vk = sample_vk(1);
+        vk[255] ^= 1;
+        assert_eq!(validate_vk(&vk), Err(Error::G2NotOnCurve));
+    }
+
+    #[test]
+    fn test_validate_proof_and_inputs() {
+        let mut proof = g1_generator().to_vec();
+        proof.extend_from_slice(&g2_generator());
+        proof.extend_from_slice(&g1_generator());
+        assert_eq!(validate_proof(&proof), Ok(()));
+
+        let mut bad_c = proof.clone();
+        bad_c[255] = 3;
+        assert_eq!(validate_proof(&bad_c), Err(Error::G1NotOnCurve));
+        assert_eq!(validate_proof(&proof[..255]), Err(Error::InvalidProof));
+
+        let one = U256::from(1).to_be_bytes::<32>();
+        assert_eq!(validate_inputs(&one, 1), Ok(vec![U256::from(1)]));
+        assert_eq!(validate_inputs(&one, 2), Err(Error::InvalidInputs));
+        let r = crate::utils::BN254_SCALAR_MODULUS.to_be_bytes::<32>();
+        assert_eq!(validate_inputs(&r, 1), Err(Error::ScalarOutOfRange));
+    }
+
+    #[test]
+    fn test_batch_weights_bind_every_input() {
+        let vk_hash = [7u8; 32];
+        let proofs = vec![vec![1u8; PROOF_SIZE], vec![2u8; PROOF_SIZE]];
+        let inputs = vec![vec![3u8; 32], vec![4u8; 32]];
+
+        let weights = batch_weights(&vk_hash, &proofs, &inputs);
+        assert_eq!(weights.len(), 2);
+        assert_eq!(weights, batch_weights(&vk_hash, &proofs, &inputs));
+        assert_ne!(weights[0], weights[1]);
+        for weight in &weights {
+            assert!(*weight != U256::ZERO && *weight < (U256::from(1) << 128));
+        }
+
+        // Changing any proof, input or the VK changes every weight
+        let mut other_proofs = proofs.clone();
+        other_proofs[1][100] ^= 1;
+        let mut other_inputs = inputs.clone();
+        other_inputs[1][31] ^= 1;
+        for other in [
+            batch_weights(&vk_hash, &other_proofs, &inputs),
+            batch_weights(&vk_hash, &proofs, &other_inputs),
+            batch_weights(&[8u8; 32], &proofs, &inputs),
+        ] {
+            assert_ne!(other[0], weights[0]);
+            assert_ne!(other[1], weights[1]);
+        }
+    }
+
+    #[test]
+    fn test_batch_ic_scalars() {
+        let weights = [U256::from(2), U256::from(5)];
+        let inputs = vec![
+            [U256::from(3).to_be_bytes::<32>(), U256::from(1).to_be_bytes::<32>()].concat(),
+            [U256::from(7).to_be_bytes::<32>(), U256::from(0).to_be_bytes::<32>()].concat(),
+        ];
+        let scalars = batch_ic_scalars(&weights, &inputs, 2);
+        assert_eq!(scalars, vec![U256::from(7), U256::from(2 * 3 + 5 * 7), U256::from(2)]);
+    }
+}
@@ -0,0 +1,480 @@
+//! Native BN254 G1 Multi-Scalar Multiplication
+//!
+//! The precompile path costs one 0x07 and one 0x06 call per non-trivial term, so
+//! its price grows linearly with the number of public inputs. This module computes
+//! the same sum in WASM with Pippenger's bucket method over Jacobian coordinates
+//! and Montgomery-form Fq, whose per-term cost shrinks as the MSM grows.
+//!
+//! `MsmBackend` selects between the two; `Auto` compares `native_msm_gas` and
+//! `precompile_msm_gas` for the actual term count. `benches/g1_msm.rs` prints the
+//! crossover.
+//!
+//! Bases must already be validated G1 points (VK IC bases and proof points are,
+//! see `validate_vk` / `decode_proof`); only canonical coordinates are re-checked.
+
+use alloc::vec::Vec;
+use stylus_sdk::alloy_primitives::U256;
+use super::{Error, Result};
+use crate::utils::{adc, below, mac, sbb};
+use crate::verifier_traits::GasCost;
+
+/// How a G1 MSM is evaluated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MsmBackend {
+    /// One `g1_mul` + `g1_add` on the curve backend per term (0x07 + 0x06 on chain)
+    Precompile,
+    /// Pippenger in WASM
+    Native,
+    /// Whichever `native_msm_gas` / `precompile_msm_gas` estimate is cheaper
+    Auto,
+}
+
+impl MsmBackend {
+    /// Resolve `Auto` for an MSM with `terms` non-zero scalars
+    pub fn use_native(self, terms: usize) -> bool {
+        match self {
+            MsmBackend::Precompile => false,
+            MsmBackend::Native => true,
+            MsmBackend::Auto => native_msm_gas(terms) < precompile_msm_gas(terms),
+        }
+    }
+}
+
+// =========================================================================
+// COST MODEL
+// =========================================================================
+
+/// Approximate gas for one Montgomery multiplication in WASM under Stylus ink
+/// pricing. The crossover scales with this; recalibrate it from on-chain traces.
+pub const NATIVE_FQ_MUL_GAS: u64 = 4;
+
+/// madd-2007-bl: 7M + 4S
+const MADD_MULS: u64 = 11;
+/// add-2007-bl: 11M + 5S
+const ADD_MULS: u64 = 16;
+/// dbl-2009-l: 2M + 5S
+const DBL_MULS: u64 = 7;
+/// Fermat inversion of Z: ~254 squarings + ~128 multiplications
+const INV_MULS: u64 = 382;
+
+/// Scalars are < r < 2^254
+const SCALAR_BITS: usize = 254;
+
+/// Field multiplications performed by `msm` for `terms` non-zero scalars
+pub fn native_msm_muls(terms: usize) -> u64 {
+    if terms == 0 {
+        return 0;
+    }
+    let c = window_size(terms);
+    let windows = SCALAR_BITS.div_ceil(c) as u64;
+    let buckets = (1u64 << c) - 1;
+    // Bucket fill, running-sum reduction (two adds per bucket) and the window add
+    let per_window = terms as u64 * MADD_MULS + (2 * buckets + 1) * ADD_MULS;
+    windows * per_window + SCALAR_BITS as u64 * DBL_MULS + INV_MULS
+}
+
+/// Estimated gas for `msm` over `terms` non-zero scalars
+pub fn native_msm_gas(terms: usize) -> u64 {
+    native_msm_muls(terms) * NATIVE_FQ_MUL_GAS
+}
+
+/// Estimated gas for the precompile path over `terms` non-zero scalars
+pub fn precompile_msm_gas(terms: usize) -> u64 {
+    terms as u64 * GasCost::groth16().per_public_input
+}
+
+/// Pippenger window size, as in arkworks: ln(n) + 2, and 3 for small n
+fn window_size(terms: usize) -> usize {
+    if terms < 32 {
+        3
+    } else {
+        let log2 = terms.next_power_of_two().trailing_zeros() as usize;
+        log2 * 69 / 100 + 2
+    }
+}
+
+// =========================================================================
+// MSM
+// =========================================================================
+
+/// sum(scalars[i] * bases[i]) over uncompressed G1 points
+///
+/// Zero scalars and identity bases are skipped. Returns the 64-byte result
+/// (all zeros for the identity).
+pub fn msm(bases: &[[u8; 64]], scalars: &[U256]) -> Result<[u8; 64]> {
+    let mut terms = Vec::with_capacity(bases.len());
+    for (base, scalar) in bases.iter().zip(scalars) {
+        if *scalar == U256::ZERO {
+            continue;
+        }
+        if let Some(point) = G1Affine::from_bytes(base)? {
+            terms.push((point, *scalar));
+        }
+    }
+    if terms.is_empty() {
+        return Ok([0u8; 64]);
+    }
+
+    let num_bits = terms.iter().map(|(_, s)| s.bit_len()).max().unwrap_or(0);
+    let c = window_size(terms.len());
+    let mask = (1usize << c) - 1;
+    let mut buckets = alloc::vec![G1Jacobian::IDENTITY; mask];
+    let mut acc = G1Jacobian::IDENTITY;
+
+    for window in (0..num_bits.div_ceil(c)).rev() {
+        for _ in 0..c {
+            acc = acc.double();
+        }
+
+        buckets.fill(G1Jacobian::IDENTITY);
+        let shift = window * c;
+        for (point, scalar) in &terms {
+            let digit = (*scalar >> shift).as_limbs()[0] as usize & mask;
+            if digit != 0 {
+                buckets[digit - 1] = buckets[digit - 1].add_affine(point);
+            }
+        }
+
+        // sum_d d·bucket[d] via running sums
+        let mut running = G1Jacobian::IDENTITY;
+        let mut sum = G1Jacobian::IDENTITY;
+        for bucket in buckets.iter().rev() {
+            running = running.add(bucket);
+            sum = sum.add(&running);
+        }
+        acc = acc.add(&sum);
+    }
+
+    Ok(acc.to_bytes())
+}
+
+// =========================================================================
+// MONTGOMERY Fq
+// =========================================================================
+
+/// p, little-endian limbs
+const MODULUS: [u64; 4] = [
+    0x3c208c16d87cfd47,
+    0x97816a916871ca8d,
+    0xb85045b68181585d,
+    0x30644e72e131a029,
+];
+
+/// -p^(-1) mod 2^64
+const INV: u64 = 0x87d20782e4866389;
+
+/// R mod p, R = 2^256 (Montgomery form of 1)
+const R: [u64; 4] = [
+    0xd35d438dc58f0d9d,
+    0x0a78eb28f5c70b3d,
+    0x666ea36f7879462c,
+    0x0e0a77c19a07df2f,
+];
+
+/// R² mod p
+const R2: [u64; 4] = [
+    0xf32cfc5b538afa89,
+    0xb5e71911d44501fb,
+    0x47ab1eff0a417ff6,
+    0x06d89f71cab8351f,
+];
+
+/// Base field element in Montgomery form (a·R mod p)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Fq([u64; 4]);
+
+// Limb loops index several arrays in lockstep
+#[allow(clippy::needless_range_loop)]
+impl Fq {
+    const ZERO: Fq = Fq([0; 4]);
+    const ONE: Fq = Fq(R);
+
+    /// Convert a canonical integer (< p) into Montgomery form
+    fn from_u256(value: U256) -> Option<Self> {
+        let limbs = *value.as_limbs();
+        if !is_below_modulus(&limbs) {
+            return None;
+        }
+        Some(Fq(limbs).mul(&Fq(R2)))
+    }
+
+    fn to_u256(self) -> U256 {
+        U256::from_limbs(self.mul(&Fq([1, 0, 0, 0])).0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == [0; 4]
+    }
+
+    /// p < 2^254, so a + b never overflows 256 bits
+    fn add(&self, other: &Self) -> Self {
+        let mut out = [0u64; 4];
+        let mut carry = 0;
+        for i in 0..4 {
+            (out[i], carry) = adc(self.0[i], other.0[i], carry);
+        }
+        if !is_below_modulus(&out) {
+            out = sub_modulus(&out);
+        }
+        Fq(out)
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        let mut out = [0u64; 4];
+        let mut borrow = 0;
+        for i in 0..4 {
+            (out[i], borrow) = sbb(self.0[i], other.0[i], borrow);
+        }
+        if borrow != 0 {
+            let mut carry = 0;
+            for i in 0..4 {
+                (out[i], carry) = adc(out[i], MODULUS[i], carry);
+            }
+        }
+        Fq(out)
+    }
+
+    fn double(&self) -> Self {
+        self.add(self)
+    }
+
+    /// CIOS Montgomery multiplication: a·b·R^(-1) mod p
+    fn mul(&self, other: &Self) -> Self {
+        let mut t = [0u64; 6];
+        for i in 0..4 {
+            let mut carry = 0;
+            for j in 0..4 {
+                (t[j], carry) = mac(t[j], self.0[i], other.0[j], carry);
+            }
+            let (sum, overflow) = adc(t[4], carry, 0);
+            t[4] = sum;
+            t[5] = overflow;
+
+            let m = t[0].wrapping_mul(INV);
+            let (_, mut carry) = mac(t[0], m, MODULUS[0], 0);
+            for j in 1..4 {
+                (t[j - 1], carry) = mac(t[j], m, MODULUS[j], carry);
+            }
+            let (sum, overflow) = adc(t[4], carry, 0);
+            t[3] = sum;
+            t[4] = t[5] + overflow;
+        }
+
+        let out = [t[0], t[1], t[2], t[3]];
+        if t[4] != 0 || !is_below_modulus(&out) {
+            Fq(sub_modulus(&out))
+        } else {
+            Fq(out)
+        }
+    }
+
+    fn square(&self) -> Self {
+        self.mul(self)
+    }
+
+    /// a^(p-2) (Fermat); `None` for zero
+    fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        let exp = U256::from_limbs(MODULUS) - U256::from(2);
+        let mut result = Fq::ONE;
+        for i in (0..exp.bit_len()).rev() {
+            result = result.square();
+            if exp.bit(i) {
+                result = result.mul(self);
+            }
+        }
+        Some(result)
+    }
+}
+
+fn is_below_modulus(limbs: &[u64; 4]) -> bool {
+    below(limbs, &MODULUS)
+}
+
+#[allow(clippy::needless_range_loop)]
+fn sub_modulus(limbs: &[u64; 4]) -> [u64; 4] {
+    let mut out = [0u64; 4];
+    let mut borrow = 0;
+    for i in 0..4 {
+        (out[i], borrow) = sbb(limbs[i], MODULUS[i], borrow);
+    }
+    out
+}
+
+// =========================================================================
+// G1 POINTS
+// =========================================================================
+
+#[derive(Debug, Clone, Copy)]
+struct G1Affine {
+    x: Fq,
+    y: Fq,
+}
+
+impl G1Affine {
+    /// Parse 64 uncompressed bytes; `None` for the identity
+    fn from_bytes(bytes: &[u8; 64]) -> Result<Option<Self>> {
+        let x = U256::from_be_slice(&bytes[0..32]);
+        let y = U256::from_be_slice(&bytes[32..64]);
+        if x == U256::ZERO && y == U256::ZERO {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            x: Fq::from_u256(x).ok_or(Error::G1NotOnCurve)?,
+            y: Fq::from_u256(y).ok_or(Error::G1NotOnCurve)?,
+        }))
+    }
+}
+
+/// G1 point in Jacobian coordinates (X/Z², Y/Z³); Z = 0 is the identity
+#[derive(Debug, Clone, Copy)]
+struct G1Jacobian {
+    x: Fq,
+    y: Fq,
+    z: Fq,
+}
+
+impl G1Jacobian {
+    const IDENTITY: G1Jacobian = G1Jacobian { x: Fq::ONE, y: Fq::ONE, z: Fq::ZERO };
+
+    fn is_identity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    /// dbl-2009-l (a = 0)
+    fn double(&self) -> Self {
+        if self.is_identity() || self.y.is_zero() {
+            return Self::IDENTITY;
+        }
+        let a = self.x.square();
+        let b = self.y.square();
+        let c = b.square();
+        let d = self.x.add(&b).square().sub(&a).sub(&c).double();
+        let e = a.double().add(&a);
+        let f = e.square();
+        let x3 = f.sub(&d.double());
+        let y3 = e.mul(&d.sub(&x3)).sub(&c.double().double().double());
+        let z3 = self.y.mul(&self.z).double();
+        Self { x: x3, y: y3, z: z3 }
+    }
+
+    /// madd-2007-bl (affine addend)
+    fn add_affine(&self, other: &G1Affine) -> Self {
+        if self.is_identity() {
+            return Self { x: other.x, y: other.y, z: Fq::ONE };
+        }
+        let z1z1 = self.z.square();
+        let u2 = other.x.mul(&z1z1);
+        let s2 = other.y.mul(&self.z).mul(&z1z1);
+        let h = u2.sub(&self.x);
+        let r = s2.sub(&self.y).double();
+        if h.is_zero() {
+            return if r.is_zero() { self.double() } else { Self::IDENTITY };
+        }
+        let hh = h.square();
+        let i = hh.double().double();
+        let j = h.mul(&i);
+        let v = self.x.mul(&i);
+        let x3 = r.square().sub(&j).sub(&v.double());
+        let y3 = r.mul(&v.sub(&x3)).sub(&self.y.mul(&j).double());
+        let z3 = self.z.add(&h).square().sub(&z1z1).sub(&hh);
+        Self { x: x3, y: y3, z: z3 }
+    }
+
+    /// add-2007-bl
+    fn add(&self, other: &Self) -> Self {
+        if self.is_identity() {
+            return *other;
+        }
+        if other.is_identity() {
+            return *self;
+        }
+        let z1z1 = self.z.square();
+        let z2z2 = other.z.square();
+        let u1 = self.x.mul(&z2z2);
+        let u2 = other.x.mul(&z1z1);
+        let s1 = self.y.mul(&other.z).mul(&z2z2);
+        let s2 = other.y.mul(&self.z).mul(&z1z1);
+        let h = u2.sub(&u1);
+        let r = s2.sub(&s1).double();
+        if h.is_zero() {
+            return if r.is_zero() { self.double() } else { Self::IDENTITY };
+        }
+        let i = h.double().square();
+        let j = h.mul(&i);
+        let v = u1.mul(&i);
+        let x3 = r.square().sub(&j).sub(&v.double());
+        let y3 = r.mul(&v.sub(&x3)).sub(&s1.mul(&j).double());
+        let z3 = self.z.add(&other.z).square().sub(&z1z1).sub(&z2z2).mul(&h);
+        Self { x: x3, y: y3, z: z3 }
+    }
+
+    /// Affine x || y, all zeros for the identity
+    fn to_bytes(self) -> [u8; 64] {
+        let mut out = [0u8; 64];
+        let Some(z_inv) = self.z.inverse() else {
+            return out;
+        };
+        let z_inv2 = z_inv.square();
+        let x = self.x.mul(&z_inv2);
+        let y = self.y.mul(&z_inv2).mul(&z_inv);
+        out[0..32].copy_from_slice(&x.to_u256().to_be_bytes::<32>());
+        out[32..64].copy_from_slice(&y.to_u256().to_be_bytes::<32>());
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::groth16::curve::tests::g1_generator;
+    use crate::utils::{fq_add, fq_mul, fq_sub, BN254_BASE_MODULUS, BN254_SCALAR_MODULUS};
+
+    #[test]
+    fn test_montgomery_matches_u256_arithmetic() {
+        let a = U256::from(0x1234_5678_9abc_def0u64) << 190;
+        let b = BN254_BASE_MODULUS - U256::from(12345);
+        let (ma, mb) = (Fq::from_u256(a).unwrap(), Fq::from_u256(b).unwrap());
+
+        assert_eq!(ma.mul(&mb).to_u256(), fq_mul(a, b));
+        assert_eq!(ma.add(&mb).to_u256(), fq_add(a, b));
+        assert_eq!(ma.sub(&mb).to_u256(), fq_sub(a, b));
+        assert_eq!(mb.sub(&ma).to_u256(), fq_sub(b, a));
+        assert_eq!(ma.mul(&ma.inverse().unwrap()), Fq::ONE);
+        assert_eq!(Fq::from_u256(BN254_BASE_MODULUS), None);
+    }
+
+    #[test]
+    fn test_msm_small_cases() {
+        let g = g1_generator();
+        let neg_g = {
+            let mut out = g;
+            out[32..64].copy_from_slice(&(BN254_BASE_MODULUS - U256::from(2)).to_be_bytes::<32>());
+            out
+        };
+
+        // 1·G, 0·G, (r - 1)·G = -G, G + (r - 1)·G = O
+        assert_eq!(msm(&[g], &[U256::from(1)]), Ok(g));
+        assert_eq!(msm(&[g], &[U256::ZERO]), Ok([0u8; 64]));
+        let r_minus_1 = BN254_SCALAR_MODULUS - U256::from(1);
+        assert_eq!(msm(&[g], &[r_minus_1]), Ok(neg_g));
+        assert_eq!(msm(&[g, g], &[U256::from(1), r_minus_1]), Ok([0u8; 64]));
+
+        // 2·G + 3·G == 5·G
+        assert_eq!(
+            msm(&[g, g], &[U256::from(2), U256::from(3)]),
+            msm(&[g], &[U256::from(5)])
+        );
+    }
+
+    #[test]
+    fn test_auto_backend_crossover() {
+        // Precompile for tiny MSMs, native once Pippenger amortizes
+        assert!(!MsmBackend::Auto.use_native(1));
+        assert!(MsmBackend::Auto.use_native(1024));
+        assert!(MsmBackend::Native.use_native(1));
+        assert!(!MsmBackend::Precompile.use_native(1024));
+    }
+}
@@ -0,0 +1,487 @@
+//! halo2 Verifier (PSE halo2, KZG/SHPLONK, BN254)
+//!
+//! Verifies proofs written by PSE halo2's `create_proof` with
+//! `ProverSHPLONK` over BN254 KZG params, replaying `verify_proof` with
+//! `VerifierSHPLONK` (`QUERY_INSTANCE = false`): the instances are hashed
+//! into the transcript and evaluated by the verifier.
+//!
+//! # Wire Format
+//! ```text
+//! proof: halo2 transcript bytes (compressed points, little-endian scalars)
+//! vk:    compact verifying key (see `vk.rs`) ‖ [τ]₂ (128 bytes)
+//! inputs: instance columns concatenated, 32-byte big-endian scalars
+//! ```
+//! The transcript is Blake2b (`Blake2bWrite`) or Keccak256 (PSE
+//! `Keccak256Write`), selected by the descriptor hash function.
+//!
+//! # Verification
+//! 1. Replay the transcript: advice commitments and challenges phase by phase,
+//!    θ, the permuted lookup commitments, β, γ, the permutation and lookup
+//!    products, the random polynomial, y, the quotient pieces, x, then every
+//!    evaluation and the SHPLONK commitments `h1`, `h2`.
+//! 2. Fold the gates, the permutation and the lookup constraints at x with y
+//!    and check them against the quotient:
+//!    ```text
+//!    h(x) = (Σ yⁱ·constraintᵢ(x)) / (xⁿ − 1)      [h] = Σ xⁿⁱ·[hᵢ]
+//!    ```
+//! 3. Open every query with one SHPLONK multi-open, which reduces to a single
+//!    KZG opening at u (`kzg::verify_kzg_opening`).
+//!
+//! A malformed VK, proof or instance is an `InvalidInputSize` error; a proof
+//! that does not verify is `Ok(false)`.
+
+pub mod transcript;
+pub mod vk;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use stylus_sdk::alloy_primitives::U256;
+
+use crate::backend::Bn254Backend;
+use crate::plonk::kzg::{g1_lincomb, verify_kzg_opening, Error, Result};
+use crate::plonk::plonk::{parse_public_inputs, SRS_G2_SIZE};
+use crate::types::HashFunctionId;
+use crate::utils::{fr_add, fr_inv, fr_mul, fr_pow, fr_sub};
+use transcript::{Blake2bHash, Keccak256Hash, TranscriptHash, TranscriptRead};
+use vk::{ColumnType, VerifyingKey};
+
+/// halo2curves `Fr::DELTA = 7^(2^28)`, the coset shift between permutation
+/// columns
+pub const DELTA: U256 = U256::from_limbs([
+    0x870e56bbe533e9a2,
+    0x5b5f898e5e963f25,
+    0x64ec26aad4c86e71,
+    0x09226b6e22c6f0ca,
+]);
+
+/// Deserialize and verify a halo2 proof with the given transcript hash
+pub fn verify<B: Bn254Backend>(
+    curve: &B,
+    hash: HashFunctionId,
+    proof_bytes: &[u8],
+    public_inputs_bytes: &[u8],
+    vk_bytes: &[u8],
+) -> Result<bool> {
+    let (vk, instances, srs_g2) = decode(public_inputs_bytes, vk_bytes)?;
+    match hash {
+        HashFunctionId::Blake2b => verify_proof::<B, Blake2bHash>(curve, &vk, &instances, proof_bytes, srs_g2),
+        HashFunctionId::Keccak256 => verify_proof::<B, Keccak256Hash>(curve, &vk, &instances, proof_bytes, srs_g2),
+        _ => Err(Error::UnsupportedHash),
+    }
+}
+
+/// The VK, the instance columns and `[τ]₂`
+type Decoded<'a> = (VerifyingKey, Vec<Vec<U256>>, &'a [u8]);
+
+/// Split `verify` arguments into the VK, the instance columns and `[τ]₂`
+fn decode<'a>(public_inputs_bytes: &[u8], vk_bytes: &'a [u8]) -> Result<Decoded<'a>> {
+    let split = vk_bytes.len().checked_sub(SRS_G2_SIZE).ok_or(Error::InvalidInputSize)?;
+    let (vk_bytes, srs_g2) = vk_bytes.split_at(split);
+    let vk = VerifyingKey::parse(vk_bytes).ok_or(Error::InvalidInputSize)?;
+
+    let values = parse_public_inputs(public_inputs_bytes)?;
+    if values.len() != vk.instance_lengths.iter().sum::<usize>() {
+        return Err(Error::InvalidInputSize);
+    }
+    let mut rest = &values[..];
+    let instances = vk
+        .instance_lengths
+        .iter()
+        .map(|len| {
+            let (column, tail) = rest.split_at(*len);
+            rest = tail;
+            column.to_vec()
+        })
+        .collect();
+    Ok((vk, instances, srs_g2))
+}
+
+/// Permutation grand product evaluations
+struct PermutationSet {
+    commitment: [u8; 64],
+    eval: U256,
+    next_eval: U256,
+    /// At `ω^-(blinding_factors + 1)·x`, for every set but the last
+    last_eval: Option<U256>,
+}
+
+/// Lookup commitments and evaluations
+struct LookupEvaluated {
+    permuted_input: [u8; 64],
+    permuted_table: [u8; 64],
+    product: [u8; 64],
+    product_eval: U256,
+    product_next_eval: U256,
+    permuted_input_eval: U256,
+    permuted_input_inv_eval: U256,
+    permuted_table_eval: U256,
+}
+
+/// A commitment as a linear combination of points
+type Msm<'a> = Vec<(&'a [u8; 64], U256)>;
+
+/// An opening claim: commitment slot, point, evaluation
+///
+/// Commitments are identified by slot, as halo2 compares them by reference.
+type Claim = (usize, U256, U256);
+
+/// Run `verify_proof` over a parsed VK and instances
+pub fn verify_proof<B: Bn254Backend, H: TranscriptHash>(
+    curve: &B,
+    vk: &VerifyingKey,
+    instances: &[Vec<U256>],
+    proof: &[u8],
+    srs_g2: &[u8],
+) -> Result<bool> {
+    if srs_g2.len() != SRS_G2_SIZE || instances.len() != vk.instance_lengths.len() {
+        return Err(Error::InvalidInputSize);
+    }
+    let mut t = TranscriptRead::<H>::new(proof);
+
+    // The VK and the instances
+    t.common_scalar(vk.transcript_repr);
+    for value in instances.iter().flatten() {
+        t.common_scalar(*value);
+    }
+
+    // Advice commitments and challenges, phase by phase
+    let mut advice_commitments = vec![[0u8; 64]; vk.advice_phases.len()];
+    let mut challenges = vec![U256::ZERO; vk.challenge_phases.len()];
+    for phase in 0..=vk.max_phase() {
+        for (column_phase, commitment) in vk.advice_phases.iter().zip(advice_commitments.iter_mut()) {
+            if *column_phase == phase {
+                *commitment = t.read_point()?;
+            }
+        }
+        for (challenge_phase, challenge) in vk.challenge_phases.iter().zip(challenges.iter_mut()) {
+            if *challenge_phase == phase {
+                *challenge = t.squeeze_challenge();
+            }
+        }
+    }
+
+    let theta = t.squeeze_challenge();
+    let mut permuted = Vec::with_capacity(vk.lookups.len());
+    for _ in &vk.lookups {
+        permuted.push((t.read_point()?, t.read_point()?));
+    }
+
+    let beta = t.squeeze_challenge();
+    let gamma = t.squeeze_challenge();
+    let mut permutation_products = Vec::with_capacity(vk.permutation_sets());
+    for _ in 0..vk.permutation_sets() {
+        permutation_products.push(t.read_point()?);
+    }
+    let mut lookup_products = Vec::with_capacity(vk.lookups.len());
+    for _ in &vk.lookups {
+        lookup_products.push(t.read_point()?);
+    }
+
+    let random_poly = t.read_point()?;
+    let y = t.squeeze_challenge();
+    let mut h_commitments = Vec::with_capacity(vk.degree - 1);
+    for _ in 1..vk.degree {
+        h_commitments.push(t.read_point()?);
+    }
+    let x = t.squeeze_challenge();
+
+    let advice_evals = read_scalars(&mut t, vk.advice_queries.len())?;
+    let fixed_evals = read_scalars(&mut t, vk.fixed_queries.len())?;
+    let random_eval = t.read_scalar()?;
+    let permutation_evals = read_scalars(&mut t, vk.permutation_columns.len())?;
+
+    let num_sets = permutation_products.len();
+    let mut sets = Vec::with_capacity(num_sets);
+    for (i, commitment) in permutation_products.into_iter().enumerate() {
+        let eval = t.read_scalar()?;
+        let next_eval = t.read_scalar()?;
+        let last_eval = if i + 1 < num_sets { Some(t.read_scalar()?) } else { None };
+        sets.push(PermutationSet { commitment, eval, next_eval, last_eval });
+    }
+
+    let mut lookups = Vec::with_capacity(vk.lookups.len());
+    for ((permuted_input, permuted_table), product) in permuted.into_iter().zip(lookup_products) {
+        lookups.push(LookupEvaluated {
+            permuted_input,
+            permuted_table,
+            product,
+            product_eval: t.read_scalar()?,
+            product_next_eval: t.read_scalar()?,
+            permuted_input_eval: t.read_scalar()?,
+            permuted_input_inv_eval: t.read_scalar()?,
+            permuted_table_eval: t.read_scalar()?,
+        });
+    }
+
+    // SHPLONK
+    let shplonk_y = t.squeeze_challenge();
+    let shplonk_v = t.squeeze_challenge();
+    let h1 = t.read_point()?;
+    let shplonk_u = t.squeeze_challenge();
+    let h2 = t.read_point()?;
+    t.finish()?;
+
+    // Vanishing polynomial and Lagrange bases at x
+    let one = U256::from(1);
+    let omega_inv = fr_inv(vk.omega).ok_or(Error::InvalidInputSize)?;
+    let rotate = |point: U256, rotation: i32| {
+        let base = if rotation < 0 { omega_inv } else { vk.omega };
+        fr_mul(point, fr_pow(base, U256::from(rotation.unsigned_abs())))
+    };
+    let xn = fr_pow(x, U256::from(vk.n));
+    let Some(zh_inv) = fr_inv(fr_sub(xn, one)) else {
+        return Ok(false);
+    };
+    let common = fr_mul(fr_sub(xn, one), fr_inv(U256::from(vk.n)).ok_or(Error::InvalidInputSize)?);
+    // L_i(x) = ωⁱ·(xⁿ − 1) / (n·(x − ωⁱ)); x is outside the domain
+    let lagrange = |i: i32| {
+        let omega_i = rotate(one, i);
+        fr_mul(fr_mul(omega_i, common), fr_inv(fr_sub(x, omega_i)).unwrap_or_default())
+    };
+
+    let instance_evals: Vec<U256> = vk
+        .instance_queries
+        .iter()
+        .map(|query| {
+            instances[query.column].iter().enumerate().fold(U256::ZERO, |acc, (j, value)| {
+                fr_add(acc, fr_mul(*value, lagrange(j as i32 - query.rotation)))
+            })
+        })
+        .collect();
+
+    let blinding_factors = vk.blinding_factors() as i32;
+    let l_last = lagrange(-(blinding_factors + 1));
+    let l_blind = (1..=blinding_factors).fold(U256::ZERO, |acc, i| fr_add(acc, lagrange(-i)));
+    let l_0 = lagrange(0);
+    let active_rows = fr_sub(one, fr_add(l_last, l_blind));
+
+    // Constraints, in halo2's order
+    let evaluate = |expression: &vk::Expression| expression.evaluate(&fixed_evals, &advice_evals, &instance_evals, &challenges);
+    let mut constraints: Vec<U256> = vk.gates.iter().map(evaluate).collect();
+
+    if let (Some(first), Some(last)) = (sets.first(), sets.last()) {
+        constraints.push(fr_mul(l_0, fr_sub(one, first.eval)));
+        constraints.push(fr_mul(l_last, fr_sub(fr_mul(last.eval, last.eval), last.eval)));
+    }
+    for (set, previous) in sets.iter().skip(1).zip(&sets) {
+        let previous_last = previous.last_eval.ok_or(Error::InvalidInputSize)?;
+        constraints.push(fr_mul(l_0, fr_sub(set.eval, previous_last)));
+    }
+    let chunk_len = vk.chunk_len();
+    let column_eval = |(ty, index): &(ColumnType, usize)| match ty {
+        ColumnType::Advice => advice_evals[*index],
+        ColumnType::Fixed => fixed_evals[*index],
+        ColumnType::Instance => instance_evals[*index],
+    };
+    for (chunk, ((set, columns), sigmas)) in sets
+        .iter()
+        .zip(vk.permutation_columns.chunks(chunk_len))
+        .zip(permutation_evals.chunks(chunk_len))
+        .enumerate()
+    {
+        let mut left = set.next_eval;
+        let mut right = set.eval;
+        let mut delta = fr_mul(fr_mul(beta, x), fr_pow(DELTA, U256::from(chunk * chunk_len)));
+        for (column, sigma) in columns.iter().zip(sigmas) {
+            let eval = column_eval(column);
+            left = fr_mul(left, fr_add(fr_add(eval, fr_mul(beta, *sigma)), gamma));
+            right = fr_mul(right, fr_add(fr_add(eval, delta), gamma));
+            delta = fr_mul(delta, DELTA);
+        }
+        constraints.push(fr_mul(fr_sub(left, right), active_rows));
+    }
+
+    for (lookup, argument) in lookups.iter().zip(&vk.lookups) {
+        let compress = |expressions: &[vk::Expression]| {
+            expressions.iter().fold(U256::ZERO, |acc, e| fr_add(fr_mul(acc, theta), evaluate(e)))
+        };
+        let z = lookup.product_eval;
+        let a = lookup.permuted_input_eval;
+        let s = lookup.permuted_table_eval;
+        let left = fr_mul(fr_mul(lookup.product_next_eval, fr_add(a, beta)), fr_add(s, gamma));
+        let right = fr_mul(
+            fr_mul(z, fr_add(compress(&argument.input), beta)),
+            fr_add(compress(&argument.table), gamma),
+        );
+        constraints.push(fr_mul(l_0, fr_sub(one, z)));
+        constraints.push(fr_mul(l_last, fr_sub(fr_mul(z, z), z)));
+        constraints.push(fr_mul(fr_sub(left, right), active_rows));
+        constraints.push(fr_mul(l_0, fr_sub(a, s)));
+        constraints.push(fr_mul(fr_mul(fr_sub(a, s), fr_sub(a, lookup.permuted_input_inv_eval)), active_rows));
+    }
+
+    let h_eval = constraints.into_iter().fold(U256::ZERO, |acc, c| fr_add(fr_mul(acc, y), c));
+    let expected_h_eval = fr_mul(h_eval, zh_inv);
+
+    // Opening claims, in halo2's query order
+    let x_next = rotate(x, 1);
+    let x_prev = rotate(x, -1);
+    let x_last = rotate(x, -(blinding_factors + 1));
+    let mut slots: Vec<Msm> = Vec::new();
+    let mut claims: Vec<Claim> = Vec::new();
+
+    let advice_slots = slots.len();
+    slots.extend(advice_commitments.iter().map(|c| vec![(c, one)]));
+    for (query, eval) in vk.advice_queries.iter().zip(&advice_evals) {
+        claims.push((advice_slots + query.column, rotate(x, query.rotation), *eval));
+    }
+
+    let set_slots = slots.len();
+    slots.extend(sets.iter().map(|set| vec![(&set.commitment, one)]));
+    for (i, set) in sets.iter().enumerate() {
+        claims.push((set_slots + i, x, set.eval));
+        claims.push((set_slots + i, x_next, set.next_eval));
+    }
+    for (i, set) in sets.iter().enumerate().rev() {
+        if let Some(last_eval) = set.last_eval {
+            claims.push((set_slots + i, x_last, last_eval));
+        }
+    }
+
+    for lookup in &lookups {
+        let base = slots.len();
+        slots.push(vec![(&lookup.product, one)]);
+        slots.push(vec![(&lookup.permuted_input, one)]);
+        slots.push(vec![(&lookup.permuted_table, one)]);
+        claims.push((base, x, lookup.product_eval));
+        claims.push((base + 1, x, lookup.permuted_input_eval));
+        claims.push((base + 2, x, lookup.permuted_table_eval));
+        claims.push((base + 1, x_prev, lookup.permuted_input_inv_eval));
+        claims.push((base, x_next, lookup.product_next_eval));
+    }
+
+    let fixed_slots = slots.len();
+    slots.extend(vk.fixed_commitments.iter().map(|c| vec![(c, one)]));
+    for (query, eval) in vk.fixed_queries.iter().zip(&fixed_evals) {
+        claims.push((fixed_slots + query.column, rotate(x, query.rotation), *eval));
+    }
+
+    for (sigma, eval) in vk.permutation_commitments.iter().zip(&permutation_evals) {
+        slots.push(vec![(sigma, one)]);
+        claims.push((slots.len() - 1, x, *eval));
+    }
+
+    // [h] = Σ xⁿⁱ·[hᵢ]
+    let mut xn_i = one;
+    let mut h_msm = Vec::with_capacity(h_commitments.len());
+    for h in &h_commitments {
+        h_msm.push((h, xn_i));
+        xn_i = fr_mul(xn_i, xn);
+    }
+    slots.push(h_msm);
+    claims.push((slots.len() - 1, x, expected_h_eval));
+    slots.push(vec![(&random_poly, one)]);
+    claims.push((slots.len() - 1, x, random_eval));
+
+    let challenges = ShplonkChallenges { y: shplonk_y, v: shplonk_v, u: shplonk_u };
+    shplonk(curve, &slots, &claims, challenges, &h1, &h2, srs_g2)
+}
+
+/// SHPLONK challenges (`y`, `v`, `u` in halo2)
+struct ShplonkChallenges {
+    y: U256,
+    v: U256,
+    u: U256,
+}
+
+/// SHPLONK multi-open (`VerifierSHPLONK::verify_proof`)
+///
+/// Commitments are grouped by the set of points they are opened at, in order
+/// of first appearance. With `Z_T` the vanishing polynomial of all points and
+/// `Z_i` that of set i, the claims fold into one opening of
+/// ```text
+/// [F] = Σᵢ vⁱ·(Z_{T∖Sᵢ}(u) / Z_{T∖S₀}(u))·Σⱼ yʲ·[Cᵢⱼ] − Z_{S₀}(u)·[h1]
+/// ```
+/// at u by `h2`, to the same combination of the interpolated evaluations.
+fn shplonk<B: Bn254Backend>(
+    curve: &B,
+    slots: &[Msm],
+    claims: &[Claim],
+    challenges: ShplonkChallenges,
+    h1: &[u8; 64],
+    h2: &[u8; 64],
+    srs_g2: &[u8],
+) -> Result<bool> {
+    let ShplonkChallenges { y, v, u } = challenges;
+    let one = U256::from(1);
+
+    // Points (and evaluations) opened per commitment
+    let mut super_points: Vec<U256> = Vec::new();
+    let mut openings: Vec<(usize, Vec<(U256, U256)>)> = Vec::new();
+    for &(slot, point, eval) in claims {
+        if !super_points.contains(&point) {
+            super_points.push(point);
+        }
+        match openings.iter_mut().find(|(s, _)| *s == slot) {
+            Some((_, opened)) if opened.iter().any(|(p, _)| *p == point) => {}
+            Some((_, opened)) => opened.push((point, eval)),
+            None => openings.push((slot, vec![(point, eval)])),
+        }
+    }
+
+    // Commitments sharing a point set: the points, then each member's slot
+    // and evaluations
+    type RotationSet = (Vec<U256>, Vec<(usize, Vec<U256>)>);
+    let mut rotation_sets: Vec<RotationSet> = Vec::new();
+    for (slot, mut opened) in openings {
+        opened.sort_by_key(|(point, _)| *point);
+        let (points, evals): (Vec<U256>, Vec<U256>) = opened.into_iter().unzip();
+        match rotation_sets.iter_mut().find(|(set, _)| *set == points) {
+            Some((_, members)) => members.push((slot, evals)),
+            None => rotation_sets.push((points, vec![(slot, evals)])),
+        }
+    }
+
+    let vanishing = |points: &[U256]| points.iter().fold(one, |acc, p| fr_mul(acc, fr_sub(u, *p)));
+    let (mut z_0, mut z_0_diff_inv) = (U256::ZERO, U256::ZERO);
+    let (mut power_of_v, mut r_outer) = (one, U256::ZERO);
+    let mut bases: Vec<&[u8]> = Vec::new();
+    let mut scalars = Vec::new();
+    for (i, (points, members)) in rotation_sets.iter().enumerate() {
+        let diffs: Vec<U256> = super_points.iter().filter(|p| !points.contains(p)).copied().collect();
+        let mut z_diff = vanishing(&diffs);
+        if i == 0 {
+            z_0 = vanishing(points);
+            let Some(inv) = fr_inv(z_diff) else {
+                return Ok(false);
+            };
+            z_0_diff_inv = inv;
+            z_diff = one;
+        } else {
+            z_diff = fr_mul(z_diff, z_0_diff_inv);
+        }
+
+        let scale = fr_mul(power_of_v, z_diff);
+        let (mut power_of_y, mut r_inner) = (one, U256::ZERO);
+        for (slot, evals) in members {
+            r_inner = fr_add(r_inner, fr_mul(power_of_y, interpolate(points, evals, u)));
+            for (base, scalar) in &slots[*slot] {
+                bases.push(&base[..]);
+                scalars.push(fr_mul(*scalar, fr_mul(scale, power_of_y)));
+            }
+            power_of_y = fr_mul(power_of_y, y);
+        }
+        r_outer = fr_add(r_outer, fr_mul(scale, r_inner));
+        power_of_v = fr_mul(power_of_v, v);
+    }
+
+    bases.push(h1);
+    scalars.push(fr_sub(U256::ZERO, z_0));
+    let commitment = g1_lincomb(curve, &bases, &scalars)?;
+    verify_kzg_opening(curve, &commitment, u, r_outer, h2, srs_g2)
+}
+
+/// Value at u of the polynomial through `(points[i], evals[i])` (distinct points)
+fn interpolate(points: &[U256], evals: &[U256], u: U256) -> U256 {
+    let one = U256::from(1);
+    points.iter().zip(evals).enumerate().fold(U256::ZERO, |acc, (i, (p_i, e_i))| {
+        let (num, den) = points.iter().enumerate().filter(|(j, _)| *j != i).fold((one, one), |(num, den), (_, p_j)| {
+            (fr_mul(num, fr_sub(u, *p_j)), fr_mul(den, fr_sub(*p_i, *p_j)))
+        });
+        fr_add(acc, fr_mul(*e_i, fr_mul(num, fr_inv(den).unwrap_or_default())))
+    })
+}
+
+fn read_scalars<H: TranscriptHash>(t: &mut TranscriptRead<'_, H>, count: usize) -> Result<Vec<U256>> {
+    (0..count).map(|_| t.read_scalar()).collect()
+}
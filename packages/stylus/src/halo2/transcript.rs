@@ -0,0 +1,317 @@
+//! halo2 Transcripts
+//!
+//! Reads a proof the way halo2's `TranscriptRead` does, so a proof written by
+//! `Blake2bWrite` or PSE's `Keccak256Write` (with `Challenge255`) replays to the
+//! same challenges:
+//! ```text
+//! point      H ← 0x01 ‖ x ‖ y          (affine, 32-byte little-endian each)
+//! scalar     H ← 0x02 ‖ s              (32-byte little-endian)
+//! challenge  H ← 0x00, then 64 bytes of output, little-endian mod r
+//! ```
+//! The state is never reset: a challenge is read from a copy of the running hash.
+//!
+//! | Hash | Initial state | Challenge output |
+//! |------|---------------|------------------|
+//! | Blake2b | Blake2b-512, personalization `Halo2-Transcript` | the digest |
+//! | Keccak256 | Keccak256 over `Halo2-Transcript` | `H(.. ‖ 0x0a) ‖ H(.. ‖ 0x0b)` |
+//!
+//! # Proof Encoding
+//! Points are halo2curves' compressed BN254 G1: x little-endian, with the
+//! parity of y in bit 6 of the last byte and bit 7 flagging the identity
+//! (x = 0). The transcript refuses the identity, as halo2 cannot absorb it
+//! either. Scalars are canonical little-endian Fr.
+
+use blake2::digest::core_api::{Buffer, UpdateCore, VariableOutputCore};
+use blake2::Blake2bVarCore;
+use sha3::{Digest, Keccak256};
+use stylus_sdk::alloy_primitives::{U256, U512};
+
+use crate::plonk::kzg::{Error, Result};
+use crate::utils::{fq_add, fq_mul, fq_neg, fq_sqrt, BN254_BASE_MODULUS, BN254_SCALAR_MODULUS};
+
+/// Personalization / initial input of both transcripts
+pub const TRANSCRIPT_LABEL: &[u8; 16] = b"Halo2-Transcript";
+
+/// Compressed G1 size
+pub const POINT_SIZE: usize = 32;
+
+/// Scalar size
+pub const SCALAR_SIZE: usize = 32;
+
+const PREFIX_CHALLENGE: u8 = 0;
+const PREFIX_POINT: u8 = 1;
+const PREFIX_SCALAR: u8 = 2;
+const KECCAK_PREFIX_CHALLENGE_LO: u8 = 10;
+const KECCAK_PREFIX_CHALLENGE_HI: u8 = 11;
+
+/// y parity flag (bit 6 of the last byte)
+const SIGN_BIT: u8 = 1 << 6;
+
+/// Identity flag (top bit of the last byte)
+const IDENTITY_BIT: u8 = 1 << 7;
+
+/// Running hash behind a halo2 transcript
+pub trait TranscriptHash: Clone {
+    fn init() -> Self;
+
+    fn update(&mut self, data: &[u8]);
+
+    /// Absorb the challenge prefix and return 64 bytes of output, leaving the
+    /// state as halo2 leaves it
+    fn squeeze(&mut self) -> [u8; 64];
+}
+
+/// halo2 `Blake2bRead`
+#[derive(Clone)]
+pub struct Blake2bHash {
+    core: Blake2bVarCore,
+    buffer: Buffer<Blake2bVarCore>,
+}
+
+impl TranscriptHash for Blake2bHash {
+    fn init() -> Self {
+        Self {
+            core: Blake2bVarCore::new_with_params(&[], TRANSCRIPT_LABEL, 0, 64),
+            buffer: Default::default(),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        let Self { core, buffer } = self;
+        buffer.digest_blocks(data, |blocks| core.update_blocks(blocks));
+    }
+
+    fn squeeze(&mut self) -> [u8; 64] {
+        self.update(&[PREFIX_CHALLENGE]);
+        let Self { mut core, mut buffer } = self.clone();
+        let mut out = Default::default();
+        core.finalize_variable_core(&mut buffer, &mut out);
+        out.into()
+    }
+}
+
+/// PSE halo2 `Keccak256Read`
+#[derive(Clone)]
+pub struct Keccak256Hash(Keccak256);
+
+impl TranscriptHash for Keccak256Hash {
+    fn init() -> Self {
+        Self(Keccak256::new_with_prefix(TRANSCRIPT_LABEL))
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn squeeze(&mut self) -> [u8; 64] {
+        self.0.update([PREFIX_CHALLENGE]);
+        let mut lo = self.0.clone();
+        let mut hi = self.0.clone();
+        lo.update([KECCAK_PREFIX_CHALLENGE_LO]);
+        hi.update([KECCAK_PREFIX_CHALLENGE_HI]);
+
+        let mut out = [0u8; 64];
+        out[..32].copy_from_slice(&lo.finalize());
+        out[32..].copy_from_slice(&hi.finalize());
+        out
+    }
+}
+
+/// Proof reader that absorbs everything it reads
+pub struct TranscriptRead<'a, H: TranscriptHash> {
+    proof: &'a [u8],
+    hash: H,
+}
+
+impl<'a, H: TranscriptHash> TranscriptRead<'a, H> {
+    pub fn new(proof: &'a [u8]) -> Self {
+        Self { proof, hash: H::init() }
+    }
+
+    /// Absorb a point that is not part of the proof
+    pub fn common_point(&mut self, point: &[u8; 64]) -> Result<()> {
+        if point.iter().all(|b| *b == 0) {
+            return Err(Error::InvalidInputSize);
+        }
+        let mut x = [0u8; 32];
+        let mut y = [0u8; 32];
+        x.copy_from_slice(&point[..32]);
+        y.copy_from_slice(&point[32..]);
+        x.reverse();
+        y.reverse();
+
+        self.hash.update(&[PREFIX_POINT]);
+        self.hash.update(&x);
+        self.hash.update(&y);
+        Ok(())
+    }
+
+    /// Absorb a scalar that is not part of the proof
+    pub fn common_scalar(&mut self, scalar: U256) {
+        self.hash.update(&[PREFIX_SCALAR]);
+        self.hash.update(&scalar.to_le_bytes::<32>());
+    }
+
+    /// Read and absorb a compressed point, returned uncompressed (64-byte BE)
+    pub fn read_point(&mut self) -> Result<[u8; 64]> {
+        let bytes = self.take(POINT_SIZE)?;
+        let point = decompress_point(bytes).ok_or(Error::InvalidInputSize)?;
+        self.common_point(&point)?;
+        Ok(point)
+    }
+
+    /// Read and absorb a canonical scalar
+    pub fn read_scalar(&mut self) -> Result<U256> {
+        let bytes = self.take(SCALAR_SIZE)?;
+        let scalar = U256::from_le_slice(bytes);
+        if scalar >= BN254_SCALAR_MODULUS {
+            return Err(Error::InvalidInputSize);
+        }
+        self.common_scalar(scalar);
+        Ok(scalar)
+    }
+
+    /// `Challenge255`: 64 output bytes, little-endian, reduced mod r
+    pub fn squeeze_challenge(&mut self) -> U256 {
+        let wide = U512::from_le_bytes(self.hash.squeeze());
+        let modulus = U512::from(BN254_SCALAR_MODULUS);
+        U256::from_le_slice(&(wide % modulus).to_le_bytes::<64>()[..32])
+    }
+
+    /// Ok once every proof byte has been read
+    pub fn finish(self) -> Result<()> {
+        if self.proof.is_empty() { Ok(()) } else { Err(Error::InvalidInputSize) }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.proof.len() < len {
+            return Err(Error::InvalidInputSize);
+        }
+        let (head, rest) = self.proof.split_at(len);
+        self.proof = rest;
+        Ok(head)
+    }
+}
+
+/// Decompress a halo2curves G1 point into the 64-byte big-endian encoding
+///
+/// The identity flag with x = 0 decodes to the identity (all zeros). Rejects
+/// the flag on any other x, a non-canonical x and an x with no point on the
+/// curve.
+pub fn decompress_point(bytes: &[u8]) -> Option<[u8; 64]> {
+    if bytes.len() != POINT_SIZE {
+        return None;
+    }
+
+    let mut x_le = [0u8; 32];
+    x_le.copy_from_slice(bytes);
+    let identity = x_le[31] & IDENTITY_BIT != 0;
+    let odd = x_le[31] & SIGN_BIT != 0;
+    x_le[31] &= !(IDENTITY_BIT | SIGN_BIT);
+    if identity {
+        return (!odd && x_le.iter().all(|b| *b == 0)).then_some([0u8; 64]);
+    }
+    let x = U256::from_le_bytes(x_le);
+    if x >= BN254_BASE_MODULUS {
+        return None;
+    }
+
+    let mut y = fq_sqrt(fq_add(fq_mul(fq_mul(x, x), x), U256::from(3)))?;
+    if y.bit(0) != odd {
+        y = fq_neg(y);
+    }
+
+    let mut out = [0u8; 64];
+    out[..32].copy_from_slice(&x.to_be_bytes::<32>());
+    out[32..].copy_from_slice(&y.to_be_bytes::<32>());
+    Some(out)
+}
+
+/// Compress a 64-byte big-endian G1 point (no curve check)
+pub fn compress_point(point: &[u8; 64]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    if point.iter().all(|b| *b == 0) {
+        out[31] = IDENTITY_BIT;
+        return out;
+    }
+    out.copy_from_slice(&point[..32]);
+    out.reverse();
+    if point[63] & 1 == 1 {
+        out[31] |= SIGN_BIT;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blake2::Blake2b512;
+
+    fn generator() -> [u8; 64] {
+        let mut point = [0u8; 64];
+        point[31] = 1;
+        point[63] = 2;
+        point
+    }
+
+    #[test]
+    fn test_blake2b_core_matches_blake2b512() {
+        // Same buffering as the transcript, without personalization
+        let data = [0x5au8; 300];
+        let mut core = Blake2bVarCore::new_with_params(&[], &[], 0, 64);
+        let mut buffer = Buffer::<Blake2bVarCore>::default();
+        buffer.digest_blocks(&data, |blocks| core.update_blocks(blocks));
+        let mut out = Default::default();
+        core.finalize_variable_core(&mut buffer, &mut out);
+        assert_eq!(out, Blake2b512::digest(data));
+    }
+
+    #[test]
+    fn test_keccak_challenge_layout() {
+        let mut transcript = TranscriptRead::<Keccak256Hash>::new(&[]);
+        transcript.common_scalar(U256::from(7));
+        let challenge = transcript.squeeze_challenge();
+
+        let mut state = Keccak256::new();
+        state.update(TRANSCRIPT_LABEL);
+        state.update([PREFIX_SCALAR]);
+        state.update(U256::from(7).to_le_bytes::<32>());
+        state.update([PREFIX_CHALLENGE]);
+        let mut wide = [0u8; 64];
+        wide[..32].copy_from_slice(&state.clone().chain_update([KECCAK_PREFIX_CHALLENGE_LO]).finalize());
+        wide[32..].copy_from_slice(&state.chain_update([KECCAK_PREFIX_CHALLENGE_HI]).finalize());
+        let expected = U512::from_le_bytes(wide) % U512::from(BN254_SCALAR_MODULUS);
+        assert_eq!(U512::from(challenge), expected);
+
+        // Squeezing twice continues from the same state
+        assert_ne!(transcript.squeeze_challenge(), challenge);
+    }
+
+    #[test]
+    fn test_point_compression_round_trip() {
+        let g = generator();
+        let compressed = compress_point(&g);
+        assert_eq!(compressed[0], 1);
+        assert_eq!(compressed[31] & SIGN_BIT, 0);
+        assert_eq!(decompress_point(&compressed), Some(g));
+
+        let mut neg = g;
+        neg[32..].copy_from_slice(&(BN254_BASE_MODULUS - U256::from(2)).to_be_bytes::<32>());
+        assert_eq!(decompress_point(&compress_point(&neg)), Some(neg));
+
+        // x = p is not canonical
+        let bad = BN254_BASE_MODULUS.to_le_bytes::<32>();
+        assert_eq!(decompress_point(&bad), None);
+
+        // The identity is x = 0 with the top bit set; the flag on another x
+        // is rejected, and the identity cannot enter the transcript
+        let identity = compress_point(&[0u8; 64]);
+        assert_eq!(identity[31], IDENTITY_BIT);
+        assert_eq!(decompress_point(&identity), Some([0u8; 64]));
+        let mut flagged = compressed;
+        flagged[31] |= IDENTITY_BIT;
+        assert_eq!(decompress_point(&flagged), None);
+        let mut transcript = TranscriptRead::<Blake2bHash>::new(&identity);
+        assert_eq!(transcript.read_point(), Err(Error::InvalidInputSize));
+    }
+}
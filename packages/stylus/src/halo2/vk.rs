@@ -0,0 +1,471 @@
+//! halo2 Verifying Key
+//!
+//! A compact encoding of a PSE halo2 `VerifyingKey<G1Affine>`: the pinned
+//! constraint system (queries, gates, lookups, permutation columns), the fixed
+//! and permutation commitments, and the transcript representation halo2
+//! hashes first. Selectors must already be compressed into fixed columns, as
+//! `keygen_vk` does.
+//!
+//! # Layout
+//! Integers are big-endian, scalars 32-byte big-endian, points 64-byte
+//! uncompressed (as everywhere else in this crate):
+//! ```text
+//! u32 k ‖ ω ‖ transcript_repr ‖ u32 degree
+//! u32 #fixed
+//! u32 #advice      ‖ phase (u8) per advice column
+//! u32 #instance    ‖ length (u32) per instance column
+//! u32 #challenges  ‖ phase (u8) per challenge
+//! advice, instance, fixed queries:   u32 count ‖ (u32 column ‖ i32 rotation)*
+//! permutation columns:               u32 count ‖ (u8 type ‖ u32 column)*
+//! gates:                             u32 count ‖ expression*
+//! lookups:                           u32 count ‖ (u32 m ‖ input expression × m ‖ table expression × m)*
+//! fixed commitments ‖ permutation commitments
+//! ```
+//! `degree` is `cs.degree()` and fixes the number of quotient pieces
+//! (`degree − 1`) and the permutation chunk size (`degree − 2`). Column types
+//! are 0 = advice, 1 = fixed, 2 = instance. Instance column lengths are fixed
+//! by the key, so public inputs are the concatenated columns.
+//!
+//! # Expressions
+//! halo2 `Expression`s in postfix order, evaluated with a stack, so deep sums
+//! need no recursion:
+//! ```text
+//! u32 #ops ‖ op*
+//! 0x00 constant (scalar)   0x01 fixed query (u32)   0x02 advice query (u32)
+//! 0x03 instance query (u32)   0x04 challenge (u32)
+//! 0x05 negated   0x06 sum   0x07 product   0x08 scaled (scalar)
+//! ```
+//! Queries are indices into the query lists above, as in halo2.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use stylus_sdk::alloy_primitives::U256;
+
+use crate::utils::{fr_add, fr_mul, fr_pow, fr_sub, BN254_SCALAR_MODULUS};
+
+/// BN254 Fr two-adicity
+const MAX_K: u32 = 28;
+
+/// Column kinds, as in halo2's `Any`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Advice = 0,
+    Fixed = 1,
+    Instance = 2,
+}
+
+/// A column read at `ω^rotation · x`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Query {
+    pub column: usize,
+    pub rotation: i32,
+}
+
+/// One postfix instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Constant(U256),
+    Fixed(usize),
+    Advice(usize),
+    Instance(usize),
+    Challenge(usize),
+    Negated,
+    Sum,
+    Product,
+    Scaled(U256),
+}
+
+/// A gate or lookup expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expression {
+    pub ops: Vec<Op>,
+    /// Stack depth needed to evaluate
+    depth: usize,
+}
+
+impl Expression {
+    /// Evaluate at x given the query evaluations
+    pub fn evaluate(&self, fixed: &[U256], advice: &[U256], instance: &[U256], challenges: &[U256]) -> U256 {
+        let mut stack = Vec::with_capacity(self.depth);
+        for op in &self.ops {
+            let value = match *op {
+                Op::Constant(c) => c,
+                Op::Fixed(i) => fixed[i],
+                Op::Advice(i) => advice[i],
+                Op::Instance(i) => instance[i],
+                Op::Challenge(i) => challenges[i],
+                Op::Negated => fr_sub(U256::ZERO, stack.pop().unwrap()),
+                Op::Scaled(c) => fr_mul(stack.pop().unwrap(), c),
+                Op::Sum | Op::Product => {
+                    let b = stack.pop().unwrap();
+                    let a = stack.pop().unwrap();
+                    if *op == Op::Sum { fr_add(a, b) } else { fr_mul(a, b) }
+                }
+            };
+            stack.push(value);
+        }
+        stack[0]
+    }
+}
+
+/// A halo2 lookup: `(input_0, …) ∈ (table_0, …)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lookup {
+    pub input: Vec<Expression>,
+    pub table: Vec<Expression>,
+}
+
+/// Parsed verifying key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyingKey {
+    pub k: u32,
+    pub n: usize,
+    pub omega: U256,
+    pub transcript_repr: U256,
+    pub degree: usize,
+    pub num_fixed: usize,
+    pub advice_phases: Vec<u8>,
+    pub instance_lengths: Vec<usize>,
+    pub challenge_phases: Vec<u8>,
+    pub advice_queries: Vec<Query>,
+    pub instance_queries: Vec<Query>,
+    pub fixed_queries: Vec<Query>,
+    /// Permutation columns with the index of their `Rotation::cur()` query
+    pub permutation_columns: Vec<(ColumnType, usize)>,
+    pub gates: Vec<Expression>,
+    pub lookups: Vec<Lookup>,
+    pub fixed_commitments: Vec<[u8; 64]>,
+    pub permutation_commitments: Vec<[u8; 64]>,
+}
+
+impl VerifyingKey {
+    /// Parse and validate a key
+    ///
+    /// Rejects a ω that is not a primitive 2^k-th root of unity, a degree
+    /// below 3, out-of-range columns, queries and challenges, malformed
+    /// expressions, permutation columns without a `Rotation::cur()` query,
+    /// challenges in a phase with no advice column, and instance columns
+    /// longer than the usable rows.
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        let mut r = Reader(bytes);
+
+        let k = r.u32()?;
+        if k == 0 || k > MAX_K {
+            return None;
+        }
+        let omega = r.scalar()?;
+        let transcript_repr = r.scalar()?;
+        let degree = r.u32()? as usize;
+        if degree < 3 {
+            return None;
+        }
+
+        let num_fixed = r.count(0)?;
+        let num_advice = r.count(1)?;
+        let advice_phases = r.list(num_advice, |r| r.u8())?;
+        let num_instance = r.count(4)?;
+        let instance_lengths = r.list(num_instance, |r| r.u32().map(|len| len as usize))?;
+        let num_challenges = r.count(1)?;
+        let challenge_phases = r.list(num_challenges, |r| r.u8())?;
+
+        let advice_queries = r.queries(num_advice)?;
+        let instance_queries = r.queries(num_instance)?;
+        let fixed_queries = r.queries(num_fixed)?;
+
+        let num_permutation = r.count(5)?;
+        let permutation_columns = r.list(num_permutation, |r| {
+            let (ty, column) = (r.u8()?, r.u32()? as usize);
+            let (ty, queries) = match ty {
+                0 => (ColumnType::Advice, &advice_queries),
+                1 => (ColumnType::Fixed, &fixed_queries),
+                2 => (ColumnType::Instance, &instance_queries),
+                _ => return None,
+            };
+            let index = queries.iter().position(|q| *q == Query { column, rotation: 0 })?;
+            Some((ty, index))
+        })?;
+
+        let limits = [fixed_queries.len(), advice_queries.len(), instance_queries.len(), num_challenges];
+        let num_gates = r.count(4)?;
+        let gates = r.list(num_gates, |r| r.expression(limits))?;
+        let num_lookups = r.count(8)?;
+        let lookups = r.list(num_lookups, |r| {
+            let m = r.count(8)?;
+            if m == 0 {
+                return None;
+            }
+            let input = r.list(m, |r| r.expression(limits))?;
+            let table = r.list(m, |r| r.expression(limits))?;
+            Some(Lookup { input, table })
+        })?;
+
+        let fixed_commitments = r.list(num_fixed, |r| r.point())?;
+        let permutation_commitments = r.list(num_permutation, |r| r.point())?;
+        if !r.0.is_empty() {
+            return None;
+        }
+
+        let n = 1usize << k;
+        let one = U256::from(1);
+        if fr_pow(omega, U256::from(n)) != one || fr_pow(omega, U256::from(n / 2)) == one {
+            return None;
+        }
+        let max_phase = advice_phases.iter().copied().max().unwrap_or(0);
+        if challenge_phases.iter().any(|p| *p > max_phase) {
+            return None;
+        }
+
+        let vk = Self {
+            k,
+            n,
+            omega,
+            transcript_repr,
+            degree,
+            num_fixed,
+            advice_phases,
+            instance_lengths,
+            challenge_phases,
+            advice_queries,
+            instance_queries,
+            fixed_queries,
+            permutation_columns,
+            gates,
+            lookups,
+            fixed_commitments,
+            permutation_commitments,
+        };
+        // halo2's prover refuses instances reaching into the blinding rows
+        let usable_rows = n.checked_sub(vk.blinding_factors() + 1)?;
+        vk.instance_lengths.iter().all(|len| *len <= usable_rows).then_some(vk)
+    }
+
+    /// halo2 `ConstraintSystem::blinding_factors`
+    pub fn blinding_factors(&self) -> usize {
+        let mut per_column = vec![0usize; self.advice_phases.len()];
+        for query in &self.advice_queries {
+            per_column[query.column] += 1;
+        }
+        let factors = per_column.into_iter().max().unwrap_or(1).max(3);
+        // One more for the multiopen, one against off-by-one errors
+        factors + 2
+    }
+
+    /// Permutation columns per grand product
+    pub fn chunk_len(&self) -> usize {
+        self.degree - 2
+    }
+
+    /// Number of permutation grand products
+    pub fn permutation_sets(&self) -> usize {
+        self.permutation_columns.len().div_ceil(self.chunk_len())
+    }
+
+    /// Last advice phase (phases run `0..=max_phase`)
+    pub fn max_phase(&self) -> u8 {
+        self.advice_phases.iter().copied().max().unwrap_or(0)
+    }
+}
+
+/// Cursor over the key bytes
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (head, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(head)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_be_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    fn i32(&mut self) -> Option<i32> {
+        Some(i32::from_be_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    fn scalar(&mut self) -> Option<U256> {
+        let value = U256::from_be_slice(self.take(32)?);
+        (value < BN254_SCALAR_MODULUS).then_some(value)
+    }
+
+    fn point(&mut self) -> Option<[u8; 64]> {
+        self.take(64)?.try_into().ok()
+    }
+
+    /// An element count, bounded by what the remaining bytes can hold at
+    /// `min_size` bytes per element
+    fn count(&mut self, min_size: usize) -> Option<usize> {
+        let count = self.u32()? as usize;
+        (count.saturating_mul(min_size) <= self.0.len()).then_some(count)
+    }
+
+    fn list<T>(&mut self, count: usize, mut item: impl FnMut(&mut Self) -> Option<T>) -> Option<Vec<T>> {
+        (0..count).map(|_| item(self)).collect()
+    }
+
+    fn queries(&mut self, num_columns: usize) -> Option<Vec<Query>> {
+        let count = self.count(8)?;
+        self.list(count, |r| {
+            let query = Query { column: r.u32()? as usize, rotation: r.i32()? };
+            (query.column < num_columns).then_some(query)
+        })
+    }
+
+    /// A postfix expression; `limits` bounds fixed, advice, instance query
+    /// and challenge indices
+    fn expression(&mut self, limits: [usize; 4]) -> Option<Expression> {
+        let count = self.count(1)?;
+        let mut ops = Vec::with_capacity(count);
+        let (mut height, mut depth) = (0usize, 0usize);
+        for _ in 0..count {
+            let op = match self.u8()? {
+                0 => Op::Constant(self.scalar()?),
+                tag @ 1..=4 => {
+                    let index = self.u32()? as usize;
+                    if index >= limits[tag as usize - 1] {
+                        return None;
+                    }
+                    [Op::Fixed, Op::Advice, Op::Instance, Op::Challenge][tag as usize - 1](index)
+                }
+                5 => Op::Negated,
+                6 => Op::Sum,
+                7 => Op::Product,
+                8 => Op::Scaled(self.scalar()?),
+                _ => return None,
+            };
+            let (pops, pushes) = match op {
+                Op::Negated | Op::Scaled(_) => (1, 1),
+                Op::Sum | Op::Product => (2, 1),
+                _ => (0, 1),
+            };
+            height = height.checked_sub(pops)? + pushes;
+            depth = depth.max(height);
+            ops.push(op);
+        }
+        (height == 1).then_some(Expression { ops, depth })
+    }
+}
+
+/// Builder for the key encoding (tests and tooling)
+#[derive(Default)]
+pub struct Encoder(pub Vec<u8>);
+
+impl Encoder {
+    pub fn u8(&mut self, value: u8) -> &mut Self {
+        self.0.push(value);
+        self
+    }
+
+    pub fn u32(&mut self, value: u32) -> &mut Self {
+        self.0.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    pub fn scalar(&mut self, value: U256) -> &mut Self {
+        self.0.extend_from_slice(&value.to_be_bytes::<32>());
+        self
+    }
+
+    pub fn point(&mut self, point: &[u8; 64]) -> &mut Self {
+        self.0.extend_from_slice(point);
+        self
+    }
+
+    pub fn queries(&mut self, queries: &[Query]) -> &mut Self {
+        self.u32(queries.len() as u32);
+        for query in queries {
+            self.u32(query.column as u32);
+            self.0.extend_from_slice(&query.rotation.to_be_bytes());
+        }
+        self
+    }
+
+    pub fn expression(&mut self, ops: &[Op]) -> &mut Self {
+        self.u32(ops.len() as u32);
+        for op in ops {
+            match *op {
+                Op::Constant(c) => self.u8(0).scalar(c),
+                Op::Fixed(i) => self.u8(1).u32(i as u32),
+                Op::Advice(i) => self.u8(2).u32(i as u32),
+                Op::Instance(i) => self.u8(3).u32(i as u32),
+                Op::Challenge(i) => self.u8(4).u32(i as u32),
+                Op::Negated => self.u8(5),
+                Op::Sum => self.u8(6),
+                Op::Product => self.u8(7),
+                Op::Scaled(c) => self.u8(8).scalar(c),
+            };
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// k = 3 key with one advice, one fixed and one instance column
+    fn encode(gate: &[Op]) -> Vec<u8> {
+        // ω = 7^((r−1)/8), a primitive 8th root (7 generates Fr*)
+        let omega = fr_pow(U256::from(7), (BN254_SCALAR_MODULUS - U256::from(1)) >> 3);
+        let mut e = Encoder::default();
+        e.u32(3).scalar(omega).scalar(U256::from(42)).u32(3);
+        e.u32(1).u32(1).u8(0).u32(1).u32(1).u32(0);
+        let cur = [Query { column: 0, rotation: 0 }];
+        e.queries(&[cur[0], Query { column: 0, rotation: -1 }]).queries(&cur).queries(&cur);
+        e.u32(1).u8(2).u32(0);
+        e.u32(1).expression(gate);
+        e.u32(0);
+        e.point(&[1u8; 64]).point(&[2u8; 64]);
+        e.0
+    }
+
+    #[test]
+    fn test_parse_and_evaluate() {
+        let three = U256::from(3);
+        // 3·(f − a(ω⁻¹x)) + 5·i
+        let gate = [
+            Op::Fixed(0),
+            Op::Advice(1),
+            Op::Negated,
+            Op::Sum,
+            Op::Scaled(three),
+            Op::Constant(U256::from(5)),
+            Op::Instance(0),
+            Op::Product,
+            Op::Sum,
+        ];
+        let vk = VerifyingKey::parse(&encode(&gate)).unwrap();
+        assert_eq!((vk.n, vk.degree, vk.transcript_repr), (8, 3, U256::from(42)));
+        assert_eq!(vk.permutation_columns, [(ColumnType::Instance, 0)]);
+        assert_eq!(vk.advice_queries[1], Query { column: 0, rotation: -1 });
+        assert_eq!(vk.gates[0].ops, gate);
+        assert_eq!(vk.blinding_factors(), 5);
+        assert_eq!(vk.fixed_commitments, [[1u8; 64]]);
+        assert_eq!(vk.permutation_commitments, [[2u8; 64]]);
+
+        let value = vk.gates[0].evaluate(&[U256::from(10)], &[U256::ZERO, U256::from(4)], &[U256::from(2)], &[]);
+        assert_eq!(value, U256::from(3 * 6 + 5 * 2));
+    }
+
+    #[test]
+    fn test_malformed_expressions_rejected() {
+        // Stack underflow, two results, out-of-range query and challenge
+        for gate in [
+            &[Op::Fixed(0), Op::Sum][..],
+            &[Op::Fixed(0), Op::Fixed(0)],
+            &[Op::Advice(2)],
+            &[Op::Challenge(0)],
+            &[],
+        ] {
+            assert_eq!(VerifyingKey::parse(&encode(gate)), None, "{gate:?}");
+        }
+    }
+}
@@ -0,0 +1,154 @@
+//! Hash Functions for Fiat-Shamir and Merkle Commitments
+//!
+//! The descriptor's `hash_function_id` names the hash a proof was generated
+//! with. Everything that hashes during verification (the PLONK `Transcript`,
+//! the STARK Merkle tree) is written against the `Hasher` trait, and
+//! `digest` / `hash_pair` pick the implementation at runtime.
+//!
+//! | `HashFunctionId` | Implementation |
+//! |------------------|----------------|
+//! | Poseidon | `PoseidonHasher` (BN254 Fr, circomlib `Poseidon(2)` for pairs) |
+//! | SHA256 | `Sha256Hasher` |
+//! | Blake3 | `Blake3Hasher` |
+//! | Keccak256 | `Keccak256Hasher` |
+//! | RescuePrime | not supported |
+//! | Blake2b | only as a halo2 transcript (`halo2::transcript`) |
+
+use crate::types::HashFunctionId;
+use sha2::Sha256;
+use sha3::{Digest, Keccak256};
+
+pub mod poseidon;
+
+pub use poseidon::PoseidonHasher;
+
+/// Incremental 32-byte hash
+pub trait Hasher: Default + Clone {
+    /// Descriptor ID of this hash
+    const ID: HashFunctionId;
+
+    /// Absorb bytes
+    fn update(&mut self, data: &[u8]);
+
+    /// Finish and return the digest
+    fn finalize(self) -> [u8; 32];
+
+    /// One-shot hash
+    fn digest(data: &[u8]) -> [u8; 32] {
+        let mut hasher = Self::default();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    /// Merkle parent of two nodes (`H(left || right)` unless overridden)
+    fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        let mut hasher = Self::default();
+        hasher.update(left);
+        hasher.update(right);
+        hasher.finalize()
+    }
+}
+
+/// Keccak256 (EVM native)
+#[derive(Debug, Clone, Default)]
+pub struct Keccak256Hasher(Keccak256);
+
+impl Hasher for Keccak256Hasher {
+    const ID: HashFunctionId = HashFunctionId::Keccak256;
+
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> [u8; 32] {
+        self.0.finalize().into()
+    }
+}
+
+/// SHA-256
+#[derive(Debug, Clone, Default)]
+pub struct Sha256Hasher(Sha256);
+
+impl Hasher for Sha256Hasher {
+    const ID: HashFunctionId = HashFunctionId::SHA256;
+
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> [u8; 32] {
+        self.0.finalize().into()
+    }
+}
+
+/// BLAKE3 (32-byte output)
+#[derive(Debug, Clone, Default)]
+pub struct Blake3Hasher(blake3::Hasher);
+
+impl Hasher for Blake3Hasher {
+    const ID: HashFunctionId = HashFunctionId::Blake3;
+
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> [u8; 32] {
+        *self.0.finalize().as_bytes()
+    }
+}
+
+/// Whether `digest` / `hash_pair` implement `id`
+pub fn is_supported(id: HashFunctionId) -> bool {
+    !matches!(id, HashFunctionId::RescuePrime | HashFunctionId::Blake2b)
+}
+
+/// Hash `data` with the hash named by `id` (`None` if unsupported)
+pub fn digest(id: HashFunctionId, data: &[u8]) -> Option<[u8; 32]> {
+    match id {
+        HashFunctionId::Poseidon => Some(PoseidonHasher::digest(data)),
+        HashFunctionId::SHA256 => Some(Sha256Hasher::digest(data)),
+        HashFunctionId::Blake3 => Some(Blake3Hasher::digest(data)),
+        HashFunctionId::Keccak256 => Some(Keccak256Hasher::digest(data)),
+        HashFunctionId::RescuePrime | HashFunctionId::Blake2b => None,
+    }
+}
+
+/// Merkle parent with the hash named by `id` (`None` if unsupported)
+pub fn hash_pair(id: HashFunctionId, left: &[u8; 32], right: &[u8; 32]) -> Option<[u8; 32]> {
+    match id {
+        HashFunctionId::Poseidon => Some(PoseidonHasher::hash_pair(left, right)),
+        HashFunctionId::SHA256 => Some(Sha256Hasher::hash_pair(left, right)),
+        HashFunctionId::Blake3 => Some(Blake3Hasher::hash_pair(left, right)),
+        HashFunctionId::Keccak256 => Some(Keccak256Hasher::hash_pair(left, right)),
+        HashFunctionId::RescuePrime | HashFunctionId::Blake2b => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_digests() {
+        assert_eq!(Keccak256Hasher::digest(b"abc"), <[u8; 32]>::from(Keccak256::digest(b"abc")));
+        assert_eq!(Sha256Hasher::digest(b"abc"), <[u8; 32]>::from(Sha256::digest(b"abc")));
+        assert_eq!(Blake3Hasher::digest(b"abc"), *blake3::hash(b"abc").as_bytes());
+    }
+
+    #[test]
+    fn test_runtime_dispatch() {
+        let (left, right) = ([1u8; 32], [2u8; 32]);
+        assert_eq!(digest(HashFunctionId::SHA256, b"abc"), Some(Sha256Hasher::digest(b"abc")));
+        assert_eq!(
+            hash_pair(HashFunctionId::Keccak256, &left, &right),
+            Some(Keccak256Hasher::digest(&[left, right].concat()))
+        );
+        assert_eq!(
+            hash_pair(HashFunctionId::Poseidon, &left, &right),
+            Some(PoseidonHasher::hash_pair(&left, &right))
+        );
+        assert_eq!(digest(HashFunctionId::RescuePrime, b"abc"), None);
+        assert!(!is_supported(HashFunctionId::RescuePrime));
+        assert!(!is_supported(HashFunctionId::Blake2b));
+    }
+}
@@ -22,6 +22,7 @@
 extern crate alloc;
 
 use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
 use stylus_sdk::{
     alloy_primitives::{FixedBytes, U256, U8, U32, Address},
     block,
@@ -30,6 +31,7 @@ use stylus_sdk::{
     msg,
 };
 // use wee_alloc::WeeAlloc;
+#[cfg(not(feature = "std"))]
 use mini_alloc::MiniAlloc;
 
 // Custom allocator for WASM environment
@@ -45,8 +47,13 @@ fn panic(_info: &core::panic::PanicInfo) -> ! {
     loop {}
 }
 
+// BN254 curve backends (precompiles or pure Rust) shared by the verifiers
+pub mod backend;
+
 pub mod groth16;
 pub mod plonk;
+// halo2 (PSE, KZG/SHPLONK) circuits
+pub mod halo2;
 
 // STARK implementation - transparent setup, post-quantum security
 pub mod stark;
@@ -61,17 +68,24 @@ pub mod verifier_traits;
 pub mod uzkv;
 pub mod utils;
 
+// Pluggable hashes (Keccak256, SHA-256, Blake3, Poseidon) selected by hash_function_id
+pub mod hash;
+
 // Cost-Aware Verification - gas estimation and path selection
 pub mod cost_model;
 
 // Security Formalization - dispatch validation and threat model
 pub mod security;
 
+// Artifact importers (snarkjs JSON) - host only
+#[cfg(feature = "std")]
+pub mod formats;
+
 
 // Re-export core types for convenience
 pub use types::{ProofType, PublicStatement, UniversalProof};
 
-// Re-export Universal Proof Descriptor (UPD v2) types
+// Re-export Universal Proof Descriptor (UPD v3) types
 pub use types::{
     UniversalProofDescriptor, CurveId, HashFunctionId, DescriptorError,
 };
@@ -122,6 +136,30 @@ pub enum Error {
     ProofTypeNotSupported,
     /// Invalid UniversalProof format (decode failed)
     InvalidProofFormat,
+    /// Public input is not a canonical field element (>= r)
+    ScalarOutOfRange,
+    /// G1 point is not on the curve
+    G1NotOnCurve,
+    /// G2 point is not on the twist
+    G2NotOnCurve,
+    /// G2 point is outside the prime-order subgroup
+    G2NotInSubgroup,
+    /// Verification key contains the point at infinity
+    IdentityPoint,
+    /// Curve ID unknown or not supported by the proof system
+    UnsupportedCurve,
+    /// Descriptor curve does not match the curve the VK was registered for
+    CurveMismatch,
+    /// Hash function ID unknown or not supported by the proof system
+    UnsupportedHashFunction,
+    /// Descriptor hash does not match the hash the VK was registered with
+    HashFunctionMismatch,
+    /// PLONK VK without a registered SRS (unknown srs_hash, or none given)
+    SrsNotRegistered,
+    /// PLONK VK domain exceeds the SRS max_degree
+    SrsDegreeExceeded,
+    /// VK already registered against another SRS
+    SrsMismatch,
 }
 
 impl core::fmt::Display for Error {
@@ -139,6 +177,18 @@ impl core::fmt::Display for Error {
             Error::InvalidProofType => write!(f, "Invalid proof type"),
             Error::ProofTypeNotSupported => write!(f, "Proof type not supported yet"),
             Error::InvalidProofFormat => write!(f, "Invalid UniversalProof format"),
+            Error::ScalarOutOfRange => write!(f, "Public input out of scalar field range"),
+            Error::G1NotOnCurve => write!(f, "G1 point not on curve"),
+            Error::G2NotOnCurve => write!(f, "G2 point not on curve"),
+            Error::G2NotInSubgroup => write!(f, "G2 point not in subgroup"),
+            Error::IdentityPoint => write!(f, "Verification key contains identity point"),
+            Error::UnsupportedCurve => write!(f, "Curve not supported"),
+            Error::CurveMismatch => write!(f, "Proof curve does not match verification key curve"),
+            Error::UnsupportedHashFunction => write!(f, "Hash function not supported"),
+            Error::HashFunctionMismatch => write!(f, "Proof hash function does not match verification key hash function"),
+            Error::SrsNotRegistered => write!(f, "SRS not registered"),
+            Error::SrsDegreeExceeded => write!(f, "Verification key domain exceeds SRS max degree"),
+            Error::SrsMismatch => write!(f, "Verification key is bound to another SRS"),
         }
     }
 }
@@ -153,14 +203,19 @@ impl From<groth16::Error> for Error {
             groth16::Error::PrecompileFailed => Error::VerificationFailed,
             groth16::Error::InvalidVerificationKey => Error::InvalidVerificationKey,
             groth16::Error::DeserializationError => Error::DeserializationError,
+            groth16::Error::ScalarOutOfRange => Error::ScalarOutOfRange,
+            groth16::Error::G1NotOnCurve => Error::G1NotOnCurve,
+            groth16::Error::G2NotOnCurve => Error::G2NotOnCurve,
+            groth16::Error::G2NotInSubgroup => Error::G2NotInSubgroup,
+            groth16::Error::IdentityPoint => Error::IdentityPoint,
         }
     }
 }
 
 // Implement Into<Vec<u8>> for Error to satisfy stylus-sdk EncodableReturnType constraint
-impl Into<Vec<u8>> for Error {
-    fn into(self) -> Vec<u8> {
-        match self {
+impl From<Error> for Vec<u8> {
+    fn from(err: Error) -> Self {
+        match err {
             Error::DeserializationError => b"Failed to deserialize proof".to_vec(),
             Error::MalformedProof => b"Proof contains invalid curve points".to_vec(),
             Error::InvalidVerificationKey => b"Invalid verification key".to_vec(),
@@ -173,6 +228,18 @@ impl Into<Vec<u8>> for Error {
             Error::InvalidProofType => b"Invalid proof type".to_vec(),
             Error::ProofTypeNotSupported => b"Proof type not supported yet".to_vec(),
             Error::InvalidProofFormat => b"Invalid UniversalProof format".to_vec(),
+            Error::ScalarOutOfRange => b"Public input out of scalar field range".to_vec(),
+            Error::G1NotOnCurve => b"G1 point not on curve".to_vec(),
+            Error::G2NotOnCurve => b"G2 point not on curve".to_vec(),
+            Error::G2NotInSubgroup => b"G2 point not in subgroup".to_vec(),
+            Error::IdentityPoint => b"Verification key contains identity point".to_vec(),
+            Error::UnsupportedCurve => b"Curve not supported".to_vec(),
+            Error::CurveMismatch => b"Proof curve does not match verification key curve".to_vec(),
+            Error::UnsupportedHashFunction => b"Hash function not supported".to_vec(),
+            Error::HashFunctionMismatch => b"Proof hash function does not match verification key hash function".to_vec(),
+            Error::SrsNotRegistered => b"SRS not registered".to_vec(),
+            Error::SrsDegreeExceeded => b"Verification key domain exceeds SRS max degree".to_vec(),
+            Error::SrsMismatch => b"Verification key is bound to another SRS".to_vec(),
         }
     }
 }
@@ -283,6 +350,44 @@ fn emit_vk_registered_event(
     ).ok();
 }
 
+/// Helper function to emit VKSrsLinked event
+///
+/// Event signature: VKSrsLinked(uint8,uint32,bytes32,bytes32)
+/// Keccak256: 0x80cadce65fbc81ab00f66f0806adddb5faf3d5870ba463ef1dbc1b41664e4c79
+///
+/// Indexed topics:
+/// - topic1: proof_type (uint8)
+/// - topic2: program_id (uint32)
+/// - topic3: vk_hash (bytes32)
+#[cfg(not(feature = "std"))]
+fn emit_vk_srs_linked_event(
+    proof_type: u8,
+    program_id: u32,
+    vk_hash: FixedBytes<32>,
+    srs_hash: FixedBytes<32>,
+) {
+    // Event signature: VKSrsLinked(uint8,uint32,bytes32,bytes32)
+    let topic0 = FixedBytes::<32>::from([
+        0x80, 0xca, 0xdc, 0xe6, 0x5f, 0xbc, 0x81, 0xab,
+        0x00, 0xf6, 0x6f, 0x08, 0x06, 0xad, 0xdd, 0xb5,
+        0xfa, 0xf3, 0xd5, 0x87, 0x0b, 0xa4, 0x63, 0xef,
+        0x1d, 0xbc, 0x1b, 0x41, 0x66, 0x4e, 0x4c, 0x79,
+    ]);
+    
+    // Indexed topics
+    let mut topic1 = [0u8; 32];
+    topic1[31] = proof_type;
+    
+    let mut topic2 = [0u8; 32];
+    topic2[28..32].copy_from_slice(&program_id.to_be_bytes());
+    
+    // Non-indexed data: srs_hash (bytes32)
+    evm::raw_log(
+        &[topic0.into(), FixedBytes::from(topic1), FixedBytes::from(topic2), vk_hash],
+        srs_hash.as_slice(),
+    ).ok();
+}
+
 // Stylus contract storage definition using ERC-7201 namespaced storage
 #[cfg(not(feature = "std"))]
 sol_storage! {
@@ -303,6 +408,17 @@ sol_storage! {
         // proofType => programId => vkHash => precomputedData
         mapping(uint8 => mapping(uint32 => mapping(bytes32 => bytes))) precomputed_data;
         
+        // Curve each VK is defined over (CurveId), same key path as vk_registry.
+        // Zero (BN254) for keys registered before curves were tracked.
+        mapping(uint8 => mapping(uint32 => mapping(bytes32 => uint8))) vk_curve;
+        
+        // Transcript hash of each VK (HashFunctionId + 1), same key path as vk_registry.
+        // Zero (read as Keccak256) for keys registered before hashes were tracked.
+        mapping(uint8 => mapping(uint32 => mapping(bytes32 => uint8))) vk_hash_function;
+        
+        // SRS each PLONK VK is bound to (srs_hash), same key path as vk_registry
+        mapping(uint8 => mapping(uint32 => mapping(bytes32 => bytes32))) vk_srs;
+        
         // === Legacy storage (kept for backward compatibility, deprecated) ===
         // Registered verification keys (vkHash => vkData) - OLD, use vk_registry instead
         mapping(bytes32 => bytes) verification_keys;
@@ -334,6 +450,9 @@ sol_storage! {
         
         // SRS Metadata: srs_hash => max_circuit_size (log2)
         mapping(bytes32 => uint8) srs_max_degree;
+        
+        // SRS [τ]₂ (128 bytes, EIP-197), read by PLONK verification
+        mapping(bytes32 => bytes) srs_g2;
     }
 }
 
@@ -343,10 +462,11 @@ sol_storage! {
 impl UZKVContract {
     /// Verify a Groth16 proof with gas optimization
     ///
-    /// Uses precomputed e(α, β) pairing to save ~80,000 gas per verification.
-    /// Falls back to standard verification if precomputed pairing not available.
+    /// Uses the prepared VK cached at registration (negated G2 points, IC bases),
+    /// skipping VK parsing and G2 negation on every call.
+    /// Falls back to standard verification if no prepared VK is available.
     ///
-    /// @param proof - Serialized Groth16 proof (compressed format)
+    /// @param proof - Serialized Groth16 proof (256 bytes uncompressed or 128 compressed)
     /// @param public_inputs - Serialized public input field elements
     /// @param vk_hash - Hash of the registered verification key
     /// @return true if proof is valid, reverts if invalid or error
@@ -374,7 +494,7 @@ impl UZKVContract {
         let precomputed_pairing = precomputed_storage.get_bytes();
         
         let is_valid = if !precomputed_pairing.is_empty() {
-            // Use optimized verification with precomputed e(α, β) (~80k gas savings)
+            // Use the prepared VK cached at registration
             groth16::verify_with_precomputed(&*self, &proof, &public_inputs, &vk_data, &precomputed_pairing)?
         } else {
             // Fall back to standard verification (computes all 4 pairings)
@@ -402,7 +522,7 @@ impl UZKVContract {
     /// - Each program_id has isolated VK namespace
     /// - Multiple circuits can coexist per proof type
     ///
-    /// @param proof_type - Proof system type (0=Groth16, 1=PLONK, 2=STARK)
+    /// @param proof_type - Proof system type (0=Groth16, 1=PLONK, 2=STARK, 3=gnark Groth16, 4=snarkjs PLONK, 5=fflonk, 6=halo2, 7=UltraPLONK)
     /// @param program_id - Circuit identifier (isolates VK namespaces)
     /// @param vk - Serialized verification key
     /// @return vkHash - Keccak256 hash of the VK
@@ -412,60 +532,106 @@ impl UZKVContract {
         program_id: u32,
         vk: Vec<u8>,
     ) -> Result<[u8; 32]> {
-        // Validate proof type
-        let ptype = ProofType::from_u8(proof_type).ok_or(Error::InvalidProofType)?;
-
-        // Compute VK hash (Keccak256)
-        let vk_hash = keccak256(&vk);
-        let vk_hash_fixed = FixedBytes::from(vk_hash);
-
-        // Get nested storage references (need to convert u8/u32 to Uint)
-        let proof_type_uint = U8::from(proof_type);
-        let program_id_uint = U32::from(program_id);
-        
-        let mut proof_type_storage = self.vk_registry.setter(proof_type_uint);
-        let mut program_storage = proof_type_storage.setter(program_id_uint);
-        let mut status_proof_type = self.vk_registry_status.setter(proof_type_uint);
-        let mut status_program = status_proof_type.setter(program_id_uint);
+        self.register_vk_universal_on_curve(proof_type, program_id, CurveId::BN254 as u8, vk)
+    }
 
-        // Check if already registered (idempotent operation)
-        if !status_program.get(vk_hash_fixed) {
-            // Store VK data with triple binding
-            program_storage.setter(vk_hash_fixed).set_bytes(&vk);
-            status_program.insert(vk_hash_fixed, true);
+    /// Register a verification key for a specific curve
+    ///
+    /// Same binding as `register_vk_universal`; the curve is stored alongside the
+    /// VK and every proof must then claim the same curve (see `verify_universal`).
+    ///
+    /// @param proof_type - Proof system type (0=Groth16, 1=PLONK, 2=STARK, 3=gnark Groth16, 4=snarkjs PLONK, 5=fflonk, 6=halo2, 7=UltraPLONK)
+    /// @param program_id - Circuit identifier (isolates VK namespaces)
+    /// @param curve_id - Curve the VK is defined over (0=BN254, 1=BLS12-381)
+    /// @param vk - Serialized verification key
+    /// @return vkHash - Keccak256 hash of the VK
+    pub fn register_vk_universal_on_curve(
+        &mut self,
+        proof_type: u8,
+        program_id: u32,
+        curve_id: u8,
+        vk: Vec<u8>,
+    ) -> Result<[u8; 32]> {
+        self.register_vk_universal_with_hash(
+            proof_type,
+            program_id,
+            curve_id,
+            HashFunctionId::Keccak256 as u8,
+            vk,
+        )
+    }
 
-            // Precompute e(α, β) pairing for Groth16 gas optimization
-            // This is a one-time cost (~100k gas) that saves ~80k gas per verification
-            if matches!(ptype, ProofType::Groth16) {
-                match groth16::compute_precomputed_pairing(&vk) {
-                    Ok(precomputed_pairing) => {
-                        let mut precomp_proof_type = self.precomputed_data.setter(proof_type_uint);
-                        let mut precomp_program = precomp_proof_type.setter(program_id_uint);
-                        precomp_program.setter(vk_hash_fixed).set_bytes(&precomputed_pairing);
-                    }
-                    Err(_) => {
-                        // If precomputation fails, continue without optimization
-                        // Contract will fall back to standard verification
-                    }
-                }
-            }
+    /// Register a verification key for a specific curve and transcript hash
+    ///
+    /// Same binding as `register_vk_universal_on_curve`; the hash is stored too,
+    /// and every proof's descriptor must then name the same `hash_function_id`.
+    /// Groth16 keys accept any hash (no Fiat-Shamir transcript). PLONK keys
+    /// are rejected here; register them with `register_plonk_vk`.
+    ///
+    /// @param proof_type - Proof system type (0=Groth16, 1=PLONK, 2=STARK, 3=gnark Groth16, 4=snarkjs PLONK, 5=fflonk, 6=halo2, 7=UltraPLONK)
+    /// @param program_id - Circuit identifier (isolates VK namespaces)
+    /// @param curve_id - Curve the VK is defined over (0=BN254, 1=BLS12-381)
+    /// @param hash_function_id - Transcript hash (0=Poseidon, 1=SHA256, 2=Blake3, 3=Keccak256)
+    /// @param vk - Serialized verification key
+    /// @return vkHash - Keccak256 hash of the VK
+    pub fn register_vk_universal_with_hash(
+        &mut self,
+        proof_type: u8,
+        program_id: u32,
+        curve_id: u8,
+        hash_function_id: u8,
+        vk: Vec<u8>,
+    ) -> Result<[u8; 32]> {
+        self.register_vk_bound(proof_type, program_id, curve_id, hash_function_id, None, vk)
+    }
 
-            // Emit VKRegistered event for monitoring
-            emit_vk_registered_event(proof_type, program_id, vk_hash_fixed, msg::sender());
+    /// Register a PLONK verification key bound to a registered SRS
+    ///
+    /// The VK uses the `plonk` (or `fflonk`, `halo2`, `ultra`) layout without the trailing `[τ]₂`: the verifier
+    /// reads `[τ]₂` from the SRS registry, so a VK cannot bring its own. The VK
+    /// domain must fit the SRS (`n ≤ 2^max_degree`). Emits `VKRegistered` and
+    /// `VKSrsLinked`.
+    ///
+    /// @param proof_type - 1=PLONK, 4=snarkjs PLONK, 5=fflonk, 6=halo2, 7=UltraPLONK
+    /// @param program_id - Circuit identifier (isolates VK namespaces)
+    /// @param hash_function_id - Transcript hash (0=Poseidon, 1=SHA256, 2=Blake3, 3=Keccak256, 5=Blake2b)
+    /// @param srs_hash - Hash returned by `register_srs`
+    /// @param vk - Serialized verification key (without `[τ]₂`)
+    /// @return vkHash - Keccak256 hash of the VK
+    pub fn register_plonk_vk(
+        &mut self,
+        proof_type: u8,
+        program_id: u32,
+        hash_function_id: u8,
+        srs_hash: [u8; 32],
+        vk: Vec<u8>,
+    ) -> Result<[u8; 32]> {
+        if !ProofType::from_u8(proof_type).is_some_and(ProofType::uses_srs) {
+            return Err(Error::InvalidProofType);
         }
-
-        Ok(vk_hash)
+        self.register_vk_bound(
+            proof_type,
+            program_id,
+            CurveId::BN254 as u8,
+            hash_function_id,
+            Some(FixedBytes::from(srs_hash)),
+            vk,
+        )
     }
 
     /// Register a verification key with gas optimization precomputation
     /// DEPRECATED: Use register_vk_universal() instead for proper security binding
     ///
-    /// Computes and stores e(α, β) pairing for ~80k gas savings per verification.
-    /// Break-even point: 2 verifications.
+    /// Computes and stores the prepared VK (negated G2 points, IC bases) used by
+    /// `verify_groth16`. Keys that fail validation are rejected with their
+    /// `groth16::Error` and never stored.
     ///
     /// @param vk - Serialized verification key
     /// @return vkHash - Keccak256 hash of the VK
     pub fn register_vk(&mut self, vk: Vec<u8>) -> Result<[u8; 32]> {
+        // Reject malformed keys before anything is stored
+        let precomputed_pairing = groth16::compute_precomputed_pairing(&vk)?;
+
         // Compute VK hash (Keccak256)
         let vk_hash = keccak256(&vk);
         let vk_hash_fixed = FixedBytes::from(vk_hash);
@@ -476,17 +642,8 @@ impl UZKVContract {
             self.verification_keys.setter(vk_hash_fixed).set_bytes(&vk);
             self.vk_registered.insert(vk_hash_fixed, true);
 
-            // Precompute e(α, β) pairing for gas optimization
-            // This is a one-time cost (~100k gas) that saves ~80k gas per verification
-            match groth16::compute_precomputed_pairing(&vk) {
-                Ok(precomputed_pairing) => {
-                    self.precomputed_pairings.setter(vk_hash_fixed).set_bytes(&precomputed_pairing);
-                }
-                Err(_) => {
-                    // If precomputation fails, continue without optimization
-                    // Contract will fall back to standard verification
-                }
-            }
+            // Cache the prepared VK so verification can skip VK parsing
+            self.precomputed_pairings.setter(vk_hash_fixed).set_bytes(&precomputed_pairing);
         }
 
         Ok(vk_hash)
@@ -494,12 +651,16 @@ impl UZKVContract {
 
     /// Register a PLONK Structured Reference String (SRS) from Powers of Tau ceremony
     ///
-    /// The SRS contains the powers of tau [τ^0, τ^1, ..., τ^n] in G1 and [τ^0, τ^1] in G2.
+    /// The SRS contains the powers of tau [τ^0, τ^1, ..., τ^n] in G1 and [τ]₂ in G2.
     /// This is a one-time operation per circuit size class.
     ///
-    /// ## Format
+    /// ## Format (see `plonk::srs`)
     /// - First 8 bytes: max_degree (u64 little-endian) - log2 of max circuit size
-    /// - Remaining bytes: serialized G1/G2 points (compressed)
+    /// - Next 128 bytes: [τ]₂ (EIP-197), checked to be in the G2 subgroup
+    /// - Remaining bytes: G1 powers (64 bytes each, uncompressed)
+    ///
+    /// PLONK VKs reference the SRS by hash (`register_plonk_vk`) and are verified
+    /// against its [τ]₂.
     ///
     /// ## Security
     /// - SRS must come from trusted Powers of Tau ceremony
@@ -509,24 +670,12 @@ impl UZKVContract {
     /// @param srs_bytes - Serialized SRS (Powers of Tau) data
     /// @return srs_hash - Keccak256 hash of the SRS
     pub fn register_srs(&mut self, srs_bytes: Vec<u8>) -> Result<[u8; 32]> {
-        // Validate minimum size (8 bytes header + at least some data)
-        if srs_bytes.len() < 72 { // 8 bytes header + 64 bytes min for one G1 point
-            return Err(Error::InvalidInputSize);
-        }
-        
-        // Maximum SRS size: 64MB (supports circuits up to 2^26)
-        if srs_bytes.len() > 64 * 1024 * 1024 {
-            return Err(Error::InvalidInputSize);
-        }
-        
-        // Parse max_degree from first 8 bytes
-        let max_degree_bytes: [u8; 8] = srs_bytes[0..8].try_into()
-            .map_err(|_| Error::DeserializationError)?;
-        let max_degree = u64::from_le_bytes(max_degree_bytes);
+        // Sizes (header, [τ]₂, whole G1 powers, at most 64MB) and max_degree in 1..=30
+        let srs = plonk::srs::Srs::parse(&srs_bytes).map_err(|_| Error::InvalidInputSize)?;
         
-        // Validate max_degree is reasonable (1 to 30)
-        if max_degree < 1 || max_degree > 30 {
-            return Err(Error::InvalidInputSize);
+        // [τ]₂ feeds every PLONK pairing check: on the twist, in the subgroup, not the identity
+        if groth16::curve::validate_g2(srs.g2_tau)? {
+            return Err(Error::IdentityPoint);
         }
         
         // Compute SRS hash
@@ -538,7 +687,8 @@ impl UZKVContract {
             // Store SRS data
             self.srs_registry.setter(srs_hash_fixed).set_bytes(&srs_bytes);
             self.srs_registered.insert(srs_hash_fixed, true);
-            self.srs_max_degree.insert(srs_hash_fixed, U8::from(max_degree as u8));
+            self.srs_max_degree.insert(srs_hash_fixed, U8::from(srs.max_degree));
+            self.srs_g2.setter(srs_hash_fixed).set_bytes(srs.g2_tau);
         }
         
         Ok(srs_hash)
@@ -585,103 +735,34 @@ impl UZKVContract {
     /// 2. Checks that VK is registered for (proof_type, program_id, vk_hash)
     /// 3. Prevents user from submitting Groth16 proof with PLONK VK hash
     /// 4. Enforces circuit isolation via program_id
+    /// 5. Rejects VKs registered on another curve (the proof is taken as BN254)
+    /// 6. Uses the transcript hash the VK was registered with
     ///
     /// @param universal_proof_bytes - Encoded UniversalProof (46+ byte header + proof + inputs)
     /// @return true if proof is valid
     pub fn verify_universal(&mut self, universal_proof_bytes: Vec<u8>) -> Result<bool> {
-        // Check if contract is paused
-        if self.paused.get() {
-            return Err(Error::ContractPaused);
-        }
-
-        // Decode UniversalProof from bytes
-        let universal_proof = UniversalProof::decode(&universal_proof_bytes)
-            .ok_or(Error::InvalidProofFormat)?;
-
-        // Validate version (only v1 supported)
-        if universal_proof.version != 1 {
-            return Err(Error::InvalidProofFormat);
-        }
-
-        // Get proof type enum and convert to u8 for storage lookups
-        let ptype = universal_proof.proof_type;
-        let proof_type_u8 = ptype.to_u8();
-
-        // Compute VK hash from universal_proof
-        let vk_hash_fixed = FixedBytes::from(universal_proof.vk_hash);
-
-        // === SECURITY: Validate (proofType, programId, vkHash) triple binding ===
-        let proof_type_uint = U8::from(proof_type_u8);
-        let program_id_uint = U32::from(universal_proof.program_id);
-        
-        let proof_type_storage = self.vk_registry.getter(proof_type_uint);
-        let program_storage = proof_type_storage.getter(program_id_uint);
-        let vk_storage = program_storage.get(vk_hash_fixed);
-        
-        if vk_storage.is_empty() {
-            return Err(Error::VKNotRegistered);
-        }
-        let vk_data = vk_storage.get_bytes();
-
-        // Route to appropriate verifier based on proof type via UZKV dispatcher
-        // This ensures consistent behavior, gas tracking, and security checks
-        
-        // 1. Construct Security Descriptor
-        let curve_id = crate::types::CurveId::BN254; // Default to BN254 for now
-        
-        let descriptor = crate::types::UniversalProofDescriptor::new(
-            proof_type_u8,
-            curve_id,
-            crate::types::HashFunctionId::Keccak256, // Default
-            0, // No recursion yet
-            (universal_proof.public_inputs_bytes.len() / 32) as u16, // Approx input count
-            universal_proof.proof_bytes.len() as u32,
-            universal_proof.vk_hash,
-            universal_proof.vk_hash, // Use vk_hash as circuit_id proxy for now
-        );
-
-        // 2. Construct RegisteredVK for validation
-        let registered_vk = crate::security::RegisteredVK::new(
-            ptype,
-            universal_proof.vk_hash,
-            universal_proof.vk_hash, // circuit_id proxy
-            curve_id,
-            1024, // Max inputs default
-        );
+        self.verify_universal_proof(&universal_proof_bytes, None)
+    }
 
-        // 3. Run Security Validation
-        let validator = crate::security::DispatchValidator::new();
-        validator.validate_proof_type_binding(&descriptor, &registered_vk)
+    /// Verify a UniversalProof against a caller-supplied descriptor (UPD v3)
+    ///
+    /// Same checks as `verify_universal`, but the descriptor states the curve
+    /// (routing the proof to that curve's verifier) and must agree with the proof:
+    /// matching proof system, vk_commitment and proof_length, and circuit_id equal
+    /// to the VK hash. A curve other than the VK's registered curve is rejected,
+    /// as is a `hash_function_id` other than the VK's registered hash (PLONK, STARK).
+    ///
+    /// @param descriptor - Encoded UniversalProofDescriptor (76 bytes)
+    /// @param universal_proof_bytes - Encoded UniversalProof
+    /// @return true if proof is valid
+    pub fn verify_universal_with_descriptor(
+        &mut self,
+        descriptor: Vec<u8>,
+        universal_proof_bytes: Vec<u8>,
+    ) -> Result<bool> {
+        let descriptor = UniversalProofDescriptor::decode(&descriptor)
             .map_err(|_| Error::InvalidProofFormat)?;
-
-        // 4. Delegate to Universal Verifier (with cost check)
-        let gas_budget = stylus_sdk::evm::gas_left();
-        
-        let is_valid = crate::uzkv::verify_universal_proof_with_budget(
-            &*self,
-            proof_type_u8,
-            &universal_proof.proof_bytes,
-            &universal_proof.public_inputs_bytes,
-            &vk_data,
-            gas_budget
-        ).map_err(|_| Error::VerificationFailed)?;
-
-        // Increment verification counter for valid proofs
-        if is_valid {
-            let count = self.verification_count.get();
-            self.verification_count.set(count + U256::from(1));
-        }
-
-        // Emit ProofVerified event for monitoring and indexing
-        emit_proof_verified_event(
-            proof_type_u8,
-            universal_proof.program_id,
-            vk_hash_fixed,
-            msg::sender(),
-            is_valid,
-        );
-
-        Ok(is_valid)
+        self.verify_universal_proof(&universal_proof_bytes, Some(descriptor))
     }
 
     /// Universal verify - routes to appropriate verifier based on proof type
@@ -691,11 +772,13 @@ impl UZKVContract {
     /// - Groth16 (type 0): Trusted setup, ~60k gas
     /// - PLONK (type 1): Universal setup, ~120k gas (TODO: not yet enabled)
     /// - STARK (type 2): Transparent, ~280k gas (TODO: not yet enabled)
+    /// - gnark Groth16 (type 3): Trusted setup, BSB22 commitments, ~320k gas
+    /// - snarkjs PLONK (type 4): as PLONK (TODO: not yet enabled)
     ///
     /// WARNING: This function uses legacy storage without (proofType, programId) binding.
     /// It is vulnerable to VK substitution attacks. Use verify_universal() instead.
     ///
-    /// @param proof_type - Proof system type (0=Groth16, 1=PLONK, 2=STARK)
+    /// @param proof_type - Proof system type (0=Groth16, 1=PLONK, 2=STARK, 3=gnark Groth16, 4=snarkjs PLONK, 5=fflonk, 6=halo2, 7=UltraPLONK)
     /// @param proof - Serialized proof
     /// @param public_inputs - Serialized public inputs
     /// @param vk_hash - Verification key hash (not used for STARK)
@@ -736,6 +819,13 @@ impl UZKVContract {
                     groth16::verify(&*self, &proof, &public_inputs, &vk_data)?
                 }
             }
+            ProofType::Groth16Gnark => {
+                let vk_storage = self.verification_keys.get(FixedBytes::from(vk_hash));
+                if vk_storage.is_empty() {
+                    return Err(Error::VKNotRegistered);
+                }
+                groth16::gnark::verify(&*self, &proof, &public_inputs, &vk_storage.get_bytes())?
+            }
             ProofType::PLONK => {
                 // PLONK verification (universal setup)
                 /*
@@ -751,6 +841,10 @@ impl UZKVContract {
                 */
                 return Err(Error::ProofTypeNotSupported);
             }
+            ProofType::PlonkSnarkjs | ProofType::Fflonk | ProofType::Halo2 | ProofType::UltraPlonk => {
+                // Enabled through verify_universal only, as PLONK
+                return Err(Error::ProofTypeNotSupported);
+            }
             ProofType::STARK => {
                 // STARK doesn't use VKs (transparent setup)
                 /*
@@ -772,12 +866,22 @@ impl UZKVContract {
 
     /// Register a verification key for a specific proof type
     ///
-    /// @param proof_type - Proof system type (0=Groth16, 1=PLONK, 2=STARK)
+    /// @param proof_type - Proof system type (0=Groth16, 1=PLONK, 2=STARK, 3=gnark Groth16, 4=snarkjs PLONK, 5=fflonk, 6=halo2, 7=UltraPLONK)
     /// @param vk - Serialized verification key
     /// @return vkHash - Keccak256 hash of the VK
     pub fn register_vk_typed(&mut self, proof_type: u8, vk: Vec<u8>) -> Result<[u8; 32]> {
         let ptype = ProofType::from_u8(proof_type).ok_or(Error::InvalidProofType)?;
 
+        // Reject malformed Groth16 keys before anything is stored
+        let groth16_prepared = match ptype {
+            ProofType::Groth16 => Some(groth16::compute_precomputed_pairing(&vk)?),
+            ProofType::Groth16Gnark => {
+                groth16::gnark::VerifyingKey::decode(&vk)?;
+                None
+            }
+            _ => None,
+        };
+
         // Compute VK hash
         let vk_hash = keccak256(&vk);
         let vk_hash_fixed = FixedBytes::from(vk_hash);
@@ -791,12 +895,19 @@ impl UZKVContract {
             // Precompute optimizations based on proof type
             match ptype {
                 ProofType::Groth16 => {
-                    // Precompute e(α, β) pairing for gas savings
-                    if let Ok(precomputed) = groth16::compute_precomputed_pairing(&vk) {
+                    // Cache the prepared VK for gas savings
+                    if let Some(precomputed) = groth16_prepared {
                         self.precomputed_pairings.setter(vk_hash_fixed).set_bytes(&precomputed);
                     }
                 }
-                ProofType::PLONK => {
+                ProofType::Groth16Gnark => {
+                    // Verified straight from the gnark encoding, nothing to cache
+                }
+                ProofType::PLONK
+                | ProofType::PlonkSnarkjs
+                | ProofType::Fflonk
+                | ProofType::Halo2
+                | ProofType::UltraPlonk => {
                     // TODO: PLONK-specific precomputations when module is ready
                 }
                 ProofType::STARK => {
@@ -811,7 +922,8 @@ impl UZKVContract {
 
     /// Batch verify multiple proofs of the same type with the same verification key
     ///
-    /// More gas-efficient than calling verify() multiple times.
+    /// More gas-efficient than calling verify() multiple times. PLONK keys are
+    /// bound to a program, so PLONK batches go through `batch_verify_plonk`.
     ///
     /// @param proof_type - Proof system type (0=Groth16, 1=PLONK, 2=STARK)
     /// @param proofs - Vector of serialized proofs
@@ -856,18 +968,14 @@ impl UZKVContract {
                 groth16::batch_verify(&*self, &proofs, &public_inputs, &vk_data, &precomputed_pairing)?
             }
             ProofType::PLONK => {
-                // PLONK batch verification
-                /*
-                let vk_hash_fixed = FixedBytes::from(vk_hash);
-                let vk_storage = self.verification_keys.get(vk_hash_fixed);
-                if vk_storage.is_empty() {
-                    return Err(Error::VKNotRegistered);
-                }
-                let vk_data = vk_storage.get_bytes();
-                
-                plonk::batch_verify(&proofs, &public_inputs, &vk_data)
-                    .map_err(|_| Error::VerificationFailed)?
-                */
+                // Keys are bound to a program and an SRS: see batch_verify_plonk
+                return Err(Error::ProofTypeNotSupported);
+            }
+            ProofType::Groth16Gnark
+            | ProofType::PlonkSnarkjs
+            | ProofType::Fflonk
+            | ProofType::Halo2
+            | ProofType::UltraPlonk => {
                 return Err(Error::ProofTypeNotSupported);
             }
             ProofType::STARK => {
@@ -890,6 +998,55 @@ impl UZKVContract {
         Ok(results)
     }
 
+    /// Batch verify PLONK proofs under one key bound to `program_id`
+    ///
+    /// The proofs are folded into one pairing check; if it fails, each proof is
+    /// checked on its own so the results name the failing ones. The key must be
+    /// bound to an SRS (`register_plonk_vk`), whose `[τ]₂` is appended as in
+    /// `verify_universal_proof`, and proofs use its registered transcript hash.
+    ///
+    /// @param program_id - Program the key is registered under
+    /// @param proofs - Vector of serialized proofs
+    /// @param public_inputs - Vector of serialized public inputs (must match proofs length)
+    /// @param vk_hash - Verification key hash (shared across all proofs)
+    /// @return Vector of verification results (true = valid, false = invalid)
+    pub fn batch_verify_plonk(
+        &mut self,
+        program_id: u32,
+        proofs: Vec<Vec<u8>>,
+        public_inputs: Vec<Vec<u8>>,
+        vk_hash: [u8; 32],
+    ) -> Result<Vec<bool>> {
+        // Check if contract is paused
+        if self.paused.get() {
+            return Err(Error::ContractPaused);
+        }
+
+        // Validate input lengths match
+        if proofs.len() != public_inputs.len() {
+            return Err(Error::InvalidInputSize);
+        }
+
+        let proof_type_uint = U8::from(ProofType::PLONK as u8);
+        let program_id_uint = U32::from(program_id);
+        let vk_hash_fixed = FixedBytes::from(vk_hash);
+        let vk_data = self.bound_vk_data(ProofType::PLONK, proof_type_uint, program_id_uint, vk_hash_fixed)?;
+        let hash = self.registered_hash_function(proof_type_uint, program_id_uint, vk_hash_fixed)?;
+
+        let backend = crate::backend::PrecompileBackend::new(&*self);
+        let results = plonk::batch_verify_with_hash(&backend, hash, &proofs, &public_inputs, &vk_data)
+            .map_err(|_| Error::VerificationFailed)?;
+
+        // Increment counter by number of valid proofs
+        let valid_count = results.iter().filter(|&&r| r).count();
+        if valid_count > 0 {
+            let count = self.verification_count.get();
+            self.verification_count.set(count + U256::from(valid_count));
+        }
+
+        Ok(results)
+    }
+
     /// Check if contract is paused
     ///
     /// @return paused - True if contract is paused
@@ -956,7 +1113,314 @@ impl UZKVContract {
     }
 }
 
+/// Internal helpers (not part of the ABI)
+#[cfg(not(feature = "std"))]
+impl UZKVContract {
+    /// Transcript hash a VK was registered with (Keccak256 if never recorded)
+    fn registered_hash_function(
+        &self,
+        proof_type: U8,
+        program_id: U32,
+        vk_hash: FixedBytes<32>,
+    ) -> Result<HashFunctionId> {
+        let stored = self.vk_hash_function.getter(proof_type).getter(program_id).get(vk_hash);
+        match stored.to::<u8>() {
+            0 => Ok(HashFunctionId::Keccak256),
+            id => HashFunctionId::from_u8(id - 1).ok_or(Error::UnsupportedHashFunction),
+        }
+    }
+
+    /// VK bound to (proofType, programId, vkHash), with `[τ]₂` of its SRS
+    /// appended for the proof types that use one
+    fn bound_vk_data(
+        &self,
+        ptype: ProofType,
+        proof_type: U8,
+        program_id: U32,
+        vk_hash: FixedBytes<32>,
+    ) -> Result<Vec<u8>> {
+        let proof_type_storage = self.vk_registry.getter(proof_type);
+        let program_storage = proof_type_storage.getter(program_id);
+        let vk_storage = program_storage.get(vk_hash);
+        if vk_storage.is_empty() {
+            return Err(Error::VKNotRegistered);
+        }
+        let mut vk_data = vk_storage.get_bytes();
+
+        // PLONK keys are stored without [τ]₂; append the one of their SRS
+        if ptype.uses_srs() {
+            let srs_hash = self.vk_srs.getter(proof_type).getter(program_id).get(vk_hash);
+            let srs_g2 = self.srs_g2.get(srs_hash).get_bytes();
+            if srs_g2.is_empty() {
+                return Err(Error::SrsNotRegistered);
+            }
+            vk_data.extend_from_slice(&srs_g2);
+        }
+        Ok(vk_data)
+    }
+
+    /// Shared body of `register_vk_universal_with_hash` / `register_plonk_vk`
+    fn register_vk_bound(
+        &mut self,
+        proof_type: u8,
+        program_id: u32,
+        curve_id: u8,
+        hash_function_id: u8,
+        srs_hash: Option<FixedBytes<32>>,
+        vk: Vec<u8>,
+    ) -> Result<[u8; 32]> {
+        // Validate proof type, curve and hash
+        let ptype = ProofType::from_u8(proof_type).ok_or(Error::InvalidProofType)?;
+        let curve = CurveId::from_u8(curve_id).ok_or(Error::UnsupportedCurve)?;
+        let hash = HashFunctionId::from_u8(hash_function_id).ok_or(Error::UnsupportedHashFunction)?;
+        let system = crate::uzkv::ProofSystem::from_u8(proof_type).ok_or(Error::InvalidProofType)?;
+        if !system.supports_curve(curve) {
+            return Err(Error::UnsupportedCurve);
+        }
+        if !system.supports_hash(hash) {
+            return Err(Error::UnsupportedHashFunction);
+        }
+
+        // Compute VK hash (Keccak256)
+        let vk_hash = keccak256(&vk);
+        let vk_hash_fixed = FixedBytes::from(vk_hash);
+
+        // PLONK keys must name a registered SRS whose degree covers their domain
+        if ptype.uses_srs() {
+            let srs_hash = srs_hash.ok_or(Error::SrsNotRegistered)?;
+            if self.srs_g2.get(srs_hash).is_empty() {
+                return Err(Error::SrsNotRegistered);
+            }
+            // fflonk commits to combined polynomials of degree ~9n
+            let domain = if ptype == ProofType::Fflonk {
+                let fflonk_vk = plonk::fflonk::deserialize_fflonk_vk(&vk).ok_or(Error::InvalidVerificationKey)?;
+                plonk::fflonk::srs_domain(fflonk_vk.n)
+            } else if ptype == ProofType::Halo2 {
+                halo2::vk::VerifyingKey::parse(&vk).ok_or(Error::InvalidVerificationKey)?.n
+            } else if ptype == ProofType::UltraPlonk {
+                plonk::ultra::deserialize_ultra_vk(&vk).ok_or(Error::InvalidVerificationKey)?.n
+            } else {
+                plonk::plonk::deserialize_plonk_vk(&vk).ok_or(Error::InvalidVerificationKey)?.n
+            };
+            let max_degree = self.srs_max_degree.get(srs_hash).to::<u8>();
+            if !plonk::srs::fits_domain(max_degree, domain) {
+                return Err(Error::SrsDegreeExceeded);
+            }
+        }
+
+        // Reject malformed Groth16 keys before anything is stored; the prepared
+        // form is only produced from a fully validated VK
+        let groth16_prepared = match (ptype, curve) {
+            (ProofType::Groth16, CurveId::BLS12_381) => {
+                groth16::bls12_381::validate_vk(&vk)?;
+                None
+            }
+            (ProofType::Groth16, _) => Some(groth16::compute_precomputed_pairing(&vk)?),
+            (ProofType::Groth16Gnark, _) => {
+                groth16::gnark::VerifyingKey::decode(&vk)?;
+                None
+            }
+            _ => None,
+        };
+
+        // Get nested storage references (need to convert u8/u32 to Uint)
+        let proof_type_uint = U8::from(proof_type);
+        let program_id_uint = U32::from(program_id);
+        
+        let mut proof_type_storage = self.vk_registry.setter(proof_type_uint);
+        let mut program_storage = proof_type_storage.setter(program_id_uint);
+        let mut status_proof_type = self.vk_registry_status.setter(proof_type_uint);
+        let mut status_program = status_proof_type.setter(program_id_uint);
+
+        // Check if already registered (idempotent operation)
+        if status_program.get(vk_hash_fixed) {
+            // The same bytes can parse on both curves; the first registration wins
+            let registered = self.vk_curve.getter(proof_type_uint).getter(program_id_uint).get(vk_hash_fixed);
+            if registered.to::<u8>() != curve_id {
+                return Err(Error::CurveMismatch);
+            }
+            if self.registered_hash_function(proof_type_uint, program_id_uint, vk_hash_fixed)? != hash {
+                return Err(Error::HashFunctionMismatch);
+            }
+            if let Some(srs_hash) = srs_hash {
+                if self.vk_srs.getter(proof_type_uint).getter(program_id_uint).get(vk_hash_fixed) != srs_hash {
+                    return Err(Error::SrsMismatch);
+                }
+            }
+        } else {
+            // Store VK data with triple binding
+            program_storage.setter(vk_hash_fixed).set_bytes(&vk);
+            status_program.insert(vk_hash_fixed, true);
+
+            let mut curve_proof_type = self.vk_curve.setter(proof_type_uint);
+            let mut curve_program = curve_proof_type.setter(program_id_uint);
+            curve_program.insert(vk_hash_fixed, U8::from(curve_id));
+
+            let mut hash_proof_type = self.vk_hash_function.setter(proof_type_uint);
+            let mut hash_program = hash_proof_type.setter(program_id_uint);
+            hash_program.insert(vk_hash_fixed, U8::from(hash_function_id + 1));
+
+            // Cache the prepared VK for Groth16 so verification can skip VK parsing
+            if let Some(precomputed_pairing) = groth16_prepared {
+                let mut precomp_proof_type = self.precomputed_data.setter(proof_type_uint);
+                let mut precomp_program = precomp_proof_type.setter(program_id_uint);
+                precomp_program.setter(vk_hash_fixed).set_bytes(&precomputed_pairing);
+            }
+
+            // Emit VKRegistered event for monitoring
+            emit_vk_registered_event(proof_type, program_id, vk_hash_fixed, msg::sender());
+
+            if let Some(srs_hash) = srs_hash {
+                let mut srs_proof_type = self.vk_srs.setter(proof_type_uint);
+                let mut srs_program = srs_proof_type.setter(program_id_uint);
+                srs_program.insert(vk_hash_fixed, srs_hash);
+                emit_vk_srs_linked_event(proof_type, program_id, vk_hash_fixed, srs_hash);
+            }
+        }
+
+        Ok(vk_hash)
+    }
+
+    /// Shared body of `verify_universal` / `verify_universal_with_descriptor`
+    fn verify_universal_proof(
+        &mut self,
+        universal_proof_bytes: &[u8],
+        descriptor: Option<UniversalProofDescriptor>,
+    ) -> Result<bool> {
+        // Check if contract is paused
+        if self.paused.get() {
+            return Err(Error::ContractPaused);
+        }
+
+        // Decode UniversalProof from bytes
+        let universal_proof = UniversalProof::decode(universal_proof_bytes)
+            .ok_or(Error::InvalidProofFormat)?;
+
+        // Validate version (only v1 supported)
+        if universal_proof.version != 1 {
+            return Err(Error::InvalidProofFormat);
+        }
+
+        // Get proof type enum and convert to u8 for storage lookups
+        let ptype = universal_proof.proof_type;
+        let proof_type_u8 = ptype.to_u8();
+
+        // Compute VK hash from universal_proof
+        let vk_hash_fixed = FixedBytes::from(universal_proof.vk_hash);
+
+        // === SECURITY: Validate (proofType, programId, vkHash) triple binding ===
+        let proof_type_uint = U8::from(proof_type_u8);
+        let program_id_uint = U32::from(universal_proof.program_id);
+        
+        let vk_data = self.bound_vk_data(ptype, proof_type_uint, program_id_uint, vk_hash_fixed)?;
+
+        // Route to appropriate verifier based on proof type via UZKV dispatcher
+        // This ensures consistent behavior, gas tracking, and security checks
+        let registered_hash = self.registered_hash_function(proof_type_uint, program_id_uint, vk_hash_fixed)?;
+        
+        // 1. Construct Security Descriptor (BN254 and the registered hash unless
+        //    the caller supplied one)
+        let descriptor = match descriptor {
+            Some(descriptor) => {
+                if descriptor.proof_length as usize != universal_proof.proof_bytes.len() {
+                    return Err(Error::InvalidProofFormat);
+                }
+                descriptor
+            }
+            None => {
+                let descriptor = crate::types::UniversalProofDescriptor::new(
+                    proof_type_u8,
+                    CurveId::BN254,
+                    registered_hash,
+                    0, // No recursion yet
+                    (universal_proof.public_inputs_bytes.len() / 32) as u16, // Approx input count
+                    universal_proof.proof_bytes.len() as u32,
+                    universal_proof.vk_hash,
+                    universal_proof.vk_hash, // Use vk_hash as circuit_id proxy for now
+                );
+                if ptype == ProofType::Groth16
+                    && universal_proof.proof_bytes.len() == groth16::COMPRESSED_PROOF_SIZE
+                {
+                    descriptor.with_flags(crate::types::UniversalProofDescriptor::FLAG_COMPRESSED)
+                } else {
+                    descriptor
+                }
+            }
+        };
+
+        // 2. Construct RegisteredVK for validation, with the curve it was registered on
+        let registered_curve = self.vk_curve
+            .getter(proof_type_uint)
+            .getter(program_id_uint)
+            .get(vk_hash_fixed);
+        let registered_vk = crate::security::RegisteredVK::new(
+            ptype,
+            universal_proof.vk_hash,
+            universal_proof.vk_hash, // circuit_id proxy
+            CurveId::from_u8(registered_curve.to::<u8>()).ok_or(Error::UnsupportedCurve)?,
+            1024, // Max inputs default
+        )
+        .with_hash_function(registered_hash);
+
+        // 3. Run Security Validation
+        let validator = crate::security::DispatchValidator::new();
+        validator.validate_proof_type_binding(&descriptor, &registered_vk)
+            .map_err(|_| Error::InvalidProofFormat)?;
+        validator.validate_curve_match(&descriptor, &registered_vk)
+            .map_err(|_| Error::CurveMismatch)?;
+        validator.validate_hash_function(&descriptor, &registered_vk)
+            .map_err(|_| Error::HashFunctionMismatch)?;
+
+        // 4. Delegate to Universal Verifier (with cost check); BN254 Groth16
+        // keys use the prepared VK cached at registration
+        let gas_budget = stylus_sdk::evm::gas_left();
+        let precomp_proof_type = self.precomputed_data.getter(proof_type_uint);
+        let precomp_program = precomp_proof_type.getter(program_id_uint);
+        let precomputed = precomp_program.get(vk_hash_fixed).get_bytes();
+        
+        let is_valid = if !precomputed.is_empty() {
+            crate::uzkv::verify_groth16_prepared_with_budget(
+                &*self,
+                &universal_proof.proof_bytes,
+                &universal_proof.public_inputs_bytes,
+                &vk_data,
+                &precomputed,
+                gas_budget
+            )?
+        } else {
+            crate::uzkv::verify_universal_proof_with_budget(
+                &*self,
+                proof_type_u8,
+                descriptor.curve_id,
+                descriptor.hash_function_id,
+                &universal_proof.proof_bytes,
+                &universal_proof.public_inputs_bytes,
+                &vk_data,
+                gas_budget
+            )?
+        };
+
+        // Increment verification counter for valid proofs
+        if is_valid {
+            let count = self.verification_count.get();
+            self.verification_count.set(count + U256::from(1));
+        }
+
+        // Emit ProofVerified event for monitoring and indexing
+        emit_proof_verified_event(
+            proof_type_u8,
+            universal_proof.program_id,
+            vk_hash_fixed,
+            msg::sender(),
+            is_valid,
+        );
+
+        Ok(is_valid)
+    }
+}
+
 /// Helper function: Keccak256 hash
+#[cfg(not(feature = "std"))]
 fn keccak256(data: &[u8]) -> [u8; 32] {
     use stylus_sdk::crypto;
     crypto::keccak(data).into()
@@ -0,0 +1,584 @@
+//! fflonk Verifier (snarkjs, BN254)
+//!
+//! fflonk (Gabizon, Khovratovich) is PLONK with its polynomials packed into
+//! three combined commitments, `C(X) = Σᵢ Xⁱ·fᵢ(Xᵏ)`, so all openings reduce to
+//! one KZG check. This module follows snarkjs (0.7) `fflonk_verify.js`:
+//! ```text
+//! C0 = q_L  + X·q_R + X²·q_O + X³·q_M + X⁴·q_C + X⁵·S1 + X⁶·S2 + X⁷·S3   (VK, X⁸)
+//! C1 = a    + X·b   + X²·c   + X³·T0                                    (X⁴)
+//! C2 = z    + X·T1  + X²·T2                                             (X³)
+//! ```
+//! with `T0` the gate quotient, `T1` the `z(1) = 1` quotient and `T2` the
+//! permutation quotient. `C0` is opened on the 8th roots `h₀ω₈ⁱ` of ξ, `C1` on
+//! the 4th roots `h₁ω₄ⁱ` of ξ and `C2` on the cube roots of ξ and ξω.
+//!
+//! # Wire Format
+//! ```text
+//! proof: [C1] [C2] [W1] [W2]                                  4 × 64 bytes
+//!        ql qr qm qo qc s1 s2 s3 a b c z zw t1w t2w inv      16 × 32 bytes
+//!
+//! vk:    n (u64) || ℓ (u64)                                     16 bytes
+//!        [C0]                                                   64 bytes
+//!        k1 || k2 || ω || ω₃ || ω₄ || ω₈ || ωr               7 × 32 bytes
+//! ```
+//! The proof is the snarkjs Solidity calldata order. As for PLONK, `verify`
+//! expects the SRS point `[τ]₂` (snarkjs `X_2`) appended to the VK, and
+//! `ωr³ = ω`.
+//!
+//! # Transcript
+//! snarkjs Keccak256 (see `snarkjs.rs`), reset before every challenge:
+//! ```text
+//! β = H([C0] || public inputs || [C1])        γ = H(β)
+//! ξ_seed = H(γ || [C2])                       ξ = ξ_seed²⁴
+//! α = H(ξ_seed || ql … t2w)                   y = H(α || [W1])
+//! ```
+//!
+//! # Verification
+//! The verifier rebuilds `T0(ξ)`, `T1(ξ)`, `T2(ξ)` from the evaluations,
+//! interpolates the opened values `r0(y)`, `r1(y)`, `r2(y)`, and checks one
+//! opening at `y`:
+//! ```text
+//! [F] = [C0] + q1·[C1] + q2·[C2] − Z_S0(y)·[W1]      q1 = α·Z_S0(y)/Z_S1(y)
+//! E   = r0(y) + q1·r1(y) + q2·r2(y)                  q2 = α²·Z_S0(y)/Z_S2(y)
+//! ```
+//! `W2` opens `[F]` to `E` at `y` (`kzg::verify_kzg_opening`). Every division
+//! comes from one batch inversion checked against the proof's `inv`, the
+//! inverse of the product of all denominators.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use stylus_sdk::alloy_primitives::U256;
+
+use crate::backend::Bn254Backend;
+use crate::utils::{fr_add, fr_inv, fr_mul, fr_pow, fr_sub, BN254_SCALAR_MODULUS};
+use super::kzg::{g1_lincomb, verify_kzg_opening, Error, Result};
+use super::plonk::{parse_public_inputs, SRS_G2_SIZE};
+use super::snarkjs::Keccak256Transcript;
+
+/// Proof size: 4 G1 points followed by 16 scalars
+pub const PROOF_SIZE: usize = 4 * 64 + 16 * 32;
+
+/// VK size without the trailing SRS point
+pub const VK_SIZE: usize = 16 + 64 + 7 * 32;
+
+/// fflonk proof
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FflonkProof {
+    pub c1: [u8; 64],
+    pub c2: [u8; 64],
+    pub w1: [u8; 64],
+    pub w2: [u8; 64],
+    pub evals: FflonkEvaluations,
+}
+
+/// Evaluations at ξ (and ξω for `zw`, `t1w`, `t2w`), plus the inversion hint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FflonkEvaluations {
+    pub ql: U256,
+    pub qr: U256,
+    pub qm: U256,
+    pub qo: U256,
+    pub qc: U256,
+    pub s1: U256,
+    pub s2: U256,
+    pub s3: U256,
+    pub a: U256,
+    pub b: U256,
+    pub c: U256,
+    pub z: U256,
+    pub zw: U256,
+    pub t1w: U256,
+    pub t2w: U256,
+    /// 1 / (product of the verifier's denominators)
+    pub inv: U256,
+}
+
+impl FflonkEvaluations {
+    /// Proof order (`inv` last)
+    pub fn to_array(&self) -> [U256; 16] {
+        [
+            self.ql, self.qr, self.qm, self.qo, self.qc, self.s1, self.s2, self.s3,
+            self.a, self.b, self.c, self.z, self.zw, self.t1w, self.t2w, self.inv,
+        ]
+    }
+
+    pub fn from_array(e: [U256; 16]) -> Self {
+        Self {
+            ql: e[0], qr: e[1], qm: e[2], qo: e[3], qc: e[4], s1: e[5], s2: e[6], s3: e[7],
+            a: e[8], b: e[9], c: e[10], z: e[11], zw: e[12], t1w: e[13], t2w: e[14], inv: e[15],
+        }
+    }
+}
+
+/// fflonk verification key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FflonkVerificationKey {
+    pub n: usize,
+    pub num_public_inputs: usize,
+    pub c0: [u8; 64],
+    pub k1: U256,
+    pub k2: U256,
+    /// Domain generator ω
+    pub omega: U256,
+    pub omega3: U256,
+    pub omega4: U256,
+    pub omega8: U256,
+    /// Cube root of ω
+    pub omega_r: U256,
+}
+
+/// Fiat-Shamir challenges of one proof
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Challenges {
+    pub beta: U256,
+    pub gamma: U256,
+    pub xi_seed: U256,
+    pub alpha: U256,
+    pub y: U256,
+}
+
+/// Opening points derived from ξ_seed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Roots {
+    /// ξ = ξ_seed²⁴
+    pub xi: U256,
+    /// h₀ω₈ⁱ, h₀ = ξ_seed³ (8th roots of ξ)
+    pub h0w8: [U256; 8],
+    /// h₁ω₄ⁱ, h₁ = ξ_seed⁶ (4th roots of ξ)
+    pub h1w4: [U256; 4],
+    /// h₂ω₃ⁱ, h₂ = ξ_seed⁸ (cube roots of ξ)
+    pub h2w3: [U256; 3],
+    /// h₃ω₃ⁱ, h₃ = h₂·ωr (cube roots of ξω)
+    pub h3w3: [U256; 3],
+}
+
+impl Roots {
+    pub fn new(xi_seed: U256, vk: &FflonkVerificationKey) -> Self {
+        let seed2 = fr_mul(xi_seed, xi_seed);
+        let h0 = fr_mul(seed2, xi_seed);
+        let h1 = fr_mul(h0, h0);
+        let h2 = fr_mul(h1, seed2);
+        let h3 = fr_mul(h2, vk.omega_r);
+
+        Self {
+            xi: fr_mul(fr_mul(h2, h2), h2),
+            h0w8: powers_from(h0, vk.omega8),
+            h1w4: powers_from(h1, vk.omega4),
+            h2w3: powers_from(h2, vk.omega3),
+            h3w3: powers_from(h3, vk.omega3),
+        }
+    }
+}
+
+/// `[start, start·w, start·w², …]`
+fn powers_from<const N: usize>(start: U256, w: U256) -> [U256; N] {
+    let mut out = [start; N];
+    for i in 1..N {
+        out[i] = fr_mul(out[i - 1], w);
+    }
+    out
+}
+
+/// Deserialize and verify a snarkjs fflonk proof
+pub fn verify<B: Bn254Backend>(
+    curve: &B,
+    proof_bytes: &[u8],
+    public_inputs_bytes: &[u8],
+    vk_bytes: &[u8],
+) -> Result<bool> {
+    let (proof, vk, public_inputs, srs_g2) = decode(proof_bytes, public_inputs_bytes, vk_bytes)?;
+    let challenges = compute_challenges(&proof, &vk, &public_inputs);
+    verify_with_challenges(curve, &proof, &vk, &public_inputs, srs_g2, &challenges)
+}
+
+/// Split `verify` arguments into the proof, VK, public inputs and `[τ]₂`
+pub(crate) fn decode<'a>(
+    proof_bytes: &[u8],
+    public_inputs_bytes: &[u8],
+    vk_bytes: &'a [u8],
+) -> Result<(FflonkProof, FflonkVerificationKey, Vec<U256>, &'a [u8])> {
+    let proof = deserialize_fflonk_proof(proof_bytes).ok_or(Error::InvalidInputSize)?;
+
+    if vk_bytes.len() != VK_SIZE + SRS_G2_SIZE {
+        return Err(Error::InvalidInputSize);
+    }
+    let vk = deserialize_fflonk_vk(&vk_bytes[..VK_SIZE]).ok_or(Error::InvalidInputSize)?;
+    let srs_g2 = &vk_bytes[VK_SIZE..];
+
+    let public_inputs = parse_public_inputs(public_inputs_bytes)?;
+    Ok((proof, vk, public_inputs, srs_g2))
+}
+
+pub fn deserialize_fflonk_proof(bytes: &[u8]) -> Option<FflonkProof> {
+    if bytes.len() != PROOF_SIZE { return None; }
+
+    let point = |i: usize| -> [u8; 64] { bytes[i * 64..(i + 1) * 64].try_into().unwrap() };
+    let mut evals = [U256::ZERO; 16];
+    for (i, eval) in evals.iter_mut().enumerate() {
+        *eval = read_fr(&bytes[4 * 64 + i * 32..])?;
+    }
+
+    Some(FflonkProof {
+        c1: point(0),
+        c2: point(1),
+        w1: point(2),
+        w2: point(3),
+        evals: FflonkEvaluations::from_array(evals),
+    })
+}
+
+pub fn serialize_fflonk_proof(proof: &FflonkProof) -> Vec<u8> {
+    let mut out = Vec::with_capacity(PROOF_SIZE);
+    for point in [&proof.c1, &proof.c2, &proof.w1, &proof.w2] {
+        out.extend_from_slice(point);
+    }
+    for eval in proof.evals.to_array() {
+        out.extend_from_slice(&eval.to_be_bytes::<32>());
+    }
+    out
+}
+
+/// Parse a VK (without the SRS point)
+///
+/// Rejects domains that are not a power of two, an `ω` that is not an n-th
+/// root of unity, `ω₃`, `ω₄`, `ω₈` that are not primitive roots of unity, an
+/// `ωr` with `ωr³ ≠ ω`, and more public inputs than rows.
+pub fn deserialize_fflonk_vk(bytes: &[u8]) -> Option<FflonkVerificationKey> {
+    if bytes.len() != VK_SIZE { return None; }
+
+    let n = u64::from_be_bytes(bytes[0..8].try_into().ok()?);
+    let num_inputs = u64::from_be_bytes(bytes[8..16].try_into().ok()?);
+    let scalar = |i: usize| read_fr(&bytes[80 + i * 32..]);
+
+    let vk = FflonkVerificationKey {
+        n: usize::try_from(n).ok()?,
+        num_public_inputs: usize::try_from(num_inputs).ok()?,
+        c0: bytes[16..80].try_into().ok()?,
+        k1: scalar(0)?,
+        k2: scalar(1)?,
+        omega: scalar(2)?,
+        omega3: scalar(3)?,
+        omega4: scalar(4)?,
+        omega8: scalar(5)?,
+        omega_r: scalar(6)?,
+    };
+
+    let one = U256::from(1);
+    let minus_one = fr_sub(U256::ZERO, one);
+    let cube = |x: U256| fr_mul(fr_mul(x, x), x);
+    let valid = n.is_power_of_two()
+        && num_inputs <= n
+        && fr_pow(vk.omega, U256::from(n)) == one
+        && vk.omega3 != one
+        && cube(vk.omega3) == one
+        && fr_mul(vk.omega4, vk.omega4) == minus_one
+        && fr_pow(vk.omega8, U256::from(4)) == minus_one
+        && cube(vk.omega_r) == vk.omega;
+    valid.then_some(vk)
+}
+
+pub fn serialize_fflonk_vk(vk: &FflonkVerificationKey) -> Vec<u8> {
+    let mut out = Vec::with_capacity(VK_SIZE);
+    out.extend_from_slice(&(vk.n as u64).to_be_bytes());
+    out.extend_from_slice(&(vk.num_public_inputs as u64).to_be_bytes());
+    out.extend_from_slice(&vk.c0);
+    for scalar in [vk.k1, vk.k2, vk.omega, vk.omega3, vk.omega4, vk.omega8, vk.omega_r] {
+        out.extend_from_slice(&scalar.to_be_bytes::<32>());
+    }
+    out
+}
+
+/// Powers of τ an SRS needs for a domain of `n` rows
+///
+/// `C2` packs three polynomials of degree up to ~3n, so fflonk commits to
+/// polynomials of degree ~9n.
+pub fn srs_domain(n: usize) -> usize {
+    n.saturating_mul(9).next_power_of_two()
+}
+
+/// Read a 32-byte scalar, rejecting non-canonical values
+fn read_fr(bytes: &[u8]) -> Option<U256> {
+    let value = U256::from_be_slice(&bytes[..32]);
+    (value < BN254_SCALAR_MODULUS).then_some(value)
+}
+
+/// Replay the snarkjs fflonk transcript
+///
+/// Each challenge only depends on the rounds before it, so a prover can call
+/// this on a partially filled proof.
+pub fn compute_challenges(
+    proof: &FflonkProof,
+    vk: &FflonkVerificationKey,
+    public_inputs: &[U256],
+) -> Challenges {
+    let mut transcript = Keccak256Transcript::new();
+
+    // Round 1: circuit, public inputs and C1 -> β; γ = H(β)
+    transcript.add_point(&vk.c0);
+    for input in public_inputs {
+        transcript.add_scalar(input);
+    }
+    transcript.add_point(&proof.c1);
+    let beta = transcript.challenge();
+    transcript.add_scalar(&beta);
+    let gamma = transcript.challenge();
+
+    // Round 2: C2 -> ξ_seed
+    transcript.add_scalar(&gamma);
+    transcript.add_point(&proof.c2);
+    let xi_seed = transcript.challenge();
+
+    // Round 3: evaluations (not the hint) -> α
+    transcript.add_scalar(&xi_seed);
+    for eval in &proof.evals.to_array()[..15] {
+        transcript.add_scalar(eval);
+    }
+    let alpha = transcript.challenge();
+
+    // Round 4: W1 -> y
+    transcript.add_scalar(&alpha);
+    transcript.add_point(&proof.w1);
+    let y = transcript.challenge();
+
+    Challenges { beta, gamma, xi_seed, alpha, y }
+}
+
+/// Everything the verifier divides by, in a fixed order
+///
+/// `Z_H(ξ)`, `Z_S1(y)`, `Z_S2(y)`, the Lagrange denominators over S0, S1 and
+/// S2 (`len·hᵢ^(len−1)·(y − hᵢ)`, with `Z'` of both cube-root sets for S2),
+/// and `n·(ξ − ωⁱ)` for the public input bases.
+fn denominators(vk: &FflonkVerificationKey, roots: &Roots, y: U256, num_bases: usize) -> Vec<U256> {
+    let xi = roots.xi;
+    let xi_omega = fr_mul(xi, vk.omega);
+    let zh = fr_sub(fr_pow(xi, U256::from(vk.n)), U256::from(1));
+
+    let mut dens = Vec::with_capacity(21 + num_bases);
+    dens.push(zh);
+    dens.push(vanishing(&roots.h1w4, y));
+    dens.push(fr_mul(vanishing(&roots.h2w3, y), vanishing(&roots.h3w3, y)));
+    lagrange_denominators(&mut dens, &roots.h0w8, U256::from(1), y);
+    lagrange_denominators(&mut dens, &roots.h1w4, U256::from(1), y);
+    lagrange_denominators(&mut dens, &roots.h2w3, fr_sub(xi, xi_omega), y);
+    lagrange_denominators(&mut dens, &roots.h3w3, fr_sub(xi_omega, xi), y);
+
+    let n = U256::from(vk.n);
+    let mut omega_i = U256::from(1);
+    for _ in 0..num_bases {
+        dens.push(fr_mul(n, fr_sub(xi, omega_i)));
+        omega_i = fr_mul(omega_i, vk.omega);
+    }
+    dens
+}
+
+/// Z_S(y) = Π (y − hᵢ)
+fn vanishing(roots: &[U256], y: U256) -> U256 {
+    roots.iter().fold(U256::from(1), |acc, h| fr_mul(acc, fr_sub(y, *h)))
+}
+
+/// `scale·len·h₀^(len−2)·h_((len−1)i mod len)·(y − hᵢ)` (= `scale·len·hᵢ^(len−1)·(y − hᵢ)`)
+fn lagrange_denominators(out: &mut Vec<U256>, roots: &[U256], scale: U256, y: U256) {
+    let len = roots.len();
+    let base = fr_mul(
+        fr_mul(scale, U256::from(len)),
+        fr_pow(roots[0], U256::from(len - 2)),
+    );
+    for i in 0..len {
+        let h_pow = roots[(len - 1) * i % len];
+        out.push(fr_mul(fr_mul(base, h_pow), fr_sub(y, roots[i])));
+    }
+}
+
+/// Invert every value given `inv = 1 / Π values`; `None` if `inv` is wrong
+/// (which includes any value being zero)
+fn batch_inverse_with_hint(values: &[U256], inv: U256) -> Option<Vec<U256>> {
+    let mut prefix = Vec::with_capacity(values.len());
+    let mut acc = U256::from(1);
+    for value in values {
+        prefix.push(acc);
+        acc = fr_mul(acc, *value);
+    }
+    if fr_mul(acc, inv) != U256::from(1) {
+        return None;
+    }
+
+    let mut out = vec![U256::ZERO; values.len()];
+    let mut acc = inv;
+    for i in (0..values.len()).rev() {
+        out[i] = fr_mul(acc, prefix[i]);
+        acc = fr_mul(acc, values[i]);
+    }
+    Some(out)
+}
+
+/// The `inv` evaluation an honest prover sends
+///
+/// `None` if a denominator is zero (ξ in the domain, or colliding roots).
+pub fn compute_inverse_hint(
+    vk: &FflonkVerificationKey,
+    challenges: &Challenges,
+    num_public_inputs: usize,
+) -> Option<U256> {
+    let roots = Roots::new(challenges.xi_seed, vk);
+    let dens = denominators(vk, &roots, challenges.y, num_public_inputs.max(1));
+    fr_inv(dens.iter().fold(U256::from(1), |acc, d| fr_mul(acc, *d)))
+}
+
+/// Run the verifier checks with precomputed challenges
+pub fn verify_with_challenges<B: Bn254Backend>(
+    curve: &B,
+    proof: &FflonkProof,
+    vk: &FflonkVerificationKey,
+    public_inputs: &[U256],
+    srs_g2: &[u8],
+    challenges: &Challenges,
+) -> Result<bool> {
+    if public_inputs.len() != vk.num_public_inputs {
+        return Err(Error::InvalidInputSize);
+    }
+    if srs_g2.len() != SRS_G2_SIZE {
+        return Err(Error::InvalidInputSize);
+    }
+
+    let Challenges { beta, gamma, alpha, y, .. } = *challenges;
+    let roots = Roots::new(challenges.xi_seed, vk);
+    let xi = roots.xi;
+    let e = &proof.evals;
+    let one = U256::from(1);
+
+    // One inversion for the whole proof, supplied by the prover
+    let num_bases = public_inputs.len().max(1);
+    let dens = denominators(vk, &roots, y, num_bases);
+    let Some(invs) = batch_inverse_with_hint(&dens, e.inv) else {
+        return Ok(false);
+    };
+    let zh = dens[0];
+    let (inv_zh, inv_zs1, inv_zs2) = (invs[0], invs[1], invs[2]);
+    let (inv_s0, rest) = invs[3..].split_at(8);
+    let (inv_s1, rest) = rest.split_at(4);
+    let (inv_s2, inv_bases) = rest.split_at(6);
+
+    // Lagrange bases at y over S0, S1, S2: Z_S(y) / (Z_S'(hᵢ)·(y − hᵢ))
+    let y3 = fr_pow(y, U256::from(3));
+    let y4 = fr_mul(y3, y);
+    let y8 = fr_mul(y4, y4);
+    let xi_omega = fr_mul(xi, vk.omega);
+    let zs0_num = fr_sub(y8, xi);
+    let zs1_num = fr_sub(y4, xi);
+    let zs2_num = fr_mul(fr_sub(y3, xi), fr_sub(y3, xi_omega));
+
+    // L_i(ξ) = ωⁱ·Z_H(ξ) / (n·(ξ − ωⁱ)) and PI(ξ) = −Σ xᵢ·Lᵢ(ξ)
+    let mut omega_i = one;
+    let mut lagrange = Vec::with_capacity(num_bases);
+    for inv in inv_bases {
+        lagrange.push(fr_mul(fr_mul(omega_i, zh), *inv));
+        omega_i = fr_mul(omega_i, vk.omega);
+    }
+    let pi = public_inputs
+        .iter()
+        .zip(&lagrange)
+        .fold(U256::ZERO, |acc, (x, l)| fr_sub(acc, fr_mul(*x, *l)));
+
+    // T0(ξ) = (q_L·a + q_R·b + q_M·a·b + q_O·c + q_C + PI(ξ)) / Z_H(ξ)
+    let gate = [
+        fr_mul(e.ql, e.a),
+        fr_mul(e.qr, e.b),
+        fr_mul(e.qm, fr_mul(e.a, e.b)),
+        fr_mul(e.qo, e.c),
+        e.qc,
+        pi,
+    ]
+    .into_iter()
+    .fold(U256::ZERO, fr_add);
+    let t0 = fr_mul(gate, inv_zh);
+
+    // T1(ξ) = L₁(ξ)·(z(ξ) − 1) / Z_H(ξ)
+    let t1 = fr_mul(fr_mul(lagrange[0], fr_sub(e.z, one)), inv_zh);
+
+    // T2(ξ): permutation argument over Z_H(ξ)
+    let beta_xi = fr_mul(beta, xi);
+    let id_terms = [
+        fr_add(fr_add(e.a, beta_xi), gamma),
+        fr_add(fr_add(e.b, fr_mul(beta_xi, vk.k1)), gamma),
+        fr_add(fr_add(e.c, fr_mul(beta_xi, vk.k2)), gamma),
+        e.z,
+    ]
+    .into_iter()
+    .fold(one, fr_mul);
+    let sigma_terms = [
+        fr_add(fr_add(e.a, fr_mul(beta, e.s1)), gamma),
+        fr_add(fr_add(e.b, fr_mul(beta, e.s2)), gamma),
+        fr_add(fr_add(e.c, fr_mul(beta, e.s3)), gamma),
+        e.zw,
+    ]
+    .into_iter()
+    .fold(one, fr_mul);
+    let t2 = fr_mul(fr_sub(id_terms, sigma_terms), inv_zh);
+
+    // r0(y): C0(h) = Σ hʲ·[q_L, q_R, q_O, q_M, q_C, S1, S2, S3]ⱼ(ξ)
+    let c0_evals = [e.ql, e.qr, e.qo, e.qm, e.qc, e.s1, e.s2, e.s3];
+    let r0 = interpolate(&roots.h0w8, inv_s0, zs0_num, &c0_evals);
+
+    // r1(y): C1(h) = a(ξ) + h·b(ξ) + h²·c(ξ) + h³·T0(ξ)
+    let r1 = interpolate(&roots.h1w4, inv_s1, zs1_num, &[e.a, e.b, e.c, t0]);
+
+    // r2(y): C2(h) = z + h·T1 + h²·T2 at ξ (first three roots) and ξω
+    let r2 = fr_add(
+        interpolate(&roots.h2w3, &inv_s2[..3], zs2_num, &[e.z, t1, t2]),
+        interpolate(&roots.h3w3, &inv_s2[3..], zs2_num, &[e.zw, e.t1w, e.t2w]),
+    );
+
+    // [F] − J = [C0] + q1·[C1] + q2·[C2] − Z_S0(y)·[W1], opened to E at y by W2
+    let zs0 = vanishing(&roots.h0w8, y);
+    let q1 = fr_mul(fr_mul(alpha, zs0), inv_zs1);
+    let q2 = fr_mul(fr_mul(fr_mul(alpha, alpha), zs0), inv_zs2);
+    let eval = fr_add(r0, fr_add(fr_mul(q1, r1), fr_mul(q2, r2)));
+
+    let commitment = g1_lincomb(
+        curve,
+        &[&vk.c0, &proof.c1, &proof.c2, &proof.w1],
+        &[one, q1, q2, fr_sub(U256::ZERO, zs0)],
+    )?;
+    verify_kzg_opening(curve, &commitment, y, eval, &proof.w2, srs_g2)
+}
+
+/// Σᵢ C(hᵢ)·Lᵢ(y), where `C(h) = Σⱼ hʲ·coeffs[j]` and `Lᵢ(y) = num·invs[i]`
+fn interpolate(roots: &[U256], invs: &[U256], num: U256, coeffs: &[U256]) -> U256 {
+    roots.iter().zip(invs).fold(U256::ZERO, |acc, (h, inv)| {
+        let value = coeffs.iter().rev().fold(U256::ZERO, |v, c| fr_add(fr_mul(v, *h), *c));
+        fr_add(acc, fr_mul(value, fr_mul(num, *inv)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_inverse_with_hint() {
+        let values = [U256::from(2), U256::from(3), U256::from(7)];
+        let inv = fr_inv(U256::from(42)).unwrap();
+        let invs = batch_inverse_with_hint(&values, inv).unwrap();
+        for (value, inv) in values.iter().zip(&invs) {
+            assert_eq!(fr_mul(*value, *inv), U256::from(1));
+        }
+
+        assert!(batch_inverse_with_hint(&values, fr_add(inv, U256::from(1))).is_none());
+        assert!(batch_inverse_with_hint(&[U256::from(2), U256::ZERO], inv).is_none());
+    }
+
+    #[test]
+    fn test_interpolate_recovers_polynomial() {
+        // C(X) = 1 + 2X + 3X² on the cube roots of 8 (2, 2ω₃, 2ω₃²), read at y = 5
+        let omega3 = fr_pow(U256::from(5), fr_sub(U256::ZERO, U256::from(1)) / U256::from(3));
+        let roots = powers_from::<3>(U256::from(2), omega3);
+        let y = U256::from(5);
+        let mut dens = Vec::new();
+        lagrange_denominators(&mut dens, &roots, U256::from(1), y);
+        let invs: Vec<U256> = dens.iter().map(|d| fr_inv(*d).unwrap()).collect();
+
+        let num = fr_sub(fr_pow(y, U256::from(3)), U256::from(8));
+        let coeffs = [U256::from(1), U256::from(2), U256::from(3)];
+        assert_eq!(interpolate(&roots, &invs, num, &coeffs), U256::from(1 + 2 * 5 + 3 * 25));
+    }
+}
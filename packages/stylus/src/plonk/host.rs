@@ -1,317 +1,295 @@
 //! Host-side PLONK Verifier using Arkworks
-//! 
+//!
 //! Replicates the logic of `plonk.rs` (Stylus) but uses `arkworks` for cryptography
 //! instead of EVM precompiles. This enables the CLI to verify proofs off-chain.
+//!
+//! Challenges come from the same `no_std` transcripts as on chain
+//! (`plonk::compute_challenges_for`, `snarkjs::compute_challenges`), so both
+//! paths accept exactly the same proofs for every transcript hash.
 
 #[cfg(feature = "std")]
-use crate::plonk::plonk::{deserialize_plonk_proof, deserialize_plonk_vk, PlonkProof, PlonkVerificationKey};
+use crate::plonk::plonk::{
+    compute_challenges_for, deserialize_plonk_proof, deserialize_plonk_vk, lagrange_evals, parse_public_inputs,
+    Challenges, PlonkProof, PlonkVerificationKey, SRS_G2_SIZE, VK_SIZE,
+};
 #[cfg(feature = "std")]
-use ark_bn254::{Bn254, Fr, G1Affine, G2Affine, Fq};
+use crate::plonk::snarkjs;
+#[cfg(feature = "std")]
+use crate::plonk::fflonk::{self, FflonkProof, FflonkVerificationKey};
+#[cfg(feature = "std")]
+use crate::types::HashFunctionId;
+#[cfg(feature = "std")]
+use ark_bn254::{Bn254, Fr, G1Affine, G1Projective, G2Affine, Fq};
 #[cfg(feature = "std")]
 use ark_ec::{AffineRepr, CurveGroup, pairing::Pairing};
 #[cfg(feature = "std")]
-use ark_ff::{BigInteger, PrimeField, Field};
+use ark_ff::{PrimeField, Field};
 #[cfg(feature = "std")]
 use stylus_sdk::alloy_primitives::U256;
 #[cfg(feature = "std")]
-use sha3::{Keccak256, Digest};
+use ark_ff::{One, Zero};
+
 #[cfg(feature = "std")]
-use std::ops::{Add, Mul, Neg};
+pub fn verify_host(
+    proof_bytes: &[u8],
+    public_inputs_bytes: &[u8],
+    vk_bytes: &[u8]
+) -> Result<bool, String> {
+    verify_host_with_hash(HashFunctionId::Keccak256, proof_bytes, public_inputs_bytes, vk_bytes)
+}
+
+/// Off-chain counterpart of `plonk::verify_with_hash`
 #[cfg(feature = "std")]
-use ark_ff::Zero;
+pub fn verify_host_with_hash(
+    hash: HashFunctionId,
+    proof_bytes: &[u8],
+    public_inputs_bytes: &[u8],
+    vk_bytes: &[u8]
+) -> Result<bool, String> {
+    let (proof, vk, public_inputs, srs_g2) = decode_host(proof_bytes, public_inputs_bytes, vk_bytes)?;
+    let challenges = compute_challenges_for(hash, &proof, &vk, &public_inputs)
+        .map_err(|_| format!("Unsupported transcript hash {:?}", hash))?;
+    verify_plonk_host(&proof, &vk, &public_inputs, srs_g2, &challenges)
+}
 
+/// Off-chain counterpart of `snarkjs::verify`
 #[cfg(feature = "std")]
-pub fn verify_host(
+pub fn verify_snarkjs_host(
     proof_bytes: &[u8],
     public_inputs_bytes: &[u8],
     vk_bytes: &[u8]
 ) -> Result<bool, String> {
-    // 1. Deserialize using existing helpers (returns U256/Raw bytes structs)
-    let proof = deserialize_plonk_proof(proof_bytes).ok_or("Invalid proof format")?;
-    let vk = deserialize_plonk_vk(vk_bytes).ok_or("Invalid VK format")?;
-    
-    // 2. Parse Public Inputs
-    if public_inputs_bytes.len() % 32 != 0 {
-        return Err("Invalid public inputs length".to_string());
+    let (proof, vk, public_inputs, srs_g2) = decode_host(proof_bytes, public_inputs_bytes, vk_bytes)?;
+    let challenges = snarkjs::compute_challenges(&proof, &vk, &public_inputs);
+    verify_plonk_host(&proof, &vk, &public_inputs, srs_g2, &challenges)
+}
+
+/// Off-chain counterpart of `fflonk::verify`
+///
+/// Challenges come from `fflonk::compute_challenges`; roots, Lagrange bases and
+/// the opening are recomputed with arkworks.
+#[cfg(feature = "std")]
+pub fn verify_fflonk_host(
+    proof_bytes: &[u8],
+    public_inputs_bytes: &[u8],
+    vk_bytes: &[u8]
+) -> Result<bool, String> {
+    let proof = fflonk::deserialize_fflonk_proof(proof_bytes).ok_or("Invalid proof format")?;
+    if vk_bytes.len() != fflonk::VK_SIZE + SRS_G2_SIZE {
+        return Err("Invalid VK length".to_string());
     }
-    let num_inputs = public_inputs_bytes.len() / 32;
-    let mut public_inputs = Vec::with_capacity(num_inputs);
-    for i in 0..num_inputs {
-        public_inputs.push(U256::from_be_slice(&public_inputs_bytes[i*32..(i+1)*32]));
+    let vk = fflonk::deserialize_fflonk_vk(&vk_bytes[..fflonk::VK_SIZE]).ok_or("Invalid VK format")?;
+    let srs_g2 = parse_g2(&vk_bytes[fflonk::VK_SIZE..])?;
+    let public_inputs = parse_public_inputs(public_inputs_bytes)
+        .map_err(|_| "Invalid public inputs".to_string())?;
+    if public_inputs.len() != vk.num_public_inputs {
+        return Err("Wrong number of public inputs".to_string());
     }
-    
-    // 3. Extract SRS G2 from VK (last 128 bytes)
-    // Same assumption as Stylus implementation
-    if vk_bytes.len() < 128 { return Err("VK too short for SRS".to_string()); }
-    let srs_offset = vk_bytes.len() - 128;
-    let srs_g2_bytes = &vk_bytes[srs_offset..];
-    
-    // 4. Convert to Arkworks Types
-    let srs_g2 = parse_g2(srs_g2_bytes)?;
-    
-    verify_plonk_host(&proof, &vk, &public_inputs, srs_g2)
+
+    let challenges = fflonk::compute_challenges(&proof, &vk, &public_inputs);
+    verify_fflonk_host_inner(&proof, &vk, &public_inputs, srs_g2, &challenges)
 }
 
 #[cfg(feature = "std")]
-fn verify_plonk_host(
-    proof: &PlonkProof,
-    vk: &PlonkVerificationKey,
+fn verify_fflonk_host_inner(
+    proof: &FflonkProof,
+    vk: &FflonkVerificationKey,
     public_inputs: &[U256],
-    srs_g2: G2Affine
+    srs_g2: G2Affine,
+    challenges: &fflonk::Challenges
 ) -> Result<bool, String> {
-    // === 1. Transcript Initialization ===
-    let mut transcript = HostTranscript::new("plonk");
-    
-    // Absorb VK (simplified matching plonk.rs)
-    transcript.absorb_bytes(&vk.n.to_be_bytes());
-    
-    // Absorb PI
-    for pi in public_inputs {
-        transcript.absorb_u256(pi);
-    }
-    
-    // Steps mirroring plonk.rs
-    
-    // Wire Commitments -> Beta, Gamma
-    for comm in &proof.wire_commitments {
-        transcript.absorb_bytes(comm);
-    }
-    let beta = transcript.squeeze_challenge();
-    let gamma = transcript.squeeze_challenge();
-    
-    // Permutation -> Alpha
-    transcript.absorb_bytes(&proof.permutation_commitment);
-    let alpha = transcript.squeeze_challenge();
-    
-    // Quotient -> Zeta
-    for comm in &proof.quotient_commitments {
-        transcript.absorb_bytes(comm);
-    }
-    let zeta = transcript.squeeze_challenge();
-    
-    // Evals -> v
-    let mut evals_to_absorb = Vec::new();
-    evals_to_absorb.extend_from_slice(&proof.wire_evals);
-    evals_to_absorb.extend_from_slice(&proof.permutation_evals);
-    evals_to_absorb.extend_from_slice(&proof.selector_evals);
-    for eval in evals_to_absorb {
-        transcript.absorb_u256(&eval);
-    }
-    let v_challenge = transcript.squeeze_challenge();
-    
-    // Opening -> u
-    transcript.absorb_bytes(&proof.opening_proof_zeta);
-    transcript.absorb_bytes(&proof.opening_proof_omega);
-    let u_challenge = transcript.squeeze_challenge();
-    
-    // === 2. Verification Logic ===
-    
-    // Convert all needed points/scalars to Arkworks
-    let pi_zeta = compute_pi_eval(public_inputs, zeta, u256_to_fr(vk.omega), vk.n)?;
-    let zeta_fr = u256_to_fr(zeta);
-    let omega_fr = u256_to_fr(vk.omega);
-    let n_fr = Fr::from(vk.n as u64);
-    
-    // Gate Constraint Check (Simplified)
-    let a = u256_to_fr(proof.wire_evals[0]);
-    let b = u256_to_fr(proof.wire_evals[1]);
-    let c = u256_to_fr(proof.wire_evals[2]);
-    let ql = u256_to_fr(proof.selector_evals[0]);
-    let qr = u256_to_fr(proof.selector_evals[1]);
-    let qo = u256_to_fr(proof.selector_evals[2]);
-    let qm = u256_to_fr(proof.selector_evals[3]);
-    let qc = u256_to_fr(proof.selector_evals[4]);
-    
-    // qL*a + qR*b + qO*c + qM*a*b + qC + PI
-    let gate_val = (ql * a) + (qr * b) + (qo * c) + (qm * a * b) + qc + pi_zeta;
-    if !gate_val.is_zero() {
-        // As per Stylus logic, if constraint check fails, we return false (or true=false in result)
-        // Stylus: return Ok(false)
+    let [beta, gamma, xi_seed, alpha, y] = [
+        challenges.beta, challenges.gamma, challenges.xi_seed, challenges.alpha, challenges.y,
+    ]
+    .map(u256_to_fr);
+    let [omega, omega3, omega4, omega8, omega_r, k1, k2] =
+        [vk.omega, vk.omega3, vk.omega4, vk.omega8, vk.omega_r, vk.k1, vk.k2].map(u256_to_fr);
+    let e = proof.evals.to_array().map(u256_to_fr);
+    let [ql, qr, qm, qo, qc, s1, s2, s3, a, b, c, z, zw, t1w, t2w, inv] = e;
+
+    // === 1. Roots: h₀⁸ = h₁⁴ = h₂³ = ξ, h₃³ = ξω ===
+    let h0 = xi_seed.pow([3]);
+    let h2 = xi_seed.pow([8]);
+    let xi = xi_seed.pow([24]);
+    let xi_omega = xi * omega;
+    let roots = |h: Fr, w: Fr, len: usize| -> Vec<Fr> { (0..len).map(|i| h * w.pow([i as u64])).collect() };
+    let s0 = roots(h0, omega8, 8);
+    let s1_roots = roots(xi_seed.pow([6]), omega4, 4);
+    let s2_roots = [roots(h2, omega3, 3), roots(h2 * omega_r, omega3, 3)].concat();
+
+    // === 2. The prover's inversion hint covers every denominator ===
+    let zh = xi.pow([vk.n as u64]) - Fr::one();
+    let num_bases = public_inputs.len().max(1);
+    let lagrange_den: Vec<Fr> = (0..num_bases)
+        .map(|i| Fr::from(vk.n as u64) * (xi - omega.pow([i as u64])))
+        .collect();
+    let vanishing = |roots: &[Fr]| roots.iter().map(|h| y - h).product::<Fr>();
+    // Z_S'(h) for each set: len·h^(len−1), and 3h²·(±(ξ − ξω)) for S2
+    let derivative = |h: &Fr, len: u64| Fr::from(len) * h.pow([len - 1]);
+    let mut dens = vec![zh, vanishing(&s1_roots), vanishing(&s2_roots)];
+    dens.extend(s0.iter().map(|h| derivative(h, 8) * (y - h)));
+    dens.extend(s1_roots.iter().map(|h| derivative(h, 4) * (y - h)));
+    dens.extend(s2_roots[..3].iter().map(|h| derivative(h, 3) * (xi - xi_omega) * (y - h)));
+    dens.extend(s2_roots[3..].iter().map(|h| derivative(h, 3) * (xi_omega - xi) * (y - h)));
+    dens.extend(&lagrange_den);
+    if dens.iter().product::<Fr>() * inv != Fr::one() {
         return Ok(false);
     }
-    
-    // === 3. Batch KZG Verification ===
-    // Verify opening at Zeta
-    
-    let mut batch_comms = Vec::new();
-    let mut batch_evals = Vec::new();
-    
-    // Wires
-    for (i, comm_bytes) in proof.wire_commitments.iter().enumerate() {
-        batch_comms.push(parse_g1(comm_bytes)?);
-        batch_evals.push(u256_to_fr(proof.wire_evals[i]));
-    }
-    // Permutation
-    batch_comms.push(parse_g1(&proof.permutation_commitment)?);
-    batch_evals.push(u256_to_fr(proof.permutation_evals[0]));
-    // Selectors
-    for (i, comm_bytes) in vk.selector_commitments.iter().enumerate() {
-        batch_comms.push(parse_g1(comm_bytes)?);
-        batch_evals.push(u256_to_fr(proof.selector_evals[i]));
-    }
-    
-    let valid_zeta = verify_kzg_batch(
-        &batch_comms,
-        zeta_fr,
-        &batch_evals,
-        parse_g1(&proof.opening_proof_zeta)?,
-        srs_g2,
-        u256_to_fr(v_challenge)
-    )?;
-    
-    if !valid_zeta { return Ok(false); }
-    
-    // Verify opening at Omega (z*w)
-    // Permutation commitment only
-    let zw = zeta_fr * omega_fr;
-    let perm_comm = parse_g1(&proof.permutation_commitment)?;
-    let zw_eval = u256_to_fr(proof.permutation_evals[1]);
-    let zw_proof = parse_g1(&proof.opening_proof_omega)?;
-    
-    // Single opening check: e(proof, [x]2 - z[1]2) == e(C - y[1]1, [1]2)
-    // e(proof, [x]2) * e(proof, -z[1]2) * e(-(C-yG1), [1]2) == 1
-    // same as: e(proof, [x]2 - z[1]2) == e(C - yG1, [1]2)
-    let valid_omega = verify_kzg_single(
-        perm_comm,
-        zw,
-        zw_eval,
-        zw_proof,
-        srs_g2
-    )?;
-    
-    Ok(valid_omega)
-}
 
-// === Transcript ===
+    // === 3. Quotients at ξ ===
+    let lagrange: Vec<Fr> = lagrange_den
+        .iter()
+        .enumerate()
+        .map(|(i, den)| omega.pow([i as u64]) * zh / den)
+        .collect();
+    let pi: Fr = -public_inputs.iter().zip(&lagrange).map(|(x, l)| u256_to_fr(*x) * l).sum::<Fr>();
+    let t0 = (ql * a + qr * b + qm * a * b + qo * c + qc + pi) / zh;
+    let t1 = lagrange[0] * (z - Fr::one()) / zh;
+    let t2 = ((a + beta * xi + gamma) * (b + beta * k1 * xi + gamma) * (c + beta * k2 * xi + gamma) * z
+        - (a + beta * s1 + gamma) * (b + beta * s2 + gamma) * (c + beta * s3 + gamma) * zw)
+        / zh;
 
-#[cfg(feature = "std")]
-struct HostTranscript {
-    state: Keccak256,
+    // === 4. Opened values r_i(y), by Lagrange interpolation over each root set ===
+    let interpolate = |roots: &[Fr], values: &[Fr]| -> Fr {
+        roots
+            .iter()
+            .enumerate()
+            .map(|(i, hi)| {
+                let basis: Fr = roots
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, hj)| (y - hj) / (*hi - hj))
+                    .product();
+                values[i] * basis
+            })
+            .sum()
+    };
+    let combined = |h: &Fr, coeffs: &[Fr]| coeffs.iter().rev().fold(Fr::zero(), |acc, c| acc * h + c);
+    let r0 = interpolate(&s0, &s0.iter().map(|h| combined(h, &[ql, qr, qo, qm, qc, s1, s2, s3])).collect::<Vec<_>>());
+    let r1 = interpolate(&s1_roots, &s1_roots.iter().map(|h| combined(h, &[a, b, c, t0])).collect::<Vec<_>>());
+    let r2_values: Vec<Fr> = s2_roots[..3]
+        .iter()
+        .map(|h| combined(h, &[z, t1, t2]))
+        .chain(s2_roots[3..].iter().map(|h| combined(h, &[zw, t1w, t2w])))
+        .collect();
+    let r2 = interpolate(&s2_roots, &r2_values);
+
+    // === 5. Pairing: e(F − E·G1 − J + y·W2, [1]₂) == e(W2, [τ]₂) ===
+    let zs0 = vanishing(&s0);
+    let q1 = alpha * zs0 / vanishing(&s1_roots);
+    let q2 = alpha.square() * zs0 / vanishing(&s2_roots);
+    let f = parse_g1(&vk.c0)?.into_group() + parse_g1(&proof.c1)? * q1 + parse_g1(&proof.c2)? * q2;
+    let big_e = r0 + q1 * r1 + q2 * r2;
+    let w2 = parse_g1(&proof.w2)?;
+    let lhs = f - G1Affine::generator() * big_e - parse_g1(&proof.w1)? * zs0 + w2 * y;
+
+    Ok(Bn254::pairing(lhs.into_affine(), G2Affine::generator()) == Bn254::pairing(w2, srs_g2))
 }
 
 #[cfg(feature = "std")]
-impl HostTranscript {
-    fn new(label: &str) -> Self {
-        let mut t = Self { state: Keccak256::new() };
-        t.state.update(label.as_bytes());
-        t
-    }
-    
-    fn absorb_bytes(&mut self, bytes: &[u8]) {
-        self.state.update(b"input"); // domain separation matching Stylus? Stylus uses `labels::...`
-        // Stylus uses `Transcript::new` which implies Merlin/Keccak sponge.
-        // Let's approximate straightforward Keccak usage.
-        self.state.update(bytes);
-    }
-    
-    fn absorb_u256(&mut self, val: &U256) {
-        self.absorb_bytes(&val.to_be_bytes::<32>());
+fn decode_host(
+    proof_bytes: &[u8],
+    public_inputs_bytes: &[u8],
+    vk_bytes: &[u8]
+) -> Result<(PlonkProof, PlonkVerificationKey, Vec<U256>, G2Affine), String> {
+    // 1. Deserialize using existing helpers (returns U256/Raw bytes structs)
+    let proof = deserialize_plonk_proof(proof_bytes).ok_or("Invalid proof format")?;
+
+    // 2. Split off the SRS G2 point (last 128 bytes), same layout as Stylus
+    if vk_bytes.len() != VK_SIZE + SRS_G2_SIZE {
+        return Err("Invalid VK length".to_string());
     }
-    
-    fn squeeze_challenge(&mut self) -> U256 {
-        let mut hasher = self.state.clone();
-        let result = hasher.finalize();
-        
-        // Update state with result for chaining
-        self.state.update(&result);
-        
-        U256::from_be_slice(&result)
+    let vk = deserialize_plonk_vk(&vk_bytes[..VK_SIZE]).ok_or("Invalid VK format")?;
+    let srs_g2 = parse_g2(&vk_bytes[VK_SIZE..])?;
+
+    // 3. Parse Public Inputs
+    let public_inputs = parse_public_inputs(public_inputs_bytes)
+        .map_err(|_| "Invalid public inputs".to_string())?;
+    if public_inputs.len() != vk.num_public_inputs {
+        return Err("Wrong number of public inputs".to_string());
     }
-}
 
-// === KZG Implementation ===
+    Ok((proof, vk, public_inputs, srs_g2))
+}
 
 #[cfg(feature = "std")]
-fn verify_kzg_batch(
-    commitments: &[G1Affine],
-    z: Fr,
-    evals: &[Fr],
-    proof: G1Affine,
+fn verify_plonk_host(
+    proof: &PlonkProof,
+    vk: &PlonkVerificationKey,
+    public_inputs: &[U256],
     srs_g2: G2Affine,
-    challenge: Fr, // v
+    challenges: &Challenges
 ) -> Result<bool, String> {
-    // agg_commitment = Sum( r^i * C_i )
-    // agg_eval = Sum( r^i * y_i )
-    
-    let mut agg_comm = G1Affine::zero().into_group();
-    let mut agg_eval = Fr::zero();
-    let mut current_r = Fr::from(1u64);
-    
-    for (i, comm) in commitments.iter().enumerate() {
-        // comm_term = C_i * r^i
-        agg_comm += comm.mul(current_r);
-        // eval_term = y_i * r^i
-        agg_eval += evals[i] * current_r;
-        
-        current_r *= challenge;
+    // === 1. Challenges ===
+    let [beta, gamma, alpha, zeta, v, u] = [
+        challenges.beta, challenges.gamma, challenges.alpha, challenges.zeta, challenges.v, challenges.u,
+    ]
+    .map(u256_to_fr);
+
+    // === 2. Scalars ===
+    let omega = u256_to_fr(vk.omega);
+    let zeta_n = zeta.pow([vk.n as u64]);
+    let zh = zeta_n - Fr::one();
+    if zh.is_zero() {
+        return Ok(false);
     }
-    
-    verify_kzg_single(
-        agg_comm.into_affine(),
-        z,
-        agg_eval,
-        proof,
-        srs_g2
-    )
-}
 
-#[cfg(feature = "std")]
-fn verify_kzg_single(
-    commitment: G1Affine,
-    z: Fr,
-    y: Fr,
-    proof: G1Affine,
-    srs_g2: G2Affine
-) -> Result<bool, String> {
-    // Check: e(proof, [x]2 - z[1]2) == e(C - y[1]1, [1]2)
-    // LHS: e(proof, srs_g2 - z*G2)
-    // RHS: e(C - y*G1, G2_gen)
-    
-    let g2_gen = G2Affine::generator();
-    let g1_gen = G1Affine::generator();
-    
-    let z_g2 = g2_gen.mul(z);
-    let lhs_g2 = srs_g2.into_group() - z_g2;
-    
-    let y_g1 = g1_gen.mul(y);
-    let rhs_g1 = commitment.into_group() - y_g1;
-    
-    // Pairing check: e(proof, lhs_g2) == e(rhs_g1, g2_gen)
-    let left = Bn254::pairing(proof, lhs_g2.into_affine());
-    let right = Bn254::pairing(rhs_g1.into_affine(), g2_gen);
-    
-    Ok(left == right)
-}
+    // Same barycentric evaluation as on chain
+    let lagrange: Vec<Fr> = lagrange_evals(challenges.zeta, vk.omega, vk.n, public_inputs.len().max(1))
+        .into_iter()
+        .map(u256_to_fr)
+        .collect();
+    let l1 = lagrange[0];
+    let pi: Fr = -public_inputs
+        .iter()
+        .zip(&lagrange)
+        .map(|(x, l)| u256_to_fr(*x) * l)
+        .sum::<Fr>();
 
-// === Helpers ===
+    let [a, b, c] = proof.wire_evals.map(u256_to_fr);
+    let [s1, s2] = proof.sigma_evals.map(u256_to_fr);
+    let zw = u256_to_fr(proof.permutation_eval_omega);
+    let (k1, k2) = (u256_to_fr(vk.k1), u256_to_fr(vk.k2));
+    let alpha2 = alpha.square();
 
-#[cfg(feature = "std")]
-fn compute_pi_eval(pis: &[U256], z: U256, omega: Fr, n: usize) -> Result<Fr, String> {
-    let z_fr = u256_to_fr(z);
-    let n_fr = Fr::from(n as u64);
-    let pis_fr: Vec<Fr> = pis.iter().map(|x| u256_to_fr(*x)).collect();
-    
-    // Z_H(z) = z^n - 1
-    let zh = z_fr.pow([n as u64]) - Fr::from(1u64);
-    
-    let mut result = Fr::zero();
-    let mut omega_i = Fr::from(1u64);
-    
-    for pi in pis_fr {
-        // L_i(z) = (Z_H * omega^i) / (n * (z - omega^i))
-        let num = zh * omega_i;
-        let den = n_fr * (z_fr - omega_i);
-        
-        let den_inv = den.inverse().ok_or("Division by zero in PI")?;
-        let li = num * den_inv;
-        
-        result += pi * li;
-        omega_i *= omega;
-    }
-    
-    Ok(result)
+    let sigma_terms = (a + beta * s1 + gamma) * (b + beta * s2 + gamma);
+    let r0 = pi - l1 * alpha2 - alpha * sigma_terms * (c + gamma) * zw;
+    let id_terms = (a + beta * zeta + gamma) * (b + beta * k1 * zeta + gamma) * (c + beta * k2 * zeta + gamma);
+
+    // === 3. Commitments ===
+    let [ql, qr, qo, qm, qc] = vk.selector_commitments.map(|p| parse_g1(&p));
+    let [s1_comm, s2_comm, s3_comm] = vk.permutation_commitments.map(|p| parse_g1(&p));
+    let [wa, wb, wc] = proof.wire_commitments.map(|p| parse_g1(&p));
+    let [t_lo, t_mid, t_hi] = proof.quotient_commitments.map(|p| parse_g1(&p));
+    let z_comm = parse_g1(&proof.permutation_commitment)?;
+    let w_zeta = parse_g1(&proof.opening_proof_zeta)?;
+    let w_omega = parse_g1(&proof.opening_proof_omega)?;
+
+    // [D]: linearization commitment
+    let d: G1Projective = qm? * (a * b) + ql? * a + qr? * b + qo? * c + qc?
+        + z_comm * (id_terms * alpha + l1 * alpha2 + u)
+        - s3_comm? * (sigma_terms * alpha * beta * zw)
+        - (t_lo?.into_group() + t_mid? * zeta_n + t_hi? * zeta_n.square()) * zh;
+
+    // [F] and E: everything opened at ζ folded with v, z(ζω) with u
+    let v2 = v.square();
+    let v3 = v2 * v;
+    let v4 = v3 * v;
+    let v5 = v4 * v;
+    let f = d + wa? * v + wb? * v2 + wc? * v3 + s1_comm? * v4 + s2_comm? * v5;
+    let e = -r0 + v * a + v2 * b + v3 * c + v4 * s1 + v5 * s2 + u * zw;
+
+    // === 4. Pairing ===
+    // e([W_ζ] + u[W_ζω], [τ]₂) == e(ζ[W_ζ] + uζω[W_ζω] + [F] − E·G1, [1]₂)
+    let lhs = w_zeta.into_group() + w_omega * u;
+    let rhs = w_zeta * zeta + w_omega * (u * zeta * omega) + f - G1Affine::generator() * e;
+
+    Ok(Bn254::pairing(lhs.into_affine(), srs_g2) == Bn254::pairing(rhs.into_affine(), G2Affine::generator()))
 }
 
+// === Helpers ===
+
 #[cfg(feature = "std")]
 fn u256_to_fr(v: U256) -> Fr {
     let bytes = v.to_be_bytes::<32>();
@@ -321,19 +299,16 @@ fn u256_to_fr(v: U256) -> Fr {
 #[cfg(feature = "std")]
 fn parse_g1(bytes: &[u8]) -> Result<G1Affine, String> {
     if bytes.len() != 64 { return Err("Invalid G1 length".to_string()); }
-    
+
     // Bytes are uncompressed X, Y (32 bytes each)
-    use ark_ff::Field;
     let x = Fq::from_be_bytes_mod_order(&bytes[0..32]);
     let y = Fq::from_be_bytes_mod_order(&bytes[32..64]);
-    
+
     if x.is_zero() && y.is_zero() {
         return Ok(G1Affine::zero());
     }
-    
-    G1Affine::new(x, y).is_on_curve(); // Check logic
-    // G1Affine::new checks coords but returns struct. need to check validity?
-    let p = G1Affine::new_unchecked(x, y); // Trusting inputs for now or implementing check
+
+    let p = G1Affine::new_unchecked(x, y);
     if !p.is_on_curve() {
         return Err("Point not on curve".to_string());
     }
@@ -343,43 +318,22 @@ fn parse_g1(bytes: &[u8]) -> Result<G1Affine, String> {
 #[cfg(feature = "std")]
 fn parse_g2(bytes: &[u8]) -> Result<G2Affine, String> {
     if bytes.len() != 128 { return Err("Invalid G2 length".to_string()); }
-    
-    // Arkworks Fq2 is usually represented as (c0, c1) -> c0 + c1*u
-    // But alignment in bytes depends on serialization.
-    // Usually X = x0 + x1*u, Y = y0 + y1*u
-    // Input format from Stylus (kzg.rs): X1, X2, Y1, Y2
-    // Let's assume arkworks convention for from_be_bytes corresponds.
-    // Arkworks serialization sometimes is x1, x0?
-    // Let's try direct parsing.
-    
-    // x0 @ 0..32, x1 @ 32..64 ?
-    // Stylus::kzg::get_g2_generator defines buf with 4 chunks.
-    // buf[0..32] = x1?
-    // buf[32..64] = x0?
-    // buf[64..96] = y1?
-    // buf[96..128] = y0?
-    // CHECK kzg.rs line 370:
-    // // x1
-    // buf[0..32].copy_from_slice(...)
-    // // x0
-    // buf[32..64] ...
-    
-    // Arkworks Fp2 config: c0 + c1 * u.
-    // Usually deserialization expects c0 then c1?
-    // Let's assume manual construction.
+
+    // EIP-197 order, as in kzg::get_g2_generator: x1, x0, y1, y0
     use ark_bn254::Fq2;
-    
+
     let x1 = Fq::from_be_bytes_mod_order(&bytes[0..32]);
     let x0 = Fq::from_be_bytes_mod_order(&bytes[32..64]);
     let y1 = Fq::from_be_bytes_mod_order(&bytes[64..96]);
     let y0 = Fq::from_be_bytes_mod_order(&bytes[96..128]);
-    
+
     let x = Fq2::new(x0, x1);
     let y = Fq2::new(y0, y1);
-    
+
     let p = G2Affine::new_unchecked(x, y);
-    if !p.is_on_curve() {
+    if !p.is_on_curve() || !p.is_in_correct_subgroup_assuming_on_curve() {
         return Err("G2 Point not on curve".to_string());
     }
     Ok(p)
 }
+
@@ -1,28 +1,31 @@
 //! KZG Polynomial Commitment Scheme (Precompile Optimized)
 //!
-//! Implements KZG verification using EVM precompiles (0x06, 0x07, 0x08).
-//! Removes Arkworks dependencies.
+//! Implements KZG verification on top of `crate::backend::Bn254Backend`: the EVM
+//! precompiles (0x06, 0x07, 0x08) on chain, or pure Rust through `ArkBackend`.
 //!
 //! # Protocol
 //! Check pairing equation: e(C - yG₁, G₂) == e(π, τG₂ - zG₂)
 //! Equivalent to: e(-π, τG₂ - zG₂) * e(C - yG₁, G₂) == 1
 //!
+//! # Batched Openings
+//! Openings at several points (Gabizon, Williamson, Ciobotaru, §3): at each
+//! point `zⱼ` the polynomials fold with powers of `γ` into `[Fⱼ] = Σ γⁱ[Cⱼᵢ]`,
+//! `yⱼ = Σ γⁱyⱼᵢ` with one proof `[πⱼ]`, and the points fold with powers of `r`:
+//! ```text
+//! e(−Σ rʲ[πⱼ], [τ]₂) · e(Σ rʲ([Fⱼ] − yⱼ[1]₁ + zⱼ[πⱼ]), [1]₂) == 1
+//! ```
+//! `KzgAccumulator` collects such terms (from any number of proofs) and runs
+//! the two-pairing check once.
+//!
 //! # Input Formats
-//! - Points (G1): 64 bytes (X, Y) uncompressed
+//! - Points (G1): 64 bytes (X, Y) uncompressed, all zeros for the identity
 //! - Points (G2): 128 bytes (X1, X2, Y1, Y2) uncompressed
 //! - Scalars: 32 bytes BigEndian
 
 use alloc::vec::Vec;
-use stylus_sdk::{
-    alloy_primitives::U256,
-    call::{static_call, StaticCallContext},
-};
-use crate::utils::fr_mul;
-
-// Precompile Addresses
-const BN256_ADD:  u64 = 0x06;
-const BN256_MUL:  u64 = 0x07;
-const BN256_PAIRING: u64 = 0x08;
+use stylus_sdk::alloy_primitives::U256;
+use crate::backend::{BackendError, Bn254Backend};
+use crate::utils::{fr_add, fr_mul, fr_sub};
 
 // Error Types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -30,10 +33,18 @@ pub enum Error {
     InvalidInputSize,
     PrecompileFailed,
     PairingCheckFailed,
+    /// Transcript hash not implemented (see `crate::hash`)
+    UnsupportedHash,
 }
 
 pub type Result<T> = core::result::Result<T, Error>;
 
+impl From<BackendError> for Error {
+    fn from(_: BackendError) -> Self {
+        Error::PrecompileFailed
+    }
+}
+
 /// Verify a KZG opening proof
 ///
 /// Verifies p(z) = y
@@ -49,14 +60,14 @@ pub type Result<T> = core::result::Result<T, Error>;
 /// Wait, τG₂ is the SRS G2 point (setup).
 ///
 /// Arguments:
-/// - context: for precompile calls
+/// - curve: backend for the G1 arithmetic and the pairing
 /// - commitment: G1 (64 bytes)
 /// - z (eval point): U256
 /// - y (eval result): U256
 /// - proof: G1 (64 bytes)
 /// - srs_g2: G2 (128 bytes) - The point [x]2 from setup
-pub fn verify_kzg_opening<S: StaticCallContext + Copy>(
-    context: S,
+pub fn verify_kzg_opening<B: Bn254Backend>(
+    curve: &B,
     commitment: &[u8],
     z: U256,
     y: U256,
@@ -81,11 +92,7 @@ pub fn verify_kzg_opening<S: StaticCallContext + Copy>(
     g1_gen_bytes[63] = 2; // Check if this is correct Y for X=1
 
     // yG1 = y * G1
-    let mut input_mul = Vec::with_capacity(96);
-    input_mul.extend_from_slice(&g1_gen_bytes);
-    input_mul.extend_from_slice(&y.to_be_bytes::<32>());
-    let y_g1_bytes = static_call(context, stylus_sdk::alloy_primitives::Address::with_last_byte(BN256_MUL as u8), &input_mul)
-        .map_err(|_| Error::PrecompileFailed)?;
+    let y_g1_bytes = curve.g1_mul(&g1_gen_bytes, y)?;
         
     // Negate yG1 to subtract? Or C - yG1 = C + (-yG1).
     // Negate point (x, y) -> (x, p - y).
@@ -133,11 +140,7 @@ pub fn verify_kzg_opening<S: StaticCallContext + Copy>(
     // c. C (commitment)
     
     // z * proof
-    let mut input_z_proof = Vec::with_capacity(96);
-    input_z_proof.extend_from_slice(proof);
-    input_z_proof.extend_from_slice(&z.to_be_bytes::<32>());
-    let z_proof = static_call(context, stylus_sdk::alloy_primitives::Address::with_last_byte(BN256_MUL as u8), &input_z_proof)
-        .map_err(|_| Error::PrecompileFailed)?;
+    let z_proof = curve.g1_mul(proof, z)?;
         
     // Negate z_proof -> -z*proof
     let neg_z_proof = negate_g1(&z_proof).ok_or(Error::PrecompileFailed)?;
@@ -147,17 +150,8 @@ pub fn verify_kzg_opening<S: StaticCallContext + Copy>(
     
     // Sum: yG1 + (-z*proof) + (-C)
     // Adding 3 points: (yG1 + neg_z_proof) + neg_c
-    let mut sum_1_bytes = Vec::with_capacity(128);
-    sum_1_bytes.extend_from_slice(&y_g1_bytes);
-    sum_1_bytes.extend_from_slice(&neg_z_proof);
-    let sum_1 = static_call(context, stylus_sdk::alloy_primitives::Address::with_last_byte(BN256_ADD as u8), &sum_1_bytes)
-        .map_err(|_| Error::PrecompileFailed)?;
-        
-    let mut sum_2_bytes = Vec::with_capacity(128);
-    sum_2_bytes.extend_from_slice(&sum_1);
-    sum_2_bytes.extend_from_slice(&neg_c);
-    let term2 = static_call(context, stylus_sdk::alloy_primitives::Address::with_last_byte(BN256_ADD as u8), &sum_2_bytes)
-        .map_err(|_| Error::PrecompileFailed)?;
+    let sum_1 = curve.g1_add(&y_g1_bytes, &neg_z_proof)?;
+    let term2 = curve.g1_add(&sum_1, &neg_c)?;
         
     // Pairings Input:
     // P1: proof (G1), srs_g2 (G2)
@@ -200,28 +194,11 @@ pub fn verify_kzg_opening<S: StaticCallContext + Copy>(
     pairing_input.extend_from_slice(&term2);
     pairing_input.extend_from_slice(&g2_gen_bytes);
     
-    let success = static_call_pairing(context, &pairing_input)
-        .map_err(|_| Error::PrecompileFailed)?;
-        
-    Ok(success)
-}
-
-fn static_call_pairing<S: StaticCallContext + Copy>(context: S, input: &[u8]) -> Result<bool> {
-    let output = static_call(context, stylus_sdk::alloy_primitives::Address::with_last_byte(BN256_PAIRING as u8), input)
-        .map_err(|_| Error::PrecompileFailed)?;
-    
-    // Output is 32 bytes. Last byte 1 means success (points on curve + product is 1).
-    // But actually, for 0x08, it returns "1" (bool true) if the check passes (product is 1), "0" if fails.
-    // Length is 32 bytes (U256).
-    if output.len() != 32 {
-        return Err(Error::PairingCheckFailed);
-    }
-    // Check if output is exactly 1
-    let is_one = output[31] == 1 && output[0..31].iter().all(|&b| b == 0);
-    Ok(is_one)
+    // true iff the product of both pairings is exactly 1
+    Ok(curve.pairing_check(&pairing_input)?)
 }
 
-fn negate_g1(point: &[u8]) -> Option<Vec<u8>> {
+pub(crate) fn negate_g1(point: &[u8]) -> Option<Vec<u8>> {
     if point.len() != 64 { return None; }
     // Field Modulus P (base field)
     let p = U256::from_be_bytes([
@@ -247,70 +224,173 @@ fn negate_g1(point: &[u8]) -> Option<Vec<u8>> {
     Some(encoded)
 }
 
+/// Linear combination Σ sᵢ·Pᵢ of G1 points
+///
+/// Zero scalars are skipped and unit scalars cost no multiplication. The
+/// all-zero encoding is the identity, which the backend's G1 add accepts.
+pub fn g1_lincomb<B: Bn254Backend>(
+    curve: &B,
+    points: &[&[u8]],
+    scalars: &[U256],
+) -> Result<[u8; 64]> {
+    if points.len() != scalars.len() {
+        return Err(Error::InvalidInputSize);
+    }
 
+    let mut acc = [0u8; 64];
+    for (point, scalar) in points.iter().zip(scalars) {
+        if *scalar == U256::ZERO {
+            continue;
+        }
+        let term = if *scalar == U256::from(1) {
+            <[u8; 64]>::try_from(*point).map_err(|_| Error::InvalidInputSize)?
+        } else {
+            curve.g1_mul(point, *scalar)?
+        };
+        acc = curve.g1_add(&acc, &term)?;
+    }
+    Ok(acc)
+}
 
-pub fn verify_kzg_batch_opening_with_challenge<S: StaticCallContext + Copy>(
-    context: S,
-    commitments: &[&[u8]], 
+/// Verify several polynomials opened at one point with one proof
+///
+/// Checks the opening of `Σ challengeⁱ·Cᵢ` to `Σ challengeⁱ·yᵢ` at `eval_point`.
+pub fn verify_kzg_batch_opening_with_challenge<B: Bn254Backend>(
+    curve: &B,
+    commitments: &[&[u8]],
     eval_point: U256,
     claimed_evals: &[U256],
     proof: &[u8],
     srs_g2: &[u8],
     challenge: U256,
 ) -> Result<bool> {
-    // 1. Compute agg_commitment = Σ r^i * C_i
-    // 2. Compute agg_eval = Σ r^i * y_i
-    
-    let mut agg_commitment = [0u8; 64]; // Identity? No, need explicit identity handling.
-    // Or start with first term.
-    
-    let mut current_r = U256::from(1);
-    let mut agg_eval = U256::ZERO;
-    let mut initialized = false;
-    
-    for i in 0..commitments.len() {
-        // eval term = eval * r^i
-        let eval_term = fr_mul(claimed_evals[i], current_r);
-        agg_eval = crate::utils::fr_add(agg_eval, eval_term);
-        
-        // comm term = C_i * r^i (Scalar Mul G1)
-        // If r=1, term is C_i.
-        let comm_term = if current_r == U256::from(1) {
-            commitments[i].to_vec()
-        } else {
-            g1_mul(context, commitments[i], current_r)?
-        };
-        
-        if !initialized {
-            agg_commitment.copy_from_slice(&comm_term);
-            initialized = true;
-        } else {
-            let sum = g1_add(context, &agg_commitment, &comm_term)?;
-            agg_commitment.copy_from_slice(&sum);
-        }
-        
-        current_r = fr_mul(current_r, challenge);
+    let opening = PointOpening { point: eval_point, commitments, evals: claimed_evals, proof };
+    verify_kzg_multi_opening(curve, &[opening], challenge, U256::from(1), srs_g2)
+}
+
+/// `[1]₁`
+pub(crate) const G1_GENERATOR: [u8; 64] = {
+    let mut g = [0u8; 64];
+    g[31] = 1;
+    g[63] = 2;
+    g
+};
+
+/// Polynomials opened at one point with one proof
+#[derive(Debug, Clone, Copy)]
+pub struct PointOpening<'a> {
+    pub point: U256,
+    pub commitments: &'a [&'a [u8]],
+    pub evals: &'a [U256],
+    pub proof: &'a [u8],
+}
+
+/// Verify openings at several points
+///
+/// Polynomials at one point fold with powers of `gamma`, points with powers of
+/// `r` (see the module docs); both must come from the transcript after every
+/// commitment, evaluation and proof. Fails with `InvalidInputSize` for no
+/// openings, an opening without polynomials or with fewer evaluations than
+/// commitments, or a malformed point.
+pub fn verify_kzg_multi_opening<B: Bn254Backend>(
+    curve: &B,
+    openings: &[PointOpening<'_>],
+    gamma: U256,
+    r: U256,
+    srs_g2: &[u8],
+) -> Result<bool> {
+    let mut acc = KzgAccumulator::new();
+    let mut weight = U256::from(1);
+    for opening in openings {
+        acc.add_opening(opening, gamma, weight)?;
+        weight = fr_mul(weight, r);
     }
-    
-    // Verify single opening of agg_commitment at eval_point evaluating to agg_eval
-    verify_kzg_opening(context, &agg_commitment, eval_point, agg_eval, proof, srs_g2)
+    acc.check(curve, srs_g2)
 }
 
-// Helpers
-fn g1_mul<S: StaticCallContext + Copy>(context: S, point: &[u8], scalar: U256) -> Result<Vec<u8>> {
-    let mut input = Vec::with_capacity(96);
-    input.extend_from_slice(point);
-    input.extend_from_slice(&scalar.to_be_bytes::<32>());
-    static_call(context, stylus_sdk::alloy_primitives::Address::with_last_byte(BN256_MUL as u8), &input)
-        .map_err(|_| Error::PrecompileFailed)
+/// Deferred KZG check `e(−Σ sᵢ[πᵢ], [τ]₂) · e(Σ tⱼ[Pⱼ], [1]₂) == 1`
+///
+/// Only points and scalars are recorded; a point added twice keeps one entry
+/// with the summed scalar, so commitments shared by several openings (e.g. the
+/// VK of a proof batch) cost one multiplication. `check` runs the two MSMs and
+/// the pairing.
+#[derive(Debug, Clone, Default)]
+pub struct KzgAccumulator<'a> {
+    proofs: Vec<(&'a [u8], U256)>,
+    terms: Vec<(&'a [u8], U256)>,
 }
 
-fn g1_add<S: StaticCallContext + Copy>(context: S, p1: &[u8], p2: &[u8]) -> Result<Vec<u8>> {
-    let mut input = Vec::with_capacity(128);
-    input.extend_from_slice(p1);
-    input.extend_from_slice(p2);
-    static_call(context, stylus_sdk::alloy_primitives::Address::with_last_byte(BN256_ADD as u8), &input)
-        .map_err(|_| Error::PrecompileFailed)
+impl<'a> KzgAccumulator<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `scalar·[π]` on the `[τ]₂` side
+    pub fn add_proof(&mut self, proof: &'a [u8], scalar: U256) -> Result<()> {
+        Self::push(&mut self.proofs, proof, scalar)
+    }
+
+    /// Add `scalar·[P]` on the `[1]₂` side
+    pub fn add_term(&mut self, point: &'a [u8], scalar: U256) -> Result<()> {
+        Self::push(&mut self.terms, point, scalar)
+    }
+
+    /// Add `−y·[1]₁` on the `[1]₂` side
+    pub fn add_eval(&mut self, y: U256) {
+        let _ = Self::push(&mut self.terms, &G1_GENERATOR, fr_sub(U256::ZERO, y));
+    }
+
+    /// Add one point's opening, weighted by `weight`
+    pub fn add_opening(&mut self, opening: &PointOpening<'a>, gamma: U256, weight: U256) -> Result<()> {
+        if opening.commitments.is_empty() || opening.evals.len() != opening.commitments.len() {
+            return Err(Error::InvalidInputSize);
+        }
+        let mut scalar = weight;
+        let mut eval = U256::ZERO;
+        for (commitment, y) in opening.commitments.iter().zip(opening.evals) {
+            self.add_term(commitment, scalar)?;
+            eval = fr_add(eval, fr_mul(scalar, *y));
+            scalar = fr_mul(scalar, gamma);
+        }
+        self.add_eval(eval);
+        self.add_proof(opening.proof, weight)?;
+        self.add_term(opening.proof, fr_mul(weight, opening.point))
+    }
+
+    fn push(list: &mut Vec<(&'a [u8], U256)>, point: &'a [u8], scalar: U256) -> Result<()> {
+        if point.len() != 64 {
+            return Err(Error::InvalidInputSize);
+        }
+        if point.iter().all(|b| *b == 0) {
+            // Identity: contributes nothing
+            return Ok(());
+        }
+        match list.iter_mut().find(|(p, _)| *p == point) {
+            Some((_, acc)) => *acc = fr_add(*acc, scalar),
+            None => list.push((point, scalar)),
+        }
+        Ok(())
+    }
+
+    /// Run the pairing check; an empty accumulator is an error, not a pass
+    pub fn check<B: Bn254Backend>(&self, curve: &B, srs_g2: &[u8]) -> Result<bool> {
+        if srs_g2.len() != 128 || (self.proofs.is_empty() && self.terms.is_empty()) {
+            return Err(Error::InvalidInputSize);
+        }
+        let lincomb = |list: &[(&[u8], U256)]| {
+            let (points, scalars): (Vec<&[u8]>, Vec<U256>) = list.iter().copied().unzip();
+            g1_lincomb(curve, &points, &scalars)
+        };
+        let lhs = negate_g1(&lincomb(&self.proofs)?).ok_or(Error::PrecompileFailed)?;
+        let rhs = lincomb(&self.terms)?;
+
+        let mut pairing_input = Vec::with_capacity(2 * 192);
+        pairing_input.extend_from_slice(&lhs);
+        pairing_input.extend_from_slice(srs_g2);
+        pairing_input.extend_from_slice(&rhs);
+        pairing_input.extend_from_slice(&get_g2_generator());
+        Ok(curve.pairing_check(&pairing_input)?)
+    }
 }
 
 
@@ -327,12 +407,12 @@ mod hex {
     }
 }
 
-fn get_g2_generator() -> [u8; 128] {
+pub(crate) fn get_g2_generator() -> [u8; 128] {
     let mut buf = [0u8; 128];
     // x1
     buf[0..32].copy_from_slice(&hex::decode("198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2").unwrap());
     // x0
-    buf[32..64].copy_from_slice(&hex::decode("1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ad").unwrap());
+    buf[32..64].copy_from_slice(&hex::decode("1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed").unwrap());
     // y1
     buf[64..96].copy_from_slice(&hex::decode("090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b").unwrap());
     // y0
@@ -3,14 +3,18 @@
 
 pub mod kzg;
 pub mod transcript;
+#[allow(clippy::module_inception)]
 pub mod plonk;
-// pub mod srs; // Removed
+pub mod snarkjs;
+pub mod fflonk;
+pub mod srs;
+pub mod ultra;
 
 // Re-export main verification function to match uzkv.rs expectation
-pub use plonk::verify;
+pub use plonk::{batch_verify, batch_verify_with_hash, verify, verify_with_hash};
 
 #[cfg(feature = "std")]
 pub mod host;
 
 #[cfg(feature = "std")]
-pub use host::verify_host;
+pub use host::{verify_fflonk_host, verify_host, verify_host_with_hash, verify_snarkjs_host};
@@ -1,451 +1,660 @@
 //! PLONK Zero-Knowledge Proof Verifier (Precompile Optimized)
 //!
-//! Implements PLONK verification using EVM precompiles and U256 scalar arithmetic.
-//! Removes Arkworks dependencies.
+//! Implements PLONK verification (Gabizon, Williamson, Ciobotaru, §8.3) using
+//! U256 scalar arithmetic, generic over the curve backend (EVM precompiles on
+//! chain, `ArkBackend` elsewhere).
+//!
+//! # Wire Format
+//! ```text
+//! proof: [a] [b] [c] [z] [t_lo] [t_mid] [t_hi] [W_ζ] [W_ζω]   9 × 64 bytes
+//!        a(ζ) b(ζ) c(ζ) sσ1(ζ) sσ2(ζ) z(ζω)                     6 × 32 bytes
+//!
+//! vk:    n (u64) || ℓ (u64)                                       16 bytes
+//!        [q_L] [q_R] [q_O] [q_M] [q_C] [sσ1] [sσ2] [sσ3]       8 × 64 bytes
+//!        ω || k1 || k2                                          3 × 32 bytes
+//! ```
+//! `verify` expects the SRS point `[τ]₂` (128 bytes) appended to the VK.
+//! Public input `i` sits in row `ω^i`, and `z(1) = 1`.
+//!
+//! # Verification
+//! With `r0 = PI(ζ) − L₁(ζ)α² − α(ā + βs̄σ1 + γ)(b̄ + βs̄σ2 + γ)(c̄ + γ)z̄ω`, the
+//! verifier builds the linearization commitment
+//! ```text
+//! [D] = āb̄[q_M] + ā[q_L] + b̄[q_R] + c̄[q_O] + [q_C]
+//!     + ((ā + βζ + γ)(b̄ + βk1ζ + γ)(c̄ + βk2ζ + γ)α + L₁(ζ)α² + u)[z]
+//!     − (ā + βs̄σ1 + γ)(b̄ + βs̄σ2 + γ)αβz̄ω[sσ3]
+//!     − Z_H(ζ)([t_lo] + ζⁿ[t_mid] + ζ²ⁿ[t_hi])
+//! ```
+//! folds every opening at ζ with powers of `v` and the opening at ζω with `u`,
+//! and checks both at once:
+//! `e(−([W_ζ] + u[W_ζω]), [τ]₂) · e(ζ[W_ζ] + uζω[W_ζω] + [F] − [E], [1]₂) == 1`.
+//! The Lagrange bases behind `PI(ζ)` share a single batch inversion
+//! ([`lagrange_evals`]), so the cost per public input is a few multiplications.
+//!
+//! # Batching
+//! `batch_verify` checks N proofs under one VK with the same two pairings: the
+//! equations are weighted by `batch_weights` (a transcript over the VK and the
+//! whole batch) and summed, and the VK points enter the sum once. If the
+//! aggregate fails, the proofs are verified one by one to tell which failed.
 
 use alloc::vec::Vec;
-use stylus_sdk::{
-    alloy_primitives::U256,
-    call::StaticCallContext,
-};
+use stylus_sdk::alloy_primitives::U256;
+use crate::backend::Bn254Backend;
 
 use crate::utils::{
-    fr_add, fr_sub, fr_mul, fr_pow, fr_inv
+    batch_inverse, fr_add, fr_sub, fr_mul, fr_pow, Fr, BN254_SCALAR_MODULUS
 };
+use crate::hash::{Blake3Hasher, Hasher, Keccak256Hasher, PoseidonHasher, Sha256Hasher};
+use crate::types::HashFunctionId;
 use super::transcript::{Transcript, labels};
-use super::kzg::{verify_kzg_batch_opening_with_challenge, Result, Error};
-// use super::srs::Srs; // We might need to mock this or remove if passing SRS manually
+use super::kzg::{KzgAccumulator, Result, Error, G1_GENERATOR};
+
+/// Proof size: 9 G1 points followed by 6 scalars
+pub const PROOF_SIZE: usize = 9 * 64 + 6 * 32;
+
+/// VK size without the trailing SRS point
+pub const VK_SIZE: usize = 16 + 8 * 64 + 3 * 32;
+
+/// SRS point `[τ]₂` appended to the VK
+pub const SRS_G2_SIZE: usize = 128;
+
+/// Wrapper to deserialize and verify (Keccak256 transcript)
+pub fn verify<B: Bn254Backend>(
+    curve: &B,
+    proof_bytes: &[u8],
+    public_inputs_bytes: &[u8],
+    vk_bytes: &[u8],
+) -> Result<bool> {
+    verify_with_hash(curve, HashFunctionId::Keccak256, proof_bytes, public_inputs_bytes, vk_bytes)
+}
 
-/// Wrapper to deserialize and verify
-pub fn verify<S: StaticCallContext + Copy>(
-    context: S,
+/// Deserialize and verify a proof whose transcript uses `hash`
+///
+/// Fails with `Error::UnsupportedHash` for hashes `crate::hash` does not implement.
+pub fn verify_with_hash<B: Bn254Backend>(
+    curve: &B,
+    hash: HashFunctionId,
     proof_bytes: &[u8],
     public_inputs_bytes: &[u8],
     vk_bytes: &[u8],
 ) -> Result<bool> {
+    let (proof, vk, public_inputs, srs_g2) = decode(proof_bytes, public_inputs_bytes, vk_bytes)?;
+    let challenges = compute_challenges_for(hash, &proof, &vk, &public_inputs)?;
+    verify_with_challenges(curve, &proof, &vk, &public_inputs, srs_g2, &challenges)
+}
+
+/// Split `verify` arguments into the proof, VK, public inputs and `[τ]₂`
+pub(crate) fn decode<'a>(
+    proof_bytes: &[u8],
+    public_inputs_bytes: &[u8],
+    vk_bytes: &'a [u8],
+) -> Result<(PlonkProof, PlonkVerificationKey, Vec<U256>, &'a [u8])> {
     let proof = deserialize_plonk_proof(proof_bytes).ok_or(Error::InvalidInputSize)?;
-    let vk = deserialize_plonk_vk(vk_bytes).ok_or(Error::InvalidInputSize)?;
-    
-    // Parse Public Inputs (32 bytes each)
-    if public_inputs_bytes.len() % 32 != 0 {
+    let (vk, srs_g2) = split_vk(vk_bytes)?;
+    let public_inputs = parse_public_inputs(public_inputs_bytes)?;
+    Ok((proof, vk, public_inputs, srs_g2))
+}
+
+/// Parse a VK followed by the SRS point `[τ]₂`
+fn split_vk(vk_bytes: &[u8]) -> Result<(PlonkVerificationKey, &[u8])> {
+    if vk_bytes.len() != VK_SIZE + SRS_G2_SIZE {
         return Err(Error::InvalidInputSize);
     }
-    let num_inputs = public_inputs_bytes.len() / 32;
-    let mut public_inputs = Vec::with_capacity(num_inputs);
-    for i in 0..num_inputs {
-        public_inputs.push(U256::from_be_slice(&public_inputs_bytes[i*32..(i+1)*32]));
+    let vk = deserialize_plonk_vk(&vk_bytes[..VK_SIZE]).ok_or(Error::InvalidInputSize)?;
+    Ok((vk, &vk_bytes[VK_SIZE..]))
+}
+
+/// Parse 32-byte big-endian public inputs, each below r
+pub fn parse_public_inputs(bytes: &[u8]) -> Result<Vec<U256>> {
+    if bytes.len() % 32 != 0 {
+        return Err(Error::InvalidInputSize);
     }
-    
-    // SRS: For now, we assume SRS is hardcoded or setup inside verify_plonk_proof for generic G2 (which it is, verify_kzg uses Preompiles).
-    // Actually, verify_plonk_proof takes `srs_g2: &[u8]`.
-    // We need the SRS [x]2 point.
-    // Ideally this comes from the VK or a separate registry.
-    // For this implementation, let's assume the VK contains the SRS G2 point at the end, 
-    // OR we default to a known SRS.
-    // Limitation: Precompiles need the SRS point.
-    // Let's expect it appended to VK or pass a dummy if using mock.
-    // Standard solution: VK usually includes g2_x.
-    // Let's modify VK parser to look for g2_x at end or separate arg.
-    // uzkv.rs interface is `verify(proof, pi, vk)`.
-    // So VK bytes MUST contain the SRS G2 point (128 bytes).
-    // Let's extract it.
-    
-    // Note: Standard VK usually has G2 elements.
-    // Let's assume the last 128 bytes of VK are the SRS point.
-    let srs_offset = vk_bytes.len().checked_sub(128).ok_or(Error::InvalidInputSize)?;
-    let srs_g2 = &vk_bytes[srs_offset..];
-    
-    verify_plonk_proof(context, &proof, &vk, &public_inputs, srs_g2)
+    bytes
+        .chunks_exact(32)
+        .map(|chunk| {
+            let value = U256::from_be_slice(chunk);
+            if value < BN254_SCALAR_MODULUS { Ok(value) } else { Err(Error::InvalidInputSize) }
+        })
+        .collect()
 }
 
 pub fn deserialize_plonk_proof(bytes: &[u8]) -> Option<PlonkProof> {
-    if bytes.len() != 896 { return None; }
-    
+    if bytes.len() != PROOF_SIZE { return None; }
+
     let mut offset = 0;
-    
-    // Helper macro or inline
-    // We can just slice directly since sizes are fixed and we checked total len.
     let read_g1 = |off: &mut usize| -> [u8; 64] {
         let mut buf = [0u8; 64];
         buf.copy_from_slice(&bytes[*off..*off+64]);
         *off += 64;
         buf
     };
-    let read_fr = |off: &mut usize| -> U256 {
-        let val = U256::from_be_slice(&bytes[*off..*off+32]);
-        *off += 32;
-        val
-    };
+
+    let wire_commitments = [read_g1(&mut offset), read_g1(&mut offset), read_g1(&mut offset)];
+    let permutation_commitment = read_g1(&mut offset);
+    let quotient_commitments = [read_g1(&mut offset), read_g1(&mut offset), read_g1(&mut offset)];
+    let opening_proof_zeta = read_g1(&mut offset);
+    let opening_proof_omega = read_g1(&mut offset);
+
+    let mut evals = [U256::ZERO; 6];
+    for eval in evals.iter_mut() {
+        *eval = read_fr(bytes, &mut offset)?;
+    }
 
     Some(PlonkProof {
-        wire_commitments: [read_g1(&mut offset), read_g1(&mut offset), read_g1(&mut offset)],
-        permutation_commitment: read_g1(&mut offset),
-        quotient_commitments: [read_g1(&mut offset), read_g1(&mut offset), read_g1(&mut offset)],
-        wire_evals: [read_fr(&mut offset), read_fr(&mut offset), read_fr(&mut offset)],
-        permutation_evals: [read_fr(&mut offset), read_fr(&mut offset)],
-        selector_evals: [read_fr(&mut offset), read_fr(&mut offset), read_fr(&mut offset), read_fr(&mut offset), read_fr(&mut offset)],
-        opening_proof_zeta: read_g1(&mut offset),
-        opening_proof_omega: read_g1(&mut offset),
+        wire_commitments,
+        permutation_commitment,
+        quotient_commitments,
+        opening_proof_zeta,
+        opening_proof_omega,
+        wire_evals: [evals[0], evals[1], evals[2]],
+        sigma_evals: [evals[3], evals[4]],
+        permutation_eval_omega: evals[5],
     })
 }
 
+pub fn serialize_plonk_proof(proof: &PlonkProof) -> Vec<u8> {
+    let mut out = Vec::with_capacity(PROOF_SIZE);
+    for point in proof.wire_commitments.iter()
+        .chain(core::iter::once(&proof.permutation_commitment))
+        .chain(proof.quotient_commitments.iter())
+        .chain([&proof.opening_proof_zeta, &proof.opening_proof_omega])
+    {
+        out.extend_from_slice(point);
+    }
+    for eval in proof.wire_evals.iter()
+        .chain(proof.sigma_evals.iter())
+        .chain(core::iter::once(&proof.permutation_eval_omega))
+    {
+        out.extend_from_slice(&eval.to_be_bytes::<32>());
+    }
+    out
+}
+
+/// Parse a VK (without the SRS point)
+///
+/// Rejects domains that are not a power of two, an `ω` that is not an n-th
+/// root of unity, and more public inputs than rows.
 pub fn deserialize_plonk_vk(bytes: &[u8]) -> Option<PlonkVerificationKey> {
-    if bytes.len() < 752 { return None; }
-    
+    if bytes.len() != VK_SIZE { return None; }
+
     let mut offset = 0;
-    let n = u64::from_be_bytes(bytes[offset..offset+8].try_into().ok()?) as usize; offset += 8;
-    let num_inputs = u64::from_be_bytes(bytes[offset..offset+8].try_into().ok()?) as usize; offset += 8;
-    
+    let n = u64::from_be_bytes(bytes[offset..offset+8].try_into().ok()?); offset += 8;
+    let num_inputs = u64::from_be_bytes(bytes[offset..offset+8].try_into().ok()?); offset += 8;
+
     let read_g1 = |off: &mut usize| -> [u8; 64] {
         let mut buf = [0u8; 64];
         buf.copy_from_slice(&bytes[*off..*off+64]);
         *off += 64;
         buf
     };
-    let read_fr = |off: &mut usize| -> U256 {
-        let val = U256::from_be_slice(&bytes[*off..*off+32]);
-        *off += 32;
-        val
-    };
-    
-    Some(PlonkVerificationKey {
-        n,
-        num_public_inputs: num_inputs,
+
+    let vk = PlonkVerificationKey {
+        n: usize::try_from(n).ok()?,
+        num_public_inputs: usize::try_from(num_inputs).ok()?,
         selector_commitments: [read_g1(&mut offset), read_g1(&mut offset), read_g1(&mut offset), read_g1(&mut offset), read_g1(&mut offset)],
         permutation_commitments: [read_g1(&mut offset), read_g1(&mut offset), read_g1(&mut offset)],
-        lagrange_first: read_g1(&mut offset),
-        lagrange_last: read_g1(&mut offset),
-        omega: read_fr(&mut offset),
-        k1: read_fr(&mut offset),
-        k2: read_fr(&mut offset),
-    })
+        omega: read_fr(bytes, &mut offset)?,
+        k1: read_fr(bytes, &mut offset)?,
+        k2: read_fr(bytes, &mut offset)?,
+    };
+
+    let valid_domain = n.is_power_of_two()
+        && num_inputs <= n
+        && fr_pow(vk.omega, U256::from(n)) == U256::from(1);
+    valid_domain.then_some(vk)
+}
+
+pub fn serialize_plonk_vk(vk: &PlonkVerificationKey) -> Vec<u8> {
+    let mut out = Vec::with_capacity(VK_SIZE);
+    out.extend_from_slice(&(vk.n as u64).to_be_bytes());
+    out.extend_from_slice(&(vk.num_public_inputs as u64).to_be_bytes());
+    for point in vk.selector_commitments.iter().chain(vk.permutation_commitments.iter()) {
+        out.extend_from_slice(point);
+    }
+    for scalar in [vk.omega, vk.k1, vk.k2] {
+        out.extend_from_slice(&scalar.to_be_bytes::<32>());
+    }
+    out
+}
+
+/// Read a 32-byte scalar, rejecting non-canonical values
+fn read_fr(bytes: &[u8], offset: &mut usize) -> Option<U256> {
+    let value = U256::from_be_slice(&bytes[*offset..*offset + 32]);
+    *offset += 32;
+    (value < BN254_SCALAR_MODULUS).then_some(value)
 }
 
 /// PLONK proof components (U256 / Raw Bytes)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PlonkProof {
-    pub wire_commitments: [[u8; 64]; 3],
-    pub permutation_commitment: [u8; 64],
-    pub quotient_commitments: [[u8; 64]; 3],
-    
+    pub wire_commitments: [[u8; 64]; 3],     // [a], [b], [c]
+    pub permutation_commitment: [u8; 64],    // [z]
+    pub quotient_commitments: [[u8; 64]; 3], // [t_lo], [t_mid], [t_hi]
+    pub opening_proof_zeta: [u8; 64],        // [W_ζ]
+    pub opening_proof_omega: [u8; 64],       // [W_ζω]
+
     pub wire_evals: [U256; 3],        // a(ζ), b(ζ), c(ζ)
-    pub permutation_evals: [U256; 2], // z(ζ), z(ζω)
-    pub selector_evals: [U256; 5],    // q_L, q_R, q_O, q_M, q_C
-    
-    pub opening_proof_zeta: [u8; 64],
-    pub opening_proof_omega: [u8; 64],
+    pub sigma_evals: [U256; 2],       // sσ1(ζ), sσ2(ζ)
+    pub permutation_eval_omega: U256, // z(ζω)
 }
 
 /// PLONK verification key
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PlonkVerificationKey {
     pub n: usize,
     pub num_public_inputs: usize,
-    pub selector_commitments: [[u8; 64]; 5],
-    pub permutation_commitments: [[u8; 64]; 3],
-    pub lagrange_first: [u8; 64],
-    pub lagrange_last: [u8; 64],
+    pub selector_commitments: [[u8; 64]; 5],    // q_L, q_R, q_O, q_M, q_C
+    pub permutation_commitments: [[u8; 64]; 3], // sσ1, sσ2, sσ3
     pub omega: U256,
     pub k1: U256,
     pub k2: U256,
 }
 
-/// Verify a PLONK proof
-pub fn verify_plonk_proof<S: StaticCallContext + Copy>(
-    context: S,
+/// Fiat-Shamir challenges of one proof
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Challenges {
+    pub beta: U256,
+    pub gamma: U256,
+    pub alpha: U256,
+    pub zeta: U256,
+    pub v: U256,
+    pub u: U256,
+}
+
+/// Replay the transcript (Keccak256)
+///
+/// Each challenge only depends on what precedes it, so a prover can call this
+/// on a partially filled proof to get the challenges of the rounds done so far.
+pub fn compute_challenges(
     proof: &PlonkProof,
     vk: &PlonkVerificationKey,
     public_inputs: &[U256],
-    srs_g2: &[u8], // Passed as raw bytes from generic storage/input
-) -> Result<bool> {
-    // Input validation
-    if public_inputs.len() != vk.num_public_inputs {
-        return Err(Error::InvalidInputSize); // Mapped error
+) -> Challenges {
+    compute_challenges_with::<Keccak256Hasher>(proof, vk, public_inputs)
+}
+
+/// Replay the transcript with the hash named by a descriptor's `hash_function_id`
+pub fn compute_challenges_for(
+    hash: HashFunctionId,
+    proof: &PlonkProof,
+    vk: &PlonkVerificationKey,
+    public_inputs: &[U256],
+) -> Result<Challenges> {
+    let challenges = match hash {
+        HashFunctionId::Keccak256 => compute_challenges_with::<Keccak256Hasher>(proof, vk, public_inputs),
+        HashFunctionId::SHA256 => compute_challenges_with::<Sha256Hasher>(proof, vk, public_inputs),
+        HashFunctionId::Blake3 => compute_challenges_with::<Blake3Hasher>(proof, vk, public_inputs),
+        HashFunctionId::Poseidon => compute_challenges_with::<PoseidonHasher>(proof, vk, public_inputs),
+        HashFunctionId::RescuePrime | HashFunctionId::Blake2b => return Err(Error::UnsupportedHash),
+    };
+    Ok(challenges)
+}
+
+/// Replay the transcript over `H`
+///
+/// Absorption order and labels are the same for every hash.
+pub fn compute_challenges_with<H: Hasher>(
+    proof: &PlonkProof,
+    vk: &PlonkVerificationKey,
+    public_inputs: &[U256],
+) -> Challenges {
+    let mut transcript = Transcript::<H>::with_hasher(labels::PLONK_PROTOCOL);
+
+    // Bind the circuit: domain, commitments and permutation constants
+    transcript.absorb_bytes(labels::VK_DOMAIN, &(vk.n as u64).to_be_bytes());
+    transcript.absorb_bytes(labels::VK_DOMAIN, &(vk.num_public_inputs as u64).to_be_bytes());
+    for comm in vk.selector_commitments.iter().chain(vk.permutation_commitments.iter()) {
+        transcript.absorb_point_bytes(labels::VK_DOMAIN, comm);
+    }
+    for constant in [&vk.omega, &vk.k1, &vk.k2] {
+        transcript.absorb_field(labels::VK_DOMAIN, constant);
     }
 
-    // Step 1: Initialize Transcript
-    let mut transcript = Transcript::new(labels::PLONK_PROTOCOL);
-    
-    // Absorb VK (simplified for now, absorbing crucial elements)
-    transcript.absorb_bytes(labels::VK_DOMAIN, &vk.n.to_be_bytes());
-    // Absorb commitments...
-    
-    // Absorb Public Inputs
     for input in public_inputs {
         transcript.absorb_field(labels::PUBLIC_INPUT, input);
     }
-    
-    // Step 2: Wire Commitments -> Beta, Gamma
+
+    // Round 1: wires -> β, γ
     for comm in &proof.wire_commitments {
         transcript.absorb_point_bytes(labels::WIRE_COMMITMENT, comm);
     }
     let beta = transcript.squeeze_challenge(labels::BETA_CHALLENGE);
     let gamma = transcript.squeeze_challenge(labels::GAMMA_CHALLENGE);
-    
-    // Step 3: Permutation Commitment -> Alpha
+
+    // Round 2: grand product -> α
     transcript.absorb_point_bytes(labels::PERMUTATION_COMMITMENT, &proof.permutation_commitment);
     let alpha = transcript.squeeze_challenge(labels::ALPHA_CHALLENGE);
-    
-    // Step 4: Quotient Commitments -> Zeta
+
+    // Round 3: quotient -> ζ
     for comm in &proof.quotient_commitments {
         transcript.absorb_point_bytes(labels::QUOTIENT_COMMITMENT, comm);
     }
     let zeta = transcript.squeeze_challenge(labels::ZETA_CHALLENGE);
-    
-    // Step 5: Evaluations -> v
-    let mut evals_to_absorb = Vec::new();
-    evals_to_absorb.extend_from_slice(&proof.wire_evals);
-    evals_to_absorb.extend_from_slice(&proof.permutation_evals);
-    evals_to_absorb.extend_from_slice(&proof.selector_evals);
-    
-    for eval in evals_to_absorb {
-        transcript.absorb_field(labels::WIRE_EVAL, &eval);
+
+    // Round 4: evaluations -> v
+    for eval in &proof.wire_evals {
+        transcript.absorb_field(labels::WIRE_EVAL, eval);
+    }
+    for eval in proof.sigma_evals.iter().chain(core::iter::once(&proof.permutation_eval_omega)) {
+        transcript.absorb_field(labels::PERMUTATION_EVAL, eval);
     }
     let v = transcript.squeeze_challenge(labels::V_CHALLENGE);
-    
-    // Step 6: Opening Proofs -> u
+
+    // Round 5: opening proofs -> u
     transcript.absorb_point_bytes(labels::OPENING_PROOF, &proof.opening_proof_zeta);
     transcript.absorb_point_bytes(labels::OPENING_PROOF, &proof.opening_proof_omega);
     let u = transcript.squeeze_challenge(labels::U_CHALLENGE);
-    
-    // Step 7: PI(ζ)
-    // Needs compute_public_input_eval using fr_*
-    let pi_zeta = compute_public_input_eval(public_inputs, zeta, vk.omega, vk.n)?;
-    
-    // Step 8: Z_H(ζ) = ζ^n - 1
-    let n_u256 = U256::from(vk.n); // Assuming n fits in u64, definitely fits U256
-    let zh_zeta = fr_sub(fr_pow(zeta, n_u256), U256::from(1));
-    
-    // Step 9: L1(ζ)
-    let l1_zeta = compute_lagrange_first(zeta, zh_zeta, vk.omega, vk.n);
-    
-    // Step 10: Verify Gate Constraints (Simple Check)
-    // Full constraint verification requires evaluating the large polynomial D(z).
-    // D(z) = (Quotients * Z_H) - ( Linearization )
-    // Just verifying that `verify_kzg_batch_opening` works implies D(z) was committed?
-    // No, D(z) is constructed by the verifier during Linearization.
-    
-    // For this Phase 2 Refactor / MVP, we will implement the PLONK logic accurately 
-    // but simplify slightly if optimizing for gas/size.
-    //
-    // Gate Constraint: 
-    // Q_L*a + Q_R*b + Q_O*c + Q_M*a*b + Q_C + PI = 0
-    //
-    // Permutation Constraint:
-    // (a + beta*z + gamma)(b + beta*k1*z + gamma)(c + beta*k2*z + gamma)*z(z)
-    // - (a + beta*s1 + gamma)(b + beta*s2 + gamma)(c + beta*s3 + gamma)*z(zw) = 0
-    //
-    // These must hold at z.
-    // We compute the 'claimed' values from evaluations (provided in proof wire_evals).
-    // Then we check if they sum to 0? No.
-    // The PROVER provides quotient commitments T satisfying these equations.
-    // The VERIFIER checks that T(z) * Z_H(z) matches the constraints.
-    //
-    // linearized_polynomial_eval = 
-    //   Gate_Constraint_Eval
-    // + alpha * Permutation_Constraint_Eval (excluding z(zw) term? No, usually z(zw) is opened)
-    // + alpha^2 * (L1(z) * (z(z) - 1))
-    //
-    // Then check: T(z) * Z_H(z) = linearized_polynomial_eval ?
-    // Actually, T is split into t_lo, t_mid, t_hi.
-    // t(z) = t_lo + z^n t_mid + z^2n t_hi
-    
-    // 1. Reconstruct t(z) evaluation from commitments? No, we don't have t_evals in proof struct!
-    // Standard PLONK proof provides t_openings? No, usually t is opened at z.
-    // My `PlonkProof` struct DOES NOT have `t_eval`.
-    // Wait, the standard protocol (Gabizon) has the prover send `t_lo, t_mid, t_hi` commitments,
-    // AND the evaluation `t(z)` is needed?
-    // Usually, the verifier computes `D` (linearization) and checks `D - t(z)*ZH(z) = 0` at z using KZG?
-    // If t is committed, we need `t(z)`.
-    // Ah, my PlonkProof struct has `quotient_commitments` but missing `quotient_eval`?
-    // Most implementations include `quotient_eval` or `t_eval` in the proof.
-    // CHECK Reference: SnarkJS / Halo2.
-    // They usually provide `t(z)`.
-    // My struct missed it!
-    // I should add `quotient_eval: U256` to `PlonkProof`?
-    // OR proceed without it if implicitly handled?
-    // No, implicit handling means we open D at z? D is linear combo of commitments.
-    // We can compute D's commitment, but we can't open it withoutProver help?
-    // The "Linearization" trick allows computing the commitment to 'r' (remainder).
-    // [r] = [q_L]*a + [q_R]*b ...
-    // Then we verify opening of (D + [r])?
-    //
-    // Let's Add `quotient_eval` to `PlonkProof` struct for correctness.
-    
-    // For now, I will proceed with the constraints check logic assuming we verify 
-    // the constraints algebraically on the evaluations.
-    // i.e., Check if `Gate(...) + alpha*Perm(...) == 0`.
-    // This is valid ONLY if quotient is zero (perfect satisfaction).
-    // BUT in ZK-SNARK, quotient is not zero (it divides Z_H).
-    // So `Constraint(...) = t(z) * Z_H(z)`.
-    // We can CHECK this equality using the provided evaluations!
-    // IF we trust the provided evaluations (which we check via KZG).
-    // Yes! The strategy is:
-    // 1. Compute LHS = Constraint( evaluations )
-    // 2. Compute RHS = t(z) * Z_H(z) via OPENING t(z).
-    // So we need t(z).
-    
-    // Since I can't easily change the struct in a `multi_replace` without potential conflict if I messed up lines,
-    // I'll assume for this precise step that `t(z)` is derived or handled.
-    // Wait, if I don't have t(z), I can't check the equation.
-    // I will verify assuming `t(z) = 0` for now (Weak security, but functional flow), 
-    // OR I will simply compute the constraints and print/log them.
-    //
-    // Given the task is "Refactor", getting the full constraints logic correct is important.
-    // I'll add a `TODO` for `t(z)` and implement the Gate Logic.
-    
-    let a = proof.wire_evals[0];
-    let b = proof.wire_evals[1];
-    let c = proof.wire_evals[2];
-    let ql = proof.selector_evals[0];
-    let qr = proof.selector_evals[1];
-    let qo = proof.selector_evals[2];
-    let qm = proof.selector_evals[3];
-    let qc = proof.selector_evals[4];
-    
-    // Gate: qL*a + qR*b + qO*c + qM*a*b + qC + PI
-    let term_lin = fr_add(
-        fr_add(fr_mul(ql, a), fr_mul(qr, b)),
-        fr_mul(qo, c)
-    );
-    let term_mul = fr_mul(qm, fr_mul(a, b));
-    let term_const = fr_add(qc, pi_zeta);
-    
-    let gate_val = fr_add(fr_add(term_lin, term_mul), term_const);
-    
-    // Verify Gate Constraint Satisfiability
-    if gate_val != U256::ZERO {
+
+    Challenges { beta, gamma, alpha, zeta, v, u }
+}
+
+/// Verify a PLONK proof
+pub fn verify_plonk_proof<B: Bn254Backend>(
+    curve: &B,
+    proof: &PlonkProof,
+    vk: &PlonkVerificationKey,
+    public_inputs: &[U256],
+    srs_g2: &[u8], // Passed as raw bytes from generic storage/input
+) -> Result<bool> {
+    let challenges = compute_challenges(proof, vk, public_inputs);
+    verify_with_challenges(curve, proof, vk, public_inputs, srs_g2, &challenges)
+}
+
+/// Run the verifier checks with challenges from any transcript
+///
+/// The math is transcript agnostic; `compute_challenges` and
+/// `snarkjs::compute_challenges` only differ in how they derive the challenges.
+pub fn verify_with_challenges<B: Bn254Backend>(
+    curve: &B,
+    proof: &PlonkProof,
+    vk: &PlonkVerificationKey,
+    public_inputs: &[U256],
+    srs_g2: &[u8],
+    challenges: &Challenges,
+) -> Result<bool> {
+    if srs_g2.len() != SRS_G2_SIZE {
+        return Err(Error::InvalidInputSize);
+    }
+    let Some(check) = opening_check(proof, vk, public_inputs, challenges)? else {
         return Ok(false);
+    };
+    let mut acc = KzgAccumulator::new();
+    check.accumulate(&mut acc, vk, U256::from(1))?;
+    acc.check(curve, srs_g2)
+}
+
+/// VK points of the opening check, in `OpeningCheck::vk_scalars` order
+fn vk_points(vk: &PlonkVerificationKey) -> [&[u8]; 9] {
+    let [ql, qr, qo, qm, qc] = &vk.selector_commitments;
+    let [sigma1, sigma2, sigma3] = &vk.permutation_commitments;
+    [qm, ql, qr, qo, qc, sigma1, sigma2, sigma3, &G1_GENERATOR]
+}
+
+/// One proof's pairing equation before any point is added:
+/// `e(−([W_ζ] + u[W_ζω]), [τ]₂) · e([R], [1]₂) == 1` with
+/// `[R] = Σ proof_scalars·proof_points + Σ vk_scalars·vk_points(vk)`
+struct OpeningCheck<'a> {
+    proof_points: [&'a [u8]; 9],
+    proof_scalars: [U256; 9],
+    /// Scalars of [q_M] [q_L] [q_R] [q_O] [q_C] [sσ1] [sσ2] [sσ3] [1]
+    vk_scalars: [U256; 9],
+    u: U256,
+}
+
+impl<'a> OpeningCheck<'a> {
+    /// Add `weight` times this equation: `[W_ζ] + u[W_ζω]` against `[τ]₂`,
+    /// `ζ[W_ζ] + uζω[W_ζω] + [D] + Σ vⁱ[pᵢ] − [E]` against `[1]₂`
+    fn accumulate(&self, acc: &mut KzgAccumulator<'a>, vk: &'a PlonkVerificationKey, weight: U256) -> Result<()> {
+        acc.add_proof(self.proof_points[0], weight)?;
+        acc.add_proof(self.proof_points[1], fr_mul(weight, self.u))?;
+        let vk_points = vk_points(vk);
+        let points = self.proof_points.iter().chain(&vk_points);
+        for (point, scalar) in points.zip(self.proof_scalars.iter().chain(&self.vk_scalars)) {
+            acc.add_term(point, fr_mul(weight, *scalar))?;
+        }
+        Ok(())
     }
-    
-    // Permutation constraint
-    // (a + beta*z + gamma)(...)
-    // ...
-    // Silence unused for now as we simplified permutation check
-    let _ = beta;
-    let _ = gamma;
-    let _ = alpha;
-    let _ = u;
-    let _ = l1_zeta;
-    
-    // Step 11: Batch KZG
-    // To verify openings of [W_a], [W_b], [W_c] at z => evaluates to a, b, c
-    // We batch these into one check.
-    
-    let mut batch_comms: Vec<&[u8]> = Vec::new();
-    let mut batch_evals: Vec<U256> = Vec::new();
-    
-    // Wires
-    for (i, comm) in proof.wire_commitments.iter().enumerate() {
-        batch_comms.push(comm);
-        batch_evals.push(proof.wire_evals[i]);
+}
+
+/// Scalars of the opening check, `None` if ζ falls in the domain
+fn opening_check<'a>(
+    proof: &'a PlonkProof,
+    vk: &PlonkVerificationKey,
+    public_inputs: &[U256],
+    challenges: &Challenges,
+) -> Result<Option<OpeningCheck<'a>>> {
+    if public_inputs.len() != vk.num_public_inputs {
+        return Err(Error::InvalidInputSize);
     }
-    // Permutation
-    batch_comms.push(&proof.permutation_commitment);
-    batch_evals.push(proof.permutation_evals[0]); // z(zeta)
-    
-    // Selectors
-    for (i, comm) in vk.selector_commitments.iter().enumerate() {
-        batch_comms.push(comm);
-        batch_evals.push(proof.selector_evals[i]);
+
+    let Challenges { beta, gamma, alpha, zeta, v, u } = *challenges;
+    let one = U256::from(1);
+
+    // Z_H(ζ) = ζⁿ − 1; ζ inside the domain would drop the quotient from [D]
+    let zeta_n = fr_pow(zeta, U256::from(vk.n));
+    let zh_zeta = fr_sub(zeta_n, one);
+    if zh_zeta == U256::ZERO {
+        return Ok(None);
     }
-    
-    // Call Batch Verify for Zeta
-    // Note: This logic assumes simple "Check all these match" at zeta.
-    // It verifies: P_i(zeta) = eval_i
-    let valid_zeta = verify_kzg_batch_opening_with_challenge(
-        context,
-        &batch_comms,
-        zeta,
-        &batch_evals,
-        &proof.opening_proof_zeta,
-        srs_g2,
-        v, // mixing challenge
-    )?;
-    
-    if !valid_zeta { return Ok(false); }
-    
-    // Call Verify for Omega (z(zw))
-    // Only one polynomial: Permutation (z)
-    // Eval: z(zw)
-    let zw = fr_mul(zeta, vk.omega);
-    let valid_omega = super::kzg::verify_kzg_opening(
-        context,
-        &proof.permutation_commitment,
-        zw,
-        proof.permutation_evals[1], // z(zw)
-        &proof.opening_proof_omega,
-        srs_g2
-    )?;
-    
-    if !valid_omega { return Ok(false); }
-    
-    Ok(true) 
+
+    // L₁(ζ) and PI(ζ) = −Σ xᵢ·Lᵢ(ζ)
+    let lagrange = lagrange_evals(zeta, vk.omega, vk.n, public_inputs.len().max(1));
+    let l1_zeta = lagrange[0];
+    let pi_zeta = public_inputs
+        .iter()
+        .zip(&lagrange)
+        .fold(U256::ZERO, |acc, (x, l)| fr_sub(acc, fr_mul(*x, *l)));
+
+    let [a, b, c] = proof.wire_evals;
+    let [s1, s2] = proof.sigma_evals;
+    let zw = proof.permutation_eval_omega;
+    let alpha2 = fr_mul(alpha, alpha);
+
+    // (ā + βs̄σ1 + γ)(b̄ + βs̄σ2 + γ), shared by r0 and the [sσ3] term
+    let sigma_terms = fr_mul(
+        fr_add(fr_add(a, fr_mul(beta, s1)), gamma),
+        fr_add(fr_add(b, fr_mul(beta, s2)), gamma),
+    );
+
+    // r0 = PI(ζ) − L₁(ζ)α² − α·sigma_terms·(c̄ + γ)·z̄ω
+    let r0 = fr_sub(
+        fr_sub(pi_zeta, fr_mul(l1_zeta, alpha2)),
+        fr_mul(fr_mul(alpha, sigma_terms), fr_mul(fr_add(c, gamma), zw)),
+    );
+
+    // [z]: (ā + βζ + γ)(b̄ + βk1ζ + γ)(c̄ + βk2ζ + γ)α + L₁(ζ)α² + u
+    let beta_zeta = fr_mul(beta, zeta);
+    let id_terms = fr_mul(
+        fr_mul(
+            fr_add(fr_add(a, beta_zeta), gamma),
+            fr_add(fr_add(b, fr_mul(beta_zeta, vk.k1)), gamma),
+        ),
+        fr_add(fr_add(c, fr_mul(beta_zeta, vk.k2)), gamma),
+    );
+    let z_scalar = fr_add(fr_add(fr_mul(id_terms, alpha), fr_mul(l1_zeta, alpha2)), u);
+
+    // [sσ3]: −sigma_terms·αβ·z̄ω
+    let s3_scalar = fr_sub(U256::ZERO, fr_mul(fr_mul(sigma_terms, fr_mul(alpha, beta)), zw));
+
+    // Quotient pieces: −Z_H(ζ)·(1, ζⁿ, ζ²ⁿ)
+    let t_lo_scalar = fr_sub(U256::ZERO, zh_zeta);
+    let t_mid_scalar = fr_mul(t_lo_scalar, zeta_n);
+    let t_hi_scalar = fr_mul(t_mid_scalar, zeta_n);
+
+    // Powers of v for a, b, c, sσ1, sσ2
+    let mut v_powers = [v; 5];
+    for i in 1..5 {
+        v_powers[i] = fr_mul(v_powers[i - 1], v);
+    }
+
+    // [E] scalar: −r0 + Σ vⁱ·evalᵢ + u·z̄ω
+    let evals = [a, b, c, s1, s2];
+    let e_scalar = evals
+        .iter()
+        .zip(&v_powers)
+        .fold(fr_add(fr_sub(U256::ZERO, r0), fr_mul(u, zw)), |acc, (eval, vi)| {
+            fr_add(acc, fr_mul(*eval, *vi))
+        });
+
+    let [wire_a, wire_b, wire_c] = &proof.wire_commitments;
+    let [t_lo, t_mid, t_hi] = &proof.quotient_commitments;
+    let zeta_omega = fr_mul(zeta, vk.omega);
+
+    Ok(Some(OpeningCheck {
+        proof_points: [
+            &proof.opening_proof_zeta, &proof.opening_proof_omega,
+            &proof.permutation_commitment,
+            t_lo, t_mid, t_hi,
+            wire_a, wire_b, wire_c,
+        ],
+        proof_scalars: [
+            zeta, fr_mul(u, zeta_omega),
+            z_scalar,
+            t_lo_scalar, t_mid_scalar, t_hi_scalar,
+            v_powers[0], v_powers[1], v_powers[2],
+        ],
+        vk_scalars: [
+            fr_mul(a, b), a, b, c, one,
+            v_powers[3], v_powers[4], s3_scalar,
+            fr_sub(U256::ZERO, e_scalar),
+        ],
+        u,
+    }))
 }
 
-fn compute_public_input_eval(pis: &[U256], z: U256, omega: U256, n: usize) -> Result<U256> {
-    if pis.is_empty() {
-        return Ok(U256::ZERO);
+/// Batch transcript weights r_i, one per proof
+///
+/// The VK and every proof and input (length-prefixed) are absorbed before the
+/// first weight is squeezed, so no proof can be chosen after its weight.
+pub fn batch_weights(vk_bytes: &[u8], proofs: &[Vec<u8>], inputs: &[Vec<u8>]) -> Vec<U256> {
+    let mut transcript = Transcript::new(labels::PLONK_BATCH_PROTOCOL);
+    transcript.absorb_bytes(labels::VK_DOMAIN, vk_bytes);
+    for (proof, input) in proofs.iter().zip(inputs) {
+        transcript.absorb_bytes(labels::BATCH_PROOF, proof);
+        transcript.absorb_bytes(labels::BATCH_INPUT, input);
     }
-    
-    // Z_H(z) = z^n - 1
-    let n_u256 = U256::from(n);
-    let zh = fr_sub(fr_pow(z, n_u256), U256::from(1));
-    
-    let mut result = U256::ZERO;
-    let mut omega_i = U256::from(1); // omega^0 = 1
-    
-    for pi in pis {
-        // L_i(z) = (z^n - 1) * omega^i / (n * (z - omega^i))
-        // numerator = zh * omega_i
-        let numerator = fr_mul(zh, omega_i);
-        
-        // denominator = n * (z - omega^i)
-        let z_minus_omega_i = fr_sub(z, omega_i);
-        let denominator = fr_mul(n_u256, z_minus_omega_i);
-        
-        if denominator == U256::ZERO {
-             return Err(Error::InvalidInputSize); // Domain invalid
-        }
-        
-        let denom_inv = fr_inv(denominator).ok_or(Error::InvalidInputSize)?;
-        let li = fr_mul(numerator, denom_inv);
-        
-        // term = pi * L_i
-        let term = fr_mul(*pi, li);
-        
-        // PI(z) = sum( -pi * Li )? 
-        // Standard PLONK constraint is usually: PI(z) + gate(...) = 0
-        // So PI(z) is positive. Check if subtraction is needed in formulation.
-        // Usually: q_L*a + ... + PI(z) = 0.
-        // And PI(z) = \sum P_i L_i(z).
-        // Let's assume standard sum.
-        result = fr_add(result, term);
-        
-        // omega^{i+1}
-        omega_i = fr_mul(omega_i, omega);
+    (0..proofs.len()).map(|_| transcript.squeeze_challenge(labels::BATCH_WEIGHT)).collect()
+}
+
+/// Verify N proofs under one VK (Keccak256 transcripts), one result per proof
+pub fn batch_verify<B: Bn254Backend>(
+    curve: &B,
+    proofs: &[Vec<u8>],
+    inputs: &[Vec<u8>],
+    vk_bytes: &[u8],
+) -> Result<Vec<bool>> {
+    batch_verify_with_hash(curve, HashFunctionId::Keccak256, proofs, inputs, vk_bytes)
+}
+
+/// Verify N proofs under one VK, one result per proof
+///
+/// Runs `verify_batch_with_hash` first; only if the aggregate check fails is
+/// every proof verified on its own, so the results name the failing proofs. A
+/// malformed proof or input fails that proof only; a malformed VK or an
+/// unsupported hash fails the batch.
+pub fn batch_verify_with_hash<B: Bn254Backend>(
+    curve: &B,
+    hash: HashFunctionId,
+    proofs: &[Vec<u8>],
+    inputs: &[Vec<u8>],
+    vk_bytes: &[u8],
+) -> Result<Vec<bool>> {
+    if proofs.len() != inputs.len() {
+        return Err(Error::InvalidInputSize);
+    }
+    split_vk(vk_bytes)?;
+
+    if proofs.len() > 1 && matches!(verify_batch_with_hash(curve, hash, proofs, inputs, vk_bytes), Ok(true)) {
+        return Ok(alloc::vec![true; proofs.len()]);
     }
-    
-    // Note: Gates usually use -PI(z) or move PI to other side.
-    // If constraint is sum + PI = 0, then we use PI.
-    // However, Gabizon usage typically sets PI(x) = \sum -P_i * L_i(x) to move to RHS.
-    // Let's implement POSITIVE sum here, and negate it in constraint if needed.
-    Ok(result)
+
+    proofs
+        .iter()
+        .zip(inputs)
+        .map(|(proof, input)| match verify_with_hash(curve, hash, proof, input, vk_bytes) {
+            Err(Error::UnsupportedHash) => Err(Error::UnsupportedHash),
+            result => Ok(result == Ok(true)),
+        })
+        .collect()
 }
 
-fn compute_lagrange_first(z: U256, zh: U256, _omega: U256, n: usize) -> U256 {
-    // L_1(z) = (z^n - 1) / (n * (z - 1))  (assuming omega^0 = 1 is first index)
-    //        = zh / (n * (z - 1))
-    
-    let n_u256 = U256::from(n);
-    let z_minus_1 = fr_sub(z, U256::from(1));
-    
-    if z_minus_1 == U256::ZERO {
-        return U256::from(1); // L_1(1) = 1
+/// Aggregate check of N proofs under one VK with two pairings
+///
+/// Each proof's equation `e(−[Wᵢ], [τ]₂) · e([Rᵢ], [1]₂) == 1` is weighted by
+/// its `batch_weights` r_i and summed, so
+/// `e(−Σ rᵢ[Wᵢ], [τ]₂) · e(Σ rᵢ[Rᵢ], [1]₂) == 1`. The VK points appear once,
+/// with the weighted sum of their scalars. Returns `Ok(true)` only if every
+/// proof is valid (except with probability ~N/r).
+pub fn verify_batch_with_hash<B: Bn254Backend>(
+    curve: &B,
+    hash: HashFunctionId,
+    proofs: &[Vec<u8>],
+    inputs: &[Vec<u8>],
+    vk_bytes: &[u8],
+) -> Result<bool> {
+    if proofs.is_empty() || proofs.len() != inputs.len() {
+        return Err(Error::InvalidInputSize);
     }
-    
-    let denom = fr_mul(n_u256, z_minus_1);
-    let denom_inv = fr_inv(denom).unwrap_or(U256::ZERO);
-    
-    fr_mul(zh, denom_inv)
+    let (vk, srs_g2) = split_vk(vk_bytes)?;
+    let decoded = proofs
+        .iter()
+        .zip(inputs)
+        .map(|(proof, input)| {
+            let proof = deserialize_plonk_proof(proof).ok_or(Error::InvalidInputSize)?;
+            Ok((proof, parse_public_inputs(input)?))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let weights = batch_weights(vk_bytes, proofs, inputs);
+
+    // The accumulator merges the VK points of all proofs
+    let mut acc = KzgAccumulator::new();
+    for ((proof, public_inputs), r) in decoded.iter().zip(&weights) {
+        let challenges = compute_challenges_for(hash, proof, &vk, public_inputs)?;
+        let Some(check) = opening_check(proof, &vk, public_inputs, &challenges)? else {
+            return Ok(false);
+        };
+        check.accumulate(&mut acc, &vk, *r)?;
+    }
+    acc.check(curve, srs_g2)
+}
+
+/// Lagrange bases L₀(ζ) .. L_{count−1}(ζ) over the domain of size n
+///
+/// Shared by the Stylus verifiers and `host.rs`; see [`lagrange_bases`].
+pub fn lagrange_evals(zeta: U256, omega: U256, n: usize, count: usize) -> Vec<U256> {
+    let omega = Fr::from_u256_reduced(omega);
+    let mut points = Vec::with_capacity(count);
+    let mut omega_i = Fr::ONE;
+    for _ in 0..count {
+        points.push(omega_i);
+        omega_i = omega_i.mul(&omega);
+    }
+    lagrange_bases(Fr::from_u256_reduced(zeta), n, &points)
+        .into_iter()
+        .map(Fr::to_u256)
+        .collect()
+}
+
+/// Lagrange bases of the size-n domain at ζ, one per domain point `ωⁱ`
+///
+/// Barycentric form `Lᵢ(ζ) = ωⁱ·Z_H(ζ) / (n·(ζ − ωⁱ))`, with every
+/// `n·(ζ − ωⁱ)` inverted in one batch. When ζ is itself a domain point ωᵏ,
+/// `Lᵢ(ζ)` is 1 for i = k and 0 otherwise.
+pub fn lagrange_bases(zeta: Fr, n: usize, points: &[Fr]) -> Vec<Fr> {
+    let zh_zeta = zeta.pow(U256::from(n)).sub(&Fr::ONE);
+    let n = Fr::from_u256_reduced(U256::from(n));
+    let mut inverses: Vec<Fr> = points.iter().map(|point| n.mul(&zeta.sub(point))).collect();
+    batch_inverse(&mut inverses);
+
+    points
+        .iter()
+        .zip(inverses)
+        .map(|(point, inv)| {
+            if *point == zeta {
+                Fr::ONE
+            } else {
+                point.mul(&zh_zeta).mul(&inv)
+            }
+        })
+        .collect()
 }
@@ -0,0 +1,121 @@
+//! snarkjs-compatible PLONK
+//!
+//! snarkjs (0.7) proves the same PLONK as `plonk.rs` with the same proof and VK
+//! layout (see `formats::snarkjs`), but derives its challenges from its own
+//! Keccak256 transcript. This module replays that transcript so snarkjs proofs
+//! verify as they are; the checks themselves are `plonk::verify_with_challenges`.
+//!
+//! # Transcript
+//! Each challenge is `keccak256(data) mod r`, where `data` is the concatenation
+//! of what was added since the last reset: G1 points as `x || y` (64 bytes),
+//! scalars as 32 bytes, all big-endian.
+//! ```text
+//! β = H([Qm] [Ql] [Qr] [Qo] [Qc] [S1] [S2] [S3] || public inputs || [A] [B] [C])
+//! γ = H(β)
+//! α = H(β || γ || [Z])
+//! ξ = H(α || [T1] [T2] [T3])
+//! v = H(ξ || a(ξ) b(ξ) c(ξ) s1(ξ) s2(ξ) z(ξω))
+//! u = H([Wxi] [Wxiw])
+//! ```
+//! snarkjs names the evaluation point ξ; here it is `Challenges::zeta`.
+
+use stylus_sdk::alloy_primitives::U256;
+use sha3::{Digest, Keccak256};
+
+use crate::backend::Bn254Backend;
+use crate::utils::fr_from_be_bytes_mod;
+use super::kzg::Result;
+use super::plonk::{decode, verify_with_challenges, Challenges, PlonkProof, PlonkVerificationKey};
+
+/// Verify a snarkjs PLONK proof (same byte layout as `plonk::verify`)
+pub fn verify<B: Bn254Backend>(
+    curve: &B,
+    proof_bytes: &[u8],
+    public_inputs_bytes: &[u8],
+    vk_bytes: &[u8],
+) -> Result<bool> {
+    let (proof, vk, public_inputs, srs_g2) = decode(proof_bytes, public_inputs_bytes, vk_bytes)?;
+    let challenges = compute_challenges(&proof, &vk, &public_inputs);
+    verify_with_challenges(curve, &proof, &vk, &public_inputs, srs_g2, &challenges)
+}
+
+/// Replay the snarkjs transcript
+///
+/// As with `plonk::compute_challenges`, each challenge only depends on the
+/// rounds before it.
+pub fn compute_challenges(
+    proof: &PlonkProof,
+    vk: &PlonkVerificationKey,
+    public_inputs: &[U256],
+) -> Challenges {
+    let [ql, qr, qo, qm, qc] = &vk.selector_commitments;
+    let mut transcript = Keccak256Transcript::new();
+
+    // Round 1: circuit, public inputs and wires -> β; γ = H(β)
+    for comm in [qm, ql, qr, qo, qc].into_iter().chain(&vk.permutation_commitments) {
+        transcript.add_point(comm);
+    }
+    for input in public_inputs {
+        transcript.add_scalar(input);
+    }
+    for comm in &proof.wire_commitments {
+        transcript.add_point(comm);
+    }
+    let beta = transcript.challenge();
+    transcript.add_scalar(&beta);
+    let gamma = transcript.challenge();
+
+    // Round 2: grand product -> α
+    transcript.add_scalar(&beta);
+    transcript.add_scalar(&gamma);
+    transcript.add_point(&proof.permutation_commitment);
+    let alpha = transcript.challenge();
+
+    // Round 3: quotient -> ξ
+    transcript.add_scalar(&alpha);
+    for comm in &proof.quotient_commitments {
+        transcript.add_point(comm);
+    }
+    let zeta = transcript.challenge();
+
+    // Round 4: evaluations -> v
+    transcript.add_scalar(&zeta);
+    for eval in proof.wire_evals.iter().chain(&proof.sigma_evals) {
+        transcript.add_scalar(eval);
+    }
+    transcript.add_scalar(&proof.permutation_eval_omega);
+    let v = transcript.challenge();
+
+    // Round 5: opening proofs -> u
+    transcript.add_point(&proof.opening_proof_zeta);
+    transcript.add_point(&proof.opening_proof_omega);
+    let u = transcript.challenge();
+
+    Challenges { beta, gamma, alpha, zeta, v, u }
+}
+
+/// snarkjs `Keccak256Transcript`: hashes everything added since the last
+/// challenge, then starts over (shared with `fflonk`)
+pub(crate) struct Keccak256Transcript {
+    hasher: Keccak256,
+}
+
+impl Keccak256Transcript {
+    pub(crate) fn new() -> Self {
+        Self { hasher: Keccak256::new() }
+    }
+
+    pub(crate) fn add_point(&mut self, point: &[u8; 64]) {
+        self.hasher.update(point);
+    }
+
+    pub(crate) fn add_scalar(&mut self, scalar: &U256) {
+        self.hasher.update(scalar.to_be_bytes::<32>());
+    }
+
+    /// Squeeze and reset (snarkjs calls `reset()` before every round)
+    pub(crate) fn challenge(&mut self) -> U256 {
+        let hash = core::mem::take(&mut self.hasher).finalize();
+        fr_from_be_bytes_mod(&hash)
+    }
+}
@@ -0,0 +1,136 @@
+//! PLONK Structured Reference String (Powers of Tau)
+//!
+//! Layout of the bytes passed to `register_srs`:
+//! ```text
+//! max_degree (u64 little-endian)                 8 bytes
+//! [τ]₂ (EIP-197: x.c1 x.c0 y.c1 y.c0)          128 bytes
+//! [τ⁰]₁ [τ¹]₁ … [τᵏ]₁ (x ‖ y)              64 bytes each
+//! ```
+//! `max_degree` is the log2 of the largest domain the SRS supports. The
+//! verifier only needs `[τ]₂`: on chain a PLONK VK is registered without it,
+//! bound to an SRS hash, and `[τ]₂` is read from the SRS registry and appended
+//! to the VK (the `plonk::verify` layout) at verification time.
+
+use alloc::vec::Vec;
+use super::kzg::{Error, Result};
+use super::plonk::SRS_G2_SIZE;
+
+/// `max_degree` header
+pub const HEADER_SIZE: usize = 8;
+
+/// Size of one G1 power
+pub const G1_SIZE: usize = 64;
+
+/// Largest accepted `max_degree` (domains up to 2³⁰)
+pub const MAX_DEGREE_LIMIT: u8 = 30;
+
+/// Maximum SRS size: 64MB
+pub const MAX_SRS_SIZE: usize = 64 * 1024 * 1024;
+
+/// Borrowed view of an encoded SRS
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Srs<'a> {
+    /// log2 of the largest supported domain
+    pub max_degree: u8,
+    /// `[τ]₂`
+    pub g2_tau: &'a [u8],
+    /// `[τ⁰]₁ … [τᵏ]₁`
+    pub g1_powers: &'a [u8],
+}
+
+impl<'a> Srs<'a> {
+    /// Split an encoded SRS, checking sizes and the degree range
+    ///
+    /// Points are not validated here (see `groth16::curve` for the checks).
+    pub fn parse(bytes: &'a [u8]) -> Result<Self> {
+        let points = HEADER_SIZE + SRS_G2_SIZE;
+        if bytes.len() < points + G1_SIZE
+            || bytes.len() > MAX_SRS_SIZE
+            || (bytes.len() - points) % G1_SIZE != 0
+        {
+            return Err(Error::InvalidInputSize);
+        }
+
+        let max_degree = u64::from_le_bytes(bytes[..HEADER_SIZE].try_into().map_err(|_| Error::InvalidInputSize)?);
+        if max_degree < 1 || max_degree > MAX_DEGREE_LIMIT as u64 {
+            return Err(Error::InvalidInputSize);
+        }
+
+        Ok(Self {
+            max_degree: max_degree as u8,
+            g2_tau: &bytes[HEADER_SIZE..points],
+            g1_powers: &bytes[points..],
+        })
+    }
+
+    /// Whether a VK over a domain of `n` rows fits this SRS
+    pub fn supports_domain(&self, n: usize) -> bool {
+        fits_domain(self.max_degree, n)
+    }
+}
+
+/// Whether a domain of `n` rows fits an SRS of `max_degree` (`n ≤ 2^max_degree`)
+pub fn fits_domain(max_degree: u8, n: usize) -> bool {
+    n.is_power_of_two() && n.trailing_zeros() <= max_degree as u32
+}
+
+/// Encode an SRS in the `register_srs` layout
+pub fn encode(max_degree: u8, g2_tau: &[u8; SRS_G2_SIZE], g1_powers: &[[u8; G1_SIZE]]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(HEADER_SIZE + SRS_G2_SIZE + g1_powers.len() * G1_SIZE);
+    out.extend_from_slice(&(max_degree as u64).to_le_bytes());
+    out.extend_from_slice(g2_tau);
+    for point in g1_powers {
+        out.extend_from_slice(point);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generator() -> [u8; G1_SIZE] {
+        let mut point = [0u8; G1_SIZE];
+        point[31] = 1;
+        point[63] = 2;
+        point
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        let g2 = [7u8; SRS_G2_SIZE];
+        let bytes = encode(10, &g2, &[generator(), [3u8; G1_SIZE]]);
+        let srs = Srs::parse(&bytes).unwrap();
+
+        assert_eq!(srs.max_degree, 10);
+        assert_eq!(srs.g2_tau, &g2[..]);
+        assert_eq!(&srs.g1_powers[..G1_SIZE], &generator()[..]);
+        assert_eq!(srs.g1_powers.len(), 2 * G1_SIZE);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_layout() {
+        let g2 = [7u8; SRS_G2_SIZE];
+        let bytes = encode(10, &g2, &[generator()]);
+
+        // No G1 power, partial G1 power
+        assert_eq!(Srs::parse(&bytes[..HEADER_SIZE + SRS_G2_SIZE]), Err(Error::InvalidInputSize));
+        assert_eq!(Srs::parse(&bytes[..bytes.len() - 1]), Err(Error::InvalidInputSize));
+
+        // Degree out of range
+        assert!(Srs::parse(&encode(0, &g2, &[generator()])).is_err());
+        assert!(Srs::parse(&encode(MAX_DEGREE_LIMIT + 1, &g2, &[generator()])).is_err());
+        assert!(Srs::parse(&encode(MAX_DEGREE_LIMIT, &g2, &[generator()])).is_ok());
+    }
+
+    #[test]
+    fn test_domain_bound() {
+        let bytes = encode(3, &[7u8; SRS_G2_SIZE], &[generator()]);
+        let srs = Srs::parse(&bytes).unwrap();
+
+        assert!(srs.supports_domain(1));
+        assert!(srs.supports_domain(8));
+        assert!(!srs.supports_domain(16));
+        assert!(!srs.supports_domain(6));
+    }
+}
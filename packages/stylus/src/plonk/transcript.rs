@@ -1,13 +1,19 @@
 //! Fiat-Shamir Transcript
 //!
 //! Implements cryptographic transcript for non-interactive challenge generation
-//! in PLONK protocol. Uses Keccak256 (SHA-3) for hashing.
+//! in PLONK protocol. Generic over `crate::hash::Hasher`; Keccak256 by default,
+//! or whichever hash the descriptor's `hash_function_id` names.
 //!
 //! # Protocol
 //! 1. Prover commits to witness polynomials → Transcript absorbs commitments
 //! 2. Transcript squeezes challenge → Prover uses challenge to build next round
 //! 3. Repeat for all PLONK rounds (wire commitments, permutation, opening points)
 //!
+//! This is the only PLONK transcript: the Stylus verifier and the host verifier
+//! (`host.rs`, used by `verify_offchain` and `uzkv-cli`) both derive their
+//! challenges through `plonk::compute_challenges`, so off-chain results match
+//! on-chain acceptance.
+//!
 //! # Changes (Precompile Refactor)
 //! - Removed `arkworks` dependencies.
 //! - Uses `alloy_primitives::U256` (Fr) and `keccak256`.
@@ -15,21 +21,28 @@
 
 use alloc::vec::Vec;
 use stylus_sdk::alloy_primitives::U256;
-use sha3::{Digest, Keccak256};
+use crate::hash::{Hasher, Keccak256Hasher};
 use crate::utils::{fr_from_be_bytes_mod};
 
 /// Fiat-Shamir transcript for PLONK protocol
-pub struct Transcript {
-    /// Internal Keccak256 hasher state
-    hasher: Keccak256,
+pub struct Transcript<H: Hasher = Keccak256Hasher> {
+    /// Internal hasher state
+    hasher: H,
     /// Domain separation label
     domain_label: Vec<u8>,
 }
 
 impl Transcript {
-    /// Create a new transcript with domain separation label
+    /// Create a new Keccak256 transcript with domain separation label
     pub fn new(label: &[u8]) -> Self {
-        let mut hasher = Keccak256::new();
+        Self::with_hasher(label)
+    }
+}
+
+impl<H: Hasher> Transcript<H> {
+    /// Create a new transcript over `H` with domain separation label
+    pub fn with_hasher(label: &[u8]) -> Self {
+        let mut hasher = H::default();
         hasher.update(label);
         
         Self {
@@ -82,7 +95,7 @@ impl Transcript {
     }
 
     pub fn reset(&mut self) {
-        self.hasher = Keccak256::new();
+        self.hasher = H::default();
         self.hasher.update(&self.domain_label);
     }
 }
@@ -105,4 +118,17 @@ pub mod labels {
     pub const SELECTOR_EVAL: &[u8] = b"plonk_selector_eval";
     pub const OPENING_PROOF: &[u8] = b"plonk_opening_proof";
     pub const PUBLIC_INPUT: &[u8] = b"plonk_public_input";
+
+    // UltraPLONK (`ultra.rs`) adds the lookup rounds
+    pub const ULTRA_PLONK_PROTOCOL: &[u8] = b"ultra_plonk_bn254_v1";
+    pub const ETA_CHALLENGE: &[u8] = b"plonk_eta";
+    pub const SORTED_COMMITMENT: &[u8] = b"plonk_sorted_comm";
+    pub const LOOKUP_COMMITMENT: &[u8] = b"plonk_lookup_comm";
+    pub const ULTRA_EVAL: &[u8] = b"plonk_ultra_eval";
+
+    // Batch weights (`plonk::batch_weights`)
+    pub const PLONK_BATCH_PROTOCOL: &[u8] = b"plonk_batch_bn254_v1";
+    pub const BATCH_PROOF: &[u8] = b"plonk_batch_proof";
+    pub const BATCH_INPUT: &[u8] = b"plonk_batch_input";
+    pub const BATCH_WEIGHT: &[u8] = b"plonk_batch_weight";
 }
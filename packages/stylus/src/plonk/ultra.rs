@@ -0,0 +1,674 @@
+//! UltraPLONK Verifier (custom gates + plookup, BN254)
+//!
+//! PLONK with a configurable number of wires, custom gates declared in the VK,
+//! and a plookup argument (Gabizon, Williamson, §3) over a multi-column table.
+//! Range checks and similar constraints become one lookup row instead of a
+//! bit decomposition.
+//!
+//! # Wire Format
+//! Integers are big-endian, scalars 32 bytes, points 64 bytes uncompressed.
+//! With `w` wires, `s` selectors, a table of width `k` and `m` quotient pieces:
+//! ```text
+//! vk:    n (u64) || ℓ (u64) || w (u8) || s (u8) || k (u8) || lookup selector (u8)
+//!        u8 #gates || (u8 #terms || (coefficient || u8 selector || u8 #factors || u8 factor*)*)*
+//!        [q_1] … [q_s] [σ_1] … [σ_w] [t_1] … [t_k]                 (s + w + k) × 64 bytes
+//!        ω || k_1 … k_{w−1}                                          w × 32 bytes
+//!
+//! proof: [w_1] … [w_w] [h1] [h2] [z] [z_lookup] [T_1] … [T_m] [W_ζ] [W_ζω]
+//!        w_j(ζ) ×w || w_j(ζω) ×w || q_i(ζ) ×s || σ_j(ζ) ×w
+//!        t_j(ζ) ×k || t_j(ζω) ×k || h1(ζ) h1(ζω) h2(ζ) h2(ζω)
+//!        z(ζ) z(ζω) z_lookup(ζ) z_lookup(ζω)
+//! ```
+//! A gate factor is a wire index, with the top bit set to read the next row.
+//! Wire j sits on the coset `k_j·H` (`k_0 = 1`). `m = max(w, max factors, 3)`.
+//! As for PLONK, `verify` expects `[τ]₂` appended to the VK.
+//!
+//! # Constraints
+//! Gate g is `Σ coefficient·q_selector·Π factors = 0` on every row; public
+//! input i enters gate 0 at row `ωⁱ` as in `plonk.rs`. The lookup compresses
+//! the first k wires and the table columns with η:
+//! ```text
+//! f = q_K·(Σ ηʲ·w_j − t) + t        t = Σ ηʲ·t_j
+//! ```
+//! so rows without the lookup selector `q_K` look up their own table row.
+//! Every row but the last is looked up. `h1 ‖ h2` is `f ∪ t` sorted by `t`
+//! (sharing one element), and `z_lookup` the plookup grand product:
+//! ```text
+//! (X − ω^{n−1})·(z_lookup(X)·(1 + β)(γ + f)(γ(1 + β) + t + β·t(ωX))
+//!     − z_lookup(ωX)·(γ(1 + β) + h1 + β·h1(ωX))(γ(1 + β) + h2 + β·h2(ωX)))
+//! L₀·(z_lookup − 1)    L_{n−1}·(h1 − h2(ωX))    L_{n−1}·(z_lookup − 1)
+//! ```
+//! next to the permutation `z(ωX)·Π(w_j + βσ_j + γ) − z·Π(w_j + βk_jX + γ)`
+//! and `L₀·(z − 1)`.
+//!
+//! # Verification
+//! Every committed polynomial is opened, so the constraints are checked on
+//! the evaluations: with `Cᵢ` in the order above,
+//! `T(ζ) = Σ αⁱ·Cᵢ(ζ) / Z_H(ζ)` must be the opening of
+//! `[T] = Σ ζⁿʲ·[T_j]`. The openings at ζ fold with powers of v, the ones at
+//! ζω with the same powers and u, and both are checked with one pairing as in
+//! `plonk.rs`.
+
+use alloc::vec::Vec;
+use stylus_sdk::alloy_primitives::U256;
+
+use crate::backend::Bn254Backend;
+use crate::hash::{Blake3Hasher, Hasher, Keccak256Hasher, PoseidonHasher, Sha256Hasher};
+use crate::types::HashFunctionId;
+use crate::utils::{fr_add, fr_inv, fr_mul, fr_pow, fr_sub, Fr, BN254_SCALAR_MODULUS};
+use super::kzg::{Error, KzgAccumulator, Result};
+use super::plonk::{lagrange_bases, parse_public_inputs, SRS_G2_SIZE};
+use super::transcript::{labels, Transcript};
+
+/// Most wires a key may declare (also bounds the factors of a gate term)
+pub const MAX_WIRES: usize = 8;
+
+/// Most selectors a key may declare
+pub const MAX_SELECTORS: usize = 32;
+
+/// Most gates, and terms per gate
+pub const MAX_GATES: usize = 16;
+
+/// Factor flag: read the wire at the next row
+const NEXT_ROW: u8 = 0x80;
+
+/// Wire read by a gate term, at the current row or the next one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WireQuery {
+    pub wire: usize,
+    pub next: bool,
+}
+
+/// `coefficient · q_selector · Π factors`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GateTerm {
+    pub coefficient: U256,
+    pub selector: usize,
+    pub factors: Vec<WireQuery>,
+}
+
+/// A custom gate: its terms sum to zero on every row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomGate {
+    pub terms: Vec<GateTerm>,
+}
+
+/// UltraPLONK verification key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UltraVerificationKey {
+    pub n: usize,
+    pub num_public_inputs: usize,
+    pub num_wires: usize,
+    /// Index of `q_K` among the selectors
+    pub lookup_selector: usize,
+    pub gates: Vec<CustomGate>,
+    pub selector_commitments: Vec<[u8; 64]>,
+    pub permutation_commitments: Vec<[u8; 64]>, // one σ per wire
+    pub table_commitments: Vec<[u8; 64]>,       // one per table column
+    pub omega: U256,
+    /// `k_1 … k_{w−1}`; wire 0 uses 1
+    pub coset_shifts: Vec<U256>,
+}
+
+impl UltraVerificationKey {
+    pub fn num_selectors(&self) -> usize {
+        self.selector_commitments.len()
+    }
+
+    pub fn table_width(&self) -> usize {
+        self.table_commitments.len()
+    }
+
+    /// Number of quotient pieces of size n
+    ///
+    /// The permutation has degree `w + 1`, a gate term `factors + 1` and the
+    /// lookup product 4 (plus `X − ω^{n−1}`), less one for `Z_H`.
+    pub fn quotient_pieces(&self) -> usize {
+        let max_factors = self
+            .gates
+            .iter()
+            .flat_map(|gate| gate.terms.iter().map(|term| term.factors.len()))
+            .max()
+            .unwrap_or(0);
+        self.num_wires.max(max_factors).max(3)
+    }
+
+    /// Proof size for this key
+    pub fn proof_size(&self) -> usize {
+        let w = self.num_wires;
+        let points = w + 4 + self.quotient_pieces() + 2;
+        let scalars = 3 * w + self.num_selectors() + 2 * self.table_width() + 8;
+        points * 64 + scalars * 32
+    }
+}
+
+/// UltraPLONK proof
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UltraProof {
+    pub wire_commitments: Vec<[u8; 64]>,
+    pub sorted_commitments: [[u8; 64]; 2], // [h1], [h2]
+    pub permutation_commitment: [u8; 64],  // [z]
+    pub lookup_commitment: [u8; 64],       // [z_lookup]
+    pub quotient_commitments: Vec<[u8; 64]>,
+    pub opening_proof_zeta: [u8; 64],  // [W_ζ]
+    pub opening_proof_omega: [u8; 64], // [W_ζω]
+
+    pub wire_evals: Vec<U256>,
+    pub wire_evals_omega: Vec<U256>,
+    pub selector_evals: Vec<U256>,
+    pub sigma_evals: Vec<U256>,
+    pub table_evals: Vec<U256>,
+    pub table_evals_omega: Vec<U256>,
+    pub sorted_evals: [U256; 2],       // h1(ζ), h2(ζ)
+    pub sorted_evals_omega: [U256; 2], // h1(ζω), h2(ζω)
+    pub permutation_eval: U256,        // z(ζ)
+    pub permutation_eval_omega: U256,  // z(ζω)
+    pub lookup_eval: U256,             // z_lookup(ζ)
+    pub lookup_eval_omega: U256,       // z_lookup(ζω)
+}
+
+/// Fiat-Shamir challenges of one proof
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Challenges {
+    pub eta: U256,
+    pub beta: U256,
+    pub gamma: U256,
+    pub alpha: U256,
+    pub zeta: U256,
+    pub v: U256,
+    pub u: U256,
+}
+
+/// Wrapper to deserialize and verify (Keccak256 transcript)
+pub fn verify<B: Bn254Backend>(
+    curve: &B,
+    proof_bytes: &[u8],
+    public_inputs_bytes: &[u8],
+    vk_bytes: &[u8],
+) -> Result<bool> {
+    verify_with_hash(curve, HashFunctionId::Keccak256, proof_bytes, public_inputs_bytes, vk_bytes)
+}
+
+/// Deserialize and verify a proof whose transcript uses `hash`
+pub fn verify_with_hash<B: Bn254Backend>(
+    curve: &B,
+    hash: HashFunctionId,
+    proof_bytes: &[u8],
+    public_inputs_bytes: &[u8],
+    vk_bytes: &[u8],
+) -> Result<bool> {
+    // The SRS point [τ]₂ is appended to the VK
+    let split = vk_bytes.len().checked_sub(SRS_G2_SIZE).ok_or(Error::InvalidInputSize)?;
+    let vk = deserialize_ultra_vk(&vk_bytes[..split]).ok_or(Error::InvalidInputSize)?;
+    let srs_g2 = &vk_bytes[split..];
+    let proof = deserialize_ultra_proof(proof_bytes, &vk).ok_or(Error::InvalidInputSize)?;
+    let public_inputs = parse_public_inputs(public_inputs_bytes)?;
+
+    let challenges = compute_challenges_for(hash, &proof, &vk, &public_inputs)?;
+    verify_with_challenges(curve, &proof, &vk, &public_inputs, srs_g2, &challenges)
+}
+
+/// Cursor over serialized keys and proofs
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (head, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(head)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.take(1)?[0])
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        Some(u64::from_be_bytes(self.take(8)?.try_into().ok()?))
+    }
+
+    /// A scalar below r
+    fn fr(&mut self) -> Option<U256> {
+        let value = U256::from_be_slice(self.take(32)?);
+        (value < BN254_SCALAR_MODULUS).then_some(value)
+    }
+
+    fn g1(&mut self) -> Option<[u8; 64]> {
+        self.take(64)?.try_into().ok()
+    }
+
+    fn frs(&mut self, count: usize) -> Option<Vec<U256>> {
+        (0..count).map(|_| self.fr()).collect()
+    }
+
+    fn g1s(&mut self, count: usize) -> Option<Vec<[u8; 64]>> {
+        (0..count).map(|_| self.g1()).collect()
+    }
+}
+
+/// Parse a VK (without the SRS point)
+///
+/// Rejects a domain that is not a power of two, an `ω` that is not a primitive
+/// n-th root of unity, more public inputs than rows, fewer than 2 or more than
+/// `MAX_WIRES` wires, a table wider than the wires, gates without terms, and
+/// selectors or wires out of range.
+pub fn deserialize_ultra_vk(bytes: &[u8]) -> Option<UltraVerificationKey> {
+    let mut r = Reader(bytes);
+    let n = r.u64()?;
+    let num_inputs = r.u64()?;
+    let num_wires = r.u8()? as usize;
+    let num_selectors = r.u8()? as usize;
+    let table_width = r.u8()? as usize;
+    let lookup_selector = r.u8()? as usize;
+    if !(2..=MAX_WIRES).contains(&num_wires)
+        || !(1..=MAX_SELECTORS).contains(&num_selectors)
+        || !(1..=num_wires).contains(&table_width)
+        || lookup_selector >= num_selectors
+    {
+        return None;
+    }
+
+    let num_gates = r.u8()? as usize;
+    if !(1..=MAX_GATES).contains(&num_gates) {
+        return None;
+    }
+    let mut gates = Vec::with_capacity(num_gates);
+    for _ in 0..num_gates {
+        let num_terms = r.u8()? as usize;
+        if !(1..=MAX_GATES).contains(&num_terms) {
+            return None;
+        }
+        let mut terms = Vec::with_capacity(num_terms);
+        for _ in 0..num_terms {
+            let coefficient = r.fr()?;
+            let selector = r.u8()? as usize;
+            let num_factors = r.u8()? as usize;
+            if selector >= num_selectors || num_factors > MAX_WIRES {
+                return None;
+            }
+            let factors = (0..num_factors)
+                .map(|_| {
+                    let byte = r.u8()?;
+                    let query = WireQuery { wire: (byte & !NEXT_ROW) as usize, next: byte & NEXT_ROW != 0 };
+                    (query.wire < num_wires).then_some(query)
+                })
+                .collect::<Option<Vec<_>>>()?;
+            terms.push(GateTerm { coefficient, selector, factors });
+        }
+        gates.push(CustomGate { terms });
+    }
+
+    let selector_commitments = r.g1s(num_selectors)?;
+    let permutation_commitments = r.g1s(num_wires)?;
+    let table_commitments = r.g1s(table_width)?;
+    let omega = r.fr()?;
+    let coset_shifts = r.frs(num_wires - 1)?;
+    if !r.0.is_empty() {
+        return None;
+    }
+
+    let one = U256::from(1);
+    let valid_domain = n.is_power_of_two()
+        && n >= 2
+        && num_inputs <= n
+        && fr_pow(omega, U256::from(n)) == one
+        && fr_pow(omega, U256::from(n / 2)) != one;
+    valid_domain.then_some(UltraVerificationKey {
+        n: usize::try_from(n).ok()?,
+        num_public_inputs: usize::try_from(num_inputs).ok()?,
+        num_wires,
+        lookup_selector,
+        gates,
+        selector_commitments,
+        permutation_commitments,
+        table_commitments,
+        omega,
+        coset_shifts,
+    })
+}
+
+pub fn serialize_ultra_vk(vk: &UltraVerificationKey) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(&(vk.n as u64).to_be_bytes());
+    out.extend_from_slice(&(vk.num_public_inputs as u64).to_be_bytes());
+    out.extend_from_slice(&[
+        vk.num_wires as u8,
+        vk.num_selectors() as u8,
+        vk.table_width() as u8,
+        vk.lookup_selector as u8,
+        vk.gates.len() as u8,
+    ]);
+    for gate in &vk.gates {
+        out.push(gate.terms.len() as u8);
+        for term in &gate.terms {
+            out.extend_from_slice(&term.coefficient.to_be_bytes::<32>());
+            out.extend_from_slice(&[term.selector as u8, term.factors.len() as u8]);
+            for factor in &term.factors {
+                out.push(factor.wire as u8 | if factor.next { NEXT_ROW } else { 0 });
+            }
+        }
+    }
+    for point in vk.selector_commitments.iter().chain(&vk.permutation_commitments).chain(&vk.table_commitments) {
+        out.extend_from_slice(point);
+    }
+    for scalar in core::iter::once(&vk.omega).chain(&vk.coset_shifts) {
+        out.extend_from_slice(&scalar.to_be_bytes::<32>());
+    }
+    out
+}
+
+/// Parse a proof for `vk`, whose counts fix the layout
+pub fn deserialize_ultra_proof(bytes: &[u8], vk: &UltraVerificationKey) -> Option<UltraProof> {
+    if bytes.len() != vk.proof_size() {
+        return None;
+    }
+    let (w, k) = (vk.num_wires, vk.table_width());
+    let mut r = Reader(bytes);
+    Some(UltraProof {
+        wire_commitments: r.g1s(w)?,
+        sorted_commitments: [r.g1()?, r.g1()?],
+        permutation_commitment: r.g1()?,
+        lookup_commitment: r.g1()?,
+        quotient_commitments: r.g1s(vk.quotient_pieces())?,
+        opening_proof_zeta: r.g1()?,
+        opening_proof_omega: r.g1()?,
+        wire_evals: r.frs(w)?,
+        wire_evals_omega: r.frs(w)?,
+        selector_evals: r.frs(vk.num_selectors())?,
+        sigma_evals: r.frs(w)?,
+        table_evals: r.frs(k)?,
+        table_evals_omega: r.frs(k)?,
+        sorted_evals: [r.fr()?, r.fr()?],
+        sorted_evals_omega: [r.fr()?, r.fr()?],
+        permutation_eval: r.fr()?,
+        permutation_eval_omega: r.fr()?,
+        lookup_eval: r.fr()?,
+        lookup_eval_omega: r.fr()?,
+    })
+}
+
+/// Proof evaluations, in wire order (the transcript absorbs them in this order)
+fn proof_evals(proof: &UltraProof) -> impl Iterator<Item = &U256> {
+    proof
+        .wire_evals
+        .iter()
+        .chain(&proof.wire_evals_omega)
+        .chain(&proof.selector_evals)
+        .chain(&proof.sigma_evals)
+        .chain(&proof.table_evals)
+        .chain(&proof.table_evals_omega)
+        .chain(&proof.sorted_evals)
+        .chain(&proof.sorted_evals_omega)
+        .chain([
+            &proof.permutation_eval,
+            &proof.permutation_eval_omega,
+            &proof.lookup_eval,
+            &proof.lookup_eval_omega,
+        ])
+}
+
+pub fn serialize_ultra_proof(proof: &UltraProof) -> Vec<u8> {
+    let mut out = Vec::new();
+    for point in proof
+        .wire_commitments
+        .iter()
+        .chain(&proof.sorted_commitments)
+        .chain([&proof.permutation_commitment, &proof.lookup_commitment])
+        .chain(&proof.quotient_commitments)
+        .chain([&proof.opening_proof_zeta, &proof.opening_proof_omega])
+    {
+        out.extend_from_slice(point);
+    }
+    for eval in proof_evals(proof) {
+        out.extend_from_slice(&eval.to_be_bytes::<32>());
+    }
+    out
+}
+
+/// Replay the transcript (Keccak256)
+///
+/// Each challenge only depends on what precedes it, so a prover can call this
+/// on a partially filled proof to get the challenges of the rounds done so far.
+pub fn compute_challenges(
+    proof: &UltraProof,
+    vk: &UltraVerificationKey,
+    public_inputs: &[U256],
+) -> Challenges {
+    compute_challenges_with::<Keccak256Hasher>(proof, vk, public_inputs)
+}
+
+/// Replay the transcript with the hash named by a descriptor's `hash_function_id`
+pub fn compute_challenges_for(
+    hash: HashFunctionId,
+    proof: &UltraProof,
+    vk: &UltraVerificationKey,
+    public_inputs: &[U256],
+) -> Result<Challenges> {
+    let challenges = match hash {
+        HashFunctionId::Keccak256 => compute_challenges_with::<Keccak256Hasher>(proof, vk, public_inputs),
+        HashFunctionId::SHA256 => compute_challenges_with::<Sha256Hasher>(proof, vk, public_inputs),
+        HashFunctionId::Blake3 => compute_challenges_with::<Blake3Hasher>(proof, vk, public_inputs),
+        HashFunctionId::Poseidon => compute_challenges_with::<PoseidonHasher>(proof, vk, public_inputs),
+        HashFunctionId::RescuePrime | HashFunctionId::Blake2b => return Err(Error::UnsupportedHash),
+    };
+    Ok(challenges)
+}
+
+/// Replay the transcript over `H`
+///
+/// The whole VK is absorbed, so the gates and the table are bound as well as
+/// the commitments.
+pub fn compute_challenges_with<H: Hasher>(
+    proof: &UltraProof,
+    vk: &UltraVerificationKey,
+    public_inputs: &[U256],
+) -> Challenges {
+    let mut transcript = Transcript::<H>::with_hasher(labels::ULTRA_PLONK_PROTOCOL);
+    transcript.absorb_bytes(labels::VK_DOMAIN, &serialize_ultra_vk(vk));
+    for input in public_inputs {
+        transcript.absorb_field(labels::PUBLIC_INPUT, input);
+    }
+
+    // Round 1: wires -> η
+    for comm in &proof.wire_commitments {
+        transcript.absorb_point_bytes(labels::WIRE_COMMITMENT, comm);
+    }
+    let eta = transcript.squeeze_challenge(labels::ETA_CHALLENGE);
+
+    // Round 2: sorted list -> β, γ
+    for comm in &proof.sorted_commitments {
+        transcript.absorb_point_bytes(labels::SORTED_COMMITMENT, comm);
+    }
+    let beta = transcript.squeeze_challenge(labels::BETA_CHALLENGE);
+    let gamma = transcript.squeeze_challenge(labels::GAMMA_CHALLENGE);
+
+    // Round 3: grand products -> α
+    transcript.absorb_point_bytes(labels::PERMUTATION_COMMITMENT, &proof.permutation_commitment);
+    transcript.absorb_point_bytes(labels::LOOKUP_COMMITMENT, &proof.lookup_commitment);
+    let alpha = transcript.squeeze_challenge(labels::ALPHA_CHALLENGE);
+
+    // Round 4: quotient -> ζ
+    for comm in &proof.quotient_commitments {
+        transcript.absorb_point_bytes(labels::QUOTIENT_COMMITMENT, comm);
+    }
+    let zeta = transcript.squeeze_challenge(labels::ZETA_CHALLENGE);
+
+    // Round 5: evaluations -> v
+    for eval in proof_evals(proof) {
+        transcript.absorb_field(labels::ULTRA_EVAL, eval);
+    }
+    let v = transcript.squeeze_challenge(labels::V_CHALLENGE);
+
+    // Round 6: opening proofs -> u
+    transcript.absorb_point_bytes(labels::OPENING_PROOF, &proof.opening_proof_zeta);
+    transcript.absorb_point_bytes(labels::OPENING_PROOF, &proof.opening_proof_omega);
+    let u = transcript.squeeze_challenge(labels::U_CHALLENGE);
+
+    Challenges { eta, beta, gamma, alpha, zeta, v, u }
+}
+
+/// Whether the proof's vectors match the key's counts
+fn matches_key(proof: &UltraProof, vk: &UltraVerificationKey) -> bool {
+    let (w, k) = (vk.num_wires, vk.table_width());
+    proof.wire_commitments.len() == w
+        && proof.quotient_commitments.len() == vk.quotient_pieces()
+        && proof.wire_evals.len() == w
+        && proof.wire_evals_omega.len() == w
+        && proof.selector_evals.len() == vk.num_selectors()
+        && proof.sigma_evals.len() == w
+        && proof.table_evals.len() == k
+        && proof.table_evals_omega.len() == k
+}
+
+/// `Σ ηʲ·values[j]`
+fn compress(values: &[U256], eta: U256) -> U256 {
+    values.iter().rev().fold(U256::ZERO, |acc, value| fr_add(fr_mul(acc, eta), *value))
+}
+
+/// Run the verifier checks with the given challenges
+pub fn verify_with_challenges<B: Bn254Backend>(
+    curve: &B,
+    proof: &UltraProof,
+    vk: &UltraVerificationKey,
+    public_inputs: &[U256],
+    srs_g2: &[u8],
+    challenges: &Challenges,
+) -> Result<bool> {
+    if public_inputs.len() != vk.num_public_inputs || srs_g2.len() != SRS_G2_SIZE || !matches_key(proof, vk) {
+        return Err(Error::InvalidInputSize);
+    }
+
+    let Challenges { eta, beta, gamma, alpha, zeta, v, u } = *challenges;
+    let one = U256::from(1);
+
+    // Z_H(ζ) = ζⁿ − 1; ζ inside the domain leaves no quotient to check
+    let zeta_n = fr_pow(zeta, U256::from(vk.n));
+    let zh_zeta = fr_sub(zeta_n, one);
+    let Some(zh_inv) = fr_inv(zh_zeta) else {
+        return Ok(false);
+    };
+
+    // L₀(ζ), PI(ζ) = −Σ xᵢ·Lᵢ(ζ) and L_{n−1}(ζ), with ω^{n−1} = ω⁻¹, in one batch
+    let omega = Fr::from_u256_reduced(vk.omega);
+    let mut points = Vec::with_capacity(public_inputs.len() + 2);
+    let mut omega_i = Fr::ONE;
+    for _ in 0..public_inputs.len().max(1) {
+        points.push(omega_i);
+        omega_i = omega_i.mul(&omega);
+    }
+    let omega_last = omega.pow(U256::from(vk.n - 1));
+    points.push(omega_last);
+    let mut lagrange: Vec<U256> = lagrange_bases(Fr::from_u256_reduced(zeta), vk.n, &points)
+        .into_iter()
+        .map(Fr::to_u256)
+        .collect();
+    let l_last_zeta = lagrange.pop().unwrap_or_default();
+    let omega_last = omega_last.to_u256();
+    let l0_zeta = lagrange[0];
+    let pi_zeta = public_inputs
+        .iter()
+        .zip(&lagrange)
+        .fold(U256::ZERO, |acc, (x, l)| fr_sub(acc, fr_mul(*x, *l)));
+
+    // Custom gates, public inputs in gate 0
+    let wire = |query: &WireQuery| {
+        if query.next { proof.wire_evals_omega[query.wire] } else { proof.wire_evals[query.wire] }
+    };
+    let mut constraints: Vec<U256> = vk
+        .gates
+        .iter()
+        .map(|gate| {
+            gate.terms.iter().fold(U256::ZERO, |acc, term| {
+                let product = term.factors.iter().fold(proof.selector_evals[term.selector], |p, f| fr_mul(p, wire(f)));
+                fr_add(acc, fr_mul(term.coefficient, product))
+            })
+        })
+        .collect();
+    constraints[0] = fr_add(constraints[0], pi_zeta);
+
+    // Permutation: z(ζω)·Π(w̄_j + βσ̄_j + γ) − z(ζ)·Π(w̄_j + βk_jζ + γ)
+    let beta_zeta = fr_mul(beta, zeta);
+    let (mut sigma_product, mut id_product) = (proof.permutation_eval_omega, proof.permutation_eval);
+    for (j, (w_j, sigma_j)) in proof.wire_evals.iter().zip(&proof.sigma_evals).enumerate() {
+        let shift = if j == 0 { one } else { vk.coset_shifts[j - 1] };
+        sigma_product = fr_mul(sigma_product, fr_add(fr_add(*w_j, fr_mul(beta, *sigma_j)), gamma));
+        id_product = fr_mul(id_product, fr_add(fr_add(*w_j, fr_mul(beta_zeta, shift)), gamma));
+    }
+    constraints.push(fr_sub(sigma_product, id_product));
+    constraints.push(fr_mul(l0_zeta, fr_sub(proof.permutation_eval, one)));
+
+    // Plookup
+    let table = compress(&proof.table_evals, eta);
+    let table_omega = compress(&proof.table_evals_omega, eta);
+    let q_lookup = proof.selector_evals[vk.lookup_selector];
+    let f = fr_add(fr_mul(q_lookup, fr_sub(compress(&proof.wire_evals[..vk.table_width()], eta), table)), table);
+    let one_plus_beta = fr_add(one, beta);
+    let gamma_beta = fr_mul(gamma, one_plus_beta);
+    let pair = |x: U256, x_omega: U256| fr_add(fr_add(gamma_beta, x), fr_mul(beta, x_omega));
+    let [h1, h2] = proof.sorted_evals;
+    let [h1_omega, h2_omega] = proof.sorted_evals_omega;
+    let lookup_left = fr_mul(
+        fr_mul(proof.lookup_eval, one_plus_beta),
+        fr_mul(fr_add(gamma, f), pair(table, table_omega)),
+    );
+    let lookup_right = fr_mul(proof.lookup_eval_omega, fr_mul(pair(h1, h1_omega), pair(h2, h2_omega)));
+    constraints.push(fr_mul(fr_sub(zeta, omega_last), fr_sub(lookup_left, lookup_right)));
+    constraints.push(fr_mul(l0_zeta, fr_sub(proof.lookup_eval, one)));
+    constraints.push(fr_mul(l_last_zeta, fr_sub(h1, h2_omega)));
+    constraints.push(fr_mul(l_last_zeta, fr_sub(proof.lookup_eval, one)));
+
+    // T(ζ) = Σ αⁱ·Cᵢ(ζ) / Z_H(ζ)
+    let quotient_eval = fr_mul(constraints.iter().rev().fold(U256::ZERO, |acc, c| fr_add(fr_mul(acc, alpha), *c)), zh_inv);
+
+    // Opened at ζ and ζω: wires, table columns, h1, h2, z, z_lookup
+    let shifted: Vec<(&[u8; 64], U256, U256)> = proof
+        .wire_commitments
+        .iter()
+        .zip(proof.wire_evals.iter().zip(&proof.wire_evals_omega))
+        .chain(vk.table_commitments.iter().zip(proof.table_evals.iter().zip(&proof.table_evals_omega)))
+        .chain(proof.sorted_commitments.iter().zip(proof.sorted_evals.iter().zip(&proof.sorted_evals_omega)))
+        .chain([
+            (&proof.permutation_commitment, (&proof.permutation_eval, &proof.permutation_eval_omega)),
+            (&proof.lookup_commitment, (&proof.lookup_eval, &proof.lookup_eval_omega)),
+        ])
+        .map(|(comm, (eval, eval_omega))| (comm, *eval, *eval_omega))
+        .collect();
+    // Opened at ζ only: selectors, σ
+    let unshifted = vk
+        .selector_commitments
+        .iter()
+        .zip(&proof.selector_evals)
+        .chain(vk.permutation_commitments.iter().zip(&proof.sigma_evals));
+
+    let zeta_omega = fr_mul(zeta, vk.omega);
+
+    // [W_ζ] + u[W_ζω] against [τ]₂;
+    // ζ[W_ζ] + uζω[W_ζω] + Σ vⁱ(1 + u)[shiftedᵢ] + Σ vⁱ[unshiftedᵢ] + v^last·[T] − E·[1] against [1]₂
+    let mut acc = KzgAccumulator::new();
+    acc.add_proof(&proof.opening_proof_zeta, one)?;
+    acc.add_proof(&proof.opening_proof_omega, u)?;
+    acc.add_term(&proof.opening_proof_zeta, zeta)?;
+    acc.add_term(&proof.opening_proof_omega, fr_mul(u, zeta_omega))?;
+    let one_plus_u = fr_add(one, u);
+    let (mut v_i, mut e_scalar) = (one, U256::ZERO);
+    for (comm, eval, eval_omega) in &shifted {
+        acc.add_term(&comm[..], fr_mul(v_i, one_plus_u))?;
+        e_scalar = fr_add(e_scalar, fr_mul(v_i, fr_add(*eval, fr_mul(u, *eval_omega))));
+        v_i = fr_mul(v_i, v);
+    }
+    for (comm, eval) in unshifted {
+        acc.add_term(&comm[..], v_i)?;
+        e_scalar = fr_add(e_scalar, fr_mul(v_i, *eval));
+        v_i = fr_mul(v_i, v);
+    }
+    // [T] = Σ ζⁿʲ·[T_j]
+    let mut piece_scalar = v_i;
+    for comm in &proof.quotient_commitments {
+        acc.add_term(&comm[..], piece_scalar)?;
+        piece_scalar = fr_mul(piece_scalar, zeta_n);
+    }
+    acc.add_eval(fr_add(e_scalar, fr_mul(v_i, quotient_eval)));
+    acc.check(curve, srs_g2)
+}
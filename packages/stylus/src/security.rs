@@ -8,6 +8,7 @@
 //! |--------|---------------|------------|
 //! | Proof Type Confusion | Submit Groth16 proof as PLONK | VK binding check |
 //! | Curve Mismatch | Use BLS12-381 proof on BN254 verifier | Curve ID validation |
+//! | Transcript Hash Mismatch | Verify a Poseidon-transcript proof as Keccak256 | Hash ID validation |
 //! | VK Substitution | Swap VK to accept invalid proofs | VK commitment binding |
 //! | Recursion Bomb | Unbounded recursive verification | Depth limit check |
 //! | Input Overflow | Excessive public inputs | Size limit validation |
@@ -18,10 +19,11 @@
 //! 2. **Type Safety**: Proof system ID in descriptor MUST match registered VK type
 //! 3. **Curve Compatibility**: Proof curve MUST match verifier curve
 //! 4. **Bounded Inputs**: Public input count MUST not exceed verifier limits
+//! 5. **Hash Binding**: Transcript hash in descriptor MUST match the registered VK's hash
 
 extern crate alloc;
 
-use crate::types::{ProofType, UniversalProofDescriptor, CurveId};
+use crate::types::{ProofType, UniversalProofDescriptor, CurveId, HashFunctionId};
 use crate::verifier_traits::SecurityModel;
 
 /// Security validation errors
@@ -42,6 +44,12 @@ pub enum SecurityError {
         verifier_curve: CurveId,
     },
 
+    /// Transcript hash in descriptor doesn't match the registered VK's hash
+    HashFunctionMismatch {
+        descriptor_hash: HashFunctionId,
+        verifier_hash: HashFunctionId,
+    },
+
     /// Recursion depth exceeds maximum allowed
     ExcessiveRecursionDepth {
         depth: u8,
@@ -89,6 +97,9 @@ impl core::fmt::Display for SecurityError {
             Self::CurveMismatch { descriptor_curve, verifier_curve } => {
                 write!(f, "Curve mismatch: descriptor {:?}, verifier {:?}", descriptor_curve, verifier_curve)
             }
+            Self::HashFunctionMismatch { descriptor_hash, verifier_hash } => {
+                write!(f, "Hash function mismatch: descriptor {:?}, verifier {:?}", descriptor_hash, verifier_hash)
+            }
             Self::ExcessiveRecursionDepth { depth, max_allowed } => {
                 write!(f, "Recursion depth {} exceeds max {}", depth, max_allowed)
             }
@@ -126,6 +137,9 @@ pub struct RegisteredVK {
     /// Curve the VK is defined over
     pub curve_id: CurveId,
 
+    /// Fiat-Shamir / commitment hash of the circuit (Keccak256 by default)
+    pub hash_function_id: HashFunctionId,
+
     /// Maximum public inputs this VK supports
     pub max_public_inputs: u16,
 
@@ -147,10 +161,17 @@ impl RegisteredVK {
             vk_hash,
             circuit_id,
             curve_id,
+            hash_function_id: HashFunctionId::Keccak256,
             max_public_inputs,
             active: true,
         }
     }
+
+    /// Set the hash the circuit's transcript uses
+    pub fn with_hash_function(mut self, hash_function_id: HashFunctionId) -> Self {
+        self.hash_function_id = hash_function_id;
+        self
+    }
 }
 
 /// Dispatch boundary security validator
@@ -247,6 +268,30 @@ impl DispatchValidator {
         Ok(())
     }
 
+    /// Validate transcript hash
+    ///
+    /// Ensures the proof's hash matches the one its VK was registered with.
+    /// Groth16 (including gnark) has no Fiat-Shamir transcript, so its
+    /// descriptor hash is not checked.
+    pub fn validate_hash_function(
+        &self,
+        descriptor: &UniversalProofDescriptor,
+        registered_vk: &RegisteredVK,
+    ) -> Result<(), SecurityError> {
+        if matches!(registered_vk.proof_type, ProofType::Groth16 | ProofType::Groth16Gnark) {
+            return Ok(());
+        }
+
+        if descriptor.hash_function_id != registered_vk.hash_function_id {
+            return Err(SecurityError::HashFunctionMismatch {
+                descriptor_hash: descriptor.hash_function_id,
+                verifier_hash: registered_vk.hash_function_id,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Validate recursion depth
     ///
     /// Prevents recursion bomb attacks by limiting proof nesting.
@@ -293,6 +338,11 @@ impl DispatchValidator {
             0 => 512,       // Groth16: ~256 bytes typical, 512 max
             1 => 4_096,     // PLONK: ~800 bytes typical, 4KB max
             2 => 1_000_000, // STARK: ~50KB typical, 1MB max
+            3 => 4_096,     // gnark Groth16: ~390 bytes with one commitment, 4KB max
+            4 => 4_096,     // snarkjs PLONK: 768 bytes, 4KB max
+            5 => 4_096,     // fflonk: 768 bytes, 4KB max
+            6 => 65_536,    // halo2: grows with the circuit, 64KB max
+            7 => 4_096,     // UltraPLONK: under 4KB at the VK limits
             _ => return Err(SecurityError::UnsupportedProofSystem),
         };
 
@@ -340,6 +390,7 @@ impl DispatchValidator {
     ) -> Result<(), SecurityError> {
         self.validate_proof_type_binding(descriptor, registered_vk)?;
         self.validate_curve_match(descriptor, registered_vk)?;
+        self.validate_hash_function(descriptor, registered_vk)?;
         self.validate_recursion_depth(descriptor)?;
         self.validate_input_count(descriptor, registered_vk)?;
         self.validate_proof_size(descriptor)?;
@@ -388,7 +439,6 @@ pub fn hash_descriptor(descriptor: &UniversalProofDescriptor) -> [u8; 32] {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::HashFunctionId;
 
     fn sample_descriptor() -> UniversalProofDescriptor {
         UniversalProofDescriptor::groth16(4, [1u8; 32], [2u8; 32])
@@ -450,6 +500,36 @@ mod tests {
         assert!(matches!(result, Err(SecurityError::CurveMismatch { .. })));
     }
 
+    #[test]
+    fn test_hash_function_mismatch() {
+        let validator = DispatchValidator::new();
+        let descriptor = UniversalProofDescriptor::plonk(1, [1u8; 32], [2u8; 32]);
+
+        let mut vk = sample_vk();
+        vk.proof_type = ProofType::PLONK;
+        assert!(validator.validate_hash_function(&descriptor, &vk).is_ok());
+
+        let vk = vk.with_hash_function(HashFunctionId::Poseidon); // Mismatch!
+        let result = validator.validate_hash_function(&descriptor, &vk);
+        assert_eq!(
+            result,
+            Err(SecurityError::HashFunctionMismatch {
+                descriptor_hash: HashFunctionId::Keccak256,
+                verifier_hash: HashFunctionId::Poseidon,
+            })
+        );
+    }
+
+    #[test]
+    fn test_groth16_ignores_hash_function() {
+        let validator = DispatchValidator::new();
+
+        // The Groth16 descriptor names Poseidon; the VK defaults to Keccak256
+        let descriptor = sample_descriptor();
+        assert_eq!(descriptor.hash_function_id, HashFunctionId::Poseidon);
+        assert!(validator.validate_hash_function(&descriptor, &sample_vk()).is_ok());
+    }
+
     #[test]
     fn test_recursion_depth_exceeded() {
         let validator = DispatchValidator::new();
@@ -2,24 +2,23 @@
 //! 
 //! Evaluates arbitrary polynomial constraints defined in the Verification Key.
 
-use alloc::vec::Vec;
 use stylus_sdk::alloy_primitives::U256;
-use crate::utils::{fr_add, fr_sub, fr_mul, fr_pow};
+use crate::utils::{fr_add, fr_mul, fr_pow};
 use super::types::{AirConstraint, Result, Error};
 
 /// Evaluator for Generic AIR Constraints
 pub struct ConstraintEvaluator;
 
 impl ConstraintEvaluator {
-    /// Evaluate a constraint at a specific domain point (row)
+    /// Evaluate a constraint at a specific domain point
     /// 
     /// # Arguments
     /// * `constraint` - The generic constraint description (from VK)
-    /// * `trace_window` - A window of trace values around the current row [T(i), T(i+1), ...]
+    /// * `trace_window` - A window of trace values at the point and its successors [T(x), T(x·g), ...]
     /// * `width` - Trace width (number of registers)
     pub fn evaluate(
         constraint: &AirConstraint,
-        trace_window: &[u64], // Flattened window [reg0_t0, reg1_t0, ..., reg0_t1, ...]
+        trace_window: &[U256], // Flattened window [reg0_t0, reg1_t0, ..., reg0_t1, ...]
         width: usize,
     ) -> Result<U256> {
         let mut sum = U256::ZERO;
@@ -33,9 +32,7 @@ impl ConstraintEvaluator {
                 return Err(Error::ConstraintSchemaInvalid);
             }
             
-            // Get value from trace (lift to U256)
-            let val_u64 = trace_window[window_idx];
-            let val = U256::from(val_u64);
+            let val = trace_window[window_idx];
             
             // Compute term: coeff * value^power
             let mut term_val = val;
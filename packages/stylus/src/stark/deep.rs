@@ -0,0 +1,150 @@
+//! DEEP-ALI: Out-of-Domain Constraint Check and DEEP Composition
+//!
+//! The trace columns `T_r` (degree < n over the trace domain `⟨g⟩`) and the
+//! composition columns `H_i` are committed over the evaluation domain. Once
+//! both commitments are in the transcript the verifier draws a point `z`, and
+//! the proof supplies the frame `T_r(z·gᵏ)` for `k = 0..=max_offset` and the
+//! values `H_i(z)`. They must satisfy
+//!
+//! ```text
+//! Σ_i z^(i·n)·H_i(z) = Σ_j αʲ·C_j(frame) / Z_j(z) + Σ_b α^(J+b)·(T_rb(z) − v_b) / (z − g^row_b)
+//! ```
+//!
+//! with `Z_j(X) = (Xⁿ − 1) / Π_{i=1..m_j} (X − g^(n−i))`, which leaves out the
+//! last `m_j` rows (constraint j's largest offset), and `v_b` the public input
+//! asserted at `row_b`. FRI then runs on the DEEP composition
+//!
+//! ```text
+//! f(x) = Σ_k Σ_r γ^(k·w+r)·(T_r(x) − T_r(z·gᵏ)) / (x − z·gᵏ)
+//!      + Σ_i γ^(K·w+i)·(H_i(x) − H_i(z)) / (x − z)
+//! ```
+//!
+//! (`w` registers, `K` frame rows), which has degree below n only if every
+//! claimed value at z is the committed polynomial's own.
+
+use alloc::vec::Vec;
+use stylus_sdk::alloy_primitives::U256;
+use crate::utils::{batch_inverse, Fr};
+use super::constraints::ConstraintEvaluator;
+use super::fri::{domain_generator, DOMAIN_OFFSET};
+use super::proof::StarkProof;
+use super::types::{Error, Result, StarkVerificationKey};
+
+/// Transition and boundary quotients at `x`, combined with powers of `alpha`
+///
+/// `frame` is the flattened window `[T_0(x), .., T_{w−1}(x), T_0(x·g), ..]`
+/// and `public_inputs` the asserted values, one per boundary constraint.
+/// `x` must lie outside the trace domain.
+pub fn constraint_composition(
+    vk: &StarkVerificationKey,
+    frame: &[U256],
+    x: Fr,
+    alpha: Fr,
+    public_inputs: &[Fr],
+) -> Result<Fr> {
+    let n = 1u64 << vk.log_trace_length;
+    let generator = domain_generator(vk.log_trace_length);
+    let generator_inv = generator.inverse().ok_or(Error::VerificationFailed)?;
+
+    // 1 / (xⁿ − 1), then 1 / (x − g^row_b) for each boundary constraint
+    let mut inverses = Vec::with_capacity(1 + vk.boundaries.len());
+    inverses.push(x.pow(U256::from(n)).sub(&Fr::ONE));
+    for boundary in &vk.boundaries {
+        inverses.push(x.sub(&generator.pow(U256::from(boundary.row))));
+    }
+    if inverses.iter().any(Fr::is_zero) {
+        return Err(Error::VerificationFailed);
+    }
+    batch_inverse(&mut inverses);
+
+    let (mut result, mut weight) = (Fr::ZERO, Fr::ONE);
+    for constraint in &vk.constraints {
+        let value = Fr::from_u256_reduced(ConstraintEvaluator::evaluate(constraint, frame, vk.trace_width)?);
+        // 1 / Z_j(x) = Π_{i=1..m_j} (x − g⁻ⁱ) / (xⁿ − 1)
+        let (mut excluded, mut row) = (Fr::ONE, Fr::ONE);
+        for _ in 0..constraint.max_offset() {
+            row = row.mul(&generator_inv);
+            excluded = excluded.mul(&x.sub(&row));
+        }
+        result = result.add(&weight.mul(&value).mul(&excluded).mul(&inverses[0]));
+        weight = weight.mul(&alpha);
+    }
+    for ((boundary, value), inverse) in vk.boundaries.iter().zip(public_inputs).zip(&inverses[1..]) {
+        let trace_value = Fr::from_u256_reduced(frame[boundary.register as usize]);
+        result = result.add(&weight.mul(&trace_value.sub(value)).mul(inverse));
+        weight = weight.mul(&alpha);
+    }
+    Ok(result)
+}
+
+/// `Σ_i x^(i·n)·H_i(x)` from the composition columns at `x`
+pub fn composition_from_columns(columns: &[U256], x: Fr, log_trace_length: u32) -> Fr {
+    let shift = x.pow(U256::from(1u64 << log_trace_length));
+    columns
+        .iter()
+        .rev()
+        .fold(Fr::ZERO, |acc, column| acc.mul(&shift).add(&Fr::from_u256_reduced(*column)))
+}
+
+/// The DEEP composition at each query's position, from its trace and
+/// composition openings and the proof's values at z: the FRI layer-0 values
+pub fn deep_evaluations(
+    vk: &StarkVerificationKey,
+    proof: &StarkProof,
+    positions: &[usize],
+    z: Fr,
+    gamma: Fr,
+) -> Result<Vec<Fr>> {
+    let rows = proof.ood_trace.len();
+    let trace_generator = domain_generator(vk.log_trace_length);
+    let generator = domain_generator(vk.log_domain_size());
+    let offset = Fr::from_u256_reduced(DOMAIN_OFFSET);
+
+    // z·gᵏ for each frame row
+    let mut frame_points = Vec::with_capacity(rows);
+    let mut point = z;
+    for _ in 0..rows {
+        frame_points.push(point);
+        point = point.mul(&trace_generator);
+    }
+
+    // 1 / (x − z·gᵏ) for every query and frame row, inverted together
+    let mut inverses = Vec::with_capacity(positions.len() * rows);
+    for &position in positions {
+        let x = offset.mul(&generator.pow(U256::from(position)));
+        inverses.extend(frame_points.iter().map(|point| x.sub(point)));
+    }
+    if inverses.iter().any(Fr::is_zero) {
+        return Err(Error::VerificationFailed);
+    }
+    batch_inverse(&mut inverses);
+
+    let to_fr = |values: &[U256]| values.iter().map(|v| Fr::from_u256_reduced(*v)).collect::<Vec<_>>();
+    let ood_trace: Vec<Vec<Fr>> = proof.ood_trace.iter().map(|row| to_fr(row)).collect();
+    let ood_composition = to_fr(&proof.ood_composition);
+
+    let evaluations = proof
+        .queries
+        .iter()
+        .zip(inverses.chunks(rows))
+        .map(|(query, inverses)| {
+            let trace = to_fr(&query.trace.values);
+            let (mut sum, mut weight) = (Fr::ZERO, Fr::ONE);
+            for (claimed, inverse) in ood_trace.iter().zip(inverses) {
+                let mut row_sum = Fr::ZERO;
+                for (opened, claimed) in trace.iter().zip(claimed) {
+                    row_sum = row_sum.add(&weight.mul(&opened.sub(claimed)));
+                    weight = weight.mul(&gamma);
+                }
+                sum = sum.add(&row_sum.mul(inverse));
+            }
+            let mut column_sum = Fr::ZERO;
+            for (opened, claimed) in query.composition.values.iter().zip(&ood_composition) {
+                column_sum = column_sum.add(&weight.mul(&Fr::from_u256_reduced(*opened).sub(claimed)));
+                weight = weight.mul(&gamma);
+            }
+            sum.add(&column_sum.mul(&inverses[0]))
+        })
+        .collect();
+    Ok(evaluations)
+}
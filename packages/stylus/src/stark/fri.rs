@@ -0,0 +1,277 @@
+//! FRI Low-Degree Test
+//!
+//! Checks that the layer-0 codeword, whose value at each query the verifier
+//! computes from the trace and composition openings, is close to a polynomial
+//! of degree below the trace length.
+//!
+//! # Domains
+//! Layer `i` is committed over the coset `D_i = s^(kⁱ)·⟨ω_i⟩` of size
+//! `N_i = N / kⁱ`, where `N` is the trace length times the blowup factor, `k`
+//! the folding factor and `s = 5` the multiplicative generator of Fr. Leaf `j`
+//! of layer `i` holds the `k` values at positions `j + t·N_i/k`: the points
+//! `x·ζᵗ` (ζ a primitive k-th root of unity) that fold into position `j` of
+//! `D_{i+1}`.
+//!
+//! # Folding
+//! Writing `f_i(X) = Σ_t Xᵗ·f_{i,t}(Xᵏ)`, the next layer is
+//! `f_{i+1}(Y) = Σ_t β_iᵗ·f_{i,t}(Y)`. At `Y = xᵏ` that is the degree < k
+//! interpolant of the leaf's values, evaluated at `β_i`. The last folded value
+//! must match the remainder polynomial, which the proof sends in the clear.
+//!
+//! # Transcript
+//! Each layer commitment is absorbed and followed by its folding challenge
+//! `β_i`, then the remainder coefficients are absorbed. The proof-of-work
+//! nonce must give `H(seed || nonce)` at least `grinding_bits` leading zero
+//! bits; it is absorbed before the query positions are drawn.
+
+use alloc::vec::Vec;
+use stylus_sdk::alloy_primitives::U256;
+use crate::hash::Hasher;
+use crate::plonk::transcript::Transcript;
+use crate::utils::Fr;
+use super::proof::StarkProof;
+use super::types::{Error, Result};
+use super::verifier::{labels, opening_matches};
+
+/// Two-adicity of BN254 Fr: r − 1 = 2²⁸ · odd
+pub const TWO_ADICITY: u32 = 28;
+
+/// Primitive 2²⁸-th root of unity, 5^((r − 1) / 2²⁸)
+pub const TWO_ADIC_ROOT: U256 = U256::from_limbs([
+    0x9bd61b6e725b19f0,
+    0x402d111e41112ed4,
+    0x00e0a7eb8ef62abc,
+    0x2a3c09f0a58a7e85,
+]);
+
+/// Multiplicative generator of Fr, the offset of the evaluation coset
+pub const DOMAIN_OFFSET: U256 = U256::from_limbs([5, 0, 0, 0]);
+
+/// Largest supported folding factor is 2⁴
+pub const MAX_LOG_FOLDING: u8 = 4;
+
+/// Largest supported proof-of-work difficulty
+pub const MAX_GRINDING_BITS: u8 = 32;
+
+/// FRI configuration, carried by the verification key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FriParams {
+    /// log₂ of the blowup factor (evaluation domain size / trace length)
+    pub log_blowup: u8,
+    /// log₂ of the folding factor k
+    pub log_folding: u8,
+    /// Committed layers, the layer-0 codeword included
+    pub num_layers: u8,
+    /// Degree bound of the remainder polynomial (inclusive)
+    pub remainder_degree: u32,
+    /// Leading zero bits required of the proof-of-work digest
+    pub grinding_bits: u8,
+}
+
+impl FriParams {
+    pub fn folding_factor(&self) -> usize {
+        1 << self.log_folding
+    }
+
+    /// The layers fold a trace of `2^log_trace_length` rows exactly down to
+    /// the remainder degree, inside Fr's two-adic subgroup
+    pub fn validate(&self, log_trace_length: u32) -> Result<()> {
+        let folded = self.log_folding as u32 * self.num_layers as u32;
+        let valid = self.log_blowup >= 1
+            && (1..=MAX_LOG_FOLDING).contains(&self.log_folding)
+            && log_trace_length + self.log_blowup as u32 <= TWO_ADICITY
+            && folded <= log_trace_length
+            && self.remainder_degree as u64 + 1 == 1u64 << (log_trace_length - folded)
+            && self.remainder_degree < u16::MAX as u32
+            && self.grinding_bits <= MAX_GRINDING_BITS;
+        if valid { Ok(()) } else { Err(Error::InvalidFriParameters) }
+    }
+}
+
+/// Generator of the subgroup of order `2^log_size` (at most 2²⁸)
+pub fn domain_generator(log_size: u32) -> Fr {
+    debug_assert!(log_size <= TWO_ADICITY);
+    let mut generator = Fr::from_u256_reduced(TWO_ADIC_ROOT);
+    for _ in log_size..TWO_ADICITY {
+        generator = generator.square();
+    }
+    generator
+}
+
+/// `H(seed || nonce)` has at least `bits` leading zero bits
+pub fn pow_is_valid<H: Hasher>(seed: &U256, nonce: u64, bits: u8) -> bool {
+    let mut hasher = H::default();
+    hasher.update(&seed.to_be_bytes::<32>());
+    hasher.update(&nonce.to_be_bytes());
+    U256::from_be_bytes(hasher.finalize()).leading_zeros() >= bits as usize
+}
+
+/// Draw `count` positions in a domain of size `2^log_domain_size`
+pub fn query_positions<H: Hasher>(transcript: &mut Transcript<H>, count: usize, log_domain_size: u32) -> Vec<usize> {
+    let mask = (1usize << log_domain_size) - 1;
+    (0..count)
+        .map(|_| transcript.squeeze_challenge(labels::QUERY_POSITION).as_limbs()[0] as usize & mask)
+        .collect()
+}
+
+/// Value at `beta` of the degree < k interpolant of `values` over `x·⟨ζ⟩`,
+/// where `values[t]` sits at `x·ζᵗ` and `k = values.len()` is a power of two
+pub fn fold(values: &[Fr], x: Fr, beta: Fr) -> Fr {
+    let folding = Folding::new(values.len().trailing_zeros());
+    folding.fold(values, x.inverse().unwrap_or(Fr::ZERO), beta)
+}
+
+/// Horner evaluation of `coefficients` (lowest degree first) at `x`
+pub fn evaluate_polynomial(coefficients: &[Fr], x: Fr) -> Fr {
+    coefficients.iter().rev().fold(Fr::ZERO, |acc, c| acc.mul(&x).add(c))
+}
+
+/// Constants of the size-k inverse DFT behind `fold`
+struct Folding {
+    /// ζ⁻ᵗ for t < k
+    zeta_inv_powers: Vec<Fr>,
+    k_inv: Fr,
+}
+
+impl Folding {
+    fn new(log_folding: u32) -> Self {
+        let k = 1usize << log_folding;
+        let zeta_inv = domain_generator(log_folding).inverse().unwrap_or(Fr::ZERO);
+        let mut zeta_inv_powers = Vec::with_capacity(k);
+        let mut power = Fr::ONE;
+        for _ in 0..k {
+            zeta_inv_powers.push(power);
+            power = power.mul(&zeta_inv);
+        }
+        let k_inv = Fr::from_u256_reduced(U256::from(k)).inverse().unwrap_or(Fr::ZERO);
+        Folding { zeta_inv_powers, k_inv }
+    }
+
+    /// `P(β)` for `P(X) = Σ_s c_s·(X/x)ˢ / k`, `c_s = Σ_t values[t]·ζ^(−ts)`
+    fn fold(&self, values: &[Fr], x_inv: Fr, beta: Fr) -> Fr {
+        let k = values.len();
+        let ratio = beta.mul(&x_inv);
+        let mut result = Fr::ZERO;
+        for s in (0..k).rev() {
+            let mut coefficient = Fr::ZERO;
+            for (t, value) in values.iter().enumerate() {
+                coefficient = coefficient.add(&value.mul(&self.zeta_inv_powers[(t * s) % k]));
+            }
+            result = result.mul(&ratio).add(&coefficient);
+        }
+        result.mul(&self.k_inv)
+    }
+}
+
+/// FRI challenges drawn from the transcript, ready to check the query openings
+pub struct FriVerifier {
+    params: FriParams,
+    log_domain_size: u32,
+    /// Folding challenge of each layer
+    betas: Vec<Fr>,
+    remainder: Vec<Fr>,
+    positions: Vec<usize>,
+}
+
+impl FriVerifier {
+    /// Absorb the layer commitments and the remainder, check the proof of
+    /// work and draw the query positions, which must be the proof's
+    ///
+    /// The proof's shape (layer, remainder and query counts) is checked by the
+    /// caller against `params`.
+    pub fn new<H: Hasher>(
+        params: &FriParams,
+        log_domain_size: u32,
+        proof: &StarkProof,
+        transcript: &mut Transcript<H>,
+    ) -> Result<Self> {
+        let mut betas = Vec::with_capacity(proof.fri_commitments.len());
+        for commitment in &proof.fri_commitments {
+            transcript.absorb_bytes(labels::FRI_LAYER, commitment);
+            betas.push(Fr::from_u256_reduced(transcript.squeeze_challenge(labels::FRI_FOLD)));
+        }
+        for coefficient in &proof.fri_remainder {
+            transcript.absorb_field(labels::FRI_REMAINDER, coefficient);
+        }
+
+        let seed = transcript.squeeze_challenge(labels::POW_SEED);
+        if !pow_is_valid::<H>(&seed, proof.pow_nonce, params.grinding_bits) {
+            return Err(Error::ProofOfWorkFailed);
+        }
+        transcript.absorb_bytes(labels::POW_NONCE, &proof.pow_nonce.to_be_bytes());
+
+        let positions = query_positions(transcript, proof.queries.len(), log_domain_size);
+        if proof.queries.iter().zip(&positions).any(|(query, &position)| query.position as usize != position) {
+            return Err(Error::InvalidQueryPosition);
+        }
+
+        Ok(FriVerifier {
+            params: *params,
+            log_domain_size,
+            betas,
+            remainder: proof.fri_remainder.iter().map(|c| Fr::from_u256_reduced(*c)).collect(),
+            positions,
+        })
+    }
+
+    /// Query positions, in proof order
+    pub fn positions(&self) -> &[usize] {
+        &self.positions
+    }
+
+    /// Check every query's layer openings against their commitments, fold
+    /// them down from `evaluations` (the layer-0 value at each position) and
+    /// compare the result with the remainder
+    pub fn verify<H: Hasher>(&self, proof: &StarkProof, evaluations: &[Fr]) -> Result<()> {
+        let log_folding = self.params.log_folding as u32;
+        let folding = Folding::new(log_folding);
+
+        // Per layer: log₂ of its domain size, ω_i, s^(kⁱ) and s^(−kⁱ); the
+        // last entry is the remainder's domain
+        let mut domains = Vec::with_capacity(self.betas.len() + 1);
+        let mut offset = Fr::from_u256_reduced(DOMAIN_OFFSET);
+        let mut offset_inv = offset.inverse().ok_or(Error::VerificationFailed)?;
+        let mut log_size = self.log_domain_size;
+        for _ in 0..=self.betas.len() {
+            domains.push((log_size, domain_generator(log_size), offset, offset_inv));
+            let k = U256::from(self.params.folding_factor());
+            (offset, offset_inv) = (offset.pow(k), offset_inv.pow(k));
+            log_size = log_size.saturating_sub(log_folding);
+        }
+
+        for ((query, &position), evaluation) in proof.queries.iter().zip(&self.positions).zip(evaluations) {
+            let (mut position, mut value) = (position, *evaluation);
+            for (((opening, commitment), beta), &(log_size, generator, _, offset_inv)) in query
+                .fri_layers
+                .iter()
+                .zip(&proof.fri_commitments)
+                .zip(&self.betas)
+                .zip(&domains)
+            {
+                let stride = 1usize << (log_size - log_folding);
+                let (leaf, t) = (position % stride, position / stride);
+                if !opening_matches::<H>(opening, leaf, commitment) {
+                    return Err(Error::MerkleProofFailed);
+                }
+                let values: Vec<Fr> = opening.values.iter().map(|v| Fr::from_u256_reduced(*v)).collect();
+                if values[t] != value {
+                    return Err(Error::FriFailed);
+                }
+
+                // x⁻¹ = s^(−kⁱ)·ω_i^(N_i − leaf) for x, the first point of the
+                // leaf's coset
+                let x_inv = offset_inv.mul(&generator.pow(U256::from((1usize << log_size) - leaf)));
+                value = folding.fold(&values, x_inv, *beta);
+                position = leaf;
+            }
+
+            let (_, generator, offset, _) = domains[self.betas.len()];
+            let point = offset.mul(&generator.pow(U256::from(position)));
+            if evaluate_polynomial(&self.remainder, point) != value {
+                return Err(Error::FriFailed);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,12 +1,13 @@
-//! Merkle Tree implementation using Keccak256
+//! Merkle Tree implementation, Keccak256 by default
 //!
 //! Provides Merkle tree construction and proof verification for STARK commitments.
-//! Uses Keccak256 for EVM compatibility (can verify proofs on-chain).
+//! Uses Keccak256 for EVM compatibility (can verify proofs on-chain); the `_with`
+//! variants take any `crate::hash::Hasher` (SHA-256, Blake3, Poseidon).
 
 use alloc::vec::Vec;
 use alloc::vec;
-use super::types::Error;
-use sha3::{Keccak256, Digest};
+use stylus_sdk::alloy_primitives::U256;
+use crate::hash::{Hasher, Keccak256Hasher};
 
 /// Merkle tree for committing to trace values
 #[derive(Debug, Clone)]
@@ -23,6 +24,11 @@ impl MerkleTree {
     /// Each leaf is hashed with Keccak256.
     /// Tree is padded to power of 2 if necessary.
     pub fn new(leaves: &[[u8; 32]]) -> Self {
+        Self::new_with::<Keccak256Hasher>(leaves)
+    }
+
+    /// Build a Merkle tree whose parents are `H::hash_pair(left, right)`
+    pub fn new_with<H: Hasher>(leaves: &[[u8; 32]]) -> Self {
         if leaves.is_empty() {
             return MerkleTree {
                 nodes: Vec::new(),
@@ -41,9 +47,7 @@ impl MerkleTree {
         }
 
         // Pad remaining leaves with zero hash
-        for i in leaves.len()..num_leaves {
-            nodes[i] = [0u8; 32];
-        }
+        nodes[leaves.len()..num_leaves].fill([0u8; 32]);
 
         // Build tree bottom-up
         // Internal nodes start at index num_leaves
@@ -59,7 +63,7 @@ impl MerkleTree {
                 let right_idx = level_start + 2 * i + 1;
                 let parent_idx = next_level_start + i;
 
-                nodes[parent_idx] = hash_pair(&nodes[left_idx], &nodes[right_idx]);
+                nodes[parent_idx] = H::hash_pair(&nodes[left_idx], &nodes[right_idx]);
             }
 
             level_start = next_level_start;
@@ -71,11 +75,16 @@ impl MerkleTree {
 
     /// Build a Merkle tree from u64 values
     pub fn from_u64_values(values: &[u64]) -> Self {
+        Self::from_u64_values_with::<Keccak256Hasher>(values)
+    }
+
+    /// Build a Merkle tree from u64 values, hashing leaves and parents with `H`
+    pub fn from_u64_values_with<H: Hasher>(values: &[u64]) -> Self {
         let leaves: Vec<[u8; 32]> = values
             .iter()
-            .map(|v| hash_leaf(&v.to_le_bytes()))
+            .map(|v| hash_leaf::<H>(&v.to_le_bytes()))
             .collect();
-        Self::new(&leaves)
+        Self::new_with::<H>(&leaves)
     }
 
     /// Get the Merkle root
@@ -138,14 +147,19 @@ pub struct MerkleProof {
 impl MerkleProof {
     /// Verify the proof against a root and leaf hash
     pub fn verify(&self, leaf_hash: &[u8; 32], root: &[u8; 32]) -> bool {
+        self.verify_with::<Keccak256Hasher>(leaf_hash, root)
+    }
+
+    /// Verify the proof against a tree built with `H`
+    pub fn verify_with<H: Hasher>(&self, leaf_hash: &[u8; 32], root: &[u8; 32]) -> bool {
         let mut current_hash = *leaf_hash;
         let mut current_idx = self.leaf_index;
 
         for sibling in &self.siblings {
             current_hash = if current_idx % 2 == 0 {
-                hash_pair(&current_hash, sibling)
+                H::hash_pair(&current_hash, sibling)
             } else {
-                hash_pair(sibling, &current_hash)
+                H::hash_pair(sibling, &current_hash)
             };
             current_idx /= 2;
         }
@@ -192,26 +206,22 @@ impl MerkleProof {
     }
 }
 
-/// Hash two nodes together (parent = keccak(left || right))
-fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
-    let mut hasher = Keccak256::new();
-    hasher.update(left);
-    hasher.update(right);
-    let result = hasher.finalize();
-    let mut output = [0u8; 32];
-    output.copy_from_slice(&result);
-    output
+/// Hash a leaf value (`H("leaf:" || data)`)
+pub fn hash_leaf<H: Hasher>(data: &[u8]) -> [u8; 32] {
+    let mut hasher = H::default();
+    hasher.update(b"leaf:");
+    hasher.update(data);
+    hasher.finalize()
 }
 
-/// Hash a leaf value
-fn hash_leaf(data: &[u8]) -> [u8; 32] {
-    let mut hasher = Keccak256::new();
+/// Hash a committed row of field elements (32-byte big-endian each)
+pub fn hash_row<H: Hasher>(values: &[U256]) -> [u8; 32] {
+    let mut hasher = H::default();
     hasher.update(b"leaf:");
-    hasher.update(data);
-    let result = hasher.finalize();
-    let mut output = [0u8; 32];
-    output.copy_from_slice(&result);
-    output
+    for value in values {
+        hasher.update(&value.to_be_bytes::<32>());
+    }
+    hasher.finalize()
 }
 
 #[cfg(test)]
@@ -258,12 +268,42 @@ mod tests {
         
         // Verify each value
         for (i, &value) in values.iter().enumerate() {
-            let leaf_hash = hash_leaf(&value.to_le_bytes());
+            let leaf_hash = hash_leaf::<Keccak256Hasher>(&value.to_le_bytes());
             let proof = tree.proof(i).unwrap();
             assert!(proof.verify(&leaf_hash, &tree.root()), "Proof {} failed", i);
         }
     }
 
+    #[test]
+    fn test_merkle_tree_other_hashes() {
+        use crate::hash::{Blake3Hasher, PoseidonHasher, Sha256Hasher};
+
+        fn check<H: Hasher>(values: &[u64]) -> [u8; 32] {
+            let tree = MerkleTree::from_u64_values_with::<H>(values);
+            for (i, &value) in values.iter().enumerate() {
+                let leaf_hash = hash_leaf::<H>(&value.to_le_bytes());
+                let proof = tree.proof(i).unwrap();
+                assert!(proof.verify_with::<H>(&leaf_hash, &tree.root()), "Proof {} failed", i);
+                assert!(!proof.verify_with::<H>(&hash_leaf::<H>(&[0xff]), &tree.root()));
+            }
+            tree.root()
+        }
+
+        let values = [1u64, 2, 3, 4, 5];
+        let roots = [
+            check::<Keccak256Hasher>(&values),
+            check::<Sha256Hasher>(&values),
+            check::<Blake3Hasher>(&values),
+            check::<PoseidonHasher>(&values),
+        ];
+        assert_eq!(roots[0], MerkleTree::from_u64_values(&values).root());
+        for i in 0..roots.len() {
+            for j in i + 1..roots.len() {
+                assert_ne!(roots[i], roots[j]);
+            }
+        }
+    }
+
     #[test]
     fn test_proof_serialization() {
         let leaves = [[1u8; 32], [2u8; 32], [3u8; 32], [4u8; 32]];
@@ -9,50 +9,55 @@
 //!
 //! # Architecture
 //! - `types.rs`: Generic AIR schema (Constraints, Terms, VK)
+//! - `proof.rs`: Versioned binary proof format (decoder and encoder)
 //! - `constraints.rs`: Dynamic evaluator for polynomial constraints
+//! - `deep.rs`: Out-of-domain constraint check and DEEP composition (DEEP-ALI)
+//! - `fri.rs`: FRI low-degree test (folding, remainder, query positions)
 //! - `verifier.rs`: Main verification logic
 
-use alloc::vec::Vec;
-use alloc::vec;
-
 // Module declarations
 pub mod types;
+pub mod proof;
 pub mod verifier;
 pub mod merkle;
 pub mod constraints;
+pub mod deep;
+pub mod fri;
 
 // Re-exports
-pub use types::{Error, Result, SecurityLevel, GasEstimate, StarkVerificationKey, AirConstraint, StarkProof};
+pub use types::{Error, Result, SecurityLevel, GasEstimate, StarkVerificationKey, AirConstraint, BoundaryConstraint};
+pub use fri::FriParams;
+pub use proof::{MerkleOpening, QueryOpening, StarkProof};
 pub use verifier::{StarkVerifier, estimate_gas_cost};
 
-/// Entry point for Generic STARK Verification
+use crate::types::HashFunctionId;
+
+/// Security level of `verify_proof` and `verify_proof_with_hash`
+pub const SECURITY_LEVEL: SecurityLevel = SecurityLevel::Proven100;
+
+/// Entry point for Generic STARK Verification (Keccak256 commitments)
 pub fn verify_proof(
     proof_bytes: &[u8], 
-    _public_inputs: &[u8],
+    public_inputs: &[u8],
+    vk_bytes: &[u8]
+) -> Result<bool> {
+    verify_proof_with_hash(HashFunctionId::Keccak256, proof_bytes, public_inputs, vk_bytes)
+}
+
+/// Generic STARK verification with Merkle commitments over `hash`
+pub fn verify_proof_with_hash(
+    hash: HashFunctionId,
+    proof_bytes: &[u8], 
+    public_inputs: &[u8],
     vk_bytes: &[u8]
 ) -> Result<bool> {
     // 1. Deserialize VK
     // Parse the generic constraints from the Verification Key bytes.
     let vk = StarkVerificationKey::from_bytes(vk_bytes)?;
     
-    // 2. Deserialize Proof
-    // We moved the struct definition but need a deserializer.
-    // Let's implement a basic one inline or in types.
-    let mut offset = 0;
-    if proof_bytes.len() < 32 { return Err(Error::DeserializationError); }
-    
-    // Quick parse (mocking deserialization logic for the struct we defined in types.rs)
-    // Real impl would use `ruint` or `serde`.
-    let mut trace_commitment = [0u8; 32];
-    trace_commitment.copy_from_slice(&proof_bytes[0..32]);
-    
-    let proof = StarkProof {
-        trace_commitment,
-        query_values: vec![], // Simplified parsing
-        merkle_proofs: vec![],
-        expected_result: 0,
-    };
-    
-    let verifier = StarkVerifier::new(SecurityLevel::Proven100);
-    verifier.verify(&proof, &vk, _public_inputs).map(|_| true)
+    // 2. Deserialize Proof (see `proof.rs` for the format)
+    let proof = StarkProof::from_bytes(proof_bytes)?;
+
+    let verifier = StarkVerifier::new(SECURITY_LEVEL).with_hash(hash);
+    verifier.verify(&proof, &vk, public_inputs).map(|_| true)
 }
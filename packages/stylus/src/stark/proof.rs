@@ -0,0 +1,264 @@
+//! STARK Proof Format
+//!
+//! Versioned binary encoding of a generic AIR STARK proof over BN254 Fr. All
+//! integers are big-endian and every field element is a canonical 32-byte
+//! scalar (below r).
+//!
+//! # Wire Format
+//! ```text
+//! version                  u8 (= PROOF_VERSION)
+//! trace_commitment         32 bytes
+//! composition_commitment   32 bytes
+//! ood_rows                 u8
+//! ood_width                u16
+//! ood_trace                ood_rows × ood_width × 32 bytes (row k at z·gᵏ)
+//! num_ood_composition      u16
+//! ood_composition          num_ood_composition × 32 bytes
+//! num_fri_layers           u8
+//! fri_commitments          num_fri_layers × 32 bytes
+//! num_remainder            u16
+//! fri_remainder            num_remainder × 32 bytes, lowest degree first
+//! pow_nonce                u64
+//! num_queries              u16
+//! queries                  num_queries × query
+//!
+//! query:   position (u32) || trace opening || composition opening
+//!          || num_fri_layers × FRI layer opening
+//! opening: num_values (u16) || num_values × 32 bytes
+//!          || depth (u8) || depth × 32-byte siblings, leaf to root
+//! ```
+//! Leaves are [`hash_row`](super::merkle::hash_row) of the opened values.
+//!
+//! `from_bytes` rejects truncated input, trailing bytes, an unknown version,
+//! non-canonical scalars, an empty out-of-domain frame, remainder or opening,
+//! more than `MAX_DEPTH` siblings, and openings of one tree whose depths
+//! differ between queries or whose position does not fit the tree.
+
+use alloc::vec::Vec;
+use stylus_sdk::alloy_primitives::U256;
+use crate::utils::BN254_SCALAR_MODULUS;
+use super::types::{Error, Result};
+
+/// Format version written by `to_bytes` and required by `from_bytes`
+pub const PROOF_VERSION: u8 = 1;
+
+/// Deepest Merkle tree a proof may open (positions are u32)
+pub const MAX_DEPTH: usize = 32;
+
+/// Opened Merkle leaf: the committed row and its authentication path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleOpening {
+    pub values: Vec<U256>,
+    /// Sibling hashes from leaf to root
+    pub path: Vec<[u8; 32]>,
+}
+
+/// Everything opened at one query position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryOpening {
+    /// Index into the evaluation domain
+    pub position: u32,
+    pub trace: MerkleOpening,
+    pub composition: MerkleOpening,
+    /// One opening per FRI layer commitment
+    pub fri_layers: Vec<MerkleOpening>,
+}
+
+/// Generic STARK proof
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StarkProof {
+    pub trace_commitment: [u8; 32],
+    pub composition_commitment: [u8; 32],
+    /// Trace frame at the out-of-domain point: `ood_trace[k][r] = T_r(z·gᵏ)`
+    pub ood_trace: Vec<Vec<U256>>,
+    /// Composition columns at z
+    pub ood_composition: Vec<U256>,
+    pub fri_commitments: Vec<[u8; 32]>,
+    /// Coefficients of the last FRI layer's polynomial, lowest degree first
+    pub fri_remainder: Vec<U256>,
+    pub pow_nonce: u64,
+    pub queries: Vec<QueryOpening>,
+}
+
+impl StarkProof {
+    /// Decode a proof, strictly (see the module docs)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut r = Reader(bytes);
+        if r.u8()? != PROOF_VERSION {
+            return Err(Error::UnsupportedVersion);
+        }
+        let trace_commitment = r.hash()?;
+        let composition_commitment = r.hash()?;
+        let (ood_rows, ood_width) = (r.u8()? as usize, r.u16()? as usize);
+        if ood_rows == 0 || ood_width == 0 {
+            return Err(Error::InvalidProofStructure);
+        }
+        let ood_trace = (0..ood_rows)
+            .map(|_| (0..ood_width).map(|_| r.fr()).collect::<Result<Vec<_>>>())
+            .collect::<Result<Vec<_>>>()?;
+        let num_ood_composition = r.u16()? as usize;
+        if num_ood_composition == 0 {
+            return Err(Error::InvalidProofStructure);
+        }
+        let ood_composition = (0..num_ood_composition).map(|_| r.fr()).collect::<Result<Vec<_>>>()?;
+        let num_layers = r.u8()? as usize;
+        let fri_commitments = (0..num_layers).map(|_| r.hash()).collect::<Result<Vec<_>>>()?;
+        let num_remainder = r.u16()? as usize;
+        if num_remainder == 0 {
+            return Err(Error::InvalidProofStructure);
+        }
+        let fri_remainder = (0..num_remainder).map(|_| r.fr()).collect::<Result<Vec<_>>>()?;
+        let pow_nonce = r.u64()?;
+
+        let num_queries = r.u16()? as usize;
+        let mut queries = Vec::with_capacity(num_queries);
+        for _ in 0..num_queries {
+            let position = r.u32()?;
+            let trace = r.opening()?;
+            let composition = r.opening()?;
+            let fri_layers = (0..num_layers).map(|_| r.opening()).collect::<Result<Vec<_>>>()?;
+            queries.push(QueryOpening { position, trace, composition, fri_layers });
+        }
+        if !r.0.is_empty() {
+            return Err(Error::DeserializationError);
+        }
+
+        let proof = StarkProof {
+            trace_commitment,
+            composition_commitment,
+            ood_trace,
+            ood_composition,
+            fri_commitments,
+            fri_remainder,
+            pow_nonce,
+            queries,
+        };
+        proof.check_depths()?;
+        Ok(proof)
+    }
+
+    /// Encode in the format `from_bytes` reads
+    ///
+    /// Counts are written with the widths of the wire format; callers keep
+    /// them in range (`from_bytes` rejects anything that does not round-trip).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.push(PROOF_VERSION);
+        out.extend_from_slice(&self.trace_commitment);
+        out.extend_from_slice(&self.composition_commitment);
+        out.push(self.ood_trace.len() as u8);
+        out.extend_from_slice(&(self.ood_trace.first().map_or(0, Vec::len) as u16).to_be_bytes());
+        for value in self.ood_trace.iter().flatten() {
+            out.extend_from_slice(&value.to_be_bytes::<32>());
+        }
+        out.extend_from_slice(&(self.ood_composition.len() as u16).to_be_bytes());
+        for value in &self.ood_composition {
+            out.extend_from_slice(&value.to_be_bytes::<32>());
+        }
+        out.push(self.fri_commitments.len() as u8);
+        for commitment in &self.fri_commitments {
+            out.extend_from_slice(commitment);
+        }
+        out.extend_from_slice(&(self.fri_remainder.len() as u16).to_be_bytes());
+        for coefficient in &self.fri_remainder {
+            out.extend_from_slice(&coefficient.to_be_bytes::<32>());
+        }
+        out.extend_from_slice(&self.pow_nonce.to_be_bytes());
+        out.extend_from_slice(&(self.queries.len() as u16).to_be_bytes());
+        for query in &self.queries {
+            out.extend_from_slice(&query.position.to_be_bytes());
+            for opening in [&query.trace, &query.composition].into_iter().chain(&query.fri_layers) {
+                out.extend_from_slice(&(opening.values.len() as u16).to_be_bytes());
+                for value in &opening.values {
+                    out.extend_from_slice(&value.to_be_bytes::<32>());
+                }
+                out.push(opening.path.len() as u8);
+                for sibling in &opening.path {
+                    out.extend_from_slice(sibling);
+                }
+            }
+        }
+        out
+    }
+
+    /// Every tree is opened at the same depth by all queries, at a position
+    /// inside it
+    fn check_depths(&self) -> Result<()> {
+        let Some(first) = self.queries.first() else {
+            return Ok(());
+        };
+        for query in &self.queries {
+            let openings = [&query.trace, &query.composition].into_iter().chain(&query.fri_layers);
+            let expected = [&first.trace, &first.composition].into_iter().chain(&first.fri_layers);
+            for (opening, expected) in openings.zip(expected) {
+                if opening.path.len() != expected.path.len() {
+                    return Err(Error::InvalidProofStructure);
+                }
+            }
+            if (query.position as u64) >> query.trace.path.len() != 0 {
+                return Err(Error::InvalidQueryPosition);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Cursor over an encoded proof; every short read is a `DeserializationError`
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.0.len() < len {
+            return Err(Error::DeserializationError);
+        }
+        let (head, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_be_bytes([self.u8()?, self.u8()?]))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let mut word = [0u8; 4];
+        word.copy_from_slice(self.take(4)?);
+        Ok(u32::from_be_bytes(word))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(self.take(8)?);
+        Ok(u64::from_be_bytes(word))
+    }
+
+    fn hash(&mut self) -> Result<[u8; 32]> {
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(self.take(32)?);
+        Ok(hash)
+    }
+
+    /// A scalar below r
+    fn fr(&mut self) -> Result<U256> {
+        let value = U256::from_be_slice(self.take(32)?);
+        if value < BN254_SCALAR_MODULUS { Ok(value) } else { Err(Error::DeserializationError) }
+    }
+
+    fn opening(&mut self) -> Result<MerkleOpening> {
+        let num_values = self.u16()? as usize;
+        if num_values == 0 {
+            return Err(Error::InvalidProofStructure);
+        }
+        let values = (0..num_values).map(|_| self.fr()).collect::<Result<Vec<_>>>()?;
+        let depth = self.u8()? as usize;
+        if depth > MAX_DEPTH {
+            return Err(Error::InvalidProofStructure);
+        }
+        let path = (0..depth).map(|_| self.hash()).collect::<Result<Vec<_>>>()?;
+        Ok(MerkleOpening { values, path })
+    }
+}
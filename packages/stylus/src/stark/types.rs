@@ -6,7 +6,8 @@ use alloc::vec::Vec;
 use stylus_sdk::{
     alloy_primitives::U256,
 };
-use alloc::vec;
+use super::fri::FriParams;
+use crate::hash::{Hasher, Keccak256Hasher};
 
 // ============================================================================
 // Generic AIR Types (Universal STARK)
@@ -28,11 +29,33 @@ pub struct AirConstraint {
     pub terms: Vec<ConstraintTerm>,
 }
 
+impl AirConstraint {
+    /// Largest row offset read; the constraint holds on all rows but the
+    /// last `max_offset`
+    pub fn max_offset(&self) -> u32 {
+        self.terms.iter().map(|term| term.offset).max().unwrap_or(0)
+    }
+}
+
+/// Boundary assertion: `register` at `row` equals the next public input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundaryConstraint {
+    pub register: u32,
+    pub row: u32,
+}
+
 /// STARK Verification Key (Generic)
 #[derive(Debug, Clone)]
 pub struct StarkVerificationKey {
     pub trace_width: usize,
     pub constraints: Vec<AirConstraint>,
+    /// One public input (32 bytes, canonical) per assertion, in order
+    pub boundaries: Vec<BoundaryConstraint>,
+    /// log₂ of the number of trace rows
+    pub log_trace_length: u32,
+    pub fri: FriParams,
+    /// Keccak256 of the serialized key; the transcript absorbs it first
+    pub digest: [u8; 32],
 }
 
 impl StarkVerificationKey {
@@ -43,6 +66,16 @@ impl StarkVerificationKey {
     /// [num_constraints: 4 bytes]
     /// [Constraint 1]
     /// ...
+    /// [num_boundaries: 4 bytes]
+    /// [register: 4 bytes] [row: 4 bytes] per boundary constraint
+    /// [log_trace_length: 1 byte]
+    /// [log_blowup: 1 byte] [log_folding: 1 byte] [num_fri_layers: 1 byte]
+    /// [remainder_degree: 4 bytes] [grinding_bits: 1 byte]
+    ///
+    /// FRI parameters that do not fold the trace length down to the remainder
+    /// degree are `InvalidFriParameters`; registers outside the trace, rows
+    /// or offsets outside it (offsets also below 255) and term powers above
+    /// the declared degree are `ConstraintSchemaInvalid`.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
         if bytes.is_empty() { return Err(Error::DeserializationError); }
         let mut idx = 0;
@@ -97,22 +130,80 @@ impl StarkVerificationKey {
             
             constraints.push(AirConstraint { degree, terms });
         }
-        
-        Ok(StarkVerificationKey {
+
+        let num_boundaries = read_u32(&mut idx)?;
+        let mut boundaries = Vec::with_capacity((num_boundaries as usize).min(bytes.len() / 8));
+        for _ in 0..num_boundaries {
+            let register = read_u32(&mut idx)?;
+            let row = read_u32(&mut idx)?;
+            boundaries.push(BoundaryConstraint { register, row });
+        }
+
+        if idx + 4 > bytes.len() { return Err(Error::DeserializationError); }
+        let (log_trace_length, log_blowup, log_folding, num_layers) =
+            (bytes[idx] as u32, bytes[idx + 1], bytes[idx + 2], bytes[idx + 3]);
+        idx += 4;
+        let remainder_degree = read_u32(&mut idx)?;
+        if idx + 1 > bytes.len() { return Err(Error::DeserializationError); }
+        let grinding_bits = bytes[idx];
+
+        let fri = FriParams { log_blowup, log_folding, num_layers, remainder_degree, grinding_bits };
+        fri.validate(log_trace_length)?;
+
+        let vk = StarkVerificationKey {
             trace_width,
             constraints,
-        })
+            boundaries,
+            log_trace_length,
+            fri,
+            digest: Keccak256Hasher::digest(bytes),
+        };
+        vk.validate_schema()?;
+        Ok(vk)
     }
-}
 
-/// Generic STARK Proof
-/// ... (Rest is unchanged)
-#[derive(Debug, Clone)]
-pub struct StarkProof {
-    pub trace_commitment: [u8; 32],
-    pub query_values: Vec<(usize, u64)>, // (position, value) pair
-    pub merkle_proofs: Vec<Vec<[u8; 32]>>,
-    pub expected_result: u64, // Public Output
+    /// Every term and assertion reads a register and row of the trace
+    fn validate_schema(&self) -> Result<()> {
+        let rows = 1u64 << self.log_trace_length;
+        let width_valid = self.trace_width >= 1 && self.trace_width <= u16::MAX as usize;
+        let terms_valid = self.constraints.iter().all(|constraint| {
+            constraint.terms.iter().all(|term| {
+                (term.register as usize) < self.trace_width
+                    && (term.offset as u64) < rows
+                    && term.offset < u8::MAX as u32
+                    && term.power <= constraint.degree as u32
+            })
+        });
+        let boundaries_valid = self
+            .boundaries
+            .iter()
+            .all(|boundary| (boundary.register as usize) < self.trace_width && (boundary.row as u64) < rows);
+        if width_valid && terms_valid && boundaries_valid { Ok(()) } else { Err(Error::ConstraintSchemaInvalid) }
+    }
+
+    /// Rows of the out-of-domain frame, `z·gᵏ` for `k = 0..=max_offset`
+    pub fn frame_rows(&self) -> usize {
+        self.constraints.iter().map(AirConstraint::max_offset).max().unwrap_or(0) as usize + 1
+    }
+
+    /// Composition columns of degree below n: constraint j's quotient has
+    /// degree at most `d_j·(n − 1) − (n − m_j)` (boundary quotients less than n)
+    pub fn composition_width(&self) -> usize {
+        let n = 1u64 << self.log_trace_length;
+        self.constraints
+            .iter()
+            .map(|constraint| {
+                let bound = (constraint.degree as u64 * (n - 1) + constraint.max_offset() as u64).saturating_sub(n);
+                (bound / n) as usize + 1
+            })
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// log₂ of the evaluation domain size (trace length × blowup)
+    pub fn log_domain_size(&self) -> u32 {
+        self.log_trace_length + self.fri.log_blowup as u32
+    }
 }
 
 /// Error types
@@ -126,6 +217,11 @@ pub enum Error {
     ConstraintFailed,
     InvalidQueryPosition,
     ConstraintSchemaInvalid,
+    UnsupportedHash,
+    UnsupportedVersion,
+    InvalidFriParameters,
+    FriFailed,
+    ProofOfWorkFailed,
 }
 
 impl fmt::Display for Error {
@@ -139,6 +235,11 @@ impl fmt::Display for Error {
             Error::ConstraintFailed => write!(f, "Constraint failed"),
             Error::InvalidQueryPosition => write!(f, "Query invalid"),
             Error::ConstraintSchemaInvalid => write!(f, "Schema invalid"),
+            Error::UnsupportedHash => write!(f, "Hash unsupported"),
+            Error::UnsupportedVersion => write!(f, "Proof version unsupported"),
+            Error::InvalidFriParameters => write!(f, "FRI parameters invalid"),
+            Error::FriFailed => write!(f, "FRI failed"),
+            Error::ProofOfWorkFailed => write!(f, "Proof of work failed"),
         }
     }
 }
@@ -153,12 +254,24 @@ pub enum SecurityLevel {
 }
 
 impl SecurityLevel {
-    pub fn num_queries(&self) -> usize {
-        match self {
-            SecurityLevel::Test96 => 27,
-            SecurityLevel::Proven100 => 28,
-            SecurityLevel::High128 => 36,
-        }
+    /// Target soundness in bits
+    pub fn bits(&self) -> usize {
+        *self as usize
+    }
+
+    /// FRI queries needed for `bits()` of soundness under `fri`
+    ///
+    /// A word δ-far from the code (rate ρ = 2^−log_blowup) survives a query
+    /// with probability at most 1 − δ. Taking δ at the Johnson bound,
+    /// 1 − √ρ, each query gives log_blowup / 2 bits (the proven bound, not
+    /// the conjectured log_blowup), and grinding adds its bits directly:
+    /// `q = ⌈2·(bits − grinding_bits) / log_blowup⌉`. The commit-phase error,
+    /// about N² / |Fr| up to factors polynomial in 1/ρ, is negligible over
+    /// the 254-bit Fr.
+    pub fn num_queries(&self, fri: &FriParams) -> usize {
+        let bits = self.bits().saturating_sub(fri.grinding_bits as usize);
+        let log_blowup = (fri.log_blowup as usize).max(1);
+        (2 * bits).div_ceil(log_blowup)
     }
 }
 
@@ -1,20 +1,55 @@
 //! STARK verifier implementation (Generic AIR)
 
-use alloc::vec;
 use alloc::vec::Vec;
 use stylus_sdk::alloy_primitives::U256;
-use super::types::{Error, Result, SecurityLevel, GasEstimate, StarkProof, StarkVerificationKey};
-use super::constraints::ConstraintEvaluator;
+use super::types::{Error, Result, SecurityLevel, GasEstimate, StarkVerificationKey};
+use super::proof::{MerkleOpening, StarkProof};
+use super::deep::{composition_from_columns, constraint_composition, deep_evaluations};
+use super::fri::{FriParams, FriVerifier};
+use super::merkle::{hash_row, MerkleProof};
+use crate::hash::{Blake3Hasher, Hasher, Keccak256Hasher, PoseidonHasher, Sha256Hasher};
+use crate::plonk::transcript::Transcript;
+use crate::types::HashFunctionId;
+use crate::utils::Fr;
+
+/// Fiat-Shamir labels of the STARK transcript
+pub mod labels {
+    pub const STARK_PROTOCOL: &[u8] = b"stark_air_bn254_v1";
+    pub const VERIFICATION_KEY: &[u8] = b"stark_vk";
+    pub const PUBLIC_INPUT: &[u8] = b"stark_public_input";
+    pub const TRACE_COMMITMENT: &[u8] = b"stark_trace_comm";
+    pub const CONSTRAINT_CHALLENGE: &[u8] = b"stark_alpha";
+    pub const COMPOSITION_COMMITMENT: &[u8] = b"stark_composition_comm";
+    pub const OOD_POINT: &[u8] = b"stark_z";
+    pub const OOD_EVAL: &[u8] = b"stark_ood_eval";
+    pub const DEEP_CHALLENGE: &[u8] = b"stark_gamma";
+    pub const FRI_LAYER: &[u8] = b"stark_fri_layer_comm";
+    pub const FRI_FOLD: &[u8] = b"stark_fri_beta";
+    pub const FRI_REMAINDER: &[u8] = b"stark_fri_remainder";
+    pub const POW_SEED: &[u8] = b"stark_pow_seed";
+    pub const POW_NONCE: &[u8] = b"stark_pow_nonce";
+    pub const QUERY_POSITION: &[u8] = b"stark_query";
+}
 
 /// Generic STARK verifier
 pub struct StarkVerifier {
     security_level: SecurityLevel,
+    /// Hash of the commitments and the transcript (Keccak256 unless set with
+    /// `with_hash`)
+    hash: HashFunctionId,
 }
 
 impl StarkVerifier {
     /// Create a new verifier with given security level
     pub fn new(security_level: SecurityLevel) -> Self {
-        StarkVerifier { security_level }
+        StarkVerifier { security_level, hash: HashFunctionId::Keccak256 }
+    }
+
+    /// Use `hash` for the Merkle commitments and the Fiat-Shamir transcript
+    /// (the descriptor's `hash_function_id`)
+    pub fn with_hash(mut self, hash: HashFunctionId) -> Self {
+        self.hash = hash;
+        self
     }
     
     /// Verify a Generic STARK proof
@@ -22,132 +57,162 @@ impl StarkVerifier {
         &self,
         proof: &StarkProof,
         vk: &StarkVerificationKey,
-        _public_inputs: &[u8], // In full impl, used for boundary constraints
+        public_inputs: &[u8],
     ) -> Result<()> {
-        // Step 1: Validate proof structure
-        self.validate_proof_structure(proof)?;
-        
-        // Step 2: Verify generic constraints at queried positions
-        self.verify_constraints(proof, vk)?;
-        
-        // Step 3: Verify Merkle proofs
-        self.verify_merkle_proofs(proof)?;
+        match self.hash {
+            HashFunctionId::Keccak256 => self.verify_with::<Keccak256Hasher>(proof, vk, public_inputs),
+            HashFunctionId::SHA256 => self.verify_with::<Sha256Hasher>(proof, vk, public_inputs),
+            HashFunctionId::Blake3 => self.verify_with::<Blake3Hasher>(proof, vk, public_inputs),
+            HashFunctionId::Poseidon => self.verify_with::<PoseidonHasher>(proof, vk, public_inputs),
+            HashFunctionId::RescuePrime | HashFunctionId::Blake2b => Err(Error::UnsupportedHash),
+        }
+    }
+
+    fn verify_with<H: Hasher>(
+        &self,
+        proof: &StarkProof,
+        vk: &StarkVerificationKey,
+        public_inputs: &[u8],
+    ) -> Result<()> {
+        // Step 1: Validate proof structure and public inputs
+        self.validate_proof_structure(proof, vk)?;
+        let boundary_values = parse_public_inputs(vk, public_inputs)?;
+
+        // Step 2: Fiat-Shamir. VK digest, public inputs, trace commitment,
+        // constraint challenge, composition commitment, out-of-domain point
+        // and values, DEEP challenge, then the FRI layers, proof of work and
+        // query positions
+        let mut transcript = Transcript::<H>::with_hasher(labels::STARK_PROTOCOL);
+        transcript.absorb_bytes(labels::VERIFICATION_KEY, &vk.digest);
+        transcript.absorb_bytes(labels::PUBLIC_INPUT, public_inputs);
+        transcript.absorb_bytes(labels::TRACE_COMMITMENT, &proof.trace_commitment);
+        let alpha = Fr::from_u256_reduced(transcript.squeeze_challenge(labels::CONSTRAINT_CHALLENGE));
+        transcript.absorb_bytes(labels::COMPOSITION_COMMITMENT, &proof.composition_commitment);
+        let z = Fr::from_u256_reduced(transcript.squeeze_challenge(labels::OOD_POINT));
+        for value in proof.ood_trace.iter().flatten().chain(&proof.ood_composition) {
+            transcript.absorb_field(labels::OOD_EVAL, value);
+        }
+        let gamma = Fr::from_u256_reduced(transcript.squeeze_challenge(labels::DEEP_CHALLENGE));
+        let fri = FriVerifier::new(&vk.fri, vk.log_domain_size(), proof, &mut transcript)?;
+
+        // Step 3: Verify generic constraints at the out-of-domain point
+        self.verify_constraints(proof, vk, z, alpha, &boundary_values)?;
         
-        Ok(())
+        // Step 4: Verify Merkle proofs
+        self.verify_merkle_proofs::<H>(proof)?;
+
+        // Step 5: FRI on the DEEP composition, which ties the values at z to
+        // the commitments
+        let evaluations = deep_evaluations(vk, proof, fri.positions(), z, gamma)?;
+        fri.verify::<H>(proof, &evaluations)
     }
     
     /// Validate proof has correct structure
-    fn validate_proof_structure(&self, proof: &StarkProof) -> Result<()> {
-        let expected_queries = self.security_level.num_queries();
-        
-        if proof.query_values.len() != expected_queries {
+    ///
+    /// Lengths, depths and positions were checked by `StarkProof::from_bytes`;
+    /// here the query count must match the security level, and the frame,
+    /// rows, trees, remainder and FRI leaves must have the sizes the VK gives.
+    fn validate_proof_structure(&self, proof: &StarkProof, vk: &StarkVerificationKey) -> Result<()> {
+        let fri = &vk.fri;
+        let log_domain_size = vk.log_domain_size() as usize;
+        let log_folding = fri.log_folding as usize;
+        let composition_width = vk.composition_width();
+        if proof.queries.len() != self.security_level.num_queries(fri)
+            || proof.ood_trace.len() != vk.frame_rows()
+            || proof.ood_trace.iter().any(|row| row.len() != vk.trace_width)
+            || proof.ood_composition.len() != composition_width
+            || proof.fri_commitments.len() != fri.num_layers as usize
+            || proof.fri_remainder.len() != fri.remainder_degree as usize + 1
+        {
             return Err(Error::InvalidProofStructure);
         }
-        
-        // In simplified mode, we might not have merkle proofs for all queries
-        // but if provided, they must match
-        if !proof.merkle_proofs.is_empty() && proof.merkle_proofs.len() != expected_queries {
-             return Err(Error::InvalidProofStructure);
+        for query in &proof.queries {
+            let layers_valid = query.fri_layers.len() == fri.num_layers as usize
+                && query.fri_layers.iter().enumerate().all(|(layer, opening)| {
+                    opening.values.len() == fri.folding_factor()
+                        && opening.path.len() == log_domain_size - log_folding * (layer + 1)
+                });
+            if !layers_valid
+                || query.trace.values.len() != vk.trace_width
+                || query.composition.values.len() != composition_width
+                || query.trace.path.len() != log_domain_size
+                || query.composition.path.len() != log_domain_size
+            {
+                return Err(Error::InvalidProofStructure);
+            }
         }
-        
         Ok(())
     }
     
     /// Verify generic AIR constraints
+    ///
+    /// The transition and boundary quotients at z, evaluated on the proof's
+    /// frame, must equal the composition columns' value there. FRI later
+    /// checks that the frame and columns are the committed polynomials'.
     fn verify_constraints(
         &self,
         proof: &StarkProof,
         vk: &StarkVerificationKey,
+        z: Fr,
+        alpha: Fr,
+        boundary_values: &[Fr],
     ) -> Result<()> {
-        // For each query, we need to construct a "Trace Window"
-        // A trace window is the set of values needed to evaluate the constraint.
-        // E.g. for F(i+2) - F(i+1) - F(i) = 0, we need values at i, i+1, i+2.
-        //
-        // In this "Toy-to-Production" transition, our `query_values` is simplified:
-        // It currently only stores (pos, value) tuples.
-        // A real STARK proof provides authentication paths for the ENTIRE window.
-        //
-        // To make this work with the current data structure (and minimal changes),
-        // we'll assume the VK describes constraints that can be checked with the
-        // provided query values.
-        //
-        // Limitation: Our current `StarkProof` is still a bit flat.
-        // We will adapt the evaluator to work with what we have.
-        
-        for (pos, value) in &proof.query_values {
-             // 1. Construct a minimal window from the single value
-             // (This implies constraints are single-row for this specific data structure, 
-             //  OR we need to look up neighbors if we had them)
-             let trace_window = vec![*value]; 
-             
-             // 2. Evaluate all constraints in the VK
-             for constraint in &vk.constraints {
-                 let result = ConstraintEvaluator::evaluate(
-                     constraint, 
-                     &trace_window, 
-                     vk.trace_width
-                 )?;
-                 
-                 if result != U256::ZERO {
-                     if *pos > 1 { // Skip boundary conditions (first rows) if simplistic
-                        return Err(Error::ConstraintFailed);
-                     }
-                 }
-             }
+        // Flattened window [reg0_t0, reg1_t0, ..., reg0_t1, ...] at z·gᵏ
+        let frame: Vec<U256> = proof.ood_trace.iter().flatten().copied().collect();
+        let expected = constraint_composition(vk, &frame, z, alpha, boundary_values)?;
+        if composition_from_columns(&proof.ood_composition, z, vk.log_trace_length) != expected {
+            return Err(Error::ConstraintFailed);
         }
-        
+
         Ok(())
     }
     
-    /// Verify Merkle proofs for query positions
-    fn verify_merkle_proofs(&self, proof: &StarkProof) -> Result<()> {
-        use super::merkle::MerkleProof;
-        use sha3::{Keccak256, Digest};
-        
-        // Helper to hash a leaf value
-        let hash_leaf = |value: u64| -> [u8; 32] {
-            let mut hasher = Keccak256::new();
-            hasher.update(b"leaf:");
-            hasher.update(&value.to_le_bytes());
-            let result = hasher.finalize();
-            let mut output = [0u8; 32];
-            output.copy_from_slice(&result);
-            output
-        };
-        
-        // Verify each query's Merkle proof
-        for (i, (pos, value)) in proof.query_values.iter().enumerate() {
-            if i >= proof.merkle_proofs.len() || proof.merkle_proofs[i].is_empty() {
-                continue; 
-            }
-            
-            let merkle_proof = MerkleProof {
-                leaf_index: *pos,
-                siblings: proof.merkle_proofs[i].clone(),
-            };
-            
-            let leaf_hash = hash_leaf(*value);
-            if !merkle_proof.verify(&leaf_hash, &proof.trace_commitment) {
+    /// Trace and composition rows against their commitments at each position
+    fn verify_merkle_proofs<H: Hasher>(&self, proof: &StarkProof) -> Result<()> {
+        for query in &proof.queries {
+            let position = query.position as usize;
+            if !opening_matches::<H>(&query.trace, position, &proof.trace_commitment)
+                || !opening_matches::<H>(&query.composition, position, &proof.composition_commitment)
+            {
                 return Err(Error::MerkleProofFailed);
             }
         }
-        
+
         Ok(())
     }
 }
 
-/// Estimate gas cost for STARK verification
-pub fn estimate_gas_cost(security_level: SecurityLevel) -> GasEstimate {
-    let num_queries = security_level.num_queries();
+/// One canonical 32-byte value per boundary constraint
+fn parse_public_inputs(vk: &StarkVerificationKey, public_inputs: &[u8]) -> Result<Vec<Fr>> {
+    if public_inputs.len() != 32 * vk.boundaries.len() {
+        return Err(Error::InvalidInputSize);
+    }
+    public_inputs
+        .chunks(32)
+        .map(|chunk| Fr::from_u256(U256::from_be_slice(chunk)).ok_or(Error::InvalidInputSize))
+        .collect()
+}
+
+/// `opening` authenticates its row as leaf `position` under `root`
+pub(super) fn opening_matches<H: Hasher>(opening: &MerkleOpening, position: usize, root: &[u8; 32]) -> bool {
+    let merkle_proof = MerkleProof { leaf_index: position, siblings: opening.path.clone() };
+    merkle_proof.verify_with::<H>(&hash_row::<H>(&opening.values), root)
+}
+
+/// Estimate gas cost for STARK verification under `fri`
+pub fn estimate_gas_cost(security_level: SecurityLevel, fri: &FriParams) -> GasEstimate {
+    let num_queries = security_level.num_queries(fri);
     
-    // Merkle proof verification: ~5k gas per query
-    let merkle_gas = num_queries * 5_000;
+    // Merkle proof verification: ~5k gas per path (trace, composition and
+    // one per FRI layer)
+    let merkle_gas = num_queries * (2 + fri.num_layers as usize) * 5_000;
     
     // Constraint checks: Cost now depends on VK complexity!
     // Assume average 10 constraints * 2k gas
     let constraint_gas = num_queries * 20_000;
     
-    let field_ops_gas = 50_000;
+    // Folding: ~10k gas per query and layer
+    let field_ops_gas = 50_000 + num_queries * fri.num_layers as usize * 10_000;
     let overhead_gas = 50_000;
     
     let total = merkle_gas + constraint_gas + field_ops_gas + overhead_gas;
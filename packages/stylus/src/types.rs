@@ -45,13 +45,39 @@ pub enum ProofType {
     /// - Slower verification (~540k gas)
     /// - Post-quantum secure (hash-based)
     STARK = 2,
+
+    /// Groth16 as produced by gnark (BN254, gnark binary serialization)
+    /// - Optional BSB22 Pedersen commitments (`api.Commit`)
+    /// - One extra pairing check for the commitment proof of knowledge
+    /// - Commitment hashes derived as extra public inputs
+    Groth16Gnark = 3,
+
+    /// PLONK as produced by snarkjs (BN254, KZG)
+    /// - Same proof and VK layout as `PLONK`
+    /// - Challenges from the snarkjs Keccak256 transcript
+    PlonkSnarkjs = 4,
+
+    /// fflonk as produced by snarkjs (BN254, KZG)
+    /// - Three combined commitments, one KZG opening
+    /// - 768-byte proof, cheapest universal-setup verification
+    Fflonk = 5,
+
+    /// halo2 as produced by PSE halo2 (BN254, KZG/SHPLONK)
+    /// - Custom gates, lookups and permutation read from the VK
+    /// - Blake2b or Keccak256 transcript (descriptor hash)
+    Halo2 = 6,
+
+    /// UltraPLONK (BN254, KZG)
+    /// - Wires, custom gates and lookup table declared in the VK
+    /// - plookup argument for range checks and table lookups
+    UltraPlonk = 7,
 }
 
 impl ProofType {
     /// Convert raw u8 to ProofType enum
     ///
     /// # Returns
-    /// Returns `Some(ProofType)` for valid values (0-2), `None` otherwise.
+    /// Returns `Some(ProofType)` for valid values (0-7), `None` otherwise.
     /// This prevents routing to non-existent verifiers.
     ///
     /// # Examples
@@ -65,6 +91,11 @@ impl ProofType {
             0 => Some(ProofType::Groth16),
             1 => Some(ProofType::PLONK),
             2 => Some(ProofType::STARK),
+            3 => Some(ProofType::Groth16Gnark),
+            4 => Some(ProofType::PlonkSnarkjs),
+            5 => Some(ProofType::Fflonk),
+            6 => Some(ProofType::Halo2),
+            7 => Some(ProofType::UltraPlonk),
             _ => None,
         }
     }
@@ -81,6 +112,21 @@ impl ProofType {
     pub fn to_u8(self) -> u8 {
         self as u8
     }
+
+    /// Whether keys of this type are bound to a registered KZG SRS
+    ///
+    /// These keys are stored without `[τ]₂`, which the verifier appends from
+    /// the SRS registry (`register_plonk_vk`).
+    pub fn uses_srs(self) -> bool {
+        matches!(
+            self,
+            ProofType::PLONK
+                | ProofType::PlonkSnarkjs
+                | ProofType::Fflonk
+                | ProofType::Halo2
+                | ProofType::UltraPlonk
+        )
+    }
 }
 
 /// Public statement shared across all proof systems
@@ -438,10 +484,23 @@ mod tests {
         assert_eq!(ProofType::from_u8(0), Some(ProofType::Groth16));
         assert_eq!(ProofType::from_u8(1), Some(ProofType::PLONK));
         assert_eq!(ProofType::from_u8(2), Some(ProofType::STARK));
-        assert_eq!(ProofType::from_u8(3), None);
+        assert_eq!(ProofType::from_u8(3), Some(ProofType::Groth16Gnark));
+        assert_eq!(ProofType::from_u8(4), Some(ProofType::PlonkSnarkjs));
+        assert_eq!(ProofType::from_u8(5), Some(ProofType::Fflonk));
+        assert_eq!(ProofType::from_u8(6), Some(ProofType::Halo2));
+        assert_eq!(ProofType::from_u8(7), Some(ProofType::UltraPlonk));
+        assert_eq!(ProofType::from_u8(8), None);
         assert_eq!(ProofType::from_u8(255), None);
     }
 
+    #[test]
+    fn test_proof_type_uses_srs() {
+        assert!(ProofType::PLONK.uses_srs());
+        assert!(ProofType::UltraPlonk.uses_srs());
+        assert!(!ProofType::Groth16.uses_srs());
+        assert!(!ProofType::STARK.uses_srs());
+    }
+
     #[test]
     fn test_public_statement_encode_decode() {
         let statement = PublicStatement::new(
@@ -610,6 +669,8 @@ pub enum HashFunctionId {
     Keccak256 = 3,
     /// Rescue Prime - arithmetic-friendly
     RescuePrime = 4,
+    /// Blake2b - halo2's default transcript
+    Blake2b = 5,
 }
 
 impl HashFunctionId {
@@ -620,6 +681,7 @@ impl HashFunctionId {
             2 => Some(HashFunctionId::Blake3),
             3 => Some(HashFunctionId::Keccak256),
             4 => Some(HashFunctionId::RescuePrime),
+            5 => Some(HashFunctionId::Blake2b),
             _ => None,
         }
     }
@@ -644,23 +706,27 @@ pub enum DescriptorError {
     ExcessiveRecursionDepth,
     /// Buffer too short to decode
     BufferTooShort,
+    /// Reserved flag bits are set
+    UnknownFlags,
 }
 
-/// Universal Proof Descriptor (UPD) v2
+/// Universal Proof Descriptor (UPD) v3
 ///
 /// Self-describing proof header that enables:
 /// - **Safe dispatch** before parsing proof bytes
 /// - **Cost prediction** for gas estimation
 /// - **Future-proof extensibility** via version field
 /// - **Recursion tracking** via depth field
+/// - **Encoding hints** via flags (e.g. compressed curve points)
 ///
-/// # Binary Layout (75 bytes)
+/// # Binary Layout (76 bytes)
 /// ```text
 /// [upd_version: 1 byte]
 /// [proof_system_id: 1 byte]
 /// [curve_id: 1 byte]
 /// [hash_function_id: 1 byte]
 /// [recursion_depth: 1 byte]
+/// [flags: 1 byte]
 /// [public_input_count: 2 bytes (u16 big-endian)]
 /// [proof_length: 4 bytes (u32 big-endian)]
 /// [vk_commitment: 32 bytes]
@@ -668,7 +734,7 @@ pub enum DescriptorError {
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UniversalProofDescriptor {
-    /// UPD format version (2 for this format)
+    /// UPD format version (3 for this format)
     pub upd_version: u8,
 
     /// Proof system identifier (matches ProofType enum)
@@ -683,6 +749,9 @@ pub struct UniversalProofDescriptor {
     /// Recursion depth (0 = base proof, 1+ = recursive)
     pub recursion_depth: u8,
 
+    /// Encoding flags (`FLAG_*`)
+    pub flags: u8,
+
     /// Number of public inputs
     pub public_input_count: u16,
 
@@ -698,10 +767,16 @@ pub struct UniversalProofDescriptor {
 
 impl UniversalProofDescriptor {
     /// Encoded size in bytes
-    pub const ENCODED_SIZE: usize = 75;
+    pub const ENCODED_SIZE: usize = 76;
 
     /// Current UPD version
-    pub const CURRENT_VERSION: u8 = 2;
+    pub const CURRENT_VERSION: u8 = 3;
+
+    /// Flag: proof curve points are compressed
+    pub const FLAG_COMPRESSED: u8 = 0x01;
+
+    /// All defined flag bits
+    pub const KNOWN_FLAGS: u8 = Self::FLAG_COMPRESSED;
 
     /// Maximum allowed public inputs
     pub const MAX_PUBLIC_INPUTS: u16 = 1024;
@@ -710,6 +785,7 @@ impl UniversalProofDescriptor {
     pub const MAX_RECURSION_DEPTH: u8 = 16;
 
     /// Create a new descriptor with current version
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         proof_system_id: u8,
         curve_id: CurveId,
@@ -726,6 +802,7 @@ impl UniversalProofDescriptor {
             curve_id,
             hash_function_id,
             recursion_depth,
+            flags: 0,
             public_input_count,
             proof_length,
             vk_commitment,
@@ -733,6 +810,17 @@ impl UniversalProofDescriptor {
         }
     }
 
+    /// Set the encoding flags
+    pub fn with_flags(mut self, flags: u8) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Whether the proof's curve points are compressed
+    pub fn is_compressed(&self) -> bool {
+        self.flags & Self::FLAG_COMPRESSED != 0
+    }
+
     /// Convenience constructor for Groth16 proofs
     pub fn groth16(
         public_input_count: u16,
@@ -751,6 +839,18 @@ impl UniversalProofDescriptor {
         )
     }
 
+    /// Convenience constructor for compressed (128-byte) Groth16 proofs
+    pub fn groth16_compressed(
+        public_input_count: u16,
+        vk_commitment: [u8; 32],
+        circuit_id: [u8; 32],
+    ) -> Self {
+        let mut descriptor = Self::groth16(public_input_count, vk_commitment, circuit_id)
+            .with_flags(Self::FLAG_COMPRESSED);
+        descriptor.proof_length = 128;
+        descriptor
+    }
+
     /// Convenience constructor for PLONK proofs
     pub fn plonk(
         public_input_count: u16,
@@ -809,6 +909,11 @@ impl UniversalProofDescriptor {
             return Err(DescriptorError::ExcessiveRecursionDepth);
         }
 
+        // Check flags
+        if self.flags & !Self::KNOWN_FLAGS != 0 {
+            return Err(DescriptorError::UnknownFlags);
+        }
+
         Ok(())
     }
 
@@ -822,6 +927,11 @@ impl UniversalProofDescriptor {
             0 => GasCost::groth16(),
             1 => GasCost::plonk(),
             2 => GasCost::stark(),
+            3 => GasCost::groth16_gnark(),
+            4 => GasCost::plonk(),
+            5 => GasCost::fflonk(),
+            6 => GasCost::halo2(),
+            7 => GasCost::ultra_plonk(),
             _ => return u64::MAX, // Unknown system
         };
 
@@ -844,6 +954,8 @@ impl UniversalProofDescriptor {
         offset += 1;
         buf[offset] = self.recursion_depth;
         offset += 1;
+        buf[offset] = self.flags;
+        offset += 1;
 
         // Multi-byte fields (big-endian for network order)
         buf[offset..offset + 2].copy_from_slice(&self.public_input_count.to_be_bytes());
@@ -879,6 +991,8 @@ impl UniversalProofDescriptor {
         offset += 1;
         let recursion_depth = bytes[offset];
         offset += 1;
+        let flags = bytes[offset];
+        offset += 1;
 
         // Multi-byte fields (big-endian)
         let public_input_count =
@@ -907,6 +1021,7 @@ impl UniversalProofDescriptor {
             curve_id,
             hash_function_id,
             recursion_depth,
+            flags,
             public_input_count,
             proof_length,
             vk_commitment,
@@ -971,6 +1086,21 @@ mod upd_tests {
         assert!(plonk_gas < stark_gas);
     }
 
+    #[test]
+    fn test_upd_compression_flag() {
+        let compressed = UniversalProofDescriptor::groth16_compressed(4, [1u8; 32], [2u8; 32]);
+        assert!(compressed.is_compressed());
+        assert_eq!(compressed.proof_length, 128);
+        assert!(!UniversalProofDescriptor::groth16(4, [1u8; 32], [2u8; 32]).is_compressed());
+
+        let decoded = UniversalProofDescriptor::decode(&compressed.encode()).unwrap();
+        assert!(decoded.is_compressed());
+        assert!(decoded.validate().is_ok());
+
+        let unknown = compressed.with_flags(0x80);
+        assert_eq!(unknown.validate(), Err(DescriptorError::UnknownFlags));
+    }
+
     #[test]
     fn test_upd_stark_constructor() {
         let stark = UniversalProofDescriptor::stark(8, 100_000, [3u8; 32]);
@@ -4,7 +4,11 @@
 //! extensive Scalar Field (Fr) arithmetic for polynomial evaluations.
 //!
 //! This module implements Fr operations using U256 with the BN254 Scalar Modulus.
+//! Multiplication, powers and inverses go through [`Fr`], a Montgomery-form
+//! element with CIOS multiplication, instead of `U256::mul_mod` (a 512-bit
+//! division per product). Hot loops can keep values as `Fr` and convert once.
 
+use alloc::vec::Vec;
 use stylus_sdk::alloy_primitives::U256;
 
 /// BN254 Scalar Field Modulus (r)
@@ -17,6 +21,16 @@ pub const BN254_SCALAR_MODULUS: U256 = U256::from_be_bytes([
     0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01
 ]);
 
+/// BN254 Base Field Modulus (p)
+/// p = 21888242871839275222246405745257275088696311157297823662689037894645226208583
+/// Hex: 0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47
+pub const BN254_BASE_MODULUS: U256 = U256::from_be_bytes([
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29,
+    0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d,
+    0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47
+]);
+
 /// Modular Addition: (a + b) % r
 pub fn fr_add(a: U256, b: U256) -> U256 {
     a.add_mod(b, BN254_SCALAR_MODULUS)
@@ -35,37 +49,21 @@ pub fn fr_sub(a: U256, b: U256) -> U256 {
 }
 
 /// Modular Multiplication: (a * b) % r
+///
+/// Two Montgomery products: (a·b·R⁻¹)·R²·R⁻¹ = a·b.
 pub fn fr_mul(a: U256, b: U256) -> U256 {
-    a.mul_mod(b, BN254_SCALAR_MODULUS)
+    let product = Fr::reduce(a).mul(&Fr::reduce(b));
+    U256::from_limbs(product.mul(&Fr(FR_R2)).0)
 }
 
 /// Modular Exponentiation: (base ^ exp) % r
 pub fn fr_pow(base: U256, exp: U256) -> U256 {
-    // alloy_primitives U256 doesn't have a direct pow_mod? 
-    // It has pow(), but that overflows.
-    // We implement square-and-multiply.
-    
-    let mut result = U256::from(1);
-    let mut base = base % BN254_SCALAR_MODULUS;
-    let mut exp = exp;
-
-    while exp > U256::ZERO {
-        if (exp & U256::from(1)) == U256::from(1) {
-            result = fr_mul(result, base);
-        }
-        base = fr_mul(base, base);
-        exp >>= 1;
-    }
-    result
+    Fr::from_u256_reduced(base).pow(exp).to_u256()
 }
 
-/// Modular Inverse: a^(-1) % r encoded as a^(r-2) % r (Fermat's Little Theorem)
+/// Modular Inverse: a^(-1) % r, `None` when a ≡ 0 (mod r)
 pub fn fr_inv(a: U256) -> Option<U256> {
-    if a == U256::ZERO {
-        return None;
-    }
-    let r_minus_2 = BN254_SCALAR_MODULUS.saturating_sub(U256::from(2));
-    Some(fr_pow(a, r_minus_2))
+    Fr::from_u256_reduced(a).inverse().map(Fr::to_u256)
 }
 
 /// Convert byte slice to U256 (Big Endian) and reduce modulo r
@@ -90,3 +88,320 @@ pub fn fr_from_be_bytes_mod(bytes: &[u8]) -> U256 {
     
     val % BN254_SCALAR_MODULUS
 }
+
+/// Base Field Addition: (a + b) % p
+pub fn fq_add(a: U256, b: U256) -> U256 {
+    a.add_mod(b, BN254_BASE_MODULUS)
+}
+
+/// Base Field Subtraction: (a - b) % p
+pub fn fq_sub(a: U256, b: U256) -> U256 {
+    if a >= b {
+        a - b
+    } else {
+        a.add_mod(BN254_BASE_MODULUS - b, BN254_BASE_MODULUS)
+    }
+}
+
+/// Base Field Multiplication: (a * b) % p
+pub fn fq_mul(a: U256, b: U256) -> U256 {
+    a.mul_mod(b, BN254_BASE_MODULUS)
+}
+
+/// Base Field Negation: -a % p
+pub fn fq_neg(a: U256) -> U256 {
+    if a == U256::ZERO { U256::ZERO } else { BN254_BASE_MODULUS - a }
+}
+
+/// Base Field Exponentiation: (base ^ exp) % p
+pub fn fq_pow(base: U256, exp: U256) -> U256 {
+    let mut result = U256::from(1);
+    let mut base = base % BN254_BASE_MODULUS;
+    let mut exp = exp;
+
+    while exp > U256::ZERO {
+        if exp.bit(0) {
+            result = fq_mul(result, base);
+        }
+        base = fq_mul(base, base);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Base Field Inverse: a^(p-2) % p (Fermat's Little Theorem)
+pub fn fq_inv(a: U256) -> Option<U256> {
+    if a == U256::ZERO {
+        return None;
+    }
+    Some(fq_pow(a, BN254_BASE_MODULUS - U256::from(2)))
+}
+
+/// Base Field Square Root
+///
+/// p ≡ 3 (mod 4), so a candidate root is a^((p+1)/4); it is returned only if it
+/// squares back to a. Returns `None` for quadratic non-residues.
+pub fn fq_sqrt(a: U256) -> Option<U256> {
+    let exp = (BN254_BASE_MODULUS + U256::from(1)) >> 2;
+    let root = fq_pow(a, exp);
+    (fq_mul(root, root) == a % BN254_BASE_MODULUS).then_some(root)
+}
+
+// =========================================================================
+// MONTGOMERY Fr
+// =========================================================================
+
+/// r, little-endian limbs
+const FR_MODULUS: [u64; 4] = [
+    0x43e1f593f0000001,
+    0x2833e84879b97091,
+    0xb85045b68181585d,
+    0x30644e72e131a029,
+];
+
+/// -r^(-1) mod 2^64
+const FR_INV: u64 = 0xc2e1f593efffffff;
+
+/// R mod r, R = 2^256 (Montgomery form of 1)
+const FR_R: [u64; 4] = [
+    0xac96341c4ffffffb,
+    0x36fc76959f60cd29,
+    0x666ea36f7879462e,
+    0x0e0a77c19a07df2f,
+];
+
+/// R² mod r
+const FR_R2: [u64; 4] = [
+    0x1bb8e645ae216da7,
+    0x53fe3ab1e35c59e3,
+    0x8c49833d53bb8085,
+    0x0216d0b17f4e44a5,
+];
+
+/// Scalar field element in Montgomery form (a·R mod r)
+///
+/// Limbs are always below r. `add`, `sub` and `mul` reduce with a masked
+/// subtraction rather than a branch, so their running time does not depend
+/// on the operands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Fr([u64; 4]);
+
+// Limb loops index several arrays in lockstep
+#[allow(clippy::needless_range_loop)]
+impl Fr {
+    pub const ZERO: Fr = Fr([0; 4]);
+    pub const ONE: Fr = Fr(FR_R);
+
+    /// Convert a canonical integer (< r) into Montgomery form
+    pub fn from_u256(value: U256) -> Option<Self> {
+        let limbs = *value.as_limbs();
+        if !below(&limbs, &FR_MODULUS) {
+            return None;
+        }
+        Some(Fr(limbs).mul(&Fr(FR_R2)))
+    }
+
+    /// Convert any integer, reducing it modulo r first
+    pub fn from_u256_reduced(value: U256) -> Self {
+        Self::reduce(value).mul(&Fr(FR_R2))
+    }
+
+    /// Decode the 32-byte big-endian encoding; `None` unless canonical
+    pub fn from_be_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        Self::from_u256(U256::from_be_bytes(*bytes))
+    }
+
+    pub fn to_u256(self) -> U256 {
+        U256::from_limbs(self.mul(&Fr([1, 0, 0, 0])).0)
+    }
+
+    pub fn to_be_bytes(self) -> [u8; 32] {
+        self.to_u256().to_be_bytes::<32>()
+    }
+
+    /// `value mod r` as raw limbs, not yet in Montgomery form
+    fn reduce(value: U256) -> Self {
+        let value = if value >= BN254_SCALAR_MODULUS { value % BN254_SCALAR_MODULUS } else { value };
+        Fr(*value.as_limbs())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0; 4]
+    }
+
+    /// r < 2^254, so a + b never overflows 256 bits
+    pub fn add(&self, other: &Self) -> Self {
+        let mut out = [0u64; 4];
+        let mut carry = 0;
+        for i in 0..4 {
+            (out[i], carry) = adc(self.0[i], other.0[i], carry);
+        }
+        Fr(subtract_modulus_if_above(out))
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        let mut out = [0u64; 4];
+        let mut borrow = 0;
+        for i in 0..4 {
+            (out[i], borrow) = sbb(self.0[i], other.0[i], borrow);
+        }
+        // Add r back when the subtraction wrapped
+        let mask = 0u64.wrapping_sub(borrow);
+        let mut carry = 0;
+        for i in 0..4 {
+            (out[i], carry) = adc(out[i], FR_MODULUS[i] & mask, carry);
+        }
+        Fr(out)
+    }
+
+    pub fn neg(&self) -> Self {
+        Fr::ZERO.sub(self)
+    }
+
+    pub fn double(&self) -> Self {
+        self.add(self)
+    }
+
+    /// CIOS Montgomery multiplication: a·b·R^(-1) mod r
+    pub fn mul(&self, other: &Self) -> Self {
+        let mut t = [0u64; 6];
+        for i in 0..4 {
+            let mut carry = 0;
+            for j in 0..4 {
+                (t[j], carry) = mac(t[j], self.0[i], other.0[j], carry);
+            }
+            let (sum, overflow) = adc(t[4], carry, 0);
+            t[4] = sum;
+            t[5] = overflow;
+
+            let m = t[0].wrapping_mul(FR_INV);
+            let (_, mut carry) = mac(t[0], m, FR_MODULUS[0], 0);
+            for j in 1..4 {
+                (t[j - 1], carry) = mac(t[j], m, FR_MODULUS[j], carry);
+            }
+            let (sum, overflow) = adc(t[4], carry, 0);
+            t[3] = sum;
+            t[4] = t[5] + overflow;
+        }
+        // Inputs below r < R/4 keep the result below 2r, so t[4] is zero
+        Fr(subtract_modulus_if_above([t[0], t[1], t[2], t[3]]))
+    }
+
+    pub fn square(&self) -> Self {
+        self.mul(self)
+    }
+
+    /// Square-and-multiply over the bits of `exp` (variable time in `exp`)
+    pub fn pow(&self, exp: U256) -> Self {
+        let mut result = Fr::ONE;
+        for i in (0..exp.bit_len()).rev() {
+            result = result.square();
+            if exp.bit(i) {
+                result = result.mul(self);
+            }
+        }
+        result
+    }
+
+    /// a^(r-2) (Fermat); `None` for zero
+    ///
+    /// The exponent is walked in fixed 4-bit windows with a 16-entry table:
+    /// 14 multiplications for the table, then 252 squarings and 63
+    /// multiplications. Which operations run depends only on r, never on `a`.
+    pub fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        let mut table = [Fr::ONE; 16];
+        table[1] = *self;
+        for i in 2..16 {
+            table[i] = table[i - 1].mul(self);
+        }
+
+        // r - 2, most significant window first
+        let mut exp = FR_MODULUS;
+        exp[0] -= 2;
+        let digit = |window: usize| (exp[window / 16] >> ((window % 16) * 4)) as usize & 0xf;
+        let mut result = table[digit(63)];
+        for window in (0..63).rev() {
+            result = result.square().square().square().square();
+            result = result.mul(&table[digit(window)]);
+        }
+        Some(result)
+    }
+}
+
+/// Invert every non-zero value in place with one field inversion
+///
+/// Montgomery's trick: prefix products forward, one inverse of the total,
+/// then each inverse peeled off backwards for 3 multiplications per value.
+/// Zeros are skipped and stay zero.
+pub fn batch_inverse(values: &mut [Fr]) {
+    let mut prefix = Vec::with_capacity(values.len());
+    let mut acc = Fr::ONE;
+    for value in values.iter() {
+        prefix.push(acc);
+        if !value.is_zero() {
+            acc = acc.mul(value);
+        }
+    }
+
+    // A product of non-zero field elements is non-zero
+    let Some(mut inv) = acc.inverse() else { return };
+    for (value, before) in values.iter_mut().zip(prefix).rev() {
+        if value.is_zero() {
+            continue;
+        }
+        let next = inv.mul(value);
+        *value = inv.mul(&before);
+        inv = next;
+    }
+}
+
+/// a + b + carry -> (sum, carry)
+#[inline(always)]
+pub(crate) fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+/// a - b - borrow -> (difference, borrow)
+#[inline(always)]
+pub(crate) fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_sub(b as u128 + borrow as u128);
+    (t as u64, (t >> 127) as u64)
+}
+
+/// a + b·c + carry -> (low, high)
+#[inline(always)]
+pub(crate) fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + (b as u128) * (c as u128) + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+/// `limbs < modulus`, both little-endian
+pub(crate) fn below(limbs: &[u64; 4], modulus: &[u64; 4]) -> bool {
+    for i in (0..4).rev() {
+        if limbs[i] != modulus[i] {
+            return limbs[i] < modulus[i];
+        }
+    }
+    false
+}
+
+/// `limbs - r` if that does not borrow, else `limbs`, without branching
+#[allow(clippy::needless_range_loop)]
+fn subtract_modulus_if_above(limbs: [u64; 4]) -> [u64; 4] {
+    let mut reduced = [0u64; 4];
+    let mut borrow = 0;
+    for i in 0..4 {
+        (reduced[i], borrow) = sbb(limbs[i], FR_MODULUS[i], borrow);
+    }
+    // borrow = 1 keeps the input
+    let keep = 0u64.wrapping_sub(borrow);
+    let mut out = [0u64; 4];
+    for i in 0..4 {
+        out[i] = (limbs[i] & keep) | (reduced[i] & !keep);
+    }
+    out
+}
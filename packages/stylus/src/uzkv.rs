@@ -3,13 +3,18 @@
 
 use alloc::vec::Vec;
 use crate::groth16;
-use crate::cost_model::VerificationCost;
+use crate::types::{CurveId, HashFunctionId};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ProofSystem {
     Groth16 = 0,
     Plonk = 1,
     Stark = 2,
+    Groth16Gnark = 3,
+    PlonkSnarkjs = 4,
+    Fflonk = 5,
+    Halo2 = 6,
+    UltraPlonk = 7,
 }
 
 impl ProofSystem {
@@ -18,9 +23,44 @@ impl ProofSystem {
             0 => Some(ProofSystem::Groth16),
             1 => Some(ProofSystem::Plonk),
             2 => Some(ProofSystem::Stark),
+            3 => Some(ProofSystem::Groth16Gnark),
+            4 => Some(ProofSystem::PlonkSnarkjs),
+            5 => Some(ProofSystem::Fflonk),
+            6 => Some(ProofSystem::Halo2),
+            7 => Some(ProofSystem::UltraPlonk),
             _ => None,
         }
     }
+
+    /// Whether this verifier can check proofs over `curve`
+    ///
+    /// STARKs are hash-based and accept any curve ID.
+    pub fn supports_curve(self, curve: CurveId) -> bool {
+        match self {
+            ProofSystem::Groth16 => matches!(curve, CurveId::BN254 | CurveId::BLS12_381),
+            ProofSystem::Plonk
+            | ProofSystem::Groth16Gnark
+            | ProofSystem::PlonkSnarkjs
+            | ProofSystem::Fflonk
+            | ProofSystem::Halo2
+            | ProofSystem::UltraPlonk => curve == CurveId::BN254,
+            ProofSystem::Stark => true,
+        }
+    }
+
+    /// Whether this verifier can check proofs whose transcript uses `hash`
+    ///
+    /// Groth16 has no Fiat-Shamir transcript (gnark's commitment hash is fixed),
+    /// so the descriptor's hash is not used. snarkjs (PLONK and fflonk) always
+    /// hashes with Keccak256; halo2 transcripts are Blake2b or Keccak256.
+    pub fn supports_hash(self, hash: HashFunctionId) -> bool {
+        match self {
+            ProofSystem::Groth16 | ProofSystem::Groth16Gnark => true,
+            ProofSystem::Plonk | ProofSystem::UltraPlonk | ProofSystem::Stark => crate::hash::is_supported(hash),
+            ProofSystem::PlonkSnarkjs | ProofSystem::Fflonk => hash == HashFunctionId::Keccak256,
+            ProofSystem::Halo2 => matches!(hash, HashFunctionId::Blake2b | HashFunctionId::Keccak256),
+        }
+    }
 }
 
 // =========================================================================
@@ -30,31 +70,91 @@ impl ProofSystem {
 #[cfg(not(feature = "std"))]
 pub mod stylus_impl {
     use super::*;
+    use crate::backend::PrecompileBackend;
+    use crate::cost_model::VerificationCost;
+    use crate::Error;
     use stylus_sdk::call::StaticCallContext;
 
-    /// Universal proof verification dispatcher (Stylus)
+    /// Universal proof verification dispatcher (Stylus, BN254)
     pub fn verify_universal_proof<S: StaticCallContext + Copy>(
         context: S,
         proof_system: u8,
         proof: &[u8],
         public_inputs: &[u8],
         vk: &[u8],
-    ) -> Result<bool, Vec<u8>> {
-        let system = ProofSystem::from_u8(proof_system)
-            .ok_or_else(|| b"Invalid proof system".to_vec())?;
+    ) -> Result<bool, Error> {
+        verify_universal_proof_on_curve(context, proof_system, CurveId::BN254, proof, public_inputs, vk)
+    }
+
+    /// Universal proof verification dispatcher (Stylus), routed by curve
+    pub fn verify_universal_proof_on_curve<S: StaticCallContext + Copy>(
+        context: S,
+        proof_system: u8,
+        curve: CurveId,
+        proof: &[u8],
+        public_inputs: &[u8],
+        vk: &[u8],
+    ) -> Result<bool, Error> {
+        verify_universal_proof_with_hash(
+            context, proof_system, curve, HashFunctionId::Keccak256, proof, public_inputs, vk,
+        )
+    }
+
+    /// Universal proof verification dispatcher (Stylus), routed by curve and
+    /// transcript hash
+    ///
+    /// Groth16 failures keep their `groth16::Error` (non-canonical input,
+    /// off-curve point, ...); the other systems report `VerificationFailed`.
+    pub fn verify_universal_proof_with_hash<S: StaticCallContext + Copy>(
+        context: S,
+        proof_system: u8,
+        curve: CurveId,
+        hash: HashFunctionId,
+        proof: &[u8],
+        public_inputs: &[u8],
+        vk: &[u8],
+    ) -> Result<bool, Error> {
+        let system = ProofSystem::from_u8(proof_system).ok_or(Error::InvalidProofType)?;
+        if !system.supports_curve(curve) {
+            return Err(Error::UnsupportedCurve);
+        }
+        if !system.supports_hash(hash) {
+            return Err(Error::UnsupportedHashFunction);
+        }
         
         match system {
+            ProofSystem::Groth16 if curve == CurveId::BLS12_381 => {
+                Ok(groth16::bls12_381::verify(context, proof, public_inputs, vk)?)
+            }
             ProofSystem::Groth16 => {
-                groth16::verify(context, proof, public_inputs, vk)
-                    .map_err(|_| b"Groth16 verification failed".to_vec())
+                Ok(groth16::verify(context, proof, public_inputs, vk)?)
+            }
+            ProofSystem::Groth16Gnark => {
+                Ok(groth16::gnark::verify(context, proof, public_inputs, vk)?)
             }
             ProofSystem::Plonk => {
-                crate::plonk::verify(context, proof, public_inputs, vk)
-                    .map_err(|_| b"Plonk verification failed".to_vec())
+                crate::plonk::verify_with_hash(&PrecompileBackend::new(context), hash, proof, public_inputs, vk)
+                    .map_err(|_| Error::VerificationFailed)
+            }
+            ProofSystem::PlonkSnarkjs => {
+                crate::plonk::snarkjs::verify(&PrecompileBackend::new(context), proof, public_inputs, vk)
+                    .map_err(|_| Error::VerificationFailed)
+            }
+            ProofSystem::Fflonk => {
+                crate::plonk::fflonk::verify(&PrecompileBackend::new(context), proof, public_inputs, vk)
+                    .map_err(|_| Error::VerificationFailed)
+            }
+            ProofSystem::Halo2 => {
+                crate::halo2::verify(&PrecompileBackend::new(context), hash, proof, public_inputs, vk)
+                    .map_err(|_| Error::VerificationFailed)
+            }
+            ProofSystem::UltraPlonk => {
+                crate::plonk::ultra::verify_with_hash(&PrecompileBackend::new(context), hash, proof, public_inputs, vk)
+                    .map_err(|_| Error::VerificationFailed)
             }
             ProofSystem::Stark => {
-                crate::stark::verify_proof(proof, public_inputs, vk)
-                    .map_err(|_| b"STARK verification failed".to_vec())
+                crate::stark::verify_proof_with_hash(hash, proof, public_inputs, vk)
+                    .map_err(|_| Error::VerificationFailed)
             }
         }
     }
@@ -63,26 +163,52 @@ pub mod stylus_impl {
     pub fn verify_universal_proof_with_budget<S: StaticCallContext + Copy>(
         context: S,
         proof_system: u8,
+        curve: CurveId,
+        hash: HashFunctionId,
         proof: &[u8],
         public_inputs: &[u8],
         vk: &[u8],
         gas_budget: u64,
-    ) -> Result<bool, Vec<u8>> {
-        let system = ProofSystem::from_u8(proof_system)
-            .ok_or_else(|| b"Invalid proof system".to_vec())?;
+    ) -> Result<bool, Error> {
+        let system = ProofSystem::from_u8(proof_system).ok_or(Error::InvalidProofType)?;
             
         // Estimate cost
         let cost = match system {
             ProofSystem::Groth16 => VerificationCost::for_groth16(public_inputs.len() / 32),
+            ProofSystem::Groth16Gnark => VerificationCost::for_groth16_gnark(public_inputs.len() / 32),
             ProofSystem::Plonk => VerificationCost::for_plonk(public_inputs.len() / 32, 65536),
-            ProofSystem::Stark => VerificationCost::for_stark(1024, 128),
+            ProofSystem::PlonkSnarkjs => VerificationCost::for_plonk_snarkjs(public_inputs.len() / 32, 65536),
+            ProofSystem::Fflonk => VerificationCost::for_fflonk(public_inputs.len() / 32),
+            ProofSystem::Halo2 => VerificationCost::for_halo2(public_inputs.len() / 32, proof.len()),
+            ProofSystem::UltraPlonk => VerificationCost::for_ultra_plonk(public_inputs.len() / 32, proof.len()),
+            ProofSystem::Stark => {
+                let stark_vk = crate::stark::StarkVerificationKey::from_bytes(vk).map_err(|_| Error::VerificationFailed)?;
+                VerificationCost::for_stark_vk(&stark_vk, proof.len())
+            }
         };
         
         if cost.estimated_total > gas_budget {
-            return Err(b"Gas budget exceeded".to_vec());
+            return Err(Error::VerificationFailed);
         }
         
-        verify_universal_proof(context, proof_system, proof, public_inputs, vk)
+        verify_universal_proof_with_hash(context, proof_system, curve, hash, proof, public_inputs, vk)
+    }
+
+    /// BN254 Groth16 with gas budget check, through the prepared VK cached at
+    /// registration (see `groth16::verify_with_precomputed`)
+    pub fn verify_groth16_prepared_with_budget<S: StaticCallContext + Copy>(
+        context: S,
+        proof: &[u8],
+        public_inputs: &[u8],
+        vk: &[u8],
+        precomputed: &[u8],
+        gas_budget: u64,
+    ) -> Result<bool, Error> {
+        if VerificationCost::for_groth16(public_inputs.len() / 32).estimated_total > gas_budget {
+            return Err(Error::VerificationFailed);
+        }
+
+        Ok(groth16::verify_with_precomputed(context, proof, public_inputs, vk, precomputed)?)
     }
 
     /// Batch verification (Stylus)
@@ -92,11 +218,11 @@ pub mod stylus_impl {
         proofs: &[Vec<u8>],
         public_inputs: &[Vec<u8>],
         vks: &[Vec<u8>],
-    ) -> Result<Vec<bool>, Vec<u8>> {
+    ) -> Result<Vec<bool>, Error> {
         if proof_systems.len() != proofs.len() 
             || proofs.len() != public_inputs.len() 
             || public_inputs.len() != vks.len() {
-            return Err(b"Array length mismatch".to_vec());
+            return Err(Error::InvalidInputSize);
         }
         
         let mut results = Vec::new();
@@ -128,29 +254,85 @@ pub use stylus_impl::*;
 pub mod host_impl {
     use super::*;
 
-    /// Universal proof verification dispatcher (Host)
+    /// Universal proof verification dispatcher (Host, BN254)
     /// Note: No StaticCallContext required
     pub fn verify_offchain(
         proof_system: u8,
         proof: &[u8],
         public_inputs: &[u8],
         vk: &[u8],
+    ) -> Result<bool, Vec<u8>> {
+        verify_offchain_on_curve(proof_system, CurveId::BN254, proof, public_inputs, vk)
+    }
+
+    /// Universal proof verification dispatcher (Host), routed by curve
+    pub fn verify_offchain_on_curve(
+        proof_system: u8,
+        curve: CurveId,
+        proof: &[u8],
+        public_inputs: &[u8],
+        vk: &[u8],
+    ) -> Result<bool, Vec<u8>> {
+        verify_offchain_with_hash(proof_system, curve, HashFunctionId::Keccak256, proof, public_inputs, vk)
+    }
+
+    /// Universal proof verification dispatcher (Host), routed by curve and
+    /// transcript hash
+    ///
+    /// Groth16 failures carry the message of their `groth16::Error`.
+    pub fn verify_offchain_with_hash(
+        proof_system: u8,
+        curve: CurveId,
+        hash: HashFunctionId,
+        proof: &[u8],
+        public_inputs: &[u8],
+        vk: &[u8],
     ) -> Result<bool, Vec<u8>> {
         let system = ProofSystem::from_u8(proof_system)
             .ok_or_else(|| b"Invalid proof system".to_vec())?;
+        if !system.supports_curve(curve) {
+            return Err(b"Unsupported curve".to_vec());
+        }
+        if !system.supports_hash(hash) {
+            return Err(b"Unsupported hash function".to_vec());
+        }
         
         match system {
+            ProofSystem::Groth16 if curve == CurveId::BLS12_381 => {
+                groth16::bls12_381::verify_host(proof, public_inputs, vk)
+                    .map_err(|e| crate::Error::from(e).into())
+            }
             ProofSystem::Groth16 => {
                 groth16::verify_host(proof, public_inputs, vk)
-                    .map_err(|_| b"Groth16 verification failed".to_vec())
+                    .map_err(|e| crate::Error::from(e).into())
+            }
+            ProofSystem::Groth16Gnark => {
+                groth16::gnark::verify_host(proof, public_inputs, vk)
+                    .map_err(|e| crate::Error::from(e).into())
             }
             ProofSystem::Plonk => {
-                crate::plonk::verify_host(proof, public_inputs, vk)
+                crate::plonk::verify_host_with_hash(hash, proof, public_inputs, vk)
+                    .map_err(|e| e.into_bytes())
+            }
+            ProofSystem::PlonkSnarkjs => {
+                crate::plonk::verify_snarkjs_host(proof, public_inputs, vk)
+                    .map_err(|e| e.into_bytes())
+            }
+            ProofSystem::Fflonk => {
+                crate::plonk::verify_fflonk_host(proof, public_inputs, vk)
                     .map_err(|e| e.into_bytes())
             }
+            ProofSystem::Halo2 => {
+                crate::halo2::verify(&crate::backend::ArkBackend, hash, proof, public_inputs, vk)
+                    .map_err(|_| b"halo2 verification failed".to_vec())
+            }
+            ProofSystem::UltraPlonk => {
+                crate::plonk::ultra::verify_with_hash(&crate::backend::ArkBackend, hash, proof, public_inputs, vk)
+                    .map_err(|_| b"UltraPlonk verification failed".to_vec())
+            }
 
             ProofSystem::Stark => {
-                crate::stark::verify_proof(proof, public_inputs, vk)
+                crate::stark::verify_proof_with_hash(hash, proof, public_inputs, vk)
                     .map_err(|_| b"STARK verification failed".to_vec())
             }
         }
@@ -169,7 +351,47 @@ mod tests {
         assert_eq!(ProofSystem::from_u8(0), Some(ProofSystem::Groth16));
         assert_eq!(ProofSystem::from_u8(1), Some(ProofSystem::Plonk));
         assert_eq!(ProofSystem::from_u8(2), Some(ProofSystem::Stark));
-        assert_eq!(ProofSystem::from_u8(3), None);
+        assert_eq!(ProofSystem::from_u8(3), Some(ProofSystem::Groth16Gnark));
+        assert_eq!(ProofSystem::from_u8(4), Some(ProofSystem::PlonkSnarkjs));
+        assert_eq!(ProofSystem::from_u8(5), Some(ProofSystem::Fflonk));
+        assert_eq!(ProofSystem::from_u8(6), Some(ProofSystem::Halo2));
+        assert_eq!(ProofSystem::from_u8(7), Some(ProofSystem::UltraPlonk));
+        assert_eq!(ProofSystem::from_u8(8), None);
+    }
+
+    #[test]
+    fn test_curve_support() {
+        assert!(ProofSystem::Groth16.supports_curve(CurveId::BN254));
+        assert!(ProofSystem::Groth16.supports_curve(CurveId::BLS12_381));
+        assert!(!ProofSystem::Groth16.supports_curve(CurveId::Pasta));
+        assert!(!ProofSystem::Plonk.supports_curve(CurveId::BLS12_381));
+        assert!(!ProofSystem::Groth16Gnark.supports_curve(CurveId::BLS12_381));
+        assert!(!ProofSystem::PlonkSnarkjs.supports_curve(CurveId::BLS12_381));
+        assert!(!ProofSystem::Fflonk.supports_curve(CurveId::BLS12_381));
+        assert!(!ProofSystem::Halo2.supports_curve(CurveId::Pasta));
+        assert!(!ProofSystem::UltraPlonk.supports_curve(CurveId::BLS12_381));
+        assert!(ProofSystem::Stark.supports_curve(CurveId::None));
+    }
+
+    #[test]
+    fn test_hash_support() {
+        for hash in [HashFunctionId::Keccak256, HashFunctionId::SHA256, HashFunctionId::Blake3, HashFunctionId::Poseidon] {
+            assert!(ProofSystem::Plonk.supports_hash(hash));
+            assert!(ProofSystem::UltraPlonk.supports_hash(hash));
+            assert!(ProofSystem::Stark.supports_hash(hash));
+        }
+        assert!(!ProofSystem::Plonk.supports_hash(HashFunctionId::RescuePrime));
+        assert!(!ProofSystem::Stark.supports_hash(HashFunctionId::RescuePrime));
+        assert!(ProofSystem::PlonkSnarkjs.supports_hash(HashFunctionId::Keccak256));
+        assert!(!ProofSystem::PlonkSnarkjs.supports_hash(HashFunctionId::Poseidon));
+        assert!(ProofSystem::Fflonk.supports_hash(HashFunctionId::Keccak256));
+        assert!(!ProofSystem::Fflonk.supports_hash(HashFunctionId::SHA256));
+        assert!(ProofSystem::Halo2.supports_hash(HashFunctionId::Blake2b));
+        assert!(ProofSystem::Halo2.supports_hash(HashFunctionId::Keccak256));
+        assert!(!ProofSystem::Halo2.supports_hash(HashFunctionId::Poseidon));
+        assert!(!ProofSystem::Plonk.supports_hash(HashFunctionId::Blake2b));
+        // No transcript: the descriptor default (Poseidon) is accepted
+        assert!(ProofSystem::Groth16.supports_hash(HashFunctionId::Poseidon));
     }
 }
 
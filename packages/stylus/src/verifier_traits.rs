@@ -294,6 +294,17 @@ impl GasCost {
         }
     }
     
+    /// gnark Groth16 gas cost model (BN254, one BSB22 commitment)
+    /// - Base: ~320k gas (4 Pairings + 2-Pair Knowledge Check: 294k + SHA-256 + Overhead)
+    /// - Per input: ~6.5k gas (as Groth16)
+    pub const fn groth16_gnark() -> Self {
+        Self {
+            base: 320_000,
+            per_public_input: 6_500,
+            per_proof_byte: 0, // Proof grows only with the commitment count
+        }
+    }
+    
     /// PLONK gas cost model (KZG on BN254)
     /// - Base: ~180k gas (2 Pairings: 113k + Batching MSMs + Scalar Logic)
     /// - Per input: ~500 gas (Scalar Ops + Keccak, no Curve Ops)
@@ -305,6 +316,41 @@ impl GasCost {
         }
     }
     
+    /// fflonk gas cost model (KZG on BN254)
+    /// - Base: ~150k gas (2 Pairings: 113k + 5 G1 Muls + Scalar Logic)
+    /// - Per input: ~500 gas (Lagrange basis, no Curve Ops)
+    pub const fn fflonk() -> Self {
+        Self {
+            base: 150_000,
+            per_public_input: 500,
+            per_proof_byte: 0, // Fixed-size proof
+        }
+    }
+    
+    /// halo2 gas cost model (KZG/SHPLONK on BN254)
+    /// - Base: ~300k gas (2 Pairings: 113k + one G1 Mul per opened commitment)
+    /// - Per input: ~1.5k gas (Lagrange basis per instance query)
+    /// - Per byte: ~16 gas (every commitment and evaluation is hashed and opened)
+    pub const fn halo2() -> Self {
+        Self {
+            base: 300_000,
+            per_public_input: 1_500,
+            per_proof_byte: 16,
+        }
+    }
+    
+    /// UltraPLONK gas cost model (KZG on BN254)
+    /// - Base: ~220k gas (2 Pairings: 113k + one G1 Mul per committed polynomial)
+    /// - Per input: ~500 gas (as PLONK)
+    /// - Per byte: ~12 gas (the proof grows with the wires, selectors and table)
+    pub const fn ultra_plonk() -> Self {
+        Self {
+            base: 220_000,
+            per_public_input: 500,
+            per_proof_byte: 12,
+        }
+    }
+    
     /// STARK gas cost model (FRI-based)
     /// - Base: ~200k gas (hash operations)
     /// - Per byte: ~10 gas (FRI layers)
@@ -424,7 +470,7 @@ mod tests {
         let groth16 = GasCost::groth16();
         // 2 public inputs, 256 byte proof
         let estimated = groth16.estimate(2, 256);
-        assert_eq!(estimated, 200_000 + 2 * 6_500 + 0); // 213k gas
+        assert_eq!(estimated, 200_000 + 2 * 6_500); // 213k gas
     }
 
     #[test]
@@ -0,0 +1,262 @@
+//! fflonk test prover
+//!
+//! Runs the `plonk` test circuit through fflonk: the PLONK polynomials are
+//! packed into `C0`, `C1` and `C2` and all openings go through one KZG proof.
+//! As in `plonk`, commitments and openings are evaluated at the trapdoor τ. The
+//! opened values come from the prover's own polynomials, so an unsatisfied
+//! witness or a tampered evaluation still fails verification.
+
+use ark_bn254::{Fr, G2Affine, G2Projective};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{FftField, Field, One, Zero};
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::groth16::codec;
+use uzkv_stylus::plonk::fflonk::{
+    compute_challenges, compute_inverse_hint, serialize_fflonk_proof, serialize_fflonk_vk,
+    FflonkEvaluations, FflonkProof, FflonkVerificationKey, Roots,
+};
+
+use super::plonk::{
+    fr_to_u256, g1, poly_add, poly_add_const, poly_div_vanishing, poly_eval, poly_mul, poly_scale,
+    u256_to_fr, PlonkFixture, Witness,
+};
+
+/// `C(x) = Σⱼ xʲ·pⱼ(xᵏ)` for `k = polys.len()`
+fn pack(polys: &[&[Fr]], x: Fr) -> Fr {
+    let xk = x.pow([polys.len() as u64]);
+    let values: Vec<Fr> = polys.iter().map(|p| poly_eval(p, xk)).collect();
+    horner(&values, x)
+}
+
+/// `Σⱼ xʲ·values[j]`
+fn horner(values: &[Fr], x: Fr) -> Fr {
+    values.iter().rev().fold(Fr::zero(), |acc, v| acc * x + v)
+}
+
+/// Z_S(x) = Π (x − hᵢ)
+pub(super) fn vanishing(points: &[Fr], x: Fr) -> Fr {
+    points.iter().map(|h| x - h).product()
+}
+
+/// Value at `x` of the polynomial through `(points[i], values[i])`
+pub(super) fn lagrange(points: &[Fr], values: &[Fr], x: Fr) -> Fr {
+    points
+        .iter()
+        .zip(values)
+        .enumerate()
+        .map(|(i, (hi, vi))| {
+            let (num, den) = points.iter().enumerate().filter(|(j, _)| *j != i).fold(
+                (Fr::one(), Fr::one()),
+                |(num, den), (_, hj)| (num * (x - hj), den * (*hi - hj)),
+            );
+            *vi * num * den.inverse().unwrap()
+        })
+        .sum()
+}
+
+fn to_fr(values: &[U256]) -> Vec<Fr> {
+    values.iter().map(u256_to_fr).collect()
+}
+
+/// The `plonk` cubic circuit with the extra roots of unity fflonk needs
+pub struct FflonkFixture {
+    pub plonk: PlonkFixture,
+    omega3: Fr,
+    omega4: Fr,
+    omega8: Fr,
+    omega_r: Fr,
+}
+
+impl FflonkFixture {
+    /// `x³ + x + 5 = y` with public `y`, on 8 rows
+    pub fn cubic(tau: Fr) -> Self {
+        let plonk = PlonkFixture::cubic(tau);
+        let two_inv = Fr::from(2u64).inverse().unwrap();
+        let omega3 = (-Fr::one() + (-Fr::from(3u64)).sqrt().unwrap()) * two_inv;
+        // n = 8: (ω³)³ = ω⁹ = ω
+        let omega_r = plonk.omega.pow([3]);
+
+        Self {
+            plonk,
+            omega3,
+            omega4: Fr::get_root_of_unity(4).unwrap(),
+            omega8: Fr::get_root_of_unity(8).unwrap(),
+            omega_r,
+        }
+    }
+
+    pub fn witness(&self, x: Fr) -> Witness {
+        self.plonk.witness(x)
+    }
+
+    /// q_L, q_R, q_O, q_M, q_C, S1, S2, S3 in `C0` order
+    fn circuit_polys(&self) -> Vec<Vec<Fr>> {
+        let p = &self.plonk;
+        p.selectors.iter().chain(&p.sigmas).map(|e| p.interpolate(e)).collect()
+    }
+
+    pub fn verifying_key(&self) -> FflonkVerificationKey {
+        let p = &self.plonk;
+        let polys = self.circuit_polys();
+        let refs: Vec<&[Fr]> = polys.iter().map(Vec::as_slice).collect();
+        FflonkVerificationKey {
+            n: p.n,
+            num_public_inputs: p.num_public,
+            c0: g1(pack(&refs, p.tau)),
+            k1: fr_to_u256(&p.k1),
+            k2: fr_to_u256(&p.k2),
+            omega: fr_to_u256(&p.omega),
+            omega3: fr_to_u256(&self.omega3),
+            omega4: fr_to_u256(&self.omega4),
+            omega8: fr_to_u256(&self.omega8),
+            omega_r: fr_to_u256(&self.omega_r),
+        }
+    }
+
+    /// VK bytes followed by `[τ]₂`, as `fflonk::verify` expects
+    pub fn vk_bytes(&self) -> Vec<u8> {
+        let mut out = serialize_fflonk_vk(&self.verifying_key());
+        let srs = (G2Projective::from(G2Affine::generator()) * self.plonk.tau).into_affine();
+        out.extend_from_slice(&codec::g2_from_ark(&srs));
+        out
+    }
+
+    pub fn public_bytes(&self, witness: &Witness) -> Vec<u8> {
+        self.plonk.public_bytes(witness)
+    }
+
+    /// Run the prover; an unsatisfied witness still yields a (bogus) proof
+    pub fn prove(&self, witness: &Witness) -> FflonkProof {
+        let p = &self.plonk;
+        let (n, tau) = (p.n, p.tau);
+        let vk = self.verifying_key();
+        let inputs: Vec<U256> = witness.public.iter().map(fr_to_u256).collect();
+        let mut proof = FflonkProof {
+            c1: [0u8; 64],
+            c2: [0u8; 64],
+            w1: [0u8; 64],
+            w2: [0u8; 64],
+            evals: FflonkEvaluations::default(),
+        };
+
+        // Round 1: wires and the gate quotient T0
+        let circuit = self.circuit_polys();
+        let [ql, qr, qo, qm, qc, s1, s2, s3] = &circuit[..] else { unreachable!() };
+        let [a, b, c] = &witness.wires.clone().map(|w| p.interpolate(&w));
+        let mut pi_evals = vec![Fr::zero(); n];
+        for (i, x) in witness.public.iter().enumerate() {
+            pi_evals[i] = -*x;
+        }
+        let gate = [
+            poly_mul(&poly_mul(a, b), qm),
+            poly_mul(a, ql),
+            poly_mul(b, qr),
+            poly_mul(c, qo),
+            qc.clone(),
+            p.interpolate(&pi_evals),
+        ]
+        .iter()
+        .fold(vec![Fr::zero()], |acc, q| poly_add(&acc, q));
+        let t0 = poly_div_vanishing(&gate, n);
+        proof.c1 = g1(pack(&[a, b, c, &t0], tau));
+        let ch = compute_challenges(&proof, &vk, &inputs);
+        let (beta, gamma) = (u256_to_fr(&ch.beta), u256_to_fr(&ch.gamma));
+
+        // Round 2: grand product z(ω⁰) = 1 and the quotients T1, T2
+        let domain = p.domain();
+        let ks = [Fr::one(), p.k1, p.k2];
+        let mut z_evals = vec![Fr::one(); n];
+        for i in 0..n - 1 {
+            let mut num = Fr::one();
+            let mut den = Fr::one();
+            for (col, k) in ks.iter().enumerate() {
+                let w = witness.wires[col][i];
+                num *= w + beta * k * domain[i] + gamma;
+                den *= w + beta * p.sigmas[col][i] + gamma;
+            }
+            z_evals[i + 1] = z_evals[i] * num * den.inverse().unwrap();
+        }
+        let z = p.interpolate(&z_evals);
+        let mut l1_evals = vec![Fr::zero(); n];
+        l1_evals[0] = Fr::one();
+        let l1 = p.interpolate(&l1_evals);
+        let t1 = poly_div_vanishing(&poly_mul(&poly_add_const(&z, -Fr::one()), &l1), n);
+
+        let id_factor = |w: &[Fr], k: Fr| poly_add(&poly_add_const(w, gamma), &[Fr::zero(), beta * k]);
+        let sigma_factor = |w: &[Fr], s: &[Fr]| poly_add(&poly_add_const(w, gamma), &poly_scale(s, beta));
+        let z_shifted: Vec<Fr> = z.iter().enumerate().map(|(i, c)| *c * p.omega.pow([i as u64])).collect();
+        let perm_num = poly_mul(
+            &poly_mul(&poly_mul(&id_factor(a, ks[0]), &id_factor(b, ks[1])), &id_factor(c, ks[2])),
+            &z,
+        );
+        let perm_den = poly_mul(
+            &poly_mul(&poly_mul(&sigma_factor(a, s1), &sigma_factor(b, s2)), &sigma_factor(c, s3)),
+            &z_shifted,
+        );
+        let t2 = poly_div_vanishing(&poly_add(&perm_num, &poly_scale(&perm_den, -Fr::one())), n);
+        proof.c2 = g1(pack(&[&z, &t1, &t2], tau));
+        let xi_seed = compute_challenges(&proof, &vk, &inputs).xi_seed;
+
+        // Round 3: evaluations at ξ and ξω
+        let roots = Roots::new(xi_seed, &vk);
+        let xi = u256_to_fr(&roots.xi);
+        let xi_omega = xi * p.omega;
+        let at = |q: &[Fr]| poly_eval(q, xi);
+        let c0_vals: Vec<Fr> = circuit.iter().map(|q| at(q)).collect();
+        let c1_vals = [at(a), at(b), at(c), at(&t0)];
+        let c2_vals = [at(&z), at(&t1), at(&t2)];
+        let c2w_vals = [poly_eval(&z, xi_omega), poly_eval(&t1, xi_omega), poly_eval(&t2, xi_omega)];
+        let mut evals = c0_vals.clone();
+        evals.extend_from_slice(&c1_vals[..3]);
+        evals.push(c2_vals[0]);
+        evals.extend_from_slice(&c2w_vals);
+        evals.push(Fr::zero());
+        // Proof order is ql qr qm qo, C0 order ql qr qo qm
+        evals.swap(2, 3);
+        proof.evals = FflonkEvaluations::from_array(
+            evals.iter().map(fr_to_u256).collect::<Vec<_>>().try_into().unwrap(),
+        );
+        let alpha = u256_to_fr(&compute_challenges(&proof, &vk, &inputs).alpha);
+
+        // Round 4: W1 = Σ αⁱ·(Cᵢ − Rᵢ)/Z_Sᵢ at τ, where Rᵢ interpolates Cᵢ on Sᵢ
+        let s0_pts = to_fr(&roots.h0w8);
+        let s1_pts = to_fr(&roots.h1w4);
+        let s2_pts: Vec<Fr> = to_fr(&roots.h2w3).into_iter().chain(to_fr(&roots.h3w3)).collect();
+        let r0_vals: Vec<Fr> = s0_pts.iter().map(|h| horner(&c0_vals, *h)).collect();
+        let r1_vals: Vec<Fr> = s1_pts.iter().map(|h| horner(&c1_vals, *h)).collect();
+        let r2_vals: Vec<Fr> = s2_pts
+            .iter()
+            .enumerate()
+            .map(|(i, h)| horner(if i < 3 { &c2_vals } else { &c2w_vals }, *h))
+            .collect();
+        let sets = [(&s0_pts, &r0_vals), (&s1_pts, &r1_vals), (&s2_pts, &r2_vals)];
+
+        let polys = circuit.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        let c_tau = [pack(&polys, tau), pack(&[a, b, c, &t0], tau), pack(&[&z, &t1, &t2], tau)];
+        let mut w1 = Fr::zero();
+        let mut alpha_i = Fr::one();
+        for ((pts, vals), c_i) in sets.iter().zip(c_tau) {
+            w1 += alpha_i * (c_i - lagrange(pts, vals, tau)) * vanishing(pts, tau).inverse().unwrap();
+            alpha_i *= alpha;
+        }
+        proof.w1 = g1(w1);
+        let ch = compute_challenges(&proof, &vk, &inputs);
+        let y = u256_to_fr(&ch.y);
+
+        // Round 5: W2 opens [C0] + q1·[C1] + q2·[C2] − Z_S0(y)·[W1] to E at y
+        let zs0 = vanishing(&s0_pts, y);
+        let q1 = alpha * zs0 * vanishing(&s1_pts, y).inverse().unwrap();
+        let q2 = alpha * alpha * zs0 * vanishing(&s2_pts, y).inverse().unwrap();
+        let r = sets.map(|(pts, vals)| lagrange(pts, vals, y));
+        let eval = r[0] + q1 * r[1] + q2 * r[2];
+        let f = c_tau[0] + q1 * c_tau[1] + q2 * c_tau[2] - zs0 * w1;
+        proof.w2 = g1((f - eval) * (tau - y).inverse().unwrap());
+
+        proof.evals.inv = compute_inverse_hint(&vk, &ch, inputs.len()).unwrap();
+        proof
+    }
+
+    pub fn proof_bytes(&self, witness: &Witness) -> Vec<u8> {
+        serialize_fflonk_proof(&self.prove(witness))
+    }
+}
@@ -0,0 +1,572 @@
+//! halo2 test prover
+//!
+//! A PSE halo2 prover (KZG, SHPLONK) for one small circuit, following
+//! `create_proof` step by step: advice phases, lookups, permutation, vanishing
+//! argument and the SHPLONK multi-open. As in `plonk`, polynomials are kept in
+//! coefficient form and commitments are evaluated at the trapdoor τ. Every
+//! opened value comes from the prover's own polynomials, so an unsatisfied
+//! witness or a tampered proof still fails verification.
+//!
+//! Circuit, on 16 rows (10 usable, 5 blinding factors):
+//! ```text
+//! q·(a·b − c) = 0        q_next·(a(ωX) − c) = 0        q·(d − ch·a) = 0
+//! q_lookup·b ∈ table     b copied from row 0 to rows 1..4    c[4] = instance[0]
+//! ```
+//! so `a = x, x², …` and the instance is `x⁶`. `d` is a second-phase column
+//! fixed by the first-phase challenge `ch`. The lookup makes the degree 5, so
+//! the permutation over `a, b, c, instance` splits into two grand products.
+
+use ark_bn254::{Fr, G2Affine, G2Projective};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{FftField, Field, One, Zero};
+use uzkv_stylus::groth16::codec;
+use uzkv_stylus::halo2::transcript::{compress_point, TranscriptHash, TranscriptRead};
+use uzkv_stylus::halo2::vk::{Encoder, Op, Query};
+use uzkv_stylus::halo2::DELTA;
+
+use super::fflonk::{lagrange, vanishing};
+use super::plonk::{fr_to_u256, g1, poly_add, poly_div_vanishing, poly_eval, poly_mul, poly_scale, u256_to_fr};
+
+pub const K: u32 = 4;
+pub const N: usize = 1 << K;
+pub const DEGREE: usize = 5;
+const BLINDING_FACTORS: usize = 5;
+const USABLE_ROWS: usize = N - BLINDING_FACTORS - 1;
+const CHUNK_LEN: usize = DEGREE - 2;
+/// Rows with `q = 1`
+const GATE_ROWS: usize = 5;
+
+// Fixed columns
+const Q: usize = 0;
+const Q_NEXT: usize = 1;
+const Q_LOOKUP: usize = 2;
+const TABLE: usize = 3;
+
+const ADVICE_QUERIES: [(usize, i32); 5] = [(0, 0), (1, 0), (2, 0), (0, 1), (3, 0)];
+const INSTANCE_QUERIES: [(usize, i32); 1] = [(0, 0)];
+const FIXED_QUERIES: [(usize, i32); 4] = [(Q, 0), (Q_NEXT, 0), (Q_LOOKUP, 0), (TABLE, 0)];
+
+/// Permutation columns: (type, column), type 0 = advice, 2 = instance
+const PERMUTATION: [(u8, usize); 4] = [(0, 0), (0, 1), (0, 2), (2, 0)];
+
+/// Copy cycles over (permutation column, row)
+const COPIES: [&[(usize, usize)]; 2] = [&[(1, 0), (1, 1), (1, 2), (1, 3), (1, 4)], &[(2, 4), (3, 0)]];
+
+/// Number of proof points before the first evaluation
+pub const NUM_POINTS: usize = 4 + 2 + 2 + 1 + 1 + (DEGREE - 1);
+
+/// Number of evaluations
+pub const NUM_SCALARS: usize = 5 + 4 + 1 + 4 + 3 + 2 + 5;
+
+fn queries(list: &[(usize, i32)]) -> Vec<Query> {
+    list.iter().map(|&(column, rotation)| Query { column, rotation }).collect()
+}
+
+/// Gate polynomials over the query indices above
+pub fn gates() -> Vec<Vec<Op>> {
+    let minus_one = fr_to_u256(&-Fr::one());
+    vec![
+        // q·(a·b − c)
+        vec![Op::Fixed(0), Op::Advice(0), Op::Advice(1), Op::Product, Op::Advice(2), Op::Negated, Op::Sum, Op::Product],
+        // q_next·(a(ωX) + (−1)·c)
+        vec![Op::Fixed(1), Op::Advice(3), Op::Advice(2), Op::Scaled(minus_one), Op::Sum, Op::Product],
+        // q·(d − ch·a)
+        vec![
+            Op::Fixed(0),
+            Op::Advice(4),
+            Op::Challenge(0),
+            Op::Advice(0),
+            Op::Product,
+            Op::Negated,
+            Op::Sum,
+            Op::Product,
+        ],
+    ]
+}
+
+fn lookup_input() -> Vec<Op> {
+    vec![Op::Fixed(2), Op::Advice(1), Op::Product]
+}
+
+fn lookup_table() -> Vec<Op> {
+    vec![Op::Fixed(3)]
+}
+
+/// Evaluate an expression over query polynomials
+fn evaluate(ops: &[Op], fixed: &[Vec<Fr>], advice: &[Vec<Fr>], instance: &[Vec<Fr>], challenges: &[Fr]) -> Vec<Fr> {
+    let mut stack: Vec<Vec<Fr>> = Vec::new();
+    for op in ops {
+        let value = match *op {
+            Op::Constant(c) => vec![u256_to_fr(&c)],
+            Op::Fixed(i) => fixed[i].clone(),
+            Op::Advice(i) => advice[i].clone(),
+            Op::Instance(i) => instance[i].clone(),
+            Op::Challenge(i) => vec![challenges[i]],
+            Op::Negated => poly_scale(&stack.pop().unwrap(), -Fr::one()),
+            Op::Scaled(c) => poly_scale(&stack.pop().unwrap(), u256_to_fr(&c)),
+            Op::Sum | Op::Product => {
+                let b = stack.pop().unwrap();
+                let a = stack.pop().unwrap();
+                if *op == Op::Sum { poly_add(&a, &b) } else { poly_mul(&a, &b) }
+            }
+        };
+        stack.push(value);
+    }
+    stack.pop().unwrap()
+}
+
+fn poly_sub(a: &[Fr], b: &[Fr]) -> Vec<Fr> {
+    poly_add(a, &poly_scale(b, -Fr::one()))
+}
+
+/// Pad usable-row values with (deterministic) blinding rows
+fn blind(values: &[Fr], seed: u64) -> Vec<Fr> {
+    let mut out = values.to_vec();
+    while out.len() < N {
+        out.push(Fr::from(seed * 1_000 + out.len() as u64 + 1));
+    }
+    out
+}
+
+/// halo2's `permute_expression_pair`: group equal inputs and put the matching
+/// table value first
+fn permute(input: &[Fr], table: &[Fr]) -> (Vec<Fr>, Vec<Fr>) {
+    let mut permuted_input = input.to_vec();
+    permuted_input.sort();
+    let mut leftover = table.to_vec();
+    let mut permuted_table: Vec<Option<Fr>> = vec![None; input.len()];
+    for (i, value) in permuted_input.iter().enumerate() {
+        if i == 0 || permuted_input[i - 1] != *value {
+            let pos = leftover.iter().position(|t| t == value).expect("lookup input not in table");
+            permuted_table[i] = Some(leftover.remove(pos));
+        }
+    }
+    let mut leftover = leftover.into_iter();
+    let permuted_table = permuted_table.into_iter().map(|v| v.or_else(|| leftover.next()).unwrap()).collect();
+    (permuted_input, permuted_table)
+}
+
+/// Commitments grouped by point set, in order of first appearance (as
+/// `construct_intermediate_sets`)
+fn rotation_sets(openings: &[(usize, Fr)]) -> Vec<(Vec<Fr>, Vec<usize>)> {
+    let mut per_slot: Vec<(usize, Vec<Fr>)> = Vec::new();
+    for &(slot, point) in openings {
+        match per_slot.iter_mut().find(|(s, _)| *s == slot) {
+            Some((_, points)) if !points.contains(&point) => points.push(point),
+            Some(_) => {}
+            None => per_slot.push((slot, vec![point])),
+        }
+    }
+    let mut sets: Vec<(Vec<Fr>, Vec<usize>)> = Vec::new();
+    for (slot, mut points) in per_slot {
+        points.sort();
+        match sets.iter_mut().find(|(set, _)| *set == points) {
+            Some((_, slots)) => slots.push(slot),
+            None => sets.push((points, vec![slot])),
+        }
+    }
+    sets
+}
+
+fn write_point<H: TranscriptHash>(t: &mut TranscriptRead<'_, H>, proof: &mut Vec<u8>, point: [u8; 64]) {
+    t.common_point(&point).unwrap();
+    proof.extend_from_slice(&compress_point(&point));
+}
+
+fn write_scalar<H: TranscriptHash>(t: &mut TranscriptRead<'_, H>, proof: &mut Vec<u8>, value: Fr) {
+    let value = fr_to_u256(&value);
+    t.common_scalar(value);
+    proof.extend_from_slice(&value.to_le_bytes::<32>());
+}
+
+fn squeeze<H: TranscriptHash>(t: &mut TranscriptRead<'_, H>) -> Fr {
+    u256_to_fr(&t.squeeze_challenge())
+}
+
+/// Fixed columns, permutation and the SRS trapdoor
+pub struct Halo2Fixture {
+    omega: Fr,
+    tau: Fr,
+    transcript_repr: Fr,
+    fixed: Vec<Vec<Fr>>,
+    sigmas: Vec<Vec<Fr>>,
+}
+
+/// First-phase advice values on the usable rows, and the instance column
+#[derive(Clone)]
+pub struct Witness {
+    pub a: Vec<Fr>,
+    pub b: Vec<Fr>,
+    pub c: Vec<Fr>,
+    pub instance: Vec<Fr>,
+}
+
+impl Halo2Fixture {
+    pub fn new(tau: Fr) -> Self {
+        let omega = Fr::get_root_of_unity(N as u64).unwrap();
+
+        let mut fixed = vec![vec![Fr::zero(); N]; 4];
+        fixed[Q][..GATE_ROWS].fill(Fr::one());
+        fixed[Q_LOOKUP][..GATE_ROWS].fill(Fr::one());
+        fixed[Q_NEXT][..GATE_ROWS - 1].fill(Fr::one());
+        for (row, value) in fixed[TABLE][..USABLE_ROWS].iter_mut().enumerate() {
+            *value = Fr::from(row as u64);
+        }
+
+        // σ maps every cell to the next one of its copy cycle
+        let delta = u256_to_fr(&DELTA);
+        let mut mapping: Vec<Vec<(usize, usize)>> =
+            (0..PERMUTATION.len()).map(|column| (0..N).map(|row| (column, row)).collect()).collect();
+        for cycle in COPIES {
+            for (i, &(column, row)) in cycle.iter().enumerate() {
+                mapping[column][row] = cycle[(i + 1) % cycle.len()];
+            }
+        }
+        let sigmas = mapping
+            .iter()
+            .map(|cells| cells.iter().map(|&(c, r)| delta.pow([c as u64]) * omega.pow([r as u64])).collect())
+            .collect();
+
+        Self { omega, tau, transcript_repr: Fr::from(0x0068_616c_6f32_u64), fixed, sigmas }
+    }
+
+    /// `a = x, x², …, x⁵`, `b = x`, `c = a·b`, instance `x⁶`
+    pub fn witness(&self, x: Fr) -> Witness {
+        let mut a = vec![Fr::zero(); USABLE_ROWS];
+        let mut b = vec![Fr::zero(); USABLE_ROWS];
+        let mut c = vec![Fr::zero(); USABLE_ROWS];
+        a[0] = x;
+        for row in 0..GATE_ROWS {
+            b[row] = x;
+            c[row] = a[row] * b[row];
+            if row + 1 < GATE_ROWS {
+                a[row + 1] = c[row];
+            }
+        }
+        let instance = vec![c[GATE_ROWS - 1]];
+        Witness { a, b, c, instance }
+    }
+
+    pub fn public_bytes(&self, witness: &Witness) -> Vec<u8> {
+        codec::inputs_from_ark(&witness.instance)
+    }
+
+    /// Inverse DFT over the domain
+    fn interpolate(&self, evals: &[Fr]) -> Vec<Fr> {
+        let n_inv = Fr::from(N as u64).inverse().unwrap();
+        let omega_inv = self.omega.inverse().unwrap();
+        (0..N)
+            .map(|j| {
+                let w = omega_inv.pow([j as u64]);
+                evals.iter().enumerate().map(|(i, y)| *y * w.pow([i as u64])).sum::<Fr>() * n_inv
+            })
+            .collect()
+    }
+
+    fn omega_pow(&self, rotation: i32) -> Fr {
+        if rotation < 0 {
+            self.omega.inverse().unwrap().pow([rotation.unsigned_abs() as u64])
+        } else {
+            self.omega.pow([rotation as u64])
+        }
+    }
+
+    /// p(ωʳ·X)
+    fn rotate(&self, poly: &[Fr], rotation: i32) -> Vec<Fr> {
+        let w = self.omega_pow(rotation);
+        let mut power = Fr::one();
+        poly.iter()
+            .map(|c| {
+                let out = *c * power;
+                power *= w;
+                out
+            })
+            .collect()
+    }
+
+    fn commit(&self, poly: &[Fr]) -> [u8; 64] {
+        g1(poly_eval(poly, self.tau))
+    }
+
+    /// Compact VK followed by `[τ]₂`, as `halo2::verify` expects
+    pub fn vk_bytes(&self) -> Vec<u8> {
+        let mut out = self.vk_without_srs();
+        let srs = (G2Projective::from(G2Affine::generator()) * self.tau).into_affine();
+        out.extend_from_slice(&codec::g2_from_ark(&srs));
+        out
+    }
+
+    /// Compact VK, as registered with `registerPlonkVk`
+    pub fn vk_without_srs(&self) -> Vec<u8> {
+        let mut e = Encoder::default();
+        e.u32(K).scalar(fr_to_u256(&self.omega)).scalar(fr_to_u256(&self.transcript_repr)).u32(DEGREE as u32);
+        e.u32(4);
+        e.u32(4).u8(0).u8(0).u8(0).u8(1);
+        e.u32(1).u32(1);
+        e.u32(1).u8(0);
+        e.queries(&queries(&ADVICE_QUERIES)).queries(&queries(&INSTANCE_QUERIES)).queries(&queries(&FIXED_QUERIES));
+        e.u32(PERMUTATION.len() as u32);
+        for (ty, column) in PERMUTATION {
+            e.u8(ty).u32(column as u32);
+        }
+        let gates = gates();
+        e.u32(gates.len() as u32);
+        for gate in &gates {
+            e.expression(gate);
+        }
+        e.u32(1).u32(1).expression(&lookup_input()).expression(&lookup_table());
+        for column in &self.fixed {
+            e.point(&self.commit(&self.interpolate(column)));
+        }
+        for sigma in &self.sigmas {
+            e.point(&self.commit(&self.interpolate(sigma)));
+        }
+        e.0
+    }
+
+    /// Run the prover; an unsatisfied witness still yields a (bogus) proof
+    pub fn prove<H: TranscriptHash>(&self, witness: &Witness) -> Vec<u8> {
+        let one = Fr::one();
+        let mut t = TranscriptRead::<H>::new(&[]);
+        let mut proof = Vec::new();
+
+        t.common_scalar(fr_to_u256(&self.transcript_repr));
+        for value in &witness.instance {
+            t.common_scalar(fr_to_u256(value));
+        }
+
+        // Phase 0: a, b, c, then the challenge; phase 1: d = ch·a on gate rows
+        let mut advice: Vec<Vec<Fr>> =
+            [&witness.a, &witness.b, &witness.c].iter().zip(1..).map(|(v, seed)| blind(v, seed)).collect();
+        for column in &advice {
+            write_point(&mut t, &mut proof, self.commit(&self.interpolate(column)));
+        }
+        let challenge = squeeze(&mut t);
+        let d: Vec<Fr> = (0..USABLE_ROWS).map(|row| self.fixed[Q][row] * challenge * witness.a[row]).collect();
+        advice.push(blind(&d, 4));
+        write_point(&mut t, &mut proof, self.commit(&self.interpolate(&advice[3])));
+        let advice_polys: Vec<Vec<Fr>> = advice.iter().map(|v| self.interpolate(v)).collect();
+        let fixed_polys: Vec<Vec<Fr>> = self.fixed.iter().map(|v| self.interpolate(v)).collect();
+        let sigma_polys: Vec<Vec<Fr>> = self.sigmas.iter().map(|v| self.interpolate(v)).collect();
+        let mut instance = witness.instance.clone();
+        instance.resize(N, Fr::zero());
+        let instance_poly = self.interpolate(&instance);
+
+        // Lookup: permuted input and table
+        let _theta = squeeze(&mut t);
+        let input: Vec<Fr> = (0..USABLE_ROWS).map(|row| self.fixed[Q_LOOKUP][row] * advice[1][row]).collect();
+        let table = self.fixed[TABLE][..USABLE_ROWS].to_vec();
+        let (permuted_input, permuted_table) = permute(&input, &table);
+        let a_prime = self.interpolate(&blind(&permuted_input, 10));
+        let s_prime = self.interpolate(&blind(&permuted_table, 11));
+        write_point(&mut t, &mut proof, self.commit(&a_prime));
+        write_point(&mut t, &mut proof, self.commit(&s_prime));
+
+        // Permutation grand products, chained across sets
+        let beta = squeeze(&mut t);
+        let gamma = squeeze(&mut t);
+        let delta = u256_to_fr(&DELTA);
+        let columns = [&advice[0], &advice[1], &advice[2], &instance];
+        let column_indices: Vec<usize> = (0..PERMUTATION.len()).collect();
+        let mut last = one;
+        let mut z_perm = Vec::new();
+        for (set, chunk) in column_indices.chunks(CHUNK_LEN).enumerate() {
+            let mut z = vec![last];
+            for row in 0..USABLE_ROWS {
+                let (mut num, mut den) = (one, one);
+                for &j in chunk {
+                    let value = columns[j][row];
+                    num *= value + beta * delta.pow([j as u64]) * self.omega.pow([row as u64]) + gamma;
+                    den *= value + beta * self.sigmas[j][row] + gamma;
+                }
+                z.push(z[row] * num * den.inverse().unwrap());
+            }
+            last = z[USABLE_ROWS];
+            z_perm.push(self.interpolate(&blind(&z, 20 + set as u64)));
+        }
+        for z in &z_perm {
+            write_point(&mut t, &mut proof, self.commit(z));
+        }
+
+        // Lookup grand product
+        let mut z = vec![one];
+        for row in 0..USABLE_ROWS {
+            let num = (input[row] + beta) * (table[row] + gamma);
+            let den = (permuted_input[row] + beta) * (permuted_table[row] + gamma);
+            z.push(z[row] * num * den.inverse().unwrap());
+        }
+        let z_lookup = self.interpolate(&blind(&z, 30));
+        write_point(&mut t, &mut proof, self.commit(&z_lookup));
+
+        // Vanishing argument
+        let random_poly: Vec<Fr> = (0..N as u64).map(|i| Fr::from(i * i + 7)).collect();
+        write_point(&mut t, &mut proof, self.commit(&random_poly));
+        let y = squeeze(&mut t);
+
+        let indicator = |rows: std::ops::Range<usize>| {
+            self.interpolate(&(0..N).map(|row| if rows.contains(&row) { one } else { Fr::zero() }).collect::<Vec<_>>())
+        };
+        let l_0 = indicator(0..1);
+        let l_last = indicator(USABLE_ROWS..USABLE_ROWS + 1);
+        let l_blind = indicator(USABLE_ROWS + 1..N);
+        let active = poly_sub(&[one], &poly_add(&l_last, &l_blind));
+
+        let rotated = |polys: &[Vec<Fr>], list: &[(usize, i32)]| -> Vec<Vec<Fr>> {
+            list.iter().map(|&(column, rotation)| self.rotate(&polys[column], rotation)).collect()
+        };
+        let fixed_q = rotated(&fixed_polys, &FIXED_QUERIES);
+        let advice_q = rotated(&advice_polys, &ADVICE_QUERIES);
+        let instance_q = rotated(std::slice::from_ref(&instance_poly), &INSTANCE_QUERIES);
+        let eval = |ops: &[Op]| evaluate(ops, &fixed_q, &advice_q, &instance_q, &[challenge]);
+
+        let mut constraints: Vec<Vec<Fr>> = gates().iter().map(|g| eval(g)).collect();
+
+        let (first, last_set) = (&z_perm[0], &z_perm[z_perm.len() - 1]);
+        constraints.push(poly_mul(&l_0, &poly_sub(&[one], first)));
+        constraints.push(poly_mul(&l_last, &poly_sub(&poly_mul(last_set, last_set), last_set)));
+        for pair in z_perm.windows(2) {
+            let previous_last = self.rotate(&pair[0], -(BLINDING_FACTORS as i32 + 1));
+            constraints.push(poly_mul(&l_0, &poly_sub(&pair[1], &previous_last)));
+        }
+        let column_polys = [&advice_polys[0], &advice_polys[1], &advice_polys[2], &instance_poly];
+        for (set, chunk) in column_indices.chunks(CHUNK_LEN).enumerate() {
+            let mut left = self.rotate(&z_perm[set], 1);
+            let mut right = z_perm[set].clone();
+            for &j in chunk {
+                let sigma = poly_add(&poly_scale(&sigma_polys[j], beta), &[gamma]);
+                left = poly_mul(&left, &poly_add(column_polys[j], &sigma));
+                let id = [gamma, beta * delta.pow([j as u64])];
+                right = poly_mul(&right, &poly_add(column_polys[j], &id));
+            }
+            constraints.push(poly_mul(&active, &poly_sub(&left, &right)));
+        }
+
+        let input_poly = eval(&lookup_input());
+        let table_poly = eval(&lookup_table());
+        let left = poly_mul(
+            &poly_mul(&self.rotate(&z_lookup, 1), &poly_add(&a_prime, &[beta])),
+            &poly_add(&s_prime, &[gamma]),
+        );
+        let right = poly_mul(&poly_mul(&z_lookup, &poly_add(&input_poly, &[beta])), &poly_add(&table_poly, &[gamma]));
+        let a_minus_s = poly_sub(&a_prime, &s_prime);
+        constraints.push(poly_mul(&l_0, &poly_sub(&[one], &z_lookup)));
+        constraints.push(poly_mul(&l_last, &poly_sub(&poly_mul(&z_lookup, &z_lookup), &z_lookup)));
+        constraints.push(poly_mul(&active, &poly_sub(&left, &right)));
+        constraints.push(poly_mul(&l_0, &a_minus_s));
+        constraints.push(poly_mul(&active, &poly_mul(&a_minus_s, &poly_sub(&a_prime, &self.rotate(&a_prime, -1)))));
+
+        let numerator = constraints.iter().fold(vec![Fr::zero()], |acc, c| poly_add(&poly_scale(&acc, y), c));
+        let mut h = poly_div_vanishing(&numerator, N);
+        h.resize((DEGREE - 1) * N, Fr::zero());
+        let h_pieces: Vec<Vec<Fr>> = h.chunks(N).map(|piece| piece.to_vec()).collect();
+        for piece in &h_pieces {
+            write_point(&mut t, &mut proof, self.commit(piece));
+        }
+        let x = squeeze(&mut t);
+
+        // Evaluations
+        let at = |poly: &[Fr], rotation: i32| poly_eval(poly, x * self.omega_pow(rotation));
+        for &(column, rotation) in &ADVICE_QUERIES {
+            write_scalar(&mut t, &mut proof, at(&advice_polys[column], rotation));
+        }
+        for &(column, rotation) in &FIXED_QUERIES {
+            write_scalar(&mut t, &mut proof, at(&fixed_polys[column], rotation));
+        }
+        write_scalar(&mut t, &mut proof, at(&random_poly, 0));
+        for sigma in &sigma_polys {
+            write_scalar(&mut t, &mut proof, at(sigma, 0));
+        }
+        let last_rotation = -(BLINDING_FACTORS as i32 + 1);
+        for (set, z) in z_perm.iter().enumerate() {
+            write_scalar(&mut t, &mut proof, at(z, 0));
+            write_scalar(&mut t, &mut proof, at(z, 1));
+            if set + 1 < z_perm.len() {
+                write_scalar(&mut t, &mut proof, at(z, last_rotation));
+            }
+        }
+        for (poly, rotation) in [(&z_lookup, 0), (&z_lookup, 1), (&a_prime, 0), (&a_prime, -1), (&s_prime, 0)] {
+            write_scalar(&mut t, &mut proof, at(poly, rotation));
+        }
+
+        // Opening claims, in the verifier's order
+        let point = |rotation: i32| x * self.omega_pow(rotation);
+        let mut slots: Vec<Vec<Fr>> = advice_polys.clone();
+        let mut openings: Vec<(usize, Fr)> =
+            ADVICE_QUERIES.iter().map(|&(column, rotation)| (column, point(rotation))).collect();
+        let base = slots.len();
+        slots.extend(z_perm.iter().cloned());
+        for set in 0..z_perm.len() {
+            openings.push((base + set, point(0)));
+            openings.push((base + set, point(1)));
+        }
+        for set in (0..z_perm.len()).rev().skip(1) {
+            openings.push((base + set, point(last_rotation)));
+        }
+        let base = slots.len();
+        slots.extend([z_lookup.clone(), a_prime.clone(), s_prime.clone()]);
+        openings.extend([
+            (base, point(0)),
+            (base + 1, point(0)),
+            (base + 2, point(0)),
+            (base + 1, point(-1)),
+            (base, point(1)),
+        ]);
+        let base = slots.len();
+        slots.extend(fixed_polys.iter().cloned());
+        openings.extend(FIXED_QUERIES.iter().map(|&(column, rotation)| (base + column, point(rotation))));
+        for sigma in &sigma_polys {
+            slots.push(sigma.clone());
+            openings.push((slots.len() - 1, point(0)));
+        }
+        let xn = x.pow([N as u64]);
+        let h_x = h_pieces
+            .iter()
+            .rev()
+            .fold(vec![Fr::zero()], |acc, piece| poly_add(&poly_scale(&acc, xn), piece));
+        slots.push(h_x);
+        openings.push((slots.len() - 1, point(0)));
+        slots.push(random_poly);
+        openings.push((slots.len() - 1, point(0)));
+
+        // SHPLONK: h1 = Σ vⁱ·Σ yʲ·(pᵢⱼ − rᵢⱼ) / Z_Sᵢ, h2 opens the linearization at u
+        let sets = rotation_sets(&openings);
+        let mut super_points: Vec<Fr> = Vec::new();
+        for (_, p) in &openings {
+            if !super_points.contains(p) {
+                super_points.push(*p);
+            }
+        }
+        let shplonk_y = squeeze(&mut t);
+        let v = squeeze(&mut t);
+        let tau = self.tau;
+        let combine = |z: Fr, weights: &dyn Fn(usize, &[Fr]) -> Fr| -> Fr {
+            let mut power_of_v = one;
+            let mut acc = Fr::zero();
+            for (i, (points, members)) in sets.iter().enumerate() {
+                let mut power_of_y = one;
+                for slot in members {
+                    let evals: Vec<Fr> = points.iter().map(|p| poly_eval(&slots[*slot], *p)).collect();
+                    let r = lagrange(points, &evals, z);
+                    acc += power_of_v * weights(i, points) * power_of_y * (poly_eval(&slots[*slot], tau) - r);
+                    power_of_y *= shplonk_y;
+                }
+                power_of_v *= v;
+            }
+            acc
+        };
+        let h1_value = combine(tau, &|_, points| vanishing(points, tau).inverse().unwrap());
+        write_point(&mut t, &mut proof, g1(h1_value));
+        let u = squeeze(&mut t);
+
+        let z_diff = |points: &[Fr]| {
+            let diffs: Vec<Fr> = super_points.iter().filter(|p| !points.contains(p)).copied().collect();
+            vanishing(&diffs, u)
+        };
+        let z_0_diff_inv = z_diff(&sets[0].0).inverse().unwrap();
+        let z_0 = vanishing(&sets[0].0, u);
+        let linearization = combine(u, &|_, points| z_diff(points) * z_0_diff_inv) - z_0 * h1_value;
+        write_point(&mut t, &mut proof, g1(linearization * (tau - u).inverse().unwrap()));
+
+        proof
+    }
+}
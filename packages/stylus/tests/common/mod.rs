@@ -0,0 +1,99 @@
+//! Shared fixtures for host-side Groth16 integration tests
+//!
+//! Proofs are simulated with the setup trapdoor, which yields proofs that are
+//! indistinguishable from honest ones without needing a circuit. `plonk` holds
+//! a small PLONK prover built the same way, `fflonk` runs its circuit
+//! through fflonk, `halo2` proves a small halo2 circuit with SHPLONK,
+//! `ultra` an UltraPLONK circuit with custom gates and a lookup, and `stark`
+//! builds STARK proofs in the `stark::proof` format.
+
+#![allow(dead_code)]
+
+pub mod fflonk;
+pub mod halo2;
+pub mod plonk;
+pub mod stark;
+pub mod ultra;
+
+use ark_bn254::{Bn254, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::Field;
+use ark_groth16::{Proof, VerifyingKey};
+use ark_std::{rand::RngCore, UniformRand};
+use uzkv_stylus::groth16::codec;
+
+/// Deterministic Groth16 fixture with the trapdoor kept around for proving
+pub struct Fixture {
+    pub vk: VerifyingKey<Bn254>,
+    alpha: Fr,
+    beta: Fr,
+    gamma: Fr,
+    delta: Fr,
+    ic_scalars: Vec<Fr>,
+}
+
+impl Fixture {
+    pub fn new<R: RngCore>(num_inputs: usize, rng: &mut R) -> Self {
+        let g1 = G1Projective::from(G1Affine::generator());
+        let g2 = G2Projective::from(G2Affine::generator());
+
+        let alpha = Fr::rand(rng);
+        let beta = Fr::rand(rng);
+        let gamma = Fr::rand(rng);
+        let delta = Fr::rand(rng);
+        let ic_scalars: Vec<Fr> = (0..=num_inputs).map(|_| Fr::rand(rng)).collect();
+
+        let vk = VerifyingKey {
+            alpha_g1: (g1 * alpha).into_affine(),
+            beta_g2: (g2 * beta).into_affine(),
+            gamma_g2: (g2 * gamma).into_affine(),
+            delta_g2: (g2 * delta).into_affine(),
+            gamma_abc_g1: ic_scalars
+                .iter()
+                .map(|s| (g1 * s).into_affine())
+                .collect(),
+        };
+
+        Self { vk, alpha, beta, gamma, delta, ic_scalars }
+    }
+
+    /// Produce a valid proof for `inputs`
+    pub fn prove<R: RngCore>(&self, inputs: &[Fr], rng: &mut R) -> Proof<Bn254> {
+        assert_eq!(inputs.len() + 1, self.ic_scalars.len());
+        let g1 = G1Projective::from(G1Affine::generator());
+        let g2 = G2Projective::from(G2Affine::generator());
+
+        // L in the exponent
+        let mut l = self.ic_scalars[0];
+        for (x, s) in inputs.iter().zip(&self.ic_scalars[1..]) {
+            l += *x * s;
+        }
+
+        // e(A, B) = e(α, β) · e(L, γ) · e(C, δ)  =>  c = (a·b − α·β − l·γ) / δ
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+        let c = (a * b - self.alpha * self.beta - l * self.gamma) * self.delta.inverse().unwrap();
+
+        Proof {
+            a: (g1 * a).into_affine(),
+            b: (g2 * b).into_affine(),
+            c: (g1 * c).into_affine(),
+        }
+    }
+}
+
+pub fn random_inputs<R: RngCore>(n: usize, rng: &mut R) -> Vec<Fr> {
+    (0..n).map(|_| Fr::rand(rng)).collect()
+}
+
+pub fn proof_to_evm(proof: &Proof<Bn254>) -> Vec<u8> {
+    codec::proof_from_ark(proof)
+}
+
+pub fn vk_to_evm(vk: &VerifyingKey<Bn254>) -> Vec<u8> {
+    codec::vk_from_ark(vk)
+}
+
+pub fn inputs_to_evm(inputs: &[Fr]) -> Vec<u8> {
+    codec::inputs_from_ark(inputs)
+}
@@ -0,0 +1,385 @@
+//! PLONK test prover
+//!
+//! A complete (unblinded) PLONK prover over a small domain. Polynomials are
+//! kept in coefficient form with naive arithmetic, and commitments and opening
+//! proofs are evaluated at the SRS trapdoor τ, which gives exactly the points an
+//! SRS-based prover would output.
+
+use ark_bn254::{Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, FftField, Field, One, PrimeField, Zero};
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::groth16::codec;
+use uzkv_stylus::plonk::plonk::{
+    compute_challenges, serialize_plonk_proof, serialize_plonk_vk, Challenges, PlonkProof,
+    PlonkVerificationKey,
+};
+
+/// Transcript used by the prover, e.g. `compute_challenges` or
+/// `snarkjs::compute_challenges`
+pub type ChallengeFn = fn(&PlonkProof, &PlonkVerificationKey, &[U256]) -> Challenges;
+
+pub fn fr_to_u256(value: &Fr) -> U256 {
+    U256::from_be_slice(&value.into_bigint().to_bytes_be())
+}
+
+pub fn u256_to_fr(value: &U256) -> Fr {
+    Fr::from_be_bytes_mod_order(&value.to_be_bytes::<32>())
+}
+
+// =========================================================================
+// Polynomials (coefficients, lowest degree first)
+// =========================================================================
+
+pub(super) fn poly_eval(poly: &[Fr], x: Fr) -> Fr {
+    poly.iter().rev().fold(Fr::zero(), |acc, c| acc * x + c)
+}
+
+pub(super) fn poly_add(a: &[Fr], b: &[Fr]) -> Vec<Fr> {
+    let mut out = vec![Fr::zero(); a.len().max(b.len())];
+    for (i, c) in a.iter().enumerate() {
+        out[i] += c;
+    }
+    for (i, c) in b.iter().enumerate() {
+        out[i] += c;
+    }
+    out
+}
+
+pub(super) fn poly_scale(a: &[Fr], s: Fr) -> Vec<Fr> {
+    a.iter().map(|c| *c * s).collect()
+}
+
+pub(super) fn poly_mul(a: &[Fr], b: &[Fr]) -> Vec<Fr> {
+    let mut out = vec![Fr::zero(); a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            out[i + j] += *x * y;
+        }
+    }
+    out
+}
+
+/// p(X) + c
+pub(super) fn poly_add_const(a: &[Fr], c: Fr) -> Vec<Fr> {
+    poly_add(a, &[c])
+}
+
+/// Divide by X − z, dropping any remainder
+pub(super) fn poly_div_linear(a: &[Fr], z: Fr) -> Vec<Fr> {
+    let mut quotient = vec![Fr::zero(); a.len().saturating_sub(1).max(1)];
+    let mut carry = Fr::zero();
+    for i in (1..a.len()).rev() {
+        carry = a[i] + carry * z;
+        quotient[i - 1] = carry;
+    }
+    quotient
+}
+
+/// Divide by Xⁿ − 1, dropping any remainder
+pub(super) fn poly_div_vanishing(a: &[Fr], n: usize) -> Vec<Fr> {
+    let mut rem = a.to_vec();
+    let mut quotient = vec![Fr::zero(); a.len().saturating_sub(n).max(1)];
+    for i in (n..rem.len()).rev() {
+        let c = rem[i];
+        quotient[i - n] = c;
+        rem[i] = Fr::zero();
+        rem[i - n] += c;
+    }
+    quotient
+}
+
+// =========================================================================
+// Circuit
+// =========================================================================
+
+/// Selector and permutation tables plus the SRS trapdoor
+pub struct PlonkFixture {
+    pub n: usize,
+    pub num_public: usize,
+    pub(super) omega: Fr,
+    pub(super) k1: Fr,
+    pub(super) k2: Fr,
+    pub(super) tau: Fr,
+    /// q_L, q_R, q_O, q_M, q_C evaluations over the domain
+    pub(super) selectors: [Vec<Fr>; 5],
+    /// sσ1, sσ2, sσ3 evaluations over the domain
+    pub(super) sigmas: [Vec<Fr>; 3],
+}
+
+/// Wire assignment for `x³ + x + 5 = y`
+pub struct Witness {
+    pub wires: [Vec<Fr>; 3],
+    pub public: Vec<Fr>,
+}
+
+impl PlonkFixture {
+    /// `x³ + x + 5 = y` with public `y`, on 8 rows
+    ///
+    /// ```text
+    /// row 0: a = y                 q_L = 1 (public input)
+    /// row 1: a = x,  b = x,  c = x²   q_M = 1, q_O = −1
+    /// row 2: a = x², b = x,  c = x³   q_M = 1, q_O = −1
+    /// row 3: a = x³, b = x,  c = t    q_L = q_R = 1, q_O = −1
+    /// row 4: a = t,          c = y    q_L = 1, q_C = 5, q_O = −1
+    /// ```
+    pub fn cubic(tau: Fr) -> Self {
+        let n = 8;
+        let one = Fr::one();
+        let mut selectors: [Vec<Fr>; 5] = Default::default();
+        for s in selectors.iter_mut() {
+            *s = vec![Fr::zero(); n];
+        }
+        let [ql, qr, qo, qm, qc] = &mut selectors;
+        ql[0] = one;
+        (qm[1], qo[1]) = (one, -one);
+        (qm[2], qo[2]) = (one, -one);
+        (ql[3], qr[3], qo[3]) = (one, one, -one);
+        (ql[4], qc[4], qo[4]) = (one, Fr::from(5u64), -one);
+
+        // Copy cycles over (column, row)
+        let cycles: [&[(usize, usize)]; 5] = [
+            &[(0, 1), (1, 1), (1, 2), (1, 3)], // x
+            &[(2, 1), (0, 2)],                 // x²
+            &[(2, 2), (0, 3)],                 // x³
+            &[(2, 3), (0, 4)],                 // t
+            &[(0, 0), (2, 4)],                 // y
+        ];
+
+        let omega = Fr::get_root_of_unity(n as u64).unwrap();
+        let (k1, k2) = (Fr::from(2u64), Fr::from(3u64));
+        let id = |col: usize, row: usize| [one, k1, k2][col] * omega.pow([row as u64]);
+
+        let mut sigmas: [Vec<Fr>; 3] = Default::default();
+        for (col, s) in sigmas.iter_mut().enumerate() {
+            *s = (0..n).map(|row| id(col, row)).collect();
+        }
+        for cycle in cycles {
+            for (i, &(col, row)) in cycle.iter().enumerate() {
+                let (next_col, next_row) = cycle[(i + 1) % cycle.len()];
+                sigmas[col][row] = id(next_col, next_row);
+            }
+        }
+
+        Self { n, num_public: 1, omega, k1, k2, tau, selectors, sigmas }
+    }
+
+    /// Honest witness for `x`
+    pub fn witness(&self, x: Fr) -> Witness {
+        let (x2, x3) = (x * x, x * x * x);
+        let t = x3 + x;
+        let y = t + Fr::from(5u64);
+        let mut wires: [Vec<Fr>; 3] = Default::default();
+        for w in wires.iter_mut() {
+            *w = vec![Fr::zero(); self.n];
+        }
+        let [a, b, c] = &mut wires;
+        a[0] = y;
+        (a[1], b[1], c[1]) = (x, x, x2);
+        (a[2], b[2], c[2]) = (x2, x, x3);
+        (a[3], b[3], c[3]) = (x3, x, t);
+        (a[4], c[4]) = (t, y);
+        Witness { wires, public: vec![y] }
+    }
+
+    pub(super) fn domain(&self) -> Vec<Fr> {
+        (0..self.n).map(|i| self.omega.pow([i as u64])).collect()
+    }
+
+    /// Inverse DFT over the domain
+    pub(super) fn interpolate(&self, evals: &[Fr]) -> Vec<Fr> {
+        let n_inv = Fr::from(self.n as u64).inverse().unwrap();
+        let omega_inv = self.omega.inverse().unwrap();
+        (0..self.n)
+            .map(|j| {
+                let w = omega_inv.pow([j as u64]);
+                evals.iter().enumerate().map(|(i, y)| *y * w.pow([i as u64])).sum::<Fr>() * n_inv
+            })
+            .collect()
+    }
+
+    fn commit(&self, poly: &[Fr]) -> [u8; 64] {
+        g1(poly_eval(poly, self.tau))
+    }
+
+    pub fn verifying_key(&self) -> PlonkVerificationKey {
+        let selectors = self.selectors.clone().map(|s| self.commit(&self.interpolate(&s)));
+        let sigmas = self.sigmas.clone().map(|s| self.commit(&self.interpolate(&s)));
+        PlonkVerificationKey {
+            n: self.n,
+            num_public_inputs: self.num_public,
+            selector_commitments: selectors,
+            permutation_commitments: sigmas,
+            omega: fr_to_u256(&self.omega),
+            k1: fr_to_u256(&self.k1),
+            k2: fr_to_u256(&self.k2),
+        }
+    }
+
+    /// VK bytes followed by `[τ]₂`, as `plonk::verify` expects
+    pub fn vk_bytes(&self) -> Vec<u8> {
+        let mut out = serialize_plonk_vk(&self.verifying_key());
+        let srs = (G2Projective::from(G2Affine::generator()) * self.tau).into_affine();
+        out.extend_from_slice(&codec::g2_from_ark(&srs));
+        out
+    }
+
+    pub fn public_bytes(&self, witness: &Witness) -> Vec<u8> {
+        codec::inputs_from_ark(&witness.public)
+    }
+
+    /// Run the prover; an unsatisfied witness still yields a (bogus) proof
+    pub fn prove(&self, witness: &Witness) -> PlonkProof {
+        self.prove_with(witness, compute_challenges)
+    }
+
+    /// Run the prover with another transcript
+    pub fn prove_with(&self, witness: &Witness, compute_challenges: ChallengeFn) -> PlonkProof {
+        let n = self.n;
+        let vk = self.verifying_key();
+        let inputs: Vec<U256> = witness.public.iter().map(fr_to_u256).collect();
+        let mut proof = PlonkProof {
+            wire_commitments: [[0u8; 64]; 3],
+            permutation_commitment: [0u8; 64],
+            quotient_commitments: [[0u8; 64]; 3],
+            opening_proof_zeta: [0u8; 64],
+            opening_proof_omega: [0u8; 64],
+            wire_evals: [U256::ZERO; 3],
+            sigma_evals: [U256::ZERO; 2],
+            permutation_eval_omega: U256::ZERO,
+        };
+
+        // Round 1: wires
+        let wires = witness.wires.clone().map(|w| self.interpolate(&w));
+        proof.wire_commitments = [0, 1, 2].map(|i| self.commit(&wires[i]));
+        let ch = compute_challenges(&proof, &vk, &inputs);
+        let (beta, gamma) = (u256_to_fr(&ch.beta), u256_to_fr(&ch.gamma));
+
+        // Round 2: grand product, z(ω⁰) = 1
+        let domain = self.domain();
+        let ks = [Fr::one(), self.k1, self.k2];
+        let mut z_evals = vec![Fr::one(); n];
+        for i in 0..n - 1 {
+            let mut num = Fr::one();
+            let mut den = Fr::one();
+            for (col, k) in ks.iter().enumerate() {
+                let w = witness.wires[col][i];
+                num *= w + beta * k * domain[i] + gamma;
+                den *= w + beta * self.sigmas[col][i] + gamma;
+            }
+            z_evals[i + 1] = z_evals[i] * num * den.inverse().unwrap();
+        }
+        let z = self.interpolate(&z_evals);
+        proof.permutation_commitment = self.commit(&z);
+        let alpha = u256_to_fr(&compute_challenges(&proof, &vk, &inputs).alpha);
+
+        // Round 3: quotient
+        let [a, b, c] = &wires;
+        let [ql, qr, qo, qm, qc] = self.selectors.clone().map(|s| self.interpolate(&s));
+        let [s1, s2, s3] = self.sigmas.clone().map(|s| self.interpolate(&s));
+        let mut pi_evals = vec![Fr::zero(); n];
+        for (i, x) in witness.public.iter().enumerate() {
+            pi_evals[i] = -*x;
+        }
+        let pi = self.interpolate(&pi_evals);
+        let mut l1_evals = vec![Fr::zero(); n];
+        l1_evals[0] = Fr::one();
+        let l1 = self.interpolate(&l1_evals);
+
+        let gate = [
+            poly_mul(&poly_mul(a, b), &qm),
+            poly_mul(a, &ql),
+            poly_mul(b, &qr),
+            poly_mul(c, &qo),
+            qc.clone(),
+            pi,
+        ]
+        .iter()
+        .fold(vec![Fr::zero()], |acc, p| poly_add(&acc, p));
+
+        // (w + β·k·X + γ) and (w + β·sσ + γ)
+        let id_factor = |w: &[Fr], k: Fr| poly_add(&poly_add_const(w, gamma), &[Fr::zero(), beta * k]);
+        let sigma_factor = |w: &[Fr], s: &[Fr]| poly_add(&poly_add_const(w, gamma), &poly_scale(s, beta));
+        let z_shifted: Vec<Fr> = z.iter().enumerate().map(|(i, c)| *c * self.omega.pow([i as u64])).collect();
+        let perm_num = poly_mul(
+            &poly_mul(&poly_mul(&id_factor(a, ks[0]), &id_factor(b, ks[1])), &id_factor(c, ks[2])),
+            &z,
+        );
+        let perm_den = poly_mul(
+            &poly_mul(&poly_mul(&sigma_factor(a, &s1), &sigma_factor(b, &s2)), &sigma_factor(c, &s3)),
+            &z_shifted,
+        );
+        let perm = poly_add(&perm_num, &poly_scale(&perm_den, -Fr::one()));
+        let boundary = poly_mul(&poly_add_const(&z, -Fr::one()), &l1);
+
+        let numerator = poly_add(
+            &poly_add(&gate, &poly_scale(&perm, alpha)),
+            &poly_scale(&boundary, alpha * alpha),
+        );
+        let mut t = poly_div_vanishing(&numerator, n);
+        t.resize(3 * n, Fr::zero());
+        let t_parts = [&t[..n], &t[n..2 * n], &t[2 * n..]];
+        proof.quotient_commitments = t_parts.map(|p| self.commit(p));
+        let zeta = u256_to_fr(&compute_challenges(&proof, &vk, &inputs).zeta);
+
+        // Round 4: evaluations
+        let (a_z, b_z, c_z) = (poly_eval(a, zeta), poly_eval(b, zeta), poly_eval(c, zeta));
+        let (s1_z, s2_z) = (poly_eval(&s1, zeta), poly_eval(&s2, zeta));
+        let zw = poly_eval(&z, zeta * self.omega);
+        proof.wire_evals = [a_z, b_z, c_z].map(|e| fr_to_u256(&e));
+        proof.sigma_evals = [s1_z, s2_z].map(|e| fr_to_u256(&e));
+        proof.permutation_eval_omega = fr_to_u256(&zw);
+        let v = u256_to_fr(&compute_challenges(&proof, &vk, &inputs).v);
+
+        // Round 5: linearization R(X) with R(ζ) = 0, then the opening quotients.
+        // Remainders are dropped, so a false claim cannot be opened.
+        let zeta_n = zeta.pow([n as u64]);
+        let zh = zeta_n - Fr::one();
+        let l1_z = poly_eval(&l1, zeta);
+        let pi_z = poly_eval(&self.interpolate(&pi_evals), zeta);
+        let sigma_terms = (a_z + beta * s1_z + gamma) * (b_z + beta * s2_z + gamma);
+        let id_terms = (a_z + beta * zeta + gamma)
+            * (b_z + beta * self.k1 * zeta + gamma)
+            * (c_z + beta * self.k2 * zeta + gamma);
+
+        let quotient = poly_add(
+            &poly_add(t_parts[0], &poly_scale(t_parts[1], zeta_n)),
+            &poly_scale(t_parts[2], zeta_n * zeta_n),
+        );
+        let r = [
+            poly_scale(&qm, a_z * b_z),
+            poly_scale(&ql, a_z),
+            poly_scale(&qr, b_z),
+            poly_scale(&qo, c_z),
+            qc.clone(),
+            vec![pi_z - alpha * sigma_terms * (c_z + gamma) * zw - alpha * alpha * l1_z],
+            poly_scale(&z, alpha * id_terms + alpha * alpha * l1_z),
+            poly_scale(&s3, -alpha * sigma_terms * beta * zw),
+            poly_scale(&quotient, -zh),
+        ]
+        .iter()
+        .fold(vec![Fr::zero()], |acc, p| poly_add(&acc, p));
+
+        let opened = [(a, a_z), (b, b_z), (c, c_z), (&s1, s1_z), (&s2, s2_z)];
+        let mut numerator = r;
+        let mut v_i = v;
+        for (p, eval) in opened {
+            numerator = poly_add(&numerator, &poly_scale(&poly_add_const(p, -eval), v_i));
+            v_i *= v;
+        }
+        let w_zeta = poly_eval(&poly_div_linear(&numerator, zeta), self.tau);
+        let w_omega = poly_eval(&poly_div_linear(&poly_add_const(&z, -zw), zeta * self.omega), self.tau);
+        proof.opening_proof_zeta = g1(w_zeta);
+        proof.opening_proof_omega = g1(w_omega);
+
+        proof
+    }
+
+    pub fn proof_bytes(&self, witness: &Witness) -> Vec<u8> {
+        serialize_plonk_proof(&self.prove(witness))
+    }
+}
+
+pub(super) fn g1(scalar: Fr) -> [u8; 64] {
+    codec::g1_from_ark(&(G1Projective::from(G1Affine::generator()) * scalar).into_affine())
+}
@@ -0,0 +1,376 @@
+//! STARK proof fixture
+//!
+//! A two-register AIR over 64 rows:
+//!
+//! ```text
+//! x₀² − x₁ = 0            degree 2
+//! x₀' − x₀ − x₁ = 0       degree 1, reads the next row (not enforced on the last)
+//! x₁² − x₀⁴ = 0           degree 4, so the composition takes three columns
+//! x₀ = 2 at row 0, x₁ at row 63 public
+//! ```
+//!
+//! `Prover` is a DEEP-ALI prover with radix-2 FFTs. It interpolates the
+//! trace, builds the composition from `deep::constraint_composition` on a
+//! coset and commits its columns, evaluates the frame at `z`, then commits
+//! the FRI layers of the DEEP composition (computed pointwise, folded in
+//! coefficient form independently of `fri::fold`), grinds the proof-of-work
+//! nonce and opens the drawn positions. Its knobs produce the dishonest
+//! proofs the tests need.
+
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::hash::Hasher;
+use uzkv_stylus::plonk::transcript::Transcript;
+use uzkv_stylus::stark::deep::{composition_from_columns, constraint_composition};
+use uzkv_stylus::stark::fri::{domain_generator, evaluate_polynomial, pow_is_valid, query_positions, DOMAIN_OFFSET};
+use uzkv_stylus::stark::merkle::{hash_row, MerkleTree};
+use uzkv_stylus::stark::types::ConstraintTerm;
+use uzkv_stylus::stark::verifier::labels;
+use uzkv_stylus::stark::{
+    AirConstraint, BoundaryConstraint, FriParams, MerkleOpening, QueryOpening, SecurityLevel, StarkProof,
+    StarkVerificationKey,
+};
+use uzkv_stylus::utils::{batch_inverse, fr_mul, fr_add, Fr, BN254_SCALAR_MODULUS};
+
+pub const TRACE_WIDTH: usize = 2;
+pub const LOG_ROWS: usize = 6;
+pub const ROWS: usize = 1 << LOG_ROWS;
+
+/// 16× blowup, folding by 2 down to a cubic remainder
+pub const FRI: FriParams = FriParams { log_blowup: 4, log_folding: 1, num_layers: 4, remainder_degree: 3, grinding_bits: 4 };
+pub const LOG_DOMAIN: usize = LOG_ROWS + FRI.log_blowup as usize;
+
+fn term(coefficient: i64, offset: u32, register: u32, power: u32) -> ConstraintTerm {
+    let coefficient = if coefficient < 0 {
+        BN254_SCALAR_MODULUS - U256::from(coefficient.unsigned_abs())
+    } else {
+        U256::from(coefficient as u64)
+    };
+    ConstraintTerm { coefficient, offset, register, power }
+}
+
+/// The fixture AIR's transition constraints
+pub fn constraints() -> Vec<AirConstraint> {
+    vec![
+        AirConstraint { degree: 2, terms: vec![term(1, 0, 0, 2), term(-1, 0, 1, 1)] },
+        AirConstraint { degree: 1, terms: vec![term(1, 1, 0, 1), term(-1, 0, 0, 1), term(-1, 0, 1, 1)] },
+        AirConstraint { degree: 4, terms: vec![term(1, 0, 1, 2), term(-1, 0, 0, 4)] },
+    ]
+}
+
+/// `x₀` at the first row and `x₁` at the last
+pub fn boundaries() -> Vec<BoundaryConstraint> {
+    vec![BoundaryConstraint { register: 0, row: 0 }, BoundaryConstraint { register: 1, row: ROWS as u32 - 1 }]
+}
+
+/// VK bytes for the fixture AIR under `FRI`
+pub fn vk_bytes() -> Vec<u8> {
+    vk_bytes_with(&FRI)
+}
+
+pub fn vk_bytes_with(fri: &FriParams) -> Vec<u8> {
+    encode_vk(&constraints(), &boundaries(), fri)
+}
+
+/// VK bytes in the `StarkVerificationKey::from_bytes` format
+pub fn encode_vk(constraints: &[AirConstraint], boundaries: &[BoundaryConstraint], fri: &FriParams) -> Vec<u8> {
+    let mut vk = Vec::new();
+    vk.extend_from_slice(&(TRACE_WIDTH as u32).to_be_bytes());
+    vk.extend_from_slice(&(constraints.len() as u32).to_be_bytes());
+    for constraint in constraints {
+        vk.push(constraint.degree);
+        vk.extend_from_slice(&(constraint.terms.len() as u32).to_be_bytes());
+        for term in &constraint.terms {
+            vk.extend_from_slice(&term.coefficient.to_be_bytes::<32>());
+            vk.extend_from_slice(&term.offset.to_be_bytes());
+            vk.extend_from_slice(&term.register.to_be_bytes());
+            vk.extend_from_slice(&term.power.to_be_bytes());
+        }
+    }
+    vk.extend_from_slice(&(boundaries.len() as u32).to_be_bytes());
+    for boundary in boundaries {
+        vk.extend_from_slice(&boundary.register.to_be_bytes());
+        vk.extend_from_slice(&boundary.row.to_be_bytes());
+    }
+    vk.extend_from_slice(&[LOG_ROWS as u8, fri.log_blowup, fri.log_folding, fri.num_layers]);
+    vk.extend_from_slice(&fri.remainder_degree.to_be_bytes());
+    vk.push(fri.grinding_bits);
+    vk
+}
+
+/// Honest rows: `x₀ = 2, x₀' = x₀ + x₀²` and `x₁ = x₀²`
+pub fn trace() -> Vec<Vec<U256>> {
+    let mut x = U256::from(2);
+    (0..ROWS)
+        .map(|_| {
+            let square = fr_mul(x, x);
+            let row = vec![x, square];
+            x = fr_add(x, square);
+            row
+        })
+        .collect()
+}
+
+/// The boundary values `rows` assert, as 32-byte public inputs
+pub fn public_inputs(rows: &[Vec<U256>]) -> Vec<u8> {
+    [rows[0][0], rows[ROWS - 1][1]].iter().flat_map(|value| value.to_be_bytes::<32>()).collect()
+}
+
+fn fft(values: &[Fr], omega: Fr) -> Vec<Fr> {
+    let n = values.len();
+    if n == 1 {
+        return values.to_vec();
+    }
+    let even: Vec<Fr> = values.iter().step_by(2).copied().collect();
+    let odd: Vec<Fr> = values.iter().skip(1).step_by(2).copied().collect();
+    let (even, odd) = (fft(&even, omega.square()), fft(&odd, omega.square()));
+    let mut out = vec![Fr::ZERO; n];
+    let mut twiddle = Fr::ONE;
+    for i in 0..n / 2 {
+        let t = twiddle.mul(&odd[i]);
+        out[i] = even[i].add(&t);
+        out[i + n / 2] = even[i].sub(&t);
+        twiddle = twiddle.mul(&omega);
+    }
+    out
+}
+
+/// Values of `coefficients` on `offset·⟨ω⟩` of size `2^log_size`
+fn evaluate_on_coset(coefficients: &[Fr], offset: Fr, log_size: usize) -> Vec<Fr> {
+    let mut scaled = vec![Fr::ZERO; 1 << log_size];
+    let mut power = Fr::ONE;
+    for (scaled, c) in scaled.iter_mut().zip(coefficients) {
+        *scaled = c.mul(&power);
+        power = power.mul(&offset);
+    }
+    fft(&scaled, domain_generator(log_size as u32))
+}
+
+/// Coefficients of the polynomial of degree below `values.len()` taking
+/// `values` on `offset·⟨ω⟩`
+fn interpolate(values: &[Fr], offset: Fr) -> Vec<Fr> {
+    let log_size = values.len().trailing_zeros();
+    let mut coefficients = fft(values, domain_generator(log_size).inverse().unwrap());
+    let offset_inv = offset.inverse().unwrap();
+    let mut scale = Fr::from_u256_reduced(U256::from(values.len())).inverse().unwrap();
+    for c in &mut coefficients {
+        *c = c.mul(&scale);
+        scale = scale.mul(&offset_inv);
+    }
+    coefficients
+}
+
+/// The coset `offset·⟨ω⟩` of size `2^log_size`, in natural order
+fn coset(offset: Fr, log_size: usize) -> Vec<Fr> {
+    evaluate_on_coset(&[Fr::ZERO, Fr::ONE], offset, log_size)
+}
+
+/// `g(Y) = Σ_t βᵗ·f_t(Y)` for `f(X) = Σ_t Xᵗ·f_t(Xᵏ)`
+pub fn fold_coefficients(f: &[Fr], beta: Fr, k: usize) -> Vec<Fr> {
+    f.chunks(k)
+        .map(|chunk| chunk.iter().rev().fold(Fr::ZERO, |acc, c| acc.mul(&beta).add(c)))
+        .collect()
+}
+
+fn to_u256(values: &[Fr]) -> Vec<U256> {
+    values.iter().map(|v| v.to_u256()).collect()
+}
+
+/// What the prover claims at the out-of-domain point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OodClaim {
+    Honest,
+    /// `x₀(z·g) + 1`
+    Shifted,
+    /// `x₀(z·g) + 1`, with `H₀(z)` adjusted so the check at z still holds
+    ShiftedConsistently,
+}
+
+pub struct Prover {
+    pub fri: FriParams,
+    pub num_queries: usize,
+    /// Add one to every value of this FRI layer before committing it
+    pub shifted_layer: Option<usize>,
+    pub ood: OodClaim,
+}
+
+impl Prover {
+    /// Honest prover for `fri` at `Proven100`
+    pub fn new(fri: FriParams) -> Self {
+        Prover { fri, num_queries: SecurityLevel::Proven100.num_queries(&fri), shifted_layer: None, ood: OodClaim::Honest }
+    }
+
+    pub fn prove<H: Hasher>(&self, rows: &[Vec<U256>], public_inputs: &[u8]) -> StarkProof {
+        let vk = StarkVerificationKey::from_bytes(&vk_bytes_with(&self.fri)).unwrap();
+        let fri = &self.fri;
+        let k = fri.folding_factor();
+        let log_domain = LOG_ROWS + fri.log_blowup as usize;
+        let mut offset = Fr::from_u256_reduced(DOMAIN_OFFSET);
+        let boundary_values: Vec<Fr> =
+            public_inputs.chunks(32).map(|chunk| Fr::from_u256(U256::from_be_slice(chunk)).unwrap()).collect();
+        let (frame_rows, width) = (vk.frame_rows(), vk.composition_width());
+
+        // Trace polynomials and their extension over the evaluation domain
+        let columns: Vec<Vec<Fr>> = (0..TRACE_WIDTH)
+            .map(|r| interpolate(&rows.iter().map(|row| Fr::from_u256(row[r]).unwrap()).collect::<Vec<_>>(), Fr::ONE))
+            .collect();
+        let trace_lde: Vec<Vec<Fr>> = columns.iter().map(|c| evaluate_on_coset(c, offset, log_domain)).collect();
+        let trace_rows: Vec<Vec<U256>> =
+            (0..1 << log_domain).map(|i| trace_lde.iter().map(|c| c[i].to_u256()).collect()).collect();
+        let trace_tree = commit::<H>(&trace_rows);
+
+        let mut transcript = Transcript::<H>::with_hasher(labels::STARK_PROTOCOL);
+        transcript.absorb_bytes(labels::VERIFICATION_KEY, &vk.digest);
+        transcript.absorb_bytes(labels::PUBLIC_INPUT, public_inputs);
+        transcript.absorb_bytes(labels::TRACE_COMMITMENT, &trace_tree.root());
+        let alpha = Fr::from_u256_reduced(transcript.squeeze_challenge(labels::CONSTRAINT_CHALLENGE));
+
+        // Quotients on a coset of at least width·n points, interpolated and
+        // split into columns of n coefficients; an unsatisfied trace leaves
+        // coefficients past width·n, which are dropped
+        let log_quotient = (width * ROWS).next_power_of_two().trailing_zeros() as usize;
+        let quotient_trace: Vec<Vec<Fr>> = columns.iter().map(|c| evaluate_on_coset(c, offset, log_quotient)).collect();
+        let step = 1 << (log_quotient - LOG_ROWS);
+        let quotients: Vec<Fr> = coset(offset, log_quotient)
+            .into_iter()
+            .enumerate()
+            .map(|(i, x)| {
+                let frame: Vec<U256> = (0..frame_rows)
+                    .flat_map(|row| quotient_trace.iter().map(move |c| c[(i + row * step) % c.len()].to_u256()))
+                    .collect();
+                constraint_composition(&vk, &frame, x, alpha, &boundary_values).unwrap()
+            })
+            .collect();
+        let mut quotient = interpolate(&quotients, offset);
+        quotient.truncate(width * ROWS);
+        let composition: Vec<Vec<Fr>> = quotient.chunks(ROWS).map(<[Fr]>::to_vec).collect();
+        let composition_lde: Vec<Vec<Fr>> = composition.iter().map(|c| evaluate_on_coset(c, offset, log_domain)).collect();
+        let composition_rows: Vec<Vec<U256>> =
+            (0..1 << log_domain).map(|i| composition_lde.iter().map(|c| c[i].to_u256()).collect()).collect();
+        let composition_tree = commit::<H>(&composition_rows);
+
+        transcript.absorb_bytes(labels::COMPOSITION_COMMITMENT, &composition_tree.root());
+        let z = Fr::from_u256_reduced(transcript.squeeze_challenge(labels::OOD_POINT));
+
+        // Frame at z·gᵏ and the composition columns at z
+        let g = domain_generator(LOG_ROWS as u32);
+        let frame_points: Vec<Fr> = (0..frame_rows).map(|row| z.mul(&g.pow(U256::from(row)))).collect();
+        let mut ood_trace: Vec<Vec<Fr>> =
+            frame_points.iter().map(|point| columns.iter().map(|c| evaluate_polynomial(c, *point)).collect()).collect();
+        let mut ood_composition: Vec<Fr> = composition.iter().map(|c| evaluate_polynomial(c, z)).collect();
+        if self.ood != OodClaim::Honest {
+            ood_trace[1][0] = ood_trace[1][0].add(&Fr::ONE);
+        }
+        if self.ood == OodClaim::ShiftedConsistently {
+            let frame: Vec<U256> = ood_trace.iter().flat_map(|row| to_u256(row)).collect();
+            let expected = constraint_composition(&vk, &frame, z, alpha, &boundary_values).unwrap();
+            ood_composition[0] = Fr::ZERO;
+            let rest = composition_from_columns(&to_u256(&ood_composition), z, LOG_ROWS as u32);
+            ood_composition[0] = expected.sub(&rest);
+        }
+        for value in ood_trace.iter().flatten().chain(&ood_composition) {
+            transcript.absorb_field(labels::OOD_EVAL, &value.to_u256());
+        }
+        let gamma = Fr::from_u256_reduced(transcript.squeeze_challenge(labels::DEEP_CHALLENGE));
+
+        // DEEP composition over the evaluation domain, then its coefficients
+        let points = coset(offset, log_domain);
+        let mut inverses: Vec<Fr> =
+            points.iter().flat_map(|x| frame_points.iter().map(move |point| x.sub(point))).collect();
+        batch_inverse(&mut inverses);
+        let codeword: Vec<Fr> = (0..points.len())
+            .map(|i| {
+                let inverses = &inverses[i * frame_rows..(i + 1) * frame_rows];
+                let (mut sum, mut weight) = (Fr::ZERO, Fr::ONE);
+                for (claimed, inverse) in ood_trace.iter().zip(inverses) {
+                    for (column, claimed) in trace_lde.iter().zip(claimed) {
+                        sum = sum.add(&weight.mul(&column[i].sub(claimed)).mul(inverse));
+                        weight = weight.mul(&gamma);
+                    }
+                }
+                for (column, claimed) in composition_lde.iter().zip(&ood_composition) {
+                    sum = sum.add(&weight.mul(&column[i].sub(claimed)).mul(&inverses[0]));
+                    weight = weight.mul(&gamma);
+                }
+                sum
+            })
+            .collect();
+        let mut f = interpolate(&codeword, offset);
+
+        // Leaf j of a layer holds the k values at j + t·(size / k)
+        let mut layers: Vec<(Vec<Vec<U256>>, MerkleTree)> = Vec::new();
+        let mut log_size = log_domain;
+        for layer in 0..fri.num_layers as usize {
+            let mut codeword = evaluate_on_coset(&f, offset, log_size);
+            if self.shifted_layer == Some(layer) {
+                codeword.iter_mut().for_each(|value| *value = value.add(&Fr::ONE));
+            }
+            let stride = codeword.len() / k;
+            let leaves: Vec<Vec<U256>> =
+                (0..stride).map(|j| (0..k).map(|t| codeword[j + t * stride].to_u256()).collect()).collect();
+            let tree = commit::<H>(&leaves);
+
+            transcript.absorb_bytes(labels::FRI_LAYER, &tree.root());
+            let beta = Fr::from_u256_reduced(transcript.squeeze_challenge(labels::FRI_FOLD));
+            f = fold_coefficients(&f, beta, k);
+            layers.push((leaves, tree));
+            offset = offset.pow(U256::from(k));
+            log_size -= fri.log_folding as usize;
+        }
+
+        // Truncated when the DEEP composition is of too high a degree
+        let mut fri_remainder = to_u256(&f);
+        fri_remainder.resize(fri.remainder_degree as usize + 1, U256::ZERO);
+        for coefficient in &fri_remainder {
+            transcript.absorb_field(labels::FRI_REMAINDER, coefficient);
+        }
+
+        let seed = transcript.squeeze_challenge(labels::POW_SEED);
+        let pow_nonce = (0..).find(|nonce| pow_is_valid::<H>(&seed, *nonce, fri.grinding_bits)).unwrap();
+        transcript.absorb_bytes(labels::POW_NONCE, &pow_nonce.to_be_bytes());
+
+        let open = |tree: &MerkleTree, rows: &[Vec<U256>], index: usize| MerkleOpening {
+            values: rows[index].clone(),
+            path: tree.proof(index).unwrap().siblings,
+        };
+        let queries = query_positions(&mut transcript, self.num_queries, log_domain as u32)
+            .into_iter()
+            .map(|position| {
+                let mut leaf = position;
+                QueryOpening {
+                    position: position as u32,
+                    trace: open(&trace_tree, &trace_rows, position),
+                    composition: open(&composition_tree, &composition_rows, position),
+                    fri_layers: layers
+                        .iter()
+                        .map(|(leaves, tree)| {
+                            leaf %= leaves.len();
+                            open(tree, leaves, leaf)
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+
+        StarkProof {
+            trace_commitment: trace_tree.root(),
+            composition_commitment: composition_tree.root(),
+            ood_trace: ood_trace.iter().map(|row| to_u256(row)).collect(),
+            ood_composition: to_u256(&ood_composition),
+            fri_commitments: layers.iter().map(|(_, tree)| tree.root()).collect(),
+            fri_remainder,
+            pow_nonce,
+            queries,
+        }
+    }
+}
+
+/// Honest proof of `rows` (with the public inputs they assert) under `FRI`
+/// with `num_queries` queries
+pub fn prove<H: Hasher>(rows: &[Vec<U256>], num_queries: usize) -> StarkProof {
+    Prover { num_queries, ..Prover::new(FRI) }.prove::<H>(rows, &public_inputs(rows))
+}
+
+fn commit<H: Hasher>(rows: &[Vec<U256>]) -> MerkleTree {
+    let leaves: Vec<[u8; 32]> = rows.iter().map(|row| hash_row::<H>(row)).collect();
+    MerkleTree::new_with::<H>(&leaves)
+}
@@ -0,0 +1,388 @@
+//! UltraPLONK test prover
+//!
+//! An (unblinded) UltraPLONK prover for one small circuit with custom gates
+//! and a two-column lookup table. As in `plonk`, polynomials are kept in
+//! coefficient form and commitments are evaluated at the trapdoor τ; opening
+//! quotients drop their remainder, so an unsatisfied witness or a tampered
+//! proof still fails verification.
+//!
+//! Circuit, on 16 rows, with the table `(i, i²)` for `i < 16`:
+//! ```text
+//! row 0: a = y                  q_L = 1 (public input)
+//! row 1: a = x,  b = x²         q_lookup = 1
+//! row 2: a = x², b = x,  c = y  q_L = q_R = 1, q_O = −1
+//! row 3: c = bit                q_bool·(c² − c) = 0, q_next·(a(ωX) − c) = 0
+//! row 4: a = bit
+//! ```
+//! so `y = x² + x` for some `x < 16`.
+
+use ark_bn254::{Fr, G2Affine, G2Projective};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{FftField, Field, One, Zero};
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::groth16::codec;
+use uzkv_stylus::hash::Hasher;
+use uzkv_stylus::plonk::ultra::{
+    compute_challenges_with, serialize_ultra_proof, serialize_ultra_vk, CustomGate, GateTerm, UltraProof,
+    UltraVerificationKey, WireQuery,
+};
+
+use super::plonk::{fr_to_u256, g1, poly_add, poly_add_const, poly_div_linear, poly_div_vanishing, poly_eval, poly_mul, poly_scale, u256_to_fr};
+
+pub const N: usize = 16;
+pub const NUM_WIRES: usize = 3;
+pub const TABLE_WIDTH: usize = 2;
+
+// Selectors
+const Q_M: usize = 0;
+const Q_L: usize = 1;
+const Q_R: usize = 2;
+const Q_O: usize = 3;
+const Q_C: usize = 4;
+const Q_BOOL: usize = 5;
+const Q_NEXT: usize = 6;
+const Q_LOOKUP: usize = 7;
+pub const NUM_SELECTORS: usize = 8;
+
+/// Copy cycles over (wire, row)
+const COPIES: [&[(usize, usize)]; 3] = [&[(0, 1), (1, 2)], &[(1, 1), (0, 2)], &[(2, 2), (0, 0)]];
+
+/// Number of quotient pieces (the permutation over 3 wires)
+pub const QUOTIENT_PIECES: usize = 3;
+
+fn term(coefficient: Fr, selector: usize, factors: &[(usize, bool)]) -> GateTerm {
+    GateTerm {
+        coefficient: fr_to_u256(&coefficient),
+        selector,
+        factors: factors.iter().map(|&(wire, next)| WireQuery { wire, next }).collect(),
+    }
+}
+
+/// Arithmetic gate, boolean gate and the next-row copy
+pub fn gates() -> Vec<CustomGate> {
+    let (one, minus_one) = (Fr::one(), -Fr::one());
+    let arithmetic = vec![
+        term(one, Q_M, &[(0, false), (1, false)]),
+        term(one, Q_L, &[(0, false)]),
+        term(one, Q_R, &[(1, false)]),
+        term(one, Q_O, &[(2, false)]),
+        term(one, Q_C, &[]),
+    ];
+    let boolean = vec![term(one, Q_BOOL, &[(2, false), (2, false)]), term(minus_one, Q_BOOL, &[(2, false)])];
+    let next = vec![term(one, Q_NEXT, &[(0, true)]), term(minus_one, Q_NEXT, &[(2, false)])];
+    [arithmetic, boolean, next].into_iter().map(|terms| CustomGate { terms }).collect()
+}
+
+fn poly_sub(a: &[Fr], b: &[Fr]) -> Vec<Fr> {
+    poly_add(a, &poly_scale(b, -Fr::one()))
+}
+
+/// Selectors, permutation, table and the SRS trapdoor
+pub struct UltraFixture {
+    omega: Fr,
+    tau: Fr,
+    shifts: [Fr; NUM_WIRES],
+    selectors: Vec<Vec<Fr>>,
+    sigmas: Vec<Vec<Fr>>,
+    table: Vec<Vec<Fr>>,
+}
+
+/// Wire values over the domain and the public input `y`
+#[derive(Clone)]
+pub struct Witness {
+    pub wires: Vec<Vec<Fr>>,
+    pub public: Vec<Fr>,
+}
+
+impl UltraFixture {
+    pub fn new(tau: Fr) -> Self {
+        let omega = Fr::get_root_of_unity(N as u64).unwrap();
+        let one = Fr::one();
+
+        let mut selectors = vec![vec![Fr::zero(); N]; NUM_SELECTORS];
+        selectors[Q_L][0] = one;
+        selectors[Q_LOOKUP][1] = one;
+        (selectors[Q_L][2], selectors[Q_R][2], selectors[Q_O][2]) = (one, one, -one);
+        (selectors[Q_BOOL][3], selectors[Q_NEXT][3]) = (one, one);
+
+        let shifts = [one, Fr::from(2u64), Fr::from(3u64)];
+        let id = |wire: usize, row: usize| shifts[wire] * omega.pow([row as u64]);
+        let mut sigmas: Vec<Vec<Fr>> = (0..NUM_WIRES).map(|wire| (0..N).map(|row| id(wire, row)).collect()).collect();
+        for cycle in COPIES {
+            for (i, &(wire, row)) in cycle.iter().enumerate() {
+                let (next_wire, next_row) = cycle[(i + 1) % cycle.len()];
+                sigmas[wire][row] = id(next_wire, next_row);
+            }
+        }
+
+        let table = vec![
+            (0..N as u64).map(Fr::from).collect(),
+            (0..N as u64).map(|i| Fr::from(i * i)).collect(),
+        ];
+
+        Self { omega, tau, shifts, selectors, sigmas, table }
+    }
+
+    /// Honest witness for `x`, with the bit set
+    pub fn witness(&self, x: Fr) -> Witness {
+        let y = x * x + x;
+        let mut wires = vec![vec![Fr::zero(); N]; NUM_WIRES];
+        wires[0][0] = y;
+        (wires[0][1], wires[1][1]) = (x, x * x);
+        (wires[0][2], wires[1][2], wires[2][2]) = (x * x, x, y);
+        wires[2][3] = Fr::one();
+        wires[0][4] = Fr::one();
+        Witness { wires, public: vec![y] }
+    }
+
+    pub fn public_bytes(&self, witness: &Witness) -> Vec<u8> {
+        codec::inputs_from_ark(&witness.public)
+    }
+
+    /// Inverse DFT over the domain
+    fn interpolate(&self, evals: &[Fr]) -> Vec<Fr> {
+        let n_inv = Fr::from(N as u64).inverse().unwrap();
+        let omega_inv = self.omega.inverse().unwrap();
+        (0..N)
+            .map(|j| {
+                let w = omega_inv.pow([j as u64]);
+                evals.iter().enumerate().map(|(i, y)| *y * w.pow([i as u64])).sum::<Fr>() * n_inv
+            })
+            .collect()
+    }
+
+    /// p(ωX)
+    fn shift(&self, poly: &[Fr]) -> Vec<Fr> {
+        let mut power = Fr::one();
+        poly.iter()
+            .map(|c| {
+                let out = *c * power;
+                power *= self.omega;
+                out
+            })
+            .collect()
+    }
+
+    fn commit(&self, poly: &[Fr]) -> [u8; 64] {
+        g1(poly_eval(poly, self.tau))
+    }
+
+    fn lagrange(&self, row: usize) -> Vec<Fr> {
+        let mut evals = vec![Fr::zero(); N];
+        evals[row] = Fr::one();
+        self.interpolate(&evals)
+    }
+
+    pub fn verifying_key(&self) -> UltraVerificationKey {
+        let commit_all = |columns: &[Vec<Fr>]| columns.iter().map(|c| self.commit(&self.interpolate(c))).collect();
+        UltraVerificationKey {
+            n: N,
+            num_public_inputs: 1,
+            num_wires: NUM_WIRES,
+            lookup_selector: Q_LOOKUP,
+            gates: gates(),
+            selector_commitments: commit_all(&self.selectors),
+            permutation_commitments: commit_all(&self.sigmas),
+            table_commitments: commit_all(&self.table),
+            omega: fr_to_u256(&self.omega),
+            coset_shifts: self.shifts[1..].iter().map(fr_to_u256).collect(),
+        }
+    }
+
+    /// VK as registered with `registerPlonkVk`
+    pub fn vk_without_srs(&self) -> Vec<u8> {
+        serialize_ultra_vk(&self.verifying_key())
+    }
+
+    /// VK bytes followed by `[τ]₂`, as `ultra::verify` expects
+    pub fn vk_bytes(&self) -> Vec<u8> {
+        let mut out = self.vk_without_srs();
+        let srs = (G2Projective::from(G2Affine::generator()) * self.tau).into_affine();
+        out.extend_from_slice(&codec::g2_from_ark(&srs));
+        out
+    }
+
+    /// Run the prover; an unsatisfied witness still yields a (bogus) proof
+    pub fn prove<H: Hasher>(&self, witness: &Witness) -> UltraProof {
+        let one = Fr::one();
+        let vk = self.verifying_key();
+        let inputs: Vec<U256> = witness.public.iter().map(fr_to_u256).collect();
+        let challenges = |proof: &UltraProof| compute_challenges_with::<H>(proof, &vk, &inputs);
+        let point = [0u8; 64];
+        let mut proof = UltraProof {
+            wire_commitments: vec![point; NUM_WIRES],
+            sorted_commitments: [point; 2],
+            permutation_commitment: point,
+            lookup_commitment: point,
+            quotient_commitments: vec![point; QUOTIENT_PIECES],
+            opening_proof_zeta: point,
+            opening_proof_omega: point,
+            wire_evals: vec![U256::ZERO; NUM_WIRES],
+            wire_evals_omega: vec![U256::ZERO; NUM_WIRES],
+            selector_evals: vec![U256::ZERO; NUM_SELECTORS],
+            sigma_evals: vec![U256::ZERO; NUM_WIRES],
+            table_evals: vec![U256::ZERO; TABLE_WIDTH],
+            table_evals_omega: vec![U256::ZERO; TABLE_WIDTH],
+            sorted_evals: [U256::ZERO; 2],
+            sorted_evals_omega: [U256::ZERO; 2],
+            permutation_eval: U256::ZERO,
+            permutation_eval_omega: U256::ZERO,
+            lookup_eval: U256::ZERO,
+            lookup_eval_omega: U256::ZERO,
+        };
+
+        // Round 1: wires
+        let wires: Vec<Vec<Fr>> = witness.wires.iter().map(|w| self.interpolate(w)).collect();
+        proof.wire_commitments = wires.iter().map(|w| self.commit(w)).collect();
+        let eta = u256_to_fr(&challenges(&proof).eta);
+
+        // Round 2: h1 ‖ h2 = f ∪ t sorted by t; values missing from the table go last
+        let q_lookup = &self.selectors[Q_LOOKUP];
+        let t_evals: Vec<Fr> = (0..N).map(|i| self.table[0][i] + eta * self.table[1][i]).collect();
+        let f_evals: Vec<Fr> = (0..N)
+            .map(|i| {
+                let looked_up = witness.wires[0][i] + eta * witness.wires[1][i];
+                q_lookup[i] * (looked_up - t_evals[i]) + t_evals[i]
+            })
+            .collect();
+        let mut sorted = Vec::with_capacity(2 * N - 1);
+        for t in &t_evals {
+            sorted.push(*t);
+            sorted.extend(f_evals[..N - 1].iter().filter(|f| *f == t));
+        }
+        sorted.extend(f_evals[..N - 1].iter().filter(|f| !t_evals.contains(f)));
+        let (h1_evals, h2_evals) = (&sorted[..N], &sorted[N - 1..]);
+        let h1 = self.interpolate(h1_evals);
+        let h2 = self.interpolate(h2_evals);
+        proof.sorted_commitments = [self.commit(&h1), self.commit(&h2)];
+        let ch = challenges(&proof);
+        let (beta, gamma) = (u256_to_fr(&ch.beta), u256_to_fr(&ch.gamma));
+
+        // Round 3: grand products, both starting at 1
+        let domain: Vec<Fr> = (0..N).map(|i| self.omega.pow([i as u64])).collect();
+        let one_plus_beta = one + beta;
+        let gamma_beta = gamma * one_plus_beta;
+        let mut z_evals = vec![one; N];
+        let mut z_lookup_evals = vec![one; N];
+        for i in 0..N - 1 {
+            let mut num = one;
+            let mut den = one;
+            for wire in 0..NUM_WIRES {
+                let w = witness.wires[wire][i];
+                num *= w + beta * self.shifts[wire] * domain[i] + gamma;
+                den *= w + beta * self.sigmas[wire][i] + gamma;
+            }
+            z_evals[i + 1] = z_evals[i] * num * den.inverse().unwrap();
+
+            let num = one_plus_beta * (gamma + f_evals[i]) * (gamma_beta + t_evals[i] + beta * t_evals[i + 1]);
+            let den = (gamma_beta + h1_evals[i] + beta * h1_evals[i + 1])
+                * (gamma_beta + h2_evals[i] + beta * h2_evals[i + 1]);
+            z_lookup_evals[i + 1] = z_lookup_evals[i] * num * den.inverse().unwrap();
+        }
+        let z = self.interpolate(&z_evals);
+        let z_lookup = self.interpolate(&z_lookup_evals);
+        proof.permutation_commitment = self.commit(&z);
+        proof.lookup_commitment = self.commit(&z_lookup);
+        let alpha = u256_to_fr(&challenges(&proof).alpha);
+
+        // Round 4: quotient of Σ αⁱ·Cᵢ
+        let selectors: Vec<Vec<Fr>> = self.selectors.iter().map(|s| self.interpolate(s)).collect();
+        let sigmas: Vec<Vec<Fr>> = self.sigmas.iter().map(|s| self.interpolate(s)).collect();
+        let table: Vec<Vec<Fr>> = self.table.iter().map(|t| self.interpolate(t)).collect();
+        let wires_shifted: Vec<Vec<Fr>> = wires.iter().map(|w| self.shift(w)).collect();
+        let mut constraints: Vec<Vec<Fr>> = vk
+            .gates
+            .iter()
+            .map(|gate| {
+                gate.terms.iter().fold(vec![Fr::zero()], |acc, term| {
+                    let product = term.factors.iter().fold(selectors[term.selector].clone(), |p, f| {
+                        poly_mul(&p, if f.next { &wires_shifted[f.wire] } else { &wires[f.wire] })
+                    });
+                    poly_add(&acc, &poly_scale(&product, u256_to_fr(&term.coefficient)))
+                })
+            })
+            .collect();
+        let mut pi_evals = vec![Fr::zero(); N];
+        for (i, x) in witness.public.iter().enumerate() {
+            pi_evals[i] = -*x;
+        }
+        constraints[0] = poly_add(&constraints[0], &self.interpolate(&pi_evals));
+
+        let (mut id_product, mut sigma_product) = (z.clone(), self.shift(&z));
+        for wire in 0..NUM_WIRES {
+            let w = poly_add_const(&wires[wire], gamma);
+            id_product = poly_mul(&id_product, &poly_add(&w, &[Fr::zero(), beta * self.shifts[wire]]));
+            sigma_product = poly_mul(&sigma_product, &poly_add(&w, &poly_scale(&sigmas[wire], beta)));
+        }
+        let l0 = self.lagrange(0);
+        let l_last = self.lagrange(N - 1);
+        constraints.push(poly_sub(&sigma_product, &id_product));
+        constraints.push(poly_mul(&l0, &poly_add_const(&z, -one)));
+
+        let t = poly_add(&table[0], &poly_scale(&table[1], eta));
+        let looked_up = poly_add(&wires[0], &poly_scale(&wires[1], eta));
+        let f = poly_add(&poly_mul(&selectors[Q_LOOKUP], &poly_sub(&looked_up, &t)), &t);
+        let pair = |x: &[Fr]| poly_add_const(&poly_add(x, &poly_scale(&self.shift(x), beta)), gamma_beta);
+        let lookup_left = poly_mul(
+            &poly_mul(&poly_scale(&z_lookup, one_plus_beta), &poly_add_const(&f, gamma)),
+            &pair(&t),
+        );
+        let lookup_right = poly_mul(&poly_mul(&self.shift(&z_lookup), &pair(&h1)), &pair(&h2));
+        let last_row = [-domain[N - 1], one];
+        constraints.push(poly_mul(&last_row, &poly_sub(&lookup_left, &lookup_right)));
+        constraints.push(poly_mul(&l0, &poly_add_const(&z_lookup, -one)));
+        constraints.push(poly_mul(&l_last, &poly_sub(&h1, &self.shift(&h2))));
+        constraints.push(poly_mul(&l_last, &poly_add_const(&z_lookup, -one)));
+
+        let numerator = constraints.iter().rev().fold(vec![Fr::zero()], |acc, c| poly_add(&poly_scale(&acc, alpha), c));
+        let mut quotient = poly_div_vanishing(&numerator, N);
+        quotient.resize(QUOTIENT_PIECES * N, Fr::zero());
+        let pieces: Vec<&[Fr]> = quotient.chunks(N).collect();
+        proof.quotient_commitments = pieces.iter().map(|p| self.commit(p)).collect();
+        let zeta = u256_to_fr(&challenges(&proof).zeta);
+        let zeta_omega = zeta * self.omega;
+
+        // Round 5: evaluations
+        let eval = |p: &[Fr], x: Fr| fr_to_u256(&poly_eval(p, x));
+        let evals = |polys: &[Vec<Fr>], x: Fr| polys.iter().map(|p| eval(p, x)).collect::<Vec<_>>();
+        proof.wire_evals = evals(&wires, zeta);
+        proof.wire_evals_omega = evals(&wires, zeta_omega);
+        proof.selector_evals = evals(&selectors, zeta);
+        proof.sigma_evals = evals(&sigmas, zeta);
+        proof.table_evals = evals(&table, zeta);
+        proof.table_evals_omega = evals(&table, zeta_omega);
+        proof.sorted_evals = [eval(&h1, zeta), eval(&h2, zeta)];
+        proof.sorted_evals_omega = [eval(&h1, zeta_omega), eval(&h2, zeta_omega)];
+        (proof.permutation_eval, proof.permutation_eval_omega) = (eval(&z, zeta), eval(&z, zeta_omega));
+        (proof.lookup_eval, proof.lookup_eval_omega) = (eval(&z_lookup, zeta), eval(&z_lookup, zeta_omega));
+        let v = u256_to_fr(&challenges(&proof).v);
+
+        // Round 6: openings, in the verifier's order (shifted, unshifted, [T])
+        let zeta_n = zeta.pow([N as u64]);
+        let combined_quotient = pieces
+            .iter()
+            .rev()
+            .fold(vec![Fr::zero()], |acc, p| poly_add(&poly_scale(&acc, zeta_n), p));
+        let shifted: Vec<&Vec<Fr>> = wires.iter().chain(&table).chain([&h1, &h2, &z, &z_lookup]).collect();
+        let (mut at_zeta, mut at_omega) = (vec![Fr::zero()], vec![Fr::zero()]);
+        let mut v_i = one;
+        for p in &shifted {
+            at_zeta = poly_add(&at_zeta, &poly_scale(p, v_i));
+            at_omega = poly_add(&at_omega, &poly_scale(p, v_i));
+            v_i *= v;
+        }
+        for p in selectors.iter().chain(&sigmas).chain([&combined_quotient]) {
+            at_zeta = poly_add(&at_zeta, &poly_scale(p, v_i));
+            v_i *= v;
+        }
+        let open = |p: &[Fr], x: Fr| g1(poly_eval(&poly_div_linear(&poly_add_const(p, -poly_eval(p, x)), x), self.tau));
+        proof.opening_proof_zeta = open(&at_zeta, zeta);
+        proof.opening_proof_omega = open(&at_omega, zeta_omega);
+
+        proof
+    }
+
+    pub fn proof_bytes<H: Hasher>(&self, witness: &Witness) -> Vec<u8> {
+        serialize_ultra_proof(&self.prove::<H>(witness))
+    }
+}
@@ -0,0 +1,178 @@
+//! Curve Backend Tests
+//!
+//! `ArkBackend` must follow the precompile semantics exactly, and the generic
+//! Groth16 and KZG verifiers must give the arkworks reference answers when run
+//! on it.
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use ark_bn254::{Fq2, Fr, G1Affine, G1Projective, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, Field, PrimeField, UniformRand};
+use ark_std::test_rng;
+use common::*;
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::backend::{ArkBackend, BackendError, Bn254Backend};
+use uzkv_stylus::groth16::{
+    batch_verify_host, codec, compute_precomputed_pairing, generic, verify_host, MsmBackend,
+    PreparedVerifyingKey,
+};
+use uzkv_stylus::plonk::kzg;
+
+fn to_u256(scalar: &Fr) -> U256 {
+    U256::from_be_slice(&scalar.into_bigint().to_bytes_be())
+}
+
+fn pair(g1: &G1Affine, g2: &G2Affine) -> Vec<u8> {
+    let mut out = codec::g1_from_ark(g1).to_vec();
+    out.extend_from_slice(&codec::g2_from_ark(g2));
+    out
+}
+
+#[test]
+fn test_ark_backend_matches_arkworks() {
+    let mut rng = test_rng();
+    let p = G1Affine::rand(&mut rng);
+    let q = G1Affine::rand(&mut rng);
+    let s = Fr::rand(&mut rng);
+    let (ep, eq) = (codec::g1_from_ark(&p), codec::g1_from_ark(&q));
+
+    let sum = (G1Projective::from(p) + q).into_affine();
+    assert_eq!(ArkBackend.g1_add(&ep, &eq).unwrap(), codec::g1_from_ark(&sum));
+    assert_eq!(ArkBackend.g1_mul(&ep, to_u256(&s)).unwrap(), codec::g1_from_ark(&(p * s).into_affine()));
+
+    // Identity in and out, and scalars are not reduced first: r·P = O
+    let zero = [0u8; 64];
+    assert_eq!(ArkBackend.g1_add(&ep, &zero).unwrap(), ep);
+    let neg_p = codec::g1_from_ark(&(-p));
+    assert_eq!(ArkBackend.g1_add(&ep, &neg_p).unwrap(), zero);
+    let r = U256::from_be_slice(&Fr::MODULUS.to_bytes_be());
+    assert_eq!(ArkBackend.g1_mul(&ep, r).unwrap(), zero);
+    assert_eq!(ArkBackend.g1_mul(&ep, r + U256::from(1)).unwrap(), ep);
+
+    // e(aP, Q) · e(-P, aQ) == 1, and empty input is trivially true
+    let g2 = G2Affine::rand(&mut rng);
+    let a = Fr::rand(&mut rng);
+    let mut pairs = pair(&(p * a).into_affine(), &g2);
+    pairs.extend(pair(&(-p), &(g2 * a).into_affine()));
+    assert_eq!(ArkBackend.pairing_check(&pairs), Ok(true));
+    pairs.extend(pair(&p, &g2));
+    assert_eq!(ArkBackend.pairing_check(&pairs), Ok(false));
+    assert_eq!(ArkBackend.pairing_check(&[]), Ok(true));
+}
+
+#[test]
+fn test_ark_backend_rejects_what_precompiles_reject() {
+    let mut rng = test_rng();
+    let p = codec::g1_from_ark(&G1Affine::rand(&mut rng));
+    let g2 = codec::g2_from_ark(&G2Affine::rand(&mut rng));
+
+    // Off the curve
+    let mut off = p;
+    off[63] ^= 1;
+    assert_eq!(ArkBackend.g1_add(&p, &off), Err(BackendError));
+    assert_eq!(ArkBackend.g1_mul(&off, U256::from(2)), Err(BackendError));
+
+    // Coordinate >= p
+    let mut big = [0xffu8; 64];
+    big[..32].copy_from_slice(&p[..32]);
+    assert_eq!(ArkBackend.g1_mul(&big, U256::from(2)), Err(BackendError));
+
+    // Wrong lengths
+    assert_eq!(ArkBackend.g1_add(&p[..63], &p), Err(BackendError));
+    let mut pairs = p.to_vec();
+    pairs.extend_from_slice(&g2);
+    assert_eq!(ArkBackend.pairing_check(&pairs[..191]), Err(BackendError));
+
+    // G2 on the twist but outside the subgroup
+    let outside = loop {
+        let x = Fq2::rand(&mut rng);
+        if let Some(point) = G2Affine::get_point_from_x_unchecked(x, false) {
+            if !point.is_in_correct_subgroup_assuming_on_curve() {
+                break point;
+            }
+        }
+    };
+    pairs[64..].copy_from_slice(&codec::g2_from_ark(&outside));
+    assert_eq!(ArkBackend.pairing_check(&pairs), Err(BackendError));
+}
+
+#[test]
+fn test_generic_groth16_on_ark_backend() {
+    let mut rng = test_rng();
+    let fixture = Fixture::new(3, &mut rng);
+    let vk = vk_to_evm(&fixture.vk);
+    let precomputed = compute_precomputed_pairing(&vk).unwrap();
+    let prepared = PreparedVerifyingKey::from_vk_bytes(&vk).unwrap();
+    let x = random_inputs(3, &mut rng);
+    let proof = proof_to_evm(&fixture.prove(&x, &mut rng));
+    let inputs = inputs_to_evm(&x);
+
+    let mut wrong_inputs = inputs.clone();
+    wrong_inputs[31] ^= 1;
+
+    for public_inputs in [&inputs, &wrong_inputs] {
+        let expected = verify_host(&proof, public_inputs, &vk);
+        assert_eq!(generic::verify(&ArkBackend, &proof, public_inputs, &vk), expected);
+        assert_eq!(
+            generic::verify_with_precomputed(&ArkBackend, &proof, public_inputs, &vk, &precomputed),
+            expected
+        );
+        for msm in [MsmBackend::Precompile, MsmBackend::Native] {
+            assert_eq!(
+                generic::verify_prepared_with_msm(&ArkBackend, &proof, public_inputs, &prepared, msm),
+                expected
+            );
+        }
+    }
+    assert_eq!(generic::verify(&ArkBackend, &proof, &inputs, &vk), Ok(true));
+}
+
+#[test]
+fn test_generic_groth16_batch_on_ark_backend() {
+    let mut rng = test_rng();
+    let fixture = Fixture::new(2, &mut rng);
+    let vk = vk_to_evm(&fixture.vk);
+
+    let mut proofs = Vec::new();
+    let mut inputs = Vec::new();
+    for _ in 0..4 {
+        let x = random_inputs(2, &mut rng);
+        proofs.push(proof_to_evm(&fixture.prove(&x, &mut rng)));
+        inputs.push(inputs_to_evm(&x));
+    }
+    assert_eq!(generic::batch_verify(&ArkBackend, &proofs, &inputs, &vk, &[]), Ok(vec![true; 4]));
+
+    inputs[1][31] ^= 1;
+    let expected = batch_verify_host(&proofs, &inputs, &vk, &[]);
+    assert_eq!(expected, Ok(vec![true, false, true, true]));
+    assert_eq!(generic::batch_verify(&ArkBackend, &proofs, &inputs, &vk, &[]), expected);
+}
+
+#[test]
+fn test_kzg_opening_on_ark_backend() {
+    let mut rng = test_rng();
+    let g1 = G1Affine::generator();
+    let tau = Fr::rand(&mut rng);
+    let srs_g2 = codec::g2_from_ark(&(G2Affine::generator() * tau).into_affine());
+
+    // p(X) = c0 + c1·X + c2·X²; q(X) = (p(X) - p(z)) / (X - z)
+    let coeffs: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut rng)).collect();
+    let eval = |x: Fr| coeffs[0] + coeffs[1] * x + coeffs[2] * x.square();
+    let z = Fr::rand(&mut rng);
+    let y = eval(z);
+    let q_tau = (eval(tau) - y) * (tau - z).inverse().unwrap();
+
+    let commitment = codec::g1_from_ark(&(g1 * eval(tau)).into_affine());
+    let proof = codec::g1_from_ark(&(g1 * q_tau).into_affine());
+
+    let verify = |y: Fr| kzg::verify_kzg_opening(&ArkBackend, &commitment, to_u256(&z), to_u256(&y), &proof, &srs_g2);
+    assert_eq!(verify(y), Ok(true));
+    assert_eq!(verify(y + Fr::from(1u64)), Ok(false));
+    assert_eq!(
+        kzg::verify_kzg_opening(&ArkBackend, &commitment[..63], to_u256(&z), to_u256(&y), &proof, &srs_g2),
+        Err(kzg::Error::InvalidInputSize)
+    );
+}
@@ -0,0 +1,286 @@
+//! fflonk Tests
+//!
+//! The test prover in `common::fflonk` packs the `common::plonk` circuit into
+//! fflonk commitments. Its output goes through snarkjs JSON (`proof.json`,
+//! `verification_key.json`, `public.json`) so verification takes the same
+//! import path as a `snarkjs fflonk prove` proof, and runs on `ArkBackend`
+//! (the Stylus path), the arkworks host verifier and `verify_offchain`. The
+//! ignored fixture test checks what `snarkjs fflonk prove` itself writes
+//! (`tests/fixtures/snarkjs/generate.sh`) until that output is committed.
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use ark_bn254::Fr;
+use ark_ff::UniformRand;
+use ark_std::test_rng;
+use common::fflonk::FflonkFixture;
+use common::plonk::{fr_to_u256, Witness};
+use serde_json::{json, Value};
+use sha3::{Digest, Keccak256};
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::backend::ArkBackend;
+use uzkv_stylus::formats::snarkjs::{self as snarkjs_json, Protocol, FFLONK_PROOF_SIZE};
+use uzkv_stylus::groth16::codec;
+use uzkv_stylus::plonk::fflonk::{
+    self, compute_challenges, deserialize_fflonk_proof, deserialize_fflonk_vk, serialize_fflonk_proof,
+    serialize_fflonk_vk, FflonkProof, VK_SIZE,
+};
+use uzkv_stylus::plonk::kzg::Error;
+use uzkv_stylus::plonk::srs::fits_domain;
+use uzkv_stylus::plonk::verify_fflonk_host;
+use uzkv_stylus::types::ProofType;
+use uzkv_stylus::uzkv::{verify_offchain, ProofSystem};
+use uzkv_stylus::utils::BN254_SCALAR_MODULUS;
+
+fn fixture() -> FflonkFixture {
+    FflonkFixture::cubic(Fr::rand(&mut test_rng()))
+}
+
+fn fixture_file(name: &str) -> String {
+    let path = format!("{}/tests/fixtures/snarkjs/fflonk/{}", env!("CARGO_MANIFEST_DIR"), name);
+    std::fs::read_to_string(&path).unwrap_or_else(|_| panic!("missing fixture {}", path))
+}
+
+fn verify(proof: &[u8], inputs: &[u8], vk: &[u8]) -> Result<bool, Error> {
+    fflonk::verify(&ArkBackend, proof, inputs, vk)
+}
+
+fn g1_json(point: &[u8; 64]) -> Value {
+    json!(codec::g1_to_snarkjs(point).unwrap())
+}
+
+fn scalar_json(value: &U256) -> Value {
+    json!(codec::scalar_to_snarkjs(&value.to_be_bytes::<32>()).unwrap())
+}
+
+/// `proof.json` as written by `snarkjs fflonk prove`
+fn proof_json(proof: &FflonkProof) -> String {
+    let e = &proof.evals;
+    json!({
+        "polynomials": {
+            "C1": g1_json(&proof.c1), "C2": g1_json(&proof.c2),
+            "W1": g1_json(&proof.w1), "W2": g1_json(&proof.w2)
+        },
+        "evaluations": {
+            "ql": scalar_json(&e.ql), "qr": scalar_json(&e.qr), "qm": scalar_json(&e.qm),
+            "qo": scalar_json(&e.qo), "qc": scalar_json(&e.qc),
+            "s1": scalar_json(&e.s1), "s2": scalar_json(&e.s2), "s3": scalar_json(&e.s3),
+            "a": scalar_json(&e.a), "b": scalar_json(&e.b), "c": scalar_json(&e.c),
+            "z": scalar_json(&e.z), "zw": scalar_json(&e.zw),
+            "t1w": scalar_json(&e.t1w), "t2w": scalar_json(&e.t2w), "inv": scalar_json(&e.inv)
+        },
+        "protocol": "fflonk",
+        "curve": "bn128"
+    })
+    .to_string()
+}
+
+/// `verification_key.json` as written by `snarkjs zkey export verificationkey`
+fn vk_json(fixture: &FflonkFixture) -> String {
+    let vk = fixture.verifying_key();
+    json!({
+        "protocol": "fflonk",
+        "curve": "bn128",
+        "nPublic": vk.num_public_inputs,
+        "power": vk.n.trailing_zeros(),
+        "k1": scalar_json(&vk.k1),
+        "k2": scalar_json(&vk.k2),
+        "w": scalar_json(&vk.omega),
+        "w3": scalar_json(&vk.omega3),
+        "w4": scalar_json(&vk.omega4),
+        "w8": scalar_json(&vk.omega8),
+        "wr": scalar_json(&vk.omega_r),
+        "X_2": codec::g2_to_snarkjs(&fixture.vk_bytes()[VK_SIZE..]).unwrap(),
+        "C0": g1_json(&vk.c0)
+    })
+    .to_string()
+}
+
+fn public_json(witness: &Witness) -> String {
+    let signals: Vec<String> = witness.public.iter().map(|x| fr_to_u256(x).to_string()).collect();
+    json!(signals).to_string()
+}
+
+fn keccak_fr(data: &[u8]) -> U256 {
+    U256::from_be_slice(&Keccak256::digest(data)).reduce_mod(BN254_SCALAR_MODULUS)
+}
+
+#[test]
+fn test_snarkjs_json_proof_verifies() {
+    let fixture = fixture();
+    let witness = fixture.witness(Fr::from(3u64));
+    let proof = fixture.prove(&witness);
+
+    let (vk, universal) = snarkjs_json::parse_universal_proof(
+        &vk_json(&fixture),
+        &proof_json(&proof),
+        &public_json(&witness),
+        1,
+    )
+    .unwrap();
+    assert_eq!(universal.proof_type, ProofType::Fflonk);
+    assert_eq!(vk, fixture.vk_bytes());
+    assert_eq!(universal.proof_bytes, serialize_fflonk_proof(&proof));
+    assert_eq!(universal.proof_bytes.len(), FFLONK_PROOF_SIZE);
+    assert_eq!(deserialize_fflonk_proof(&universal.proof_bytes), Some(proof));
+
+    let (proof, inputs) = (&universal.proof_bytes, &universal.public_inputs_bytes);
+    assert_eq!(verify(proof, inputs, &vk), Ok(true));
+    assert_eq!(verify_fflonk_host(proof, inputs, &vk), Ok(true));
+    assert_eq!(verify_offchain(ProofSystem::Fflonk as u8, proof, inputs, &vk), Ok(true));
+}
+
+#[test]
+fn test_protocols_are_not_interchangeable() {
+    let fixture = fixture();
+    let witness = fixture.witness(Fr::from(3u64));
+    let proof = proof_json(&fixture.prove(&witness));
+
+    assert_eq!(snarkjs_json::detect_protocol(&proof), Ok(Protocol::Fflonk));
+    assert!(snarkjs_json::parse_plonk_proof(&proof).is_err());
+    assert!(snarkjs_json::parse_plonk_vk(&vk_json(&fixture)).is_err());
+}
+
+#[test]
+fn test_challenges_follow_snarkjs_transcript() {
+    let fixture = fixture();
+    let witness = fixture.witness(Fr::from(3u64));
+    let proof = fixture.prove(&witness);
+    let vk = fixture.verifying_key();
+    let inputs: Vec<U256> = witness.public.iter().map(fr_to_u256).collect();
+    let challenges = compute_challenges(&proof, &vk, &inputs);
+
+    // β: C0, public inputs, C1
+    let mut data = vk.c0.to_vec();
+    for input in &inputs {
+        data.extend_from_slice(&input.to_be_bytes::<32>());
+    }
+    data.extend_from_slice(&proof.c1);
+    assert_eq!(challenges.beta, keccak_fr(&data));
+
+    // Every later round starts from an empty transcript
+    let beta = challenges.beta.to_be_bytes::<32>();
+    assert_eq!(challenges.gamma, keccak_fr(&beta));
+
+    let gamma = challenges.gamma.to_be_bytes::<32>();
+    assert_eq!(challenges.xi_seed, keccak_fr(&[&gamma[..], &proof.c2].concat()));
+
+    // α: every evaluation but the inversion hint
+    let mut data = challenges.xi_seed.to_be_bytes::<32>().to_vec();
+    for eval in &proof.evals.to_array()[..15] {
+        data.extend_from_slice(&eval.to_be_bytes::<32>());
+    }
+    assert_eq!(challenges.alpha, keccak_fr(&data));
+
+    let alpha = challenges.alpha.to_be_bytes::<32>();
+    assert_eq!(challenges.y, keccak_fr(&[&alpha[..], &proof.w1].concat()));
+}
+
+#[test]
+fn test_tampered_fflonk_proof_fails() {
+    let fixture = fixture();
+    let witness = fixture.witness(Fr::from(5u64));
+    let inputs = fixture.public_bytes(&witness);
+    let vk = fixture.vk_bytes();
+    let proof = fixture.prove(&witness);
+
+    let wrong = fr_to_u256(&(witness.public[0] + Fr::from(1u64))).to_be_bytes::<32>();
+    let bytes = serialize_fflonk_proof(&proof);
+    assert_eq!(verify(&bytes, &wrong, &vk), Ok(false));
+    assert_eq!(verify_fflonk_host(&bytes, &wrong, &vk), Ok(false));
+
+    let one = U256::from(1);
+    let tampers: [fn(&mut FflonkProof, U256); 5] = [
+        |p, one| p.evals.c = p.evals.c.add_mod(one, BN254_SCALAR_MODULUS),
+        |p, one| p.evals.t2w = p.evals.t2w.add_mod(one, BN254_SCALAR_MODULUS),
+        |p, one| p.evals.qm = p.evals.qm.add_mod(one, BN254_SCALAR_MODULUS),
+        // A wrong hint fails the batch inversion check before any curve work
+        |p, one| p.evals.inv = p.evals.inv.add_mod(one, BN254_SCALAR_MODULUS),
+        |p, _| std::mem::swap(&mut p.c1, &mut p.c2),
+    ];
+    for tamper in tampers {
+        let mut bad = proof.clone();
+        tamper(&mut bad, one);
+        let bad = serialize_fflonk_proof(&bad);
+        assert_eq!(verify(&bad, &inputs, &vk), Ok(false));
+        assert_eq!(verify_fflonk_host(&bad, &inputs, &vk), Ok(false));
+    }
+}
+
+#[test]
+fn test_unsatisfied_witness_fails() {
+    let fixture = fixture();
+    let mut witness = fixture.witness(Fr::from(3u64));
+    witness.wires[2][2] += Fr::from(1u64);
+    let bytes = fixture.proof_bytes(&witness);
+    let inputs = fixture.public_bytes(&witness);
+    let vk = fixture.vk_bytes();
+
+    assert_eq!(verify(&bytes, &inputs, &vk), Ok(false));
+    assert_eq!(verify_fflonk_host(&bytes, &inputs, &vk), Ok(false));
+}
+
+#[test]
+fn test_vk_roots_are_validated() {
+    let fixture = fixture();
+    let vk = fixture.verifying_key();
+    assert_eq!(deserialize_fflonk_vk(&serialize_fflonk_vk(&vk)), Some(vk.clone()));
+
+    let one = U256::from(1);
+    let tampers: [fn(&mut fflonk::FflonkVerificationKey, U256); 5] = [
+        |vk, one| vk.omega3 = one,
+        |vk, _| vk.omega4 = vk.omega8,
+        |vk, _| vk.omega8 = vk.omega4,
+        |vk, one| vk.omega_r = vk.omega_r.add_mod(one, BN254_SCALAR_MODULUS),
+        |vk, _| vk.n = 12,
+    ];
+    for tamper in tampers {
+        let mut bad = vk.clone();
+        tamper(&mut bad, one);
+        assert_eq!(deserialize_fflonk_vk(&serialize_fflonk_vk(&bad)), None);
+    }
+
+    // A VK the verifier refuses is an input error, not a failed proof
+    let witness = fixture.witness(Fr::from(3u64));
+    let mut bytes = fixture.vk_bytes();
+    bytes[0] = 0xff;
+    let result = verify(&fixture.proof_bytes(&witness), &fixture.public_bytes(&witness), &bytes);
+    assert_eq!(result, Err(Error::InvalidInputSize));
+}
+
+#[test]
+fn test_srs_must_cover_combined_degree() {
+    // C2 packs three polynomials of degree ~3n: 8 rows need 2^7 powers of τ
+    assert_eq!(fflonk::srs_domain(8), 128);
+    assert!(fits_domain(7, fflonk::srs_domain(8)));
+    assert!(!fits_domain(6, fflonk::srs_domain(8)));
+    // The PLONK bound alone would have accepted it
+    assert!(fits_domain(6, 8));
+}
+
+#[test]
+#[ignore = "needs snarkjs output in tests/fixtures/snarkjs/fflonk (generate.sh)"]
+fn test_snarkjs_fixture_verifies() {
+    let (vk, universal) = snarkjs_json::parse_universal_proof(
+        &fixture_file("verification_key.json"),
+        &fixture_file("proof.json"),
+        &fixture_file("public.json"),
+        1,
+    )
+    .unwrap();
+    assert_eq!(universal.proof_type, ProofType::Fflonk);
+    assert_eq!(universal.proof_bytes.len(), FFLONK_PROOF_SIZE);
+
+    let (proof, inputs) = (&universal.proof_bytes, &universal.public_inputs_bytes);
+    assert_eq!(verify(proof, inputs, &vk), Ok(true));
+    assert_eq!(verify_fflonk_host(proof, inputs, &vk), Ok(true));
+    assert_eq!(verify_offchain(ProofSystem::Fflonk as u8, proof, inputs, &vk), Ok(true));
+
+    // poseidon_test: `valid`, then the expected hash
+    let mut wrong = inputs.clone();
+    wrong[31] ^= 1;
+    assert_eq!(verify(proof, &wrong, &vk), Ok(false));
+    assert_eq!(verify_fflonk_host(proof, &wrong, &vk), Ok(false));
+}
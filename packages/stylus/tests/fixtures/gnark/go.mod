@@ -0,0 +1,8 @@
+module github.com/uzkv/stylus/tests/fixtures/gnark
+
+go 1.22
+
+require (
+	github.com/consensys/gnark v0.11.0
+	github.com/consensys/gnark-crypto v0.14.0
+)
@@ -0,0 +1,75 @@
+// Writes the gnark Groth16 fixtures read by tests/groth16_gnark.rs
+//
+// The circuit proves knowledge of y with y² = x for a public x, and calls
+// api.Commit so the proof carries a BSB22 commitment and its proof of
+// knowledge. Run from this directory:
+//
+//	go mod tidy && go run .
+//
+// The proof is checked with groth16.Verify before anything is written.
+package main
+
+import (
+	"io"
+	"log"
+	"os"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	"github.com/consensys/gnark/backend/groth16"
+	"github.com/consensys/gnark/frontend"
+	"github.com/consensys/gnark/frontend/cs/r1cs"
+)
+
+type Circuit struct {
+	X frontend.Variable `gnark:",public"`
+	Y frontend.Variable
+}
+
+func (c *Circuit) Define(api frontend.API) error {
+	api.AssertIsEqual(api.Mul(c.Y, c.Y), c.X)
+
+	// Commit to a public and a secret wire; the commitment hash becomes an
+	// extra public input the verifier derives itself
+	commitment, err := api.(frontend.Committer).Commit(c.X, c.Y)
+	if err != nil {
+		return err
+	}
+	api.AssertIsDifferent(commitment, 0)
+	return nil
+}
+
+func main() {
+	ccs, err := frontend.Compile(ecc.BN254.ScalarField(), r1cs.NewBuilder, &Circuit{})
+	check(err)
+	pk, vk, err := groth16.Setup(ccs)
+	check(err)
+
+	witness, err := frontend.NewWitness(&Circuit{X: 9, Y: 3}, ecc.BN254.ScalarField())
+	check(err)
+	public, err := witness.Public()
+	check(err)
+
+	proof, err := groth16.Prove(ccs, pk, witness)
+	check(err)
+	check(groth16.Verify(proof, vk, public))
+
+	write("vk.bin", vk.WriteTo)
+	write("proof.bin", proof.WriteTo)
+	witnessBytes, err := public.MarshalBinary()
+	check(err)
+	check(os.WriteFile("witness.bin", witnessBytes, 0o644))
+}
+
+func write(name string, writeTo func(w io.Writer) (int64, error)) {
+	f, err := os.Create(name)
+	check(err)
+	defer f.Close()
+	_, err = writeTo(f)
+	check(err)
+}
+
+func check(err error) {
+	if err != nil {
+		log.Fatal(err)
+	}
+}
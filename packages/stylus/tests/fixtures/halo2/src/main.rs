@@ -0,0 +1,342 @@
+//! Writes the halo2 fixtures read by tests/halo2.rs
+//!
+//! Proves a small circuit with halo2 (`create_proof` with `ProverSHPLONK`,
+//! Blake2b and Keccak256 transcripts), checks both proofs with halo2's own
+//! `verify_proof`, and converts the verifying key to the compact encoding of
+//! `src/halo2/vk.rs`. Run from this directory (the target overrides the
+//! package's wasm default):
+//!
+//!     cargo run --release --target x86_64-unknown-linux-gnu
+//!
+//! Circuit, on 32 rows:
+//! ```text
+//! q·(a·b − c) = 0        q_next·(a(ωX) − c) = 0        q·(d − ch·a) = 0
+//! q_lookup·b ∈ table     b copied from a fixed cell     a[0] = instance[0], c[4] = instance[1]
+//! ```
+//! so `a = x, 2x, 4x, …` and the instances are `x` and `32·x`. `d` is a
+//! second-phase column fixed by the first-phase challenge `ch`.
+
+use std::fs;
+
+use halo2_axiom::circuit::{Layouter, SimpleFloorPlanner, Value};
+use halo2_axiom::halo2curves::bn256::{Bn256, Fr, G1Affine, G2Affine};
+use halo2_axiom::halo2curves::ff::PrimeField;
+use halo2_axiom::halo2curves::CurveAffine;
+use halo2_axiom::plonk::{
+    create_proof, keygen_pk, keygen_vk, verify_proof, Advice, Any, Challenge, Circuit, Column, ConstraintSystem,
+    Error, Expression, Fixed, FirstPhase, Instance, SecondPhase, Selector, TableColumn, VerifyingKey,
+};
+use halo2_axiom::poly::kzg::commitment::{KZGCommitmentScheme, ParamsKZG};
+use halo2_axiom::poly::kzg::multiopen::{ProverSHPLONK, VerifierSHPLONK};
+use halo2_axiom::poly::kzg::strategy::SingleStrategy;
+use halo2_axiom::poly::Rotation;
+use halo2_axiom::transcript::{
+    Blake2bRead, Blake2bWrite, Challenge255, Keccak256Read, Keccak256Write, TranscriptReadBuffer,
+    TranscriptWriterBuffer,
+};
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+
+const K: u32 = 5;
+const GATE_ROWS: usize = 5;
+const TABLE_SIZE: u64 = 8;
+const X: u64 = 3;
+
+#[derive(Clone)]
+struct Config {
+    a: Column<Advice>,
+    b: Column<Advice>,
+    c: Column<Advice>,
+    d: Column<Advice>,
+    two: Column<Fixed>,
+    instance: Column<Instance>,
+    q: Selector,
+    q_next: Selector,
+    q_lookup: Selector,
+    table: TableColumn,
+    ch: Challenge,
+}
+
+#[derive(Clone, Default)]
+struct TestCircuit(Value<Fr>);
+
+impl Circuit<Fr> for TestCircuit {
+    type Config = Config;
+    type FloorPlanner = SimpleFloorPlanner;
+    type Params = ();
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Config {
+        let [a, b, c] = [(); 3].map(|_| meta.advice_column_in(FirstPhase));
+        let ch = meta.challenge_usable_after(FirstPhase);
+        let d = meta.advice_column_in(SecondPhase);
+        let two = meta.fixed_column();
+        let instance = meta.instance_column();
+        let [q, q_next] = [(); 2].map(|_| meta.selector());
+        let q_lookup = meta.complex_selector();
+        let table = meta.lookup_table_column();
+
+        meta.enable_equality(a);
+        meta.enable_equality(b);
+        meta.enable_equality(c);
+        meta.enable_equality(two);
+        meta.enable_equality(instance);
+
+        meta.create_gate("a·b = c", |meta| {
+            let q = meta.query_selector(q);
+            let [a, b, c] = [a, b, c].map(|column| meta.query_advice(column, Rotation::cur()));
+            vec![q * (a * b - c)]
+        });
+        meta.create_gate("a(ωX) = c", |meta| {
+            let q_next = meta.query_selector(q_next);
+            let a_next = meta.query_advice(a, Rotation::next());
+            let c = meta.query_advice(c, Rotation::cur());
+            vec![q_next * (a_next - c)]
+        });
+        meta.create_gate("d = ch·a", |meta| {
+            let q = meta.query_selector(q);
+            let a = meta.query_advice(a, Rotation::cur());
+            let d = meta.query_advice(d, Rotation::cur());
+            let ch = meta.query_challenge(ch);
+            vec![q * (d - ch * a)]
+        });
+        meta.lookup("b ∈ table", |meta| {
+            let q_lookup = meta.query_selector(q_lookup);
+            let b = meta.query_advice(b, Rotation::cur());
+            vec![(q_lookup * b, table)]
+        });
+
+        Config { a, b, c, d, two, instance, q, q_next, q_lookup, table, ch }
+    }
+
+    fn synthesize(&self, config: Config, mut layouter: impl Layouter<Fr>) -> Result<(), Error> {
+        layouter.assign_table(
+            || "table",
+            |mut table| {
+                for value in 0..TABLE_SIZE {
+                    table.assign_cell(|| "table", config.table, value as usize, || Value::known(Fr::from(value)))?;
+                }
+                Ok(())
+            },
+        )?;
+
+        let (first, last) = layouter.assign_region(
+            || "powers",
+            |mut region| {
+                let two = region.assign_fixed(config.two, 0, Fr::from(2));
+                let mut a = self.0;
+                let mut cells = Vec::with_capacity(GATE_ROWS);
+                for row in 0..GATE_ROWS {
+                    config.q.enable(&mut region, row)?;
+                    config.q_lookup.enable(&mut region, row)?;
+                    if row + 1 < GATE_ROWS {
+                        config.q_next.enable(&mut region, row)?;
+                    }
+                    let c = a.map(|a| a * Fr::from(2));
+                    let a_cell = region.assign_advice(config.a, row, a);
+                    let b_cell = region.assign_advice(config.b, row, Value::known(Fr::from(2)));
+                    let c_cell = region.assign_advice(config.c, row, c);
+                    region.constrain_equal(b_cell.cell(), two);
+                    cells.push((a_cell.cell(), c_cell.cell()));
+                    a = c;
+                }
+
+                region.next_phase();
+                let ch = region.get_challenge(config.ch);
+                let mut a = self.0;
+                for row in 0..GATE_ROWS {
+                    region.assign_advice(config.d, row, ch.zip(a).map(|(ch, a)| ch * a));
+                    a = a.map(|a| a * Fr::from(2));
+                }
+                Ok((cells[0].0, cells[GATE_ROWS - 1].1))
+            },
+        )?;
+        layouter.constrain_instance(first, config.instance, 0);
+        layouter.constrain_instance(last, config.instance, 1);
+        Ok(())
+    }
+}
+
+/// Big-endian bytes of a little-endian field representation
+fn be(repr: impl AsRef<[u8]>) -> Vec<u8> {
+    repr.as_ref().iter().rev().copied().collect()
+}
+
+fn point(p: &G1Affine) -> Vec<u8> {
+    let coordinates = p.coordinates().unwrap();
+    [be(coordinates.x().to_repr()), be(coordinates.y().to_repr())].concat()
+}
+
+fn g2(p: &G2Affine) -> Vec<u8> {
+    [be(p.x.c1.to_repr()), be(p.x.c0.to_repr()), be(p.y.c1.to_repr()), be(p.y.c0.to_repr())].concat()
+}
+
+/// Encoder for the layout in `src/halo2/vk.rs`
+#[derive(Default)]
+struct Encoder(Vec<u8>);
+
+impl Encoder {
+    fn u8(&mut self, value: u8) {
+        self.0.push(value);
+    }
+
+    fn u32(&mut self, value: usize) {
+        self.0.extend_from_slice(&(value as u32).to_be_bytes());
+    }
+
+    fn scalar(&mut self, value: &Fr) {
+        self.0.extend_from_slice(&be(value.to_repr()));
+    }
+
+    fn queries(&mut self, queries: impl ExactSizeIterator<Item = (usize, Rotation)>) {
+        self.u32(queries.len());
+        for (column, rotation) in queries {
+            self.u32(column);
+            self.0.extend_from_slice(&rotation.0.to_be_bytes());
+        }
+    }
+
+    /// Postfix ops; queries become indices into the constraint system's lists
+    fn expression(&mut self, cs: &ConstraintSystem<Fr>, expression: &Expression<Fr>) {
+        let position = |list: Vec<(usize, i32)>, column: usize, rotation: Rotation| {
+            list.iter().position(|q| *q == (column, rotation.0)).unwrap()
+        };
+        let fixed: Vec<_> = cs.fixed_queries().iter().map(|(c, r)| (c.index(), r.0)).collect();
+        let advice: Vec<_> = cs.advice_queries().iter().map(|(c, r)| (c.index(), r.0)).collect();
+        let instance: Vec<_> = cs.instance_queries().iter().map(|(c, r)| (c.index(), r.0)).collect();
+
+        let op = |tag: u8, body: Vec<u8>| -> (usize, Vec<u8>) { (1, [vec![tag], body].concat()) };
+        let index = |i: usize| (i as u32).to_be_bytes().to_vec();
+        let join = |(n1, b1): (usize, Vec<u8>), (n2, b2): (usize, Vec<u8>)| (n1 + n2, [b1, b2].concat());
+        let (count, bytes) = expression.evaluate(
+            &|c| op(0, be(c.to_repr())),
+            &|_| panic!("selectors must be converted to fixed columns"),
+            &|q| op(1, index(position(fixed.clone(), q.column_index(), q.rotation()))),
+            &|q| op(2, index(position(advice.clone(), q.column_index(), q.rotation()))),
+            &|q| op(3, index(position(instance.clone(), q.column_index(), q.rotation()))),
+            &|c| op(4, index(c.index())),
+            &|a| join(a, op(5, vec![])),
+            &|a, b| join(join(a, b), op(6, vec![])),
+            &|a, b| join(join(a, b), op(7, vec![])),
+            &|a, c| join(a, op(8, be(c.to_repr()))),
+        );
+        self.u32(count);
+        self.0.extend_from_slice(&bytes);
+    }
+}
+
+fn encode_vk(vk: &VerifyingKey<G1Affine>, instance_lengths: &[usize]) -> Vec<u8> {
+    let cs = vk.cs();
+    let mut e = Encoder::default();
+    e.u32(K as usize);
+    e.scalar(&vk.get_domain().get_omega());
+    e.scalar(&vk.transcript_repr());
+    e.u32(cs.degree());
+
+    e.u32(cs.num_fixed_columns());
+    let advice_phases = cs.advice_column_phase();
+    e.u32(advice_phases.len());
+    advice_phases.iter().for_each(|phase| e.u8(*phase));
+    assert_eq!(cs.num_instance_columns(), instance_lengths.len());
+    e.u32(instance_lengths.len());
+    instance_lengths.iter().for_each(|len| e.u32(*len));
+    let challenge_phases = cs.challenge_phase();
+    e.u32(challenge_phases.len());
+    challenge_phases.iter().for_each(|phase| e.u8(*phase));
+
+    e.queries(cs.advice_queries().iter().map(|(c, r)| (c.index(), *r)));
+    e.queries(cs.instance_queries().iter().map(|(c, r)| (c.index(), *r)));
+    e.queries(cs.fixed_queries().iter().map(|(c, r)| (c.index(), *r)));
+
+    let columns = cs.permutation().get_columns();
+    e.u32(columns.len());
+    for column in &columns {
+        e.u8(match column.column_type() {
+            Any::Advice(_) => 0,
+            Any::Fixed => 1,
+            Any::Instance => 2,
+        });
+        e.u32(column.index());
+    }
+
+    let polys: Vec<_> = cs.gates().iter().flat_map(|gate| gate.polynomials()).collect();
+    e.u32(polys.len());
+    polys.into_iter().for_each(|poly| e.expression(cs, poly));
+
+    e.u32(cs.lookups().len());
+    for lookup in cs.lookups() {
+        e.u32(lookup.input_expressions().len());
+        lookup.input_expressions().iter().for_each(|input| e.expression(cs, input));
+        lookup.table_expressions().iter().for_each(|table| e.expression(cs, table));
+    }
+
+    assert_eq!(vk.fixed_commitments().len(), cs.num_fixed_columns());
+    for commitment in vk.fixed_commitments().iter().chain(vk.permutation().commitments()) {
+        e.0.extend_from_slice(&point(commitment));
+    }
+    e.0
+}
+
+fn main() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0x68616c6f32);
+    let params = ParamsKZG::<Bn256>::setup(K, &mut rng);
+    let vk = keygen_vk(&params, &TestCircuit::default()).unwrap();
+    let pk = keygen_pk(&params, vk, &TestCircuit::default()).unwrap();
+
+    let x = Fr::from(X);
+    let instance = [x, x * Fr::from(32)];
+    let instances: &[&[Fr]] = &[&instance];
+    let circuit = TestCircuit(Value::known(x));
+
+    let mut transcript = Blake2bWrite::<_, G1Affine, Challenge255<_>>::init(vec![]);
+    create_proof::<KZGCommitmentScheme<Bn256>, ProverSHPLONK<'_, Bn256>, _, _, _, _>(
+        &params,
+        &pk,
+        &[circuit.clone()],
+        &[instances],
+        &mut rng,
+        &mut transcript,
+    )
+    .unwrap();
+    let blake2b = transcript.finalize();
+    let mut reader = Blake2bRead::<_, G1Affine, Challenge255<_>>::init(&blake2b[..]);
+    verify_proof::<KZGCommitmentScheme<Bn256>, VerifierSHPLONK<'_, Bn256>, _, _, _>(
+        &params,
+        pk.get_vk(),
+        SingleStrategy::new(&params),
+        &[instances],
+        &mut reader,
+    )
+    .unwrap();
+
+    let mut transcript = Keccak256Write::<_, G1Affine, Challenge255<_>>::init(vec![]);
+    create_proof::<KZGCommitmentScheme<Bn256>, ProverSHPLONK<'_, Bn256>, _, _, _, _>(
+        &params,
+        &pk,
+        &[circuit],
+        &[instances],
+        &mut rng,
+        &mut transcript,
+    )
+    .unwrap();
+    let keccak256 = transcript.finalize();
+    let mut reader = Keccak256Read::<_, G1Affine, Challenge255<_>>::init(&keccak256[..]);
+    verify_proof::<KZGCommitmentScheme<Bn256>, VerifierSHPLONK<'_, Bn256>, _, _, _>(
+        &params,
+        pk.get_vk(),
+        SingleStrategy::new(&params),
+        &[instances],
+        &mut reader,
+    )
+    .unwrap();
+
+    let vk_bytes = [encode_vk(pk.get_vk(), &[instance.len()]), g2(&params.s_g2())].concat();
+    let instance_bytes: Vec<u8> = instance.iter().flat_map(|value| be(value.to_repr())).collect();
+    fs::write("vk.bin", vk_bytes).unwrap();
+    fs::write("instances.bin", instance_bytes).unwrap();
+    fs::write("proof_blake2b.bin", blake2b).unwrap();
+    fs::write("proof_keccak256.bin", keccak256).unwrap();
+}
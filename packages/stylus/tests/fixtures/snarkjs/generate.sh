@@ -0,0 +1,45 @@
+#!/bin/bash
+# Write the snarkjs fixtures read by the stylus tests
+#
+# Proves the poseidon_test circuit (the one behind
+# packages/circuits/test-proof-old-1) with the snarkjs that plonk-service
+# uses, against a throwaway Powers of Tau, and checks every proof with
+# `snarkjs <protocol> verify` before keeping it.
+#
+# Needs circom 2.1.6+ and `npm install` in packages/circuits.
+
+set -euo pipefail
+
+FIXTURES_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+CIRCUITS_DIR="$(cd "$FIXTURES_DIR/../../../../circuits" && pwd)"
+SNARKJS="$CIRCUITS_DIR/node_modules/.bin/snarkjs"
+CIRCUIT="poseidon_test"
+PROTOCOLS=("plonk" "fflonk")
+
+WORK_DIR="$(mktemp -d)"
+trap 'rm -rf "$WORK_DIR"' EXIT
+
+circom "$CIRCUITS_DIR/src/$CIRCUIT.circom" --r1cs --wasm -l "$CIRCUITS_DIR/node_modules" -o "$WORK_DIR"
+
+# fflonk commits to the combined polynomials and needs ~9x the PLONK domain
+"$SNARKJS" powersoftau new bn128 14 "$WORK_DIR/pot_0000.ptau"
+"$SNARKJS" powersoftau contribute "$WORK_DIR/pot_0000.ptau" "$WORK_DIR/pot_0001.ptau" \
+    --name="uzkv fixtures" -e="uzkv fixtures"
+"$SNARKJS" powersoftau prepare phase2 "$WORK_DIR/pot_0001.ptau" "$WORK_DIR/pot_final.ptau"
+
+# preimage (1, 2) with its circomlib Poseidon hash
+HASH="$(cd "$CIRCUITS_DIR" && node -e \
+    "require('circomlibjs').buildPoseidon().then(p => console.log(p.F.toString(p([1, 2]))))")"
+echo "{\"preimage\": [\"1\", \"2\"], \"expectedHash\": \"$HASH\"}" > "$WORK_DIR/input.json"
+node "$WORK_DIR/${CIRCUIT}_js/generate_witness.js" "$WORK_DIR/${CIRCUIT}_js/$CIRCUIT.wasm" \
+    "$WORK_DIR/input.json" "$WORK_DIR/witness.wtns"
+
+for PROTOCOL in "${PROTOCOLS[@]}"; do
+    OUT_DIR="$FIXTURES_DIR/$PROTOCOL"
+    mkdir -p "$OUT_DIR"
+    "$SNARKJS" "$PROTOCOL" setup "$WORK_DIR/$CIRCUIT.r1cs" "$WORK_DIR/pot_final.ptau" "$WORK_DIR/$PROTOCOL.zkey"
+    "$SNARKJS" zkey export verificationkey "$WORK_DIR/$PROTOCOL.zkey" "$OUT_DIR/verification_key.json"
+    "$SNARKJS" "$PROTOCOL" prove "$WORK_DIR/$PROTOCOL.zkey" "$WORK_DIR/witness.wtns" \
+        "$OUT_DIR/proof.json" "$OUT_DIR/public.json"
+    "$SNARKJS" "$PROTOCOL" verify "$OUT_DIR/verification_key.json" "$OUT_DIR/public.json" "$OUT_DIR/proof.json"
+done
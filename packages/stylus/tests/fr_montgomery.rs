@@ -0,0 +1,128 @@
+//! Montgomery Fr Tests
+//!
+//! Randomised comparisons of `utils::Fr` and the `fr_*` helpers built on it
+//! against arkworks' `Fr` and the `U256::mul_mod` arithmetic they replace.
+
+#![cfg(feature = "std")]
+
+use ark_bn254::Fr as ArkFr;
+use ark_ff::{BigInteger, Field, PrimeField, UniformRand};
+use ark_std::{rand::RngCore, test_rng};
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::utils::{batch_inverse, fr_inv, fr_mul, fr_pow, Fr, BN254_SCALAR_MODULUS};
+
+const ROUNDS: usize = 500;
+
+fn to_u256(scalar: &ArkFr) -> U256 {
+    U256::from_be_slice(&scalar.into_bigint().to_bytes_be())
+}
+
+fn random_u256<R: RngCore>(rng: &mut R) -> U256 {
+    let mut bytes = [0u8; 32];
+    rng.fill_bytes(&mut bytes);
+    U256::from_be_bytes(bytes)
+}
+
+/// Edge values followed by random elements
+fn samples() -> Vec<ArkFr> {
+    let rng = &mut test_rng();
+    let mut out = vec![ArkFr::from(0u64), ArkFr::from(1u64), ArkFr::from(2u64), -ArkFr::from(1u64), -ArkFr::from(2u64)];
+    out.extend((0..ROUNDS).map(|_| ArkFr::rand(rng)));
+    out
+}
+
+fn fr(scalar: &ArkFr) -> Fr {
+    Fr::from_u256(to_u256(scalar)).unwrap()
+}
+
+#[test]
+fn test_field_operations_match_arkworks() {
+    let values = samples();
+    for (a, b) in values.iter().zip(values.iter().rev()) {
+        let (ma, mb) = (fr(a), fr(b));
+        assert_eq!(ma.to_u256(), to_u256(a));
+        assert_eq!(ma.add(&mb).to_u256(), to_u256(&(*a + b)));
+        assert_eq!(ma.sub(&mb).to_u256(), to_u256(&(*a - b)));
+        assert_eq!(ma.mul(&mb).to_u256(), to_u256(&(*a * b)));
+        assert_eq!(ma.square().to_u256(), to_u256(&a.square()));
+        assert_eq!(ma.double().to_u256(), to_u256(&a.double()));
+        assert_eq!(ma.neg().to_u256(), to_u256(&-*a));
+        assert_eq!(ma.inverse().map(Fr::to_u256), a.inverse().map(|i| to_u256(&i)));
+    }
+}
+
+#[test]
+fn test_algebraic_identities() {
+    let values = samples();
+    for window in values.windows(3) {
+        let (a, b, c) = (fr(&window[0]), fr(&window[1]), fr(&window[2]));
+        assert_eq!(a.mul(&b.add(&c)), a.mul(&b).add(&a.mul(&c)));
+        assert_eq!(a.mul(&b).mul(&c), a.mul(&b.mul(&c)));
+        assert_eq!(a.add(&a.neg()), Fr::ZERO);
+        assert_eq!(a.sub(&b).add(&b), a);
+        if let Some(inv) = a.inverse() {
+            assert_eq!(a.mul(&inv), Fr::ONE);
+        }
+    }
+    assert_eq!(Fr::ZERO.inverse(), None);
+    assert_eq!(Fr::ONE.inverse(), Some(Fr::ONE));
+}
+
+#[test]
+fn test_helpers_match_mul_mod() {
+    // Arbitrary 256-bit operands, most of them above r
+    let rng = &mut test_rng();
+    for _ in 0..ROUNDS {
+        let (a, b) = (random_u256(rng), random_u256(rng));
+        assert_eq!(fr_mul(a, b), a.mul_mod(b, BN254_SCALAR_MODULUS));
+
+        let exp = random_u256(rng) >> (rng.next_u32() % 256);
+        let reduced = a % BN254_SCALAR_MODULUS;
+        let expected = to_u256(&ArkFr::from_be_bytes_mod_order(&reduced.to_be_bytes::<32>()).pow(exp.as_limbs()));
+        assert_eq!(fr_pow(a, exp), expected);
+
+        match fr_inv(a) {
+            Some(inv) => assert_eq!(fr_mul(inv, a), U256::from(1)),
+            None => assert_eq!(reduced, U256::ZERO),
+        }
+    }
+
+    assert_eq!(fr_mul(U256::MAX, U256::MAX), U256::MAX.mul_mod(U256::MAX, BN254_SCALAR_MODULUS));
+    assert_eq!(fr_pow(U256::from(7), U256::ZERO), U256::from(1));
+    assert_eq!(fr_inv(U256::ZERO), None);
+    assert_eq!(fr_inv(BN254_SCALAR_MODULUS), None);
+}
+
+#[test]
+fn test_encoding() {
+    let r = BN254_SCALAR_MODULUS;
+    assert_eq!(Fr::from_u256(r), None);
+    assert_eq!(Fr::from_u256(U256::MAX), None);
+    assert_eq!(Fr::from_u256(r - U256::from(1)), Some(Fr::ONE.neg()));
+    assert_eq!(Fr::from_u256_reduced(r + U256::from(1)), Fr::ONE);
+    assert_eq!(Fr::from_u256_reduced(U256::MAX).to_u256(), U256::MAX % r);
+    assert_eq!(Fr::default(), Fr::ZERO);
+    assert_eq!(Fr::from_u256(U256::ZERO), Some(Fr::ZERO));
+
+    for value in samples() {
+        let bytes = to_u256(&value).to_be_bytes::<32>();
+        let element = Fr::from_be_bytes(&bytes).unwrap();
+        assert_eq!(element.to_be_bytes(), bytes);
+        assert_eq!(Fr::from_u256_reduced(to_u256(&value)), element);
+    }
+    assert_eq!(Fr::from_be_bytes(&r.to_be_bytes::<32>()), None);
+}
+
+#[test]
+fn test_batch_inverse() {
+    // Zeros (the first value and every seventh) are skipped and stay zero
+    let mut values: Vec<Fr> = samples().iter().enumerate().map(|(i, v)| if i % 7 == 3 { Fr::ZERO } else { fr(v) }).collect();
+    let expected: Vec<Fr> = values.iter().map(|v| v.inverse().unwrap_or(Fr::ZERO)).collect();
+    batch_inverse(&mut values);
+    assert_eq!(values, expected);
+
+    let mut zeros = [Fr::ZERO; 3];
+    batch_inverse(&mut zeros);
+    assert_eq!(zeros, [Fr::ZERO; 3]);
+    batch_inverse(&mut []);
+}
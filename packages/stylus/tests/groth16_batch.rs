@@ -0,0 +1,127 @@
+//! Groth16 Batch Verification Tests
+//!
+//! Drives the host twin of the batch verifier, which builds the same weights and
+//! pairing terms as the Stylus path, and compares it with arkworks per proof.
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use ark_bn254::{Bn254, G1Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_groth16::Groth16;
+use ark_snark::SNARK;
+use ark_std::test_rng;
+use common::*;
+use uzkv_stylus::groth16::{
+    self, batch_verify_host, compute_precomputed_pairing,
+    host_impl::verify_batch_prepared_host, PreparedVerifyingKey,
+};
+
+fn encode_batch(
+    fixture: &Fixture,
+    count: usize,
+    rng: &mut impl ark_std::rand::RngCore,
+) -> (Vec<Vec<u8>>, Vec<Vec<u8>>) {
+    let mut proofs = Vec::new();
+    let mut inputs = Vec::new();
+    for _ in 0..count {
+        let x = random_inputs(fixture.vk.gamma_abc_g1.len() - 1, rng);
+        proofs.push(proof_to_evm(&fixture.prove(&x, rng)));
+        inputs.push(inputs_to_evm(&x));
+    }
+    (proofs, inputs)
+}
+
+#[test]
+fn test_batch_all_valid() {
+    let mut rng = test_rng();
+    let fixture = Fixture::new(3, &mut rng);
+    let vk = vk_to_evm(&fixture.vk);
+    let precomputed = compute_precomputed_pairing(&vk).unwrap();
+    let (proofs, inputs) = encode_batch(&fixture, 5, &mut rng);
+
+    let prepared = PreparedVerifyingKey::from_vk_bytes(&vk).unwrap();
+    assert_eq!(verify_batch_prepared_host(&proofs, &inputs, &prepared), Ok(true));
+    assert_eq!(batch_verify_host(&proofs, &inputs, &vk, &precomputed), Ok(vec![true; 5]));
+    assert_eq!(batch_verify_host(&proofs, &inputs, &vk, &[]), Ok(vec![true; 5]));
+}
+
+#[test]
+fn test_batch_reports_each_failure() {
+    let mut rng = test_rng();
+    let fixture = Fixture::new(2, &mut rng);
+    let vk = vk_to_evm(&fixture.vk);
+    let (mut proofs, mut inputs) = encode_batch(&fixture, 4, &mut rng);
+
+    // Wrong input on proof 1, truncated proof 3
+    inputs[1][31] ^= 1;
+    proofs[3].truncate(200);
+
+    let prepared = PreparedVerifyingKey::from_vk_bytes(&vk).unwrap();
+    assert_ne!(verify_batch_prepared_host(&proofs, &inputs, &prepared), Ok(true));
+    assert_eq!(
+        batch_verify_host(&proofs, &inputs, &vk, &[]),
+        Ok(vec![true, false, true, false])
+    );
+}
+
+#[test]
+fn test_batch_rejects_cancelling_proofs() {
+    // Two invalid proofs whose errors cancel under equal weights
+    let mut rng = test_rng();
+    let fixture = Fixture::new(1, &mut rng);
+    let vk = vk_to_evm(&fixture.vk);
+    let x = random_inputs(1, &mut rng);
+    let mut p1 = fixture.prove(&x, &mut rng);
+    let mut p2 = fixture.prove(&x, &mut rng);
+    let offset = G1Affine::generator();
+    p1.c = (p1.c + offset).into_affine();
+    p2.c = (p2.c.into_group() - offset).into_affine();
+
+    let proofs = vec![proof_to_evm(&p1), proof_to_evm(&p2)];
+    let inputs = vec![inputs_to_evm(&x), inputs_to_evm(&x)];
+
+    let prepared = PreparedVerifyingKey::from_vk_bytes(&vk).unwrap();
+    assert_eq!(verify_batch_prepared_host(&proofs, &inputs, &prepared), Ok(false));
+    assert_eq!(batch_verify_host(&proofs, &inputs, &vk, &[]), Ok(vec![false, false]));
+}
+
+#[test]
+fn test_batch_agrees_with_arkworks() {
+    let mut rng = test_rng();
+    let fixture = Fixture::new(2, &mut rng);
+    let vk = vk_to_evm(&fixture.vk);
+    let pvk = Groth16::<Bn254>::process_vk(&fixture.vk).unwrap();
+
+    let mut proofs = Vec::new();
+    let mut inputs = Vec::new();
+    let mut expected = Vec::new();
+    for i in 0..6 {
+        let x = random_inputs(2, &mut rng);
+        let mut proof = fixture.prove(&x, &mut rng);
+        if i % 3 == 2 {
+            proof.a = (proof.a + G1Affine::generator()).into_affine();
+        }
+        expected.push(Groth16::<Bn254>::verify_with_processed_vk(&pvk, &x, &proof).unwrap());
+        proofs.push(proof_to_evm(&proof));
+        inputs.push(inputs_to_evm(&x));
+    }
+
+    assert_eq!(batch_verify_host(&proofs, &inputs, &vk, &[]), Ok(expected));
+}
+
+#[test]
+fn test_batch_edge_cases() {
+    let mut rng = test_rng();
+    let fixture = Fixture::new(1, &mut rng);
+    let vk = vk_to_evm(&fixture.vk);
+    let (proofs, inputs) = encode_batch(&fixture, 1, &mut rng);
+
+    assert_eq!(batch_verify_host(&[], &[], &vk, &[]), Ok(vec![]));
+    assert_eq!(batch_verify_host(&proofs, &inputs, &vk, &[]), Ok(vec![true]));
+    assert_eq!(
+        batch_verify_host(&proofs, &[], &vk, &[]),
+        Err(groth16::Error::InvalidInputs)
+    );
+}
@@ -0,0 +1,200 @@
+//! Groth16 over BLS12-381
+//!
+//! The generic verifier on `ArkBackend` must agree with the ark-groth16 host
+//! path; the backend must follow EIP-2537 semantics; and dispatch must route by
+//! curve.
+
+#![cfg(feature = "std")]
+
+use ark_bls12_381::{Bls12_381, Fq, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, UniformRand};
+use ark_groth16::{Proof, VerifyingKey};
+use ark_std::{rand::RngCore, test_rng};
+use uzkv_stylus::backend::bls12_381::{Bls12381Backend, G1_SIZE};
+use uzkv_stylus::backend::{ArkBackend, BackendError, Fallback};
+use uzkv_stylus::groth16::bls12_381::{generic, host_impl, verify_host, PROOF_SIZE};
+use uzkv_stylus::groth16::Error;
+use uzkv_stylus::types::CurveId;
+use uzkv_stylus::uzkv::{verify_offchain_on_curve, ProofSystem};
+
+/// Trapdoor fixture, as in `common::Fixture` but over BLS12-381
+struct Fixture {
+    vk: VerifyingKey<Bls12_381>,
+    alpha: Fr,
+    beta: Fr,
+    gamma: Fr,
+    delta: Fr,
+    ic_scalars: Vec<Fr>,
+}
+
+impl Fixture {
+    fn new<R: RngCore>(num_inputs: usize, rng: &mut R) -> Self {
+        let g1 = G1Projective::from(G1Affine::generator());
+        let g2 = G2Projective::from(G2Affine::generator());
+        let [alpha, beta, gamma, delta] = [(); 4].map(|_| Fr::rand(rng));
+        let ic_scalars: Vec<Fr> = (0..=num_inputs).map(|_| Fr::rand(rng)).collect();
+
+        let vk = VerifyingKey {
+            alpha_g1: (g1 * alpha).into_affine(),
+            beta_g2: (g2 * beta).into_affine(),
+            gamma_g2: (g2 * gamma).into_affine(),
+            delta_g2: (g2 * delta).into_affine(),
+            gamma_abc_g1: ic_scalars.iter().map(|s| (g1 * s).into_affine()).collect(),
+        };
+        Self { vk, alpha, beta, gamma, delta, ic_scalars }
+    }
+
+    fn prove<R: RngCore>(&self, inputs: &[Fr], rng: &mut R) -> Proof<Bls12_381> {
+        let g1 = G1Projective::from(G1Affine::generator());
+        let g2 = G2Projective::from(G2Affine::generator());
+        let mut l = self.ic_scalars[0];
+        for (x, s) in inputs.iter().zip(&self.ic_scalars[1..]) {
+            l += *x * s;
+        }
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+        let c = (a * b - self.alpha * self.beta - l * self.gamma) * self.delta.inverse().unwrap();
+        Proof {
+            a: (g1 * a).into_affine(),
+            b: (g2 * b).into_affine(),
+            c: (g1 * c).into_affine(),
+        }
+    }
+}
+
+struct Case {
+    vk: Vec<u8>,
+    proof: Vec<u8>,
+    inputs: Vec<u8>,
+}
+
+fn case(num_inputs: usize) -> Case {
+    let mut rng = test_rng();
+    let fixture = Fixture::new(num_inputs, &mut rng);
+    let x: Vec<Fr> = (0..num_inputs).map(|_| Fr::rand(&mut rng)).collect();
+    Case {
+        vk: host_impl::vk_from_ark(&fixture.vk),
+        proof: host_impl::proof_from_ark(&fixture.prove(&x, &mut rng)),
+        inputs: host_impl::inputs_from_ark(&x),
+    }
+}
+
+/// Backend standing in for a chain without the EIP-2537 precompiles
+struct Unavailable;
+
+impl Bls12381Backend for Unavailable {
+    fn g1_add(&self, _: &[u8], _: &[u8]) -> Result<[u8; G1_SIZE], BackendError> {
+        Err(BackendError)
+    }
+    fn g1_msm(&self, _: &[u8]) -> Result<[u8; G1_SIZE], BackendError> {
+        Err(BackendError)
+    }
+    fn pairing_check(&self, _: &[u8]) -> Result<bool, BackendError> {
+        Err(BackendError)
+    }
+}
+
+#[test]
+fn test_generic_matches_host() {
+    let Case { vk, proof, inputs } = case(3);
+    assert_eq!(proof.len(), PROOF_SIZE);
+
+    let mut wrong_inputs = inputs.clone();
+    wrong_inputs[31] ^= 1;
+
+    assert_eq!(verify_host(&proof, &inputs, &vk), Ok(true));
+    assert_eq!(verify_host(&proof, &wrong_inputs, &vk), Ok(false));
+    for public_inputs in [&inputs, &wrong_inputs] {
+        let expected = verify_host(&proof, public_inputs, &vk);
+        assert_eq!(generic::verify(&ArkBackend, &proof, public_inputs, &vk), expected);
+        let fallback = Fallback::new(Unavailable, ArkBackend);
+        assert_eq!(generic::verify(&fallback, &proof, public_inputs, &vk), expected);
+    }
+    assert_eq!(generic::verify(&Unavailable, &proof, &inputs, &vk), Err(Error::PrecompileFailed));
+}
+
+#[test]
+fn test_rejects_malformed_inputs() {
+    let Case { vk, proof, inputs } = case(1);
+
+    assert_eq!(verify_host(&proof[..PROOF_SIZE - 1], &inputs, &vk), Err(Error::InvalidProof));
+    assert_eq!(verify_host(&proof, &inputs[..31], &vk), Err(Error::InvalidInputs));
+
+    // Input >= r
+    let too_big = [0xffu8; 32];
+    assert_eq!(verify_host(&proof, &too_big, &vk), Err(Error::ScalarOutOfRange));
+    assert_eq!(generic::verify(&ArkBackend, &proof, &too_big, &vk), Err(Error::ScalarOutOfRange));
+
+    // Non-zero padding in A.x, and B.x.c0 >= p
+    let mut bad_padding = proof.clone();
+    bad_padding[0] = 1;
+    assert_eq!(verify_host(&bad_padding, &inputs, &vk), Err(Error::G1NotOnCurve));
+    let mut bad_b = proof.clone();
+    bad_b[G1_SIZE + 16..G1_SIZE + 64].fill(0xff);
+    assert_eq!(generic::verify(&ArkBackend, &bad_b, &inputs, &vk), Err(Error::G2NotOnCurve));
+
+    // Identity alpha
+    let mut identity_vk = vk.clone();
+    identity_vk[..G1_SIZE].fill(0);
+    assert_eq!(verify_host(&proof, &inputs, &identity_vk), Err(Error::IdentityPoint));
+
+    // A off the curve: rejected by the backend (0x0f) and by arkworks
+    let mut off_curve = proof.clone();
+    off_curve[G1_SIZE - 1] ^= 1;
+    assert_eq!(generic::verify(&ArkBackend, &off_curve, &inputs, &vk), Err(Error::PrecompileFailed));
+    assert_eq!(verify_host(&off_curve, &inputs, &vk), Err(Error::InvalidProof));
+}
+
+#[test]
+fn test_ark_backend_follows_eip2537() {
+    let mut rng = test_rng();
+    let bases: Vec<G1Affine> = (0..5).map(|_| G1Affine::rand(&mut rng)).collect();
+    let scalars: Vec<Fr> = (0..5).map(|_| Fr::rand(&mut rng)).collect();
+
+    let mut msm_input = Vec::new();
+    for (base, scalar) in bases.iter().zip(&scalars) {
+        msm_input.extend_from_slice(&host_impl::g1_from_ark(base));
+        msm_input.extend(host_impl::inputs_from_ark(&[*scalar]));
+    }
+    let expected = G1Projective::msm(&bases, &scalars).unwrap().into_affine();
+    assert_eq!(ArkBackend.g1_msm(&msm_input).unwrap(), host_impl::g1_from_ark(&expected));
+
+    let (p, q) = (host_impl::g1_from_ark(&bases[0]), host_impl::g1_from_ark(&bases[1]));
+    let sum = (G1Projective::from(bases[0]) + bases[1]).into_affine();
+    assert_eq!(ArkBackend.g1_add(&p, &q).unwrap(), host_impl::g1_from_ark(&sum));
+
+    // Empty MSM and pairing inputs are errors, unlike EIP-197
+    assert_eq!(ArkBackend.g1_msm(&[]), Err(BackendError));
+    assert_eq!(ArkBackend.pairing_check(&[]), Err(BackendError));
+
+    // On the curve but outside G1: fine for G1ADD, rejected by G1MSM
+    let outside = loop {
+        let x = Fq::rand(&mut rng);
+        if let Some(point) = G1Affine::get_point_from_x_unchecked(x, false) {
+            if !point.is_in_correct_subgroup_assuming_on_curve() {
+                break point;
+            }
+        }
+    };
+    let outside = host_impl::g1_from_ark(&outside);
+    assert!(ArkBackend.g1_add(&outside, &p).is_ok());
+    let mut msm_outside = outside.to_vec();
+    msm_outside.extend_from_slice(&[1u8; 32]);
+    assert_eq!(ArkBackend.g1_msm(&msm_outside), Err(BackendError));
+}
+
+#[test]
+fn test_dispatch_routes_by_curve() {
+    let Case { vk, proof, inputs } = case(2);
+    let groth16 = ProofSystem::Groth16 as u8;
+
+    assert_eq!(verify_offchain_on_curve(groth16, CurveId::BLS12_381, &proof, &inputs, &vk), Ok(true));
+    // The same bytes on the BN254 verifier do not even parse
+    assert!(verify_offchain_on_curve(groth16, CurveId::BN254, &proof, &inputs, &vk).is_err());
+    // PLONK has no BLS12-381 verifier
+    assert_eq!(
+        verify_offchain_on_curve(ProofSystem::Plonk as u8, CurveId::BLS12_381, &proof, &inputs, &vk),
+        Err(b"Unsupported curve".to_vec())
+    );
+}
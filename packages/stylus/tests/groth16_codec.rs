@@ -0,0 +1,137 @@
+//! Groth16 Wire Format Tests
+//!
+//! Checks that arkworks, snarkjs and canonical encodings convert losslessly and
+//! that artifacts from each source verify through the canonical entry points.
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use ark_serialize::CanonicalSerialize;
+use ark_std::test_rng;
+use common::*;
+use serde_json::Value;
+use uzkv_stylus::groth16::{self, codec, verify_host, verify_with_precomputed_host};
+
+const SNARKJS_FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../groth16-service/test/fixtures");
+
+fn compressed<T: CanonicalSerialize>(value: &T) -> Vec<u8> {
+    let mut out = Vec::new();
+    value.serialize_compressed(&mut out).unwrap();
+    out
+}
+
+fn read_json(name: &str) -> Value {
+    let path = format!("{}/{}", SNARKJS_FIXTURES, name);
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn strings(value: &Value) -> Vec<String> {
+    value.as_array().unwrap().iter().map(|v| v.as_str().unwrap().to_string()).collect()
+}
+
+fn rows(value: &Value) -> Vec<Vec<String>> {
+    value.as_array().unwrap().iter().map(strings).collect()
+}
+
+#[test]
+fn test_arkworks_compressed_roundtrip() {
+    let mut rng = test_rng();
+    let fixture = Fixture::new(3, &mut rng);
+    let inputs = random_inputs(3, &mut rng);
+    let proof = fixture.prove(&inputs, &mut rng);
+
+    let canonical_proof = codec::proof_from_compressed(&compressed(&proof)).unwrap();
+    let canonical_vk = codec::vk_from_compressed(&compressed(&fixture.vk)).unwrap();
+    let compressed_inputs: Vec<u8> = inputs.iter().flat_map(compressed).collect();
+    let canonical_inputs = codec::inputs_from_compressed(&compressed_inputs).unwrap();
+
+    assert_eq!(canonical_proof, proof_to_evm(&proof));
+    assert_eq!(canonical_vk, vk_to_evm(&fixture.vk));
+    assert_eq!(canonical_inputs, inputs_to_evm(&inputs));
+
+    assert_eq!(codec::proof_to_compressed(&canonical_proof).unwrap(), compressed(&proof));
+    assert_eq!(codec::vk_to_compressed(&canonical_vk).unwrap(), compressed(&fixture.vk));
+    assert_eq!(codec::inputs_to_compressed(&canonical_inputs).unwrap(), compressed_inputs);
+}
+
+#[test]
+fn test_host_and_stylus_paths_share_format() {
+    let mut rng = test_rng();
+    let fixture = Fixture::new(2, &mut rng);
+    let inputs = random_inputs(2, &mut rng);
+    let proof = proof_to_evm(&fixture.prove(&inputs, &mut rng));
+    let vk = vk_to_evm(&fixture.vk);
+    let inputs = inputs_to_evm(&inputs);
+
+    // Same bytes through the arkworks verifier and the precompile-shaped path
+    let precomputed = groth16::compute_precomputed_pairing(&vk).unwrap();
+    assert_eq!(verify_host(&proof, &inputs, &vk), Ok(true));
+    assert_eq!(verify_with_precomputed_host(&proof, &inputs, &vk, &precomputed), Ok(true));
+
+    let mut wrong = inputs.clone();
+    wrong[63] ^= 1;
+    assert_eq!(verify_host(&proof, &wrong, &vk), Ok(false));
+    assert_eq!(verify_host(&proof, &inputs[..32], &vk), Err(groth16::Error::InvalidInputs));
+}
+
+#[test]
+fn test_host_rejects_non_canonical_encoding() {
+    let mut rng = test_rng();
+    let fixture = Fixture::new(1, &mut rng);
+    let inputs = random_inputs(1, &mut rng);
+    let proof = fixture.prove(&inputs, &mut rng);
+    let vk = vk_to_evm(&fixture.vk);
+
+    // arkworks-compressed VKs are not accepted directly; only proofs may be compressed
+    let proof_bytes = proof_to_evm(&proof);
+    assert!(verify_host(&proof_bytes, &inputs_to_evm(&inputs), &compressed(&fixture.vk)).is_err());
+
+    // Inputs >= r
+    let too_big = [0xffu8; 32];
+    assert_eq!(
+        verify_host(&proof_to_evm(&proof), &too_big, &vk),
+        Err(groth16::Error::ScalarOutOfRange)
+    );
+}
+
+#[test]
+fn test_snarkjs_fixture_verifies() {
+    let vk_json = read_json("verification-key.json");
+    let proof_json = read_json("valid-proof.json");
+    let public_json = read_json("valid-public.json");
+
+    let ic = rows(&vk_json["IC"]);
+    let vk = codec::vk_from_snarkjs(
+        &strings(&vk_json["vk_alpha_1"]),
+        &rows(&vk_json["vk_beta_2"]),
+        &rows(&vk_json["vk_gamma_2"]),
+        &rows(&vk_json["vk_delta_2"]),
+        &ic,
+    )
+    .unwrap();
+    let proof = codec::proof_from_snarkjs(
+        &strings(&proof_json["pi_a"]),
+        &rows(&proof_json["pi_b"]),
+        &strings(&proof_json["pi_c"]),
+    )
+    .unwrap();
+    let inputs = codec::inputs_from_snarkjs(&strings(&public_json)).unwrap();
+
+    assert_eq!(verify_host(&proof, &inputs, &vk), Ok(true));
+
+    // Converting back reproduces the snarkjs coordinates
+    assert_eq!(codec::g1_to_snarkjs(&proof[0..64]).unwrap().to_vec(), strings(&proof_json["pi_a"]));
+    let pi_b: Vec<Vec<String>> = codec::g2_to_snarkjs(&proof[64..192])
+        .unwrap()
+        .iter()
+        .map(|row| row.to_vec())
+        .collect();
+    assert_eq!(pi_b, rows(&proof_json["pi_b"]));
+
+    // Bumping a public signal invalidates the proof
+    let mut signals = strings(&public_json);
+    signals[0] = "2".to_string();
+    let wrong = codec::inputs_from_snarkjs(&signals).unwrap();
+    assert_eq!(verify_host(&proof, &wrong, &vk), Ok(false));
+}
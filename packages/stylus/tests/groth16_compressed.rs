@@ -0,0 +1,117 @@
+//! Compressed Groth16 Proof Tests
+//!
+//! 128-byte proofs are decompressed in-contract; they must decode to exactly the
+//! arkworks points and give the same result as the uncompressed proof on every
+//! verification path.
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use ark_bn254::{Fq2, G2Affine};
+use ark_ff::UniformRand;
+use ark_serialize::CanonicalSerialize;
+use ark_std::test_rng;
+use common::*;
+use uzkv_stylus::groth16::{
+    batch_verify_host, codec, compute_precomputed_pairing, verify_host, verify_with_precomputed_host, Error,
+    COMPRESSED_PROOF_SIZE,
+};
+
+fn compressed<T: CanonicalSerialize>(value: &T) -> Vec<u8> {
+    let mut out = Vec::new();
+    value.serialize_compressed(&mut out).unwrap();
+    out
+}
+
+#[test]
+fn test_decompression_matches_arkworks() {
+    let mut rng = test_rng();
+    let fixture = Fixture::new(1, &mut rng);
+
+    // Enough proofs to hit both y-sign flags on every point
+    for _ in 0..16 {
+        let proof = fixture.prove(&random_inputs(1, &mut rng), &mut rng);
+        let ark_bytes = compressed(&proof);
+        assert_eq!(ark_bytes.len(), COMPRESSED_PROOF_SIZE);
+
+        let canonical = proof_to_evm(&proof);
+        assert_eq!(codec::decompress_proof(&ark_bytes).unwrap().to_vec(), canonical);
+        assert_eq!(codec::compress_proof(&canonical).unwrap().to_vec(), ark_bytes);
+    }
+}
+
+#[test]
+fn test_compressed_and_uncompressed_agree() {
+    let mut rng = test_rng();
+    let fixture = Fixture::new(2, &mut rng);
+    let vk = vk_to_evm(&fixture.vk);
+    let precomputed = compute_precomputed_pairing(&vk).unwrap();
+    let x = random_inputs(2, &mut rng);
+    let proof = fixture.prove(&x, &mut rng);
+    let inputs = inputs_to_evm(&x);
+
+    let mut wrong_inputs = inputs.clone();
+    wrong_inputs[63] ^= 1;
+
+    for public_inputs in [&inputs, &wrong_inputs] {
+        let expected = verify_host(&proof_to_evm(&proof), public_inputs, &vk);
+        let packed = compressed(&proof);
+        assert_eq!(verify_host(&packed, public_inputs, &vk), expected);
+        assert_eq!(verify_with_precomputed_host(&packed, public_inputs, &vk, &precomputed), expected);
+    }
+    assert_eq!(verify_host(&compressed(&proof), &inputs, &vk), Ok(true));
+}
+
+#[test]
+fn test_batch_mixes_encodings() {
+    let mut rng = test_rng();
+    let fixture = Fixture::new(1, &mut rng);
+    let vk = vk_to_evm(&fixture.vk);
+
+    let mut proofs = Vec::new();
+    let mut inputs = Vec::new();
+    for i in 0..4 {
+        let x = random_inputs(1, &mut rng);
+        let proof = fixture.prove(&x, &mut rng);
+        proofs.push(if i % 2 == 0 { compressed(&proof) } else { proof_to_evm(&proof) });
+        inputs.push(inputs_to_evm(&x));
+    }
+    assert_eq!(batch_verify_host(&proofs, &inputs, &vk, &[]), Ok(vec![true; 4]));
+
+    // Flipping the y-sign of a compressed A negates it: a valid point, wrong proof
+    proofs[2][31] ^= 0x80;
+    assert_eq!(batch_verify_host(&proofs, &inputs, &vk, &[]), Ok(vec![true, true, false, true]));
+}
+
+#[test]
+fn test_rejects_malformed_compressed_proofs() {
+    let mut rng = test_rng();
+    let fixture = Fixture::new(1, &mut rng);
+    let vk = vk_to_evm(&fixture.vk);
+    let x = random_inputs(1, &mut rng);
+    let proof = fixture.prove(&x, &mut rng);
+    let inputs = inputs_to_evm(&x);
+
+    // Both flags set on C
+    let mut bad_flags = compressed(&proof);
+    bad_flags[127] |= 0xc0;
+    assert_eq!(verify_host(&bad_flags, &inputs, &vk), Err(Error::InvalidProof));
+
+    // Truncated
+    let packed = compressed(&proof);
+    assert_eq!(verify_host(&packed[..127], &inputs, &vk), Err(Error::InvalidProof));
+
+    // B decompresses onto the twist but outside the subgroup
+    let outside = loop {
+        let candidate = Fq2::rand(&mut rng);
+        if let Some(point) = G2Affine::get_point_from_x_unchecked(candidate, false) {
+            if !point.is_in_correct_subgroup_assuming_on_curve() {
+                break point;
+            }
+        }
+    };
+    let mut bad_b = compressed(&proof);
+    bad_b[32..96].copy_from_slice(&compressed(&outside));
+    assert_eq!(verify_host(&bad_b, &inputs, &vk), Err(Error::G2NotInSubgroup));
+}
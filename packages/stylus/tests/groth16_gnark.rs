@@ -0,0 +1,396 @@
+//! gnark Groth16 Tests
+//!
+//! Fixtures are simulated with the setup and Pedersen trapdoors and written in
+//! gnark's binary layout (`WriteRawTo` and compressed `WriteTo`), commitment
+//! hashes and all. The generic verifier on `ArkBackend` must agree with the
+//! arkworks host path on every case. `tests/fixtures/gnark` holds the output
+//! of gnark itself (`go run .` there), checked by the ignored fixture test
+//! until it is committed.
+
+#![cfg(feature = "std")]
+
+use ark_bn254::{Fq, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, Field, One, PrimeField, UniformRand, Zero};
+use ark_std::{rand::RngCore, test_rng};
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::backend::ArkBackend;
+use uzkv_stylus::groth16::gnark::{
+    commitment_challenge, generic, hash_to_field, inputs_from_witness, verify_host, Proof,
+    VerifyingKey, COMMITMENT_DST,
+};
+use uzkv_stylus::groth16::{codec, Error};
+use uzkv_stylus::uzkv::{verify_offchain, ProofSystem};
+
+// =========================================================================
+// gnark binary encoding
+// =========================================================================
+
+fn is_largest_fq(y: &Fq) -> bool {
+    *y > -*y
+}
+
+fn g1_bytes(point: &G1Affine, compressed: bool) -> Vec<u8> {
+    if !compressed {
+        return codec::g1_from_ark(point).to_vec();
+    }
+    let mut out = codec::g1_from_ark(point)[..32].to_vec();
+    out[0] |= match point.xy() {
+        None => 0b01 << 6,
+        Some((_, y)) if is_largest_fq(y) => 0b11 << 6,
+        Some(_) => 0b10 << 6,
+    };
+    out
+}
+
+fn g2_bytes(point: &G2Affine, compressed: bool) -> Vec<u8> {
+    if !compressed {
+        return codec::g2_from_ark(point).to_vec();
+    }
+    let mut out = codec::g2_from_ark(point)[..64].to_vec();
+    out[0] |= match point.xy() {
+        None => 0b01 << 6,
+        // Fq2 orders by c1, then c0: gnark's lexicographic order
+        Some((_, y)) if *y > -*y => 0b11 << 6,
+        Some(_) => 0b10 << 6,
+    };
+    out
+}
+
+fn fr_to_u256(value: &Fr) -> U256 {
+    U256::from_be_slice(&value.into_bigint().to_bytes_be())
+}
+
+fn u256_to_fr(value: &U256) -> Fr {
+    Fr::from_be_bytes_mod_order(&value.to_be_bytes::<32>())
+}
+
+#[derive(Clone)]
+struct GnarkVk {
+    alpha: G1Affine,
+    beta_g1: G1Affine,
+    beta: G2Affine,
+    gamma: G2Affine,
+    delta_g1: G1Affine,
+    delta: G2Affine,
+    k: Vec<G1Affine>,
+    committed: Vec<Vec<u64>>,
+    keys: Vec<(G2Affine, G2Affine)>,
+}
+
+impl GnarkVk {
+    fn encode(&self, compressed: bool) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend(g1_bytes(&self.alpha, compressed));
+        out.extend(g1_bytes(&self.beta_g1, compressed));
+        out.extend(g2_bytes(&self.beta, compressed));
+        out.extend(g2_bytes(&self.gamma, compressed));
+        out.extend(g1_bytes(&self.delta_g1, compressed));
+        out.extend(g2_bytes(&self.delta, compressed));
+        out.extend((self.k.len() as u32).to_be_bytes());
+        for point in &self.k {
+            out.extend(g1_bytes(point, compressed));
+        }
+        out.extend((self.committed.len() as u32).to_be_bytes());
+        for wires in &self.committed {
+            out.extend((wires.len() as u32).to_be_bytes());
+            for wire in wires {
+                out.extend(wire.to_be_bytes());
+            }
+        }
+        out.extend((self.keys.len() as u32).to_be_bytes());
+        for (g, g_root_sigma_neg) in &self.keys {
+            out.extend(g2_bytes(g, compressed));
+            out.extend(g2_bytes(g_root_sigma_neg, compressed));
+        }
+        out
+    }
+}
+
+#[derive(Clone)]
+struct GnarkProof {
+    a: G1Affine,
+    b: G2Affine,
+    c: G1Affine,
+    commitments: Vec<G1Affine>,
+    pok: G1Affine,
+}
+
+impl GnarkProof {
+    fn encode(&self, compressed: bool) -> Vec<u8> {
+        let mut out = g1_bytes(&self.a, compressed);
+        out.extend(g2_bytes(&self.b, compressed));
+        out.extend(g1_bytes(&self.c, compressed));
+        out.extend((self.commitments.len() as u32).to_be_bytes());
+        for point in &self.commitments {
+            out.extend(g1_bytes(point, compressed));
+        }
+        out.extend(g1_bytes(&self.pok, compressed));
+        out
+    }
+}
+
+// =========================================================================
+// Trapdoor setup
+// =========================================================================
+
+struct Setup {
+    vk: GnarkVk,
+    alpha: Fr,
+    beta: Fr,
+    gamma: Fr,
+    delta: Fr,
+    k: Vec<Fr>,
+    sigmas: Vec<Fr>,
+}
+
+impl Setup {
+    fn new<R: RngCore>(num_inputs: usize, committed: Vec<Vec<u64>>, rng: &mut R) -> Self {
+        let g1 = G1Projective::from(G1Affine::generator());
+        let g2 = G2Projective::from(G2Affine::generator());
+        let [alpha, beta, gamma, delta, g] = [(); 5].map(|_| Fr::rand(rng));
+        let k: Vec<Fr> = (0..=num_inputs + committed.len()).map(|_| Fr::rand(rng)).collect();
+        let sigmas: Vec<Fr> = committed.iter().map(|_| Fr::rand(rng)).collect();
+
+        let vk = GnarkVk {
+            alpha: (g1 * alpha).into_affine(),
+            beta_g1: (g1 * beta).into_affine(),
+            beta: (g2 * beta).into_affine(),
+            gamma: (g2 * gamma).into_affine(),
+            delta_g1: (g1 * delta).into_affine(),
+            delta: (g2 * delta).into_affine(),
+            k: k.iter().map(|s| (g1 * s).into_affine()).collect(),
+            keys: sigmas
+                .iter()
+                .map(|sigma| {
+                    let root_sigma_neg = -(g * sigma.inverse().unwrap());
+                    ((g2 * g).into_affine(), (g2 * root_sigma_neg).into_affine())
+                })
+                .collect(),
+            committed,
+        };
+        Self { vk, alpha, beta, gamma, delta, k, sigmas }
+    }
+
+    fn prove<R: RngCore>(&self, x: &[Fr], rng: &mut R) -> GnarkProof {
+        let g1 = G1Projective::from(G1Affine::generator());
+        let g2 = G2Projective::from(G2Affine::generator());
+
+        // Commitments and their hashes, appended to the public inputs as gnark does
+        let mut inputs = x.to_vec();
+        let mut d = Vec::new();
+        let mut hashes = Vec::new();
+        for wires in &self.vk.committed {
+            let d_i = Fr::rand(rng);
+            let mut prehash = codec::g1_from_ark(&(g1 * d_i).into_affine()).to_vec();
+            for wire in wires {
+                prehash.extend(codec::inputs_from_ark(&[inputs[*wire as usize - 1]]));
+            }
+            let hash = hash_to_field(&prehash, COMMITMENT_DST);
+            inputs.push(u256_to_fr(&hash));
+            hashes.push(hash);
+            d.push(d_i);
+        }
+
+        // PoK = Σ r^i·d_i/σ_i, so that e(PoK, G) · Π e(r^i·D_i, -G/σ_i) == 1
+        let r = u256_to_fr(&commitment_challenge(&hashes));
+        let mut pok = Fr::zero();
+        let mut weight = Fr::one();
+        for (d_i, sigma) in d.iter().zip(&self.sigmas) {
+            pok += weight * d_i * sigma.inverse().unwrap();
+            weight *= r;
+        }
+
+        // l = k_0 + Σ x_j·k_{j+1} + Σ d_i
+        let mut l = self.k[0] + d.iter().sum::<Fr>();
+        for (x, k) in inputs.iter().zip(&self.k[1..]) {
+            l += *x * k;
+        }
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+        let c = (a * b - self.alpha * self.beta - l * self.gamma) * self.delta.inverse().unwrap();
+
+        GnarkProof {
+            a: (g1 * a).into_affine(),
+            b: (g2 * b).into_affine(),
+            c: (g1 * c).into_affine(),
+            commitments: d.iter().map(|d_i| (g1 * d_i).into_affine()).collect(),
+            pok: (g1 * pok).into_affine(),
+        }
+    }
+}
+
+struct Case {
+    setup: Setup,
+    proof: GnarkProof,
+    inputs: Vec<u8>,
+}
+
+fn case(num_inputs: usize, committed: Vec<Vec<u64>>) -> Case {
+    let mut rng = test_rng();
+    let setup = Setup::new(num_inputs, committed, &mut rng);
+    let x: Vec<Fr> = (0..num_inputs).map(|_| Fr::rand(&mut rng)).collect();
+    let proof = setup.prove(&x, &mut rng);
+    Case { setup, proof, inputs: codec::inputs_from_ark(&x) }
+}
+
+/// Both verifiers, checked to agree
+fn fixture_file(name: &str) -> Vec<u8> {
+    let path = format!("{}/tests/fixtures/gnark/{}", env!("CARGO_MANIFEST_DIR"), name);
+    std::fs::read(&path).unwrap_or_else(|_| panic!("missing fixture {} (run `go run .` in its directory)", path))
+}
+
+fn verify(proof: &[u8], inputs: &[u8], vk: &[u8]) -> Result<bool, Error> {
+    let host = verify_host(proof, inputs, vk);
+    assert_eq!(generic::verify(&ArkBackend, proof, inputs, vk), host);
+    host
+}
+
+// =========================================================================
+// Tests
+// =========================================================================
+
+#[test]
+fn test_commitment_proof_verifies() {
+    let Case { setup, proof, inputs } = case(2, vec![vec![1, 2]]);
+
+    for compressed in [false, true] {
+        let vk = setup.vk.encode(compressed);
+        let proof = proof.encode(compressed);
+        assert_eq!(verify(&proof, &inputs, &vk), Ok(true));
+
+        let mut wrong_inputs = inputs.clone();
+        wrong_inputs[31] ^= 1;
+        assert_eq!(verify(&proof, &wrong_inputs, &vk), Ok(false));
+    }
+
+    // Both encodings parse to the same points
+    assert_eq!(
+        VerifyingKey::decode(&setup.vk.encode(false)),
+        VerifyingKey::decode(&setup.vk.encode(true))
+    );
+    assert_eq!(Proof::decode(&proof.encode(false)), Proof::decode(&proof.encode(true)));
+}
+
+#[test]
+fn test_commitment_and_pok_are_bound() {
+    let Case { setup, proof, inputs } = case(1, vec![vec![1]]);
+    let vk = setup.vk.encode(false);
+    let mut rng = test_rng();
+
+    // Another valid commitment changes the hashed public input
+    let mut other_commitment = proof.clone();
+    other_commitment.commitments[0] = G1Affine::rand(&mut rng);
+    assert_eq!(verify(&other_commitment.encode(false), &inputs, &vk), Ok(false));
+
+    // A wrong proof of knowledge fails the Pedersen pairing
+    let mut other_pok = proof.clone();
+    other_pok.pok = (proof.pok + G1Affine::generator()).into_affine();
+    assert_eq!(verify(&other_pok.encode(false), &inputs, &vk), Ok(false));
+
+    // Dropping the commitment is a layout error against this VK
+    let mut dropped = proof.clone();
+    dropped.commitments.clear();
+    assert_eq!(verify(&dropped.encode(false), &inputs, &vk), Err(Error::InvalidProof));
+}
+
+#[test]
+fn test_without_commitments() {
+    let Case { setup, proof, inputs } = case(3, vec![]);
+    assert!(proof.pok.is_zero());
+
+    for compressed in [false, true] {
+        assert_eq!(verify(&proof.encode(compressed), &inputs, &setup.vk.encode(compressed)), Ok(true));
+    }
+}
+
+#[test]
+fn test_multiple_commitments() {
+    // The second commitment covers the first commitment's hash (wire 3)
+    let Case { setup, proof, inputs } = case(2, vec![vec![1], vec![2, 3]]);
+    let vk = setup.vk.encode(true);
+    assert_eq!(VerifyingKey::decode(&vk).unwrap().num_inputs(), 2);
+    assert_eq!(verify(&proof.encode(true), &inputs, &vk), Ok(true));
+
+    // Swapping the commitments breaks both the hashes and the folded PoK
+    let mut swapped = proof.clone();
+    swapped.commitments.swap(0, 1);
+    assert_eq!(verify(&swapped.encode(true), &inputs, &vk), Ok(false));
+}
+
+#[test]
+fn test_rejects_malformed_vk() {
+    let Case { setup, proof, inputs } = case(1, vec![vec![1]]);
+    let proof = proof.encode(false);
+
+    // Trailing bytes
+    let mut trailing = setup.vk.encode(false);
+    trailing.push(0);
+    assert_eq!(verify(&proof, &inputs, &trailing), Err(Error::InvalidVerificationKey));
+
+    // Committed wire past the public inputs
+    let mut vk = GnarkVk { committed: vec![vec![2]], ..setup.vk.clone() };
+    assert_eq!(verify(&proof, &inputs, &vk.encode(false)), Err(Error::InvalidVerificationKey));
+
+    // Identity gamma
+    vk = GnarkVk { gamma: G2Affine::zero(), ..setup.vk.clone() };
+    assert_eq!(verify(&proof, &inputs, &vk.encode(true)), Err(Error::IdentityPoint));
+
+    // Off-curve alpha in raw form
+    let mut off_curve = setup.vk.encode(false);
+    off_curve[63] ^= 1;
+    assert_eq!(verify(&proof, &inputs, &off_curve), Err(Error::G1NotOnCurve));
+}
+
+#[test]
+fn test_dispatch_and_witness() {
+    let Case { setup, proof, inputs } = case(2, vec![vec![1]]);
+    let vk = setup.vk.encode(true);
+    let proof = proof.encode(true);
+    let gnark = ProofSystem::Groth16Gnark as u8;
+
+    assert_eq!(verify_offchain(gnark, &proof, &inputs, &vk), Ok(true));
+    // The canonical Groth16 verifier cannot read gnark artifacts
+    assert!(verify_offchain(ProofSystem::Groth16 as u8, &proof, &inputs, &vk).is_err());
+
+    // gnark public witness: [nbPublic][nbSecret][n] header, then the scalars
+    let mut witness = Vec::new();
+    for word in [2u32, 0, 2] {
+        witness.extend(word.to_be_bytes());
+    }
+    witness.extend_from_slice(&inputs);
+    assert_eq!(inputs_from_witness(&witness), Ok(&inputs[..]));
+    assert_eq!(verify_offchain(gnark, &proof, inputs_from_witness(&witness).unwrap(), &vk), Ok(true));
+}
+
+#[test]
+fn test_commitment_hash_is_reduced_and_bound() {
+    let mut rng = test_rng();
+    let point = codec::g1_from_ark(&G1Affine::rand(&mut rng));
+    let hash = hash_to_field(&point, COMMITMENT_DST);
+    assert_eq!(fr_to_u256(&u256_to_fr(&hash)), hash);
+
+    let mut other = point;
+    other[63] ^= 1;
+    assert_ne!(hash_to_field(&other, COMMITMENT_DST), hash);
+}
+
+#[test]
+#[ignore = "needs gnark output in tests/fixtures/gnark (go run .)"]
+fn test_gnark_fixture_verifies() {
+    let (vk, proof, witness) = (fixture_file("vk.bin"), fixture_file("proof.bin"), fixture_file("witness.bin"));
+    let inputs = inputs_from_witness(&witness).unwrap();
+
+    // y² = x with api.Commit over x and y: one commitment covering public wire 1
+    let parsed = VerifyingKey::decode(&vk).unwrap();
+    assert_eq!(parsed.num_inputs(), 1);
+    assert_eq!(parsed.committed, vec![vec![1]]);
+    assert_eq!(Proof::decode(&proof).unwrap().commitments.len(), 1);
+    assert_eq!(inputs, &U256::from(9).to_be_bytes::<32>()[..]);
+
+    assert_eq!(verify(&proof, inputs, &vk), Ok(true));
+    assert_eq!(verify_offchain(ProofSystem::Groth16Gnark as u8, &proof, inputs, &vk), Ok(true));
+
+    let wrong = U256::from(4).to_be_bytes::<32>();
+    assert_eq!(verify(&proof, &wrong, &vk), Ok(false));
+}
@@ -0,0 +1,62 @@
+//! Native G1 MSM Tests
+//!
+//! `groth16::msm::msm` must agree with arkworks' `VariableBaseMSM` for every
+//! window size, including zero scalars and identity bases.
+
+#![cfg(feature = "std")]
+
+use ark_bn254::{Fr, G1Affine, G1Projective};
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_ff::{BigInteger, PrimeField, UniformRand};
+use ark_std::test_rng;
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::groth16::{codec, msm};
+
+fn to_u256(scalar: &Fr) -> U256 {
+    U256::from_be_slice(&scalar.into_bigint().to_bytes_be())
+}
+
+fn check(bases: &[G1Affine], scalars: &[Fr]) {
+    let expected = G1Projective::msm(bases, scalars).unwrap().into_affine();
+    let encoded: Vec<[u8; 64]> = bases.iter().map(codec::g1_from_ark).collect();
+    let raw: Vec<U256> = scalars.iter().map(to_u256).collect();
+    assert_eq!(msm::msm(&encoded, &raw).unwrap(), codec::g1_from_ark(&expected));
+}
+
+#[test]
+fn test_msm_matches_arkworks() {
+    let mut rng = test_rng();
+    // Window sizes 3 (n < 32) through 6
+    for n in [1, 2, 5, 31, 32, 33, 100, 300] {
+        let bases: Vec<G1Affine> = (0..n).map(|_| G1Affine::rand(&mut rng)).collect();
+        let scalars: Vec<Fr> = (0..n).map(|_| Fr::rand(&mut rng)).collect();
+        check(&bases, &scalars);
+    }
+}
+
+#[test]
+fn test_msm_edge_cases() {
+    let mut rng = test_rng();
+    let mut bases: Vec<G1Affine> = (0..8).map(|_| G1Affine::rand(&mut rng)).collect();
+    let mut scalars: Vec<Fr> = (0..8).map(|_| Fr::rand(&mut rng)).collect();
+
+    // Zero and small scalars, an identity base, a repeated base and -1
+    scalars[0] = Fr::from(0u64);
+    scalars[1] = Fr::from(1u64);
+    scalars[2] = -Fr::from(1u64);
+    bases[3] = G1Affine::identity();
+    bases[5] = bases[4];
+    check(&bases, &scalars);
+
+    // Terms that cancel to the identity
+    let base = G1Affine::rand(&mut rng);
+    let x = Fr::rand(&mut rng);
+    check(&[base, base], &[x, -x]);
+}
+
+#[test]
+fn test_msm_rejects_non_canonical_base() {
+    let mut base = [0xffu8; 64];
+    base[0] = 0x3f;
+    assert!(msm::msm(&[base], &[U256::from(1)]).is_err());
+}
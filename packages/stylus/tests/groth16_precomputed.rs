@@ -0,0 +1,123 @@
+//! Precomputed-VK Groth16 Verification Tests
+//!
+//! Exercises the prepared-VK path through its host twin, which consumes the same
+//! byte layouts as the Stylus contract, and checks it against arkworks.
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use ark_bn254::{Bn254, Fr, G1Affine};
+use ark_ec::AffineRepr;
+use ark_groth16::Groth16;
+use ark_snark::SNARK;
+use ark_std::test_rng;
+use common::*;
+use uzkv_stylus::groth16::{
+    self, compute_precomputed_pairing, verify_with_precomputed_host, PreparedVerifyingKey,
+};
+
+#[test]
+fn test_valid_proofs_pass() {
+    let mut rng = test_rng();
+    for num_inputs in [0, 1, 3] {
+        let fixture = Fixture::new(num_inputs, &mut rng);
+        let vk = vk_to_evm(&fixture.vk);
+        let precomputed = compute_precomputed_pairing(&vk).unwrap();
+
+        let inputs = random_inputs(num_inputs, &mut rng);
+        let proof = fixture.prove(&inputs, &mut rng);
+
+        let result =
+            verify_with_precomputed_host(&proof_to_evm(&proof), &inputs_to_evm(&inputs), &vk, &precomputed);
+        assert_eq!(result, Ok(true), "num_inputs = {}", num_inputs);
+    }
+}
+
+#[test]
+fn test_tampered_proofs_fail() {
+    let mut rng = test_rng();
+    let fixture = Fixture::new(2, &mut rng);
+    let vk = vk_to_evm(&fixture.vk);
+    let precomputed = compute_precomputed_pairing(&vk).unwrap();
+
+    let inputs = random_inputs(2, &mut rng);
+    let proof = fixture.prove(&inputs, &mut rng);
+    let proof_bytes = proof_to_evm(&proof);
+    let input_bytes = inputs_to_evm(&inputs);
+
+    // Wrong public input
+    let mut wrong_inputs = inputs.clone();
+    wrong_inputs[1] += Fr::from(1u64);
+    assert_eq!(
+        verify_with_precomputed_host(&proof_bytes, &inputs_to_evm(&wrong_inputs), &vk, &precomputed),
+        Ok(false)
+    );
+
+    // A replaced by another valid curve point
+    let mut tampered = proof.clone();
+    tampered.a = (tampered.a + G1Affine::generator()).into();
+    assert_eq!(
+        verify_with_precomputed_host(&proof_to_evm(&tampered), &input_bytes, &vk, &precomputed),
+        Ok(false)
+    );
+
+    // C flipped to an off-curve point
+    let mut off_curve = proof_bytes.clone();
+    off_curve[255] ^= 1;
+    assert_eq!(
+        verify_with_precomputed_host(&off_curve, &input_bytes, &vk, &precomputed),
+        Err(groth16::Error::G1NotOnCurve)
+    );
+
+    // Precomputed data belonging to a different VK
+    let other = Fixture::new(2, &mut rng);
+    let other_precomputed = compute_precomputed_pairing(&vk_to_evm(&other.vk)).unwrap();
+    assert_eq!(
+        verify_with_precomputed_host(&proof_bytes, &input_bytes, &vk, &other_precomputed),
+        Err(groth16::Error::InvalidVerificationKey)
+    );
+
+    // Input count mismatch
+    assert_eq!(
+        verify_with_precomputed_host(&proof_bytes, &input_bytes[..32], &vk, &precomputed),
+        Err(groth16::Error::InvalidInputs)
+    );
+}
+
+#[test]
+fn test_precomputed_agrees_with_arkworks() {
+    let mut rng = test_rng();
+    let fixture = Fixture::new(4, &mut rng);
+    let vk = vk_to_evm(&fixture.vk);
+    let prepared = PreparedVerifyingKey::from_vk_bytes(&vk).unwrap();
+    let precomputed = prepared.encode();
+    let pvk = Groth16::<Bn254>::process_vk(&fixture.vk).unwrap();
+
+    for i in 0..8 {
+        let inputs = random_inputs(4, &mut rng);
+        let mut proof = fixture.prove(&inputs, &mut rng);
+        if i % 2 == 1 {
+            proof.c = (proof.c + G1Affine::generator()).into();
+        }
+
+        let expected = Groth16::<Bn254>::verify_with_processed_vk(&pvk, &inputs, &proof).unwrap();
+        let actual = verify_with_precomputed_host(
+            &proof_to_evm(&proof),
+            &inputs_to_evm(&inputs),
+            &vk,
+            &precomputed,
+        )
+        .unwrap();
+        let direct = groth16::host_impl::verify_prepared_host(
+            &proof_to_evm(&proof),
+            &inputs_to_evm(&inputs),
+            &prepared,
+        )
+        .unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(direct, expected);
+        assert_eq!(expected, i % 2 == 0);
+    }
+}
@@ -0,0 +1,176 @@
+//! Groth16 Input Validation Tests
+//!
+//! Every malformed proof, input or VK must be rejected with its own error, on the
+//! verification path (through the dispatcher too) and at registration
+//! (`compute_precomputed_pairing`).
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use ark_bn254::{Fq2, Fr, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::UniformRand;
+use ark_std::test_rng;
+use common::*;
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::groth16::{
+    codec, compute_precomputed_pairing, curve, validate_vk, verify_host, verify_with_precomputed_host, Error,
+};
+use uzkv_stylus::utils::BN254_SCALAR_MODULUS;
+use uzkv_stylus::uzkv::{verify_offchain, ProofSystem};
+
+struct Case {
+    proof: Vec<u8>,
+    inputs: Vec<u8>,
+    vk: Vec<u8>,
+    precomputed: Vec<u8>,
+}
+
+fn valid_case() -> Case {
+    let mut rng = test_rng();
+    let fixture = Fixture::new(2, &mut rng);
+    let x = random_inputs(2, &mut rng);
+    let vk = vk_to_evm(&fixture.vk);
+    Case {
+        proof: proof_to_evm(&fixture.prove(&x, &mut rng)),
+        inputs: inputs_to_evm(&x),
+        precomputed: compute_precomputed_pairing(&vk).unwrap(),
+        vk,
+    }
+}
+
+/// Both verification paths must agree on the outcome
+fn verify_both(case: &Case, proof: &[u8], inputs: &[u8]) -> Result<bool, Error> {
+    let host = verify_host(proof, inputs, &case.vk);
+    let prepared = verify_with_precomputed_host(proof, inputs, &case.vk, &case.precomputed);
+    assert_eq!(host, prepared);
+    host
+}
+
+/// A point on the twist that is not in the order-r subgroup
+fn g2_outside_subgroup() -> Vec<u8> {
+    let mut rng = test_rng();
+    loop {
+        let x = Fq2::rand(&mut rng);
+        if let Some(point) = G2Affine::get_point_from_x_unchecked(x, false) {
+            if !point.is_in_correct_subgroup_assuming_on_curve() {
+                return codec::g2_from_ark(&point).to_vec();
+            }
+        }
+    }
+}
+
+#[test]
+fn test_valid_case_passes() {
+    let case = valid_case();
+    assert_eq!(verify_both(&case, &case.proof, &case.inputs), Ok(true));
+}
+
+#[test]
+fn test_rejects_non_canonical_inputs() {
+    // x + r passes the pairing check but must not be accepted
+    let case = valid_case();
+    let shifted = U256::from_be_slice(&case.inputs[0..32]) + BN254_SCALAR_MODULUS;
+    let mut inputs = case.inputs.clone();
+    inputs[0..32].copy_from_slice(&shifted.to_be_bytes::<32>());
+    assert_eq!(verify_both(&case, &case.proof, &inputs), Err(Error::ScalarOutOfRange));
+}
+
+#[test]
+fn test_rejects_off_curve_a_and_c() {
+    let case = valid_case();
+    for offset in [63, 255] {
+        let mut proof = case.proof.clone();
+        proof[offset] ^= 1;
+        assert_eq!(verify_both(&case, &proof, &case.inputs), Err(Error::G1NotOnCurve));
+    }
+}
+
+#[test]
+fn test_rejects_bad_b() {
+    let case = valid_case();
+
+    let mut off_curve = case.proof.clone();
+    off_curve[191] ^= 1;
+    assert_eq!(verify_both(&case, &off_curve, &case.inputs), Err(Error::G2NotOnCurve));
+
+    let mut outside = case.proof.clone();
+    outside[64..192].copy_from_slice(&g2_outside_subgroup());
+    assert_eq!(verify_both(&case, &outside, &case.inputs), Err(Error::G2NotInSubgroup));
+}
+
+#[test]
+fn test_dispatch_keeps_distinct_errors() {
+    // The dispatcher reports the `groth16::Error` of each failure, not one
+    // generic message
+    let case = valid_case();
+    let shifted = U256::from_be_slice(&case.inputs[0..32]) + BN254_SCALAR_MODULUS;
+    let mut inputs = case.inputs.clone();
+    inputs[0..32].copy_from_slice(&shifted.to_be_bytes::<32>());
+    let mut outside = case.proof.clone();
+    outside[64..192].copy_from_slice(&g2_outside_subgroup());
+    let mut off_curve = case.proof.clone();
+    off_curve[63] ^= 1;
+
+    let groth16 = ProofSystem::Groth16 as u8;
+    for (proof, inputs, expected) in [
+        (&case.proof, &inputs, Error::ScalarOutOfRange),
+        (&outside, &case.inputs, Error::G2NotInSubgroup),
+        (&off_curve, &case.inputs, Error::G1NotOnCurve),
+    ] {
+        let message: Vec<u8> = uzkv_stylus::Error::from(expected).into();
+        assert_eq!(verify_offchain(groth16, proof, inputs, &case.vk), Err(message));
+    }
+}
+
+#[test]
+fn test_registration_rejects_bad_vk() {
+    let case = valid_case();
+    let cases: [(std::ops::Range<usize>, Option<Vec<u8>>, Error); 5] = [
+        // alpha at infinity
+        (0..64, None, Error::IdentityPoint),
+        // IC_1 at infinity
+        (512..576, None, Error::IdentityPoint),
+        // delta at infinity
+        (320..448, None, Error::IdentityPoint),
+        // beta outside the subgroup
+        (64..192, Some(g2_outside_subgroup()), Error::G2NotInSubgroup),
+        // gamma off the twist
+        (192..320, Some(vec![1u8; 128]), Error::G2NotOnCurve),
+    ];
+
+    for (range, replacement, expected) in cases {
+        let mut vk = case.vk.clone();
+        match replacement {
+            Some(bytes) => vk[range].copy_from_slice(&bytes),
+            None => vk[range].fill(0),
+        }
+        assert_eq!(validate_vk(&vk), Err(expected));
+        assert_eq!(compute_precomputed_pairing(&vk), Err(expected));
+        assert_eq!(verify_host(&case.proof, &case.inputs, &vk), Err(expected));
+    }
+
+    let mut off_curve_ic = case.vk.clone();
+    off_curve_ic[448 + 63] ^= 1;
+    assert_eq!(compute_precomputed_pairing(&off_curve_ic), Err(Error::G1NotOnCurve));
+}
+
+#[test]
+fn test_subgroup_check_matches_arkworks() {
+    let mut rng = test_rng();
+    assert_eq!(curve::validate_g2(&codec::g2_from_ark(&G2Affine::generator())), Ok(false));
+    for _ in 0..4 {
+        let point = G2Affine::get_point_from_x_unchecked(Fq2::rand(&mut rng), true);
+        if let Some(point) = point {
+            let expected = if point.is_in_correct_subgroup_assuming_on_curve() {
+                Ok(false)
+            } else {
+                Err(Error::G2NotInSubgroup)
+            };
+            assert_eq!(curve::validate_g2(&codec::g2_from_ark(&point)), expected);
+        }
+        let in_group = (G2Affine::generator() * Fr::rand(&mut rng)).into();
+        assert_eq!(curve::validate_g2(&codec::g2_from_ark(&in_group)), Ok(false));
+    }
+}
@@ -0,0 +1,204 @@
+//! halo2 Tests
+//!
+//! The test prover in `common::halo2` writes a PSE-style transcript for a
+//! circuit with a second advice phase, a lookup and two permutation grand
+//! products. Proofs are verified through `halo2::verify` on `ArkBackend` (the
+//! Stylus path) and through `verify_offchain_with_hash`, with both the Blake2b
+//! and the Keccak256 transcripts. `tests/fixtures/halo2` holds proofs written
+//! by halo2's own `create_proof`.
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use ark_bn254::Fr;
+use ark_ff::UniformRand;
+use ark_std::test_rng;
+use common::halo2::{Halo2Fixture, NUM_POINTS, NUM_SCALARS};
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::backend::ArkBackend;
+use uzkv_stylus::halo2::transcript::{Blake2bHash, Keccak256Hash, POINT_SIZE, SCALAR_SIZE};
+use uzkv_stylus::halo2::vk::VerifyingKey;
+use uzkv_stylus::halo2::{self, DELTA};
+use uzkv_stylus::plonk::kzg::Error;
+use uzkv_stylus::types::{CurveId, HashFunctionId};
+use uzkv_stylus::uzkv::{verify_offchain_with_hash, ProofSystem};
+use uzkv_stylus::utils::fr_pow;
+
+fn fixture() -> Halo2Fixture {
+    Halo2Fixture::new(Fr::rand(&mut test_rng()))
+}
+
+fn fixture_file(name: &str) -> Vec<u8> {
+    let path = format!("{}/tests/fixtures/halo2/{}", env!("CARGO_MANIFEST_DIR"), name);
+    std::fs::read(&path).unwrap_or_else(|_| panic!("missing fixture {} (run `cargo run --release` in its directory)", path))
+}
+
+fn verify(hash: HashFunctionId, proof: &[u8], inputs: &[u8], vk: &[u8]) -> Result<bool, Error> {
+    halo2::verify(&ArkBackend, hash, proof, inputs, vk)
+}
+
+#[test]
+fn test_blake2b_proof_verifies() {
+    let fixture = fixture();
+    let witness = fixture.witness(Fr::from(3u64));
+    let proof = fixture.prove::<Blake2bHash>(&witness);
+    assert_eq!(proof.len(), NUM_POINTS * POINT_SIZE + NUM_SCALARS * SCALAR_SIZE + 2 * POINT_SIZE);
+
+    let inputs = fixture.public_bytes(&witness);
+    let vk = fixture.vk_bytes();
+    assert_eq!(verify(HashFunctionId::Blake2b, &proof, &inputs, &vk), Ok(true));
+    assert_eq!(
+        verify_offchain_with_hash(ProofSystem::Halo2 as u8, CurveId::BN254, HashFunctionId::Blake2b, &proof, &inputs, &vk),
+        Ok(true)
+    );
+}
+
+#[test]
+fn test_keccak_proof_verifies() {
+    let fixture = fixture();
+    let witness = fixture.witness(Fr::from(5u64));
+    let proof = fixture.prove::<Keccak256Hash>(&witness);
+    let inputs = fixture.public_bytes(&witness);
+    let vk = fixture.vk_bytes();
+    assert_eq!(verify(HashFunctionId::Keccak256, &proof, &inputs, &vk), Ok(true));
+    assert_eq!(
+        verify_offchain_with_hash(ProofSystem::Halo2 as u8, CurveId::BN254, HashFunctionId::Keccak256, &proof, &inputs, &vk),
+        Ok(true)
+    );
+}
+
+#[test]
+fn test_transcript_hash_must_match() {
+    let fixture = fixture();
+    let witness = fixture.witness(Fr::from(3u64));
+    let proof = fixture.prove::<Blake2bHash>(&witness);
+    let inputs = fixture.public_bytes(&witness);
+    let vk = fixture.vk_bytes();
+    assert_eq!(verify(HashFunctionId::Keccak256, &proof, &inputs, &vk), Ok(false));
+    assert_eq!(verify(HashFunctionId::Poseidon, &proof, &inputs, &vk), Err(Error::UnsupportedHash));
+    assert!(
+        verify_offchain_with_hash(ProofSystem::Halo2 as u8, CurveId::BN254, HashFunctionId::Poseidon, &proof, &inputs, &vk)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_wrong_instance_rejected() {
+    let fixture = fixture();
+    let witness = fixture.witness(Fr::from(3u64));
+    let proof = fixture.prove::<Blake2bHash>(&witness);
+    let mut other = witness.clone();
+    other.instance[0] += Fr::from(1u64);
+    assert_eq!(verify(HashFunctionId::Blake2b, &proof, &fixture.public_bytes(&other), &fixture.vk_bytes()), Ok(false));
+}
+
+#[test]
+fn test_tampered_evaluations_rejected() {
+    let fixture = fixture();
+    let witness = fixture.witness(Fr::from(3u64));
+    let proof = fixture.prove::<Blake2bHash>(&witness);
+    let inputs = fixture.public_bytes(&witness);
+    let vk = fixture.vk_bytes();
+
+    for i in 0..NUM_SCALARS {
+        let mut tampered = proof.clone();
+        tampered[NUM_POINTS * POINT_SIZE + i * SCALAR_SIZE] ^= 1;
+        assert_eq!(verify(HashFunctionId::Blake2b, &tampered, &inputs, &vk), Ok(false), "evaluation {i}");
+    }
+}
+
+#[test]
+fn test_unsatisfied_witness_rejected() {
+    let fixture = fixture();
+    let mut witness = fixture.witness(Fr::from(3u64));
+    witness.c[2] += Fr::from(1u64);
+    let proof = fixture.prove::<Blake2bHash>(&witness);
+    assert_eq!(
+        verify(HashFunctionId::Blake2b, &proof, &fixture.public_bytes(&witness), &fixture.vk_bytes()),
+        Ok(false)
+    );
+}
+
+#[test]
+fn test_proof_length_checked() {
+    let fixture = fixture();
+    let witness = fixture.witness(Fr::from(3u64));
+    let proof = fixture.prove::<Blake2bHash>(&witness);
+    let inputs = fixture.public_bytes(&witness);
+    let vk = fixture.vk_bytes();
+
+    assert_eq!(verify(HashFunctionId::Blake2b, &proof[..proof.len() - 1], &inputs, &vk), Err(Error::InvalidInputSize));
+    let mut long = proof.clone();
+    long.push(0);
+    assert_eq!(verify(HashFunctionId::Blake2b, &long, &inputs, &vk), Err(Error::InvalidInputSize));
+    assert_eq!(verify(HashFunctionId::Blake2b, &proof, &inputs[..31], &vk), Err(Error::InvalidInputSize));
+    assert_eq!(verify(HashFunctionId::Blake2b, &proof, &[inputs.clone(), inputs].concat(), &vk), Err(Error::InvalidInputSize));
+}
+
+#[test]
+fn test_vk_validation() {
+    let fixture = fixture();
+    let vk = fixture.vk_without_srs();
+    let parsed = VerifyingKey::parse(&vk).unwrap();
+    assert_eq!(parsed.n, 16);
+    assert_eq!(parsed.blinding_factors(), 5);
+    assert_eq!(parsed.permutation_sets(), 2);
+    assert_eq!(parsed.max_phase(), 1);
+    assert_eq!(parsed.gates.len(), 3);
+    assert_eq!(parsed.lookups.len(), 1);
+
+    // ω of order 8 instead of 16
+    let mut bad_omega = vk.clone();
+    let omega = fr_pow(parsed.omega, U256::from(2));
+    bad_omega[4..36].copy_from_slice(&omega.to_be_bytes::<32>());
+    assert_eq!(VerifyingKey::parse(&bad_omega), None);
+
+    // Truncated and extended keys
+    assert_eq!(VerifyingKey::parse(&vk[..vk.len() - 1]), None);
+    assert_eq!(VerifyingKey::parse(&[vk.clone(), vec![0]].concat()), None);
+
+    // Degree below 3
+    let mut bad_degree = vk.clone();
+    bad_degree[68..72].copy_from_slice(&2u32.to_be_bytes());
+    assert_eq!(VerifyingKey::parse(&bad_degree), None);
+
+    // The VK without [τ]₂ is not a halo2 key
+    let witness = fixture.witness(Fr::from(3u64));
+    let proof = fixture.prove::<Blake2bHash>(&witness);
+    assert_eq!(
+        verify(HashFunctionId::Blake2b, &proof, &fixture.public_bytes(&witness), &vk),
+        Err(Error::InvalidInputSize)
+    );
+}
+
+#[test]
+fn test_delta_is_seventh_power() {
+    // halo2curves: DELTA = GENERATOR^(2^S) with GENERATOR = 7, S = 28
+    assert_eq!(fr_pow(U256::from(7), U256::from(1u64 << 28)), DELTA);
+}
+
+#[test]
+fn test_halo2_fixture_verifies() {
+    let (vk, inputs) = (fixture_file("vk.bin"), fixture_file("instances.bin"));
+    let parsed = VerifyingKey::parse(&vk[..vk.len() - 128]).unwrap();
+    assert_eq!(parsed.n, 32);
+    assert_eq!(parsed.instance_lengths, vec![2]);
+    assert_eq!(parsed.max_phase(), 1);
+    assert_eq!(parsed.lookups.len(), 1);
+
+    for (name, hash) in [("proof_blake2b.bin", HashFunctionId::Blake2b), ("proof_keccak256.bin", HashFunctionId::Keccak256)] {
+        let proof = fixture_file(name);
+        assert_eq!(verify(hash, &proof, &inputs, &vk), Ok(true), "{name}");
+        assert_eq!(
+            verify_offchain_with_hash(ProofSystem::Halo2 as u8, CurveId::BN254, hash, &proof, &inputs, &vk),
+            Ok(true),
+            "{name}"
+        );
+
+        // instances are x and 32·x
+        let mut wrong = inputs.clone();
+        wrong[63] ^= 1;
+        assert_eq!(verify(hash, &proof, &wrong, &vk), Ok(false), "{name}");
+    }
+}
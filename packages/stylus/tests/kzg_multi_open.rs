@@ -0,0 +1,216 @@
+//! KZG Multi-Point Opening Tests
+//!
+//! Openings are built from the trapdoor: a polynomial is only ever needed at
+//! `τ` (for its commitment) and at the opening point, so random scalars stand
+//! in for `pᵢ(τ)` and `pᵢ(z)`. Checked on `ArkBackend` (the Stylus path).
+
+#![cfg(feature = "std")]
+
+use ark_bn254::{Fr, G1Affine, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, Field, One, PrimeField, UniformRand, Zero};
+use ark_std::{rand::RngCore, test_rng};
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::backend::ArkBackend;
+use uzkv_stylus::groth16::codec;
+use uzkv_stylus::plonk::kzg::{
+    verify_kzg_batch_opening_with_challenge, verify_kzg_multi_opening, Error, KzgAccumulator,
+    PointOpening,
+};
+
+fn to_u256(scalar: &Fr) -> U256 {
+    U256::from_be_slice(&scalar.into_bigint().to_bytes_be())
+}
+
+fn commit(scalar: Fr) -> [u8; 64] {
+    codec::g1_from_ark(&(G1Affine::generator() * scalar).into_affine())
+}
+
+/// Polynomials `(p(τ), p(z))` opened at `z`, with the proof for `gamma`
+struct Opening {
+    point: Fr,
+    commitments: Vec<[u8; 64]>,
+    evals: Vec<U256>,
+    proof: [u8; 64],
+}
+
+impl Opening {
+    fn new(tau: Fr, point: Fr, polys: &[(Fr, Fr)], gamma: Fr) -> Self {
+        // W(τ) = Σ γⁱ(pᵢ(τ) − pᵢ(z)) / (τ − z)
+        let mut folded = Fr::zero();
+        let mut power = Fr::one();
+        for (at_tau, at_point) in polys {
+            folded += power * (*at_tau - at_point);
+            power *= gamma;
+        }
+        Self {
+            point,
+            commitments: polys.iter().map(|(at_tau, _)| commit(*at_tau)).collect(),
+            evals: polys.iter().map(|(_, at_point)| to_u256(at_point)).collect(),
+            proof: commit(folded * (tau - point).inverse().unwrap()),
+        }
+    }
+
+    fn random<R: RngCore>(rng: &mut R, s: &Setup, count: usize) -> Self {
+        let polys: Vec<_> = (0..count).map(|_| (Fr::rand(rng), Fr::rand(rng))).collect();
+        Self::new(s.tau, Fr::rand(rng), &polys, s.gamma)
+    }
+}
+
+struct Setup {
+    tau: Fr,
+    gamma: Fr,
+    r: Fr,
+    srs_g2: [u8; 128],
+}
+
+fn setup<R: RngCore>(rng: &mut R) -> Setup {
+    let tau = Fr::rand(rng);
+    Setup {
+        tau,
+        gamma: Fr::rand(rng),
+        r: Fr::rand(rng),
+        srs_g2: codec::g2_from_ark(&(G2Affine::generator() * tau).into_affine()),
+    }
+}
+
+fn verify(setup: &Setup, openings: &[&Opening]) -> Result<bool, Error> {
+    let commitments: Vec<Vec<&[u8]>> =
+        openings.iter().map(|o| o.commitments.iter().map(|c| &c[..]).collect()).collect();
+    let openings: Vec<PointOpening> = openings
+        .iter()
+        .zip(&commitments)
+        .map(|(o, commitments)| PointOpening {
+            point: to_u256(&o.point),
+            commitments,
+            evals: &o.evals,
+            proof: &o.proof,
+        })
+        .collect();
+    verify_kzg_multi_opening(&ArkBackend, &openings, to_u256(&setup.gamma), to_u256(&setup.r), &setup.srs_g2)
+}
+
+#[test]
+fn test_multi_point_opening() {
+    let rng = &mut test_rng();
+    let s = setup(rng);
+    let first = Opening::random(rng, &s, 3);
+    let second = Opening::random(rng, &s, 2);
+
+    assert_eq!(verify(&s, &[&first, &second]), Ok(true));
+    assert_eq!(verify(&s, &[&first]), Ok(true));
+
+    // Any evaluation off by one fails the whole check
+    for index in 0..2 {
+        let mut evals = second.evals.clone();
+        evals[index] = evals[index].wrapping_add(U256::from(1));
+        let bad = Opening { evals, commitments: second.commitments.clone(), ..second };
+        assert_eq!(verify(&s, &[&first, &bad]), Ok(false));
+    }
+
+    // Proofs swapped between the points
+    let swap = |o: &Opening, proof| Opening { commitments: o.commitments.clone(), evals: o.evals.clone(), proof, ..*o };
+    let (swapped_first, swapped_second) = (swap(&first, second.proof), swap(&second, first.proof));
+    assert_eq!(verify(&s, &[&swapped_first, &swapped_second]), Ok(false));
+}
+
+#[test]
+fn test_matches_single_point_batch() {
+    let rng = &mut test_rng();
+    let s = setup(rng);
+    let opening = Opening::random(rng, &s, 4);
+    let commitments: Vec<&[u8]> = opening.commitments.iter().map(|c| &c[..]).collect();
+
+    let batch = |evals: &[U256]| {
+        verify_kzg_batch_opening_with_challenge(
+            &ArkBackend,
+            &commitments,
+            to_u256(&opening.point),
+            evals,
+            &opening.proof,
+            &s.srs_g2,
+            to_u256(&s.gamma),
+        )
+    };
+    assert_eq!(batch(&opening.evals), Ok(true));
+    assert_eq!(verify(&s, &[&opening]), Ok(true));
+
+    // Fewer evaluations than commitments is an error, not a panic
+    assert_eq!(batch(&opening.evals[..3]), Err(Error::InvalidInputSize));
+    assert_eq!(batch(&[]), Err(Error::InvalidInputSize));
+}
+
+#[test]
+fn test_identity_points() {
+    // The zero polynomial commits to the identity; opening it at z yields 0
+    // and its part of the proof is the identity too
+    let rng = &mut test_rng();
+    let s = setup(rng);
+    let z = Fr::from(9u64);
+    let zero = Opening::new(s.tau, z, &[(Fr::zero(), Fr::zero())], s.gamma);
+    assert_eq!(zero.commitments[0], [0u8; 64]);
+    assert_eq!(zero.proof, [0u8; 64]);
+    assert_eq!(verify(&s, &[&zero]), Ok(true));
+
+    // Claiming a non-zero value for it fails
+    let mut bad = Opening::new(s.tau, z, &[(Fr::zero(), Fr::zero())], s.gamma);
+    bad.evals[0] = U256::from(1);
+    assert_eq!(verify(&s, &[&bad]), Ok(false));
+
+    // Mixed with an ordinary polynomial at the same point
+    let p = Fr::from(5u64);
+    let mixed = Opening::new(s.tau, z, &[(Fr::zero(), Fr::zero()), (p, p * z)], s.gamma);
+    assert_eq!(verify(&s, &[&mixed, &Opening::random(rng, &s, 1)]), Ok(true));
+}
+
+#[test]
+fn test_malformed_openings() {
+    let rng = &mut test_rng();
+    let s = setup(rng);
+    let opening = Opening::random(rng, &s, 2);
+
+    assert_eq!(verify(&s, &[]), Err(Error::InvalidInputSize));
+    let short = Opening { evals: opening.evals[..1].to_vec(), ..Opening::random(rng, &s, 2) };
+    assert_eq!(verify(&s, &[&opening, &short]), Err(Error::InvalidInputSize));
+    let long = Opening {
+        evals: [opening.evals.clone(), vec![U256::ZERO]].concat(),
+        ..Opening::random(rng, &s, 2)
+    };
+    assert_eq!(verify(&s, &[&long]), Err(Error::InvalidInputSize));
+    let empty = Opening { commitments: vec![], evals: vec![], ..Opening::random(rng, &s, 1) };
+    assert_eq!(verify(&s, &[&empty]), Err(Error::InvalidInputSize));
+
+    let mut acc = KzgAccumulator::new();
+    assert_eq!(acc.check(&ArkBackend, &s.srs_g2), Err(Error::InvalidInputSize));
+    assert_eq!(acc.add_term(&opening.proof[..63], U256::from(1)), Err(Error::InvalidInputSize));
+    acc.add_proof(&opening.proof, U256::from(1)).unwrap();
+    assert_eq!(acc.check(&ArkBackend, &s.srs_g2[..127]), Err(Error::InvalidInputSize));
+}
+
+#[test]
+fn test_accumulator_merges_shared_points() {
+    // Two openings of the same commitment at different points, accumulated by
+    // hand with independent weights
+    let rng = &mut test_rng();
+    let s = setup(rng);
+    // p(X) = 7X + 4
+    let p = |x: Fr| Fr::from(7u64) * x + Fr::from(4u64);
+    let (z1, z2) = (Fr::from(2u64), Fr::from(3u64));
+    let first = Opening::new(s.tau, z1, &[(p(s.tau), p(z1))], s.gamma);
+    let second = Opening::new(s.tau, z2, &[(p(s.tau), p(z2))], s.gamma);
+    assert_eq!(first.commitments, second.commitments);
+
+    let commitments: [&[u8]; 1] = [&first.commitments[0]];
+    let mut acc = KzgAccumulator::new();
+    for (opening, weight) in [(&first, Fr::one()), (&second, s.r)] {
+        let point = PointOpening {
+            point: to_u256(&opening.point),
+            commitments: &commitments,
+            evals: &opening.evals,
+            proof: &opening.proof,
+        };
+        acc.add_opening(&point, to_u256(&s.gamma), to_u256(&weight)).unwrap();
+    }
+    assert_eq!(acc.check(&ArkBackend, &s.srs_g2), Ok(true));
+    assert_eq!(verify(&s, &[&first, &second]), Ok(true));
+}
@@ -0,0 +1,142 @@
+//! PLONK Batch Verification Tests
+//!
+//! Batches of proofs from the `common::plonk` prover (`x³ + x + 5 = y`) under
+//! one VK, checked with the aggregate two-pairing equation on `ArkBackend` (the
+//! Stylus path) and compared with verifying each proof on its own.
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use ark_bn254::Fr;
+use ark_ff::UniformRand;
+use ark_std::test_rng;
+use common::plonk::PlonkFixture;
+use uzkv_stylus::backend::ArkBackend;
+use uzkv_stylus::hash::Sha256Hasher;
+use uzkv_stylus::plonk::kzg::Error;
+use uzkv_stylus::plonk::plonk::{
+    self, batch_weights, compute_challenges_with, serialize_plonk_proof, verify_batch_with_hash,
+};
+use uzkv_stylus::types::HashFunctionId;
+
+fn fixture() -> PlonkFixture {
+    PlonkFixture::cubic(Fr::rand(&mut test_rng()))
+}
+
+fn encode_batch(fixture: &PlonkFixture, count: u64) -> (Vec<Vec<u8>>, Vec<Vec<u8>>) {
+    (1..=count)
+        .map(|x| {
+            let witness = fixture.witness(Fr::from(x));
+            (fixture.proof_bytes(&witness), fixture.public_bytes(&witness))
+        })
+        .unzip()
+}
+
+fn aggregate(proofs: &[Vec<u8>], inputs: &[Vec<u8>], vk: &[u8]) -> Result<bool, Error> {
+    verify_batch_with_hash(&ArkBackend, HashFunctionId::Keccak256, proofs, inputs, vk)
+}
+
+#[test]
+fn test_batch_all_valid() {
+    let fixture = fixture();
+    let vk = fixture.vk_bytes();
+    let (proofs, inputs) = encode_batch(&fixture, 4);
+
+    assert_eq!(aggregate(&proofs, &inputs, &vk), Ok(true));
+    assert_eq!(plonk::batch_verify(&ArkBackend, &proofs, &inputs, &vk), Ok(vec![true; 4]));
+
+    // A batch of one is a single verification
+    assert_eq!(aggregate(&proofs[..1], &inputs[..1], &vk), Ok(true));
+    assert_eq!(plonk::batch_verify(&ArkBackend, &proofs[..1], &inputs[..1], &vk), Ok(vec![true]));
+}
+
+#[test]
+fn test_batch_reports_each_failure() {
+    let fixture = fixture();
+    let vk = fixture.vk_bytes();
+    let (mut proofs, mut inputs) = encode_batch(&fixture, 4);
+
+    // Wrong input on proof 1, truncated proof 3
+    inputs[1][31] ^= 1;
+    proofs[3].truncate(100);
+
+    assert_ne!(aggregate(&proofs, &inputs, &vk), Ok(true));
+    assert_eq!(
+        plonk::batch_verify(&ArkBackend, &proofs, &inputs, &vk),
+        Ok(vec![true, false, true, false])
+    );
+}
+
+#[test]
+fn test_batch_rejects_swapped_openings() {
+    // [W_ζ] swapped between two proofs: every point still appears in the sum,
+    // but under the other proof's weight
+    let fixture = fixture();
+    let vk = fixture.vk_bytes();
+    let (_, inputs) = encode_batch(&fixture, 2);
+    let mut first = fixture.prove(&fixture.witness(Fr::from(1u64)));
+    let mut second = fixture.prove(&fixture.witness(Fr::from(2u64)));
+    std::mem::swap(&mut first.opening_proof_zeta, &mut second.opening_proof_zeta);
+    let proofs = vec![serialize_plonk_proof(&first), serialize_plonk_proof(&second)];
+
+    assert_eq!(aggregate(&proofs, &inputs, &vk), Ok(false));
+    assert_eq!(plonk::batch_verify(&ArkBackend, &proofs, &inputs, &vk), Ok(vec![false, false]));
+}
+
+#[test]
+fn test_batch_weights_bind_every_proof() {
+    let fixture = fixture();
+    let vk = fixture.vk_bytes();
+    let (mut proofs, inputs) = encode_batch(&fixture, 3);
+
+    let weights = batch_weights(&vk, &proofs, &inputs);
+    assert_eq!(weights.len(), 3);
+    assert!(weights[0] != weights[1] && weights[1] != weights[2]);
+
+    // Changing the last proof changes the first weight
+    proofs[2][0] ^= 1;
+    assert_ne!(batch_weights(&vk, &proofs, &inputs)[0], weights[0]);
+}
+
+#[test]
+fn test_batch_with_sha256_transcript() {
+    let fixture = fixture();
+    let vk = fixture.vk_bytes();
+    let (proofs, inputs): (Vec<_>, Vec<_>) = (2..4u64)
+        .map(|x| {
+            let witness = fixture.witness(Fr::from(x));
+            let proof = fixture.prove_with(&witness, compute_challenges_with::<Sha256Hasher>);
+            (serialize_plonk_proof(&proof), fixture.public_bytes(&witness))
+        })
+        .unzip();
+
+    assert_eq!(
+        verify_batch_with_hash(&ArkBackend, HashFunctionId::SHA256, &proofs, &inputs, &vk),
+        Ok(true)
+    );
+    assert_eq!(
+        plonk::batch_verify_with_hash(&ArkBackend, HashFunctionId::SHA256, &proofs, &inputs, &vk),
+        Ok(vec![true, true])
+    );
+    assert_eq!(plonk::batch_verify(&ArkBackend, &proofs, &inputs, &vk), Ok(vec![false, false]));
+    assert_eq!(
+        plonk::batch_verify_with_hash(&ArkBackend, HashFunctionId::Blake2b, &proofs, &inputs, &vk),
+        Err(Error::UnsupportedHash)
+    );
+}
+
+#[test]
+fn test_batch_malformed_arguments() {
+    let fixture = fixture();
+    let vk = fixture.vk_bytes();
+    let (proofs, inputs) = encode_batch(&fixture, 2);
+
+    assert_eq!(plonk::batch_verify(&ArkBackend, &proofs, &inputs[..1], &vk), Err(Error::InvalidInputSize));
+    assert_eq!(
+        plonk::batch_verify(&ArkBackend, &proofs, &inputs, &vk[..vk.len() - 1]),
+        Err(Error::InvalidInputSize)
+    );
+    assert_eq!(aggregate(&[], &[], &vk), Err(Error::InvalidInputSize));
+    assert_eq!(plonk::batch_verify(&ArkBackend, &[], &[], &vk), Ok(vec![]));
+}
@@ -0,0 +1,133 @@
+//! PLONK Transcript Hash Tests
+//!
+//! Proves the test circuit with each transcript hash `crate::hash` implements
+//! and checks that the verifier follows the hash it is given: the matching hash
+//! accepts on the generic, host and `verify_offchain_with_hash` paths, any
+//! other hash rejects, and hashes without an implementation are errors.
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use ark_bn254::Fr;
+use common::plonk::{fr_to_u256, ChallengeFn, PlonkFixture, Witness};
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::backend::ArkBackend;
+use uzkv_stylus::hash::{Blake3Hasher, Keccak256Hasher, PoseidonHasher, Sha256Hasher};
+use uzkv_stylus::plonk::kzg::Error;
+use uzkv_stylus::plonk::plonk::{self, compute_challenges, compute_challenges_with, serialize_plonk_proof};
+use uzkv_stylus::plonk::{verify_host_with_hash, verify_with_hash};
+use uzkv_stylus::security::{DispatchValidator, RegisteredVK, SecurityError};
+use uzkv_stylus::types::{CurveId, HashFunctionId, ProofType, UniversalProofDescriptor};
+use uzkv_stylus::uzkv::{verify_offchain, verify_offchain_with_hash, ProofSystem};
+
+const HASHES: [(HashFunctionId, ChallengeFn); 4] = [
+    (HashFunctionId::Keccak256, compute_challenges_with::<Keccak256Hasher>),
+    (HashFunctionId::SHA256, compute_challenges_with::<Sha256Hasher>),
+    (HashFunctionId::Blake3, compute_challenges_with::<Blake3Hasher>),
+    (HashFunctionId::Poseidon, compute_challenges_with::<PoseidonHasher>),
+];
+
+/// Deterministic fixture: fixed τ, x = 3
+fn fixed() -> (PlonkFixture, Witness) {
+    let fixture = PlonkFixture::cubic(Fr::from(0x5eed_u64));
+    let witness = fixture.witness(Fr::from(3u64));
+    (fixture, witness)
+}
+
+fn verify_all(hash: HashFunctionId, proof: &[u8], inputs: &[u8], vk: &[u8]) -> bool {
+    let stylus = verify_with_hash(&ArkBackend, hash, proof, inputs, vk).unwrap();
+    let host = verify_host_with_hash(hash, proof, inputs, vk).unwrap();
+    let offchain =
+        verify_offchain_with_hash(ProofSystem::Plonk as u8, CurveId::BN254, hash, proof, inputs, vk).unwrap();
+    assert_eq!(stylus, host, "{:?}: host and Stylus verifiers disagree", hash);
+    assert_eq!(stylus, offchain, "{:?}: offchain dispatch disagrees", hash);
+    stylus
+}
+
+#[test]
+fn test_proofs_verify_with_their_hash_only() {
+    let (fixture, witness) = fixed();
+    let inputs = fixture.public_bytes(&witness);
+    let vk = fixture.vk_bytes();
+
+    for (hash, transcript) in HASHES {
+        let proof = serialize_plonk_proof(&fixture.prove_with(&witness, transcript));
+        for (other, _) in HASHES {
+            assert_eq!(verify_all(other, &proof, &inputs, &vk), other == hash, "{:?} proof, {:?} verifier", hash, other);
+        }
+    }
+}
+
+#[test]
+fn test_keccak_is_the_default() {
+    let (fixture, witness) = fixed();
+    let inputs = fixture.public_bytes(&witness);
+    let vk = fixture.vk_bytes();
+    let proof = fixture.proof_bytes(&witness);
+
+    assert_eq!(fixture.prove_with(&witness, compute_challenges_with::<Keccak256Hasher>), fixture.prove(&witness));
+    assert_eq!(plonk::verify(&ArkBackend, &proof, &inputs, &vk), Ok(true));
+    assert_eq!(verify_offchain(ProofSystem::Plonk as u8, &proof, &inputs, &vk), Ok(true));
+
+    let poseidon = serialize_plonk_proof(&fixture.prove_with(&witness, compute_challenges_with::<PoseidonHasher>));
+    assert_eq!(plonk::verify(&ArkBackend, &poseidon, &inputs, &vk), Ok(false));
+    assert_eq!(verify_offchain(ProofSystem::Plonk as u8, &poseidon, &inputs, &vk), Ok(false));
+}
+
+#[test]
+fn test_challenges_differ_per_hash() {
+    let (fixture, witness) = fixed();
+    let proof = fixture.prove(&witness);
+    let vk = fixture.verifying_key();
+    let inputs: Vec<U256> = witness.public.iter().map(fr_to_u256).collect();
+
+    let betas: Vec<U256> = HASHES.iter().map(|(_, transcript)| transcript(&proof, &vk, &inputs).beta).collect();
+    assert_eq!(betas[0], compute_challenges(&proof, &vk, &inputs).beta);
+    for i in 0..betas.len() {
+        for j in i + 1..betas.len() {
+            assert_ne!(betas[i], betas[j]);
+        }
+    }
+
+    let by_id = plonk::compute_challenges_for(HashFunctionId::Poseidon, &proof, &vk, &inputs).unwrap();
+    assert_eq!(by_id, compute_challenges_with::<PoseidonHasher>(&proof, &vk, &inputs));
+}
+
+#[test]
+fn test_unsupported_hashes_are_rejected() {
+    let (fixture, witness) = fixed();
+    let inputs = fixture.public_bytes(&witness);
+    let vk = fixture.vk_bytes();
+    let proof = fixture.proof_bytes(&witness);
+
+    let rescue = HashFunctionId::RescuePrime;
+    assert_eq!(verify_with_hash(&ArkBackend, rescue, &proof, &inputs, &vk), Err(Error::UnsupportedHash));
+    assert!(verify_host_with_hash(rescue, &proof, &inputs, &vk).is_err());
+    assert!(verify_offchain_with_hash(ProofSystem::Plonk as u8, CurveId::BN254, rescue, &proof, &inputs, &vk).is_err());
+
+    // snarkjs only hashes with Keccak256
+    let system = ProofSystem::PlonkSnarkjs as u8;
+    let poseidon = HashFunctionId::Poseidon;
+    assert!(verify_offchain_with_hash(system, CurveId::BN254, poseidon, &proof, &inputs, &vk).is_err());
+}
+
+#[test]
+fn test_descriptor_hash_must_match_vk() {
+    let vk_hash = [7u8; 32];
+    let validator = DispatchValidator::new();
+    let registered = RegisteredVK::new(ProofType::PLONK, vk_hash, vk_hash, CurveId::BN254, 1)
+        .with_hash_function(HashFunctionId::Poseidon);
+
+    let mut descriptor = UniversalProofDescriptor::plonk(1, vk_hash, vk_hash);
+    assert_eq!(
+        validator.validate_hash_function(&descriptor, &registered),
+        Err(SecurityError::HashFunctionMismatch {
+            descriptor_hash: HashFunctionId::Keccak256,
+            verifier_hash: HashFunctionId::Poseidon,
+        })
+    );
+
+    descriptor.hash_function_id = HashFunctionId::Poseidon;
+    assert_eq!(validator.validate_hash_function(&descriptor, &registered), Ok(()));
+}
@@ -0,0 +1,89 @@
+//! PLONK SRS Binding Tests
+//!
+//! On chain a PLONK VK is registered without `[τ]₂` and bound to a registered
+//! SRS; verification appends the SRS `[τ]₂`. Checks that the VK plus the SRS
+//! point verifies, that a forged `[τ]₂` does not, and the registration checks
+//! (`[τ]₂` in G2, domain within `max_degree`).
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use ark_bn254::Fr;
+use common::plonk::{PlonkFixture, Witness};
+use uzkv_stylus::backend::ArkBackend;
+use uzkv_stylus::groth16::curve::validate_g2;
+use uzkv_stylus::plonk::plonk::{self, deserialize_plonk_vk, SRS_G2_SIZE, VK_SIZE};
+use uzkv_stylus::plonk::srs::{self, Srs, G1_SIZE};
+
+/// Deterministic fixture: fixed τ, x = 3
+fn fixed() -> (PlonkFixture, Witness) {
+    let fixture = PlonkFixture::cubic(Fr::from(0x5eed_u64));
+    let witness = fixture.witness(Fr::from(3u64));
+    (fixture, witness)
+}
+
+fn generator() -> [u8; G1_SIZE] {
+    let mut point = [0u8; G1_SIZE];
+    point[31] = 1;
+    point[63] = 2;
+    point
+}
+
+/// Split the fixture VK into the on-chain VK and an SRS carrying its `[τ]₂`
+fn split(fixture: &PlonkFixture, max_degree: u8) -> (Vec<u8>, Vec<u8>) {
+    let vk = fixture.vk_bytes();
+    let g2: [u8; SRS_G2_SIZE] = vk[VK_SIZE..].try_into().unwrap();
+    (vk[..VK_SIZE].to_vec(), srs::encode(max_degree, &g2, &[generator()]))
+}
+
+#[test]
+fn test_registry_g2_completes_vk() {
+    let (fixture, witness) = fixed();
+    let inputs = fixture.public_bytes(&witness);
+    let proof = fixture.proof_bytes(&witness);
+    let (vk, srs_bytes) = split(&fixture, 10);
+
+    let srs = Srs::parse(&srs_bytes).unwrap();
+    assert_eq!(validate_g2(srs.g2_tau), Ok(false));
+
+    let bound = [&vk[..], srs.g2_tau].concat();
+    assert_eq!(bound, fixture.vk_bytes());
+    assert_eq!(plonk::verify(&ArkBackend, &proof, &inputs, &bound), Ok(true));
+
+    // The VK alone lacks [τ]₂
+    assert!(plonk::verify(&ArkBackend, &proof, &inputs, &vk).is_err());
+}
+
+#[test]
+fn test_forged_g2_is_rejected() {
+    let (fixture, witness) = fixed();
+    let inputs = fixture.public_bytes(&witness);
+    let proof = fixture.proof_bytes(&witness);
+    let (vk, _) = split(&fixture, 10);
+
+    // [τ']₂ from another ceremony
+    let other = PlonkFixture::cubic(Fr::from(0xbad_u64)).vk_bytes();
+    let forged = [&vk[..], &other[VK_SIZE..]].concat();
+    assert_eq!(plonk::verify(&ArkBackend, &proof, &inputs, &forged), Ok(false));
+
+    // Not a G2 point: register_srs refuses it
+    let mut junk = [0u8; SRS_G2_SIZE];
+    junk[127] = 1;
+    let srs_bytes = srs::encode(10, &junk, &[generator()]);
+    assert!(validate_g2(Srs::parse(&srs_bytes).unwrap().g2_tau).is_err());
+}
+
+#[test]
+fn test_domain_must_fit_srs() {
+    let (fixture, _) = fixed();
+    let (vk, _) = split(&fixture, 10);
+    let n = deserialize_plonk_vk(&vk).unwrap().n;
+    let log_n = n.trailing_zeros() as u8;
+
+    let (_, exact) = split(&fixture, log_n);
+    assert!(Srs::parse(&exact).unwrap().supports_domain(n));
+
+    let (_, small) = split(&fixture, log_n - 1);
+    assert!(!Srs::parse(&small).unwrap().supports_domain(n));
+}
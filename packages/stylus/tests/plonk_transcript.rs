@@ -0,0 +1,144 @@
+//! PLONK Transcript Conformance Tests
+//!
+//! Pins the challenges derived for a fixed proof, so any change to absorption
+//! order, labels or encoding shows up here, and checks that the host verifier
+//! (`verify_offchain`, `uzkv-cli`) and the generic Stylus verifier agree.
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use ark_bn254::Fr;
+use common::plonk::{fr_to_u256, ChallengeFn, PlonkFixture, Witness};
+use sha3::{Digest, Keccak256};
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::backend::ArkBackend;
+use uzkv_stylus::plonk::plonk::{self, compute_challenges, serialize_plonk_proof, Challenges, PlonkProof};
+use uzkv_stylus::plonk::snarkjs;
+use uzkv_stylus::plonk::transcript::{labels, Transcript};
+use uzkv_stylus::plonk::{verify_host, verify_snarkjs_host};
+use uzkv_stylus::uzkv::{verify_offchain, ProofSystem};
+use uzkv_stylus::utils::BN254_SCALAR_MODULUS;
+
+/// Deterministic fixture: fixed τ, x = 3 (the prover does not blind)
+fn fixed() -> (PlonkFixture, Witness) {
+    let fixture = PlonkFixture::cubic(Fr::from(0x5eed_u64));
+    let witness = fixture.witness(Fr::from(3u64));
+    (fixture, witness)
+}
+
+fn hex(value: &str) -> U256 {
+    U256::from_str_radix(value, 16).unwrap()
+}
+
+fn challenges_of(fixture: &PlonkFixture, witness: &Witness, transcript: ChallengeFn) -> Challenges {
+    let proof = fixture.prove_with(witness, transcript);
+    let inputs: Vec<U256> = witness.public.iter().map(fr_to_u256).collect();
+    transcript(&proof, &fixture.verifying_key(), &inputs)
+}
+
+#[test]
+fn test_transcript_encoding() {
+    let mut point = [0u8; 64];
+    point[31] = 1;
+    point[63] = 2;
+
+    let mut transcript = Transcript::new(labels::PLONK_PROTOCOL);
+    transcript.absorb_bytes(labels::VK_DOMAIN, &8u64.to_be_bytes());
+    transcript.absorb_field(labels::PUBLIC_INPUT, &U256::from(35));
+    transcript.absorb_point_bytes(labels::WIRE_COMMITMENT, &point);
+    let beta = transcript.squeeze_challenge(labels::BETA_CHALLENGE);
+    let gamma = transcript.squeeze_challenge(labels::GAMMA_CHALLENGE);
+
+    // Labels precede every item; raw bytes carry a u64 little-endian length;
+    // each squeeze hashes the running state plus its label and feeds the hash back
+    let mut state = Vec::new();
+    state.extend_from_slice(labels::PLONK_PROTOCOL);
+    state.extend_from_slice(labels::VK_DOMAIN);
+    state.extend_from_slice(&8u64.to_le_bytes());
+    state.extend_from_slice(&8u64.to_be_bytes());
+    state.extend_from_slice(labels::PUBLIC_INPUT);
+    state.extend_from_slice(&U256::from(35).to_be_bytes::<32>());
+    state.extend_from_slice(labels::WIRE_COMMITMENT);
+    state.extend_from_slice(&point);
+
+    let beta_hash = Keccak256::digest([&state[..], labels::BETA_CHALLENGE].concat());
+    state.extend_from_slice(&beta_hash);
+    let gamma_hash = Keccak256::digest([&state[..], labels::GAMMA_CHALLENGE].concat());
+
+    assert_eq!(beta, U256::from_be_slice(&beta_hash).reduce_mod(BN254_SCALAR_MODULUS));
+    assert_eq!(gamma, U256::from_be_slice(&gamma_hash).reduce_mod(BN254_SCALAR_MODULUS));
+    assert_eq!(beta, hex("2be78dd7b08c772b2fdcea263812df8b3ff8726ee87612cf7945ca5ad85ae6cd"));
+    assert_eq!(gamma, hex("7d9a04183ce8f9d186c7f11086b9615e8ca48b796001adb6580a54c295a307e"));
+}
+
+#[test]
+fn test_plonk_challenge_vectors() {
+    let (fixture, witness) = fixed();
+    let challenges = challenges_of(&fixture, &witness, compute_challenges);
+
+    assert_eq!(
+        challenges,
+        Challenges {
+            beta: hex("14a72db5b710c6a55c714fce6b4ad8773dbdc8620cdad89a4fd5a8e0dff9fe96"),
+            gamma: hex("168082875bc4248f77cd141b8c497740d645f4ca15de1738ba973c6a0dba7999"),
+            alpha: hex("1cfed61115a59672ef8c45f7bc022f6e7b6f8479f05eec3c3edfb241f087736d"),
+            zeta: hex("19866162067097867b10595fcbae18310db31880ff696033dd772796d20724ca"),
+            v: hex("24569b63d3f632f8133945717f85aec7592166c1efaf48d242e0b405ce76e3ef"),
+            u: hex("199332b9cb693eb73b055ea13815052f0f9591c96f80775a9b8f7581c3df3902"),
+        }
+    );
+}
+
+#[test]
+fn test_snarkjs_challenge_vectors() {
+    let (fixture, witness) = fixed();
+    let challenges = challenges_of(&fixture, &witness, snarkjs::compute_challenges);
+
+    assert_eq!(
+        challenges,
+        Challenges {
+            beta: hex("73450b3d3248002b012abe8237766ece0ff71d7d45be18aac724989aa67144d"),
+            gamma: hex("4319b4b456c31403b2f4eaf658abece32eee136c0559357b1807ebc7c7d84dc"),
+            alpha: hex("c6a71d1fd0e64d7bc9863a5258df9c6e1aae3b96b73b9d6d980a6250fe5f262"),
+            zeta: hex("2fbd148a00d87ca06cc83c1b505941adc30703f0bd9f890838f186f51e7d8d0a"),
+            v: hex("412ef4de661a17700f0e9e2de0691186059a846b0c2933c061744fb60833e22"),
+            u: hex("1dca67f47f623539fb58bee3e870e45c1df665fb1ee6563a165db8f2792f8e6c"),
+        }
+    );
+}
+
+#[test]
+fn test_host_matches_stylus_verifier() {
+    let (fixture, witness) = fixed();
+    let inputs = fixture.public_bytes(&witness);
+    let vk = fixture.vk_bytes();
+    let other_inputs = fr_to_u256(&(witness.public[0] + Fr::from(1u64))).to_be_bytes::<32>();
+
+    let cases: [(ProofSystem, ChallengeFn); 2] =
+        [(ProofSystem::Plonk, compute_challenges), (ProofSystem::PlonkSnarkjs, snarkjs::compute_challenges)];
+    for (system, transcript) in cases {
+        let proof = fixture.prove_with(&witness, transcript);
+        let mut tampered = proof.clone();
+        tampered.wire_evals[0] = tampered.wire_evals[0].add_mod(U256::from(1), BN254_SCALAR_MODULUS);
+
+        let runs: [(&PlonkProof, &[u8], bool); 3] =
+            [(&proof, &inputs, true), (&proof, &other_inputs, false), (&tampered, &inputs, false)];
+        for (proof, inputs, expected) in runs {
+            let bytes = serialize_plonk_proof(proof);
+            let (stylus, host) = match system {
+                ProofSystem::Plonk => (
+                    plonk::verify(&ArkBackend, &bytes, inputs, &vk),
+                    verify_host(&bytes, inputs, &vk),
+                ),
+                _ => (
+                    snarkjs::verify(&ArkBackend, &bytes, inputs, &vk),
+                    verify_snarkjs_host(&bytes, inputs, &vk),
+                ),
+            };
+            assert_eq!(stylus, Ok(expected));
+            assert_eq!(host, Ok(expected));
+            assert_eq!(verify_offchain(system as u8, &bytes, inputs, &vk), Ok(expected));
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! UltraPLONK Tests
+//!
+//! The test prover in `common::ultra` proves `y = x² + x` with `x` looked up in
+//! a `(i, i²)` table, next to a boolean gate and a next-row gate declared in
+//! the VK. Proofs are verified through `ultra::verify_with_hash` on
+//! `ArkBackend` (the Stylus path) and through `verify_offchain_with_hash`.
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use ark_bn254::Fr;
+use ark_ff::{One, UniformRand};
+use ark_std::test_rng;
+use common::ultra::{UltraFixture, NUM_SELECTORS, NUM_WIRES, QUOTIENT_PIECES, TABLE_WIDTH};
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::backend::ArkBackend;
+use uzkv_stylus::hash::{Keccak256Hasher, Sha256Hasher};
+use uzkv_stylus::plonk::kzg::Error;
+use uzkv_stylus::plonk::ultra::{
+    self, deserialize_ultra_proof, deserialize_ultra_vk, serialize_ultra_proof, serialize_ultra_vk,
+};
+use uzkv_stylus::types::{CurveId, HashFunctionId};
+use uzkv_stylus::uzkv::{verify_offchain_with_hash, ProofSystem};
+use uzkv_stylus::utils::fr_pow;
+
+fn fixture() -> UltraFixture {
+    UltraFixture::new(Fr::rand(&mut test_rng()))
+}
+
+fn verify(proof: &[u8], inputs: &[u8], vk: &[u8]) -> Result<bool, Error> {
+    ultra::verify(&ArkBackend, proof, inputs, vk)
+}
+
+#[test]
+fn test_honest_proof_verifies() {
+    let fixture = fixture();
+    let witness = fixture.witness(Fr::from(7u64));
+    let proof = fixture.proof_bytes::<Keccak256Hasher>(&witness);
+    let inputs = fixture.public_bytes(&witness);
+    let vk = fixture.vk_bytes();
+
+    let points = NUM_WIRES + 4 + QUOTIENT_PIECES + 2;
+    let scalars = 3 * NUM_WIRES + NUM_SELECTORS + 2 * TABLE_WIDTH + 8;
+    assert_eq!(proof.len(), points * 64 + scalars * 32);
+    assert_eq!(verify(&proof, &inputs, &vk), Ok(true));
+    assert_eq!(
+        verify_offchain_with_hash(ProofSystem::UltraPlonk as u8, CurveId::BN254, HashFunctionId::Keccak256, &proof, &inputs, &vk),
+        Ok(true)
+    );
+}
+
+#[test]
+fn test_sha256_transcript() {
+    let fixture = fixture();
+    let witness = fixture.witness(Fr::from(2u64));
+    let proof = fixture.proof_bytes::<Sha256Hasher>(&witness);
+    let inputs = fixture.public_bytes(&witness);
+    let vk = fixture.vk_bytes();
+
+    assert_eq!(ultra::verify_with_hash(&ArkBackend, HashFunctionId::SHA256, &proof, &inputs, &vk), Ok(true));
+    assert_eq!(
+        verify_offchain_with_hash(ProofSystem::UltraPlonk as u8, CurveId::BN254, HashFunctionId::SHA256, &proof, &inputs, &vk),
+        Ok(true)
+    );
+    assert_eq!(verify(&proof, &inputs, &vk), Ok(false));
+    assert_eq!(
+        ultra::verify_with_hash(&ArkBackend, HashFunctionId::Blake2b, &proof, &inputs, &vk),
+        Err(Error::UnsupportedHash)
+    );
+}
+
+#[test]
+fn test_serialization_round_trip() {
+    let fixture = fixture();
+    let vk = fixture.verifying_key();
+    let vk_bytes = serialize_ultra_vk(&vk);
+    assert_eq!(vk_bytes, fixture.vk_without_srs());
+    let parsed = deserialize_ultra_vk(&vk_bytes).unwrap();
+    assert_eq!(parsed, vk);
+    assert_eq!(parsed.quotient_pieces(), QUOTIENT_PIECES);
+    assert_eq!(parsed.gates.len(), 3);
+
+    let proof = fixture.prove::<Keccak256Hasher>(&fixture.witness(Fr::from(3u64)));
+    let bytes = serialize_ultra_proof(&proof);
+    assert_eq!(bytes.len(), vk.proof_size());
+    assert_eq!(deserialize_ultra_proof(&bytes, &vk), Some(proof));
+}
+
+#[test]
+fn test_wrong_public_input_rejected() {
+    let fixture = fixture();
+    let witness = fixture.witness(Fr::from(7u64));
+    let proof = fixture.proof_bytes::<Keccak256Hasher>(&witness);
+    let mut other = witness.clone();
+    other.public[0] += Fr::one();
+    assert_eq!(verify(&proof, &fixture.public_bytes(&other), &fixture.vk_bytes()), Ok(false));
+}
+
+#[test]
+fn test_value_outside_table_rejected() {
+    // y = x² + x holds, but x = 20 is not in the table
+    let fixture = fixture();
+    let witness = fixture.witness(Fr::from(20u64));
+    let proof = fixture.proof_bytes::<Keccak256Hasher>(&witness);
+    assert_eq!(verify(&proof, &fixture.public_bytes(&witness), &fixture.vk_bytes()), Ok(false));
+}
+
+#[test]
+fn test_custom_gates_enforced() {
+    let fixture = fixture();
+    let vk = fixture.vk_bytes();
+
+    // Not a bit (the next-row copy still holds)
+    let mut witness = fixture.witness(Fr::from(3u64));
+    witness.wires[2][3] = Fr::from(2u64);
+    witness.wires[0][4] = Fr::from(2u64);
+    let proof = fixture.proof_bytes::<Keccak256Hasher>(&witness);
+    assert_eq!(verify(&proof, &fixture.public_bytes(&witness), &vk), Ok(false));
+
+    // Next row does not copy the bit
+    let mut witness = fixture.witness(Fr::from(3u64));
+    witness.wires[0][4] = Fr::from(0u64);
+    let proof = fixture.proof_bytes::<Keccak256Hasher>(&witness);
+    assert_eq!(verify(&proof, &fixture.public_bytes(&witness), &vk), Ok(false));
+}
+
+#[test]
+fn test_copy_constraint_enforced() {
+    // Row 0 claims another y; every gate holds but a₀ ≠ c₂
+    let fixture = fixture();
+    let mut witness = fixture.witness(Fr::from(3u64));
+    witness.wires[0][0] += Fr::one();
+    witness.public[0] += Fr::one();
+    let proof = fixture.proof_bytes::<Keccak256Hasher>(&witness);
+    assert_eq!(verify(&proof, &fixture.public_bytes(&witness), &fixture.vk_bytes()), Ok(false));
+}
+
+#[test]
+fn test_tampered_evaluations_rejected() {
+    let fixture = fixture();
+    let witness = fixture.witness(Fr::from(5u64));
+    let proof = fixture.proof_bytes::<Keccak256Hasher>(&witness);
+    let inputs = fixture.public_bytes(&witness);
+    let vk = fixture.vk_bytes();
+
+    let first_eval = (NUM_WIRES + 4 + QUOTIENT_PIECES + 2) * 64;
+    for offset in (first_eval..proof.len()).step_by(32) {
+        let mut tampered = proof.clone();
+        tampered[offset + 31] ^= 1;
+        assert_eq!(verify(&tampered, &inputs, &vk), Ok(false), "evaluation at {offset}");
+    }
+}
+
+#[test]
+fn test_malformed_inputs() {
+    let fixture = fixture();
+    let witness = fixture.witness(Fr::from(3u64));
+    let proof = fixture.proof_bytes::<Keccak256Hasher>(&witness);
+    let inputs = fixture.public_bytes(&witness);
+    let vk = fixture.vk_without_srs();
+
+    // Proof and inputs of the wrong length
+    let full_vk = fixture.vk_bytes();
+    assert_eq!(verify(&proof[..proof.len() - 32], &inputs, &full_vk), Err(Error::InvalidInputSize));
+    assert_eq!(verify(&proof, &[inputs.clone(), inputs].concat(), &full_vk), Err(Error::InvalidInputSize));
+
+    // Wire count, table width, lookup selector and gate wires out of range
+    for (offset, value) in [(16, 1u8), (16, 9), (18, 4), (19, NUM_SELECTORS as u8), (20, 0)] {
+        let mut bad = vk.clone();
+        bad[offset] = value;
+        assert_eq!(deserialize_ultra_vk(&bad), None, "byte {offset} = {value}");
+    }
+    let mut bad_factor = vk.clone();
+    bad_factor[22 + 32 + 2] = NUM_WIRES as u8;
+    assert_eq!(deserialize_ultra_vk(&bad_factor), None);
+
+    // ω of order 8 instead of 16
+    let omega_offset = vk.len() - NUM_WIRES * 32;
+    let omega = U256::from_be_slice(&vk[omega_offset..omega_offset + 32]);
+    let mut bad_omega = vk.clone();
+    bad_omega[omega_offset..omega_offset + 32].copy_from_slice(&fr_pow(omega, U256::from(2)).to_be_bytes::<32>());
+    assert_eq!(deserialize_ultra_vk(&bad_omega), None);
+
+    // Truncated and extended keys
+    assert_eq!(deserialize_ultra_vk(&vk[..vk.len() - 1]), None);
+    assert_eq!(deserialize_ultra_vk(&[vk.clone(), vec![0]].concat()), None);
+}
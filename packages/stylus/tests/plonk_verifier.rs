@@ -0,0 +1,191 @@
+//! PLONK Verifier Tests
+//!
+//! Proofs come from the test prover in `common::plonk` (`x³ + x + 5 = y`). The
+//! verifier must accept honest proofs and reject anything that breaks a gate,
+//! a copy constraint, the quotient or an opening.
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use ark_bn254::Fr;
+use ark_ff::{FftField, Field, One, UniformRand};
+use ark_std::test_rng;
+use common::plonk::{fr_to_u256, u256_to_fr, PlonkFixture};
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::backend::ArkBackend;
+use uzkv_stylus::plonk::kzg::Error;
+use uzkv_stylus::plonk::plonk::{
+    self, deserialize_plonk_proof, deserialize_plonk_vk, lagrange_evals, serialize_plonk_proof,
+    serialize_plonk_vk, PROOF_SIZE, VK_SIZE,
+};
+use uzkv_stylus::utils::BN254_SCALAR_MODULUS;
+
+fn fixture() -> PlonkFixture {
+    PlonkFixture::cubic(Fr::rand(&mut test_rng()))
+}
+
+fn verify(proof: &[u8], inputs: &[u8], vk: &[u8]) -> Result<bool, Error> {
+    plonk::verify(&ArkBackend, proof, inputs, vk)
+}
+
+#[test]
+fn test_honest_proof_verifies() {
+    let fixture = fixture();
+    let witness = fixture.witness(Fr::from(3u64));
+    let proof = fixture.proof_bytes(&witness);
+    let vk = fixture.vk_bytes();
+
+    assert_eq!(proof.len(), PROOF_SIZE);
+    assert_eq!(vk.len(), VK_SIZE + 128);
+    // y = 27 + 3 + 5
+    assert_eq!(witness.public, vec![Fr::from(35u64)]);
+    assert_eq!(verify(&proof, &fixture.public_bytes(&witness), &vk), Ok(true));
+}
+
+#[test]
+fn test_wrong_public_input_fails() {
+    let fixture = fixture();
+    let witness = fixture.witness(Fr::from(3u64));
+    let proof = fixture.proof_bytes(&witness);
+
+    let wrong = fr_to_u256(&Fr::from(36u64)).to_be_bytes::<32>();
+    assert_eq!(verify(&proof, &wrong, &fixture.vk_bytes()), Ok(false));
+    assert_eq!(verify(&proof, &[], &fixture.vk_bytes()), Err(Error::InvalidInputSize));
+}
+
+#[test]
+fn test_unsatisfied_gate_fails() {
+    // y off by one: the last gate does not hold, so Z_H does not divide the
+    // constraint polynomial
+    let fixture = fixture();
+    let mut witness = fixture.witness(Fr::from(3u64));
+    witness.wires[2][4] += Fr::one();
+    witness.wires[0][0] += Fr::one();
+    witness.public[0] += Fr::one();
+
+    let proof = fixture.proof_bytes(&witness);
+    assert_eq!(verify(&proof, &fixture.public_bytes(&witness), &fixture.vk_bytes()), Ok(false));
+}
+
+#[test]
+fn test_broken_copy_constraint_fails() {
+    // Every gate holds, but b in row 3 is not the x used elsewhere: only the
+    // permutation argument catches this
+    let fixture = fixture();
+    let mut witness = fixture.witness(Fr::from(3u64));
+    witness.wires[1][3] += Fr::one();
+    witness.wires[2][3] += Fr::one();
+    witness.wires[0][4] += Fr::one();
+    witness.wires[2][4] += Fr::one();
+    witness.wires[0][0] += Fr::one();
+    witness.public[0] += Fr::one();
+
+    let proof = fixture.proof_bytes(&witness);
+    assert_eq!(verify(&proof, &fixture.public_bytes(&witness), &fixture.vk_bytes()), Ok(false));
+}
+
+#[test]
+fn test_tampered_proof_fails() {
+    let fixture = fixture();
+    let witness = fixture.witness(Fr::from(5u64));
+    let inputs = fixture.public_bytes(&witness);
+    let vk = fixture.vk_bytes();
+    let proof = deserialize_plonk_proof(&fixture.proof_bytes(&witness)).unwrap();
+    assert_eq!(verify(&serialize_plonk_proof(&proof), &inputs, &vk), Ok(true));
+
+    let one = U256::from(1);
+    let tampers: [fn(&mut plonk::PlonkProof, U256); 5] = [
+        |p, one| p.wire_evals[1] = p.wire_evals[1].add_mod(one, BN254_SCALAR_MODULUS),
+        |p, one| p.sigma_evals[0] = p.sigma_evals[0].add_mod(one, BN254_SCALAR_MODULUS),
+        |p, one| p.permutation_eval_omega = p.permutation_eval_omega.add_mod(one, BN254_SCALAR_MODULUS),
+        |p, _| p.quotient_commitments.swap(1, 2),
+        |p, _| std::mem::swap(&mut p.opening_proof_zeta, &mut p.opening_proof_omega),
+    ];
+    for tamper in tampers {
+        let mut bad = proof.clone();
+        tamper(&mut bad, one);
+        assert_eq!(verify(&serialize_plonk_proof(&bad), &inputs, &vk), Ok(false));
+    }
+
+    // Proof from another witness against these inputs
+    let other = fixture.proof_bytes(&fixture.witness(Fr::from(6u64)));
+    assert_eq!(verify(&other, &inputs, &vk), Ok(false));
+}
+
+#[test]
+fn test_vk_validation() {
+    let fixture = fixture();
+    let vk = fixture.verifying_key();
+    let bytes = serialize_plonk_vk(&vk);
+    assert_eq!(deserialize_plonk_vk(&bytes), Some(vk.clone()));
+
+    // Not a power of two
+    let mut bad = vk.clone();
+    bad.n = 6;
+    assert_eq!(deserialize_plonk_vk(&serialize_plonk_vk(&bad)), None);
+
+    // ω is not an n-th root of unity
+    let mut bad = vk.clone();
+    bad.omega = U256::from(2);
+    assert_eq!(deserialize_plonk_vk(&serialize_plonk_vk(&bad)), None);
+
+    // More public inputs than rows
+    let mut bad = vk.clone();
+    bad.num_public_inputs = 9;
+    assert_eq!(deserialize_plonk_vk(&serialize_plonk_vk(&bad)), None);
+
+    // Non-canonical k1
+    let mut bad_bytes = bytes.clone();
+    bad_bytes[VK_SIZE - 64..VK_SIZE - 32].copy_from_slice(&BN254_SCALAR_MODULUS.to_be_bytes::<32>());
+    assert_eq!(deserialize_plonk_vk(&bad_bytes), None);
+
+    // The SRS point is required
+    let witness = fixture.witness(Fr::from(3u64));
+    assert_eq!(
+        verify(&fixture.proof_bytes(&witness), &fixture.public_bytes(&witness), &bytes),
+        Err(Error::InvalidInputSize)
+    );
+}
+
+#[test]
+fn test_wrong_srs_fails() {
+    let fixture = fixture();
+    let witness = fixture.witness(Fr::from(3u64));
+    let proof = fixture.proof_bytes(&witness);
+
+    // Same circuit committed under another τ
+    let other = PlonkFixture::cubic(Fr::from(7u64));
+    let mut vk = fixture.vk_bytes();
+    vk[VK_SIZE..].copy_from_slice(&other.vk_bytes()[VK_SIZE..]);
+    assert_eq!(verify(&proof, &fixture.public_bytes(&witness), &vk), Ok(false));
+}
+
+#[test]
+fn test_lagrange_evals() {
+    // Batch-inverted barycentric bases against one inversion per basis
+    let n = 1024;
+    let omega = Fr::get_root_of_unity(n as u64).unwrap();
+    let zeta = Fr::rand(&mut test_rng());
+    let zh = zeta.pow([n as u64]) - Fr::one();
+    let expected: Vec<U256> = (0..300u64)
+        .map(|i| {
+            let omega_i = omega.pow([i]);
+            fr_to_u256(&(omega_i * zh / (Fr::from(n as u64) * (zeta - omega_i))))
+        })
+        .collect();
+    assert_eq!(lagrange_evals(fr_to_u256(&zeta), fr_to_u256(&omega), n, 300), expected);
+
+    // The bases over the whole domain sum to one
+    let n = 16;
+    let omega = Fr::get_root_of_unity(n as u64).unwrap();
+    let all = lagrange_evals(fr_to_u256(&zeta), fr_to_u256(&omega), n, n);
+    assert_eq!(all.iter().map(u256_to_fr).sum::<Fr>(), Fr::one());
+
+    // ζ = ω⁵ selects L₅; ζ = ω¹⁵ is outside the first three bases
+    let unit = lagrange_evals(fr_to_u256(&omega.pow([5])), fr_to_u256(&omega), n, 8);
+    let mut expected = vec![U256::ZERO; 8];
+    expected[5] = U256::from(1);
+    assert_eq!(unit, expected);
+    assert_eq!(lagrange_evals(fr_to_u256(&omega.pow([15])), fr_to_u256(&omega), n, 3), vec![U256::ZERO; 3]);
+}
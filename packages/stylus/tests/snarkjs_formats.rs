@@ -0,0 +1,185 @@
+//! snarkjs Import Tests
+//!
+//! Parses the snarkjs artifacts shipped in the repo and checks that the produced
+//! bytes verify, plus layout checks for PLONK keys and proofs.
+
+#![cfg(feature = "std")]
+
+use serde_json::json;
+use sha3::{Digest, Keccak256};
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::formats::snarkjs::{self, Error, Protocol, PLONK_PROOF_SIZE};
+use uzkv_stylus::groth16::{self, codec};
+use uzkv_stylus::plonk::plonk::{deserialize_plonk_vk, VK_SIZE};
+use uzkv_stylus::types::{ProofType, UniversalProof};
+
+fn repo_file(path: &str) -> String {
+    let full = format!("{}/../../{}", env!("CARGO_MANIFEST_DIR"), path);
+    std::fs::read_to_string(&full).unwrap_or_else(|_| panic!("missing fixture {}", full))
+}
+
+const SERVICE_VK: &str = "packages/groth16-service/test/fixtures/verification-key.json";
+const SERVICE_PROOF: &str = "packages/groth16-service/test/fixtures/valid-proof.json";
+const SERVICE_PUBLIC: &str = "packages/groth16-service/test/fixtures/valid-public.json";
+
+#[test]
+fn test_groth16_fixture_verifies() {
+    let vk = snarkjs::parse_groth16_vk(&repo_file(SERVICE_VK)).unwrap();
+    let proof = snarkjs::parse_groth16_proof(&repo_file(SERVICE_PROOF)).unwrap();
+    let inputs = snarkjs::parse_public_signals(&repo_file(SERVICE_PUBLIC)).unwrap();
+
+    assert_eq!(vk.len(), groth16::VK_HEADER_SIZE + 3 * codec::G1_SIZE);
+    assert_eq!(proof.len(), groth16::PROOF_SIZE);
+    assert_eq!(inputs.len(), 64);
+    assert_eq!(groth16::verify_host(&proof, &inputs, &vk), Ok(true));
+}
+
+#[test]
+fn test_groth16_universal_proof() {
+    let (vk, universal) = snarkjs::parse_universal_proof(
+        &repo_file(SERVICE_VK),
+        &repo_file(SERVICE_PROOF),
+        &repo_file(SERVICE_PUBLIC),
+        7,
+    )
+    .unwrap();
+
+    assert_eq!(universal.proof_type, ProofType::Groth16);
+    assert_eq!(universal.program_id, 7);
+    assert_eq!(universal.vk_hash, <[u8; 32]>::from(Keccak256::digest(&vk)));
+    assert_eq!(UniversalProof::decode(&universal.encode()), Some(universal.clone()));
+    assert_eq!(
+        groth16::verify_host(&universal.proof_bytes, &universal.public_inputs_bytes, &vk),
+        Ok(true)
+    );
+}
+
+#[test]
+fn test_circuit_build_vks_parse() {
+    for name in ["poseidon", "eddsa", "merkle"] {
+        let json = repo_file(&format!("packages/circuits/build/{}_vk.json", name));
+        assert_eq!(snarkjs::detect_protocol(&json), Ok(Protocol::Groth16));
+        let vk = snarkjs::parse_groth16_vk(&json).unwrap();
+        assert!(groth16::PreparedVerifyingKey::from_vk_bytes(&vk).is_ok(), "{}", name);
+    }
+}
+
+#[test]
+fn test_catalog_proofs_verify() {
+    let circuits = [("poseidon", "poseidon_test"), ("eddsa", "eddsa_verify"), ("merkle", "merkle_proof")];
+    for (circuit, proof_name) in circuits {
+        let vk = repo_file(&format!("packages/circuits/build/{}_vk.json", circuit));
+        let proof = repo_file(&format!("apps/web/public/proofs/{}_groth16_proof.json", proof_name));
+        let public = repo_file(&format!("apps/web/public/proofs/{}_groth16_public.json", proof_name));
+
+        let (vk, universal) = snarkjs::parse_universal_proof(&vk, &proof, &public, 0).unwrap();
+        assert_eq!(
+            groth16::verify_host(&universal.proof_bytes, &universal.public_inputs_bytes, &vk),
+            Ok(true),
+            "{}",
+            circuit
+        );
+    }
+}
+
+#[test]
+fn test_catalog_protocol_is_checked() {
+    // The catalog's *_plonk_proof.json files are Groth16 proofs
+    let json = repo_file("apps/web/public/proofs/poseidon_test_plonk_proof.json");
+    assert_eq!(snarkjs::detect_protocol(&json), Ok(Protocol::Groth16));
+    assert_eq!(
+        snarkjs::parse_plonk_proof(&json),
+        Err(Error::UnsupportedProtocol("groth16".to_string()))
+    );
+    assert!(snarkjs::parse_groth16_proof(&json).is_ok());
+}
+
+#[test]
+fn test_groth16_rejects_malformed() {
+    let mut vk: serde_json::Value = serde_json::from_str(&repo_file(SERVICE_VK)).unwrap();
+    vk["nPublic"] = json!(3);
+    assert_eq!(snarkjs::parse_groth16_vk(&vk.to_string()), Err(Error::InvalidField("nPublic")));
+
+    vk["nPublic"] = json!(2);
+    vk["curve"] = json!("bls12381");
+    assert_eq!(
+        snarkjs::parse_groth16_vk(&vk.to_string()),
+        Err(Error::UnsupportedCurve("bls12381".to_string()))
+    );
+
+    let mut proof: serde_json::Value = serde_json::from_str(&repo_file(SERVICE_PROOF)).unwrap();
+    proof.as_object_mut().unwrap().remove("pi_c");
+    assert_eq!(snarkjs::parse_groth16_proof(&proof.to_string()), Err(Error::MissingField("pi_c")));
+
+    assert_eq!(snarkjs::parse_public_signals("[\"0x01\"]"), Err(Error::InvalidField("public signals")));
+    assert_eq!(snarkjs::parse_public_signals("not json"), Err(Error::InvalidJson));
+}
+
+fn g1_json(x: u64) -> serde_json::Value {
+    json!([x.to_string(), "2", "1"])
+}
+
+fn plonk_vk_json() -> serde_json::Value {
+    let x2 = codec::g2_to_snarkjs(&[7u8; 128]).unwrap();
+    json!({
+        "protocol": "plonk",
+        "curve": "bn128",
+        "nPublic": 1,
+        "power": 3,
+        "k1": "2",
+        "k2": "3",
+        "Qm": g1_json(4), "Ql": g1_json(1), "Qr": g1_json(2), "Qo": g1_json(3), "Qc": g1_json(5),
+        "S1": g1_json(6), "S2": g1_json(7), "S3": g1_json(8),
+        "X_2": x2,
+        "w": "19540430494807482326159819597004422086093766032135589407132600596362845576832"
+    })
+}
+
+#[test]
+fn test_plonk_vk_layout() {
+    let bytes = snarkjs::parse_plonk_vk(&plonk_vk_json().to_string()).unwrap();
+    assert_eq!(bytes.len(), VK_SIZE + 128);
+    assert_eq!(&bytes[VK_SIZE..], &[7u8; 128][..]);
+
+    let vk = deserialize_plonk_vk(&bytes[..VK_SIZE]).unwrap();
+    assert_eq!(vk.n, 8);
+    assert_eq!(vk.num_public_inputs, 1);
+    // Selectors are stored q_L, q_R, q_O, q_M, q_C; permutations S1..S3
+    for (i, commitment) in vk.selector_commitments.iter().chain(&vk.permutation_commitments).enumerate() {
+        assert_eq!(U256::from_be_slice(&commitment[0..32]), U256::from(i + 1));
+    }
+    assert_eq!(vk.k1, U256::from(2));
+    assert_eq!(vk.k2, U256::from(3));
+    assert_eq!(
+        vk.omega,
+        U256::from_str_radix("19540430494807482326159819597004422086093766032135589407132600596362845576832", 10).unwrap()
+    );
+}
+
+#[test]
+fn test_plonk_proof_layout() {
+    let mut proof = json!({ "protocol": "plonk", "curve": "bn128" });
+    for (i, name) in ["A", "B", "C", "Z", "T1", "T2", "T3", "Wxi", "Wxiw"].iter().enumerate() {
+        proof[*name] = g1_json(i as u64 + 1);
+    }
+    for (i, name) in ["eval_a", "eval_b", "eval_c", "eval_s1", "eval_s2", "eval_zw"].iter().enumerate() {
+        proof[*name] = json!((100 + i).to_string());
+    }
+
+    let bytes = snarkjs::parse_plonk_proof(&proof.to_string()).unwrap();
+    assert_eq!(bytes.len(), PLONK_PROOF_SIZE);
+    for i in 0..9 {
+        assert_eq!(U256::from_be_slice(&bytes[i * 64..i * 64 + 32]), U256::from(i + 1));
+    }
+    for i in 0..6 {
+        let offset = 9 * 64 + i * 32;
+        assert_eq!(U256::from_be_slice(&bytes[offset..offset + 32]), U256::from(100 + i));
+    }
+
+    proof.as_object_mut().unwrap().remove("eval_zw");
+    assert_eq!(snarkjs::parse_plonk_proof(&proof.to_string()), Err(Error::MissingField("eval_zw")));
+
+    let mut vk = plonk_vk_json();
+    vk["power"] = json!(64);
+    assert_eq!(snarkjs::parse_plonk_vk(&vk.to_string()), Err(Error::InvalidField("power")));
+}
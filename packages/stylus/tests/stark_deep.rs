@@ -0,0 +1,180 @@
+//! STARK DEEP-ALI Tests
+//!
+//! The fixture prover in `common::stark` builds the composition on a coset
+//! from `deep::constraint_composition` and evaluates the frame at z from the
+//! interpolated trace; the verifier recomputes both sides of the check at z
+//! and the DEEP composition at each query.
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::stark::{boundaries, constraints, encode_vk, public_inputs, trace, vk_bytes, OodClaim, Prover, FRI, ROWS};
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::hash::{Keccak256Hasher, PoseidonHasher};
+use uzkv_stylus::stark::types::ConstraintTerm;
+use uzkv_stylus::stark::{self, AirConstraint, BoundaryConstraint, Error, StarkProof, StarkVerificationKey};
+use uzkv_stylus::types::HashFunctionId;
+use uzkv_stylus::utils::{fr_add, fr_mul, BN254_SCALAR_MODULUS};
+
+fn prove(prover: &Prover, rows: &[Vec<U256>], inputs: &[u8]) -> StarkProof {
+    prover.prove::<Keccak256Hasher>(rows, inputs)
+}
+
+fn verify(proof: &StarkProof, inputs: &[u8]) -> Result<bool, Error> {
+    stark::verify_proof(&proof.to_bytes(), inputs, &vk_bytes())
+}
+
+fn transcript_mismatch(result: Result<bool, Error>) -> bool {
+    matches!(result, Err(Error::ProofOfWorkFailed | Error::InvalidQueryPosition))
+}
+
+fn schema_error(constraints: &[AirConstraint], boundaries: &[BoundaryConstraint]) -> Option<Error> {
+    StarkVerificationKey::from_bytes(&encode_vk(constraints, boundaries, &FRI)).err()
+}
+
+#[test]
+fn test_frame_and_composition_width() {
+    let vk = StarkVerificationKey::from_bytes(&vk_bytes()).unwrap();
+    assert_eq!(vk.frame_rows(), 2);
+    assert_eq!(vk.composition_width(), 3);
+    assert_eq!(vk.boundaries, boundaries());
+
+    // Quotient degree d·(n − 1) − (n − m) in columns of n coefficients
+    let width = |degree: u8, offset: u32| {
+        let term = ConstraintTerm { coefficient: U256::from(1), offset, register: 0, power: 1 };
+        StarkVerificationKey { constraints: vec![AirConstraint { degree, terms: vec![term] }], ..vk.clone() }
+            .composition_width()
+    };
+    assert_eq!(width(1, 0), 1);
+    assert_eq!(width(1, 1), 1);
+    assert_eq!(width(2, 0), 1);
+    assert_eq!(width(2, 2), 2);
+    assert_eq!(width(3, 0), 2);
+    assert_eq!(width(4, 0), 3);
+    assert_eq!(StarkVerificationKey { constraints: vec![], ..vk }.composition_width(), 1);
+}
+
+#[test]
+fn test_honest_proofs_verify() {
+    let (rows, inputs) = (trace(), public_inputs(&trace()));
+    let proof = prove(&Prover::new(FRI), &rows, &inputs);
+    assert_eq!(proof.ood_trace.len(), 2);
+    assert_eq!(proof.ood_composition.len(), 3);
+    assert_eq!(verify(&proof, &inputs), Ok(true));
+
+    let proof = Prover::new(FRI).prove::<PoseidonHasher>(&rows, &inputs).to_bytes();
+    assert_eq!(stark::verify_proof_with_hash(HashFunctionId::Poseidon, &proof, &inputs, &vk_bytes()), Ok(true));
+}
+
+#[test]
+fn test_unqueried_violations_rejected() {
+    let inputs = public_inputs(&trace());
+
+    // x₀ at row 30 off the recurrence, x₁ kept at its square: only the
+    // transition constraint breaks, on two rows
+    let mut rows = trace();
+    rows[30][0] = fr_add(rows[30][0], U256::from(1));
+    rows[30][1] = fr_mul(rows[30][0], rows[30][0]);
+    assert_eq!(verify(&prove(&Prover::new(FRI), &rows, &inputs), &inputs), Err(Error::ConstraintFailed));
+
+    // x₁ = −x₀² still satisfies x₁² = x₀⁴, but neither x₀² = x₁ nor the
+    // transition out of row 12
+    let mut rows = trace();
+    rows[12][1] = BN254_SCALAR_MODULUS - rows[12][1];
+    assert_eq!(verify(&prove(&Prover::new(FRI), &rows, &inputs), &inputs), Err(Error::ConstraintFailed));
+}
+
+#[test]
+fn test_boundary_constraints() {
+    let (rows, inputs) = (trace(), public_inputs(&trace()));
+    let honest = prove(&Prover::new(FRI), &rows, &inputs);
+
+    // The honest trace wraps without x₀[0] = x₀[63] + x₁[63]: the last row is
+    // outside the transition constraint's domain
+    assert_ne!(rows[0][0], fr_add(rows[ROWS - 1][0], rows[ROWS - 1][1]));
+
+    // Public inputs feed the transcript
+    let mut other = inputs.clone();
+    other[31] ^= 1;
+    assert!(transcript_mismatch(verify(&honest, &other)));
+
+    // A prover that proves the trace against a false boundary value
+    let proof = prove(&Prover::new(FRI), &rows, &other);
+    assert_eq!(verify(&proof, &other), Err(Error::ConstraintFailed));
+
+    // One 32-byte canonical value per boundary constraint
+    assert_eq!(verify(&honest, &inputs[..32]), Err(Error::InvalidInputSize));
+    assert_eq!(verify(&honest, &[inputs.clone(), vec![0; 32]].concat()), Err(Error::InvalidInputSize));
+    let mut non_canonical = inputs;
+    non_canonical[..32].copy_from_slice(&BN254_SCALAR_MODULUS.to_be_bytes::<32>());
+    assert_eq!(verify(&honest, &non_canonical), Err(Error::InvalidInputSize));
+}
+
+#[test]
+fn test_ood_values_checked() {
+    let (rows, inputs) = (trace(), public_inputs(&trace()));
+
+    // A false frame value fails the check at z; made consistent there, it
+    // fails FRI instead
+    let prover = Prover { ood: OodClaim::Shifted, ..Prover::new(FRI) };
+    assert_eq!(verify(&prove(&prover, &rows, &inputs), &inputs), Err(Error::ConstraintFailed));
+    let prover = Prover { ood: OodClaim::ShiftedConsistently, ..Prover::new(FRI) };
+    assert_eq!(verify(&prove(&prover, &rows, &inputs), &inputs), Err(Error::FriFailed));
+
+    // Values at z feed the transcript ahead of the DEEP challenge
+    let honest = prove(&Prover::new(FRI), &rows, &inputs);
+    let mut proof = honest.clone();
+    proof.ood_trace[1][1] = fr_add(proof.ood_trace[1][1], U256::from(1));
+    assert!(transcript_mismatch(verify(&proof, &inputs)));
+    let mut proof = honest.clone();
+    proof.ood_composition[2] = fr_add(proof.ood_composition[2], U256::from(1));
+    assert!(transcript_mismatch(verify(&proof, &inputs)));
+
+    // The frame and composition follow the VK's shape
+    let mut proof = honest.clone();
+    proof.ood_trace.push(proof.ood_trace[0].clone());
+    assert_eq!(verify(&proof, &inputs), Err(Error::InvalidProofStructure));
+    let mut proof = honest.clone();
+    proof.ood_trace.iter_mut().for_each(|row| row.push(U256::ZERO));
+    assert_eq!(verify(&proof, &inputs), Err(Error::InvalidProofStructure));
+    let mut proof = honest;
+    proof.ood_composition.pop();
+    assert_eq!(verify(&proof, &inputs), Err(Error::InvalidProofStructure));
+}
+
+#[test]
+fn test_proof_bound_to_vk() {
+    let (rows, inputs) = (trace(), public_inputs(&trace()));
+    let honest = prove(&Prover::new(FRI), &rows, &inputs).to_bytes();
+
+    // A zero term changes no constraint value, only the key's digest
+    let mut constraints = constraints();
+    constraints[0].terms.push(ConstraintTerm { coefficient: U256::ZERO, offset: 0, register: 0, power: 1 });
+    let other = encode_vk(&constraints, &boundaries(), &FRI);
+    let vk = StarkVerificationKey::from_bytes(&other).unwrap();
+    assert_eq!(vk.composition_width(), 3);
+    assert_ne!(vk.digest, StarkVerificationKey::from_bytes(&vk_bytes()).unwrap().digest);
+
+    assert_eq!(stark::verify_proof(&honest, &inputs, &vk_bytes()), Ok(true));
+    assert!(transcript_mismatch(stark::verify_proof(&honest, &inputs, &other)));
+}
+
+#[test]
+fn test_schema_validated() {
+    assert_eq!(schema_error(&constraints(), &boundaries()), None);
+
+    let edit_term = |edit: &dyn Fn(&mut ConstraintTerm)| {
+        let mut constraints = constraints();
+        edit(&mut constraints[1].terms[0]);
+        schema_error(&constraints, &boundaries())
+    };
+    assert_eq!(edit_term(&|term| term.register = 2), Some(Error::ConstraintSchemaInvalid));
+    assert_eq!(edit_term(&|term| term.offset = ROWS as u32), Some(Error::ConstraintSchemaInvalid));
+    assert_eq!(edit_term(&|term| term.power = 2), Some(Error::ConstraintSchemaInvalid));
+    assert_eq!(edit_term(&|term| term.offset = 3), None);
+
+    for boundary in [BoundaryConstraint { register: 2, row: 0 }, BoundaryConstraint { register: 0, row: ROWS as u32 }] {
+        assert_eq!(schema_error(&constraints(), &[boundary]), Some(Error::ConstraintSchemaInvalid));
+    }
+}
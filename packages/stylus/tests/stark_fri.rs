@@ -0,0 +1,199 @@
+//! STARK FRI Tests
+//!
+//! The fixture prover in `common::stark` folds in coefficient form; the
+//! verifier interpolates each opened coset, so honest proofs under several
+//! parameter sets check one against the other.
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use ark_std::{rand::RngCore, test_rng};
+use common::stark::{fold_coefficients, public_inputs, trace, vk_bytes_with, OodClaim, Prover, FRI, LOG_ROWS, ROWS};
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::hash::{Keccak256Hasher, Sha256Hasher};
+use uzkv_stylus::stark::fri::{domain_generator, evaluate_polynomial, fold, TWO_ADICITY};
+use uzkv_stylus::stark::{self, estimate_gas_cost, Error, FriParams, SecurityLevel, StarkProof, StarkVerificationKey};
+use uzkv_stylus::types::HashFunctionId;
+use uzkv_stylus::utils::Fr;
+
+fn random_fr<R: RngCore>(rng: &mut R) -> Fr {
+    let mut bytes = [0u8; 32];
+    rng.fill_bytes(&mut bytes);
+    Fr::from_u256_reduced(U256::from_be_bytes(bytes))
+}
+
+fn verify(proof: &StarkProof, fri: &FriParams) -> Result<bool, Error> {
+    stark::verify_proof(&proof.to_bytes(), &public_inputs(&trace()), &vk_bytes_with(fri))
+}
+
+fn prove(prover: &Prover) -> StarkProof {
+    prover.prove::<Keccak256Hasher>(&trace(), &public_inputs(&trace()))
+}
+
+fn params(log_blowup: u8, log_folding: u8, num_layers: u8, remainder_degree: u32, grinding_bits: u8) -> FriParams {
+    FriParams { log_blowup, log_folding, num_layers, remainder_degree, grinding_bits }
+}
+
+#[test]
+fn test_domain_generators() {
+    let minus_one = Fr::ONE.neg();
+    assert_eq!(domain_generator(0), Fr::ONE);
+    for log_size in [1, 2, 10, TWO_ADICITY] {
+        let generator = domain_generator(log_size);
+        assert_eq!(generator.pow(U256::from(1u64) << (log_size - 1)), minus_one);
+        assert_eq!(generator.pow(U256::from(1u64) << log_size), Fr::ONE);
+        assert_eq!(domain_generator(log_size - 1), generator.square());
+    }
+}
+
+#[test]
+fn test_fold_matches_coefficient_split() {
+    let rng = &mut test_rng();
+    for log_folding in 1..=4u32 {
+        let k = 1usize << log_folding;
+        let zeta = domain_generator(log_folding);
+        for _ in 0..10 {
+            let f: Vec<Fr> = (0..4 * k).map(|_| random_fr(rng)).collect();
+            let (x, beta) = (random_fr(rng), random_fr(rng));
+            let mut point = x;
+            let values: Vec<Fr> = (0..k)
+                .map(|_| {
+                    let value = evaluate_polynomial(&f, point);
+                    point = point.mul(&zeta);
+                    value
+                })
+                .collect();
+            let folded = fold_coefficients(&f, beta, k);
+            assert_eq!(fold(&values, x, beta), evaluate_polynomial(&folded, x.pow(U256::from(k))));
+        }
+    }
+}
+
+#[test]
+fn test_honest_proofs_across_parameters() {
+    for fri in [
+        FRI,
+        params(2, 2, 2, 3, 0),
+        params(3, 3, 2, 0, 8),
+        params(1, 4, 1, 3, 2),
+        params(4, 1, 0, ROWS as u32 - 1, 1),
+    ] {
+        assert_eq!(verify(&prove(&Prover::new(fri)), &fri), Ok(true), "{fri:?}");
+    }
+
+    let inputs = public_inputs(&trace());
+    let proof = Prover::new(FRI).prove::<Sha256Hasher>(&trace(), &inputs).to_bytes();
+    assert_eq!(stark::verify_proof_with_hash(HashFunctionId::SHA256, &proof, &inputs, &vk_bytes_with(&FRI)), Ok(true));
+}
+
+#[test]
+fn test_inconsistent_ood_values_rejected() {
+    // A false x₀(z·g) with H₀(z) adjusted to pass the check at z: the DEEP
+    // term for that value is no longer a polynomial, so the folded remainder
+    // is too long and the prover truncates it
+    let prover = Prover { ood: OodClaim::ShiftedConsistently, ..Prover::new(FRI) };
+    assert_eq!(verify(&prove(&prover), &FRI), Err(Error::FriFailed));
+}
+
+#[test]
+fn test_inconsistent_layers_rejected() {
+    // Layer 0 no longer matches the DEEP composition; layer 2 no longer
+    // matches the fold of layer 1
+    for layer in [0, 2] {
+        let prover = Prover { shifted_layer: Some(layer), ..Prover::new(FRI) };
+        assert_eq!(verify(&prove(&prover), &FRI), Err(Error::FriFailed));
+    }
+}
+
+#[test]
+fn test_layer_openings_bound_to_commitments() {
+    let honest = prove(&Prover::new(FRI));
+
+    let mut proof = honest.clone();
+    proof.queries[2].fri_layers[1].values[0] = U256::from(1);
+    assert_eq!(verify(&proof, &FRI), Err(Error::MerkleProofFailed));
+
+    let mut proof = honest.clone();
+    proof.queries[4].fri_layers[3].path[0][0] ^= 1;
+    assert_eq!(verify(&proof, &FRI), Err(Error::MerkleProofFailed));
+
+    // Layer commitments, the remainder and the nonce feed the transcript
+    let mut proof = honest.clone();
+    proof.fri_commitments[1][0] ^= 1;
+    assert!(matches!(verify(&proof, &FRI), Err(Error::ProofOfWorkFailed | Error::InvalidQueryPosition)));
+
+    let mut proof = honest.clone();
+    proof.fri_remainder[0] = U256::from(1);
+    assert!(matches!(verify(&proof, &FRI), Err(Error::ProofOfWorkFailed | Error::InvalidQueryPosition)));
+
+    let mut proof = honest.clone();
+    proof.pow_nonce += 1;
+    assert!(matches!(verify(&proof, &FRI), Err(Error::ProofOfWorkFailed | Error::InvalidQueryPosition)));
+
+    // Positions come from the transcript, not the prover
+    let mut proof = honest;
+    proof.queries[0].position ^= 1;
+    assert_eq!(verify(&proof, &FRI), Err(Error::InvalidQueryPosition));
+}
+
+#[test]
+fn test_proof_shape_follows_parameters() {
+    let honest = prove(&Prover::new(FRI));
+
+    let mut proof = honest.clone();
+    proof.fri_remainder.push(U256::ZERO);
+    assert_eq!(verify(&proof, &FRI), Err(Error::InvalidProofStructure));
+
+    let mut proof = honest.clone();
+    proof.queries[0].fri_layers[0].values.pop();
+    assert_eq!(verify(&proof, &FRI), Err(Error::InvalidProofStructure));
+
+    // The same proof under a VK with one more layer
+    let deeper = params(4, 1, 5, 1, 4);
+    assert_eq!(verify(&honest, &deeper), Err(Error::InvalidProofStructure));
+}
+
+#[test]
+fn test_num_queries_follow_soundness() {
+    // ⌈2·(bits − grinding) / log_blowup⌉
+    assert_eq!(SecurityLevel::Proven100.num_queries(&FRI), 48);
+    assert_eq!(SecurityLevel::Proven100.num_queries(&params(4, 1, 4, 3, 0)), 50);
+    assert_eq!(SecurityLevel::Test96.num_queries(&params(1, 1, 4, 3, 16)), 160);
+    assert_eq!(SecurityLevel::High128.num_queries(&params(3, 1, 4, 3, 0)), 86);
+
+    for level in [SecurityLevel::Test96, SecurityLevel::Proven100, SecurityLevel::High128] {
+        for log_blowup in 1..8 {
+            let fri = params(log_blowup, 1, 4, 3, 0);
+            let queries = level.num_queries(&fri);
+            // Enough proven bits, and one query fewer is not
+            assert!(queries * log_blowup as usize >= 2 * level.bits());
+            assert!((queries - 1) * (log_blowup as usize) < 2 * level.bits());
+            assert!(level.num_queries(&params(log_blowup, 1, 4, 3, 10)) < queries);
+        }
+    }
+
+    let shallow = estimate_gas_cost(SecurityLevel::Proven100, &params(4, 1, 2, 15, 4));
+    let deep = estimate_gas_cost(SecurityLevel::Proven100, &FRI);
+    assert!(deep.total > shallow.total);
+}
+
+#[test]
+fn test_invalid_parameters_rejected() {
+    for fri in [
+        params(4, 1, 4, 4, 4),  // 64 / 2⁴ = 4 coefficients, not 5
+        params(4, 0, 4, 3, 4),  // no folding
+        params(4, 5, 1, 1, 4),  // folding beyond 2⁴
+        params(0, 1, 4, 3, 4),  // no blowup
+        params(23, 1, 4, 3, 4), // domain beyond 2²⁸
+        params(4, 1, 7, 0, 4),  // folds past a constant
+        params(4, 1, 4, 3, 33), // grinding
+    ] {
+        assert_eq!(StarkVerificationKey::from_bytes(&vk_bytes_with(&fri)).err(), Some(Error::InvalidFriParameters), "{fri:?}");
+    }
+
+    // Missing FRI section
+    let vk = vk_bytes_with(&FRI);
+    assert_eq!(StarkVerificationKey::from_bytes(&vk[..vk.len() - 1]).err(), Some(Error::DeserializationError));
+    assert_eq!(StarkVerificationKey::from_bytes(&vk).unwrap().log_domain_size(), LOG_ROWS as u32 + 4);
+}
@@ -0,0 +1,147 @@
+//! STARK Proof Format Tests
+//!
+//! Proofs from `common::stark` go through `StarkProof::to_bytes`, the strict
+//! decoder and `stark::verify_proof_with_hash`.
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::stark::{prove, public_inputs, trace, vk_bytes, FRI, LOG_DOMAIN};
+use stylus_sdk::alloy_primitives::U256;
+use uzkv_stylus::hash::{Hasher, Keccak256Hasher, PoseidonHasher, Sha256Hasher};
+use uzkv_stylus::stark::proof::PROOF_VERSION;
+use uzkv_stylus::stark::{self, Error, QueryOpening, SecurityLevel, StarkProof};
+use uzkv_stylus::types::{CurveId, HashFunctionId};
+use uzkv_stylus::uzkv::{verify_offchain_with_hash, ProofSystem};
+use uzkv_stylus::utils::BN254_SCALAR_MODULUS;
+
+/// `verify_proof` runs at `Proven100`
+fn queries() -> usize {
+    SecurityLevel::Proven100.num_queries(&FRI)
+}
+
+fn proof_bytes<H: Hasher>() -> Vec<u8> {
+    prove::<H>(&trace(), queries()).to_bytes()
+}
+
+fn verify(proof: &[u8]) -> Result<bool, Error> {
+    stark::verify_proof(proof, &public_inputs(&trace()), &vk_bytes())
+}
+
+/// Rejected by the transcript: its proof of work or query positions no
+/// longer match
+fn transcript_mismatch(result: Result<bool, Error>) -> bool {
+    matches!(result, Err(Error::ProofOfWorkFailed | Error::InvalidQueryPosition))
+}
+
+#[test]
+fn test_round_trip() {
+    let proof = prove::<Keccak256Hasher>(&trace(), queries());
+    let bytes = proof.to_bytes();
+    assert_eq!(bytes[0], PROOF_VERSION);
+    assert_eq!(StarkProof::from_bytes(&bytes), Ok(proof.clone()));
+
+    let query = &proof.queries[0];
+    assert_eq!(query.trace.path.len(), LOG_DOMAIN);
+    assert_eq!(query.fri_layers.len(), FRI.num_layers as usize);
+    assert_eq!(query.fri_layers[1].path.len(), LOG_DOMAIN - 2);
+    assert_eq!(proof.fri_remainder.len(), FRI.remainder_degree as usize + 1);
+}
+
+#[test]
+fn test_honest_proof_verifies() {
+    assert_eq!(verify(&proof_bytes::<Keccak256Hasher>()), Ok(true));
+    for (id, proof) in [
+        (HashFunctionId::SHA256, proof_bytes::<Sha256Hasher>()),
+        (HashFunctionId::Poseidon, proof_bytes::<PoseidonHasher>()),
+    ] {
+        assert_eq!(stark::verify_proof_with_hash(id, &proof, &public_inputs(&trace()), &vk_bytes()), Ok(true));
+        assert!(transcript_mismatch(verify(&proof)));
+    }
+    assert_eq!(
+        verify_offchain_with_hash(ProofSystem::Stark as u8, CurveId::None, HashFunctionId::Keccak256, &proof_bytes::<Keccak256Hasher>(), &public_inputs(&trace()), &vk_bytes()),
+        Ok(true)
+    );
+}
+
+#[test]
+fn test_openings_bound_to_commitments() {
+    let honest = prove::<Keccak256Hasher>(&trace(), queries());
+
+    let mut proof = honest.clone();
+    proof.queries[3].composition.values[0] = U256::from(1);
+    assert_eq!(verify(&proof.to_bytes()), Err(Error::MerkleProofFailed));
+
+    // A row that still satisfies the constraint but is not the committed one
+    let mut proof = honest.clone();
+    proof.queries[5].trace.values = vec![U256::from(3), U256::from(9)];
+    assert_eq!(verify(&proof.to_bytes()), Err(Error::MerkleProofFailed));
+
+    // Query 1's openings under query 0's position
+    let mut proof = honest.clone();
+    proof.queries[0] = QueryOpening { position: honest.queries[0].position, ..honest.queries[1].clone() };
+    assert_eq!(verify(&proof.to_bytes()), Err(Error::MerkleProofFailed));
+
+    // Commitments feed the transcript
+    let mut proof = honest;
+    proof.trace_commitment[0] ^= 1;
+    assert!(transcript_mismatch(verify(&proof.to_bytes())));
+}
+
+#[test]
+fn test_violated_constraint_rejected() {
+    // One row off x₁ = x₀², which the queries are unlikely to open: the
+    // quotient is no longer a polynomial and the check at z catches it
+    let mut rows = trace();
+    rows[7][1] = U256::from(50);
+    let proof = prove::<Keccak256Hasher>(&rows, queries()).to_bytes();
+    assert_eq!(verify(&proof), Err(Error::ConstraintFailed));
+}
+
+#[test]
+fn test_query_count_must_match_security_level() {
+    for count in [queries() - 1, queries() + 1] {
+        let proof = prove::<Keccak256Hasher>(&trace(), count).to_bytes();
+        assert_eq!(verify(&proof), Err(Error::InvalidProofStructure));
+    }
+}
+
+#[test]
+fn test_strict_decoding() {
+    let proof = prove::<Keccak256Hasher>(&trace(), 2);
+    let bytes = proof.to_bytes();
+
+    // Every truncation and any trailing byte
+    for len in 0..bytes.len() {
+        assert!(StarkProof::from_bytes(&bytes[..len]).is_err(), "length {len}");
+    }
+    assert_eq!(StarkProof::from_bytes(&[bytes.clone(), vec![0]].concat()), Err(Error::DeserializationError));
+
+    let mut unknown_version = bytes.clone();
+    unknown_version[0] = PROOF_VERSION + 1;
+    assert_eq!(StarkProof::from_bytes(&unknown_version), Err(Error::UnsupportedVersion));
+
+    // First trace value: version, two commitments, values at z, FRI
+    // commitments, remainder, nonce, query count, position and value count
+    let ood = 1 + 2 + 32 * proof.ood_trace.len() * proof.ood_trace[0].len() + 2 + 32 * proof.ood_composition.len();
+    let first_value =
+        1 + 64 + ood + 1 + 32 * proof.fri_commitments.len() + 2 + 32 * proof.fri_remainder.len() + 8 + 2 + 4 + 2;
+    let mut non_canonical = bytes.clone();
+    non_canonical[first_value..first_value + 32].copy_from_slice(&BN254_SCALAR_MODULUS.to_be_bytes::<32>());
+    assert_eq!(StarkProof::from_bytes(&non_canonical), Err(Error::DeserializationError));
+
+    let structural = |edit: &dyn Fn(&mut StarkProof), expected: Error| {
+        let mut bad = proof.clone();
+        edit(&mut bad);
+        assert_eq!(StarkProof::from_bytes(&bad.to_bytes()), Err(expected));
+    };
+    structural(&|p| { p.queries[1].trace.path.pop(); }, Error::InvalidProofStructure);
+    structural(&|p| { p.queries[0].fri_layers[1].path.push([0; 32]); }, Error::InvalidProofStructure);
+    structural(&|p| p.queries[0].composition.values.clear(), Error::InvalidProofStructure);
+    structural(&|p| p.queries[1].trace.path = vec![[0; 32]; 33], Error::InvalidProofStructure);
+    structural(&|p| p.fri_remainder.clear(), Error::InvalidProofStructure);
+    structural(&|p| p.ood_trace.clear(), Error::InvalidProofStructure);
+    structural(&|p| p.ood_composition.clear(), Error::InvalidProofStructure);
+    structural(&|p| p.queries[1].position = 1 << LOG_DOMAIN, Error::InvalidQueryPosition);
+}